@@ -0,0 +1,250 @@
+//! A pipeline of the parser, the semantic analysis, the transformer, the minifier and the
+//! codegen, for the embedders which compile a file in one call
+
+use oxc_allocator::Allocator;
+use oxc_ast::Trivias;
+use oxc_codegen::{Codegen, CodegenOptions, CommentOptions, SourceMap, SourceMapOptions};
+use oxc_diagnostics::Error;
+use oxc_minifier::{Minifier, MinifierOptions};
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::SourceType;
+use oxc_transformer::{TransformOptions, Transformer};
+
+/// Compiles a source text with the steps enabled by the builder methods:
+///
+/// 1. parse, always
+/// 2. semantic, which checks the syntax errors found by the semantic analysis
+/// 3. transform, see [Compiler::with_transform]
+/// 4. minify, see [Compiler::with_minify]
+/// 5. codegen, always
+///
+/// The compilation stops at the first step with errors.
+///
+/// ```ignore
+/// let allocator = Allocator::default();
+/// let ret = Compiler::new(SourceType::from_path("index.ts").unwrap())
+///     .with_transform(TransformOptions::default())
+///     .with_source_map(SourceMapOptions::default())
+///     .compile(&allocator, source_text);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Compiler {
+    source_type: SourceType,
+    allow_return_outside_function: bool,
+    check_syntax_error: bool,
+    transform: Option<TransformOptions>,
+    minify: Option<MinifierOptions>,
+    remove_whitespace: bool,
+    codegen: CodegenOptions,
+    comments: Option<CommentOptions>,
+    source_map: Option<SourceMapOptions>,
+}
+
+/// The output of [Compiler::compile]
+#[derive(Debug, Default)]
+pub struct CompilerReturn {
+    /// The printed code, empty when there are errors
+    pub code: String,
+    /// The source map of the code, see [Compiler::with_source_map]
+    pub source_map: Option<SourceMap>,
+    /// The errors of the step which stopped the compilation
+    pub errors: Vec<Error>,
+}
+
+impl Compiler {
+    pub fn new(source_type: SourceType) -> Self {
+        Self {
+            source_type,
+            allow_return_outside_function: false,
+            check_syntax_error: true,
+            transform: None,
+            minify: None,
+            remove_whitespace: false,
+            codegen: CodegenOptions::default(),
+            comments: None,
+            source_map: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_allow_return_outside_function(mut self, yes: bool) -> Self {
+        self.allow_return_outside_function = yes;
+        self
+    }
+
+    /// Check the syntax errors found by the semantic analysis, such as the duplicate labels.
+    ///
+    /// Default `true`
+    #[must_use]
+    pub fn with_check_syntax_error(mut self, yes: bool) -> Self {
+        self.check_syntax_error = yes;
+        self
+    }
+
+    /// Run the transforms, e.g. strip the TypeScript types and lower the syntax to the target
+    #[must_use]
+    pub fn with_transform(mut self, options: TransformOptions) -> Self {
+        self.transform = Some(options);
+        self
+    }
+
+    /// Compress and mangle the code after the transforms
+    #[must_use]
+    pub fn with_minify(mut self, options: MinifierOptions) -> Self {
+        self.minify = Some(options);
+        self
+    }
+
+    /// Print the code without the whitespace, as the minified code
+    #[must_use]
+    pub fn with_remove_whitespace(mut self, yes: bool) -> Self {
+        self.remove_whitespace = yes;
+        self
+    }
+
+    #[must_use]
+    pub fn with_codegen(mut self, options: CodegenOptions) -> Self {
+        self.codegen = options;
+        self
+    }
+
+    /// Print the comments of the source text, see [CommentOptions]
+    #[must_use]
+    pub fn with_comments(mut self, options: CommentOptions) -> Self {
+        self.comments = Some(options);
+        self
+    }
+
+    /// Generate a source map of the code
+    #[must_use]
+    pub fn with_source_map(mut self, options: SourceMapOptions) -> Self {
+        self.source_map = Some(options);
+        self
+    }
+
+    /// All the steps allocate in `allocator`, which can be reused after the return is dropped
+    pub fn compile<'a>(&self, allocator: &'a Allocator, source_text: &'a str) -> CompilerReturn {
+        let ret = Parser::new(allocator, source_text, self.source_type)
+            .allow_return_outside_function(self.allow_return_outside_function)
+            .parse();
+        if !ret.errors.is_empty() {
+            return CompilerReturn { errors: ret.errors, ..CompilerReturn::default() };
+        }
+        let program = allocator.alloc(ret.program);
+
+        // The comments are read by the transforms, e.g. the `@jsx` pragmas
+        let trivias =
+            if self.comments.is_some() { ret.trivias.clone() } else { Trivias::default() };
+        let semantic_ret = SemanticBuilder::new(source_text, self.source_type)
+            .with_trivias(ret.trivias)
+            .with_check_syntax_error(self.check_syntax_error)
+            .build(program);
+        if !semantic_ret.errors.is_empty() {
+            return CompilerReturn { errors: semantic_ret.errors, ..CompilerReturn::default() };
+        }
+
+        if let Some(options) = &self.transform {
+            let transformer = Transformer::new(
+                allocator,
+                self.source_type,
+                semantic_ret.semantic,
+                options.clone(),
+            );
+            if let Err(errors) = transformer.build(program) {
+                return CompilerReturn { errors, ..CompilerReturn::default() };
+            }
+        }
+
+        if let Some(options) = &self.minify {
+            Minifier::new(options.clone()).build(allocator, program);
+        }
+
+        let ret = if self.remove_whitespace {
+            self.codegen::<true>(source_text, trivias).build_with_source_map(program)
+        } else {
+            self.codegen::<false>(source_text, trivias).build_with_source_map(program)
+        };
+        CompilerReturn { code: ret.source_text, source_map: ret.source_map, errors: vec![] }
+    }
+
+    fn codegen<const MINIFY: bool>(&self, source_text: &str, trivias: Trivias) -> Codegen<MINIFY> {
+        let mut codegen = Codegen::<MINIFY>::new(source_text.len(), self.codegen);
+        if let Some(options) = self.comments {
+            codegen = codegen.with_comments(source_text, trivias, options);
+        }
+        if let Some(options) = &self.source_map {
+            codegen = codegen.with_source_map(source_text, options.clone());
+        }
+        codegen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::SourceMapOptions;
+    use oxc_minifier::MinifierOptions;
+    use oxc_span::SourceType;
+    use oxc_transformer::{
+        ReactJsxOptions, ReactJsxRuntime, ReactJsxRuntimeOption, TransformOptions,
+    };
+
+    use super::Compiler;
+
+    #[test]
+    fn codegen() {
+        let allocator = Allocator::default();
+        let ret = Compiler::new(SourceType::default()).compile(&allocator, "let  a = 1");
+        assert!(ret.errors.is_empty());
+        assert_eq!(ret.code, "let a = 1;\n");
+        assert!(ret.source_map.is_none());
+    }
+
+    #[test]
+    fn transform_and_minify() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path("index.ts").unwrap();
+        let ret = Compiler::new(source_type)
+            .with_transform(TransformOptions::default())
+            .with_minify(MinifierOptions::default())
+            .with_remove_whitespace(true)
+            .with_source_map(SourceMapOptions::default())
+            .compile(&allocator, "function foo(bar: number): number { return bar }");
+        assert!(ret.errors.is_empty());
+        assert_eq!(ret.code, "function foo(o){return o}");
+        assert!(ret.source_map.is_some());
+    }
+
+    #[test]
+    fn jsx_pragma() {
+        let allocator = Allocator::default();
+        let options = TransformOptions {
+            react_jsx: Some(ReactJsxOptions {
+                runtime: Some(ReactJsxRuntimeOption::Valid(ReactJsxRuntime::Classic)),
+                ..ReactJsxOptions::default()
+            }),
+            ..TransformOptions::default()
+        };
+        let ret = Compiler::new(SourceType::from_path("index.jsx").unwrap())
+            .with_transform(options)
+            .compile(&allocator, "/** @jsx h */\n<div />");
+        assert!(ret.errors.is_empty());
+        assert_eq!(ret.code, "h('div', null);\n");
+    }
+
+    #[test]
+    fn errors() {
+        let allocator = Allocator::default();
+        let compiler = Compiler::new(SourceType::default().with_module(true));
+        let ret = compiler.compile(&allocator, "let a = ");
+        assert_eq!(ret.errors.len(), 1);
+        assert!(ret.code.is_empty());
+
+        // The duplicate label is found by the semantic analysis
+        let ret = compiler.compile(&allocator, "a: a: ;");
+        assert_eq!(ret.errors.len(), 1);
+        let ret = compiler.with_check_syntax_error(false).compile(&allocator, "a: a: ;");
+        assert!(ret.errors.is_empty());
+    }
+}
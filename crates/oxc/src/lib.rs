@@ -1,6 +1,17 @@
 //! # The JavaScript Oxidation Compiler
 //!
 //! <https://github.com/oxc-project/oxc>
+//!
+//! ## Cargo Features
+//! * `"semantic"`, `"transformer"`, `"minifier"` and `"codegen"` re-export the crates
+//! * `"compiler"` enables them all and the [Compiler] pipeline
+//! * `"serde"` enables support for serde serialization
+
+#[cfg(feature = "compiler")]
+mod compiler;
+
+#[cfg(feature = "compiler")]
+pub use crate::compiler::{Compiler, CompilerReturn};
 
 pub mod allocator {
     #[doc(inline)]
@@ -27,6 +38,11 @@ pub mod parser {
     pub use oxc_parser::*;
 }
 
+pub mod sourcemap {
+    #[doc(inline)]
+    pub use oxc_sourcemap::*;
+}
+
 pub mod span {
     #[doc(inline)]
     pub use oxc_span::*;
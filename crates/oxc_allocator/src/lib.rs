@@ -1,15 +1,48 @@
 use std::{convert::From, ops::Deref};
 
 mod arena;
+mod pool;
 
 pub use arena::{Box, String, Vec};
 use bumpalo::Bump;
+pub use pool::{AllocatorPool, AllocatorPoolStats, PooledAllocator};
 
-#[derive(Default)]
+/// The arena of the AST and of the other data of a parse, which are freed all at once
+#[derive(Debug, Default)]
 pub struct Allocator {
     bump: Bump,
 }
 
+/// The memory of an [Allocator]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocatorStats {
+    /// The bytes of the chunks of the arena, not including their metadata
+    pub capacity: usize,
+    /// The bytes left in the current chunk, allocations larger than this allocate a new chunk
+    pub remaining_capacity: usize,
+}
+
+impl Allocator {
+    /// An allocator with a first chunk of `capacity` bytes, e.g. a few times the size of the
+    /// source text to parse
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { bump: Bump::with_capacity(capacity) }
+    }
+
+    /// Frees everything allocated while keeping the largest chunk, so the memory is reused by
+    /// the next parse. The borrow checker ensures that nothing allocated is still in use.
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+
+    pub fn stats(&self) -> AllocatorStats {
+        AllocatorStats {
+            capacity: self.bump.allocated_bytes(),
+            remaining_capacity: self.bump.chunk_capacity(),
+        }
+    }
+}
+
 impl From<Bump> for Allocator {
     fn from(bump: Bump) -> Self {
         Self { bump }
@@ -40,4 +73,19 @@ mod test {
             _ = allocator.deref();
         }
     }
+
+    #[test]
+    fn reset() {
+        let mut allocator = Allocator::with_capacity(1000);
+        assert!(allocator.stats().remaining_capacity >= 1000);
+        allocator.alloc_slice_fill_copy(10_000, 0u8);
+        let capacity = allocator.stats().capacity;
+        assert!(capacity >= 11_000);
+
+        // The largest chunk is kept
+        allocator.reset();
+        let stats = allocator.stats();
+        assert!(stats.capacity >= 10_000 && stats.capacity < capacity);
+        assert!(stats.remaining_capacity >= 10_000);
+    }
 }
@@ -0,0 +1,135 @@
+//! A pool of arenas reused across parses, for the processes which parse many files over their
+//! lifetime, e.g. a language server or a linter running on many threads
+
+use std::{
+    mem,
+    ops::Deref,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{Allocator, AllocatorStats};
+
+/// The allocators taken by [AllocatorPool::get] are reset and returned to the pool when they are
+/// dropped, so the memory of their chunks is allocated once per thread instead of once per file.
+#[derive(Debug, Default)]
+pub struct AllocatorPool {
+    allocators: Mutex<Vec<Allocator>>,
+    /// The allocators larger than this capacity are freed instead of being returned to the pool,
+    /// so that a single large file does not keep its memory for the lifetime of the process
+    max_capacity: Option<usize>,
+}
+
+/// An allocator of an [AllocatorPool]
+#[derive(Debug)]
+pub struct PooledAllocator<'pool> {
+    allocator: Allocator,
+    pool: &'pool AllocatorPool,
+}
+
+/// The memory kept by the idle allocators of an [AllocatorPool]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocatorPoolStats {
+    /// The number of allocators in the pool, which are not in use
+    pub idle_allocators: usize,
+    /// The sum of the capacities of the idle allocators in bytes
+    pub capacity: usize,
+}
+
+impl AllocatorPool {
+    #[must_use]
+    pub fn with_max_capacity(mut self, max_capacity: usize) -> Self {
+        self.max_capacity = Some(max_capacity);
+        self
+    }
+
+    /// Takes an allocator of the pool, or creates one when all of them are in use
+    pub fn get(&self) -> PooledAllocator<'_> {
+        let allocator = self.lock().pop().unwrap_or_default();
+        PooledAllocator { allocator, pool: self }
+    }
+
+    pub fn stats(&self) -> AllocatorPoolStats {
+        let allocators = self.lock();
+        AllocatorPoolStats {
+            idle_allocators: allocators.len(),
+            capacity: allocators.iter().map(|allocator| allocator.stats().capacity).sum(),
+        }
+    }
+
+    /// Frees the memory of the idle allocators
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Allocator>> {
+        // The allocators are reset before they are returned, so they are valid after a panic
+        self.allocators.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<'pool> PooledAllocator<'pool> {
+    pub fn stats(&self) -> AllocatorStats {
+        self.allocator.stats()
+    }
+}
+
+impl<'pool> Deref for PooledAllocator<'pool> {
+    type Target = Allocator;
+
+    fn deref(&self) -> &Self::Target {
+        &self.allocator
+    }
+}
+
+impl<'pool> Drop for PooledAllocator<'pool> {
+    fn drop(&mut self) {
+        let mut allocator = mem::take(&mut self.allocator);
+        allocator.reset();
+        if self.pool.max_capacity.is_some_and(|max| allocator.stats().capacity > max) {
+            return;
+        }
+        self.pool.lock().push(allocator);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AllocatorPool;
+
+    #[test]
+    fn reuse() {
+        let pool = AllocatorPool::default();
+        {
+            let allocator = pool.get();
+            allocator.alloc_slice_fill_copy(1000, 0u8);
+            assert!(allocator.stats().capacity >= 1000);
+        }
+        let stats = pool.stats();
+        assert_eq!(stats.idle_allocators, 1);
+        assert!(stats.capacity >= 1000);
+
+        // The allocator is reused, and a second one is created while it is in use
+        let first = pool.get();
+        assert!(first.stats().capacity >= 1000);
+        assert!(first.stats().remaining_capacity >= 1000);
+        let second = pool.get();
+        assert!(second.stats().capacity < 1000);
+        drop((first, second));
+        assert_eq!(pool.stats().idle_allocators, 2);
+
+        pool.clear();
+        assert_eq!(pool.stats().idle_allocators, 0);
+    }
+
+    #[test]
+    fn max_capacity() {
+        let pool = AllocatorPool::default().with_max_capacity(4096);
+        let (small, large) = (pool.get(), pool.get());
+        small.alloc_slice_fill_copy(100, 0u8);
+        large.alloc_slice_fill_copy(100_000, 0u8);
+        drop((small, large));
+        let stats = pool.stats();
+        assert_eq!(stats.idle_allocators, 1);
+        assert!(stats.capacity <= 4096);
+    }
+}
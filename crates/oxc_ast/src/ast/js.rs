@@ -584,6 +584,14 @@ impl<'a> MemberExpression<'a> {
         }
     }
 
+    pub fn object_mut(&mut self) -> &mut Expression<'a> {
+        match self {
+            MemberExpression::ComputedMemberExpression(expr) => &mut expr.object,
+            MemberExpression::StaticMemberExpression(expr) => &mut expr.object,
+            MemberExpression::PrivateFieldExpression(expr) => &mut expr.object,
+        }
+    }
+
     pub fn static_property_name(&self) -> Option<&str> {
         match self {
             MemberExpression::ComputedMemberExpression(expr) => match &expr.expression {
@@ -669,6 +677,9 @@ pub struct CallExpression<'a> {
     pub arguments: Vec<'a, Argument<'a>>,
     pub optional: bool, // for optional chaining
     pub type_parameters: Option<Box<'a, TSTypeParameterInstantiation<'a>>>,
+    /// Annotated with `/* #__PURE__ */`, the call can be removed when its result is unused
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pure: bool,
 }
 
 impl<'a> CallExpression<'a> {
@@ -721,6 +732,9 @@ pub struct NewExpression<'a> {
     pub callee: Expression<'a>,
     pub arguments: Vec<'a, Argument<'a>>,
     pub type_parameters: Option<Box<'a, TSTypeParameterInstantiation<'a>>>,
+    /// Annotated with `/* #__PURE__ */`, the expression can be removed when its result is unused
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pure: bool,
 }
 
 /// Meta Property `new.target` | `import.meta`
@@ -1568,6 +1582,10 @@ pub struct Function<'a> {
     pub return_type: Option<Box<'a, TSTypeAnnotation<'a>>>,
     /// Valid modifiers: `export`, `default`, `async`
     pub modifiers: Modifiers<'a>,
+    /// Annotated with `/* #__NO_SIDE_EFFECTS__ */`, the calls of the function can be removed
+    /// when their results are unused
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pure: bool,
 }
 
 impl<'a> Function<'a> {
@@ -1690,6 +1708,9 @@ pub struct ArrowExpression<'a> {
 
     pub type_parameters: Option<Box<'a, TSTypeParameterDeclaration<'a>>>,
     pub return_type: Option<Box<'a, TSTypeAnnotation<'a>>>,
+    /// Annotated with `/* #__NO_SIDE_EFFECTS__ */`, see [Function::pure]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pure: bool,
 }
 
 impl<'a> ArrowExpression<'a> {
@@ -1993,6 +2014,7 @@ pub struct AccessorProperty<'a> {
     pub value: Option<Expression<'a>>,
     pub computed: bool,
     pub r#static: bool,
+    pub decorators: Vec<'a, Decorator<'a>>,
 }
 
 #[derive(Debug, Hash)]
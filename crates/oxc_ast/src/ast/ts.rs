@@ -884,6 +884,10 @@ impl<'a> Modifiers<'a> {
     pub fn is_contains_declare(&self) -> bool {
         self.contains(ModifierKind::Declare)
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Modifier> + '_ {
+        self.0.iter().flat_map(|modifiers| modifiers.iter())
+    }
 }
 
 /// Export Assignment in non-module files
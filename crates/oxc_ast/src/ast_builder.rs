@@ -21,6 +21,8 @@ use oxc_syntax::{
 use crate::ast::*;
 
 /// AST builder for creating AST nodes
+///
+/// The nodes of source text snippets are built by the templates of `oxc_parser::template`.
 pub struct AstBuilder<'a> {
     pub allocator: &'a Allocator,
 }
@@ -121,6 +123,92 @@ impl<'a> AstBuilder<'a> {
         self.unary_expression(Span::default(), UnaryOperator::Void, num)
     }
 
+    /// `name`
+    pub fn identifier_expression(&self, span: Span, name: Atom) -> Expression<'a> {
+        self.identifier_reference_expression(IdentifierReference::new(span, name))
+    }
+
+    /// `"value"`
+    pub fn string_expression(&self, span: Span, value: Atom) -> Expression<'a> {
+        self.literal_string_expression(StringLiteral::new(span, value))
+    }
+
+    /// `value`, `-value` for the negative numbers
+    pub fn number_expression(&self, span: Span, value: f64) -> Expression<'a> {
+        let raw = self.new_str(&value.abs().to_string());
+        let literal = self.number_literal(span, value.abs(), raw, NumberBase::Decimal);
+        let expr = self.literal_number_expression(literal);
+        if value.is_sign_negative() {
+            self.unary_expression(span, UnaryOperator::UnaryNegation, expr)
+        } else {
+            expr
+        }
+    }
+
+    /// `true` or `false`
+    pub fn boolean_expression(&self, span: Span, value: bool) -> Expression<'a> {
+        self.literal_boolean_expression(self.boolean_literal(span, value))
+    }
+
+    /// `null`
+    pub fn null_expression(&self, span: Span) -> Expression<'a> {
+        self.literal_null_expression(NullLiteral::new(span))
+    }
+
+    /// The static member expressions of a dotted path, e.g. `Object.prototype.hasOwnProperty`
+    pub fn member_path(&self, span: Span, path: &str) -> Expression<'a> {
+        let mut names = path.split('.');
+        let object = self.identifier_expression(span, names.next().unwrap_or_default().into());
+        names.fold(object, |object, name| {
+            let property = IdentifierName::new(span, name.into());
+            self.static_member_expression(span, object, property, false)
+        })
+    }
+
+    /// `callee(...arguments)`, without spread arguments
+    pub fn call<I: IntoIterator<Item = Expression<'a>>>(
+        &self,
+        span: Span,
+        callee: Expression<'a>,
+        arguments: I,
+    ) -> Expression<'a> {
+        let mut args = self.new_vec();
+        args.extend(arguments.into_iter().map(Argument::Expression));
+        self.call_expression(span, callee, args, false, None)
+    }
+
+    /// `name = right`
+    pub fn assign_identifier(
+        &self,
+        span: Span,
+        name: Atom,
+        right: Expression<'a>,
+    ) -> Expression<'a> {
+        let target = self.simple_assignment_target_identifier(IdentifierReference::new(span, name));
+        let target = AssignmentTarget::SimpleAssignmentTarget(target);
+        self.assignment_expression(span, AssignmentOperator::Assign, target, right)
+    }
+
+    /// `kind name = init`, one declarator without a type annotation
+    pub fn variable_statement(
+        &self,
+        span: Span,
+        kind: VariableDeclarationKind,
+        name: Atom,
+        init: Option<Expression<'a>>,
+    ) -> Statement<'a> {
+        let binding = self.binding_pattern_identifier(BindingIdentifier::new(span, name));
+        let binding = self.binding_pattern(binding, None, false);
+        let declarator = self.variable_declarator(span, kind, binding, init, false);
+        let decl = self.variable_declaration(
+            span,
+            kind,
+            self.new_vec_single(declarator),
+            Modifiers::empty(),
+        );
+        Statement::Declaration(Declaration::VariableDeclaration(decl))
+    }
+
     /* ---------- Literals ---------- */
 
     pub fn number_literal(
@@ -424,6 +512,7 @@ impl<'a> AstBuilder<'a> {
             body,
             type_parameters,
             return_type,
+            pure: false,
         }))
     }
 
@@ -484,6 +573,7 @@ impl<'a> AstBuilder<'a> {
             arguments,
             optional,
             type_parameters,
+            pure: false,
         }))
     }
 
@@ -614,6 +704,7 @@ impl<'a> AstBuilder<'a> {
             callee,
             arguments,
             type_parameters,
+            pure: false,
         }))
     }
 
@@ -784,6 +875,7 @@ impl<'a> AstBuilder<'a> {
             type_parameters,
             return_type,
             modifiers,
+            pure: false,
         })
     }
 
@@ -874,6 +966,7 @@ impl<'a> AstBuilder<'a> {
         value: Option<Expression<'a>>,
         computed: bool,
         r#static: bool,
+        decorators: Vec<'a, Decorator<'a>>,
     ) -> ClassElement<'a> {
         ClassElement::AccessorProperty(self.alloc(AccessorProperty {
             span,
@@ -881,6 +974,7 @@ impl<'a> AstBuilder<'a> {
             value,
             computed,
             r#static,
+            decorators,
         }))
     }
 
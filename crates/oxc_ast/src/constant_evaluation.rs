@@ -0,0 +1,366 @@
+//! Compile-time evaluation of constant expressions
+//!
+//! An expression is constant when it is built from literals and operators only, e.g.
+//! `1 + 2`, `"a" + 1`, `` `v${1 + 1}` `` or `typeof 0`. Evaluating a constant expression has no
+//! side effects, so its value can be used in place of the expression.
+//!
+//! The identifiers `undefined`, `NaN` and `Infinity` are assumed to refer to the global values.
+
+use std::borrow::Cow;
+
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator};
+
+use crate::ast::{
+    BinaryExpression, Expression, LogicalExpression, NumberLiteral, TemplateLiteral,
+    UnaryExpression,
+};
+
+/// The value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue<'a> {
+    Number(f64),
+    BigInt(BigInt),
+    String(Cow<'a, str>),
+    Boolean(bool),
+    Undefined,
+    Null,
+}
+
+impl<'a> ConstantValue<'a> {
+    /// [`ToBoolean`](https://tc39.es/ecma262/#sec-toboolean)
+    pub fn to_boolean(&self) -> bool {
+        match self {
+            Self::Number(n) => !(n.is_nan() || *n == 0.0),
+            Self::BigInt(n) => !n.is_zero(),
+            Self::String(s) => !s.is_empty(),
+            Self::Boolean(b) => *b,
+            Self::Undefined | Self::Null => false,
+        }
+    }
+
+    /// [`ToNumber`](https://tc39.es/ecma262/#sec-tonumber), `None` for BigInts which throw
+    pub fn to_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::BigInt(_) => None,
+            Self::String(s) => Some(string_to_number(s)),
+            Self::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+            Self::Undefined => Some(f64::NAN),
+            Self::Null => Some(0.0),
+        }
+    }
+
+    /// [`ToString`](https://tc39.es/ecma262/#sec-tostring)
+    pub fn to_js_string(&self) -> Cow<'a, str> {
+        match self {
+            Self::Number(n) => Cow::Owned(number_to_string(*n)),
+            Self::BigInt(n) => Cow::Owned(n.to_string()),
+            Self::String(s) => s.clone(),
+            Self::Boolean(b) => Cow::Borrowed(if *b { "true" } else { "false" }),
+            Self::Undefined => Cow::Borrowed("undefined"),
+            Self::Null => Cow::Borrowed("null"),
+        }
+    }
+
+    /// The result of the `typeof` operator
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "number",
+            Self::BigInt(_) => "bigint",
+            Self::String(_) => "string",
+            Self::Boolean(_) => "boolean",
+            Self::Undefined => "undefined",
+            Self::Null => "object",
+        }
+    }
+
+    /// [`IsStrictlyEqual`](https://tc39.es/ecma262/#sec-isstrictlyequal)
+    #[allow(clippy::float_cmp)]
+    pub fn strict_equals(&self, other: &Self) -> bool {
+        match (self, other) {
+            // `NaN !== NaN` and `0 === -0`
+            (Self::Number(a), Self::Number(b)) => a == b,
+            _ => self == other,
+        }
+    }
+
+    /// [`IsLooselyEqual`](https://tc39.es/ecma262/#sec-islooselyequal), `None` when the
+    /// comparison between a BigInt and a string or number is not supported.
+    #[allow(clippy::float_cmp)]
+    pub fn loose_equals(&self, other: &Self) -> Option<bool> {
+        match (self, other) {
+            (Self::Undefined | Self::Null, Self::Undefined | Self::Null) => Some(true),
+            (Self::Undefined | Self::Null, _) | (_, Self::Undefined | Self::Null) => Some(false),
+            (Self::BigInt(a), Self::BigInt(b)) => Some(a == b),
+            (Self::BigInt(_), _) | (_, Self::BigInt(_)) => None,
+            (Self::String(a), Self::String(b)) => Some(a == b),
+            _ => Some(self.to_number()? == other.to_number()?),
+        }
+    }
+}
+
+/// Evaluate an expression at compile time.
+pub trait ConstantEvaluation {
+    /// Get the value of the expression, or `None` if it is not a constant expression.
+    fn evaluate_constant(&self) -> Option<ConstantValue<'_>>;
+}
+
+impl<'a> ConstantEvaluation for Expression<'a> {
+    fn evaluate_constant(&self) -> Option<ConstantValue<'_>> {
+        match self {
+            Self::NumberLiteral(lit) => Some(ConstantValue::Number(lit.value)),
+            Self::BigintLiteral(lit) => Some(ConstantValue::BigInt(lit.value.clone())),
+            Self::StringLiteral(lit) => Some(ConstantValue::String(Cow::Borrowed(&lit.value))),
+            Self::BooleanLiteral(lit) => Some(ConstantValue::Boolean(lit.value)),
+            Self::NullLiteral(_) => Some(ConstantValue::Null),
+            Self::Identifier(ident) => match ident.name.as_str() {
+                "undefined" => Some(ConstantValue::Undefined),
+                "NaN" => Some(ConstantValue::Number(f64::NAN)),
+                "Infinity" => Some(ConstantValue::Number(f64::INFINITY)),
+                _ => None,
+            },
+            Self::TemplateLiteral(lit) => lit.evaluate_constant(),
+            Self::ParenthesizedExpression(expr) => expr.expression.evaluate_constant(),
+            Self::SequenceExpression(expr) => {
+                let mut value = None;
+                for expr in &expr.expressions {
+                    value = Some(expr.evaluate_constant()?);
+                }
+                value
+            }
+            Self::ConditionalExpression(expr) => {
+                if expr.test.evaluate_constant()?.to_boolean() {
+                    expr.consequent.evaluate_constant()
+                } else {
+                    expr.alternate.evaluate_constant()
+                }
+            }
+            Self::UnaryExpression(expr) => expr.evaluate_constant(),
+            Self::BinaryExpression(expr) => expr.evaluate_constant(),
+            Self::LogicalExpression(expr) => expr.evaluate_constant(),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> ConstantEvaluation for TemplateLiteral<'a> {
+    fn evaluate_constant(&self) -> Option<ConstantValue<'_>> {
+        if self.expressions.is_empty() {
+            let cooked = self.quasis.first()?.value.cooked.as_ref()?;
+            return Some(ConstantValue::String(Cow::Borrowed(cooked.as_str())));
+        }
+        let mut value = String::new();
+        for (i, quasi) in self.quasis.iter().enumerate() {
+            value.push_str(quasi.value.cooked.as_ref()?);
+            if let Some(expr) = self.expressions.get(i) {
+                value.push_str(&expr.evaluate_constant()?.to_js_string());
+            }
+        }
+        Some(ConstantValue::String(Cow::Owned(value)))
+    }
+}
+
+impl<'a> ConstantEvaluation for UnaryExpression<'a> {
+    fn evaluate_constant(&self) -> Option<ConstantValue<'_>> {
+        if self.operator == UnaryOperator::Typeof
+            && matches!(
+                self.argument.without_parenthesized(),
+                Expression::FunctionExpression(_) | Expression::ArrowExpression(_)
+            )
+        {
+            return Some(ConstantValue::String(Cow::Borrowed("function")));
+        }
+        let value = self.argument.evaluate_constant()?;
+        match self.operator {
+            UnaryOperator::LogicalNot => Some(ConstantValue::Boolean(!value.to_boolean())),
+            UnaryOperator::UnaryNegation => match value {
+                ConstantValue::BigInt(n) => Some(ConstantValue::BigInt(-n)),
+                value => Some(ConstantValue::Number(-value.to_number()?)),
+            },
+            UnaryOperator::UnaryPlus => value.to_number().map(ConstantValue::Number),
+            UnaryOperator::BitwiseNot => match value {
+                ConstantValue::BigInt(n) => Some(ConstantValue::BigInt(!n)),
+                value => {
+                    let n = NumberLiteral::ecmascript_to_int32(value.to_number()?);
+                    Some(ConstantValue::Number(f64::from(!n)))
+                }
+            },
+            UnaryOperator::Typeof => Some(ConstantValue::String(Cow::Borrowed(value.type_of()))),
+            UnaryOperator::Void => Some(ConstantValue::Undefined),
+            UnaryOperator::Delete => None,
+        }
+    }
+}
+
+impl<'a> ConstantEvaluation for LogicalExpression<'a> {
+    fn evaluate_constant(&self) -> Option<ConstantValue<'_>> {
+        // The right side is not evaluated when the left side decides the result
+        let left = self.left.evaluate_constant()?;
+        let use_left = match self.operator {
+            LogicalOperator::And => !left.to_boolean(),
+            LogicalOperator::Or => left.to_boolean(),
+            LogicalOperator::Coalesce => {
+                !matches!(left, ConstantValue::Undefined | ConstantValue::Null)
+            }
+        };
+        if use_left {
+            Some(left)
+        } else {
+            self.right.evaluate_constant()
+        }
+    }
+}
+
+impl<'a> ConstantEvaluation for BinaryExpression<'a> {
+    fn evaluate_constant(&self) -> Option<ConstantValue<'_>> {
+        let left = self.left.evaluate_constant()?;
+        let right = self.right.evaluate_constant()?;
+        evaluate_binary(self.operator, &left, &right)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::float_cmp)]
+fn evaluate_binary<'a>(
+    operator: BinaryOperator,
+    left: &ConstantValue<'a>,
+    right: &ConstantValue<'a>,
+) -> Option<ConstantValue<'a>> {
+    use ConstantValue::{BigInt as Big, Boolean, Number};
+
+    let value = match operator {
+        BinaryOperator::Addition => match (left, right) {
+            (ConstantValue::String(_), _) | (_, ConstantValue::String(_)) => {
+                let value = left.to_js_string().into_owned() + &right.to_js_string();
+                ConstantValue::String(Cow::Owned(value))
+            }
+            (Big(a), Big(b)) => Big(a + b),
+            _ => Number(left.to_number()? + right.to_number()?),
+        },
+        BinaryOperator::Subtraction
+        | BinaryOperator::Multiplication
+        | BinaryOperator::Division
+        | BinaryOperator::Remainder
+        | BinaryOperator::Exponential => {
+            if let (Big(a), Big(b)) = (left, right) {
+                Big(bigint_arithmetic(operator, a, b)?)
+            } else {
+                let (a, b) = (left.to_number()?, right.to_number()?);
+                Number(match operator {
+                    BinaryOperator::Subtraction => a - b,
+                    BinaryOperator::Multiplication => a * b,
+                    BinaryOperator::Division => a / b,
+                    BinaryOperator::Remainder => a % b,
+                    // `1 ** NaN` and `1 ** Infinity` are `NaN`
+                    _ if b.is_nan() || (a.abs() == 1.0 && b.is_infinite()) => f64::NAN,
+                    _ => a.powf(b),
+                })
+            }
+        }
+        BinaryOperator::BitwiseAnd | BinaryOperator::BitwiseOR | BinaryOperator::BitwiseXOR => {
+            if let (Big(a), Big(b)) = (left, right) {
+                Big(match operator {
+                    BinaryOperator::BitwiseAnd => a & b,
+                    BinaryOperator::BitwiseOR => a | b,
+                    _ => a ^ b,
+                })
+            } else {
+                let a = NumberLiteral::ecmascript_to_int32(left.to_number()?);
+                let b = NumberLiteral::ecmascript_to_int32(right.to_number()?);
+                Number(f64::from(match operator {
+                    BinaryOperator::BitwiseAnd => a & b,
+                    BinaryOperator::BitwiseOR => a | b,
+                    _ => a ^ b,
+                }))
+            }
+        }
+        BinaryOperator::ShiftLeft
+        | BinaryOperator::ShiftRight
+        | BinaryOperator::ShiftRightZeroFill => {
+            if matches!(left, Big(_)) || matches!(right, Big(_)) {
+                return None;
+            }
+            let a = NumberLiteral::ecmascript_to_int32(left.to_number()?);
+            let shift = NumberLiteral::ecmascript_to_int32(right.to_number()?) as u32 & 0x1f;
+            Number(match operator {
+                BinaryOperator::ShiftLeft => f64::from(a.wrapping_shl(shift)),
+                BinaryOperator::ShiftRight => f64::from(a >> shift),
+                _ => f64::from((a as u32) >> shift),
+            })
+        }
+        BinaryOperator::StrictEquality => Boolean(left.strict_equals(right)),
+        BinaryOperator::StrictInequality => Boolean(!left.strict_equals(right)),
+        BinaryOperator::Equality => Boolean(left.loose_equals(right)?),
+        BinaryOperator::Inequality => Boolean(!left.loose_equals(right)?),
+        BinaryOperator::LessThan
+        | BinaryOperator::LessEqualThan
+        | BinaryOperator::GreaterThan
+        | BinaryOperator::GreaterEqualThan => {
+            let ordering = match (left, right) {
+                (ConstantValue::String(a), ConstantValue::String(b)) => {
+                    // Strings are compared by UTF-16 code units
+                    Some(a.encode_utf16().cmp(b.encode_utf16()))
+                }
+                (Big(a), Big(b)) => Some(a.cmp(b)),
+                (Big(_), _) | (_, Big(_)) => return None,
+                _ => left.to_number()?.partial_cmp(&right.to_number()?),
+            };
+            // Comparisons with `NaN` are always false
+            Boolean(ordering.is_some_and(|ordering| match operator {
+                BinaryOperator::LessThan => ordering.is_lt(),
+                BinaryOperator::LessEqualThan => ordering.is_le(),
+                BinaryOperator::GreaterThan => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        BinaryOperator::In | BinaryOperator::Instanceof => return None,
+    };
+    Some(value)
+}
+
+/// BigInt arithmetic, `None` when the operation throws
+fn bigint_arithmetic(operator: BinaryOperator, a: &BigInt, b: &BigInt) -> Option<BigInt> {
+    match operator {
+        BinaryOperator::Subtraction => Some(a - b),
+        BinaryOperator::Multiplication => Some(a * b),
+        BinaryOperator::Division => (!b.is_zero()).then(|| a / b),
+        BinaryOperator::Remainder => (!b.is_zero()).then(|| a % b),
+        BinaryOperator::Exponential => b.to_u32().map(|exponent| a.pow(exponent)),
+        _ => None,
+    }
+}
+
+/// [`StringToNumber`](https://tc39.es/ecma262/#sec-stringtonumber)
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}');
+    if s.is_empty() {
+        return 0.0;
+    }
+    let radix = match s.get(..2) {
+        Some("0x" | "0X") => Some(16),
+        Some("0o" | "0O") => Some(8),
+        Some("0b" | "0B") => Some(2),
+        _ => None,
+    };
+    if let Some(radix) = radix {
+        return BigInt::parse_bytes(s[2..].as_bytes(), radix)
+            .and_then(|n| n.to_f64())
+            .unwrap_or(f64::NAN);
+    }
+    match s {
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        // Reject the `inf` and `nan` spellings accepted by Rust
+        _ if s.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')) => {
+            s.parse::<f64>().unwrap_or(f64::NAN)
+        }
+        _ => f64::NAN,
+    }
+}
+
+/// [`Number::toString`](https://tc39.es/ecma262/#sec-numeric-types-number-tostring)
+fn number_to_string(n: f64) -> String {
+    let mut buffer = ryu_js::Buffer::new();
+    buffer.format(n).to_string()
+}
@@ -0,0 +1,375 @@
+//! Conversion of the serialized AST to [ESTree](https://github.com/estree/estree)
+//!
+//! The AST is serialized first, then the nodes which differ from ESTree are rewritten:
+//! * the literals are `Literal` with their `raw` text
+//! * the identifiers are `Identifier`
+//! * the member expressions are `MemberExpression`
+//! * the directives are `ExpressionStatement` with a `directive` in the bodies
+//! * the binding patterns and the assignment targets are the ESTree patterns
+//! * the parenthesized expressions are replaced by their expressions
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::{ast::Program, serialize::EcmaFormatter, CommentKind, Trivias};
+
+impl<'a> Program<'a> {
+    /// The program as an ESTree `Program`, `source_text` is the parsed text for the `raw` of the
+    /// literals
+    ///
+    /// # Panics
+    pub fn to_estree(&self, source_text: &str) -> Value {
+        convert(serde_json::to_value(self).unwrap(), source_text)
+    }
+
+    /// # Panics
+    pub fn to_estree_json(&self, source_text: &str) -> String {
+        let mut ser = serde_json::Serializer::with_formatter(vec![], EcmaFormatter);
+        self.to_estree(source_text).serialize(&mut ser).unwrap();
+        String::from_utf8(ser.into_inner()).unwrap()
+    }
+}
+
+/// An ESTree comment, `value` is the text without the `//`, `/*` and `*/`
+#[derive(Debug, Clone, Serialize)]
+pub struct EstreeComment {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub value: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Trivias {
+    /// The comments as ESTree comments, the span of the comments in the trivias is the span of
+    /// their text
+    pub fn to_estree_comments(&self, source_text: &str) -> Vec<EstreeComment> {
+        self.comments
+            .iter()
+            .map(|&(start, end, kind)| {
+                let (kind, end) = match kind {
+                    CommentKind::SingleLine => ("Line", end),
+                    CommentKind::MultiLine => ("Block", end + 2),
+                };
+                let value = &source_text[start as usize..end as usize];
+                let value = value.strip_suffix("*/").unwrap_or(value).to_string();
+                EstreeComment { kind, value, start: start - 2, end }
+            })
+            .collect()
+    }
+}
+
+/// Converts the children first, so that a node sees the converted children
+fn convert(value: Value, source_text: &str) -> Value {
+    match value {
+        Value::Array(items) => {
+            Value::Array(items.into_iter().map(|item| convert(item, source_text)).collect())
+        }
+        Value::Object(node) => {
+            let node =
+                node.into_iter().map(|(key, value)| (key, convert(value, source_text))).collect();
+            convert_node(node, source_text)
+        }
+        value => value,
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn convert_node(mut node: Map<String, Value>, source_text: &str) -> Value {
+    let Some(Value::String(node_type)) = node.get("type").cloned() else {
+        return Value::Object(node);
+    };
+    let node_type = match node_type.as_str() {
+        "BooleanLiteral" | "NullLiteral" | "NumberLiteral" | "StringLiteral" | "BigintLiteral"
+        | "RegExpLiteral" => {
+            let raw = raw(&node, source_text).to_string();
+            match node_type.as_str() {
+                "NullLiteral" | "RegExpLiteral" => {
+                    node.insert("value".into(), Value::Null);
+                }
+                "BigintLiteral" => {
+                    let bigint = raw.trim_end_matches('n').replace('_', "");
+                    node.insert("value".into(), Value::Null);
+                    node.insert("bigint".into(), bigint.into());
+                }
+                _ => {}
+            }
+            node.insert("raw".into(), raw.into());
+            "Literal"
+        }
+        "IdentifierName" | "IdentifierReference" | "BindingIdentifier" | "LabelIdentifier" => {
+            "Identifier"
+        }
+        "StaticMemberExpression" => {
+            node.insert("computed".into(), false.into());
+            "MemberExpression"
+        }
+        "ComputedMemberExpression" => {
+            rename(&mut node, "expression", "property");
+            node.insert("computed".into(), true.into());
+            "MemberExpression"
+        }
+        "PrivateFieldExpression" => {
+            rename(&mut node, "field", "property");
+            node.insert("computed".into(), false.into());
+            "MemberExpression"
+        }
+        "Program" => {
+            let module_kind = node.get("sourceType").and_then(|t| t.get("moduleKind")).cloned();
+            node.insert("sourceType".into(), module_kind.unwrap_or_else(|| json!("module")));
+            let body = take_array(&mut node, "directives")
+                .into_iter()
+                .chain(take_array(&mut node, "body"))
+                .collect();
+            node.insert("body".into(), Value::Array(body));
+            "Program"
+        }
+        "FunctionBody" => {
+            let body = take_array(&mut node, "directives")
+                .into_iter()
+                .chain(take_array(&mut node, "statements"))
+                .collect();
+            node.insert("body".into(), Value::Array(body));
+            "BlockStatement"
+        }
+        "Directive" => "ExpressionStatement",
+        "ArrowExpression" => {
+            node.remove("span");
+            "ArrowFunctionExpression"
+        }
+        // The parameters are the patterns of the items and the rest element
+        "FormalParameters" => {
+            let mut params = take_array(&mut node, "items");
+            params.extend(node.remove("rest").filter(|rest| !rest.is_null()));
+            return Value::Array(params);
+        }
+        "FormalParameter" => return node.remove("pattern").unwrap_or_default(),
+        "BindingPattern" => {
+            let Some(Value::Object(mut kind)) = node.remove("kind") else {
+                return Value::Object(node);
+            };
+            if let Some(type_annotation) = node.remove("typeAnnotation").filter(|t| !t.is_null()) {
+                kind.insert("typeAnnotation".into(), type_annotation);
+            }
+            if node.get("optional") == Some(&Value::Bool(true)) {
+                kind.insert("optional".into(), true.into());
+            }
+            return Value::Object(kind);
+        }
+        "BindingRestElement" => "RestElement",
+        "BindingProperty" => {
+            node.insert("kind".into(), "init".into());
+            node.insert("method".into(), false.into());
+            "Property"
+        }
+        "ObjectProperty" => {
+            node.remove("init");
+            "Property"
+        }
+        "ArrayPattern" | "ObjectPattern" => {
+            let key = if node_type == "ArrayPattern" { "elements" } else { "properties" };
+            append_rest(&mut node, key, source_text);
+            node_type.as_str()
+        }
+        "ArrayAssignmentTarget" | "ObjectAssignmentTarget" => {
+            let (key, pattern_type) = if node_type == "ArrayAssignmentTarget" {
+                ("elements", "ArrayPattern")
+            } else {
+                ("properties", "ObjectPattern")
+            };
+            node.remove("trailing_comma");
+            append_rest(&mut node, key, source_text);
+            pattern_type
+        }
+        "AssignmentTargetWithDefault" => {
+            rename(&mut node, "binding", "left");
+            rename(&mut node, "init", "right");
+            "AssignmentPattern"
+        }
+        "AssignmentTargetPropertyIdentifier" => {
+            let binding = node.remove("binding").unwrap_or_default();
+            let value = node.remove("init").filter(|init| !init.is_null()).map_or_else(
+                || binding.clone(),
+                |init| {
+                    let mut pattern = Map::new();
+                    pattern.insert("type".into(), "AssignmentPattern".into());
+                    copy_span(&node, &mut pattern);
+                    pattern.insert("left".into(), binding.clone());
+                    pattern.insert("right".into(), init);
+                    Value::Object(pattern)
+                },
+            );
+            node.insert("key".into(), binding);
+            node.insert("value".into(), value);
+            node.insert("kind".into(), "init".into());
+            node.insert("method".into(), false.into());
+            node.insert("shorthand".into(), true.into());
+            node.insert("computed".into(), false.into());
+            "Property"
+        }
+        "AssignmentTargetPropertyProperty" => {
+            rename(&mut node, "name", "key");
+            rename(&mut node, "binding", "value");
+            let key_start =
+                node.get("key").and_then(Value::as_object).map(|key| offset(key, "start"));
+            let computed = key_start.is_some_and(|start| {
+                source_text.get(..start).is_some_and(|s| s.trim_end().ends_with('['))
+            });
+            node.insert("kind".into(), "init".into());
+            node.insert("method".into(), false.into());
+            node.insert("shorthand".into(), false.into());
+            node.insert("computed".into(), computed.into());
+            "Property"
+        }
+        "ObjectExpression" => {
+            node.remove("trailing_comma");
+            "ObjectExpression"
+        }
+        "ParenthesizedExpression" => return node.remove("expression").unwrap_or_default(),
+        _ => return Value::Object(node),
+    };
+    node.insert("type".into(), node_type.into());
+    Value::Object(node)
+}
+
+/// The `start` or the `end` of a node
+#[allow(clippy::cast_possible_truncation)]
+fn offset(node: &Map<String, Value>, key: &str) -> usize {
+    node.get(key).and_then(Value::as_u64).unwrap_or(0) as usize
+}
+
+fn raw<'s>(node: &Map<String, Value>, source_text: &'s str) -> &'s str {
+    source_text.get(offset(node, "start")..offset(node, "end")).unwrap_or_default()
+}
+
+fn rename(node: &mut Map<String, Value>, from: &str, to: &str) {
+    if let Some(value) = node.remove(from) {
+        node.insert(to.into(), value);
+    }
+}
+
+fn take_array(node: &mut Map<String, Value>, key: &str) -> Vec<Value> {
+    match node.remove(key) {
+        Some(Value::Array(items)) => items,
+        _ => vec![],
+    }
+}
+
+fn copy_span(from: &Map<String, Value>, to: &mut Map<String, Value>) {
+    for key in ["start", "end"] {
+        if let Some(offset) = from.get(key) {
+            to.insert(key.into(), offset.clone());
+        }
+    }
+}
+
+/// Moves the `rest` of a pattern to the end of its elements or properties, the rest of the
+/// assignment targets is wrapped in a `RestElement` starting at its `...`
+fn append_rest(node: &mut Map<String, Value>, key: &str, source_text: &str) {
+    let Some(Value::Object(rest)) = node.remove("rest") else { return };
+    let rest = if rest.get("type").and_then(Value::as_str) == Some("RestElement") {
+        rest
+    } else {
+        let argument_start = offset(&rest, "start");
+        let start = source_text
+            .get(..argument_start)
+            .and_then(|before| before.rfind("..."))
+            .unwrap_or(argument_start);
+        let mut element = Map::new();
+        element.insert("type".into(), "RestElement".into());
+        element.insert("start".into(), start.into());
+        element.insert("end".into(), rest.get("end").cloned().unwrap_or_default());
+        element.insert("argument".into(), Value::Object(rest));
+        element
+    };
+    let mut items = take_array(node, key);
+    items.push(Value::Object(rest));
+    node.insert(key.into(), Value::Array(items));
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+    use serde_json::{json, Value};
+
+    fn estree(source_text: &str) -> Value {
+        let allocator = Allocator::default();
+        let ret =
+            Parser::new(&allocator, source_text, SourceType::default().with_module(true)).parse();
+        assert!(ret.errors.is_empty());
+        ret.program.to_estree(source_text)
+    }
+
+    #[test]
+    fn program() {
+        let program = estree("'use strict'; a;");
+        assert_eq!(program["sourceType"], json!("module"));
+        assert_eq!(program["body"][0]["type"], json!("ExpressionStatement"));
+        assert_eq!(program["body"][0]["directive"], json!("use strict"));
+        assert_eq!(program["body"][1]["expression"]["type"], json!("Identifier"));
+        assert!(program.get("directives").is_none());
+    }
+
+    #[test]
+    fn literals() {
+        let program = estree("[1_0, 'a', null, 1n, /a/g]");
+        let elements = &program["body"][0]["expression"]["elements"];
+        assert_eq!(
+            elements[0],
+            json!({ "type": "Literal", "start": 1, "end": 4, "value": 10.0, "raw": "1_0" })
+        );
+        assert_eq!(elements[1]["raw"], json!("'a'"));
+        assert_eq!(elements[2]["value"], Value::Null);
+        assert_eq!(elements[3]["bigint"], json!("1"));
+        assert_eq!(elements[4]["regex"]["pattern"], json!("a"));
+    }
+
+    #[test]
+    fn members() {
+        let program = estree("class A { #b; c() { a.b, a[b], this.#b, (a); } }");
+        let method = &program["body"][0]["body"]["body"][1]["value"];
+        assert_eq!(method["params"], json!([]));
+        let expressions = &method["body"]["body"][0]["expression"]["expressions"];
+        assert_eq!(expressions[0]["type"], json!("MemberExpression"));
+        assert_eq!(expressions[0]["computed"], json!(false));
+        assert_eq!(expressions[1]["computed"], json!(true));
+        assert_eq!(expressions[1]["property"]["name"], json!("b"));
+        assert_eq!(expressions[2]["property"]["type"], json!("PrivateIdentifier"));
+        assert_eq!(expressions[3]["type"], json!("Identifier"));
+    }
+
+    #[test]
+    fn patterns() {
+        let program = estree("function f(a, { b, c: [d = 1] }, ...e) {} [x, ...y] = z;");
+        let params = &program["body"][0]["params"];
+        assert_eq!(params[0]["type"], json!("Identifier"));
+        assert_eq!(params[1]["type"], json!("ObjectPattern"));
+        assert_eq!(params[1]["properties"][0]["type"], json!("Property"));
+        let d = &params[1]["properties"][1]["value"]["elements"][0];
+        assert_eq!(d["type"], json!("AssignmentPattern"));
+        assert_eq!(params[2]["type"], json!("RestElement"));
+
+        let left = &program["body"][1]["expression"]["left"];
+        assert_eq!(left["type"], json!("ArrayPattern"));
+        let rest = &left["elements"][1];
+        assert_eq!(rest["type"], json!("RestElement"));
+        assert_eq!((&rest["start"], &rest["end"]), (&json!(46), &json!(50)));
+    }
+
+    #[test]
+    fn comments() {
+        let source_text = "// a\n/* b */ c;";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+        let comments = serde_json::to_value(ret.trivias.to_estree_comments(source_text)).unwrap();
+        assert_eq!(
+            comments,
+            json!([
+                { "type": "Line", "value": " a", "start": 0, "end": 4 },
+                { "type": "Block", "value": " b ", "start": 5, "end": 12 },
+            ])
+        );
+    }
+}
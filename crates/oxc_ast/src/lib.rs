@@ -9,12 +9,15 @@
 //! ## Cargo Features
 //! * `"serde"` enables support for serde serialization
 
+#[cfg(feature = "serde")]
+mod estree;
 #[cfg(feature = "serde")]
 mod serialize;
 
 pub mod ast;
 mod ast_builder;
 mod ast_kind;
+pub mod constant_evaluation;
 pub mod precedence;
 mod span;
 pub mod syntax_directed_operations;
@@ -24,6 +27,9 @@ mod visit_mut;
 
 pub use num_bigint::BigUint;
 
+#[cfg(feature = "serde")]
+pub use crate::estree::EstreeComment;
+
 pub use crate::{
     ast_builder::AstBuilder,
     ast_kind::AstKind,
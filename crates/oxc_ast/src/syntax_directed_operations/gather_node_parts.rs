@@ -14,6 +14,10 @@ impl<'a> GatherNodeParts for Expression<'a> {
             Self::AssignmentExpression(expr) => expr.left.gather(f),
             Self::UpdateExpression(expr) => expr.argument.gather(f),
             Self::StringLiteral(lit) => lit.gather(f),
+            Self::MetaProperty(expr) => {
+                expr.meta.gather(f);
+                expr.property.gather(f);
+            }
             _ => f(Atom::from("ref")),
         }
     }
@@ -4,7 +4,7 @@ use oxc_span::Span;
 use std::collections::BTreeMap;
 
 /// A vec of trivias from the lexer, tupled by (span.start, span.end).
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Trivias {
     pub comments: Vec<(u32, u32, CommentKind)>,
     pub irregular_whitespaces: Vec<Span>,
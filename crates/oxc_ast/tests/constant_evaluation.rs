@@ -0,0 +1,110 @@
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::Statement,
+    constant_evaluation::{ConstantEvaluation, ConstantValue},
+};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+/// Evaluate the expression statement `source_text` and convert the result to a string.
+fn evaluate(source_text: &str) -> Option<String> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    assert!(ret.errors.is_empty(), "{source_text}");
+    let Some(Statement::ExpressionStatement(stmt)) = ret.program.body.first() else {
+        panic!("Expected an expression statement: {source_text}");
+    };
+    stmt.expression.evaluate_constant().map(|value| match value {
+        ConstantValue::String(s) => format!("{s:?}"),
+        ConstantValue::BigInt(n) => format!("{n}n"),
+        value => value.to_js_string().into_owned(),
+    })
+}
+
+#[test]
+fn literals() {
+    assert_eq!(evaluate("1").as_deref(), Some("1"));
+    assert_eq!(evaluate("1.5e3").as_deref(), Some("1500"));
+    assert_eq!(evaluate("('a')").as_deref(), Some("\"a\""));
+    assert_eq!(evaluate("10n").as_deref(), Some("10n"));
+    assert_eq!(evaluate("null").as_deref(), Some("null"));
+    assert_eq!(evaluate("undefined").as_deref(), Some("undefined"));
+    assert_eq!(evaluate("NaN").as_deref(), Some("NaN"));
+    assert_eq!(evaluate("(1, 2)").as_deref(), Some("2"));
+    assert_eq!(evaluate("foo"), None);
+    assert_eq!(evaluate("(foo(), 2)"), None);
+}
+
+#[test]
+fn numbers() {
+    assert_eq!(evaluate("1 + 2 * 3").as_deref(), Some("7"));
+    assert_eq!(evaluate("1 / 0").as_deref(), Some("Infinity"));
+    assert_eq!(evaluate("-1 / 0").as_deref(), Some("-Infinity"));
+    assert_eq!(evaluate("0.1 + 0.2").as_deref(), Some("0.30000000000000004"));
+    assert_eq!(evaluate("2 ** 10").as_deref(), Some("1024"));
+    assert_eq!(evaluate("1 ** Infinity").as_deref(), Some("NaN"));
+    assert_eq!(evaluate("'3' * '4'").as_deref(), Some("12"));
+    assert_eq!(evaluate("+' 0x10 '").as_deref(), Some("16"));
+    assert_eq!(evaluate("+''").as_deref(), Some("0"));
+    assert_eq!(evaluate("+'inf'").as_deref(), Some("NaN"));
+    assert_eq!(evaluate("+'-Infinity'").as_deref(), Some("-Infinity"));
+    assert_eq!(evaluate("~5").as_deref(), Some("-6"));
+    assert_eq!(evaluate("1 << 31").as_deref(), Some("-2147483648"));
+    assert_eq!(evaluate("-1 >>> 0").as_deref(), Some("4294967295"));
+    assert_eq!(evaluate("5 & 3 | 8 ^ 1").as_deref(), Some("9"));
+    assert_eq!(evaluate("2n ** 64n").as_deref(), Some("18446744073709551616n"));
+    assert_eq!(evaluate("-7n / 2n").as_deref(), Some("-3n"));
+    assert_eq!(evaluate("1n / 0n"), None);
+    assert_eq!(evaluate("1n + 1"), None);
+}
+
+#[test]
+fn strings() {
+    assert_eq!(evaluate("'a' + 1").as_deref(), Some("\"a1\""));
+    assert_eq!(evaluate("1 + 2 + 'a'").as_deref(), Some("\"3a\""));
+    assert_eq!(evaluate("'' + 1e21").as_deref(), Some("\"1e+21\""));
+    assert_eq!(evaluate("'' + null + undefined").as_deref(), Some("\"nullundefined\""));
+    assert_eq!(evaluate("`a${1 + 1}b${'c'}`").as_deref(), Some("\"a2bc\""));
+    assert_eq!(evaluate("`\\n`").as_deref(), Some("\"\\n\""));
+    assert_eq!(evaluate("`a${foo}`"), None);
+}
+
+#[test]
+fn booleans() {
+    assert_eq!(evaluate("!0").as_deref(), Some("true"));
+    assert_eq!(evaluate("!''").as_deref(), Some("true"));
+    assert_eq!(evaluate("!0n").as_deref(), Some("true"));
+    assert_eq!(evaluate("1 === 1.0").as_deref(), Some("true"));
+    assert_eq!(evaluate("NaN === NaN").as_deref(), Some("false"));
+    assert_eq!(evaluate("null == undefined").as_deref(), Some("true"));
+    assert_eq!(evaluate("null == 0").as_deref(), Some("false"));
+    assert_eq!(evaluate("'1' == 1").as_deref(), Some("true"));
+    assert_eq!(evaluate("'1' === 1").as_deref(), Some("false"));
+    assert_eq!(evaluate("'b' > 'a'").as_deref(), Some("true"));
+    assert_eq!(evaluate("'10' < '9'").as_deref(), Some("true"));
+    assert_eq!(evaluate("NaN <= 1").as_deref(), Some("false"));
+    assert_eq!(evaluate("1 in foo"), None);
+}
+
+#[test]
+fn logical_and_conditional() {
+    assert_eq!(evaluate("0 && foo").as_deref(), Some("0"));
+    assert_eq!(evaluate("1 || foo").as_deref(), Some("1"));
+    assert_eq!(evaluate("null ?? 'a'").as_deref(), Some("\"a\""));
+    assert_eq!(evaluate("0 ?? 'a'").as_deref(), Some("0"));
+    assert_eq!(evaluate("1 && foo"), None);
+    assert_eq!(evaluate("1 ? 'a' : foo").as_deref(), Some("\"a\""));
+    assert_eq!(evaluate("foo ? 1 : 2"), None);
+}
+
+#[test]
+fn type_of() {
+    assert_eq!(evaluate("typeof 1").as_deref(), Some("\"number\""));
+    assert_eq!(evaluate("typeof 1n").as_deref(), Some("\"bigint\""));
+    assert_eq!(evaluate("typeof null").as_deref(), Some("\"object\""));
+    assert_eq!(evaluate("typeof void 0").as_deref(), Some("\"undefined\""));
+    assert_eq!(evaluate("typeof `a`").as_deref(), Some("\"string\""));
+    assert_eq!(evaluate("typeof function () {}").as_deref(), Some("\"function\""));
+    assert_eq!(evaluate("typeof (() => {})").as_deref(), Some("\"function\""));
+    assert_eq!(evaluate("typeof foo"), None);
+}
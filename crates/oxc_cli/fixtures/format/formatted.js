@@ -0,0 +1,5 @@
+const a = 1;
+
+function foo(b) {
+  return a + b;
+}
@@ -75,6 +75,28 @@ pub struct MiscOptions {
     pub threads: Option<usize>,
 }
 
+/// Miscellaneous Lint Options
+#[derive(Debug, Clone, Bpaf)]
+pub struct LintMiscOptions {
+    /// Print the configuration resolved for the file at PATH, with the enabled rules and their
+    /// severities and options
+    #[bpaf(argument("PATH"), hide_usage)]
+    pub print_config: Option<PathBuf>,
+
+    /// Print the time spent in the slowest rules and files
+    #[bpaf(switch, hide_usage)]
+    pub timing: bool,
+
+    /// Write a starter configuration for the project in PATH or the current directory, with the
+    /// plugins of its frameworks and test runner
+    #[bpaf(switch, hide_usage)]
+    pub init: bool,
+
+    /// Exit with an error when one of the paths does not exist or matches no file to lint
+    #[bpaf(switch, hide_usage)]
+    pub error_on_unmatched_pattern: bool,
+}
+
 /// Enable Plugins
 #[derive(Debug, Clone, Bpaf)]
 pub struct EnablePlugins {
@@ -115,6 +137,9 @@ pub struct LintOptions {
     #[bpaf(external)]
     pub misc_options: MiscOptions,
 
+    #[bpaf(external)]
+    pub lint_misc_options: LintMiscOptions,
+
     #[bpaf(external)]
     pub codeowner_options: CodeownerOptions,
 
@@ -171,6 +196,11 @@ impl LintFilter {
 
 #[derive(Debug, Clone, Bpaf)]
 pub struct FormatOptions {
+    /// Check that the files are formatted instead of writing them,
+    /// the unformatted files are listed with a diff
+    #[bpaf(switch)]
+    pub check: bool,
+
     #[bpaf(external)]
     pub misc_options: MiscOptions,
 
@@ -200,6 +230,15 @@ pub struct FixOptions {
     /// Fix as many issues as possible. Only unfixed issues are reported in the output
     #[bpaf(switch)]
     pub fix: bool,
+
+    /// Compute the fixes without writing them, and print them as unified diffs
+    #[bpaf(switch)]
+    pub fix_dry_run: bool,
+
+    /// Write the fixed files to DIR at their paths relative to the working directory, instead of
+    /// fixing them in place
+    #[bpaf(argument("DIR"))]
+    pub fix_to: Option<PathBuf>,
 }
 
 const NO_IGNORE_HELP: &[(&str, Style)] = &[
@@ -227,6 +266,10 @@ pub struct IgnoreOptions {
     ///
     #[bpaf(switch, hide_usage, help(NO_IGNORE_HELP))]
     pub no_ignore: bool,
+
+    /// Disables excluding of files from .gitignore files
+    #[bpaf(switch, hide_usage)]
+    pub no_gitignore: bool,
 }
 
 /// Handle Warnings
@@ -275,6 +318,50 @@ mod misc_options {
     }
 }
 
+#[cfg(test)]
+mod lint_misc_options {
+    use super::{lint_command, LintMiscOptions};
+    use std::path::PathBuf;
+
+    fn get_lint_misc_options(arg: &str) -> LintMiscOptions {
+        let args = arg.split(' ').map(std::string::ToString::to_string).collect::<Vec<_>>();
+        lint_command().run_inner(args.as_slice()).unwrap().lint_options.lint_misc_options
+    }
+
+    #[test]
+    fn default() {
+        let options = get_lint_misc_options(".");
+        assert!(options.print_config.is_none());
+        assert!(!options.timing);
+        assert!(!options.init);
+        assert!(!options.error_on_unmatched_pattern);
+    }
+
+    #[test]
+    fn print_config() {
+        let options = get_lint_misc_options("--print-config src/index.js");
+        assert_eq!(options.print_config, Some(PathBuf::from("src/index.js")));
+    }
+
+    #[test]
+    fn timing() {
+        let options = get_lint_misc_options("--timing .");
+        assert!(options.timing);
+    }
+
+    #[test]
+    fn init() {
+        let options = get_lint_misc_options("--init");
+        assert!(options.init);
+    }
+
+    #[test]
+    fn error_on_unmatched_pattern() {
+        let options = get_lint_misc_options("--error-on-unmatched-pattern .");
+        assert!(options.error_on_unmatched_pattern);
+    }
+}
+
 #[cfg(test)]
 mod warning_options {
     use super::{lint_command, WarningOptions};
@@ -337,6 +424,20 @@ mod lint_options {
         assert!(options.fix_options.fix);
     }
 
+    #[test]
+    fn fix_dry_run() {
+        let options = get_lint_options("--fix-dry-run test.js");
+        assert!(!options.fix_options.fix);
+        assert!(options.fix_options.fix_dry_run);
+        assert_eq!(options.fix_options.fix_to, None);
+    }
+
+    #[test]
+    fn fix_to() {
+        let options = get_lint_options("--fix-to out test.js");
+        assert_eq!(options.fix_options.fix_to, Some(PathBuf::from("out")));
+    }
+
     #[test]
     fn filter() {
         let options =
@@ -383,6 +484,13 @@ mod ignore_options {
         assert!(options.no_ignore);
     }
 
+    #[test]
+    fn no_gitignore() {
+        let options = get_ignore_options("--no-gitignore foo.js");
+        assert!(options.no_gitignore);
+        assert!(!options.no_ignore);
+    }
+
     #[test]
     fn single_ignore_pattern() {
         let options = get_ignore_options("--ignore-pattern ./test foo.js");
@@ -395,3 +503,28 @@ mod ignore_options {
         assert_eq!(options.ignore_pattern, vec![String::from("./test"), String::from("bar.js")]);
     }
 }
+
+#[cfg(test)]
+mod format_options {
+    use super::{format_command, FormatOptions};
+    use std::path::PathBuf;
+
+    fn get_format_options(arg: &str) -> FormatOptions {
+        let args = arg.split(' ').map(std::string::ToString::to_string).collect::<Vec<_>>();
+        format_command().run_inner(args.as_slice()).unwrap().format_options
+    }
+
+    #[test]
+    fn default() {
+        let options = get_format_options(".");
+        assert_eq!(options.paths, vec![PathBuf::from(".")]);
+        assert!(!options.check);
+    }
+
+    #[test]
+    fn check() {
+        let options = get_format_options("--check --ignore-pattern ./test src");
+        assert!(options.check);
+        assert_eq!(options.ignore_options.ignore_pattern, vec![String::from("./test")]);
+    }
+}
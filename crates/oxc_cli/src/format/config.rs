@@ -0,0 +1,468 @@
+//! Formatter configuration
+//!
+//! The options of a file are resolved like Prettier does: the `.editorconfig` files from the
+//! one with `root = true` down to the directory of the file are applied first, then the nearest
+//! `.prettierrc*` file (or the `prettier` key of a `package.json`) overrides them.
+//!
+//! `overrides` and JavaScript configuration files are not supported, the `overrides` of the JSON,
+//! YAML and TOML files are ignored.
+
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+};
+
+use glob::{MatchOptions, Pattern};
+use oxc_prettier::{ArrowParens, EndOfLine, PrettierOptions, QuoteProps, TrailingComma};
+use serde_json::{Map, Value};
+
+/// The names of the Prettier configuration files, in the order of their priority
+const PRETTIERRC_FILES: &[&str] = &[
+    "package.json",
+    ".prettierrc",
+    ".prettierrc.json",
+    ".prettierrc.yaml",
+    ".prettierrc.yml",
+    ".prettierrc.toml",
+];
+
+/// The configuration files of the directories of the formatted files
+#[derive(Debug, Default)]
+pub struct FormatConfigs {
+    directories: HashMap<PathBuf, DirectoryConfig>,
+}
+
+#[derive(Debug, Default)]
+struct DirectoryConfig {
+    prettierrc: Option<PrettierConfig>,
+    editorconfig: Option<EditorConfig>,
+}
+
+/// The options which are set by a configuration file
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct PrettierConfig {
+    print_width: Option<usize>,
+    tab_width: Option<usize>,
+    use_tabs: Option<bool>,
+    end_of_line: Option<EndOfLine>,
+    semi: Option<bool>,
+    single_quote: Option<bool>,
+    quote_props: Option<QuoteProps>,
+    jsx_single_quote: Option<bool>,
+    trailing_comma: Option<TrailingComma>,
+    bracket_spacing: Option<bool>,
+    bracket_same_line: Option<bool>,
+    arrow_parens: Option<ArrowParens>,
+}
+
+#[derive(Debug, Default)]
+struct EditorConfig {
+    root: bool,
+    /// The patterns of each section, relative to the directory of the `.editorconfig` file
+    sections: Vec<(Vec<Pattern>, PrettierConfig)>,
+}
+
+impl FormatConfigs {
+    /// Reads the configuration files of the directories of `paths` and their ancestors.
+    ///
+    /// # Errors
+    ///
+    /// * A configuration file cannot be read or parsed
+    pub fn load(paths: &[Box<Path>]) -> Result<Self, String> {
+        let mut configs = Self::default();
+        for path in paths {
+            for dir in absolute_path(path).ancestors().skip(1) {
+                if configs.directories.contains_key(dir) {
+                    // The ancestors have been read too
+                    break;
+                }
+                let config = DirectoryConfig::load(dir)?;
+                configs.directories.insert(dir.to_path_buf(), config);
+            }
+        }
+        Ok(configs)
+    }
+
+    pub fn options(&self, path: &Path) -> PrettierOptions {
+        let path = absolute_path(path);
+        let mut options = PrettierOptions::default();
+
+        let mut editorconfigs = vec![];
+        for dir in path.ancestors().skip(1) {
+            let Some(editorconfig) = self.editorconfig(dir) else { continue };
+            editorconfigs.push((dir, editorconfig));
+            if editorconfig.root {
+                break;
+            }
+        }
+        for (dir, editorconfig) in editorconfigs.into_iter().rev() {
+            let relative_path = path.strip_prefix(dir).unwrap_or(&path);
+            for (patterns, config) in &editorconfig.sections {
+                let options_for_match =
+                    MatchOptions { require_literal_separator: true, ..MatchOptions::default() };
+                if patterns.iter().any(|p| p.matches_path_with(relative_path, options_for_match)) {
+                    config.apply(&mut options);
+                }
+            }
+        }
+
+        if let Some(prettierrc) = path.ancestors().skip(1).find_map(|dir| self.prettierrc(dir)) {
+            prettierrc.apply(&mut options);
+        }
+
+        options
+    }
+
+    fn editorconfig(&self, dir: &Path) -> Option<&EditorConfig> {
+        self.directories.get(dir)?.editorconfig.as_ref()
+    }
+
+    fn prettierrc(&self, dir: &Path) -> Option<&PrettierConfig> {
+        self.directories.get(dir)?.prettierrc.as_ref()
+    }
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path))
+}
+
+impl DirectoryConfig {
+    fn load(dir: &Path) -> Result<Self, String> {
+        let mut prettierrc = None;
+        for name in PRETTIERRC_FILES {
+            let path = dir.join(name);
+            let Ok(source_text) = std::fs::read_to_string(&path) else { continue };
+            let config = PrettierConfig::parse(name, &source_text)
+                .map_err(|err| format!("Failed to parse {}: {err}", path.display()))?;
+            if config.is_some() {
+                prettierrc = config;
+                break;
+            }
+        }
+        let editorconfig = std::fs::read_to_string(dir.join(".editorconfig"))
+            .ok()
+            .map(|source_text| EditorConfig::parse(&source_text));
+        Ok(Self { prettierrc, editorconfig })
+    }
+}
+
+impl PrettierConfig {
+    /// `None` for a `package.json` without a `prettier` key
+    fn parse(file_name: &str, source_text: &str) -> Result<Option<Self>, String> {
+        let value = match file_name {
+            "package.json" => {
+                let value =
+                    serde_json::from_str::<Value>(source_text).map_err(|e| e.to_string())?;
+                let Some(value) = value.get("prettier") else { return Ok(None) };
+                value.clone()
+            }
+            ".prettierrc.json" => {
+                serde_json::from_str::<Value>(source_text).map_err(|e| e.to_string())?
+            }
+            ".prettierrc.toml" => parse_toml(source_text)?,
+            // `.prettierrc` is either JSON or YAML
+            ".prettierrc" => match serde_json::from_str::<Value>(source_text) {
+                Ok(value) => value,
+                Err(_) => parse_yaml(source_text)?,
+            },
+            _ => parse_yaml(source_text)?,
+        };
+        let Some(object) = value.as_object() else {
+            return Err("the configuration is not an object".to_string());
+        };
+        Self::from_object(object).map(Some)
+    }
+
+    fn from_object(object: &Map<String, Value>) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (key, value) in object {
+            match key.as_str() {
+                "printWidth" => config.print_width = Some(number(key, value)?),
+                "tabWidth" => config.tab_width = Some(number(key, value)?),
+                "useTabs" => config.use_tabs = Some(boolean(key, value)?),
+                "endOfLine" => {
+                    config.end_of_line = Some(choice(key, value, &["lf", "crlf", "cr", "auto"])?);
+                }
+                "semi" => config.semi = Some(boolean(key, value)?),
+                "singleQuote" => config.single_quote = Some(boolean(key, value)?),
+                "quoteProps" => {
+                    let value =
+                        choice::<String>(key, value, &["as-needed", "consistent", "preserve"])?;
+                    config.quote_props = value.replace('-', "_").parse().ok();
+                }
+                "jsxSingleQuote" => config.jsx_single_quote = Some(boolean(key, value)?),
+                "trailingComma" => {
+                    config.trailing_comma = Some(choice(key, value, &["all", "es5", "none"])?);
+                }
+                "bracketSpacing" => config.bracket_spacing = Some(boolean(key, value)?),
+                "bracketSameLine" => config.bracket_same_line = Some(boolean(key, value)?),
+                "arrowParens" => {
+                    config.arrow_parens = Some(choice(key, value, &["always", "avoid"])?);
+                }
+                // The options of the other languages and the plugins
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+
+    fn apply(&self, options: &mut PrettierOptions) {
+        macro_rules! apply {
+            ($($field:ident),*) => {
+                $(
+                    if let Some(value) = self.$field {
+                        options.$field = value;
+                    }
+                )*
+            };
+        }
+        apply!(
+            print_width,
+            tab_width,
+            use_tabs,
+            end_of_line,
+            semi,
+            single_quote,
+            quote_props,
+            jsx_single_quote,
+            trailing_comma,
+            bracket_spacing,
+            bracket_same_line,
+            arrow_parens
+        );
+    }
+}
+
+fn number(key: &str, value: &Value) -> Result<usize, String> {
+    value
+        .as_u64()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| format!("`{key}` should be a number, but got {value}"))
+}
+
+fn boolean(key: &str, value: &Value) -> Result<bool, String> {
+    value.as_bool().ok_or_else(|| format!("`{key}` should be a boolean, but got {value}"))
+}
+
+fn choice<T: std::str::FromStr>(key: &str, value: &Value, choices: &[&str]) -> Result<T, String> {
+    value
+        .as_str()
+        .filter(|s| choices.contains(s))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("`{key}` should be one of {choices:?}, but got {value}"))
+}
+
+/// An empty YAML file is an empty configuration
+fn parse_yaml(source_text: &str) -> Result<Value, String> {
+    match serde_yaml::from_str::<Value>(source_text).map_err(|e| e.to_string())? {
+        Value::Null => Ok(Value::Object(Map::new())),
+        value => Ok(value),
+    }
+}
+
+fn parse_toml(source_text: &str) -> Result<Value, String> {
+    let document = source_text.parse::<toml_edit::Document>().map_err(|e| e.to_string())?;
+    Ok(toml_table(document.as_table()))
+}
+
+fn toml_table(table: &toml_edit::Table) -> Value {
+    Value::Object(table.iter().map(|(key, item)| (key.to_string(), toml_item(item))).collect())
+}
+
+fn toml_item(item: &toml_edit::Item) -> Value {
+    match item {
+        toml_edit::Item::None => Value::Null,
+        toml_edit::Item::Value(value) => toml_value(value),
+        toml_edit::Item::Table(table) => toml_table(table),
+        toml_edit::Item::ArrayOfTables(tables) => {
+            Value::Array(tables.iter().map(toml_table).collect())
+        }
+    }
+}
+
+fn toml_value(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(n) => Value::from(*n.value()),
+        toml_edit::Value::Float(n) => Value::from(*n.value()),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(datetime) => Value::String(datetime.value().to_string()),
+        toml_edit::Value::Array(array) => Value::Array(array.iter().map(toml_value).collect()),
+        toml_edit::Value::InlineTable(table) => Value::Object(
+            table.iter().map(|(key, value)| (key.to_string(), toml_value(value))).collect(),
+        ),
+    }
+}
+
+impl EditorConfig {
+    /// <https://spec.editorconfig.org>, the unknown properties and values are ignored
+    fn parse(source_text: &str) -> Self {
+        let mut editorconfig = Self::default();
+        let mut section: Option<(Vec<Pattern>, PrettierConfig)> = None;
+        for line in source_text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(glob) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                editorconfig.sections.extend(section.take());
+                section = Some((section_patterns(glob), PrettierConfig::default()));
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else { continue };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_lowercase();
+            let Some((_, config)) = section.as_mut() else {
+                // The preamble only has the `root` property
+                if key == "root" {
+                    editorconfig.root = value == "true";
+                }
+                continue;
+            };
+            match key.as_str() {
+                "indent_style" => config.use_tabs = Some(value == "tab"),
+                "indent_size" if value != "tab" => config.tab_width = value.parse().ok(),
+                "tab_width" => config.tab_width = value.parse().ok(),
+                "max_line_length" => config.print_width = value.parse().ok(),
+                "end_of_line" => config.end_of_line = value.parse().ok(),
+                _ => {}
+            }
+        }
+        editorconfig.sections.extend(section);
+        editorconfig
+    }
+}
+
+/// The glob of a section matches the file names in any directory unless it has a `/`,
+/// `{a,b}` is expanded to the patterns of each alternative.
+fn section_patterns(glob: &str) -> Vec<Pattern> {
+    let glob = if glob.contains('/') {
+        glob.trim_start_matches('/').to_string()
+    } else {
+        format!("**/{glob}")
+    };
+    expand_braces(&glob).iter().filter_map(|glob| Pattern::new(glob).ok()).collect()
+}
+
+fn expand_braces(glob: &str) -> Vec<String> {
+    let Some(open) = glob.find('{') else { return vec![glob.to_string()] };
+    let Some(close) = glob[open..].find('}').map(|i| open + i) else {
+        return vec![glob.to_string()];
+    };
+    let (prefix, suffix) = (&glob[..open], &glob[close + 1..]);
+    glob[open + 1..close]
+        .split(',')
+        .flat_map(|alternative| expand_braces(&format!("{prefix}{alternative}{suffix}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use oxc_prettier::{EndOfLine, QuoteProps, TrailingComma};
+
+    use super::{expand_braces, EditorConfig, FormatConfigs, PrettierConfig};
+
+    #[test]
+    fn prettierrc() {
+        let json = r#"{ "printWidth": 100, "singleQuote": true, "trailingComma": "es5", "quoteProps": "consistent", "overrides": [] }"#;
+        let yaml = "printWidth: 100 # comment\nsingleQuote: true\ntrailingComma: 'es5'\nquoteProps: consistent";
+        let toml = "printWidth = 100\nsingleQuote = true\ntrailingComma = \"es5\"\nquoteProps = \"consistent\"";
+        let expected = PrettierConfig {
+            print_width: Some(100),
+            single_quote: Some(true),
+            trailing_comma: Some(TrailingComma::ES5),
+            quote_props: Some(QuoteProps::Consistent),
+            ..PrettierConfig::default()
+        };
+        assert_eq!(PrettierConfig::parse(".prettierrc.json", json), Ok(Some(expected.clone())));
+        assert_eq!(PrettierConfig::parse(".prettierrc", json), Ok(Some(expected.clone())));
+        assert_eq!(PrettierConfig::parse(".prettierrc", yaml), Ok(Some(expected.clone())));
+        assert_eq!(PrettierConfig::parse(".prettierrc.toml", toml), Ok(Some(expected)));
+        assert_eq!(PrettierConfig::parse("package.json", r#"{ "name": "a" }"#), Ok(None));
+        assert_eq!(
+            PrettierConfig::parse(".prettierrc.yaml", ""),
+            Ok(Some(PrettierConfig::default()))
+        );
+        assert_eq!(
+            PrettierConfig::parse(".prettierrc.json", r#"{ "trailingComma": "some" }"#),
+            Err(r#"`trailingComma` should be one of ["all", "es5", "none"], but got "some""#
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn overrides() {
+        let json = r#"{ "semi": false, "quoteProps": "preserve", "overrides": [{ "files": "*.ts", "options": { "semi": true } }] }"#;
+        let yaml = "semi: false\nquoteProps: \"preserve\" # comment\noverrides:\n  - files: \"#*.ts\"\n    options:\n      semi: true\n";
+        let toml = "semi = false\nquoteProps = \"preserve\" # comment\n\n[[overrides]]\nfiles = \"#*.ts\"\n\n[overrides.options]\nsemi = true\n";
+        let expected = PrettierConfig {
+            semi: Some(false),
+            quote_props: Some(QuoteProps::Preserve),
+            ..PrettierConfig::default()
+        };
+        assert_eq!(PrettierConfig::parse(".prettierrc.json", json), Ok(Some(expected.clone())));
+        assert_eq!(PrettierConfig::parse(".prettierrc.yaml", yaml), Ok(Some(expected.clone())));
+        assert_eq!(PrettierConfig::parse(".prettierrc", yaml), Ok(Some(expected.clone())));
+        assert_eq!(PrettierConfig::parse(".prettierrc.toml", toml), Ok(Some(expected)));
+
+        // `#` in a quoted value is not a comment
+        let yaml = "trailingComma: \"es5#\"";
+        assert_eq!(
+            PrettierConfig::parse(".prettierrc.yaml", yaml),
+            Err(r#"`trailingComma` should be one of ["all", "es5", "none"], but got "es5#""#
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn editorconfig() {
+        let editorconfig = EditorConfig::parse(
+            "root = true\n\n[*]\nindent_style = space\nindent_size = 4\nend_of_line = crlf\n\n[*.{js,ts}]\nmax_line_length = 120\n\n[lib/**.js]\nindent_style = tab\n",
+        );
+        assert!(editorconfig.root);
+        assert_eq!(editorconfig.sections.len(), 3);
+        assert_eq!(editorconfig.sections[0].1.tab_width, Some(4));
+        assert!(matches!(editorconfig.sections[0].1.end_of_line, Some(EndOfLine::Crlf)));
+        assert_eq!(editorconfig.sections[1].0.len(), 2);
+        assert_eq!(editorconfig.sections[1].1.print_width, Some(120));
+        assert_eq!(editorconfig.sections[2].1.use_tabs, Some(true));
+    }
+
+    #[test]
+    fn braces() {
+        assert_eq!(expand_braces("*.js"), vec!["*.js"]);
+        assert_eq!(expand_braces("*.{js,jsx}"), vec!["*.js", "*.jsx"]);
+        assert_eq!(expand_braces("{a,b}/*.{c,d}"), vec!["a/*.c", "a/*.d", "b/*.c", "b/*.d"]);
+    }
+
+    #[test]
+    fn options() {
+        let fixture = env::current_dir().unwrap().join("fixtures/format_config");
+        let paths = [
+            fixture.join("editorconfig.js").into_boxed_path(),
+            fixture.join("nested/prettierrc.ts").into_boxed_path(),
+        ];
+        let configs = FormatConfigs::load(&paths).unwrap();
+
+        // `.editorconfig` and `.prettierrc`
+        let options = configs.options(&paths[0]);
+        assert_eq!(options.tab_width, 4);
+        assert_eq!(options.print_width, 100);
+        assert!(options.single_quote);
+        assert!(!options.use_tabs);
+
+        // `.editorconfig` and the nearer `nested/.prettierrc.json` which replaces `.prettierrc`
+        let options = configs.options(&paths[1]);
+        assert_eq!(options.tab_width, 4);
+        assert_eq!(options.print_width, 120);
+        assert!(!options.single_quote);
+        assert!(options.use_tabs);
+        assert!(options.trailing_comma.is_none());
+    }
+}
@@ -1,15 +1,20 @@
-use std::path::Path;
+mod config;
 
+use std::{env, path::Path};
+
+use miette::NamedSource;
 use oxc_allocator::Allocator;
 use oxc_parser::Parser;
 use oxc_prettier::{Prettier, PrettierOptions};
 use oxc_span::SourceType;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use similar::TextDiff;
 
+use self::config::FormatConfigs;
 use crate::{
     command::FormatOptions,
     result::{CliRunResult, FormatResult},
-    walk::Walk,
+    walk::{IgnoreMatcher, Walk},
     Runner,
 };
 
@@ -17,6 +22,18 @@ pub struct FormatRunner {
     options: FormatOptions,
 }
 
+/// The outcome of formatting a single file
+enum FormatOutcome {
+    Formatted,
+    /// The file was not formatted, it has been rewritten or, in `--check` mode, reported with a
+    /// diff
+    Unformatted {
+        diff: Option<String>,
+    },
+    /// The file could not be read, parsed or written
+    Error,
+}
+
 impl Runner for FormatRunner {
     type Options = FormatOptions;
 
@@ -25,34 +42,171 @@ impl Runner for FormatRunner {
     }
 
     fn run(self) -> CliRunResult {
-        let FormatOptions { paths, ignore_options, .. } = &self.options;
+        let FormatOptions { paths, ignore_options, check, .. } = self.options;
+
+        let mut paths = paths;
+        let provided_path_count = paths.len();
+        let now = std::time::Instant::now();
+
+        let matcher = match IgnoreMatcher::new(&ignore_options, None) {
+            Ok(matcher) => matcher,
+            Err(message) => return CliRunResult::InvalidOptions { message },
+        };
+        Walk::retain_unignored_paths(&mut paths, &matcher);
 
         if paths.is_empty() {
-            return CliRunResult::InvalidOptions { message: "No paths are provided.".to_string() };
+            // If explicit paths were provided, but all have been
+            // filtered, return early.
+            if provided_path_count > 0 {
+                return CliRunResult::FormatResult(FormatResult {
+                    duration: now.elapsed(),
+                    check,
+                    ..FormatResult::default()
+                });
+            }
+
+            if let Ok(cwd) = env::current_dir() {
+                paths.push(cwd);
+            } else {
+                return CliRunResult::InvalidOptions {
+                    message: "Failed to get current working directory.".to_string(),
+                };
+            }
         }
 
-        let now = std::time::Instant::now();
+        let mut paths = Walk::new(&paths, &matcher).paths();
+        paths.sort_unstable();
 
-        let paths = Walk::new(paths, ignore_options).paths();
+        let configs = match FormatConfigs::load(&paths) {
+            Ok(configs) => configs,
+            Err(message) => return CliRunResult::InvalidOptions { message },
+        };
 
-        paths.par_iter().for_each(|path| {
-            Self::format(path);
-        });
+        let outcomes = paths
+            .par_iter()
+            .map(|path| Self::format(path, configs.options(path), check))
+            .collect::<Vec<_>>();
+
+        let mut number_of_unformatted_files = 0;
+        let mut number_of_errors = 0;
+        for outcome in outcomes {
+            match outcome {
+                FormatOutcome::Formatted => {}
+                FormatOutcome::Unformatted { diff } => {
+                    number_of_unformatted_files += 1;
+                    if let Some(diff) = diff {
+                        print!("{diff}");
+                    }
+                }
+                FormatOutcome::Error => number_of_errors += 1,
+            }
+        }
 
         CliRunResult::FormatResult(FormatResult {
             duration: now.elapsed(),
             number_of_files: paths.len(),
+            number_of_unformatted_files,
+            number_of_errors,
+            check,
         })
     }
 }
 
 impl FormatRunner {
-    fn format(path: &Path) {
-        let source_text = std::fs::read_to_string(path).unwrap();
+    fn format(path: &Path, options: PrettierOptions, check: bool) -> FormatOutcome {
+        let Ok(source_text) = std::fs::read_to_string(path) else {
+            eprintln!("Failed to read {}", path.display());
+            return FormatOutcome::Error;
+        };
         let allocator = Allocator::default();
         let source_type = SourceType::from_path(path).unwrap();
         let ret = Parser::new(&allocator, &source_text, source_type).preserve_parens(false).parse();
-        let _ = Prettier::new(&allocator, &source_text, ret.trivias, PrettierOptions::default())
-            .build(&ret.program);
+        if !ret.errors.is_empty() {
+            for error in ret.errors {
+                let source = NamedSource::new(path.to_string_lossy(), source_text.clone());
+                eprintln!("{:?}", error.with_source_code(source));
+            }
+            return FormatOutcome::Error;
+        }
+        let formatted =
+            Prettier::new(&allocator, &source_text, ret.trivias, options).build(&ret.program);
+
+        if formatted == source_text {
+            return FormatOutcome::Formatted;
+        }
+
+        if check {
+            let name = path.to_string_lossy();
+            let diff = TextDiff::from_lines(source_text.as_str(), formatted.as_str())
+                .unified_diff()
+                .header(&name, &name)
+                .to_string();
+            return FormatOutcome::Unformatted { diff: Some(diff) };
+        }
+
+        if std::fs::write(path, formatted).is_err() {
+            eprintln!("Failed to write {}", path.display());
+            return FormatOutcome::Error;
+        }
+        FormatOutcome::Unformatted { diff: None }
+    }
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod test {
+    use super::FormatRunner;
+    use crate::{format_command, result::FormatResult, CliRunResult, Runner};
+
+    fn test(args: &[&str]) -> FormatResult {
+        let mut new_args = vec!["--check"];
+        new_args.extend(args);
+        let options = format_command().run_inner(new_args.as_slice()).unwrap().format_options;
+        match FormatRunner::new(options).run() {
+            CliRunResult::FormatResult(format_result) => format_result,
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn dir() {
+        let args = &["fixtures/format"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_unformatted_files, 1);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
+    #[test]
+    fn formatted_file() {
+        let args = &["fixtures/format/formatted.js"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_unformatted_files, 0);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
+    #[test]
+    fn ignore_pattern() {
+        let args = &["--ignore-pattern", "**/unformatted.js", "fixtures/format"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_unformatted_files, 0);
+
+        let args = &["--ignore-pattern", "**/*.js", "fixtures/format"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 0);
+    }
+
+    #[test]
+    fn ignore_path() {
+        let args = &[
+            "--ignore-path",
+            "fixtures/format/.customignore",
+            "fixtures/format/formatted.js",
+            "fixtures/format/unformatted.js",
+        ];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_unformatted_files, 0);
     }
 }
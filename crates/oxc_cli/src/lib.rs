@@ -10,6 +10,6 @@ pub use crate::{
     command::*,
     format::FormatRunner,
     lint::LintRunner,
-    result::{CliRunResult, LintResult},
+    result::{exit_code, CliRunResult, LintResult},
     runner::Runner,
 };
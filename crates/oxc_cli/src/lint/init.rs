@@ -0,0 +1,176 @@
+use std::{fs, path::Path};
+
+use serde_json::{json, Map, Value};
+
+use crate::CliRunResult;
+
+/// The name of the config file written by `--init`
+pub const CONFIG_FILE_NAME: &str = ".oxlintrc.json";
+
+/// The build output directories which are ignored when they exist
+const OUTPUT_DIRS: [&str; 5] = ["dist", "build", "out", "coverage", ".next"];
+
+/// What the starter config is tailored to, found in the `package.json` and the files of the
+/// project
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Project {
+    pub typescript: bool,
+    pub react: bool,
+    pub nextjs: bool,
+    /// Jest or Vitest, whose test APIs are checked by the Jest plugin
+    pub test_runner: Option<&'static str>,
+    pub output_dirs: Vec<&'static str>,
+}
+
+impl Project {
+    pub fn inspect(dir: &Path) -> Self {
+        let package_json = fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+            .unwrap_or_default();
+        let has_dependency = |name: &str| {
+            ["dependencies", "devDependencies", "peerDependencies"]
+                .iter()
+                .filter_map(|key| package_json.get(key)?.as_object())
+                .any(|dependencies| dependencies.contains_key(name))
+        };
+        Self {
+            typescript: has_dependency("typescript") || dir.join("tsconfig.json").is_file(),
+            react: has_dependency("react") || has_dependency("preact"),
+            nextjs: has_dependency("next"),
+            test_runner: ["vitest", "jest"].into_iter().find(|name| has_dependency(name)),
+            output_dirs: OUTPUT_DIRS.into_iter().filter(|name| dir.join(name).is_dir()).collect(),
+        }
+    }
+
+    /// The config enabling the plugins of the frameworks and the test runner of the project
+    pub fn starter_config(&self) -> Value {
+        let mut plugins = vec![];
+        if self.test_runner.is_some() {
+            plugins.push("jest");
+        }
+        if self.react || self.nextjs {
+            plugins.push("jsx-a11y");
+        }
+        if self.nextjs {
+            plugins.push("nextjs");
+        }
+
+        let mut rules = Map::new();
+        if self.typescript {
+            rules.insert("@typescript-eslint/ban-ts-comment".into(), json!("error"));
+            rules.insert("@typescript-eslint/no-explicit-any".into(), json!("warn"));
+        }
+
+        let ignore_patterns =
+            self.output_dirs.iter().map(|dir| format!("{dir}/")).collect::<Vec<_>>();
+        json!({ "plugins": plugins, "rules": rules, "ignorePatterns": ignore_patterns })
+    }
+
+    /// The detected frameworks, e.g. `TypeScript, React and Jest`
+    fn description(&self) -> String {
+        let mut names = vec![];
+        if self.typescript {
+            names.push("TypeScript");
+        }
+        if self.nextjs {
+            names.push("Next.js");
+        } else if self.react {
+            names.push("React");
+        }
+        match self.test_runner {
+            Some("vitest") => names.push("Vitest"),
+            Some(_) => names.push("Jest"),
+            None => {}
+        }
+        match names.split_last() {
+            None => "JavaScript".to_string(),
+            Some((last, [])) => (*last).to_string(),
+            Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        }
+    }
+}
+
+/// Writes the starter config of the project in `dir`, an existing config is never overwritten
+pub fn init(dir: &Path) -> CliRunResult {
+    let config_path = dir.join(CONFIG_FILE_NAME);
+    if config_path.exists() {
+        return CliRunResult::InvalidOptions {
+            message: format!("{} already exists.", config_path.display()),
+        };
+    }
+
+    let project = Project::inspect(dir);
+    let config = serde_json::to_string_pretty(&project.starter_config()).unwrap();
+    if let Err(err) = fs::write(&config_path, config + "\n") {
+        return CliRunResult::InvalidOptions {
+            message: format!("Failed to write {}: {err}", config_path.display()),
+        };
+    }
+
+    println!("Created {} for a {} project.", config_path.display(), project.description());
+    println!("It is used for the files in {}.", dir.display());
+    CliRunResult::None
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod test {
+    use std::{env, fs, path::Path};
+
+    use serde_json::json;
+
+    use super::{init, Project, CONFIG_FILE_NAME};
+    use crate::CliRunResult;
+
+    #[test]
+    fn inspect() {
+        let project = Project::inspect(Path::new("fixtures/init"));
+        assert_eq!(
+            project,
+            Project {
+                typescript: true,
+                react: true,
+                nextjs: false,
+                test_runner: Some("vitest"),
+                output_dirs: vec!["dist"],
+            }
+        );
+        assert_eq!(project.description(), "TypeScript, React and Vitest");
+        assert_eq!(Project::inspect(Path::new("fixtures/linter")), Project::default());
+    }
+
+    #[test]
+    fn starter_config() {
+        let config = Project::inspect(Path::new("fixtures/init")).starter_config();
+        assert_eq!(
+            config,
+            json!({
+                "plugins": ["jest", "jsx-a11y"],
+                "rules": {
+                    "@typescript-eslint/ban-ts-comment": "error",
+                    "@typescript-eslint/no-explicit-any": "warn",
+                },
+                "ignorePatterns": ["dist/"],
+            })
+        );
+        let config = Project { nextjs: true, ..Project::default() }.starter_config();
+        assert_eq!(config["plugins"], json!(["jsx-a11y", "nextjs"]));
+    }
+
+    #[test]
+    fn write_config() {
+        let dir = env::temp_dir().join("oxc_cli_init");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("package.json"), r#"{ "devDependencies": { "jest": "*" } }"#).unwrap();
+
+        assert!(matches!(init(&dir), CliRunResult::None));
+        let config = fs::read_to_string(dir.join(CONFIG_FILE_NAME)).unwrap();
+        let config = serde_json::from_str::<serde_json::Value>(&config).unwrap();
+        assert_eq!(config["plugins"], json!(["jest"]));
+
+        // The existing config is kept
+        assert!(matches!(init(&dir), CliRunResult::InvalidOptions { .. }));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
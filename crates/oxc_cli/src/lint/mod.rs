@@ -1,14 +1,29 @@
-use ignore::gitignore::Gitignore;
-use std::{env, io::BufWriter, path::Path, time::Instant, vec::Vec};
+mod init;
+mod timing;
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    env,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+    vec::Vec,
+};
 
 use oxc_diagnostics::{DiagnosticService, GraphicalReportHandler};
-use oxc_linter::{partial_loader::LINT_PARTIAL_LOADER_EXT, LintOptions, LintService, Linter};
+use oxc_linter::{
+    partial_loader::LINT_PARTIAL_LOADER_EXT, ConfigFinder, FixOutput, LintOptions, LintService,
+    Linter,
+};
 use oxc_span::VALID_EXTENSIONS;
+use serde_json::{json, Value};
+use similar::TextDiff;
 
 use crate::{
     codeowners,
-    command::LintOptions as CliLintOptions,
-    walk::{Extensions, Walk},
+    command::{IgnoreOptions, LintOptions as CliLintOptions},
+    walk::{Extensions, IgnoreMatcher, Walk},
     CliRunResult, CodeownerOptions, LintResult, Runner,
 };
 
@@ -30,6 +45,21 @@ impl Runner for LintRunner {
             return CliRunResult::None;
         }
 
+        if self.options.lint_misc_options.init {
+            let dir = match self.options.paths.first() {
+                Some(dir) => dir.clone(),
+                None => match env::current_dir() {
+                    Ok(cwd) => cwd,
+                    Err(_) => {
+                        return CliRunResult::InvalidOptions {
+                            message: "Failed to get current working directory.".to_string(),
+                        };
+                    }
+                },
+            };
+            return init::init(&dir);
+        }
+
         let CliLintOptions {
             paths,
             filter,
@@ -38,28 +68,94 @@ impl Runner for LintRunner {
             fix_options,
             codeowner_options,
             enable_plugins,
+            misc_options: _,
+            lint_misc_options,
             config,
-            ..
         } = self.options;
 
+        let fix_output = match (fix_options.fix_dry_run, fix_options.fix_to) {
+            (true, Some(_)) => {
+                return CliRunResult::InvalidOptions {
+                    message: "--fix-dry-run and --fix-to cannot be used together.".to_string(),
+                };
+            }
+            (true, None) => FixOutput::DryRun,
+            (false, Some(dir)) => {
+                if let Err(e) = std::fs::create_dir_all(&dir) {
+                    return CliRunResult::InvalidOptions {
+                        message: format!("Failed to create the --fix-to directory {dir:?}: {e}"),
+                    };
+                }
+                FixOutput::Directory(dir)
+            }
+            (false, None) => FixOutput::InPlace,
+        };
+        let fix = fix_options.fix || fix_output != FixOutput::InPlace;
+
+        let lint_options = LintOptions::default()
+            .with_filter(filter)
+            .with_fix(fix)
+            .with_fix_output(fix_output)
+            .with_timing(lint_misc_options.timing)
+            .with_import_plugin(enable_plugins.import_plugin)
+            .with_jest_plugin(enable_plugins.jest_plugin)
+            .with_jsx_a11y_plugin(enable_plugins.jsx_a11y_plugin)
+            .with_nextjs_plugin(enable_plugins.nextjs_plugin);
+
+        let Ok(cwd) = env::current_dir() else {
+            return CliRunResult::InvalidOptions {
+                message: "Failed to get current working directory.".to_string(),
+            };
+        };
+
+        // Without `--config`, the config files of the directories of the files are used, the
+        // ones of the working directory are also used to ignore the paths while walking
+        let mut config_finder = ConfigFinder::default();
+        let mut find_config_paths = |dir: &Path| {
+            config
+                .as_ref()
+                .map_or_else(|| config_finder.find(&cwd.join(dir)), |config| vec![config.clone()])
+        };
+
+        if let Some(path) = lint_misc_options.print_config {
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            let lint_options = lint_options.with_config_paths(find_config_paths(dir));
+            return match Self::load_linter(lint_options, &ignore_options) {
+                Ok((linter, matcher)) => Self::print_config(&linter, &matcher, &path),
+                Err(err) => err,
+            };
+        }
+
+        let root_config_paths = find_config_paths(Path::new(""));
+        let (linter, matcher) = match Self::load_linter(
+            lint_options.clone().with_config_paths(root_config_paths.clone()),
+            &ignore_options,
+        ) {
+            Ok(loaded) => loaded,
+            Err(err) => return err,
+        };
+
+        if lint_misc_options.error_on_unmatched_pattern {
+            let missing_paths =
+                paths.iter().filter(|path| !path.exists()).cloned().collect::<Vec<_>>();
+            if !missing_paths.is_empty() {
+                return CliRunResult::PathNotFound { paths: missing_paths };
+            }
+        }
+
+        let provided_paths = paths.clone();
         let mut paths = paths;
-        let provided_path_count = paths.len();
         let now = Instant::now();
 
-        // The ignore crate whitelists explicit paths, but priority
-        // should be given to the ignore file. Many users lint
-        // automatically and pass a list of changed files explicitly.
-        // To accommodate this, unless `--no-ignore` is passed,
-        // pre-filter the paths.
-        if !paths.is_empty() && !ignore_options.no_ignore {
-            let (ignore, _err) = Gitignore::new(&ignore_options.ignore_path);
-            paths.retain(|p| if p.is_dir() { true } else { !ignore.matched(p, false).is_ignore() });
-        }
+        Walk::retain_unignored_paths(&mut paths, &matcher);
 
         if paths.is_empty() {
             // If explicit paths were provided, but all have been
             // filtered, return early.
-            if provided_path_count > 0 {
+            if !provided_paths.is_empty() {
+                if lint_misc_options.error_on_unmatched_pattern {
+                    return CliRunResult::NoFilesFound { paths: provided_paths };
+                }
                 return CliRunResult::LintResult(LintResult {
                     duration: now.elapsed(),
                     deny_warnings: warning_options.deny_warnings,
@@ -82,40 +178,54 @@ impl Runner for LintRunner {
             .copied()
             .collect::<Vec<&'static str>>();
 
-        let paths =
-            Walk::new(&paths, &ignore_options).with_extensions(Extensions(extensions)).paths();
+        let paths = Walk::new(&paths, &matcher).with_extensions(Extensions(extensions)).paths();
+
+        if lint_misc_options.error_on_unmatched_pattern {
+            let unmatched_paths = provided_paths
+                .into_iter()
+                .filter(|provided| !paths.iter().any(|path| path.starts_with(provided)))
+                .collect::<Vec<_>>();
+            if !unmatched_paths.is_empty() {
+                return CliRunResult::NoFilesFound { paths: unmatched_paths };
+            }
+        }
 
         let paths = match Self::apply_codeowners_file(&codeowner_options, paths) {
             Ok(new_paths) => new_paths,
             Err(err) => return err,
         };
 
-        let number_of_files = paths.len();
-
-        let cwd = std::env::current_dir().unwrap().into_boxed_path();
-        let lint_options = LintOptions::default()
-            .with_filter(filter)
-            .with_config_path(config)
-            .with_fix(fix_options.fix)
-            .with_import_plugin(enable_plugins.import_plugin)
-            .with_jest_plugin(enable_plugins.jest_plugin)
-            .with_jsx_a11y_plugin(enable_plugins.jsx_a11y_plugin)
-            .with_nextjs_plugin(enable_plugins.nextjs_plugin);
-
-        let linter = match Linter::from_options(lint_options) {
-            Ok(lint_service) => lint_service,
-            Err(diagnostic) => {
-                let handler = GraphicalReportHandler::new();
-                let mut err = String::new();
-                handler.render_report(&mut err, diagnostic.as_ref()).unwrap();
-                eprintln!("{err}");
-                return CliRunResult::InvalidOptions {
-                    message: "Failed to parse configuration file.".to_string(),
-                };
+        // The files whose directories have other config files than the working directory
+        let mut linters = HashMap::<Vec<PathBuf>, (Arc<Linter>, IgnoreMatcher)>::new();
+        let mut file_linters = vec![];
+        let mut lint_paths = Vec::with_capacity(paths.len());
+        for path in paths {
+            let config_paths = find_config_paths(path.parent().unwrap_or_else(|| Path::new("")));
+            if config_paths == root_config_paths {
+                lint_paths.push(path);
+                continue;
             }
-        };
+            let (linter, matcher) = match linters.entry(config_paths) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let lint_options = lint_options.clone().with_config_paths(entry.key().clone());
+                    match Self::load_linter(lint_options, &ignore_options) {
+                        Ok((linter, matcher)) => entry.insert((Arc::new(linter), matcher)),
+                        Err(err) => return err,
+                    }
+                }
+            };
+            if !matcher.is_ignored(&path, false) {
+                file_linters.push((path.clone(), Arc::clone(linter)));
+                lint_paths.push(path);
+            }
+        }
+        let paths = lint_paths;
+
+        let number_of_files = paths.len();
 
-        let lint_service = LintService::new(cwd, &paths, linter);
+        let cwd = cwd.into_boxed_path();
+        let lint_service = LintService::with_file_linters(cwd, &paths, linter, file_linters);
 
         let diagnostic_service = DiagnosticService::default()
             .with_quiet(warning_options.quiet)
@@ -131,6 +241,31 @@ impl Runner for LintRunner {
         });
         diagnostic_service.run();
 
+        for fixed_file in lint_service.fixed_files() {
+            let name = fixed_file.path.to_string_lossy();
+            let diff = TextDiff::from_lines(&fixed_file.source_text, &fixed_file.fixed_text)
+                .unified_diff()
+                .header(&name, &name)
+                .to_string();
+            print!("{diff}");
+        }
+
+        if lint_misc_options.timing {
+            let rules = lint_service
+                .rule_timings()
+                .into_iter()
+                .map(|timing| (timing.name, timing.duration))
+                .collect::<Vec<_>>();
+            let files = lint_service
+                .file_timings()
+                .into_iter()
+                .map(|timing| (timing.path.to_string_lossy().to_string(), timing.duration))
+                .collect::<Vec<_>>();
+            println!();
+            println!("{}", timing::timing_table("Rule", &rules));
+            print!("{}", timing::timing_table("File", &files));
+        }
+
         CliRunResult::LintResult(LintResult {
             duration: now.elapsed(),
             number_of_rules: lint_service.linter().number_of_rules(),
@@ -144,6 +279,65 @@ impl Runner for LintRunner {
 }
 
 impl LintRunner {
+    /// The linter of the config files in `lint_options`, and the matcher of the paths ignored by
+    /// the options and the config files
+    fn load_linter(
+        lint_options: LintOptions,
+        ignore_options: &IgnoreOptions,
+    ) -> Result<(Linter, IgnoreMatcher), CliRunResult> {
+        // The `ignorePatterns` are relative to the directory of the first config file
+        let config_dir = lint_options
+            .config_paths
+            .first()
+            .and_then(|path| path.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let linter = match Linter::from_options(lint_options) {
+            Ok(linter) => linter,
+            Err(diagnostic) => {
+                let handler = GraphicalReportHandler::new();
+                let mut err = String::new();
+                handler.render_report(&mut err, diagnostic.as_ref()).unwrap();
+                eprintln!("{err}");
+                return Err(CliRunResult::InvalidOptions {
+                    message: "Failed to parse configuration file.".to_string(),
+                });
+            }
+        };
+        let matcher =
+            IgnoreMatcher::new(ignore_options, Some((&config_dir, linter.ignore_patterns())))
+                .map_err(|message| CliRunResult::InvalidOptions { message })?;
+        Ok((linter, matcher))
+    }
+
+    fn print_config(linter: &Linter, matcher: &IgnoreMatcher, path: &Path) -> CliRunResult {
+        if !path.is_file() {
+            return CliRunResult::InvalidOptions {
+                message: format!("The file {} does not exist.", path.display()),
+            };
+        }
+        let mut config = json!({
+            "filePath": path.to_string_lossy(),
+            "ignored": matcher.is_ignored(path, false),
+        });
+        if let (Value::Object(config), Value::Object(resolved)) =
+            (&mut config, linter.resolved_config())
+        {
+            config.extend(resolved);
+        }
+        let mut stdout = std::io::stdout().lock();
+        let written = serde_json::to_writer_pretty(&mut stdout, &config)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(stdout));
+        match written {
+            // The output is piped to a command which exits early, e.g. `head`
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => CliRunResult::InvalidOptions {
+                message: format!("Failed to print the configuration: {err}"),
+            },
+            _ => CliRunResult::None,
+        }
+    }
+
     fn apply_codeowners_file(
         options: &CodeownerOptions,
         paths: Vec<Box<Path>>,
@@ -192,6 +386,8 @@ impl LintRunner {
 
 #[cfg(all(test, not(target_os = "windows")))]
 mod test {
+    use std::path::PathBuf;
+
     use super::LintRunner;
     use crate::{lint_command, CliRunResult, LintResult, Runner};
 
@@ -242,6 +438,114 @@ mod test {
         assert_eq!(result.number_of_errors, 0);
     }
 
+    #[test]
+    fn fix_dry_run() {
+        let args = &["--fix-dry-run", "fixtures/linter/debugger.js"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_warnings, 0);
+        let source_text = std::fs::read_to_string("fixtures/linter/debugger.js").unwrap();
+        assert_eq!(source_text, "debugger;\n");
+    }
+
+    #[test]
+    fn fix_to() {
+        let dir = std::env::temp_dir().join("oxc_cli_fix_to");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir_arg = dir.to_string_lossy();
+        let args = &["--fix-to", &dir_arg, "fixtures/linter/debugger.js"];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 0);
+        let fixed_text = std::fs::read_to_string(dir.join("fixtures/linter/debugger.js")).unwrap();
+        assert_eq!(fixed_text, "\n");
+        let source_text = std::fs::read_to_string("fixtures/linter/debugger.js").unwrap();
+        assert_eq!(source_text, "debugger;\n");
+    }
+
+    #[test]
+    fn fix_to_invalid_directory() {
+        let args = &["--fix-to", "fixtures/linter/debugger.js/out", "fixtures/linter/debugger.js"];
+        let options = lint_command().run_inner(args.as_slice()).unwrap().lint_options;
+        assert!(matches!(LintRunner::new(options).run(), CliRunResult::InvalidOptions { .. }));
+    }
+
+    #[test]
+    fn fix_to_unwritable_file() {
+        // The directory of the fixed file cannot be created over this file
+        let dir = std::env::temp_dir().join("oxc_cli_fix_to_unwritable");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("fixtures"), "").unwrap();
+        let dir_arg = dir.to_string_lossy();
+        let args = &["--fix-to", &dir_arg, "fixtures/linter/debugger.js"];
+        let result = test(args);
+        assert_eq!(result.number_of_errors, 1);
+    }
+
+    #[test]
+    fn fix_dry_run_with_fix_to() {
+        let args = &["--fix-dry-run", "--fix-to", "out", "fixtures/linter/debugger.js"];
+        let options = lint_command().run_inner(args.as_slice()).unwrap().lint_options;
+        assert!(matches!(LintRunner::new(options).run(), CliRunResult::InvalidOptions { .. }));
+    }
+
+    #[test]
+    fn print_config() {
+        let args = &["--print-config", "fixtures/linter/debugger.js"];
+        let options = lint_command().run_inner(args.as_slice()).unwrap().lint_options;
+        assert!(matches!(LintRunner::new(options).run(), CliRunResult::None));
+
+        let args = &["--print-config", "fixtures/linter/missing.js"];
+        let options = lint_command().run_inner(args.as_slice()).unwrap().lint_options;
+        assert!(matches!(LintRunner::new(options).run(), CliRunResult::InvalidOptions { .. }));
+    }
+
+    #[test]
+    fn config_discovery() {
+        let args = &["fixtures/config_discovery"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_warnings, 1);
+
+        // `--config` is used for all of the files
+        let args = &["-c", "fixtures/config_discovery/.eslintrc.json", "fixtures/config_discovery"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_warnings, 0);
+    }
+
+    #[test]
+    fn error_on_unmatched_pattern() {
+        let run = |args: &[&str]| {
+            let mut new_args = vec!["--quiet", "--error-on-unmatched-pattern"];
+            new_args.extend(args);
+            let options = lint_command().run_inner(new_args.as_slice()).unwrap().lint_options;
+            LintRunner::new(options).run()
+        };
+        assert!(matches!(run(&["fixtures/linter"]), CliRunResult::LintResult(_)));
+        assert!(matches!(
+            run(&["fixtures/linter", "fixtures/missing"]),
+            CliRunResult::PathNotFound { paths } if paths == [PathBuf::from("fixtures/missing")]
+        ));
+        // A directory without files to lint
+        assert!(matches!(
+            run(&["fixtures/linter", "fixtures/init"]),
+            CliRunResult::NoFilesFound { paths } if paths == [PathBuf::from("fixtures/init")]
+        ));
+        assert!(matches!(
+            run(&["--ignore-pattern", "*.js", "fixtures/linter/debugger.js"]),
+            CliRunResult::NoFilesFound { .. }
+        ));
+    }
+
+    #[test]
+    fn timing() {
+        let args = &["--timing", "fixtures/linter"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_warnings, 2);
+    }
+
     #[test]
     fn wrong_extension() {
         let args = &["foo.asdf"];
@@ -287,6 +591,28 @@ mod test {
         assert_eq!(result.number_of_errors, 0);
     }
 
+    #[test]
+    fn config_ignore_patterns() {
+        let args = &["-c", "fixtures/ignore_patterns/eslintrc.json", "fixtures/ignore_patterns"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_warnings, 1);
+    }
+
+    #[test]
+    fn ignore_pattern_overrides_config_ignore_patterns() {
+        let args = &[
+            "-c",
+            "fixtures/ignore_patterns/eslintrc.json",
+            "--ignore-pattern",
+            "!ignored.js",
+            "fixtures/ignore_patterns",
+        ];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_warnings, 2);
+    }
+
     #[test]
     fn filter_allow_all() {
         let args = &["-A", "all", "fixtures/linter"];
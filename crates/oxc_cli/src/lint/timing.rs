@@ -0,0 +1,54 @@
+use std::{fmt::Write, time::Duration};
+
+/// The number of the slowest rules and files in the report
+const NUMBER_OF_ROWS: usize = 10;
+
+/// A table of the slowest `rows`, which are sorted by their durations, with their share of the
+/// total time, like the `TIMING=1` report of ESLint
+pub fn timing_table(title: &str, rows: &[(String, Duration)]) -> String {
+    let total = rows.iter().map(|(_, duration)| *duration).sum::<Duration>();
+    let rows = &rows[..rows.len().min(NUMBER_OF_ROWS)];
+    let width = rows.iter().map(|(name, _)| name.len()).chain([title.len()]).max().unwrap_or(0);
+
+    let mut table = String::new();
+    writeln!(table, "{title:<width$} | Time (ms) | Relative").unwrap();
+    writeln!(table, "{:-<width$}-|-----------|---------", "").unwrap();
+    for (name, duration) in rows {
+        let ms = duration.as_secs_f64() * 1000.0;
+        let relative = if total.is_zero() {
+            0.0
+        } else {
+            duration.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        writeln!(table, "{name:<width$} | {ms:>9.3} | {relative:>7.1}%").unwrap();
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::timing_table;
+
+    #[test]
+    fn table() {
+        let rows = vec![
+            ("eslint/no-debugger".to_string(), Duration::from_millis(3)),
+            ("eslint/no-eval".to_string(), Duration::from_millis(1)),
+        ];
+        let expected = "\
+Rule               | Time (ms) | Relative
+-------------------|-----------|---------
+eslint/no-debugger |     3.000 |    75.0%
+eslint/no-eval     |     1.000 |    25.0%
+";
+        assert_eq!(timing_table("Rule", &rows), expected);
+    }
+
+    #[test]
+    fn slowest_rows() {
+        let rows = (0..20).map(|i| (format!("file{i}.js"), Duration::ZERO)).collect::<Vec<_>>();
+        assert_eq!(timing_table("File", &rows).lines().count(), 12);
+    }
+}
@@ -7,11 +7,33 @@ use std::{
 #[derive(Debug)]
 pub enum CliRunResult {
     None,
-    InvalidOptions { message: String },
-    PathNotFound { paths: Vec<PathBuf> },
+    InvalidOptions {
+        message: String,
+    },
+    PathNotFound {
+        paths: Vec<PathBuf>,
+    },
+    /// The paths which are ignored or contain no file to be linted
+    NoFilesFound {
+        paths: Vec<PathBuf>,
+    },
     LintResult(LintResult),
     FormatResult(FormatResult),
-    TypeCheckResult { duration: Duration, number_of_diagnostics: usize },
+    TypeCheckResult {
+        duration: Duration,
+        number_of_diagnostics: usize,
+    },
+}
+
+/// The exit codes, which tell the problems found in the code from the problems of the command
+/// itself, like the ones of ESLint
+pub mod exit_code {
+    /// No problems are found, or the warnings are allowed
+    pub const SUCCESS: u8 = 0;
+    /// Errors are found, or warnings with `--deny-warnings` or beyond `--max-warnings`
+    pub const PROBLEMS_FOUND: u8 = 1;
+    /// The options, the configuration file or the paths are invalid
+    pub const INVALID_INVOCATION: u8 = 2;
 }
 
 #[derive(Debug, Default)]
@@ -25,23 +47,32 @@ pub struct LintResult {
     pub deny_warnings: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct FormatResult {
     pub duration: Duration,
     pub number_of_files: usize,
+    /// The files which have been rewritten, or which would be rewritten in `--check` mode
+    pub number_of_unformatted_files: usize,
+    /// The files which could not be read, parsed or written
+    pub number_of_errors: usize,
+    pub check: bool,
 }
 
 impl Termination for CliRunResult {
     fn report(self) -> ExitCode {
         match self {
-            Self::None => ExitCode::from(0),
+            Self::None => ExitCode::from(exit_code::SUCCESS),
             Self::InvalidOptions { message } => {
                 println!("Invalid Options: {message}");
-                ExitCode::from(1)
+                ExitCode::from(exit_code::INVALID_INVOCATION)
             }
             Self::PathNotFound { paths } => {
                 println!("Path {paths:?} does not exist.");
-                ExitCode::from(1)
+                ExitCode::from(exit_code::INVALID_INVOCATION)
+            }
+            Self::NoFilesFound { paths } => {
+                println!("No files matching {paths:?} were found.");
+                ExitCode::from(exit_code::INVALID_INVOCATION)
             }
             Self::LintResult(LintResult {
                 duration,
@@ -67,7 +98,7 @@ impl Termination for CliRunResult {
 
                 if max_warnings_exceeded {
                     println!("Exceeded maximum number of warnings. Found {number_of_warnings}.");
-                    return ExitCode::from(1);
+                    return ExitCode::from(exit_code::PROBLEMS_FOUND);
                 }
 
                 println!(
@@ -76,18 +107,41 @@ impl Termination for CliRunResult {
                     if number_of_errors == 1 { "" } else { "s" }
                 );
 
-                let exit_code =
-                    u8::from((number_of_warnings > 0 && deny_warnings) || number_of_errors > 0);
-                ExitCode::from(exit_code)
+                if (number_of_warnings > 0 && deny_warnings) || number_of_errors > 0 {
+                    return ExitCode::from(exit_code::PROBLEMS_FOUND);
+                }
+                ExitCode::from(exit_code::SUCCESS)
             }
-            Self::FormatResult(FormatResult { duration, number_of_files }) => {
+            Self::FormatResult(FormatResult {
+                duration,
+                number_of_files,
+                number_of_unformatted_files,
+                number_of_errors,
+                check,
+            }) => {
                 let threads = rayon::current_num_threads();
                 let time = Self::get_execution_time(&duration);
                 let s = if number_of_files == 1 { "" } else { "s" };
                 println!(
                     "Finished in {time} on {number_of_files} file{s} using {threads} threads."
                 );
-                ExitCode::from(0)
+
+                let s = if number_of_unformatted_files == 1 { "" } else { "s" };
+                if check {
+                    println!("Found {number_of_unformatted_files} unformatted file{s}.");
+                } else {
+                    println!("Formatted {number_of_unformatted_files} file{s}.");
+                }
+
+                if number_of_errors > 0 {
+                    let s = if number_of_errors == 1 { "" } else { "s" };
+                    println!("Failed to format {number_of_errors} file{s}.");
+                }
+
+                if (check && number_of_unformatted_files > 0) || number_of_errors > 0 {
+                    return ExitCode::from(exit_code::PROBLEMS_FOUND);
+                }
+                ExitCode::from(exit_code::SUCCESS)
             }
             Self::TypeCheckResult { duration, number_of_diagnostics } => {
                 let time = Self::get_execution_time(&duration);
@@ -95,10 +149,10 @@ impl Termination for CliRunResult {
 
                 if number_of_diagnostics > 0 {
                     println!("Found {number_of_diagnostics} errors.");
-                    return ExitCode::from(1);
+                    return ExitCode::from(exit_code::PROBLEMS_FOUND);
                 }
 
-                ExitCode::from(0)
+                ExitCode::from(exit_code::SUCCESS)
             }
         }
     }
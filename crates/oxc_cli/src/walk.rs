@@ -1,9 +1,13 @@
 use std::{
+    env,
     path::{Path, PathBuf},
     sync::mpsc,
 };
 
-use ignore::{overrides::OverrideBuilder, DirEntry};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    DirEntry, Match,
+};
 use oxc_span::VALID_EXTENSIONS;
 
 use crate::IgnoreOptions;
@@ -17,6 +21,95 @@ impl Default for Extensions {
     }
 }
 
+/// Matches the paths against the ignore file and the ignore patterns, in the order of their
+/// precedence:
+///
+/// 1. The `node_modules` and `.git` directories, which `--no-ignore` does not include
+/// 2. The ignore file of `--ignore-path`, `.eslintignore` by default
+/// 3. The `ignorePatterns` of the config file
+/// 4. The patterns of `--ignore-pattern`
+///
+/// The last matching pattern wins as in a `.gitignore` file, so that a negated pattern, e.g.
+/// `!dist/index.js`, includes a file excluded by an earlier pattern. The `.gitignore` files are
+/// applied by the traversal, the files they exclude cannot be included again.
+#[derive(Debug, Clone)]
+pub struct IgnoreMatcher {
+    cwd: PathBuf,
+    gitignores: Vec<Gitignore>,
+    use_gitignore: bool,
+}
+
+impl IgnoreMatcher {
+    /// `config_patterns` are the `ignorePatterns` of the config file and its directory, which
+    /// the patterns are relative to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the ignore file or one of the patterns is not valid
+    pub fn new(
+        options: &IgnoreOptions,
+        config_patterns: Option<(&Path, &[String])>,
+    ) -> Result<Self, String> {
+        let cwd = env::current_dir()
+            .map_err(|_| "Failed to get current working directory.".to_string())?;
+        let mut matcher = Self { cwd, gitignores: vec![], use_gitignore: !options.no_gitignore };
+        matcher.add_patterns(
+            &matcher.cwd.clone(),
+            &["**/node_modules/".to_string(), "**/.git/".to_string()],
+        )?;
+        if options.no_ignore {
+            return Ok(matcher);
+        }
+
+        let ignore_path = matcher.cwd.join(&options.ignore_path);
+        if ignore_path.is_file() {
+            let (gitignore, err) = Gitignore::new(&ignore_path);
+            if let Some(err) = err {
+                return Err(format!("Failed to parse {}: {err}", ignore_path.display()));
+            }
+            matcher.gitignores.push(gitignore);
+        }
+
+        if let Some((root, patterns)) = config_patterns {
+            matcher.add_patterns(&matcher.cwd.join(root), patterns)?;
+        }
+
+        matcher.add_patterns(&matcher.cwd.clone(), &options.ignore_pattern)?;
+        Ok(matcher)
+    }
+
+    fn add_patterns(&mut self, root: &Path, patterns: &[String]) -> Result<(), String> {
+        if patterns.is_empty() {
+            return Ok(());
+        }
+        let mut builder = GitignoreBuilder::new(root);
+        for pattern in patterns {
+            builder
+                .add_line(None, pattern)
+                .map_err(|err| format!("Invalid ignore pattern {pattern:?}: {err}"))?;
+        }
+        let gitignore = builder.build().map_err(|err| err.to_string())?;
+        self.gitignores.push(gitignore);
+        Ok(())
+    }
+
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let path = self.cwd.join(path);
+        let mut ignored = false;
+        for gitignore in &self.gitignores {
+            if !path.starts_with(gitignore.path()) {
+                continue;
+            }
+            match gitignore.matched_path_or_any_parents(&path, is_dir) {
+                Match::None => {}
+                Match::Ignore(_) => ignored = true,
+                Match::Whitelist(_) => ignored = false,
+            }
+        }
+        ignored
+    }
+}
+
 pub struct Walk {
     inner: ignore::WalkParallel,
     /// The file extensions to include during the traversal.
@@ -70,7 +163,7 @@ impl ignore::ParallelVisitor for WalkCollector {
 impl Walk {
     /// Will not canonicalize paths.
     /// # Panics
-    pub fn new(paths: &[PathBuf], options: &IgnoreOptions) -> Self {
+    pub fn new(paths: &[PathBuf], matcher: &IgnoreMatcher) -> Self {
         assert!(!paths.is_empty(), "At least one path must be provided to Walk::new");
 
         let mut inner = ignore::WalkBuilder::new(
@@ -86,28 +179,32 @@ impl Walk {
             }
         }
 
-        if !options.no_ignore {
-            inner.add_custom_ignore_filename(&options.ignore_path);
-
-            if !options.ignore_pattern.is_empty() {
-                let mut override_builder = OverrideBuilder::new(Path::new("/"));
-                for pattern in &options.ignore_pattern {
-                    // Meaning of ignore pattern is reversed
-                    // <https://docs.rs/ignore/latest/ignore/overrides/struct.OverrideBuilder.html#method.add>
-                    let pattern = format!("!{pattern}");
-                    override_builder.add(&pattern).unwrap();
-                }
-                let overrides = override_builder.build().unwrap();
-                inner.overrides(overrides);
-            }
-        }
+        let use_gitignore = matcher.use_gitignore;
+        let matcher = matcher.clone();
+        inner.filter_entry(move |entry| {
+            !matcher.is_ignored(entry.path(), entry.file_type().is_some_and(|ft| ft.is_dir()))
+        });
         // Turning off `follow_links` because:
         // * following symlinks is a really slow syscall
         // * it is super rare to have symlinked source code
-        let inner = inner.ignore(false).git_global(false).follow_links(false).build_parallel();
+        let inner = inner
+            .ignore(false)
+            .git_global(false)
+            .git_ignore(use_gitignore)
+            .git_exclude(use_gitignore)
+            .follow_links(false)
+            .build_parallel();
         Self { inner, extensions: Extensions::default() }
     }
 
+    /// The ignore crate whitelists explicit paths, but priority
+    /// should be given to the ignore file. Many users lint or format
+    /// automatically and pass a list of changed files explicitly.
+    /// To accommodate this, pre-filter the paths.
+    pub fn retain_unignored_paths(paths: &mut Vec<PathBuf>, matcher: &IgnoreMatcher) {
+        paths.retain(|p| p.is_dir() || !matcher.is_ignored(p, false));
+    }
+
     pub fn paths(self) -> Vec<Box<Path>> {
         let (sender, receiver) = mpsc::channel::<Vec<Box<Path>>>();
         let mut builder = WalkBuilder { sender, extensions: self.extensions };
@@ -142,7 +239,7 @@ mod test {
 
     use crate::IgnoreOptions;
 
-    use super::{Extensions, Walk};
+    use super::{Extensions, IgnoreMatcher, Walk};
 
     #[test]
     fn test_walk_with_extensions() {
@@ -152,9 +249,11 @@ mod test {
             no_ignore: false,
             ignore_path: OsString::from(".gitignore"),
             ignore_pattern: vec![],
+            no_gitignore: false,
         };
+        let matcher = IgnoreMatcher::new(&ignore_options, None).unwrap();
 
-        let mut paths = Walk::new(&fixtures, &ignore_options)
+        let mut paths = Walk::new(&fixtures, &matcher)
             .with_extensions(Extensions(["js", "vue"].to_vec()))
             .paths()
             .into_iter()
@@ -165,3 +264,65 @@ mod test {
         assert_eq!(paths, vec!["bar.vue", "foo.js"]);
     }
 }
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod ignore_matcher {
+    use std::{ffi::OsString, path::Path};
+
+    use crate::IgnoreOptions;
+
+    use super::IgnoreMatcher;
+
+    fn new_matcher(ignore_pattern: &[&str], config_patterns: &[&str]) -> IgnoreMatcher {
+        let options = IgnoreOptions {
+            no_ignore: false,
+            ignore_path: OsString::from("fixtures/linter/.customignore"),
+            ignore_pattern: ignore_pattern.iter().map(ToString::to_string).collect(),
+            no_gitignore: false,
+        };
+        let config_patterns = config_patterns.iter().map(ToString::to_string).collect::<Vec<_>>();
+        IgnoreMatcher::new(&options, Some((Path::new("fixtures"), &config_patterns))).unwrap()
+    }
+
+    #[test]
+    fn precedence() {
+        let matcher = new_matcher(&[], &[]);
+        assert!(matcher.is_ignored(Path::new("fixtures/linter/nan.js"), false));
+        assert!(!matcher.is_ignored(Path::new("fixtures/linter/debugger.js"), false));
+        assert!(matcher.is_ignored(Path::new("foo/node_modules/bar.js"), false));
+
+        // The config patterns are relative to the directory of the config file
+        let matcher = new_matcher(&[], &["/linter/debugger.js", "!/linter/nan.js"]);
+        assert!(!matcher.is_ignored(Path::new("fixtures/linter/nan.js"), false));
+        assert!(matcher.is_ignored(Path::new("fixtures/linter/debugger.js"), false));
+
+        let matcher = new_matcher(&["!debugger.js"], &["/linter/debugger.js"]);
+        assert!(!matcher.is_ignored(Path::new("fixtures/linter/debugger.js"), false));
+    }
+
+    #[test]
+    fn no_ignore() {
+        let options = IgnoreOptions {
+            no_ignore: true,
+            ignore_path: OsString::from("fixtures/linter/.customignore"),
+            ignore_pattern: vec!["debugger.js".to_string()],
+            no_gitignore: false,
+        };
+        let matcher = IgnoreMatcher::new(&options, None).unwrap();
+        assert!(!matcher.is_ignored(Path::new("fixtures/linter/nan.js"), false));
+        assert!(!matcher.is_ignored(Path::new("fixtures/linter/debugger.js"), false));
+        assert!(matcher.is_ignored(Path::new("foo/node_modules/bar.js"), false));
+        assert!(matcher.is_ignored(Path::new(".git/hooks/pre-commit.js"), false));
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let options = IgnoreOptions {
+            no_ignore: false,
+            ignore_path: OsString::from(".eslintignore"),
+            ignore_pattern: vec!["a/**b[".to_string()],
+            no_gitignore: false,
+        };
+        assert!(IgnoreMatcher::new(&options, None).is_err());
+    }
+}
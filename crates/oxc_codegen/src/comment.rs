@@ -0,0 +1,175 @@
+//! Comments of the original source which are printed before the statements
+
+use oxc_ast::{Trivias, TriviasMap};
+use oxc_span::Span;
+
+use crate::Codegen;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CommentOptions {
+    /// Print all the comments before the statements.
+    ///
+    /// Default `false`
+    pub preserve_all_comments: bool,
+
+    /// Print the license comments before the statements: `/*! ... */`, `//! ...` and the comments
+    /// containing `@license` or `@preserve`.
+    ///
+    /// Default `true`
+    pub preserve_legal_comments: bool,
+
+    /// Print `/* #__PURE__ */` and `/* #__NO_SIDE_EFFECTS__ */` before the annotated calls and
+    /// functions.
+    ///
+    /// Default `true`
+    pub preserve_annotation_comments: bool,
+}
+
+impl Default for CommentOptions {
+    fn default() -> Self {
+        Self {
+            preserve_all_comments: false,
+            preserve_legal_comments: true,
+            preserve_annotation_comments: true,
+        }
+    }
+}
+
+/// The comments of the original source
+pub struct Comments {
+    source_text: String,
+    trivias: TriviasMap,
+    /// The comments before this position are printed or dropped
+    printed_until: u32,
+}
+
+impl Comments {
+    pub fn new(source_text: &str, trivias: Trivias) -> Self {
+        Self {
+            source_text: source_text.to_string(),
+            trivias: TriviasMap::from(trivias),
+            printed_until: 0,
+        }
+    }
+
+    /// The comments which are only separated by whitespace from `start`, with their delimiters
+    fn take_leading_comments(&mut self, start: u32) -> Vec<(Span, bool)> {
+        if start <= self.printed_until || start as usize > self.source_text.len() {
+            return vec![];
+        }
+        let mut comments = vec![];
+        let mut end = start;
+        for (content_start, comment) in
+            self.trivias.comments().range(self.printed_until..start).rev()
+        {
+            let is_multi_line = comment.is_multi_line();
+            let comment_end = if is_multi_line { comment.end() + 2 } else { comment.end() };
+            let is_leading = comment_end <= end
+                && self.source_text[comment_end as usize..end as usize].trim().is_empty();
+            if !is_leading {
+                break;
+            }
+            end = content_start - 2;
+            comments.push((Span::new(end, comment_end), is_multi_line));
+        }
+        self.printed_until = start;
+        comments.reverse();
+        comments
+    }
+}
+
+/// `/*! ... */`, `//! ...`, `@license` and `@preserve`
+fn is_legal_comment(text: &str) -> bool {
+    text[2..].starts_with('!') || text.contains("@license") || text.contains("@preserve")
+}
+
+/// `#__PURE__` and `#__NO_SIDE_EFFECTS__` are printed for the annotated nodes
+fn is_annotation_comment(text: &str) -> bool {
+    let content = text.trim_start_matches('/').trim_start_matches('*').trim_end_matches('/');
+    let content = content.trim_end_matches('*').trim();
+    matches!(content, "#__PURE__" | "@__PURE__" | "#__NO_SIDE_EFFECTS__" | "@__NO_SIDE_EFFECTS__")
+}
+
+impl<const MINIFY: bool> Codegen<MINIFY> {
+    /// Print the comments before the statement or directive starting at `start`
+    pub(crate) fn print_leading_comments(&mut self, start: u32) {
+        let Some(comments) = &mut self.comments else { return };
+        let leading_comments = comments.take_leading_comments(start);
+        // A comment does not continue the previous statement
+        let omitted_semicolon = std::mem::take(&mut self.omitted_semicolon);
+        for (span, is_multi_line) in leading_comments {
+            let Some(comments) = &self.comments else { break };
+            let text = span.source_text(&comments.source_text).to_string();
+            let options = self.comment_options;
+            let is_printed = !is_annotation_comment(&text)
+                && (options.preserve_all_comments
+                    || (options.preserve_legal_comments && is_legal_comment(&text)));
+            if !is_printed {
+                continue;
+            }
+            self.print_indent();
+            self.print_str(text.as_bytes());
+            if is_multi_line {
+                self.print_soft_newline();
+            } else {
+                self.print(b'\n');
+            }
+        }
+        self.omitted_semicolon = omitted_semicolon;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::CommentOptions;
+    use crate::{Codegen, CodegenOptions};
+
+    fn print<const MINIFY: bool>(source_text: &str, options: CommentOptions) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+        Codegen::<MINIFY>::new(source_text.len(), CodegenOptions::default())
+            .with_comments(source_text, ret.trivias, options)
+            .build(&ret.program)
+    }
+
+    #[test]
+    fn legal_comments() {
+        let options = CommentOptions::default();
+        let source_text = "/*! banner */\n// comment\nfoo();\n/** @license MIT */ bar();";
+        assert_eq!(
+            print::<true>(source_text, options),
+            "/*! banner */foo();/** @license MIT */bar();"
+        );
+        assert_eq!(
+            print::<false>("function f() {\n  //! legal\n  return 1;\n}", options),
+            "function f() {\n\t//! legal\n\treturn 1;\n}\n"
+        );
+        let options =
+            CommentOptions { preserve_legal_comments: false, ..CommentOptions::default() };
+        assert_eq!(print::<true>(source_text, options), "foo();bar();");
+    }
+
+    #[test]
+    fn all_comments() {
+        let options = CommentOptions { preserve_all_comments: true, ..CommentOptions::default() };
+        let source_text = "// a\n/* b */ foo(/* c */);\n'use strict' // d\nbar();";
+        assert_eq!(
+            print::<false>(source_text, options),
+            "// a\n/* b */\nfoo();\n'use strict';\n// d\nbar();\n"
+        );
+    }
+
+    #[test]
+    fn annotation_comments() {
+        let source_text = "/* #__PURE__ */ foo(); x = /* @__PURE__ */ bar();";
+        let options = CommentOptions { preserve_all_comments: true, ..CommentOptions::default() };
+        assert_eq!(print::<true>(source_text, options), "/*#__PURE__*/foo();x=/*#__PURE__*/bar();");
+        let options =
+            CommentOptions { preserve_annotation_comments: false, ..CommentOptions::default() };
+        assert_eq!(print::<true>(source_text, options), "foo();x=bar();");
+    }
+}
@@ -0,0 +1,162 @@
+//! The escape sequences of the non-ASCII characters and of `</script` in the printed code
+
+use oxc_syntax::identifier::{LS, PS};
+
+use crate::{options::Charset, Codegen};
+
+/// `</script` of the code after `<` would end the inline script of HTML
+fn is_script_end_tag(rest: &str) -> bool {
+    rest.get(..7).is_some_and(|s| s.eq_ignore_ascii_case("/script"))
+}
+
+impl<const MINIFY: bool> Codegen<MINIFY> {
+    fn is_ascii_only(&self) -> bool {
+        self.options.charset == Charset::Ascii
+    }
+
+    fn print_char(&mut self, c: char) {
+        let mut buffer = [0; 4];
+        self.print_str(c.encode_utf8(&mut buffer).as_bytes());
+    }
+
+    /// `\xE9`, `\u2026` or the surrogate pair `\uD83D\uDE00` of a string or template literal,
+    /// which are also the escapes of a regular expression without the `u` flag
+    fn print_utf16_escape(&mut self, c: char, latin1: bool) {
+        let code = u32::from(c);
+        if latin1 && code <= 0xFF {
+            self.print_str(format!("\\x{code:02X}").as_bytes());
+            return;
+        }
+        let mut buffer = [0; 2];
+        for unit in c.encode_utf16(&mut buffer) {
+            self.print_str(format!("\\u{unit:04X}").as_bytes());
+        }
+    }
+
+    /// `\u00E9`, or `\u{1F600}` which is the only escape of the characters outside of the Basic
+    /// Multilingual Plane in the identifiers and the regular expressions with the `u` flag
+    fn print_code_point_escape(&mut self, c: char) {
+        let code = u32::from(c);
+        if code <= 0xFFFF {
+            self.print_str(format!("\\u{code:04X}").as_bytes());
+        } else {
+            self.print_str(format!("\\u{{{code:X}}}").as_bytes());
+        }
+    }
+
+    /// A non-ASCII character of a string literal, template literal or module specifier
+    pub(crate) fn print_non_ascii_in_str(&mut self, c: char) {
+        if self.is_ascii_only() {
+            self.print_utf16_escape(c, true);
+        } else {
+            self.print_char(c);
+        }
+    }
+
+    /// `<` before `/script` is printed as `<\`
+    pub(crate) fn print_less_than_in_str(&mut self, rest: &str) {
+        if self.options.escape_script_end_tags && is_script_end_tag(rest) {
+            self.print_str(b"<\\");
+        } else {
+            self.print(b'<');
+        }
+    }
+
+    pub(crate) fn print_identifier(&mut self, name: &str) {
+        if !self.is_ascii_only() || name.is_ascii() {
+            self.print_str(name.as_bytes());
+            return;
+        }
+        for c in name.chars() {
+            if c.is_ascii() {
+                self.print(c as u8);
+            } else {
+                self.print_code_point_escape(c);
+            }
+        }
+    }
+
+    /// The pattern of a regular expression, the identity escape `\é` is printed as `é`
+    pub(crate) fn print_regexp_pattern(&mut self, pattern: &str, unicode: bool) {
+        if !self.is_ascii_only() || pattern.is_ascii() {
+            self.print_str(pattern.as_bytes());
+            return;
+        }
+        let mut escaped = false;
+        for c in pattern.chars() {
+            if c.is_ascii() {
+                if escaped {
+                    self.print(b'\\');
+                }
+                escaped = c == '\\' && !escaped;
+                if !escaped {
+                    self.print(c as u8);
+                }
+            } else if unicode {
+                escaped = false;
+                self.print_code_point_escape(c);
+            } else {
+                escaped = false;
+                self.print_utf16_escape(c, false);
+            }
+        }
+        if escaped {
+            self.print(b'\\');
+        }
+    }
+
+    /// The raw text of an untagged template literal, the tagged templates read the raw text so it
+    /// is printed as it is
+    pub(crate) fn print_template_raw(&mut self, raw: &str, tagged: bool) {
+        let needs_escapes = (self.is_ascii_only() && !raw.is_ascii())
+            || (self.options.escape_script_end_tags && raw.contains('<'));
+        if tagged || !needs_escapes {
+            self.print_str(raw.as_bytes());
+            return;
+        }
+        let mut escaped = false;
+        let mut chars = raw.chars();
+        while let Some(c) = chars.next() {
+            if escaped {
+                escaped = false;
+                // The identity escape of a non-ASCII character is the character
+                if c.is_ascii() {
+                    self.print(b'\\');
+                }
+                self.print_template_char(c, chars.as_str(), true);
+            } else if c == '\\' {
+                escaped = true;
+            } else {
+                self.print_template_char(c, chars.as_str(), false);
+            }
+        }
+        if escaped {
+            self.print(b'\\');
+        }
+    }
+
+    fn print_template_char(&mut self, c: char, rest: &str, escaped: bool) {
+        match c {
+            '<' => self.print_less_than_in_str(rest),
+            // A line continuation is not a character of the template
+            LS | PS if escaped => {}
+            c if c.is_ascii() => self.print(c as u8),
+            c => self.print_non_ascii_in_str(c),
+        }
+    }
+
+    /// The JSX text and attribute strings have no escape sequences but HTML entities
+    pub(crate) fn print_jsx_text(&mut self, text: &str) {
+        if !self.is_ascii_only() || text.is_ascii() {
+            self.print_str(text.as_bytes());
+            return;
+        }
+        for c in text.chars() {
+            if c.is_ascii() {
+                self.print(c as u8);
+            } else {
+                self.print_str(format!("&#x{:X};", u32::from(c)).as_bytes());
+            }
+        }
+    }
+}
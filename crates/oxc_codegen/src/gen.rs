@@ -1,6 +1,7 @@
 use oxc_allocator::{Box, Vec};
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::ast::*;
+use oxc_span::GetSpan;
 use oxc_syntax::{
     identifier::{LS, PS},
     operator::{BinaryOperator, UnaryOperator},
@@ -92,6 +93,7 @@ impl<const MINIFY: bool> Gen<MINIFY> for Hashbang {
 
 impl<const MINIFY: bool> Gen<MINIFY> for Directive {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
+        p.print_leading_comments(self.span.start);
         // Use the string value instead of the raw self.directive because it can cannot escaped values.
         print_str(self.expression.value.as_str(), p);
         p.print_semicolon();
@@ -100,6 +102,8 @@ impl<const MINIFY: bool> Gen<MINIFY> for Directive {
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for Statement<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
+        p.print_leading_comments(self.span().start);
+        p.add_source_mapping(self.span());
         match self {
             Self::BlockStatement(stmt) => stmt.gen(p, ctx),
             Self::BreakStatement(stmt) => stmt.gen(p, ctx),
@@ -179,16 +183,19 @@ fn print_if<const MINIFY: bool>(
     if let Some(alternate) = if_stmt.alternate.as_ref() {
         p.print_semicolon_if_needed();
         p.print_space_before_identifier();
-        p.print_str(b"else ");
+        p.print_str(b"else");
         match alternate {
             Statement::BlockStatement(block) => {
+                p.print_soft_space();
                 p.print_block1(block, ctx);
                 p.print_soft_newline();
             }
             Statement::IfStatement(if_stmt) => {
+                p.print_hard_space();
                 print_if(if_stmt, p, ctx);
             }
             _ => {
+                p.print_hard_space();
                 p.print_soft_newline();
                 p.indent();
                 alternate.gen(p, ctx);
@@ -584,6 +591,9 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Function<'a> {
         let n = p.code_len();
         let wrap = self.is_expression() && (p.start_of_stmt == n || p.start_of_default_export == n);
         p.wrap(wrap, |p| {
+            if self.pure {
+                p.print_annotation_comment(b"#__NO_SIDE_EFFECTS__");
+            }
             if self.r#async {
                 p.print_str(b"async ");
             }
@@ -643,12 +653,11 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for FormalParameters<'a> {
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for ImportDeclaration<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
-        p.print_str(b"import ");
+        p.print_str(b"import");
+        p.print_soft_space();
         if let Some(specifiers) = &self.specifiers {
             if specifiers.is_empty() {
-                p.print(b'\'');
-                p.print_str(self.source.value.as_bytes());
-                p.print(b'\'');
+                self.source.gen(p, ctx);
                 self.with_clause.gen(p, ctx);
                 p.print_semicolon_after_statement();
                 return;
@@ -664,6 +673,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ImportDeclaration<'a> {
                         } else if index != 0 {
                             p.print_comma();
                         }
+                        p.print_space_before_identifier();
                         spec.local.gen(p, ctx);
                     }
                     ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
@@ -673,7 +683,9 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ImportDeclaration<'a> {
                         } else if index != 0 {
                             p.print_comma();
                         }
-                        p.print_str(b"* as ");
+                        p.print(b'*');
+                        p.print_soft_space();
+                        p.print_str(b"as ");
                         spec.local.gen(p, ctx);
                     }
                     ImportDeclarationSpecifier::ImportSpecifier(spec) => {
@@ -710,7 +722,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ImportDeclaration<'a> {
             if in_block {
                 p.print(b'}');
             }
-            p.print_str(b" from ");
+            p.print_soft_space();
+            p.print_space_before_identifier();
+            p.print_str(b"from");
+            p.print_soft_space();
         }
         self.source.gen(p, ctx);
         self.with_clause.gen(p, ctx);
@@ -751,10 +766,14 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ExportNamedDeclaration<'a> {
         if self.is_typescript_syntax() {
             return;
         }
-        p.print_str(b"export ");
+        p.print_str(b"export");
         match &self.declaration {
-            Some(decl) => decl.gen(p, ctx),
+            Some(decl) => {
+                p.print_hard_space();
+                decl.gen(p, ctx);
+            }
             None => {
+                p.print_soft_space();
                 p.print(b'{');
                 if !self.specifiers.is_empty() {
                     p.print_soft_space();
@@ -804,11 +823,15 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ExportAllDeclaration<'a> {
         p.print(b'*');
 
         if let Some(exported) = &self.exported {
+            p.print_soft_space();
             p.print_str(b"as ");
             exported.gen(p, ctx);
         }
 
-        p.print_str(b" from");
+        p.print_soft_space();
+        p.print_space_before_identifier();
+        p.print_str(b"from");
+        p.print_soft_space();
         self.source.gen(p, ctx);
         self.with_clause.gen(p, ctx);
 
@@ -847,6 +870,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ExportDefaultDeclarationKind<'a> {
 
 impl<'a, const MINIFY: bool> GenExpr<MINIFY> for Expression<'a> {
     fn gen_expr(&self, p: &mut Codegen<{ MINIFY }>, precedence: Precedence, ctx: Context) {
+        p.add_source_mapping(self.span());
         match self {
             Self::BooleanLiteral(lit) => lit.gen(p, ctx),
             Self::NullLiteral(lit) => lit.gen(p, ctx),
@@ -902,25 +926,28 @@ impl<const MINIFY: bool> Gen<MINIFY> for IdentifierReference {
         // }
         // }
         // }
-        p.print_str(self.name.as_bytes());
+        p.add_source_mapping_for_name(self.span);
+        p.print_identifier(self.name.as_str());
     }
 }
 
 impl<const MINIFY: bool> Gen<MINIFY> for IdentifierName {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
-        p.print_str(self.name.as_bytes());
+        p.add_source_mapping(self.span);
+        p.print_identifier(self.name.as_str());
     }
 }
 
 impl<const MINIFY: bool> Gen<MINIFY> for BindingIdentifier {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
+        p.add_source_mapping_for_name(self.span);
         p.print_symbol(self.symbol_id.get(), &self.name);
     }
 }
 
 impl<const MINIFY: bool> Gen<MINIFY> for LabelIdentifier {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
-        p.print_str(self.name.as_bytes());
+        p.print_identifier(self.name.as_str());
     }
 }
 
@@ -1063,12 +1090,16 @@ impl<const MINIFY: bool> Gen<MINIFY> for RegExpLiteral {
         // Avoid forming a single-line comment or "</script" sequence
         if Some('/') == last
             || (Some('<') == last
+                && p.options.escape_script_end_tags
                 && self.regex.pattern.as_str().to_lowercase().starts_with("script"))
         {
             p.print_hard_space();
         }
         p.print(b'/');
-        p.print_str(self.regex.pattern.as_bytes());
+        p.print_regexp_pattern(
+            self.regex.pattern.as_str(),
+            self.regex.flags.contains(RegExpFlags::U),
+        );
         p.print(b'/');
         p.print_str(self.regex.flags.to_string().as_bytes());
         p.prev_reg_exp_end = p.code().len();
@@ -1076,21 +1107,62 @@ impl<const MINIFY: bool> Gen<MINIFY> for RegExpLiteral {
 }
 
 fn print_str<const MINIFY: bool>(s: &str, p: &mut Codegen<{ MINIFY }>) {
-    p.print(b'\'');
-    for c in s.chars() {
-        match c {
-            // Allow `U+2028` and `U+2029` in string literals
-            // <https://tc39.es/proposal-json-superset>
-            // <https://github.com/tc39/proposal-json-superset>
-            LS => p.print_str(b"\\u2028"),
-            PS => p.print_str(b"\\u2029"),
-            '\u{a0}' => {
-                p.print_str(b"\\xA0");
-            }
-            _ => p.print_str(c.escape_default().to_string().as_bytes()),
-        }
+    let quote = choose_quote(s, p);
+    p.print(quote);
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        print_char_in_str(c, chars.as_str(), quote, p);
+    }
+    p.print(quote);
+}
+
+/// A character of a string literal followed by `rest`
+fn print_char_in_str<const MINIFY: bool>(
+    c: char,
+    rest: &str,
+    quote: u8,
+    p: &mut Codegen<{ MINIFY }>,
+) {
+    match c {
+        c if c == char::from(quote) => {
+            p.print(b'\\');
+            p.print(quote);
+        }
+        '\\' => p.print_str(b"\\\\"),
+        '\n' => p.print_str(b"\\n"),
+        '\r' => p.print_str(b"\\r"),
+        '\t' => p.print_str(b"\\t"),
+        '\u{8}' => p.print_str(b"\\b"),
+        '\u{b}' => p.print_str(b"\\v"),
+        '\u{c}' => p.print_str(b"\\f"),
+        // `\01` would be an octal escape
+        '\0' if !rest.starts_with(|c: char| c.is_ascii_digit()) => p.print_str(b"\\0"),
+        '\0'..='\u{1f}' | '\u{7f}' => p.print_str(format!("\\x{:02X}", c as u8).as_bytes()),
+        '<' => p.print_less_than_in_str(rest),
+        // Allow `U+2028` and `U+2029` in string literals
+        // <https://tc39.es/proposal-json-superset>
+        // <https://github.com/tc39/proposal-json-superset>
+        LS => p.print_str(b"\\u2028"),
+        PS => p.print_str(b"\\u2029"),
+        c if c.is_ascii() => p.print(c as u8),
+        c => p.print_non_ascii_in_str(c),
+    }
+}
+
+/// The quote of [crate::CodegenOptions::quote_style], or the other quote when minifying if it
+/// needs fewer escapes
+fn choose_quote<const MINIFY: bool>(s: &str, p: &Codegen<{ MINIFY }>) -> u8 {
+    let quote = p.options.quote_style.as_byte();
+    if !MINIFY {
+        return quote;
+    }
+    let other = if quote == b'"' { b'\'' } else { b'"' };
+    let count = |quote: u8| s.bytes().filter(|c| *c == quote).count();
+    if count(other) < count(quote) {
+        other
+    } else {
+        quote
     }
-    p.print(b'\'');
 }
 
 impl<const MINIFY: bool> Gen<MINIFY> for StringLiteral {
@@ -1158,6 +1230,9 @@ impl<'a, const MINIFY: bool> GenExpr<MINIFY> for PrivateFieldExpression<'a> {
 impl<'a, const MINIFY: bool> GenExpr<MINIFY> for CallExpression<'a> {
     fn gen_expr(&self, p: &mut Codegen<{ MINIFY }>, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > self.precedence(), |p| {
+            if self.pure {
+                p.print_annotation_comment(b"#__PURE__");
+            }
             self.callee.gen_expr(p, self.precedence(), ctx);
             if self.optional {
                 p.print_str(b"?.");
@@ -1304,6 +1379,9 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for PropertyKey<'a> {
 impl<'a, const MINIFY: bool> GenExpr<MINIFY> for ArrowExpression<'a> {
     fn gen_expr(&self, p: &mut Codegen<{ MINIFY }>, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > Precedence::Assign, |p| {
+            if self.pure {
+                p.print_annotation_comment(b"#__NO_SIDE_EFFECTS__");
+            }
             if self.r#async {
                 p.print_str(b"async");
                 p.print_soft_space();
@@ -1647,27 +1725,32 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ImportExpression<'a> {
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for TemplateLiteral<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
-        p.print(b'`');
-        let mut expressions = self.expressions.iter();
-
-        for quasi in &self.quasis {
-            p.print_str(quasi.value.raw.as_bytes());
+        print_template(self, false, p);
+    }
+}
 
-            if let Some(expr) = expressions.next() {
-                p.print_str(b"${");
-                p.print_expression(expr);
-                p.print(b'}');
-            }
+fn print_template<const MINIFY: bool>(
+    template: &TemplateLiteral,
+    tagged: bool,
+    p: &mut Codegen<{ MINIFY }>,
+) {
+    p.print(b'`');
+    let mut expressions = template.expressions.iter();
+    for quasi in &template.quasis {
+        p.print_template_raw(quasi.value.raw.as_str(), tagged);
+        if let Some(expr) = expressions.next() {
+            p.print_str(b"${");
+            p.print_expression(expr);
+            p.print(b'}');
         }
-
-        p.print(b'`');
     }
+    p.print(b'`');
 }
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for TaggedTemplateExpression<'a> {
-    fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
+    fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
         self.tag.gen_expr(p, Precedence::Call, Context::default());
-        self.quasi.gen(p, ctx);
+        print_template(&self.quasi, true, p);
     }
 }
 
@@ -1698,6 +1781,9 @@ impl<'a, const MINIFY: bool> GenExpr<MINIFY> for ChainExpression<'a> {
 impl<'a, const MINIFY: bool> GenExpr<MINIFY> for NewExpression<'a> {
     fn gen_expr(&self, p: &mut Codegen<{ MINIFY }>, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > self.precedence(), |p| {
+            if self.pure {
+                p.print_annotation_comment(b"#__PURE__");
+            }
             p.print_str(b"new ");
             self.callee.gen_expr(p, self.precedence(), ctx);
             p.wrap(true, |p| {
@@ -1804,7 +1890,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for JSXElementName<'a> {
 impl<const MINIFY: bool> Gen<MINIFY> for JSXNamespacedName {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
         self.namespace.gen(p, ctx);
-        p.print(b'.');
+        p.print(b':');
         self.property.gen(p, ctx);
     }
 }
@@ -1854,12 +1940,23 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for JSXAttributeValue<'a> {
         match self {
             Self::Fragment(fragment) => fragment.gen(p, ctx),
             Self::Element(el) => el.gen(p, ctx),
-            Self::StringLiteral(lit) => lit.gen(p, ctx),
+            Self::StringLiteral(lit) => print_jsx_str(lit, p),
             Self::ExpressionContainer(expr_container) => expr_container.gen(p, ctx),
         }
     }
 }
 
+/// The JSX strings have no escape sequences, `<a b="\" />` is the string `\`
+fn print_jsx_str<const MINIFY: bool>(lit: &StringLiteral, p: &mut Codegen<{ MINIFY }>) {
+    let mut quote = p.options.quote_style.as_byte();
+    if lit.value.as_bytes().contains(&quote) {
+        quote = if quote == b'"' { b'\'' } else { b'"' };
+    }
+    p.print(quote);
+    p.print_jsx_text(lit.value.as_str());
+    p.print(quote);
+}
+
 impl<'a, const MINIFY: bool> Gen<MINIFY> for JSXSpreadAttribute<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
         p.print_str(b"{...");
@@ -1927,14 +2024,15 @@ impl<const MINIFY: bool> Gen<MINIFY> for JSXClosingFragment {
 
 impl<const MINIFY: bool> Gen<MINIFY> for JSXText {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
-        p.print_str(self.value.as_bytes());
+        p.print_jsx_text(self.value.as_str());
     }
 }
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for JSXSpreadChild<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
-        p.print_str(b"...");
+        p.print_str(b"{...");
         p.print_expression(&self.expression);
+        p.print(b'}');
     }
 }
 
@@ -1943,7 +2041,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for JSXChild<'a> {
         match self {
             Self::Fragment(fragment) => fragment.gen(p, ctx),
             Self::Element(el) => el.gen(p, ctx),
-            Self::Spread(spread) => p.print_expression(&spread.expression),
+            Self::Spread(spread) => spread.gen(p, ctx),
             Self::ExpressionContainer(expr_container) => expr_container.gen(p, ctx),
             Self::Text(text) => text.gen(p, ctx),
         }
@@ -2036,6 +2134,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for PropertyDefinition<'a> {
 
 impl<'a, const MINIFY: bool> Gen<MINIFY> for AccessorProperty<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
+        self.decorators.gen(p, ctx);
         if self.r#static {
             p.print_str(b"static ");
         }
@@ -2057,7 +2156,7 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for AccessorProperty<'a> {
 impl<const MINIFY: bool> Gen<MINIFY> for PrivateIdentifier {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
         p.print(b'#');
-        p.print_str(self.name.as_bytes());
+        p.print_identifier(self.name.as_str());
     }
 }
 
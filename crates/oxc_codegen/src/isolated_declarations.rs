@@ -0,0 +1,962 @@
+//! TypeScript Declarations
+//!
+//! Emits the `.d.ts` of a module from its syntax only, like
+//! [isolatedDeclarations](https://www.typescriptlang.org/tsconfig#isolatedDeclarations):
+//! the declarations need explicit type annotations, except for the literals whose types can be
+//! inferred. The type annotations are printed as they are written in the source.
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::Error,
+};
+use oxc_span::{Atom, GetSpan, Span};
+use oxc_syntax::operator::UnaryOperator;
+
+#[derive(Debug, Error, Diagnostic)]
+#[error(
+    "TS9007: Function must have an explicit return type annotation with --isolatedDeclarations."
+)]
+#[diagnostic()]
+struct FunctionMustHaveExplicitReturnType(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9008: Method must have an explicit return type annotation with --isolatedDeclarations.")]
+#[diagnostic()]
+struct MethodMustHaveExplicitReturnType(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9009: At least one accessor must have an explicit return type annotation with --isolatedDeclarations.")]
+#[diagnostic()]
+struct AccessorMustHaveExplicitReturnType(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9010: Variable must have an explicit type annotation with --isolatedDeclarations.")]
+#[diagnostic()]
+struct VariableMustHaveExplicitType(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9011: Parameter must have an explicit type annotation with --isolatedDeclarations.")]
+#[diagnostic()]
+struct ParameterMustHaveExplicitType(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9012: Property must have an explicit type annotation with --isolatedDeclarations.")]
+#[diagnostic()]
+struct PropertyMustHaveExplicitType(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9019: Binding elements can't be exported directly with --isolatedDeclarations.")]
+#[diagnostic()]
+struct BindingElementExport(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9021: Extends clause can't contain an expression with --isolatedDeclarations.")]
+#[diagnostic()]
+struct ExtendsClauseExpression(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9037: Default exports can't be inferred with --isolatedDeclarations.")]
+#[diagnostic()]
+struct DefaultExportsCannotBeInferred(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("TS9038: Computed property names on class or object literals cannot be inferred with --isolatedDeclarations.")]
+#[diagnostic()]
+struct ComputedPropertyName(#[label] Span);
+
+pub struct IsolatedDeclarations<'a> {
+    source_text: &'a str,
+    code: String,
+    indentation: usize,
+    /// The name of the function whose overload signature is printed last, its implementation is
+    /// not printed
+    signature: Option<Atom>,
+    errors: Vec<oxc_diagnostics::Error>,
+}
+
+pub struct IsolatedDeclarationsReturn {
+    pub source_text: String,
+    /// The declarations which need explicit type annotations
+    pub errors: Vec<oxc_diagnostics::Error>,
+}
+
+impl<'a> IsolatedDeclarations<'a> {
+    pub fn new(source_text: &'a str) -> Self {
+        Self { source_text, code: String::new(), indentation: 0, signature: None, errors: vec![] }
+    }
+
+    pub fn build(mut self, program: &Program<'_>) -> IsolatedDeclarationsReturn {
+        let has_local_declarations = self.print_statements(&program.body, false);
+        // The declarations of a module `.d.ts` are exported unless it has `export {}`
+        let is_module =
+            program.body.iter().any(|stmt| matches!(stmt, Statement::ModuleDeclaration(_)));
+        if has_local_declarations && is_module {
+            self.code.push_str("export {};\n");
+        }
+        IsolatedDeclarationsReturn { source_text: self.code, errors: self.errors }
+    }
+
+    fn error<T: Diagnostic + Send + Sync + 'static>(&mut self, error: T) {
+        self.errors.push(error.into());
+    }
+
+    fn source(&self, span: Span) -> &'a str {
+        span.source_text(self.source_text)
+    }
+
+    fn print_str(&mut self, s: &str) {
+        self.code.push_str(s);
+    }
+
+    fn print_indent(&mut self) {
+        for _ in 0..self.indentation {
+            self.code.push_str("    ");
+        }
+    }
+
+    /// Print the source of a statement from `start`, which is written as it is in the declarations
+    fn print_source_statement(&mut self, start: u32, end: u32) {
+        let text = Span::new(start, end).source_text(self.source_text).trim_end();
+        self.code.push_str(text);
+        if !text.ends_with(';') && !text.ends_with('}') {
+            self.code.push(';');
+        }
+        self.code.push('\n');
+    }
+
+    /// Returns whether a declaration which is not exported is printed
+    fn print_statements(&mut self, stmts: &[Statement<'_>], ambient: bool) -> bool {
+        self.signature = None;
+        let mut has_local_declarations = false;
+        for stmt in stmts {
+            match stmt {
+                Statement::Declaration(decl) => {
+                    has_local_declarations |= !matches!(decl, Declaration::UsingDeclaration(_))
+                        && !matches!(decl, Declaration::TSModuleDeclaration(module)
+                            if !matches!(module.id, TSModuleDeclarationName::Identifier(_))
+                                || module.id.name().as_str() == "global");
+                    self.print_declaration(decl, "", ambient);
+                }
+                Statement::ModuleDeclaration(decl) => self.print_module_declaration(decl, ambient),
+                _ => {}
+            }
+        }
+        self.signature = None;
+        has_local_declarations
+    }
+
+    fn print_module_declaration(&mut self, decl: &ModuleDeclaration<'_>, ambient: bool) {
+        match decl {
+            ModuleDeclaration::ImportDeclaration(decl) => {
+                self.print_indent();
+                self.print_source_statement(decl.span.start, decl.span.end);
+            }
+            ModuleDeclaration::ExportAllDeclaration(decl) => {
+                self.print_indent();
+                self.print_source_statement(decl.span.start, decl.span.end);
+            }
+            ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                if let Some(declaration) = &decl.declaration {
+                    self.print_declaration(declaration, "export ", ambient);
+                } else {
+                    self.print_indent();
+                    self.print_source_statement(decl.span.start, decl.span.end);
+                }
+            }
+            ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                self.print_export_default_declaration(decl);
+            }
+            ModuleDeclaration::TSExportAssignment(decl) => {
+                self.print_indent();
+                self.print_source_statement(decl.span.start, decl.span.end);
+            }
+            ModuleDeclaration::TSNamespaceExportDeclaration(decl) => {
+                self.print_indent();
+                self.print_source_statement(decl.span.start, decl.span.end);
+            }
+        }
+    }
+
+    fn print_export_default_declaration(&mut self, decl: &ExportDefaultDeclaration<'_>) {
+        match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                self.print_function(func, "export default ");
+            }
+            ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                self.print_class(class, "export default ");
+            }
+            ExportDefaultDeclarationKind::TSInterfaceDeclaration(decl) => {
+                self.print_indent();
+                self.print_str("export default interface ");
+                self.print_source_statement(decl.id.span.start, decl.span.end);
+            }
+            ExportDefaultDeclarationKind::Expression(Expression::Identifier(_))
+            | ExportDefaultDeclarationKind::TSEnumDeclaration(_) => {
+                self.print_indent();
+                self.print_source_statement(decl.span.start, decl.span.end);
+            }
+            ExportDefaultDeclarationKind::Expression(expr) => {
+                let Some(ty) = self.infer_type(expr) else {
+                    self.error(DefaultExportsCannotBeInferred(expr.span()));
+                    return;
+                };
+                self.print_indent();
+                self.print_str(&format!("declare const _default: {ty};\n"));
+                self.print_indent();
+                self.print_str("export default _default;\n");
+            }
+        }
+    }
+
+    fn print_declaration(&mut self, decl: &Declaration<'_>, export: &str, ambient: bool) {
+        let declare = if ambient { "" } else { "declare " };
+        match decl {
+            Declaration::VariableDeclaration(decl) => {
+                self.print_variable_declaration(decl, &format!("{export}{declare}"));
+            }
+            Declaration::FunctionDeclaration(func) => {
+                self.print_function(func, &format!("{export}{declare}"));
+            }
+            Declaration::ClassDeclaration(class) => {
+                self.print_class(class, &format!("{export}{declare}"));
+            }
+            Declaration::UsingDeclaration(_) => {}
+            Declaration::TSTypeAliasDeclaration(decl) => {
+                self.print_indent();
+                self.print_str(&format!("{export}type "));
+                self.print_source_statement(decl.id.span.start, decl.span.end);
+            }
+            Declaration::TSInterfaceDeclaration(decl) => {
+                self.print_indent();
+                self.print_str(&format!("{export}interface "));
+                self.print_source_statement(decl.id.span.start, decl.span.end);
+            }
+            Declaration::TSEnumDeclaration(decl) => {
+                let r#const =
+                    if decl.modifiers.contains(ModifierKind::Const) { "const " } else { "" };
+                self.print_indent();
+                self.print_str(&format!("{export}{declare}{const}enum "));
+                self.print_source_statement(decl.id.span.start, decl.span.end);
+            }
+            Declaration::TSModuleDeclaration(decl) => {
+                self.print_ts_module_declaration(decl, &format!("{export}{declare}"));
+            }
+            Declaration::TSImportEqualsDeclaration(decl) => {
+                self.print_indent();
+                if decl.is_export && !self.source(decl.span).starts_with("export") {
+                    self.print_str("export ");
+                }
+                self.print_source_statement(decl.span.start, decl.span.end);
+            }
+        }
+    }
+
+    fn print_variable_declaration(&mut self, decl: &VariableDeclaration<'_>, prefix: &str) {
+        let mut declarators = vec![];
+        for declarator in &decl.declarations {
+            let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                self.error(BindingElementExport(declarator.id.span()));
+                continue;
+            };
+            let ty = if let Some(ty) = &declarator.id.type_annotation {
+                Some(format!(": {}", self.source(ty.type_annotation.span())))
+            } else {
+                match &declarator.init {
+                    None => Some(": any".to_string()),
+                    Some(init) if decl.kind.is_const() && is_literal(init) => {
+                        Some(format!(" = {}", self.source(init.span())))
+                    }
+                    Some(init) => self.infer_type(init).map(|ty| format!(": {ty}")),
+                }
+            };
+            if let Some(ty) = ty {
+                declarators.push(format!("{}{ty}", ident.name));
+            } else {
+                self.error(VariableMustHaveExplicitType(ident.span));
+                declarators.push(ident.name.to_string());
+            }
+        }
+        if declarators.is_empty() {
+            return;
+        }
+        self.print_indent();
+        self.print_str(&format!("{prefix}{} {};\n", decl.kind.as_str(), declarators.join(", ")));
+    }
+
+    fn print_function(&mut self, func: &Function<'_>, prefix: &str) {
+        let name = func.id.as_ref().map(|id| id.name.clone());
+        let is_implementation = func.body.is_some() && name.is_some() && self.signature == name;
+        self.signature = if func.body.is_none() { name } else { None };
+        if is_implementation {
+            return;
+        }
+        let signature = self.signature_text(
+            func.type_parameters.as_deref(),
+            func.this_param.as_ref(),
+            &func.params,
+        );
+        self.print_indent();
+        self.print_str(prefix);
+        self.print_str("function");
+        if let Some(id) = &func.id {
+            self.print_str(&format!(" {}", id.name));
+        }
+        self.print_str(&signature);
+        if let Some(return_type) = &func.return_type {
+            self.print_str(&format!(": {}", self.source(return_type.type_annotation.span())));
+        } else {
+            let span = func.id.as_ref().map_or(func.span, |id| id.span);
+            self.error(FunctionMustHaveExplicitReturnType(span));
+        }
+        self.print_str(";\n");
+    }
+
+    fn print_class(&mut self, class: &Class<'_>, prefix: &str) {
+        self.print_indent();
+        self.print_str(prefix);
+        if class.modifiers.contains(ModifierKind::Abstract) {
+            self.print_str("abstract ");
+        }
+        self.print_str("class");
+        if let Some(id) = &class.id {
+            self.print_str(&format!(" {}", id.name));
+        }
+        if let Some(type_parameters) = &class.type_parameters {
+            self.print_str(self.source(type_parameters.span));
+        }
+        if let Some(super_class) = &class.super_class {
+            if matches!(super_class, Expression::Identifier(_) | Expression::MemberExpression(_)) {
+                self.print_str(&format!(" extends {}", self.source(super_class.span())));
+                if let Some(type_parameters) = &class.super_type_parameters {
+                    self.print_str(self.source(type_parameters.span));
+                }
+            } else {
+                self.error(ExtendsClauseExpression(super_class.span()));
+            }
+        }
+        if let Some(implements) = &class.implements {
+            let implements = implements
+                .iter()
+                .map(|implement| self.source(implement.span))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.print_str(&format!(" implements {implements}"));
+        }
+        self.print_str(" {\n");
+        self.indentation += 1;
+        if class.body.body.iter().any(is_private_element) {
+            self.print_indent();
+            self.print_str("#private;\n");
+        }
+        let mut signature = None;
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    self.print_method(method, class, false, &mut signature);
+                }
+                ClassElement::TSAbstractMethodDefinition(method) => {
+                    self.print_method(&method.method_definition, class, true, &mut signature);
+                }
+                ClassElement::PropertyDefinition(property) => {
+                    self.print_property(property, false);
+                }
+                ClassElement::TSAbstractPropertyDefinition(property) => {
+                    self.print_property(&property.property_definition, true);
+                }
+                ClassElement::AccessorProperty(property) => self.print_accessor_property(property),
+                ClassElement::TSIndexSignature(signature) => {
+                    self.print_indent();
+                    self.print_source_statement(signature.span.start, signature.span.end);
+                }
+                ClassElement::StaticBlock(_) => {}
+            }
+        }
+        self.indentation -= 1;
+        self.print_indent();
+        self.print_str("}\n");
+    }
+
+    /// `private static abstract readonly `
+    fn print_member_modifiers(
+        &mut self,
+        accessibility: Option<TSAccessibility>,
+        r#static: bool,
+        r#abstract: bool,
+        readonly: bool,
+    ) {
+        self.print_indent();
+        match accessibility {
+            Some(TSAccessibility::Private) => self.print_str("private "),
+            Some(TSAccessibility::Protected) => self.print_str("protected "),
+            Some(TSAccessibility::Public) | None => {}
+        }
+        if r#static {
+            self.print_str("static ");
+        }
+        if r#abstract {
+            self.print_str("abstract ");
+        }
+        if readonly {
+            self.print_str("readonly ");
+        }
+    }
+
+    fn print_method(
+        &mut self,
+        method: &MethodDefinition<'_>,
+        class: &Class<'_>,
+        r#abstract: bool,
+        signature: &mut Option<String>,
+    ) {
+        if matches!(method.key, PropertyKey::PrivateIdentifier(_)) {
+            return;
+        }
+        let Some(key) = self.property_key(&method.key, method.computed) else { return };
+        // The implementation of the overloaded method is not printed
+        let is_implementation =
+            method.value.body.is_some() && signature.as_ref().is_some_and(|name| *name == key);
+        *signature = method.value.body.is_none().then(|| key.clone());
+        if is_implementation {
+            return;
+        }
+        if method.kind.is_constructor() {
+            self.print_parameter_properties(&method.value.params);
+        }
+        self.print_member_modifiers(method.accessibility, method.r#static, r#abstract, false);
+        if method.accessibility == Some(TSAccessibility::Private) {
+            let key = if method.kind.is_constructor() { "constructor()" } else { &key };
+            self.print_str(&format!("{key};\n"));
+            return;
+        }
+        let func = &method.value;
+        match method.kind {
+            MethodDefinitionKind::Constructor => {
+                let params = self.params_text(None, &func.params);
+                self.print_str(&format!("constructor({params});\n"));
+            }
+            MethodDefinitionKind::Get => {
+                let ty = func
+                    .return_type
+                    .as_ref()
+                    .map(|ty| self.source(ty.type_annotation.span()))
+                    .or_else(|| self.accessor_type(class, &key, MethodDefinitionKind::Set));
+                if let Some(ty) = ty {
+                    self.print_str(&format!("get {key}(): {ty};\n"));
+                } else {
+                    self.error(AccessorMustHaveExplicitReturnType(method.key.span()));
+                    self.print_str(&format!("get {key}();\n"));
+                }
+            }
+            MethodDefinitionKind::Set => {
+                let param = func.params.items.first().map(|param| &param.pattern);
+                let name =
+                    param.map_or_else(|| "value".to_string(), |param| self.binding_name(param));
+                let ty = param
+                    .and_then(|param| param.type_annotation.as_ref())
+                    .map(|ty| self.source(ty.type_annotation.span()))
+                    .or_else(|| self.accessor_type(class, &key, MethodDefinitionKind::Get));
+                if let Some(ty) = ty {
+                    self.print_str(&format!("set {key}({name}: {ty});\n"));
+                } else {
+                    self.error(AccessorMustHaveExplicitReturnType(method.key.span()));
+                    self.print_str(&format!("set {key}({name});\n"));
+                }
+            }
+            MethodDefinitionKind::Method => {
+                let optional = if method.optional { "?" } else { "" };
+                let signature = self.signature_text(
+                    func.type_parameters.as_deref(),
+                    func.this_param.as_ref(),
+                    &func.params,
+                );
+                self.print_str(&format!("{key}{optional}{signature}"));
+                if let Some(return_type) = &func.return_type {
+                    self.print_str(&format!(
+                        ": {}",
+                        self.source(return_type.type_annotation.span())
+                    ));
+                } else {
+                    self.error(MethodMustHaveExplicitReturnType(method.key.span()));
+                }
+                self.print_str(";\n");
+            }
+        }
+    }
+
+    /// The type of the other accessor of `key`, the return type of the getter or the parameter
+    /// type of the setter
+    fn accessor_type(
+        &self,
+        class: &Class<'_>,
+        key: &str,
+        kind: MethodDefinitionKind,
+    ) -> Option<&'a str> {
+        class.body.body.iter().find_map(|element| {
+            let ClassElement::MethodDefinition(method) = element else { return None };
+            if method.kind != kind || self.property_key_text(&method.key, method.computed) != key {
+                return None;
+            }
+            let ty = match kind {
+                MethodDefinitionKind::Get => method.value.return_type.as_ref(),
+                _ => method.value.params.items.first()?.pattern.type_annotation.as_ref(),
+            }?;
+            Some(self.source(ty.type_annotation.span()))
+        })
+    }
+
+    /// `constructor(private a: string)` -> `private a;`
+    fn print_parameter_properties(&mut self, params: &FormalParameters<'_>) {
+        for param in &params.items {
+            if param.accessibility.is_none() && !param.readonly {
+                continue;
+            }
+            let (pattern, initializer) = match &param.pattern.kind {
+                BindingPatternKind::AssignmentPattern(pattern) => {
+                    (&pattern.left, Some(&pattern.right))
+                }
+                _ => (&param.pattern, None),
+            };
+            let name = self.binding_name(pattern);
+            self.print_member_modifiers(param.accessibility, false, false, param.readonly);
+            if param.accessibility == Some(TSAccessibility::Private) {
+                self.print_str(&format!("{name};\n"));
+                continue;
+            }
+            let optional = if pattern.optional || initializer.is_some() { "?" } else { "" };
+            let ty = pattern
+                .type_annotation
+                .as_ref()
+                .map(|ty| self.source(ty.type_annotation.span()).to_string())
+                .or_else(|| initializer.and_then(|init| self.infer_type(init)));
+            if let Some(ty) = ty {
+                self.print_str(&format!("{name}{optional}: {ty};\n"));
+            } else {
+                self.error(PropertyMustHaveExplicitType(pattern.span()));
+                self.print_str(&format!("{name}{optional};\n"));
+            }
+        }
+    }
+
+    fn print_property(&mut self, property: &PropertyDefinition<'_>, r#abstract: bool) {
+        if matches!(property.key, PropertyKey::PrivateIdentifier(_)) {
+            return;
+        }
+        let Some(key) = self.property_key(&property.key, property.computed) else { return };
+        self.print_member_modifiers(
+            property.accessibility,
+            property.r#static,
+            r#abstract,
+            property.readonly,
+        );
+        let optional = if property.optional { "?" } else { "" };
+        if property.accessibility == Some(TSAccessibility::Private) {
+            self.print_str(&format!("{key}{optional};\n"));
+            return;
+        }
+        let ty = if let Some(ty) = &property.type_annotation {
+            Some(format!(": {}", self.source(ty.type_annotation.span())))
+        } else {
+            match &property.value {
+                None => Some(": any".to_string()),
+                Some(value) if property.readonly && is_literal(value) => {
+                    Some(format!(" = {}", self.source(value.span())))
+                }
+                Some(value) => self.infer_type(value).map(|ty| format!(": {ty}")),
+            }
+        };
+        if let Some(ty) = ty {
+            self.print_str(&format!("{key}{optional}{ty};\n"));
+        } else {
+            self.error(PropertyMustHaveExplicitType(property.key.span()));
+            self.print_str(&format!("{key}{optional};\n"));
+        }
+    }
+
+    fn print_accessor_property(&mut self, property: &AccessorProperty<'_>) {
+        if matches!(property.key, PropertyKey::PrivateIdentifier(_)) {
+            return;
+        }
+        let Some(key) = self.property_key(&property.key, property.computed) else { return };
+        self.print_member_modifiers(None, property.r#static, false, false);
+        let ty = property.value.as_ref().and_then(|value| self.infer_type(value));
+        if let Some(ty) = ty {
+            self.print_str(&format!("accessor {key}: {ty};\n"));
+        } else {
+            self.error(PropertyMustHaveExplicitType(property.key.span()));
+            self.print_str(&format!("accessor {key};\n"));
+        }
+    }
+
+    fn print_ts_module_declaration(&mut self, decl: &TSModuleDeclaration<'_>, prefix: &str) {
+        let head = Span::new(decl.span.start, decl.id.span().start).source_text(self.source_text);
+        let keyword = if head.trim_end().ends_with("module") {
+            "module "
+        } else if head.trim_end().ends_with("namespace") {
+            "namespace "
+        } else {
+            // `declare global`
+            ""
+        };
+        self.print_indent();
+        self.print_str(&format!("{prefix}{keyword}{}", self.source(decl.id.span())));
+        let mut body = &decl.body;
+        loop {
+            match body {
+                TSModuleDeclarationBody::TSModuleDeclaration(decl) => {
+                    self.print_str(&format!(".{}", decl.id.name()));
+                    body = &decl.body;
+                }
+                TSModuleDeclarationBody::TSModuleBlock(block) => {
+                    self.print_str(" {\n");
+                    self.indentation += 1;
+                    // `declare global` only augments the global scope
+                    if self.print_statements(&block.body, true) && !keyword.is_empty() {
+                        self.print_indent();
+                        self.print_str("export {};\n");
+                    }
+                    self.indentation -= 1;
+                    self.print_indent();
+                    self.print_str("}\n");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// `<T>(a: T, b?: number)`
+    fn signature_text(
+        &mut self,
+        type_parameters: Option<&TSTypeParameterDeclaration<'_>>,
+        this_param: Option<&TSThisParameter<'_>>,
+        params: &FormalParameters<'_>,
+    ) -> String {
+        let type_parameters =
+            type_parameters.map_or("", |type_parameters| self.source(type_parameters.span));
+        let params = self.params_text(this_param, params);
+        format!("{type_parameters}({params})")
+    }
+
+    fn params_text(
+        &mut self,
+        this_param: Option<&TSThisParameter<'_>>,
+        params: &FormalParameters<'_>,
+    ) -> String {
+        let mut items = vec![];
+        if let Some(this_param) = this_param {
+            items.push(self.source(this_param.span).to_string());
+        }
+        for (i, param) in params.items.iter().enumerate() {
+            // A parameter with an initializer is optional unless a required parameter follows
+            let required_follows = params.items.iter().skip(i + 1).any(|param| {
+                !param.pattern.optional
+                    && !matches!(param.pattern.kind, BindingPatternKind::AssignmentPattern(_))
+            });
+            items.push(self.param_text(&param.pattern, required_follows));
+        }
+        if let Some(rest) = &params.rest {
+            let name = self.binding_name(&rest.argument);
+            if let Some(ty) = &rest.argument.type_annotation {
+                items.push(format!("...{name}: {}", self.source(ty.type_annotation.span())));
+            } else {
+                self.error(ParameterMustHaveExplicitType(rest.span));
+                items.push(format!("...{name}"));
+            }
+        }
+        items.join(", ")
+    }
+
+    fn param_text(&mut self, pattern: &BindingPattern<'_>, required_follows: bool) -> String {
+        let (pattern, initializer) = match &pattern.kind {
+            BindingPatternKind::AssignmentPattern(pattern) => (&pattern.left, Some(&pattern.right)),
+            _ => (pattern, None),
+        };
+        let name = self.binding_name(pattern);
+        let optional = pattern.optional || (initializer.is_some() && !required_follows);
+        let optional = if optional { "?" } else { "" };
+        let ty = pattern
+            .type_annotation
+            .as_ref()
+            .map(|ty| self.source(ty.type_annotation.span()).to_string())
+            .or_else(|| initializer.and_then(|init| self.infer_type(init)));
+        let Some(ty) = ty else {
+            self.error(ParameterMustHaveExplicitType(pattern.span()));
+            return format!("{name}{optional}");
+        };
+        if initializer.is_some() && required_follows {
+            format!("{name}: {ty} | undefined")
+        } else {
+            format!("{name}{optional}: {ty}")
+        }
+    }
+
+    /// The binding without the initializers, `{ a = 1, b: [c] }` -> `{ a, b: [c] }`
+    fn binding_name(&self, pattern: &BindingPattern<'_>) -> String {
+        match &pattern.kind {
+            BindingPatternKind::BindingIdentifier(ident) => ident.name.to_string(),
+            BindingPatternKind::AssignmentPattern(pattern) => self.binding_name(&pattern.left),
+            BindingPatternKind::ObjectPattern(pattern) => {
+                let mut properties = pattern
+                    .properties
+                    .iter()
+                    .map(|property| {
+                        let value = self.binding_name(&property.value);
+                        if property.shorthand {
+                            value
+                        } else {
+                            let key = self.property_key_text(&property.key, property.computed);
+                            format!("{key}: {value}")
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(rest) = &pattern.rest {
+                    properties.push(format!("...{}", self.binding_name(&rest.argument)));
+                }
+                if properties.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", properties.join(", "))
+                }
+            }
+            BindingPatternKind::ArrayPattern(pattern) => {
+                let mut elements = pattern
+                    .elements
+                    .iter()
+                    .map(|element| {
+                        element
+                            .as_ref()
+                            .map_or_else(String::new, |element| self.binding_name(element))
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(rest) = &pattern.rest {
+                    elements.push(format!("...{}", self.binding_name(&rest.argument)));
+                }
+                format!("[{}]", elements.join(", "))
+            }
+        }
+    }
+
+    /// The key of a class member, the computed keys must be literals or entity names
+    fn property_key(&mut self, key: &PropertyKey<'_>, computed: bool) -> Option<String> {
+        if computed
+            && !matches!(
+                key,
+                PropertyKey::Expression(
+                    Expression::StringLiteral(_)
+                        | Expression::NumberLiteral(_)
+                        | Expression::Identifier(_)
+                        | Expression::MemberExpression(_)
+                )
+            )
+        {
+            self.error(ComputedPropertyName(key.span()));
+            return None;
+        }
+        Some(self.property_key_text(key, computed))
+    }
+
+    fn property_key_text(&self, key: &PropertyKey<'_>, computed: bool) -> String {
+        let text = self.source(key.span());
+        if computed {
+            format!("[{text}]")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// The type of an expression which can be known without type checking
+    fn infer_type(&mut self, expr: &Expression<'_>) -> Option<String> {
+        match expr {
+            Expression::ParenthesizedExpression(expr) => self.infer_type(&expr.expression),
+            Expression::NumberLiteral(_) => Some("number".to_string()),
+            Expression::StringLiteral(_) => Some("string".to_string()),
+            Expression::TemplateLiteral(lit) if lit.expressions.is_empty() => {
+                Some("string".to_string())
+            }
+            Expression::BooleanLiteral(_) => Some("boolean".to_string()),
+            Expression::BigintLiteral(_) => Some("bigint".to_string()),
+            Expression::UnaryExpression(expr)
+                if matches!(
+                    expr.operator,
+                    UnaryOperator::UnaryNegation | UnaryOperator::UnaryPlus
+                ) && matches!(expr.argument, Expression::NumberLiteral(_)) =>
+            {
+                Some("number".to_string())
+            }
+            Expression::TSAsExpression(expr) if !expr.type_annotation.is_const_type_reference() => {
+                Some(self.source(expr.type_annotation.span()).to_string())
+            }
+            Expression::TSTypeAssertion(expr)
+                if !expr.type_annotation.is_const_type_reference() =>
+            {
+                Some(self.source(expr.type_annotation.span()).to_string())
+            }
+            Expression::TSSatisfiesExpression(expr) => self.infer_type(&expr.expression),
+            Expression::ArrowExpression(arrow) => {
+                let return_type = arrow.return_type.as_ref()?;
+                let signature =
+                    self.signature_text(arrow.type_parameters.as_deref(), None, &arrow.params);
+                Some(format!("{signature} => {}", self.source(return_type.type_annotation.span())))
+            }
+            Expression::FunctionExpression(func) => {
+                let return_type = func.return_type.as_ref()?;
+                let signature = self.signature_text(
+                    func.type_parameters.as_deref(),
+                    func.this_param.as_ref(),
+                    &func.params,
+                );
+                Some(format!("{signature} => {}", self.source(return_type.type_annotation.span())))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The literals which are kept as the initializers of the `const` declarations and the readonly
+/// properties, `declare const a = 1`
+fn is_literal(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::NumberLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::BigintLiteral(_) => true,
+        Expression::UnaryExpression(expr) => {
+            expr.operator == UnaryOperator::UnaryNegation
+                && matches!(expr.argument, Expression::NumberLiteral(_))
+        }
+        _ => false,
+    }
+}
+
+fn is_private_element(element: &ClassElement<'_>) -> bool {
+    match element {
+        ClassElement::MethodDefinition(method) => {
+            matches!(method.key, PropertyKey::PrivateIdentifier(_))
+        }
+        ClassElement::PropertyDefinition(property) => {
+            matches!(property.key, PropertyKey::PrivateIdentifier(_))
+        }
+        ClassElement::AccessorProperty(property) => {
+            matches!(property.key, PropertyKey::PrivateIdentifier(_))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::IsolatedDeclarations;
+
+    fn emit(source_text: &str) -> (String, Vec<String>) {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true).with_module(true);
+        let program = Parser::new(&allocator, source_text, source_type).parse().program;
+        let ret = IsolatedDeclarations::new(source_text).build(&program);
+        (ret.source_text, ret.errors.iter().map(ToString::to_string).collect())
+    }
+
+    fn test(source_text: &str, expected: &str) {
+        let (code, errors) = emit(source_text);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(code, expected);
+    }
+
+    fn test_error(source_text: &str, code: &str) {
+        let (_, errors) = emit(source_text);
+        assert!(errors.iter().any(|error| error.starts_with(code)), "{errors:?}");
+    }
+
+    #[test]
+    fn variables() {
+        test(
+            "export const a = 1, b: string = c(); export let d = 'd', e: E;",
+            "export declare const a = 1, b: string;\nexport declare let d: string, e: E;\n",
+        );
+        test(
+            "export const f = (a: number, b = 1): void => {};",
+            "export declare const f: (a: number, b?: number) => void;\n",
+        );
+        test_error("export const a = b();", "TS9010");
+        test_error("export const { a } = b;", "TS9019");
+    }
+
+    #[test]
+    fn functions() {
+        test(
+            "export function f<T>(a: T, { b, c = 1 }: O, d = 2, ...e: T[]): T { return a }",
+            "export declare function f<T>(a: T, { b, c }: O, d?: number, ...e: T[]): T;\n",
+        );
+        test(
+            "export function f(a: string): void;\nexport function f(a: number): void;\nexport function f(a: any) {}",
+            "export declare function f(a: string): void;\nexport declare function f(a: number): void;\n",
+        );
+        test(
+            "export default function (a = 1, b: B): void {}",
+            "export default function(a: number | undefined, b: B): void;\n",
+        );
+        test_error("export function f(a: string) {}", "TS9007");
+        test_error("export function f(a): void {}", "TS9011");
+    }
+
+    #[test]
+    fn classes() {
+        test(
+            "export class A<T> extends B<T> implements C {
+                #a = 1;
+                static b = 1;
+                private c: string;
+                readonly d = 'd';
+                constructor(public e: number, private f: string) { super() }
+                get g(): number { return 1 }
+                set g(value) {}
+                m?(a: T): void {}
+                static {}
+            }",
+            "export declare class A<T> extends B<T> implements C {\n    #private;\n    static b: number;\n    private c;\n    readonly d = 'd';\n    e: number;\n    private f;\n    constructor(e: number, f: string);\n    get g(): number;\n    set g(value: number);\n    m?(a: T): void;\n}\n",
+        );
+        test(
+            "export abstract class A { abstract m(): void }",
+            "export declare abstract class A {\n    abstract m(): void;\n}\n",
+        );
+        test_error("export class A extends f() {}", "TS9021");
+        test_error("export class A { m() {} }", "TS9008");
+        test_error("export class A { a = b }", "TS9012");
+        test_error("export class A { [a()]: number }", "TS9038");
+    }
+
+    #[test]
+    fn types() {
+        test(
+            "import { A } from 'a';\nexport interface B extends A { b: string }\nexport type C = A | B\nexport enum D { E = 1 }\nexport * from 'f';\nexport { A };",
+            "import { A } from 'a';\nexport interface B extends A { b: string }\nexport type C = A | B;\nexport declare enum D { E = 1 }\nexport * from 'f';\nexport { A };\n",
+        );
+    }
+
+    #[test]
+    fn modules() {
+        test(
+            "export namespace A.B { export const c = 1; function d(): void {} }",
+            "export declare namespace A.B {\n    export const c = 1;\n    function d(): void;\n    export {};\n}\n",
+        );
+        test(
+            "interface A {}\nexport const a: A = {};",
+            "interface A {}\nexport declare const a: A;\nexport {};\n",
+        );
+        test(
+            "declare global { interface Window {} }",
+            "declare global {\n    interface Window {}\n}\n",
+        );
+    }
+
+    #[test]
+    fn default_exports() {
+        test("export default a;", "export default a;\n");
+        test("export default 1;", "declare const _default: number;\nexport default _default;\n");
+        test_error("export default f();", "TS9037");
+    }
+}
@@ -4,19 +4,25 @@
 //!
 //! * whitespace removal
 //! * sourcemaps
+//! * TypeScript declarations (`.d.ts`)
 //!
 //! Code adapted from
 //! * [esbuild](https://github.com/evanw/esbuild/blob/main/internal/js_printer/js_printer.go)
 
+mod comment;
 mod context;
+mod escape;
 mod gen;
+mod isolated_declarations;
 mod operator;
+mod options;
+mod sourcemap;
 
 use std::str::from_utf8_unchecked;
 
 #[allow(clippy::wildcard_imports)]
-use oxc_ast::ast::*;
-use oxc_span::Atom;
+use oxc_ast::{ast::*, Trivias};
+use oxc_span::{Atom, Span};
 use oxc_syntax::{
     identifier::is_identifier_part,
     operator::{BinaryOperator, UnaryOperator, UpdateOperator},
@@ -25,17 +31,19 @@ use oxc_syntax::{
 };
 
 pub use crate::{
+    comment::CommentOptions,
     context::Context,
     gen::{Gen, GenExpr},
+    isolated_declarations::{IsolatedDeclarations, IsolatedDeclarationsReturn},
     operator::Operator,
+    options::{Charset, CodegenOptions, IndentStyle, QuoteStyle, SemicolonStyle},
+    sourcemap::SourceMapOptions,
 };
+use crate::{comment::Comments, sourcemap::SourceMapBuilder};
+pub use oxc_sourcemap::SourceMap;
 // use crate::mangler::Mangler;
 
-#[derive(Debug, Default, Clone, Copy)]
-pub struct CodegenOptions;
-
 pub struct Codegen<const MINIFY: bool> {
-    #[allow(unused)]
     options: CodegenOptions,
 
     // mangler: Option<Mangler>,
@@ -50,6 +58,10 @@ pub struct Codegen<const MINIFY: bool> {
     /// For avoiding `;` if the previous statement ends with `}`.
     needs_semicolon: bool,
 
+    /// The semicolon of the previous statement is omitted by [SemicolonStyle::AsNeeded], it is
+    /// printed before the next statement if that one would continue the previous statement.
+    omitted_semicolon: bool,
+
     prev_op: Option<Operator>,
 
     start_of_stmt: usize,
@@ -58,6 +70,16 @@ pub struct Codegen<const MINIFY: bool> {
 
     /// Track the current indentation level
     indentation: u8,
+
+    source_map: Option<SourceMapBuilder>,
+
+    comments: Option<Comments>,
+    comment_options: CommentOptions,
+}
+
+pub struct CodegenReturn {
+    pub source_text: String,
+    pub source_map: Option<SourceMap>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -77,6 +99,7 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
             // mangler: None,
             code: Vec::with_capacity(capacity),
             needs_semicolon: false,
+            omitted_semicolon: false,
             need_space_before_dot: 0,
             prev_op_end: 0,
             prev_reg_exp_end: 0,
@@ -85,16 +108,59 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
             start_of_arrow_expr: 0,
             start_of_default_export: 0,
             indentation: 0,
+            source_map: None,
+            comments: None,
+            comment_options: CommentOptions::default(),
         }
     }
 
+    /// Print the comments of `trivias` from the original `source_text`, see [CommentOptions]
+    #[must_use]
+    pub fn with_comments(
+        mut self,
+        source_text: &str,
+        trivias: Trivias,
+        options: CommentOptions,
+    ) -> Self {
+        self.comments = Some(Comments::new(source_text, trivias));
+        self.comment_options = options;
+        self
+    }
+
+    /// Generate a source map of the printed code, which maps to the positions of `source_text`
+    #[must_use]
+    pub fn with_source_map(mut self, source_text: &str, options: SourceMapOptions) -> Self {
+        self.source_map = Some(SourceMapBuilder::new(source_text, options));
+        self
+    }
+
     // fn with_mangler(&mut self, mangler: Mangler) {
     // self.mangler = Some(mangler);
     // }
 
-    pub fn build(mut self, program: &Program<'_>) -> String {
+    pub fn build(self, program: &Program<'_>) -> String {
+        self.build_with_source_map(program).source_text
+    }
+
+    /// The printed code and its source map, see [Codegen::with_source_map].
+    /// The source map is appended to the code when it is [SourceMapOptions::inline].
+    pub fn build_with_source_map(mut self, program: &Program<'_>) -> CodegenReturn {
         program.gen(&mut self, Context::default());
-        self.into_code()
+        self.print_trailing_newline();
+        let source_map = self.source_map.take().map(|builder| {
+            let is_inline = builder.is_inline();
+            let source_map = builder.into_source_map();
+            if is_inline {
+                if self.code.last().is_some_and(|ch| *ch != b'\n') {
+                    self.print(b'\n');
+                }
+                self.print_str(b"//# sourceMappingURL=");
+                self.print_str(source_map.to_data_url().as_bytes());
+                self.print(b'\n');
+            }
+            source_map
+        });
+        CodegenReturn { source_text: self.into_code(), source_map }
     }
 
     pub fn into_code(self) -> String {
@@ -112,14 +178,52 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
 
     /// Push a single character into the buffer
     pub fn print(&mut self, ch: u8) {
+        if self.omitted_semicolon {
+            self.print_semicolon_for_asi(ch);
+        }
         self.code.push(ch);
     }
 
     /// Push a string into the buffer
     pub fn print_str(&mut self, s: &[u8]) {
+        if self.omitted_semicolon {
+            if let Some(i) = s.iter().position(|ch| !ch.is_ascii_whitespace()) {
+                self.code.extend_from_slice(&s[..i]);
+                self.print_semicolon_for_asi(s[i]);
+                self.code.extend_from_slice(&s[i..]);
+                return;
+            }
+        }
         self.code.extend_from_slice(s);
     }
 
+    /// `a\n(b)` is `a(b)`, the statement after an omitted semicolon must not start with a
+    /// character which continues the previous statement
+    fn print_semicolon_for_asi(&mut self, ch: u8) {
+        if ch.is_ascii_whitespace() {
+            return;
+        }
+        self.omitted_semicolon = false;
+        if matches!(ch, b'(' | b'[' | b'`' | b'+' | b'-' | b'/' | b'*') {
+            self.code.push(b';');
+        }
+    }
+
+    fn print_trailing_newline(&mut self) {
+        if MINIFY {
+            return;
+        }
+        if self.options.trailing_newline {
+            if self.code.last().is_some_and(|ch| *ch != b'\n') {
+                self.code.push(b'\n');
+            }
+        } else {
+            while self.code.last() == Some(&b'\n') {
+                self.code.pop();
+            }
+        }
+    }
+
     fn print_soft_space(&mut self) {
         if !MINIFY {
             self.print(b' ');
@@ -153,6 +257,51 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
         }
     }
 
+    /// `/* #__PURE__ */` and `/* #__NO_SIDE_EFFECTS__ */` for the bundlers and minifiers
+    /// which process the output.
+    fn print_annotation_comment(&mut self, annotation: &[u8]) {
+        if !self.comment_options.preserve_annotation_comments {
+            return;
+        }
+        // `a / /* */` is not a line comment
+        if self.peek_nth(0) == Some('/') {
+            self.print_hard_space();
+        }
+        let n = self.code_len();
+        self.print_str(b"/*");
+        self.print_soft_space();
+        self.print_str(annotation);
+        self.print_soft_space();
+        self.print_str(b"*/");
+        self.print_soft_space();
+        // The annotated expression is still at the start of the statement, e.g. `({}).a()`
+        let end = self.code_len();
+        for start in [
+            &mut self.start_of_stmt,
+            &mut self.start_of_arrow_expr,
+            &mut self.start_of_default_export,
+        ] {
+            if *start == n {
+                *start = end;
+            }
+        }
+    }
+
+    /// Maps the current position of the printed code to the start of `span`
+    fn add_source_mapping(&mut self, span: Span) {
+        if let Some(source_map) = &mut self.source_map {
+            source_map.add_mapping(&self.code, span, false);
+        }
+    }
+
+    /// Maps the current position of the printed code to the identifier at `span`, which is
+    /// recorded with its original name
+    fn add_source_mapping_for_name(&mut self, span: Span) {
+        if let Some(source_map) = &mut self.source_map {
+            source_map.add_mapping(&self.code, span, true);
+        }
+    }
+
     fn peek_nth(&self, n: usize) -> Option<char> {
         // SAFETY: criteria of `from_utf8_unchecked`.are met.
         unsafe { from_utf8_unchecked(self.code()) }.chars().nth_back(n)
@@ -172,8 +321,9 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
 
     fn print_indent(&mut self) {
         if !MINIFY {
-            for _ in 0..self.indentation {
-                self.print(b'\t');
+            let ch = self.options.indent_style.as_byte();
+            for _ in 0..usize::from(self.indentation) * usize::from(self.options.indent_width) {
+                self.code.push(ch);
             }
         }
     }
@@ -181,6 +331,9 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
     fn print_semicolon_after_statement(&mut self) {
         if MINIFY {
             self.needs_semicolon = true;
+        } else if self.options.semicolons == SemicolonStyle::AsNeeded {
+            self.print(b'\n');
+            self.omitted_semicolon = true;
         } else {
             self.print_str(b";\n");
         }
@@ -282,7 +435,7 @@ impl<const MINIFY: bool> Codegen<MINIFY> {
         // return;
         // }
         // }
-        self.print_str(fallback.as_bytes());
+        self.print_identifier(fallback.as_str());
     }
 
     fn print_space_before_operator(&mut self, next: Operator) {
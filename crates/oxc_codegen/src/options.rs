@@ -0,0 +1,201 @@
+//! The style of the printed code
+
+#[derive(Debug, Clone, Copy)]
+pub struct CodegenOptions {
+    /// The quotes of the string literals and the module specifiers.
+    ///
+    /// Default [QuoteStyle::Single]
+    pub quote_style: QuoteStyle,
+
+    /// Print a semicolon after every statement, or only where the next line would continue the
+    /// statement. The minified code only has the semicolons which are needed between the statements.
+    ///
+    /// Default [SemicolonStyle::Always]
+    pub semicolons: SemicolonStyle,
+
+    /// The character of the indentation, ignored when minifying.
+    ///
+    /// Default [IndentStyle::Tab]
+    pub indent_style: IndentStyle,
+
+    /// The number of indentation characters for each level, ignored when minifying.
+    ///
+    /// Default `1`
+    pub indent_width: u8,
+
+    /// End the code with a newline, ignored when minifying.
+    ///
+    /// Default `true`
+    pub trailing_newline: bool,
+
+    /// Print the non-ASCII characters as escape sequences or as they are.
+    ///
+    /// Default [Charset::Utf8]
+    pub charset: Charset,
+
+    /// Escape `</script` in the strings, template literals and regular expressions, so that the
+    /// code can be inlined in an HTML `<script>` tag.
+    ///
+    /// Default `false`
+    pub escape_script_end_tags: bool,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            quote_style: QuoteStyle::Single,
+            semicolons: SemicolonStyle::Always,
+            indent_style: IndentStyle::Tab,
+            indent_width: 1,
+            trailing_newline: true,
+            charset: Charset::Utf8,
+            escape_script_end_tags: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// `'foo'`
+    Single,
+    /// `"foo"`
+    Double,
+}
+
+impl QuoteStyle {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Self::Single => b'\'',
+            Self::Double => b'"',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemicolonStyle {
+    /// `a;\nb;\n`
+    Always,
+    /// `a\nb\n`, and `a\n;(b)\n` where the next line starts with `(`, `[`, `` ` ``, `+`, `-`,
+    /// `/` or `*`
+    AsNeeded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+impl IndentStyle {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Self::Tab => b'\t',
+            Self::Space => b' ',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    /// `'\xE9t\xE9'`, `\u00E9t\u00E9` and `&#xE9;t&#xE9;`, for the consumers which do not
+    /// decode the code as UTF-8, e.g. with a latin-1 `Content-Type`
+    Ascii,
+    /// `'été'`, `été` and `été`
+    Utf8,
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::{Charset, CodegenOptions, IndentStyle, QuoteStyle, SemicolonStyle};
+    use crate::Codegen;
+
+    fn print<const MINIFY: bool>(source_text: &str, options: CodegenOptions) -> String {
+        let allocator = Allocator::default();
+        let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+        Codegen::<MINIFY>::new(source_text.len(), options).build(&program)
+    }
+
+    #[test]
+    fn quote_style() {
+        let options =
+            CodegenOptions { quote_style: QuoteStyle::Double, ..CodegenOptions::default() };
+        assert_eq!(print::<true>("import 'a'; let b = 'b'", options), r#"import"a";let b="b";"#);
+        assert_eq!(print::<true>("let b = 'b'", CodegenOptions::default()), "let b='b';");
+    }
+
+    #[test]
+    fn semicolons() {
+        let options =
+            CodegenOptions { semicolons: SemicolonStyle::AsNeeded, ..CodegenOptions::default() };
+        assert_eq!(print::<false>("a; b; let c = d", options), "a\nb\nlet c = d\n");
+        assert_eq!(
+            print::<false>("a; ({}).b; [c]; `d`; /e/", options),
+            "a\n;({}).b\n;[c]\n;`d`\n;/e/\n"
+        );
+        assert_eq!(print::<false>("if (a) {\n  b\n}\n[c]", options), "if (a) {\n\tb\n}\n[c]\n");
+    }
+
+    #[test]
+    fn indentation() {
+        let options = CodegenOptions {
+            indent_style: IndentStyle::Space,
+            indent_width: 2,
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            print::<false>("if (a) { if (b) { c } }", options),
+            "if (a) {\n  if (b) {\n    c;\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn trailing_newline() {
+        let options = CodegenOptions { trailing_newline: false, ..CodegenOptions::default() };
+        assert_eq!(print::<false>("a; b", options), "a;\nb;");
+        assert_eq!(print::<false>("'use strict'", CodegenOptions::default()), "'use strict';\n");
+        assert_eq!(print::<true>("a", CodegenOptions::default()), "a;");
+    }
+
+    #[test]
+    fn charset() {
+        let source_text = "let été = 'café…😀'; /é😀/u; `é\\é`; x.é; <a b='é'>é</a>";
+        let source_type = SourceType::default().with_jsx(true);
+        let allocator = Allocator::default();
+        let program = Parser::new(&allocator, source_text, source_type).parse().program;
+        let print_jsx = |options| Codegen::<true>::new(source_text.len(), options).build(&program);
+        let ascii = CodegenOptions { charset: Charset::Ascii, ..CodegenOptions::default() };
+        assert_eq!(
+            print_jsx(ascii),
+            r"let \u00E9t\u00E9='caf\xE9\u2026\uD83D\uDE00';/\u00E9\u{1F600}/u;`\xE9\xE9`;x.\u00E9;<a b='&#xE9;'>&#xE9;</a>;"
+        );
+        assert_eq!(
+            print_jsx(CodegenOptions::default()),
+            "let été='café…😀';/é😀/u;`é\\é`;x.é;<a b='é'>é</a>;"
+        );
+        // The raw text of the tagged templates is kept
+        assert_eq!(print::<true>("String.raw`é`", ascii), "String.raw`é`;");
+        assert_eq!(print::<true>("/\\é😀/", ascii), r"/\u00E9\uD83D\uDE00/;");
+    }
+
+    #[test]
+    fn escape_script_end_tags() {
+        let source_text = "x = '</script>'; y = `</SCRIPT>`";
+        let options = CodegenOptions { escape_script_end_tags: true, ..CodegenOptions::default() };
+        assert_eq!(print::<true>(source_text, options), r"x='<\/script>';y=`<\/SCRIPT>`;");
+        assert_eq!(
+            print::<true>(source_text, CodegenOptions::default()),
+            "x='</script>';y=`</SCRIPT>`;"
+        );
+    }
+
+    #[test]
+    fn control_characters() {
+        let options = CodegenOptions::default();
+        assert_eq!(print::<true>(r"'\0\b\f\n\r\t\v\x01\\'", options), r"'\0\b\f\n\r\t\v\x01\\';");
+        assert_eq!(print::<true>(r"'\x001'", options), r"'\x001';");
+    }
+}
@@ -0,0 +1,190 @@
+//! Source Map Revision 3
+//!
+//! <https://sourcemaps.info/spec.html>
+
+use std::collections::HashMap;
+
+use oxc_sourcemap::{SourceMap, Token};
+use oxc_span::{LineColumn, LineIndex, Span};
+
+#[derive(Debug, Default, Clone)]
+pub struct SourceMapOptions {
+    /// The name of the original file in `sources`, e.g. `src/index.ts`
+    pub source_name: String,
+    /// Append the source map to the code as a `//# sourceMappingURL=data:...` comment
+    pub inline: bool,
+}
+
+/// Records the mappings from the positions of the printed code to the positions of the original
+/// source while the code is printed
+pub struct SourceMapBuilder {
+    options: SourceMapOptions,
+    source_text: String,
+    /// The lines and the UTF-16 columns of the original source
+    line_index: LineIndex,
+
+    names: Vec<String>,
+    name_indexes: HashMap<String, u32>,
+    tokens: Vec<Token>,
+
+    /// The generated position at `generated_offset` of the code
+    generated_offset: usize,
+    generated_line: u32,
+    generated_column: u32,
+
+    /// The token which is not added yet, it is replaced by the following token of the same
+    /// generated position when that one has a name
+    pending: Option<Token>,
+}
+
+impl SourceMapBuilder {
+    pub fn new(source_text: &str, options: SourceMapOptions) -> Self {
+        Self {
+            options,
+            source_text: source_text.to_string(),
+            line_index: LineIndex::new(source_text),
+            names: vec![],
+            name_indexes: HashMap::new(),
+            tokens: vec![],
+            generated_offset: 0,
+            generated_line: 0,
+            generated_column: 0,
+            pending: None,
+        }
+    }
+
+    pub fn is_inline(&self) -> bool {
+        self.options.inline
+    }
+
+    /// Maps the end of `code` to the start of `span` of the original source.
+    /// `name` is the original name of an identifier at `span`.
+    pub fn add_mapping(&mut self, code: &[u8], span: Span, name: bool) {
+        // The nodes created by the transformers and the minifier have no position
+        if span.end == 0 || span.end as usize > self.source_text.len() {
+            return;
+        }
+        self.advance_generated_position(code);
+        let (original_line, original_column) = self.original_position(span.start);
+        let name = name.then(|| self.name_index(span));
+        let token = Token {
+            dst_line: self.generated_line,
+            dst_col: self.generated_column,
+            src_line: original_line,
+            src_col: original_column,
+            // The only source
+            source_id: Some(0),
+            name_id: name,
+        };
+        if let Some(pending) = &mut self.pending {
+            if pending.dst_line == token.dst_line && pending.dst_col == token.dst_col {
+                if token.name_id.is_some() {
+                    *pending = token;
+                }
+                return;
+            }
+        }
+        self.flush();
+        self.pending = Some(token);
+    }
+
+    pub fn into_source_map(mut self) -> SourceMap {
+        self.flush();
+        SourceMap {
+            sources: vec![self.options.source_name],
+            sources_content: vec![Some(self.source_text)],
+            names: self.names,
+            tokens: self.tokens,
+        }
+    }
+
+    /// Counts the lines and the UTF-16 columns of the code printed since the last mapping
+    fn advance_generated_position(&mut self, code: &[u8]) {
+        for byte in &code[self.generated_offset..] {
+            match byte {
+                b'\n' => {
+                    self.generated_line += 1;
+                    self.generated_column = 0;
+                }
+                // The first byte of a four bytes character, which is a surrogate pair in UTF-16
+                0xF0.. => self.generated_column += 2,
+                // The continuation bytes of a character
+                0x80..=0xBF => {}
+                _ => self.generated_column += 1,
+            }
+        }
+        self.generated_offset = code.len();
+    }
+
+    fn original_position(&self, offset: u32) -> (u32, u32) {
+        let LineColumn { line, column } = self.line_index.line_column_utf16(offset);
+        (line, column)
+    }
+
+    fn name_index(&mut self, span: Span) -> u32 {
+        let name = span.source_text(&self.source_text);
+        if let Some(index) = self.name_indexes.get(name) {
+            return *index;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.name_indexes.insert(name.to_string(), index);
+        index
+    }
+
+    fn flush(&mut self) {
+        if let Some(token) = self.pending.take() {
+            self.tokens.push(token);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::SourceMapOptions;
+    use crate::{Codegen, CodegenOptions};
+
+    fn build(source_text: &str, inline: bool) -> crate::CodegenReturn {
+        let allocator = Allocator::default();
+        let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+        let options = SourceMapOptions { source_name: "test.js".into(), inline };
+        Codegen::<true>::new(source_text.len(), CodegenOptions::default())
+            .with_source_map(source_text, options)
+            .build_with_source_map(&program)
+    }
+
+    #[test]
+    fn mappings() {
+        let ret = build("let foo = 1;\n\nbar(foo);", false);
+        assert_eq!(ret.source_text, "let foo=1;bar(foo);");
+        let source_map = ret.source_map.unwrap();
+        assert_eq!(source_map.sources, vec!["test.js"]);
+        assert_eq!(source_map.names, vec!["foo", "bar"]);
+        assert_eq!(source_map.encode_mappings(), "AAAA,IAAIA,IAAM,EAEVC,IAAID");
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let ret = build("`😀`; a;", false);
+        assert_eq!(ret.source_text, "`😀`;a;");
+        // `a` is at the column 6 of the original code and at the column 5 of the printed code
+        assert_eq!(ret.source_map.unwrap().encode_mappings(), "AAAA,KAAMA");
+    }
+
+    #[test]
+    fn inline() {
+        let ret = build("a", true);
+        let source_map = ret.source_map.unwrap();
+        let url = source_map.to_data_url();
+        assert_eq!(ret.source_text, format!("a;\n//# sourceMappingURL={url}\n"));
+        assert_eq!(
+            source_map.to_json_string(),
+            r#"{"version":3,"sources":["test.js"],"sourcesContent":["a"],"names":["a"],"mappings":"AAAAA"}"#
+        );
+    }
+}
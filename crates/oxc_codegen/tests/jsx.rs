@@ -0,0 +1,47 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn print<const MINIFY: bool>(source_text: &str) -> String {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_jsx(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    assert!(ret.errors.is_empty(), "{source_text}");
+    Codegen::<MINIFY>::new(source_text.len(), CodegenOptions::default()).build(&ret.program)
+}
+
+/// The printed JSX is parsed and printed again to the same code
+fn test(source_text: &str, expected: &str) {
+    let printed = print::<false>(source_text);
+    assert_eq!(printed, expected);
+    assert_eq!(print::<false>(&printed), expected);
+}
+
+#[test]
+fn elements() {
+    test("<div />", "<div/>;\n");
+    test("<a.b.c></a.b.c>", "<a.b.c></a.b.c>;\n");
+    test("<svg:rect />", "<svg:rect/>;\n");
+    test("<>text<b>{x}</b></>", "<>text<b>{x}</b></>;\n");
+    test("<div>{/* comment */}</div>", "<div>{}</div>;\n");
+}
+
+#[test]
+fn attributes() {
+    test(r#"<a b="c" d={e} {...f} g />"#, "<a b='c' d={e} {...f} g/>;\n");
+    test(r#"<a b="'" c='"' d="\" />"#, "<a b=\"'\" c='\"' d='\\'/>;\n");
+    test("<a b=<c /> />", "<a b=<c/>/>;\n");
+    test("<a xlink:href='#' />", "<a xlink:href='#'/>;\n");
+}
+
+#[test]
+fn children() {
+    test("<a>{...b}</a>", "<a>{...b}</a>;\n");
+    test("<a>\n  &nbsp;text\n</a>", "<a>\n  &nbsp;text\n</a>;\n");
+}
+
+#[test]
+fn minify() {
+    assert_eq!(print::<true>("const a = <b c='d'>{ e }</b>"), "const a=<b c='d'>{e}</b>;");
+}
@@ -0,0 +1,132 @@
+//! The services of the methods, which analyze a single file
+
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use oxc::{
+    allocator::Allocator,
+    diagnostics::{Error, SerializedDiagnostic},
+    parser::{Parser, ParserReturn},
+    semantic::{SemanticBuilder, SemanticBuilderReturn},
+    span::SourceType,
+};
+use oxc_linter::{LintContext, Linter};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// The file of a request, its source text is read from `path` when it is not sent
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    pub path: PathBuf,
+    pub source_text: Option<String>,
+    /// `script` or `module`, overrides the source type of the extension of `path`
+    pub source_type: Option<String>,
+}
+
+impl Document {
+    /// # Errors
+    ///
+    /// * The file cannot be read
+    pub fn source_text(&self) -> Result<String, String> {
+        self.source_text.as_ref().map_or_else(
+            || {
+                std::fs::read_to_string(&self.path)
+                    .map_err(|error| format!("Failed to read {}: {error}", self.path.display()))
+            },
+            |source_text| Ok(source_text.clone()),
+        )
+    }
+
+    fn source_type(&self) -> SourceType {
+        let source_type = SourceType::from_path(&self.path).unwrap_or_default();
+        match self.source_type.as_deref() {
+            Some("script") => source_type.with_script(true),
+            Some("module") => source_type.with_module(true),
+            _ => source_type,
+        }
+    }
+
+    fn parse<'a>(&self, allocator: &'a Allocator, source_text: &'a str) -> ParserReturn<'a> {
+        Parser::new(allocator, source_text, self.source_type()).parse()
+    }
+
+    fn build_semantic<'a>(
+        &self,
+        allocator: &'a Allocator,
+        source_text: &'a str,
+    ) -> Result<SemanticBuilderReturn<'a>, Vec<Error>> {
+        let ret = self.parse(allocator, source_text);
+        if !ret.errors.is_empty() {
+            return Err(ret.errors);
+        }
+        let program = allocator.alloc(ret.program);
+        Ok(SemanticBuilder::new(source_text, self.source_type())
+            .with_trivias(ret.trivias)
+            .with_check_syntax_error(true)
+            .build(program))
+    }
+}
+
+/// `{ program, comments, errors }`, the program is an ESTree AST
+pub fn parse(allocator: &Allocator, document: &Document, source_text: &str) -> Value {
+    let ret = document.parse(allocator, source_text);
+    json!({
+        "program": ret.program.to_estree(source_text),
+        "comments": ret.trivias.to_estree_comments(source_text),
+        "errors": SerializedDiagnostic::from_errors(&ret.errors),
+    })
+}
+
+/// `{ symbols, errors }`, the symbols are the declared names with their references
+pub fn semantic(allocator: &Allocator, document: &Document, source_text: &str) -> Value {
+    let ret = match document.build_semantic(allocator, source_text) {
+        Ok(ret) => ret,
+        Err(errors) => {
+            return json!({ "symbols": [], "errors": SerializedDiagnostic::from_errors(&errors) })
+        }
+    };
+    let symbols = ret.semantic.symbols();
+    let symbols = symbols
+        .iter()
+        .map(|symbol_id| {
+            let span = symbols.get_span(symbol_id);
+            let references = symbols
+                .get_resolved_references(symbol_id)
+                .map(|reference| json!({ "start": reference.span().start, "end": reference.span().end }))
+                .collect::<Vec<_>>();
+            json!({
+                "name": symbols.get_name(symbol_id).as_str(),
+                "flags": format!("{:?}", symbols.get_flag(symbol_id)),
+                "start": span.start,
+                "end": span.end,
+                "references": references,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "symbols": symbols, "errors": SerializedDiagnostic::from_errors(&ret.errors) })
+}
+
+/// `{ path, diagnostics }`, with the syntax errors and the diagnostics of the rules
+pub fn lint(
+    allocator: &Allocator,
+    document: &Document,
+    source_text: &str,
+    linter: &Linter,
+) -> Value {
+    let diagnostics = match document.build_semantic(allocator, source_text) {
+        Ok(ret) => {
+            let mut diagnostics = SerializedDiagnostic::from_errors(&ret.errors);
+            let semantic = Rc::new(ret.semantic);
+            let path: Box<Path> = document.path.clone().into_boxed_path();
+            let lint_ctx = LintContext::new(path, &semantic);
+            let errors = linter.run(lint_ctx).into_iter().map(|message| message.error);
+            diagnostics.extend(SerializedDiagnostic::from_errors(&errors.collect::<Vec<_>>()));
+            diagnostics
+        }
+        Err(errors) => SerializedDiagnostic::from_errors(&errors),
+    };
+    json!({ "path": document.path, "diagnostics": diagnostics })
+}
@@ -0,0 +1,155 @@
+//! A long-lived process serving the parser, the semantic analysis and the linter to the tools
+//! which are not written in Rust, e.g. build systems and editors.
+//!
+//! The client and the server exchange JSON-RPC 2.0 messages framed by `Content-Length` headers,
+//! over the standard input and output or a TCP connection. See the README for the methods.
+
+mod analysis;
+pub mod protocol;
+mod service;
+
+use std::io::{self, BufRead, Write};
+
+pub use crate::service::{Service, METHODS};
+
+/// Handles the messages of `reader` until the `exit` notification or the end of the input
+///
+/// # Errors
+///
+/// * The messages cannot be read or written
+pub fn serve<R: BufRead, W: Write>(mut reader: R, mut writer: W) -> io::Result<()> {
+    let mut service = Service::default();
+    while let Some(content) = protocol::read_message(&mut reader)? {
+        for message in service.handle(&content) {
+            protocol::write_message(&mut writer, &message)?;
+        }
+        if service.exited() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, io::Cursor};
+
+    use serde_json::{json, Value};
+
+    use crate::{
+        protocol::{error_code, read_message, write_message, PROTOCOL_VERSION},
+        serve, Service,
+    };
+
+    fn initialized() -> Service {
+        let mut service = Service::default();
+        let request = json!({ "id": 0, "method": "initialize", "params": { "protocolVersion": PROTOCOL_VERSION } });
+        let responses = service.handle(&request.to_string());
+        assert_eq!(responses[0]["result"]["protocolVersion"], PROTOCOL_VERSION);
+        service
+    }
+
+    fn request(service: &mut Service, method: &str, params: &Value) -> Vec<Value> {
+        service.handle(
+            &json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string(),
+        )
+    }
+
+    #[test]
+    fn initialize() {
+        let mut service = Service::default();
+        let responses =
+            request(&mut service, "parse", &json!({ "path": "a.js", "sourceText": "" }));
+        assert_eq!(responses[0]["error"]["code"], error_code::SERVER_NOT_INITIALIZED);
+
+        let responses = request(&mut service, "initialize", &json!({ "protocolVersion": 0 }));
+        assert_eq!(responses[0]["error"]["code"], error_code::UNSUPPORTED_PROTOCOL_VERSION);
+
+        let responses =
+            request(&mut service, "initialize", &json!({ "protocolVersion": PROTOCOL_VERSION }));
+        assert_eq!(responses[0]["result"]["serverInfo"]["name"], "oxc_daemon");
+        assert_eq!(responses[0]["id"], 1);
+    }
+
+    #[test]
+    fn invalid_messages() {
+        let mut service = initialized();
+        let responses = service.handle("{");
+        assert_eq!(responses[0]["error"]["code"], error_code::PARSE_ERROR);
+        let responses = request(&mut service, "format", &json!({}));
+        assert_eq!(responses[0]["error"]["code"], error_code::METHOD_NOT_FOUND);
+        let responses = request(&mut service, "parse", &json!({ "sourceText": "" }));
+        assert_eq!(responses[0]["error"]["code"], error_code::INVALID_PARAMS);
+        let responses = request(&mut service, "parse", &json!({ "path": "missing/file.js" }));
+        assert_eq!(responses[0]["error"]["code"], error_code::FILE_NOT_READABLE);
+    }
+
+    #[test]
+    fn parse() {
+        let mut service = initialized();
+        let params = json!({ "path": "a.js", "sourceText": "foo // bar" });
+        let result = &request(&mut service, "parse", &params)[0]["result"];
+        assert_eq!(result["program"]["type"], "Program");
+        assert_eq!(
+            result["comments"][0],
+            json!({ "type": "Line", "value": " bar", "start": 4, "end": 10 })
+        );
+        assert_eq!(result["errors"], json!([]));
+
+        let params = json!({ "path": "a.js", "sourceText": "let" });
+        let result = &request(&mut service, "parse", &params)[0]["result"];
+        assert_eq!(result["errors"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn semantic() {
+        let mut service = initialized();
+        let params = json!({ "path": "a.js", "sourceText": "let a = 1; a;" });
+        let result = &request(&mut service, "semantic", &params)[0]["result"];
+        let symbol = &result["symbols"][0];
+        assert_eq!(symbol["name"], "a");
+        assert_eq!((&symbol["start"], &symbol["end"]), (&json!(4), &json!(5)));
+        assert_eq!(symbol["references"], json!([{ "start": 11, "end": 12 }]));
+    }
+
+    #[test]
+    fn subscriptions() {
+        let path = env::current_dir().unwrap().join("a.js");
+        let mut service = initialized();
+        let params = json!({ "path": path, "sourceText": "debugger;" });
+        let result = &request(&mut service, "lint", &params)[0]["result"];
+        assert_eq!(result["diagnostics"].as_array().unwrap().len(), 1);
+
+        // The file does not exist, so it is sent in the notifications
+        let responses = request(&mut service, "subscribe", &json!({ "paths": [path] }));
+        assert_eq!(responses[1]["method"], "analysis/result");
+        let changed = json!({ "method": "file/changed", "params": { "path": path, "sourceText": "debugger;" } });
+        let notifications = service.handle(&changed.to_string());
+        assert_eq!(
+            notifications[0]["params"]["diagnostics"][0]["message"],
+            result["diagnostics"][0]["message"]
+        );
+
+        request(&mut service, "unsubscribe", &json!({ "paths": [path] }));
+        assert!(service.handle(&changed.to_string()).is_empty());
+    }
+
+    #[test]
+    fn serve_until_exit() {
+        let mut input = vec![];
+        let initialize = json!({ "id": 0, "method": "initialize", "params": { "protocolVersion": PROTOCOL_VERSION } });
+        write_message(&mut input, &initialize).unwrap();
+        write_message(&mut input, &json!({ "id": 1, "method": "shutdown" })).unwrap();
+        write_message(&mut input, &json!({ "method": "exit" })).unwrap();
+        write_message(&mut input, &json!({ "id": 2, "method": "shutdown" })).unwrap();
+        let mut output = vec![];
+        serve(Cursor::new(input), &mut output).unwrap();
+
+        let mut output = Cursor::new(output);
+        let mut ids = vec![];
+        while let Some(content) = read_message(&mut output).unwrap() {
+            ids.push(serde_json::from_str::<Value>(&content).unwrap()["id"].clone());
+        }
+        assert_eq!(ids, vec![json!(0), json!(1)]);
+    }
+}
@@ -0,0 +1,29 @@
+use std::{
+    io::{self, BufReader},
+    net::TcpListener,
+    thread,
+};
+
+/// Serves the standard input and output, or the connections of `--listen <address>`
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    match (args.next().as_deref(), args.next()) {
+        (None, _) => oxc_daemon::serve(io::stdin().lock(), io::stdout().lock()),
+        (Some("--listen"), Some(address)) => {
+            let listener = TcpListener::bind(address)?;
+            eprintln!("Listening on {}", listener.local_addr()?);
+            for stream in listener.incoming() {
+                let stream = stream?;
+                thread::spawn(move || {
+                    let reader = BufReader::new(stream.try_clone()?);
+                    oxc_daemon::serve(reader, stream)
+                });
+            }
+            Ok(())
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Usage: oxc_daemon [--listen <address>]",
+        )),
+    }
+}
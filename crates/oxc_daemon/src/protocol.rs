@@ -0,0 +1,154 @@
+//! JSON-RPC 2.0 messages framed by a `Content-Length` header, as in the Language Server Protocol
+//!
+//! <https://www.jsonrpc.org/specification>
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The version of the methods and of their params and results. A client must send it in the
+/// `initialize` request, and the server refuses the other versions.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The error codes of the responses
+pub mod error_code {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// The protocol version of the `initialize` request is not [super::PROTOCOL_VERSION]
+    pub const UNSUPPORTED_PROTOCOL_VERSION: i64 = -32001;
+    /// A request other than `initialize` was received before it
+    pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+    /// The source text of a file could not be read
+    pub const FILE_NOT_READABLE: i64 = -32003;
+}
+
+/// A request when it has an `id`, a notification otherwise
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ResponseError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResponseError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// A message sent by the server without a request, e.g. the analysis results of the subscribed
+/// files
+#[derive(Debug, Serialize)]
+pub struct Notification {
+    jsonrpc: &'static str,
+    method: &'static str,
+    params: Value,
+}
+
+impl Response {
+    pub fn result(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    pub fn error(id: Value, code: i64, message: String) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(ResponseError { code, message }) }
+    }
+}
+
+impl Notification {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { jsonrpc: "2.0", method, params }
+    }
+}
+
+/// Reads the content of the next message, `None` at the end of the input
+///
+/// # Errors
+///
+/// * The input cannot be read, or the headers have no valid `Content-Length`
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut content_length = None;
+    let mut has_headers = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if has_headers {
+                break;
+            }
+            // The blank lines between the messages are ignored
+            continue;
+        }
+        has_headers = true;
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(content_length) = content_length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header"));
+    };
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    String::from_utf8(content)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// # Errors
+///
+/// * The output cannot be written
+pub fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use serde_json::json;
+
+    use super::{read_message, write_message, Response};
+
+    #[test]
+    fn framing() {
+        let mut output = vec![];
+        write_message(&mut output, &Response::result(json!(1), json!(null))).unwrap();
+        write_message(&mut output, &Response::error(json!(2), -32601, "Not found".into())).unwrap();
+        let text = String::from_utf8(output.clone()).unwrap();
+        assert!(text.starts_with("Content-Length: 38\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":1,"));
+
+        let mut input = Cursor::new(output);
+        let first = read_message(&mut input).unwrap().unwrap();
+        assert_eq!(first, r#"{"jsonrpc":"2.0","id":1,"result":null}"#);
+        let second = read_message(&mut input).unwrap().unwrap();
+        assert!(second.contains(r#""error":{"code":-32601,"message":"Not found"}"#));
+        assert!(read_message(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn missing_content_length() {
+        let mut input = Cursor::new("Content-Type: json\r\n\r\n{}");
+        assert!(read_message(&mut input).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//! The state of a connection, which handles its messages in order
+
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use oxc::allocator::Allocator;
+use oxc_linter::{ConfigFinder, LintOptions, Linter};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use crate::{
+    analysis::{self, Document},
+    protocol::{error_code, Message, Notification, Response, PROTOCOL_VERSION},
+};
+
+/// The methods of the requests, returned by `initialize`
+pub const METHODS: [&str; 7] =
+    ["initialize", "parse", "semantic", "lint", "subscribe", "unsubscribe", "shutdown"];
+
+#[derive(Debug, Default)]
+pub struct Service {
+    initialized: bool,
+    shutdown: bool,
+    exit: bool,
+    /// The files whose lint results are sent by `analysis/result` notifications
+    subscriptions: FxHashSet<PathBuf>,
+    config_finder: ConfigFinder,
+    /// The linters of the config files which apply to a directory, from the farthest to the
+    /// nearest
+    linters: FxHashMap<Vec<PathBuf>, Rc<Linter>>,
+    /// The arena of the files, reset after each of them
+    allocator: Allocator,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeParams {
+    protocol_version: u32,
+}
+
+#[derive(Deserialize)]
+struct SubscribeParams {
+    paths: Vec<PathBuf>,
+}
+
+/// The result of a request, or the code and message of its error
+type RequestResult = Result<Value, (i64, String)>;
+
+impl Service {
+    /// The `exit` notification was received, the connection should be closed
+    pub fn exited(&self) -> bool {
+        self.exit
+    }
+
+    /// Handles the content of a message, and returns the messages to send back: the response of
+    /// a request followed by the notifications
+    pub fn handle(&mut self, content: &str) -> Vec<Value> {
+        let message = match serde_json::from_str::<Value>(content) {
+            Ok(message) => message,
+            Err(error) => {
+                let response =
+                    Response::error(Value::Null, error_code::PARSE_ERROR, error.to_string());
+                return vec![to_value(&response)];
+            }
+        };
+        let id = message.get("id").cloned();
+        let message = match serde_json::from_value::<Message>(message) {
+            Ok(message) => message,
+            Err(error) => {
+                let response = Response::error(
+                    id.unwrap_or_default(),
+                    error_code::INVALID_REQUEST,
+                    error.to_string(),
+                );
+                return vec![to_value(&response)];
+            }
+        };
+
+        let mut notifications = vec![];
+        let Some(id) = message.id else {
+            self.handle_notification(&message.method, message.params, &mut notifications);
+            return notifications.iter().map(to_value).collect();
+        };
+        let response =
+            match self.handle_request(&message.method, message.params, &mut notifications) {
+                Ok(result) => Response::result(id, result),
+                Err((code, message)) => Response::error(id, code, message),
+            };
+        std::iter::once(to_value(&response)).chain(notifications.iter().map(to_value)).collect()
+    }
+
+    fn handle_request(
+        &mut self,
+        method: &str,
+        params: Value,
+        notifications: &mut Vec<Notification>,
+    ) -> RequestResult {
+        if method == "initialize" {
+            let params: InitializeParams = from_params(params)?;
+            if params.protocol_version != PROTOCOL_VERSION {
+                return Err((
+                    error_code::UNSUPPORTED_PROTOCOL_VERSION,
+                    format!(
+                        "Unsupported protocol version {}, the server supports version {PROTOCOL_VERSION}",
+                        params.protocol_version
+                    ),
+                ));
+            }
+            self.initialized = true;
+            return Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "serverInfo": { "name": "oxc_daemon", "version": env!("CARGO_PKG_VERSION") },
+                "methods": METHODS,
+            }));
+        }
+        if !self.initialized {
+            return Err((error_code::SERVER_NOT_INITIALIZED, "Server not initialized".into()));
+        }
+        if self.shutdown {
+            return Err((error_code::INVALID_REQUEST, "Server is shut down".into()));
+        }
+        match method {
+            "parse" => {
+                let document: Document = from_params(params)?;
+                let source_text = read_source_text(&document)?;
+                Ok(analysis::parse(self.allocator(), &document, &source_text))
+            }
+            "semantic" => {
+                let document: Document = from_params(params)?;
+                let source_text = read_source_text(&document)?;
+                Ok(analysis::semantic(self.allocator(), &document, &source_text))
+            }
+            "lint" => {
+                let document: Document = from_params(params)?;
+                let source_text = read_source_text(&document)?;
+                let linter = self.linter(&document.path)?;
+                Ok(analysis::lint(self.allocator(), &document, &source_text, &linter))
+            }
+            "subscribe" => {
+                let params: SubscribeParams = from_params(params)?;
+                for path in params.paths {
+                    let document = Document { path, source_text: None, source_type: None };
+                    self.publish(&document, notifications);
+                    self.subscriptions.insert(document.path);
+                }
+                Ok(Value::Null)
+            }
+            "unsubscribe" => {
+                let params: SubscribeParams = from_params(params)?;
+                for path in &params.paths {
+                    self.subscriptions.remove(path);
+                }
+                Ok(Value::Null)
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            _ => Err((error_code::METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        }
+    }
+
+    /// The notifications have no response, so the invalid ones are ignored
+    fn handle_notification(
+        &mut self,
+        method: &str,
+        params: Value,
+        notifications: &mut Vec<Notification>,
+    ) {
+        match method {
+            "exit" => self.exit = true,
+            // The file was changed on disk, or its unsaved `sourceText` is sent
+            "file/changed" if self.initialized && !self.shutdown => {
+                let Ok(document) = serde_json::from_value::<Document>(params) else { return };
+                if self.subscriptions.contains(&document.path) {
+                    self.publish(&document, notifications);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Sends the lint results of a file, or the error of reading it as a diagnostic
+    fn publish(&mut self, document: &Document, notifications: &mut Vec<Notification>) {
+        let params = match read_source_text(document)
+            .and_then(|source_text| Ok((source_text, self.linter(&document.path)?)))
+        {
+            Ok((source_text, linter)) => {
+                analysis::lint(self.allocator(), document, &source_text, &linter)
+            }
+            Err((_, message)) => json!({
+                "path": document.path,
+                "diagnostics": [{ "start": 0, "end": 0, "severity": "Error", "message": message, "help": null }],
+            }),
+        };
+        notifications.push(Notification::new("analysis/result", params));
+    }
+
+    /// The allocator reset from the previous file
+    fn allocator(&mut self) -> &Allocator {
+        self.allocator.reset();
+        &self.allocator
+    }
+
+    /// The linter of the config files found for the directory of `path`, the current directory
+    /// for the relative paths
+    fn linter(&mut self, path: &Path) -> Result<Rc<Linter>, (i64, String)> {
+        let dir = match path.parent() {
+            Some(dir) if dir.is_absolute() => dir.to_path_buf(),
+            dir => std::env::current_dir()
+                .map(|cwd| cwd.join(dir.unwrap_or_else(|| Path::new(""))))
+                .map_err(|error| (error_code::FILE_NOT_READABLE, error.to_string()))?,
+        };
+        let config_paths = self.config_finder.find(&dir);
+        if let Some(linter) = self.linters.get(&config_paths) {
+            return Ok(Rc::clone(linter));
+        }
+        let options = LintOptions::default().with_config_paths(config_paths.clone());
+        let linter = Linter::from_options(options)
+            .map_err(|error| (error_code::INVALID_PARAMS, error.to_string()))?;
+        let linter = Rc::new(linter);
+        self.linters.insert(config_paths, Rc::clone(&linter));
+        Ok(linter)
+    }
+}
+
+fn from_params<T: DeserializeOwned>(params: Value) -> Result<T, (i64, String)> {
+    serde_json::from_value(params).map_err(|error| (error_code::INVALID_PARAMS, error.to_string()))
+}
+
+fn read_source_text(document: &Document) -> Result<String, (i64, String)> {
+    document.source_text().map_err(|message| (error_code::FILE_NOT_READABLE, message))
+}
+
+fn to_value<T: serde::Serialize>(message: &T) -> Value {
+    serde_json::to_value(message).unwrap_or_default()
+}
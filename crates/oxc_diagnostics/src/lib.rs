@@ -1,12 +1,19 @@
 //! Diagnostics Wrapper
 //! Exports `thiserror` and `miette`
+//!
+//! ## Cargo Features
+//! * `"serde"` enables [SerializedDiagnostic] for the bindings
 
 mod graphic_reporter;
 mod graphical_theme;
+#[cfg(feature = "serde")]
+mod serialize;
 mod service;
 
 use std::path::PathBuf;
 
+#[cfg(feature = "serde")]
+pub use crate::serialize::SerializedDiagnostic;
 pub use crate::service::{DiagnosticSender, DiagnosticService, DiagnosticTuple};
 pub use graphic_reporter::{GraphicalReportHandler, GraphicalTheme};
 pub use miette;
@@ -30,3 +37,8 @@ pub struct MinifiedFileError(pub PathBuf);
 #[error("Failed to open file {0:?} with error \"{1}\"")]
 #[diagnostic(help("Failed to open file {0:?} with error \"{1}\""))]
 pub struct FailedToOpenFileError(pub PathBuf, pub std::io::Error);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Failed to write file {0:?} with error \"{1}\"")]
+#[diagnostic(help("Check that the directory of {0:?} can be written"))]
+pub struct FailedToWriteFileError(pub PathBuf, pub std::io::Error);
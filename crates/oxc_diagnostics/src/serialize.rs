@@ -0,0 +1,38 @@
+use serde::Serialize;
+
+use crate::Error;
+
+/// A diagnostic at one of the labels of an error, the errors without labels are at the start
+/// of the file. The offsets are in bytes of the UTF-8 source text.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SerializedDiagnostic {
+    pub start: usize,
+    pub end: usize,
+    pub severity: String,
+    pub message: String,
+    pub help: Option<String>,
+}
+
+impl SerializedDiagnostic {
+    pub fn from_errors(errors: &[Error]) -> Vec<Self> {
+        errors
+            .iter()
+            .flat_map(|error| {
+                let severity = format!("{:?}", error.severity().unwrap_or_default());
+                let message = format!("{error}");
+                let help = error.help().map(|help| help.to_string());
+                let spans = error.labels().map_or_else(
+                    || vec![(0, 0)],
+                    |labels| labels.map(|label| (label.offset(), label.len())).collect(),
+                );
+                spans.into_iter().map(move |(offset, len)| Self {
+                    start: offset,
+                    end: offset + len,
+                    severity: severity.clone(),
+                    message: message.clone(),
+                    help: help.clone(),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,34 @@
+/* The C ABI of oxc, see `crates/oxc_ffi/src/lib.rs` for the options and the results. */
+
+#ifndef OXC_H
+#define OXC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Parses the UTF-8 source text into the JSON `{ program, comments, errors }`, where `program` is
+ * an ESTree AST. `options` is null or a JSON object such as `{ "sourceFilename": "index.ts" }`.
+ * The result must be released with `oxc_free_result`.
+ */
+char *oxc_parse(const uint8_t *source_text, size_t source_len, const char *options);
+
+/*
+ * Lints the UTF-8 source text into the JSON `{ diagnostics }`. `options` is null or a JSON object
+ * such as `{ "filter": [["deny", "correctness"]], "jestPlugin": true }`.
+ * The result must be released with `oxc_free_result`.
+ */
+char *oxc_lint(const uint8_t *source_text, size_t source_len, const char *options);
+
+/* Releases a result of `oxc_parse` or `oxc_lint`, null is ignored. */
+void oxc_free_result(char *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OXC_H */
@@ -0,0 +1,254 @@
+//! # Oxc C FFI
+//!
+//! A C ABI for embedding oxc in other languages, declared in `include/oxc.h`.
+//!
+//! * The source text is passed as a pointer and a length of UTF-8 bytes.
+//! * The options are a NUL-terminated JSON object, or null for the default options.
+//! * The results are NUL-terminated JSON strings owned by the caller, which must be released
+//!   with `oxc_free_result`. A call with a source text which is not UTF-8 or invalid options
+//!   returns `{ "error": "..." }`, as does a panic, which never unwinds into the caller.
+
+mod options;
+mod result;
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+};
+
+use oxc::{allocator::Allocator, diagnostics::SerializedDiagnostic, semantic::SemanticBuilder};
+use oxc_linter::{LintContext, Linter};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    options::{LintFunctionOptions, ParseOptions},
+    result::{InvalidInvocation, LintResult, ParseResult},
+};
+
+/// Parses the source text into `{ program, comments, errors }`, the program is an ESTree AST
+///
+/// The options are `{ sourceType, sourceFilename, allowReturnOutsideFunction }`.
+///
+/// # Safety
+///
+/// * `source_text` must point to `source_len` readable bytes
+/// * `options` must be null or point to a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn oxc_parse(
+    source_text: *const u8,
+    source_len: usize,
+    options: *const c_char,
+) -> *mut c_char {
+    guard(|| {
+        // SAFETY: the requirements are the ones of this function
+        let input = unsafe { read_input::<ParseOptions>(source_text, source_len, options) };
+        input.map_or_else(invalid_invocation, |(source_text, options)| {
+            let allocator = Allocator::default();
+            let ret = options.parse(&allocator, source_text);
+            to_json(&ParseResult {
+                program: ret.program.to_estree(source_text),
+                comments: ret.trivias.to_estree_comments(source_text),
+                errors: SerializedDiagnostic::from_errors(&ret.errors),
+            })
+        })
+    })
+}
+
+/// Lints the source text into `{ diagnostics }`, the correctness rules are denied by default
+///
+/// The options are the parse options and `{ filter, importPlugin, jestPlugin, jsxA11yPlugin,
+/// nextjsPlugin }`, where `filter` is like `[["deny", "correctness"], ["allow", "no-debugger"]]`.
+///
+/// # Safety
+///
+/// * `source_text` must point to `source_len` readable bytes
+/// * `options` must be null or point to a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn oxc_lint(
+    source_text: *const u8,
+    source_len: usize,
+    options: *const c_char,
+) -> *mut c_char {
+    guard(|| {
+        // SAFETY: the requirements are the ones of this function
+        let input = unsafe { read_input::<LintFunctionOptions>(source_text, source_len, options) };
+        input
+            .and_then(|(source_text, options)| {
+                let linter = options.lint_options().and_then(|lint_options| {
+                    Linter::from_options(lint_options).map_err(|error| error.to_string())
+                })?;
+                Ok(lint(source_text, &options, &linter))
+            })
+            .map_or_else(invalid_invocation, |result| to_json(&result))
+    })
+}
+
+/// Releases a result of `oxc_parse` or `oxc_lint`, null is ignored
+///
+/// # Safety
+///
+/// * `result` must be null or returned by `oxc_parse` or `oxc_lint`, and not be released yet
+#[no_mangle]
+pub unsafe extern "C" fn oxc_free_result(result: *mut c_char) {
+    if !result.is_null() {
+        // SAFETY: `result` was created by `CString::into_raw` in `into_result`
+        drop(unsafe { CString::from_raw(result) });
+    }
+}
+
+fn lint(source_text: &str, options: &LintFunctionOptions, linter: &Linter) -> LintResult {
+    let allocator = Allocator::default();
+    let ret = options.parse.parse(&allocator, source_text);
+    if !ret.errors.is_empty() {
+        return LintResult { diagnostics: SerializedDiagnostic::from_errors(&ret.errors) };
+    }
+    let program = allocator.alloc(ret.program);
+    let semantic_ret = SemanticBuilder::new(source_text, options.parse.source_type())
+        .with_trivias(ret.trivias)
+        .with_check_syntax_error(true)
+        .build(program);
+    let mut diagnostics = SerializedDiagnostic::from_errors(&semantic_ret.errors);
+    let semantic = Rc::new(semantic_ret.semantic);
+    let lint_ctx = LintContext::new(options.parse.path().into_boxed_path(), &semantic);
+    let errors = linter.run(lint_ctx).into_iter().map(|message| message.error).collect::<Vec<_>>();
+    diagnostics.extend(SerializedDiagnostic::from_errors(&errors));
+    LintResult { diagnostics }
+}
+
+/// # Safety
+///
+/// The requirements of `oxc_parse`
+unsafe fn read_input<'a, T: Default + DeserializeOwned>(
+    source_text: *const u8,
+    source_len: usize,
+    options: *const c_char,
+) -> Result<(&'a str, T), String> {
+    if source_text.is_null() {
+        return Err("The source text is null.".to_string());
+    }
+    // SAFETY: `source_text` is not null and points to `source_len` readable bytes
+    let bytes = unsafe { std::slice::from_raw_parts(source_text, source_len) };
+    let source_text = std::str::from_utf8(bytes)
+        .map_err(|error| format!("The source text is not UTF-8: {error}"))?;
+    if options.is_null() {
+        return Ok((source_text, T::default()));
+    }
+    // SAFETY: `options` is not null and points to a NUL-terminated string
+    let options = unsafe { CStr::from_ptr(options) };
+    let options = options
+        .to_str()
+        .map_err(|error| format!("The options are not UTF-8: {error}"))
+        .and_then(|options| {
+        serde_json::from_str(options).map_err(|error| format!("The options are invalid: {error}"))
+    })?;
+    Ok((source_text, options))
+}
+
+fn invalid_invocation(error: String) -> String {
+    to_json(&InvalidInvocation { error })
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+/// Runs `f` and returns its JSON as a result, a panic is returned as `{ "error": "..." }`
+/// since unwinding across the FFI boundary is undefined behavior
+fn guard<F: FnOnce() -> String>(f: F) -> *mut c_char {
+    let json = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown error".to_string());
+        invalid_invocation(format!("oxc panicked: {message}"))
+    });
+    into_result(json)
+}
+
+/// serde_json escapes the control characters, a JSON string with a NUL is an error
+fn into_result(json: String) -> *mut c_char {
+    CString::new(json)
+        .unwrap_or_else(|_| {
+            let json = invalid_invocation("The result contains a NUL character.".to_string());
+            CString::new(json).unwrap_or_default()
+        })
+        .into_raw()
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::{c_char, CStr, CString};
+
+    use serde_json::{json, Value};
+
+    use super::{guard, into_result, oxc_free_result, oxc_lint, oxc_parse};
+
+    type Function = unsafe extern "C" fn(*const u8, usize, *const c_char) -> *mut c_char;
+
+    fn call(function: Function, source_text: &str, options: Option<&str>) -> Value {
+        let options = options.map(|options| CString::new(options).unwrap());
+        let options = options.as_deref().map_or(std::ptr::null(), CStr::as_ptr);
+        // SAFETY: the arguments are valid
+        into_json(unsafe { function(source_text.as_ptr(), source_text.len(), options) })
+    }
+
+    fn into_json(result: *mut c_char) -> Value {
+        // SAFETY: the result is released once
+        unsafe {
+            let json = serde_json::from_str(CStr::from_ptr(result).to_str().unwrap()).unwrap();
+            oxc_free_result(result);
+            json
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let result = call(oxc_parse, "// comment\nfoo", None);
+        assert_eq!(result["program"]["type"], "Program");
+        assert_eq!(result["program"]["body"][0]["expression"]["name"], "foo");
+        assert_eq!(
+            result["comments"],
+            json!([{ "type": "Line", "value": " comment", "start": 0, "end": 10 }])
+        );
+        assert_eq!(result["errors"], json!([]));
+
+        let result = call(oxc_parse, "let x: number", Some(r#"{"sourceFilename": "a.ts"}"#));
+        assert_eq!(result["errors"], json!([]));
+        let result = call(oxc_parse, "let x: number", None);
+        assert_eq!(result["errors"][0]["severity"], "Error");
+    }
+
+    #[test]
+    fn lint() {
+        let result = call(oxc_lint, "debugger", None);
+        let diagnostics = result["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["start"], 0);
+        assert_eq!(diagnostics[0]["end"], 8);
+        assert!(diagnostics[0]["message"].as_str().unwrap().contains("no-debugger"));
+
+        let options = r#"{"filter": [["allow", "no-debugger"]]}"#;
+        let result = call(oxc_lint, "debugger", Some(options));
+        assert_eq!(result["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn invalid_invocation() {
+        let result = call(oxc_lint, "debugger", Some(r#"{"filter": [["on", "all"]]}"#));
+        assert!(result["error"].is_string());
+        let result = call(oxc_parse, "foo", Some(r#"{"sourceType": 1}"#));
+        assert!(result["error"].as_str().unwrap().starts_with("The options are invalid"));
+        // SAFETY: null is ignored
+        unsafe { oxc_free_result(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn panics() {
+        let result = into_json(guard(|| panic!("boom")));
+        assert_eq!(result["error"], "oxc panicked: boom");
+        let result = into_json(into_result("{\"a\": \"\0\"}".to_string()));
+        assert_eq!(result["error"], "The result contains a NUL character.");
+    }
+}
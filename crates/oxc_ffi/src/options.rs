@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use oxc::{
+    allocator::Allocator,
+    parser::{Parser, ParserReturn},
+    span::SourceType,
+};
+use oxc_linter::{AllowWarnDeny, LintOptions};
+use serde::Deserialize;
+
+/// The options of `oxc_parse`, in JSON
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParseOptions {
+    /// `script` or `module`, overrides the source type of the file name
+    pub source_type: Option<String>,
+    /// The JS, JSX, TS and TSX syntax is chosen by the extension
+    pub source_filename: Option<String>,
+    pub allow_return_outside_function: bool,
+}
+
+impl ParseOptions {
+    pub fn path(&self) -> PathBuf {
+        PathBuf::from(self.source_filename.as_deref().unwrap_or("file.js"))
+    }
+
+    pub fn source_type(&self) -> SourceType {
+        let source_type = SourceType::from_path(self.path()).unwrap_or_default();
+        match self.source_type.as_deref() {
+            Some("script") => source_type.with_script(true),
+            Some("module") => source_type.with_module(true),
+            _ => source_type,
+        }
+    }
+
+    pub fn parse<'a>(&self, allocator: &'a Allocator, source_text: &'a str) -> ParserReturn<'a> {
+        Parser::new(allocator, source_text, self.source_type())
+            .allow_return_outside_function(self.allow_return_outside_function)
+            .parse()
+    }
+}
+
+/// The options of `oxc_lint`, in JSON
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LintFunctionOptions {
+    #[serde(flatten)]
+    pub parse: ParseOptions,
+    /// The rules or categories allowed, warned or denied in order, as in
+    /// `[["deny", "correctness"], ["allow", "no-debugger"]]`
+    pub filter: Vec<(String, String)>,
+    pub import_plugin: bool,
+    pub jest_plugin: bool,
+    pub jsx_a11y_plugin: bool,
+    pub nextjs_plugin: bool,
+}
+
+impl LintFunctionOptions {
+    /// # Errors
+    ///
+    /// * A filter is not `allow`, `warn` or `deny`
+    pub fn lint_options(&self) -> Result<LintOptions, String> {
+        let mut filter = vec![];
+        for (allow_warn_deny, name_or_category) in &self.filter {
+            let allow_warn_deny = AllowWarnDeny::try_from(allow_warn_deny.as_str())
+                .map_err(|error| error.to_string())?;
+            filter.push((allow_warn_deny, name_or_category.clone()));
+        }
+        Ok(LintOptions::default()
+            .with_filter(filter)
+            .with_import_plugin(self.import_plugin)
+            .with_jest_plugin(self.jest_plugin)
+            .with_jsx_a11y_plugin(self.jsx_a11y_plugin)
+            .with_nextjs_plugin(self.nextjs_plugin))
+    }
+}
@@ -0,0 +1,25 @@
+use oxc::{ast::EstreeComment, diagnostics::SerializedDiagnostic};
+use serde::Serialize;
+
+/// The result of `oxc_parse`, in JSON
+#[derive(Debug, Serialize)]
+pub struct ParseResult {
+    /// The ESTree `Program`
+    pub program: serde_json::Value,
+    pub comments: Vec<EstreeComment>,
+    pub errors: Vec<SerializedDiagnostic>,
+}
+
+/// The result of `oxc_lint`, in JSON
+#[derive(Debug, Serialize)]
+pub struct LintResult {
+    /// The syntax errors and the diagnostics of the rules
+    pub diagnostics: Vec<SerializedDiagnostic>,
+}
+
+/// The result of an invalid call, such as a source text which is not UTF-8 or options which
+/// are not JSON
+#[derive(Debug, Serialize)]
+pub struct InvalidInvocation {
+    pub error: String,
+}
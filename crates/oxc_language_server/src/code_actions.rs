@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, Diagnostic, NumberOrString, Position, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+
+use crate::linter::DiagnosticReport;
+
+/// The quick fix applying the fix of the rule
+pub fn fix_action(uri: &Url, report: &DiagnosticReport) -> Option<CodeAction> {
+    let fixed_content = report.fixed_content.as_ref()?;
+    let title = report.diagnostic.code.as_ref().map_or_else(
+        || "Fix this problem".into(),
+        |code| format!("Fix this {} problem", code_str(code)),
+    );
+    let edit = TextEdit { range: fixed_content.range, new_text: fixed_content.code.clone() };
+    Some(code_action(title, uri, edit, &report.diagnostic, true))
+}
+
+/// Inserts `// eslint-disable-next-line rule-name` above the line of the diagnostic, with the
+/// indentation of the line
+pub fn disable_line_action(
+    uri: &Url,
+    report: &DiagnosticReport,
+    source_text: &str,
+) -> Option<CodeAction> {
+    let rule_name = rule_name(&report.diagnostic)?;
+    let line = report.diagnostic.range.start.line;
+    let indentation = source_text
+        .lines()
+        .nth(line as usize)
+        .map_or("", |line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]);
+    let edit = TextEdit {
+        range: Range::new(Position::new(line, 0), Position::new(line, 0)),
+        new_text: format!("{indentation}// eslint-disable-next-line {rule_name}\n"),
+    };
+    let title = format!("Disable {rule_name} for this line");
+    Some(code_action(title, uri, edit, &report.diagnostic, false))
+}
+
+/// Inserts `/* eslint-disable rule-name */` at the start of the file, after the hashbang
+pub fn disable_file_action(
+    uri: &Url,
+    report: &DiagnosticReport,
+    source_text: &str,
+) -> Option<CodeAction> {
+    let rule_name = rule_name(&report.diagnostic)?;
+    let line = u32::from(source_text.starts_with("#!"));
+    let edit = TextEdit {
+        range: Range::new(Position::new(line, 0), Position::new(line, 0)),
+        new_text: format!("/* eslint-disable {rule_name} */\n"),
+    };
+    let title = format!("Disable {rule_name} for this file");
+    Some(code_action(title, uri, edit, &report.diagnostic, false))
+}
+
+/// The name of the rule in the `plugin(rule-name)` code of the diagnostic, which is the name in
+/// the directive comments
+fn rule_name(diagnostic: &Diagnostic) -> Option<&str> {
+    let code = code_str(diagnostic.code.as_ref()?);
+    let (_, rule_name) = code.strip_suffix(')')?.split_once('(')?;
+    Some(rule_name)
+}
+
+fn code_str(code: &NumberOrString) -> &str {
+    match code {
+        NumberOrString::String(code) => code,
+        NumberOrString::Number(_) => "",
+    }
+}
+
+fn code_action(
+    title: String,
+    uri: &Url,
+    edit: TextEdit,
+    diagnostic: &Diagnostic,
+    is_preferred: bool,
+) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        is_preferred: Some(is_preferred),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+            ..WorkspaceEdit::default()
+        }),
+        disabled: None,
+        data: None,
+        diagnostics: Some(vec![diagnostic.clone()]),
+        command: None,
+    }
+}
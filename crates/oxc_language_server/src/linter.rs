@@ -7,7 +7,7 @@ use std::{
 };
 
 use miette::NamedSource;
-use oxc_allocator::Allocator;
+use oxc_allocator::AllocatorPool;
 use oxc_diagnostics::{miette, Error, Severity};
 use oxc_linter::{
     partial_loader::{
@@ -18,10 +18,10 @@ use oxc_linter::{
 };
 use oxc_parser::Parser;
 use oxc_semantic::SemanticBuilder;
-use oxc_span::{SourceType, VALID_EXTENSIONS};
-use ropey::Rope;
+use oxc_span::{LineColumn, LineIndex, SourceType, VALID_EXTENSIONS};
 use tower_lsp::lsp_types::{
-    self, DiagnosticRelatedInformation, DiagnosticSeverity, Position, Range, Url,
+    self, CodeDescription, DiagnosticRelatedInformation, DiagnosticSeverity, NumberOrString,
+    Position, Range, Url,
 };
 
 #[derive(Debug)]
@@ -43,7 +43,7 @@ struct LabeledSpanWithPosition {
 impl ErrorWithPosition {
     pub fn new(
         error: Error,
-        text: &str,
+        line_index: &LineIndex,
         fixed_content: Option<FixedContent>,
         start: usize,
     ) -> Self {
@@ -51,13 +51,11 @@ impl ErrorWithPosition {
         let labels_with_pos: Vec<LabeledSpanWithPosition> = labels
             .iter()
             .map(|labeled_span| LabeledSpanWithPosition {
-                start_pos: offset_to_position(labeled_span.offset() + start, text)
-                    .unwrap_or_default(),
+                start_pos: offset_to_position(labeled_span.offset() + start, line_index),
                 end_pos: offset_to_position(
                     labeled_span.offset() + start + labeled_span.len(),
-                    text,
-                )
-                .unwrap_or_default(),
+                    line_index,
+                ),
                 message: labeled_span.label().map(ToString::to_string),
             })
             .collect();
@@ -110,18 +108,25 @@ impl ErrorWithPosition {
             },
         );
 
+        let error_message = self.miette_err.to_string();
+        let (code, error_message) = split_rule_code(&error_message);
         let message = self.miette_err.help().map_or_else(
-            || self.miette_err.to_string(),
-            |help| format!("{}\nhelp: {}", self.miette_err, help),
+            || error_message.to_string(),
+            |help| format!("{error_message}\nhelp: {help}"),
         );
+        let code_description = self
+            .miette_err
+            .url()
+            .and_then(|url| Url::parse(&url.to_string()).ok())
+            .map(|href| CodeDescription { href });
 
         lsp_types::Diagnostic {
             range,
             severity,
-            code: None,
+            code: code.map(|code| NumberOrString::String(code.to_string())),
             message,
             source: Some("oxc".into()),
-            code_description: None,
+            code_description,
             related_information,
             tags: None,
             data: None,
@@ -156,11 +161,12 @@ pub struct FixedContent {
 #[derive(Debug)]
 pub struct IsolatedLintHandler {
     linter: Arc<Linter>,
+    allocator_pool: Arc<AllocatorPool>,
 }
 
 impl IsolatedLintHandler {
-    pub fn new(linter: Arc<Linter>) -> Self {
-        Self { linter }
+    pub fn new(linter: Arc<Linter>, allocator_pool: Arc<AllocatorPool>) -> Self {
+        Self { linter, allocator_pool }
     }
 
     pub fn run_single(
@@ -169,7 +175,7 @@ impl IsolatedLintHandler {
         content: Option<String>,
     ) -> Option<Vec<DiagnosticReport>> {
         if Self::is_wanted_ext(path) {
-            Some(Self::lint_path(&self.linter, path, content).map_or(vec![], |(p, errors)| {
+            Some(self.lint_path(path, content).map_or(vec![], |(p, errors)| {
                 let mut diagnostics: Vec<DiagnosticReport> =
                     errors.into_iter().map(|e| e.into_diagnostic_report(&p)).collect();
                 // a diagnostics connected from related_info to original diagnostic
@@ -194,7 +200,7 @@ impl IsolatedLintHandler {
                             diagnostic: lsp_types::Diagnostic {
                                 range: r.location.range,
                                 severity: Some(DiagnosticSeverity::HINT),
-                                code: None,
+                                code: d.diagnostic.code.clone(),
                                 message: r.message.clone(),
                                 source: Some("oxc".into()),
                                 code_description: None,
@@ -253,13 +259,14 @@ impl IsolatedLintHandler {
     }
 
     fn lint_path(
-        linter: &Linter,
+        &self,
         path: &Path,
         source_text: Option<String>,
     ) -> Option<(PathBuf, Vec<ErrorWithPosition>)> {
         let ext = path.extension().and_then(std::ffi::OsStr::to_str)?;
         let (source_type, original_source_text) =
             Self::get_source_type_and_text(path, source_text, ext)?;
+        let line_index = LineIndex::new(&original_source_text);
         let javascript_sources = Self::may_need_extract_js_content(&original_source_text, ext)
             .unwrap_or_else(|| {
                 vec![JavaScriptSource { source_text: &original_source_text, source_type, start: 0 }]
@@ -270,7 +277,7 @@ impl IsolatedLintHandler {
         for source in javascript_sources {
             let JavaScriptSource { source_text: javascript_source_text, source_type, start } =
                 source;
-            let allocator = Allocator::default();
+            let allocator = self.allocator_pool.get();
             let ret = Parser::new(&allocator, javascript_source_text, source_type)
                 .allow_return_outside_function(true)
                 .parse();
@@ -281,7 +288,13 @@ impl IsolatedLintHandler {
                     .into_iter()
                     .map(|diagnostic| ErrorReport { error: diagnostic, fixed_content: None })
                     .collect();
-                return Some(Self::wrap_diagnostics(path, &original_source_text, reports, start));
+                return Some(Self::wrap_diagnostics(
+                    path,
+                    &original_source_text,
+                    &line_index,
+                    reports,
+                    start,
+                ));
             };
 
             let program = allocator.alloc(ret.program);
@@ -296,7 +309,13 @@ impl IsolatedLintHandler {
                     .into_iter()
                     .map(|diagnostic| ErrorReport { error: diagnostic, fixed_content: None })
                     .collect();
-                return Some(Self::wrap_diagnostics(path, &original_source_text, reports, start));
+                return Some(Self::wrap_diagnostics(
+                    path,
+                    &original_source_text,
+                    &line_index,
+                    reports,
+                    start,
+                ));
             };
 
             let lint_ctx = LintContext::new(
@@ -304,7 +323,7 @@ impl IsolatedLintHandler {
                 &Rc::new(semantic_ret.semantic),
             );
 
-            let result = linter.run(lint_ctx);
+            let result = self.linter.run(lint_ctx);
 
             let reports = result
                 .into_iter()
@@ -312,16 +331,8 @@ impl IsolatedLintHandler {
                     let fixed_content = msg.fix.map(|f| FixedContent {
                         code: f.content.to_string(),
                         range: Range {
-                            start: offset_to_position(
-                                f.span.start as usize + start,
-                                javascript_source_text,
-                            )
-                            .unwrap_or_default(),
-                            end: offset_to_position(
-                                f.span.end as usize + start,
-                                javascript_source_text,
-                            )
-                            .unwrap_or_default(),
+                            start: offset_to_position(f.span.start as usize + start, &line_index),
+                            end: offset_to_position(f.span.end as usize + start, &line_index),
                         },
                     });
 
@@ -329,7 +340,7 @@ impl IsolatedLintHandler {
                 })
                 .collect::<Vec<ErrorReport>>();
             let (_, errors_with_position) =
-                Self::wrap_diagnostics(path, &original_source_text, reports, start);
+                Self::wrap_diagnostics(path, &original_source_text, &line_index, reports, start);
             diagnostics.extend(errors_with_position);
         }
 
@@ -339,6 +350,7 @@ impl IsolatedLintHandler {
     fn wrap_diagnostics(
         path: &Path,
         source_text: &str,
+        line_index: &LineIndex,
         reports: Vec<ErrorReport>,
         start: usize,
     ) -> (PathBuf, Vec<ErrorWithPosition>) {
@@ -348,7 +360,7 @@ impl IsolatedLintHandler {
             .map(|report| {
                 ErrorWithPosition::new(
                     report.error.with_source_code(Arc::clone(&source)),
-                    source_text,
+                    line_index,
                     report.fixed_content,
                     start,
                 )
@@ -366,32 +378,43 @@ fn get_valid_extensions() -> Vec<&'static str> {
         .collect::<Vec<&'static str>>()
 }
 
+/// The LSP positions are in UTF-16 code units
 #[allow(clippy::cast_possible_truncation)]
-fn offset_to_position(offset: usize, source_text: &str) -> Option<Position> {
-    let rope = Rope::from_str(source_text);
-    let line = rope.try_char_to_line(offset).ok()?;
-    let first_char_of_line = rope.try_line_to_char(line).ok()?;
-    let column = offset - first_char_of_line;
-    Some(Position::new(line as u32, column as u32))
+fn offset_to_position(offset: usize, line_index: &LineIndex) -> Position {
+    let LineColumn { line, column } = line_index.line_column_utf16(offset as u32);
+    Position::new(line, column)
 }
 
-#[derive(Debug)]
+/// Splits the `plugin(rule-name): ` prefix of the messages of the lint rules, which is the code
+/// of the diagnostic
+fn split_rule_code(message: &str) -> (Option<&str>, &str) {
+    match message.split_once(": ") {
+        Some((code, rest)) if code.ends_with(')') && code.contains('(') && !code.contains(' ') => {
+            (Some(code), rest)
+        }
+        _ => (None, message),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ServerLinter {
     linter: Arc<Linter>,
+    /// The arenas of the documents, which are linted on every change
+    allocator_pool: Arc<AllocatorPool>,
 }
 
 impl ServerLinter {
     pub fn new() -> Self {
         let linter = Linter::default().with_fix(true);
-        Self { linter: Arc::new(linter) }
+        Self::new_with_linter(linter)
     }
 
     pub fn new_with_linter(linter: Linter) -> Self {
-        Self { linter: Arc::new(linter) }
+        Self { linter: Arc::new(linter), allocator_pool: Arc::default() }
     }
 
     pub fn run_single(&self, uri: &Url, content: Option<String>) -> Option<Vec<DiagnosticReport>> {
-        IsolatedLintHandler::new(Arc::clone(&self.linter))
+        IsolatedLintHandler::new(Arc::clone(&self.linter), Arc::clone(&self.allocator_pool))
             .run_single(&uri.to_file_path().unwrap(), content)
     }
 }
@@ -1,3 +1,4 @@
+mod code_actions;
 mod linter;
 mod options;
 
@@ -6,9 +7,11 @@ use globset::Glob;
 use ignore::gitignore::Gitignore;
 use log::{debug, error, info};
 use oxc_linter::{LintOptions, Linter};
+use oxc_span::VALID_EXTENSIONS;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -19,11 +22,12 @@ use tower_lsp::jsonrpc::{Error, ErrorCode, Result};
 use tower_lsp::lsp_types::{
     CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
     CodeActionProviderCapability, CodeActionResponse, ConfigurationItem, Diagnostic,
-    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializeResult,
-    InitializedParams, OneOf, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
-    TextDocumentSyncKind, TextEdit, Url, WorkDoneProgressOptions, WorkspaceEdit,
-    WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities,
+    DiagnosticSeverity, DidChangeConfigurationParams, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    InitializeParams, InitializeResult, InitializedParams, OneOf, Range, ServerCapabilities,
+    ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url, WorkDoneProgressOptions, WorkspaceFoldersServerCapabilities,
+    WorkspaceServerCapabilities,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
@@ -33,9 +37,17 @@ struct Backend {
     root_uri: OnceCell<Option<Url>>,
     server_linter: RwLock<ServerLinter>,
     diagnostics_report_map: DashMap<String, Vec<DiagnosticReport>>,
+    /// The open documents keyed by their uri, their content may not be saved to disk
+    documents: DashMap<String, Document>,
     options: Mutex<Options>,
     gitignore_glob: Mutex<Option<Gitignore>>,
 }
+
+#[derive(Debug, Clone)]
+struct Document {
+    version: i32,
+    text: String,
+}
 #[derive(Debug, Serialize, Deserialize, Default, PartialEq, PartialOrd, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 enum Run {
@@ -95,8 +107,14 @@ impl LanguageServer for Backend {
             server_info: Some(ServerInfo { name: "oxc".into(), version: None }),
             offset_encoding: None,
             capabilities: ServerCapabilities {
-                text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::FULL),
+                        will_save: None,
+                        will_save_wait_until: None,
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    },
                 )),
                 workspace: Some(WorkspaceServerCapabilities {
                     workspace_folders: Some(WorkspaceFoldersServerCapabilities {
@@ -183,89 +201,122 @@ impl LanguageServer for Backend {
         if self.is_ignored(&uri).await {
             return;
         }
-        self.handle_file_update(uri, None, None).await;
+        // The saved content is the one of the open document, the file is read when it is unknown
+        let document = self.documents.get(&uri.to_string()).map(|document| document.clone());
+        match document {
+            Some(Document { version, text }) => {
+                let text = params.text.unwrap_or(text);
+                self.handle_file_update(uri, Some(text), Some(version)).await;
+            }
+            None => self.handle_file_update(uri, params.text, None).await,
+        }
     }
 
     /// When the document changed, it may not be written to disk, so we should
     /// get the file context from the language client
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // The documents are synced in full, the last change is the content of the document
+        let uri = params.text_document.uri;
+        let version = params.text_document.version;
+        let Some(change) = params.content_changes.into_iter().last() else { return };
+        self.documents.insert(uri.to_string(), Document { version, text: change.text.clone() });
+
         let run_level = { self.options.lock().await.get_lint_level() };
         if run_level < SyntheticRunLevel::OnType {
             return;
         }
-
-        let uri = &params.text_document.uri;
-        if self.is_ignored(uri).await {
+        if self.is_ignored(&uri).await {
             return;
         }
-        let content = params.content_changes.first().map(|c| c.text.clone());
-        self.handle_file_update(
-            params.text_document.uri,
-            content,
-            Some(params.text_document.version),
-        )
-        .await;
+        self.handle_file_update(uri, Some(change.text), Some(version)).await;
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let document = params.text_document;
+        self.documents.insert(
+            document.uri.to_string(),
+            Document { version: document.version, text: document.text.clone() },
+        );
+
         let run_level = { self.options.lock().await.get_lint_level() };
         if run_level < SyntheticRunLevel::OnType {
             return;
         }
-        if self.is_ignored(&params.text_document.uri).await {
+        if self.is_ignored(&document.uri).await {
             return;
         }
-        self.handle_file_update(params.text_document.uri, None, Some(params.text_document.version))
-            .await;
+        self.handle_file_update(document.uri, Some(document.text), Some(document.version)).await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
-        let uri = params.text_document.uri.to_string();
-        self.diagnostics_report_map.remove(&uri);
+        let uri = params.text_document.uri;
+        self.documents.remove(&uri.to_string());
+        if self.diagnostics_report_map.remove(&uri.to_string()).is_some() {
+            self.client.publish_diagnostics(uri, vec![], None).await;
+        }
     }
 
+    /// The fixes of the diagnostics in the range, and the actions disabling their rules for
+    /// the line or for the file
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
         let uri = params.text_document.uri;
-
-        if let Some(value) = self.diagnostics_report_map.get(&uri.to_string()) {
-            if let Some(report) = value
-                .iter()
-                .find(|r| r.diagnostic.range == params.range && r.fixed_content.is_some())
-            {
-                let title =
-                    report.diagnostic.message.split(':').next().map_or_else(
-                        || "Fix this problem".into(),
-                        |s| format!("Fix this {s} problem"),
-                    );
-
-                let fixed_content = report.fixed_content.clone().unwrap();
-
-                return Ok(Some(vec![CodeActionOrCommand::CodeAction(CodeAction {
-                    title,
-                    kind: Some(CodeActionKind::QUICKFIX),
-                    is_preferred: Some(true),
-                    edit: Some(WorkspaceEdit {
-                        changes: Some(HashMap::from([(
-                            uri,
-                            vec![TextEdit {
-                                range: fixed_content.range,
-                                new_text: fixed_content.code,
-                            }],
-                        )])),
-                        ..WorkspaceEdit::default()
-                    }),
-                    disabled: None,
-                    data: None,
-                    diagnostics: None,
-                    command: None,
-                })]));
+        let Some(reports) =
+            self.diagnostics_report_map.get(&uri.to_string()).map(|reports| reports.clone())
+        else {
+            return Ok(None);
+        };
+        let source_text = self.documents.get(&uri.to_string()).map_or_else(
+            || uri.to_file_path().ok().and_then(|path| fs::read_to_string(path).ok()),
+            |document| Some(document.text.clone()),
+        );
+        // The directive comments are JavaScript comments, the other files only have JavaScript in
+        // some of their parts
+        let is_javascript = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| path.extension().map(|ext| ext.to_string_lossy().to_string()))
+            .is_some_and(|ext| VALID_EXTENSIONS.contains(&ext.as_str()));
+
+        let mut fixes = vec![];
+        let mut disable_actions = vec![];
+        for report in reports.iter().filter(|report| {
+            report.diagnostic.severity != Some(DiagnosticSeverity::HINT)
+                && overlaps(&report.diagnostic.range, &params.range)
+        }) {
+            fixes.extend(code_actions::fix_action(&uri, report));
+            if let Some(source_text) = &source_text {
+                disable_actions.extend(code_actions::disable_line_action(
+                    &uri,
+                    report,
+                    source_text,
+                ));
+                if is_javascript {
+                    disable_actions.extend(code_actions::disable_file_action(
+                        &uri,
+                        report,
+                        source_text,
+                    ));
+                }
             }
         }
-
-        Ok(None)
+        // The diagnostics of a rule on the same line are disabled by the same action
+        let mut titles = HashSet::new();
+        disable_actions.retain(|action: &CodeAction| titles.insert(action.title.clone()));
+
+        let actions = fixes
+            .into_iter()
+            .chain(disable_actions)
+            .map(CodeActionOrCommand::CodeAction)
+            .collect::<Vec<_>>();
+        Ok((!actions.is_empty()).then_some(actions))
     }
 }
 
+/// Whether the ranges overlap, an empty range overlaps the ranges which contain its position
+fn overlaps(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
 impl Backend {
     fn init(&self, root_uri: Option<Url>) -> Result<()> {
         self.root_uri.set(root_uri).map_err(|err| {
@@ -337,6 +388,11 @@ impl Backend {
         if rc_json_config.exists() {
             config_path = Some(rc_json_config);
         }
+        // The config written by `oxlint --init` takes precedence over the ESLint configs
+        let oxlintrc_config = root_path.join(".oxlintrc.json");
+        if oxlintrc_config.exists() {
+            config_path = Some(oxlintrc_config);
+        }
         if let Some(config_path) = config_path {
             let mut linter = self.server_linter.write().await;
             *linter = ServerLinter::new_with_linter(
@@ -348,9 +404,31 @@ impl Backend {
         }
     }
 
+    /// Lints the content of the document at `version`, the diagnostics are dropped when the
+    /// document has been changed in the meantime since they will be published for the newer
+    /// version, or when it has been closed since they were cleared by `did_close`
     async fn handle_file_update(&self, uri: Url, content: Option<String>, version: Option<i32>) {
         if let Some(Some(_root_uri)) = self.root_uri.get() {
-            if let Some(diagnostics) = self.server_linter.read().await.run_single(&uri, content) {
+            let server_linter = self.server_linter.read().await.clone();
+            let document_uri = uri.clone();
+            // Linting is blocking, the other messages are handled in the meantime
+            let diagnostics = tokio::task::spawn_blocking(move || {
+                server_linter.run_single(&document_uri, content)
+            })
+            .await
+            .ok()
+            .flatten();
+            if let Some(version) = version {
+                let is_outdated = self
+                    .documents
+                    .get(&uri.to_string())
+                    .map_or(true, |document| document.version != version);
+                if is_outdated {
+                    debug!("drop the outdated diagnostics of {uri} at version {version}");
+                    return;
+                }
+            }
+            if let Some(diagnostics) = diagnostics {
                 self.client
                     .publish_diagnostics(
                         uri.clone(),
@@ -400,6 +478,7 @@ async fn main() {
         root_uri: OnceCell::new(),
         server_linter: RwLock::new(server_linter),
         diagnostics_report_map,
+        documents: DashMap::new(),
         options: Mutex::new(Options::default()),
         gitignore_glob: Mutex::new(None),
     })
@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+
+/// The names of the config files looked up in each directory, the first one found is used
+pub const CONFIG_FILE_NAMES: [&str; 3] = [".oxlintrc.json", ".eslintrc.json", ".eslintrc"];
+
+/// Finds the config files of the directories like ESLint does: the config file of a directory
+/// is merged over the ones of its parent directories, up to a config with `"root": true` or the
+/// root of the repository.
+#[derive(Debug, Default)]
+pub struct ConfigFinder {
+    /// The config files found for the directories, from the farthest to the nearest
+    chains: FxHashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl ConfigFinder {
+    /// The config files which apply to the files in `dir`, from the farthest to the nearest, to
+    /// be merged in this order. `dir` should be absolute to be able to look up its parents.
+    pub fn find(&mut self, dir: &Path) -> Vec<PathBuf> {
+        if let Some(chain) = self.chains.get(dir) {
+            return chain.clone();
+        }
+        let config =
+            CONFIG_FILE_NAMES.iter().map(|name| dir.join(name)).find(|path| path.is_file());
+        let is_root = config.as_deref().is_some_and(is_root_config) || dir.join(".git").exists();
+        let mut chain = match dir.parent() {
+            Some(parent) if !is_root => self.find(parent),
+            _ => vec![],
+        };
+        chain.extend(config);
+        self.chains.insert(dir.to_path_buf(), chain.clone());
+        chain
+    }
+}
+
+/// A config which cannot be read is not a root config, its errors are reported when it is
+/// parsed for linting
+fn is_root_config(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .and_then(|json| json.get("root").and_then(Value::as_bool))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::ConfigFinder;
+
+    #[test]
+    fn find() {
+        let root = env::current_dir().unwrap().join("fixtures/config_discovery");
+        let mut finder = ConfigFinder::default();
+        assert_eq!(finder.find(&root), vec![root.join(".eslintrc.json")]);
+        assert_eq!(
+            finder.find(&root.join("packages/a/src")),
+            vec![root.join(".eslintrc.json"), root.join("packages/a/.oxlintrc.json")]
+        );
+        // `"root": true` stops the lookup
+        assert_eq!(finder.find(&root.join("packages/b")), vec![root.join("packages/b/.eslintrc")]);
+        assert_eq!(finder.find(&root.join("packages/c")), vec![root.join(".eslintrc.json")]);
+    }
+}
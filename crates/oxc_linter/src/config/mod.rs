@@ -1,5 +1,6 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+mod discovery;
 pub mod errors;
 use oxc_diagnostics::{Error, FailedToOpenFileError, Report};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -7,13 +8,24 @@ use serde_json::Value;
 
 use crate::{rules::RuleEnum, settings::Nextjs, AllowWarnDeny, JsxA11y, LintSettings};
 
+pub use self::discovery::{ConfigFinder, CONFIG_FILE_NAMES};
 use self::errors::{
-    FailedToParseConfigError, FailedToParseConfigJsonError, FailedToParseRuleValueError,
+    FailedToParseConfigError, FailedToParseConfigJsonError, FailedToParseConfigPropertyError,
+    FailedToParseRuleValueError,
 };
 
 pub struct ESLintConfig {
     rules: Vec<ESLintRuleConfig>,
-    settings: LintSettings,
+    /// The `settings` object, which is parsed once the configs have been merged
+    settings: Value,
+    ignore_patterns: Vec<String>,
+    plugins: Vec<String>,
+}
+
+impl Default for ESLintConfig {
+    fn default() -> Self {
+        Self { rules: vec![], settings: Value::Null, ignore_patterns: vec![], plugins: vec![] }
+    }
 }
 
 #[derive(Debug)]
@@ -28,12 +40,72 @@ impl ESLintConfig {
     pub fn new(path: &Path) -> Result<Self, Report> {
         let json = Self::read_json(path)?;
         let rules = parse_rules(&json)?;
-        let settings = parse_settings_from_root(&json);
-        Ok(Self { rules, settings })
+        let settings = json.get("settings").cloned().unwrap_or_default();
+        let ignore_patterns = parse_ignore_patterns(&json)?;
+        let plugins = parse_plugins(&json)?;
+        Ok(Self { rules, settings, ignore_patterns, plugins })
+    }
+
+    /// The configs at `paths` merged from the first to the last, the configs of the nested
+    /// directories are last and override the ones of their parent directories.
+    ///
+    /// The `ignorePatterns` of the merged config are relative to the directory of the first
+    /// config.
+    pub fn from_paths(paths: &[PathBuf]) -> Result<Self, Report> {
+        let Some((first, rest)) = paths.split_first() else {
+            return Ok(Self::default());
+        };
+        let mut config = Self::new(first)?;
+        let base_dir = first.parent().unwrap_or_else(|| Path::new(""));
+        for path in rest {
+            let nearer = Self::new(path)?;
+            let dir = path.parent().and_then(|dir| dir.strip_prefix(base_dir).ok());
+            config.merge(nearer, dir.unwrap_or_else(|| Path::new("")));
+        }
+        Ok(config)
+    }
+
+    /// Overrides `self` with the config in `dir`, which is relative to the directory of `self`
+    fn merge(&mut self, nearer: Self, dir: &Path) {
+        for rule in nearer.rules {
+            self.rules
+                .retain(|r| r.rule_name != rule.rule_name || r.plugin_name != rule.plugin_name);
+            self.rules.push(rule);
+        }
+        merge_json(&mut self.settings, nearer.settings);
+        self.ignore_patterns.extend(
+            nearer.ignore_patterns.iter().map(|pattern| rebase_ignore_pattern(pattern, dir)),
+        );
+        for plugin in nearer.plugins {
+            if !self.plugins.contains(&plugin) {
+                self.plugins.push(plugin);
+            }
+        }
     }
 
     pub fn settings(self) -> LintSettings {
-        self.settings
+        parse_settings(&self.settings)
+    }
+
+    pub fn ignore_patterns(&self) -> &[String] {
+        &self.ignore_patterns
+    }
+
+    /// The names of the plugins enabled by the `plugins` of the config file
+    pub fn plugins(&self) -> &[String] {
+        &self.plugins
+    }
+
+    /// The severity and the options of `rule` in the config file, resolved as in
+    /// [Self::override_rules]
+    pub fn rule_config(&self, rule: &RuleEnum) -> Option<(AllowWarnDeny, Option<&Value>)> {
+        let configs = self.rules.iter().filter(|r| r.rule_name == rule.name()).collect::<Vec<_>>();
+        let config = if configs.len() == 1 {
+            configs.first().filter(|r| r.plugin_name == rule.plugin_name())
+        } else {
+            configs.iter().find(|r| r.severity.is_warn_deny())
+        };
+        config.map(|r| (r.severity, r.config.as_ref()))
     }
 
     fn read_json(path: &Path) -> Result<serde_json::Value, Error> {
@@ -157,12 +229,72 @@ fn parse_rules(root_json: &Value) -> Result<Vec<ESLintRuleConfig>, Error> {
         .collect::<Result<Vec<_>, Error>>()
 }
 
-fn parse_settings_from_root(root_json: &Value) -> LintSettings {
-    let Value::Object(root_object) = root_json else { return LintSettings::default() };
+/// The `ignorePatterns` is a pattern or an array of patterns
+fn parse_ignore_patterns(root_json: &Value) -> Result<Vec<String>, Error> {
+    let Some(value) = root_json.get("ignorePatterns") else { return Ok(Vec::default()) };
+    match value {
+        Value::String(pattern) => Ok(vec![pattern.clone()]),
+        Value::Array(patterns) => patterns
+            .iter()
+            .map(|pattern| {
+                pattern.as_str().map(ToString::to_string).ok_or_else(|| {
+                    FailedToParseConfigPropertyError("ignorePatterns", "Expected a string").into()
+                })
+            })
+            .collect(),
+        _ => Err(FailedToParseConfigPropertyError(
+            "ignorePatterns",
+            "Expected a string or an array of strings",
+        )
+        .into()),
+    }
+}
 
-    let Some(settings_value) = root_object.get("settings") else { return LintSettings::default() };
+/// The `plugins` is an array of plugin names, e.g. `["jest", "jsx-a11y"]`
+fn parse_plugins(root_json: &Value) -> Result<Vec<String>, Error> {
+    let Some(value) = root_json.get("plugins") else { return Ok(Vec::default()) };
+    let Value::Array(plugins) = value else {
+        return Err(
+            FailedToParseConfigPropertyError("plugins", "Expected an array of strings").into()
+        );
+    };
+    plugins
+        .iter()
+        .map(|plugin| {
+            plugin.as_str().map(ToString::to_string).ok_or_else(|| {
+                FailedToParseConfigPropertyError("plugins", "Expected a string").into()
+            })
+        })
+        .collect()
+}
 
-    parse_settings(settings_value)
+/// The pattern of the `ignorePatterns` of a config in `dir` for the config of a parent
+/// directory, which is relative to `dir`
+fn rebase_ignore_pattern(pattern: &str, dir: &Path) -> String {
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    if dir.is_empty() {
+        return pattern.to_string();
+    }
+    let (negation, pattern) = pattern.strip_prefix('!').map_or(("", pattern), |p| ("!", p));
+    // The patterns without a slash, but the trailing one, match at any depth
+    if pattern.trim_end_matches('/').contains('/') {
+        format!("{negation}{dir}/{}", pattern.trim_start_matches('/'))
+    } else {
+        format!("{negation}{dir}/**/{pattern}")
+    }
+}
+
+/// Merges the objects of `nearer` into `value` recursively, the other values replace `value`
+fn merge_json(value: &mut Value, nearer: Value) {
+    match (value, nearer) {
+        (Value::Object(object), Value::Object(nearer)) => {
+            for (key, nearer) in nearer {
+                merge_json(object.entry(key).or_insert(Value::Null), nearer);
+            }
+        }
+        (_, Value::Null) => {}
+        (value, nearer) => *value = nearer,
+    }
 }
 
 pub fn parse_settings(setting_value: &Value) -> LintSettings {
@@ -256,7 +388,8 @@ fn resolve_rule_value(value: &serde_json::Value) -> Result<(AllowWarnDeny, Optio
 
 #[cfg(test)]
 mod test {
-    use super::parse_rules;
+    use super::{parse_ignore_patterns, parse_plugins, parse_rules, ESLintConfig};
+    use serde_json::json;
     use std::env;
 
     #[test]
@@ -267,4 +400,49 @@ mod test {
         let rules = parse_rules(&file).unwrap();
         insta::assert_debug_snapshot!(rules);
     }
+
+    #[test]
+    fn test_parse_ignore_patterns() {
+        let patterns = |json| parse_ignore_patterns(&json).map_err(|err| err.to_string());
+        assert_eq!(patterns(json!({})), Ok(vec![]));
+        assert_eq!(patterns(json!({ "ignorePatterns": "dist" })), Ok(vec!["dist".to_string()]));
+        assert_eq!(
+            patterns(json!({ "ignorePatterns": ["dist", "!dist/keep.js"] })),
+            Ok(vec!["dist".to_string(), "!dist/keep.js".to_string()])
+        );
+        assert!(patterns(json!({ "ignorePatterns": [1] })).is_err());
+    }
+
+    #[test]
+    fn test_parse_plugins() {
+        let plugins = |json| parse_plugins(&json).map_err(|err| err.to_string());
+        assert_eq!(plugins(json!({})), Ok(vec![]));
+        assert_eq!(
+            plugins(json!({ "plugins": ["jest", "jsx-a11y"] })),
+            Ok(vec!["jest".to_string(), "jsx-a11y".to_string()])
+        );
+        assert!(plugins(json!({ "plugins": "jest" })).is_err());
+        assert!(plugins(json!({ "plugins": [1] })).is_err());
+    }
+
+    #[test]
+    fn test_from_paths() {
+        let root = env::current_dir().unwrap().join("fixtures/config_discovery");
+        let paths = [root.join(".eslintrc.json"), root.join("packages/a/.oxlintrc.json")];
+        let config = ESLintConfig::from_paths(&paths).unwrap();
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| (rule.rule_name.as_str(), rule.severity.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(rules, [("no-console", "error"), ("no-debugger", "off"), ("eqeqeq", "error")]);
+        assert_eq!(
+            config.ignore_patterns(),
+            ["dist/", "packages/a/**/*.generated.js", "packages/a/lib"]
+        );
+        assert_eq!(config.plugins(), ["jest"]);
+        let settings = config.settings();
+        assert_eq!(settings.jsx_a11y.polymorphic_prop_name.as_deref(), Some("as"));
+        assert_eq!(settings.jsx_a11y.components.get("Button").map(String::as_str), Some("button"));
+    }
 }
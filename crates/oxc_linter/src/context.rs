@@ -2,7 +2,7 @@ use std::{cell::RefCell, path::Path, rc::Rc, sync::Arc};
 
 use oxc_codegen::{Codegen, CodegenOptions};
 use oxc_diagnostics::Error;
-use oxc_semantic::{AstNodes, JSDocComment, ScopeTree, Semantic, SymbolTable};
+use oxc_semantic::{AstNodes, ControlFlowGraph, JSDocComment, ScopeTree, Semantic, SymbolTable};
 use oxc_span::SourceType;
 
 use crate::{
@@ -124,9 +124,13 @@ impl<'a> LintContext<'a> {
         self.semantic().symbols()
     }
 
+    pub fn cfg(&self) -> &ControlFlowGraph {
+        self.semantic().cfg()
+    }
+
     #[allow(clippy::unused_self)]
     pub fn codegen(&self) -> Codegen<false> {
-        Codegen::<false>::new(0, CodegenOptions)
+        Codegen::<false>::new(0, CodegenOptions::default())
     }
 
     /* JSDoc */
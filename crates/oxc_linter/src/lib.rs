@@ -19,21 +19,32 @@ mod settings;
 mod utils;
 
 use rustc_hash::FxHashMap;
-use std::{io::Write, rc::Rc, sync::Arc};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
-use oxc_diagnostics::Report;
+use oxc_diagnostics::{Report, Severity};
 
 pub use crate::{
+    config::{ConfigFinder, CONFIG_FILE_NAMES},
     context::LintContext,
     fixer::Fix,
     fixer::{FixResult, Fixer, Message},
-    options::{AllowWarnDeny, LintOptions},
+    options::{AllowWarnDeny, FixOutput, LintOptions},
     rule::RuleCategory,
     rules::RULES,
-    service::LintService,
+    service::{FileTiming, FixedFile, LintService},
     settings::LintSettings,
 };
-pub(crate) use crate::{rules::RuleEnum, settings::JsxA11y};
+pub(crate) use crate::{options::RuleConfig, rules::RuleEnum, settings::JsxA11y};
 pub(crate) use oxc_semantic::AstNode;
 
 #[cfg(target_pointer_width = "64")]
@@ -52,6 +63,20 @@ pub struct Linter {
     rules: Vec<(/* rule name */ &'static str, RuleEnum)>,
     options: LintOptions,
     settings: Arc<LintSettings>,
+    /// The severities and the options of the `rules`, keyed by `plugin/rule-name`
+    rule_configs: FxHashMap<String, RuleConfig>,
+    /// The `ignorePatterns` of the config file
+    ignore_patterns: Vec<String>,
+    /// The nanoseconds spent in each of the `rules` when [LintOptions::timing] is enabled
+    rule_timings: Box<[AtomicU64]>,
+}
+
+/// The time spent in a rule, summed over the threads
+#[derive(Debug, Clone)]
+pub struct RuleTiming {
+    /// `plugin/rule-name`
+    pub name: String,
+    pub duration: Duration,
 }
 
 impl Default for Linter {
@@ -64,15 +89,27 @@ impl Linter {
     /// # Errors
     ///
     /// Returns `Err` if there are any errors parsing the configuration file.
-    pub fn from_options(options: LintOptions) -> Result<Self, Report> {
-        let (rules, settings) = options.derive_rules_and_settings()?;
-        let rules = rules.into_iter().map(|rule| (rule.name(), rule)).collect();
-        Ok(Self { rules, options, settings: Arc::new(settings) })
+    pub fn from_options(mut options: LintOptions) -> Result<Self, Report> {
+        let config = options.derive_rules_and_settings()?;
+        let rules = config.rules.into_iter().map(|rule| (rule.name(), rule)).collect::<Vec<_>>();
+        Ok(Self {
+            rule_timings: Self::new_rule_timings(rules.len()),
+            rules,
+            options,
+            settings: Arc::new(config.settings),
+            rule_configs: config.rule_configs,
+            ignore_patterns: config.ignore_patterns,
+        })
+    }
+
+    fn new_rule_timings(number_of_rules: usize) -> Box<[AtomicU64]> {
+        (0..number_of_rules).map(|_| AtomicU64::new(0)).collect()
     }
 
     #[must_use]
     pub fn with_rules(mut self, rules: Vec<RuleEnum>) -> Self {
         self.rules = rules.into_iter().map(|rule| (rule.name(), rule)).collect();
+        self.rule_timings = Self::new_rule_timings(self.rules.len());
         self
     }
 
@@ -86,6 +123,49 @@ impl Linter {
         &self.options
     }
 
+    /// The patterns of the files to be ignored from the `ignorePatterns` of the config file,
+    /// which are relative to the directory of the config file
+    pub fn ignore_patterns(&self) -> &[String] {
+        &self.ignore_patterns
+    }
+
+    /// The resolved configuration as an ESLint config: the enabled rules with their severities
+    /// and options, and the settings
+    ///
+    /// The severity is the one of the diagnostics of the rules, which are all declared as
+    /// warnings. The severities of the config file and of `--deny` only enable the rules.
+    pub fn resolved_config(&self) -> Value {
+        let rules = self
+            .rules
+            .iter()
+            .map(|(rule_name, rule)| {
+                let name = format!("{}/{rule_name}", rule.plugin_name());
+                let severity = AllowWarnDeny::from(Severity::Warning).as_str();
+                let options =
+                    self.rule_configs.get(&name).and_then(|config| config.options.as_ref());
+                let value = match options {
+                    Some(Value::Array(options)) => Value::Array(
+                        std::iter::once(json!(severity)).chain(options.iter().cloned()).collect(),
+                    ),
+                    _ => json!(severity),
+                };
+                (name, value)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let jsx_a11y = &self.settings.jsx_a11y;
+        let components = jsx_a11y.components.iter().collect::<BTreeMap<_, _>>();
+        json!({
+            "rules": rules,
+            "settings": {
+                "jsx-a11y": {
+                    "polymorphicPropName": jsx_a11y.polymorphic_prop_name,
+                    "components": components,
+                },
+                "next": { "rootDir": self.settings.nextjs.root_dir },
+            },
+        })
+    }
+
     pub fn number_of_rules(&self) -> usize {
         self.rules.len()
     }
@@ -96,7 +176,27 @@ impl Linter {
         self
     }
 
+    /// The time spent in each of the rules, the slowest first, when [LintOptions::timing] is
+    /// enabled
+    pub fn rule_timings(&self) -> Vec<RuleTiming> {
+        let mut rule_timings = self
+            .rules
+            .iter()
+            .zip(self.rule_timings.iter())
+            .map(|((rule_name, rule), nanos)| RuleTiming {
+                name: format!("{}/{rule_name}", rule.plugin_name()),
+                duration: Duration::from_nanos(nanos.load(Ordering::Relaxed)),
+            })
+            .collect::<Vec<_>>();
+        rule_timings.sort_unstable_by(|a, b| b.duration.cmp(&a.duration));
+        rule_timings
+    }
+
     pub fn run<'a>(&self, ctx: LintContext<'a>) -> Vec<Message<'a>> {
+        if self.options.timing {
+            return self.run_with_timing(ctx);
+        }
+
         let semantic = Rc::clone(ctx.semantic());
         let mut ctx = ctx.with_fix(self.options.fix).with_settings(&self.settings);
 
@@ -122,6 +222,45 @@ impl Linter {
         ctx.into_message()
     }
 
+    /// [Self::run] with the time spent in each of the rules added to [Self::rule_timings]
+    fn run_with_timing<'a>(&self, ctx: LintContext<'a>) -> Vec<Message<'a>> {
+        let semantic = Rc::clone(ctx.semantic());
+        let mut ctx = ctx.with_fix(self.options.fix).with_settings(&self.settings);
+        let mut durations = vec![Duration::ZERO; self.rules.len()];
+
+        for ((rule_name, rule), duration) in self.rules.iter().zip(durations.iter_mut()) {
+            ctx.with_rule_name(rule_name);
+            let start = Instant::now();
+            rule.run_once(&ctx);
+            *duration += start.elapsed();
+        }
+
+        for symbol in semantic.symbols().iter() {
+            for ((rule_name, rule), duration) in self.rules.iter().zip(durations.iter_mut()) {
+                ctx.with_rule_name(rule_name);
+                let start = Instant::now();
+                rule.run_on_symbol(symbol, &ctx);
+                *duration += start.elapsed();
+            }
+        }
+
+        for node in semantic.nodes().iter() {
+            for ((rule_name, rule), duration) in self.rules.iter().zip(durations.iter_mut()) {
+                ctx.with_rule_name(rule_name);
+                let start = Instant::now();
+                rule.run(node, &ctx);
+                *duration += start.elapsed();
+            }
+        }
+
+        for (nanos, duration) in self.rule_timings.iter().zip(durations) {
+            #[allow(clippy::cast_possible_truncation)]
+            nanos.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+        }
+
+        ctx.into_message()
+    }
+
     pub fn print_rules<W: Write>(writer: &mut W) {
         let rules_by_category = RULES.iter().fold(
             FxHashMap::default(),
@@ -145,7 +284,11 @@ impl Linter {
 
 #[cfg(test)]
 mod test {
-    use super::Linter;
+    use std::env;
+
+    use serde_json::json;
+
+    use super::{LintOptions, Linter};
 
     #[test]
     fn print_rules() {
@@ -153,4 +296,37 @@ mod test {
         Linter::print_rules(&mut writer);
         assert!(!writer.is_empty());
     }
+
+    #[test]
+    fn resolved_config() {
+        let config_path = env::current_dir().unwrap().join("fixtures/eslint_config.json");
+        let options = LintOptions::default().with_config_path(Some(config_path));
+        let config = Linter::from_options(options).unwrap().resolved_config();
+        let rules = &config["rules"];
+        assert_eq!(rules["eslint/no-debugger"], json!("warn"));
+        assert_eq!(rules["eslint/eqeqeq"], json!(["warn", "always", { "null": "ignore" }]));
+        assert_eq!(rules["eslint/no-bitwise"], json!(["warn", { "allow": ["~"] }]));
+        assert_eq!(rules["typescript/ban-types"], json!("warn"));
+        assert!(rules.get("eslint/no-console").is_none());
+        assert_eq!(config["settings"]["next"], json!({ "rootDir": [] }));
+
+        let options = LintOptions::default()
+            .with_filter(vec![(super::AllowWarnDeny::Deny, "no-debugger".to_string())]);
+        let config = Linter::from_options(options).unwrap().resolved_config();
+        assert_eq!(config["rules"], json!({ "eslint/no-debugger": "warn" }));
+    }
+
+    #[test]
+    fn config_plugins() {
+        let config_path = env::current_dir().unwrap().join("fixtures/plugins_config.json");
+        let options = LintOptions::default().with_config_path(Some(config_path));
+        let linter = Linter::from_options(options).unwrap();
+        assert!(linter.options().jest_plugin);
+        assert!(linter.options().nextjs_plugin);
+        assert!(!linter.options().jsx_a11y_plugin);
+        let config = linter.resolved_config();
+        let rules = config["rules"].as_object().unwrap();
+        assert!(rules.keys().any(|name| name.starts_with("jest/")));
+        assert!(rules.keys().any(|name| name.starts_with("nextjs/")));
+    }
 }
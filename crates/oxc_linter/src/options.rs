@@ -11,17 +11,33 @@ use crate::{
     rules::RULES,
     LintSettings, RuleCategory, RuleEnum,
 };
-use oxc_diagnostics::Error;
-use rustc_hash::FxHashSet;
+use oxc_diagnostics::{Error, Severity};
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde_json::{Number, Value};
 
-#[derive(Debug)]
+/// What is done with the fixed code of the files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FixOutput {
+    /// Write the fixed code to the files
+    #[default]
+    InPlace,
+    /// Keep the files, the fixed code is collected by [crate::LintService::fixed_files]
+    DryRun,
+    /// Write the fixed code to this directory, at the paths of the files relative to the working
+    /// directory, and keep the files
+    Directory(PathBuf),
+}
+
+#[derive(Debug, Clone)]
 pub struct LintOptions {
     /// Allow / Deny rules in order. [("allow" / "deny", rule name)]
     /// Defaults to [("deny", "correctness")]
     pub filter: Vec<(AllowWarnDeny, String)>,
-    pub config_path: Option<PathBuf>,
+    /// The config files, which are merged from the first to the last
+    pub config_paths: Vec<PathBuf>,
     pub fix: bool,
+    /// What is done with the fixed code when `fix` is enabled
+    pub fix_output: FixOutput,
     pub timing: bool,
     pub import_plugin: bool,
     pub jest_plugin: bool,
@@ -33,8 +49,9 @@ impl Default for LintOptions {
     fn default() -> Self {
         Self {
             filter: vec![(AllowWarnDeny::Deny, String::from("correctness"))],
-            config_path: None,
+            config_paths: vec![],
             fix: false,
+            fix_output: FixOutput::default(),
             timing: false,
             import_plugin: false,
             jest_plugin: false,
@@ -55,7 +72,15 @@ impl LintOptions {
 
     #[must_use]
     pub fn with_config_path(mut self, filter: Option<PathBuf>) -> Self {
-        self.config_path = filter;
+        self.config_paths = filter.into_iter().collect();
+        self
+    }
+
+    /// The config files of a directory and its parents found by [crate::ConfigFinder], from the
+    /// farthest to the nearest
+    #[must_use]
+    pub fn with_config_paths(mut self, config_paths: Vec<PathBuf>) -> Self {
+        self.config_paths = config_paths;
         self
     }
 
@@ -65,6 +90,12 @@ impl LintOptions {
         self
     }
 
+    #[must_use]
+    pub fn with_fix_output(mut self, fix_output: FixOutput) -> Self {
+        self.fix_output = fix_output;
+        self
+    }
+
     #[must_use]
     pub fn with_timing(mut self, yes: bool) -> Self {
         self.timing = yes;
@@ -104,6 +135,15 @@ pub enum AllowWarnDeny {
 }
 
 impl AllowWarnDeny {
+    /// The name of the severity in an ESLint config, `off`, `warn` or `error`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "off",
+            Self::Warn => "warn",
+            Self::Deny => "error",
+        }
+    }
+
     pub fn is_warn_deny(self) -> bool {
         self != Self::Allow
     }
@@ -113,6 +153,16 @@ impl AllowWarnDeny {
     }
 }
 
+/// The severity of the rules reporting diagnostics of `severity`
+impl From<Severity> for AllowWarnDeny {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => Self::Deny,
+            Severity::Warning | Severity::Advice => Self::Warn,
+        }
+    }
+}
+
 impl TryFrom<&str> for AllowWarnDeny {
     type Error = Error;
 
@@ -155,12 +205,37 @@ const JEST_PLUGIN_NAME: &str = "jest";
 const JSX_A11Y_PLUGIN_NAME: &str = "jsx_a11y";
 const NEXTJS_PLUGIN_NAME: &str = "nextjs";
 
+/// The options of an enabled rule
+#[derive(Debug, Clone)]
+pub struct RuleConfig {
+    /// The options of the rule in the config file
+    pub options: Option<Value>,
+}
+
+/// The rules, the settings and the `ignorePatterns` of the options and the config file
+pub struct DerivedConfig {
+    pub rules: Vec<RuleEnum>,
+    /// The configs of the `rules`, keyed by `plugin/rule-name`
+    pub rule_configs: FxHashMap<String, RuleConfig>,
+    pub settings: LintSettings,
+    pub ignore_patterns: Vec<String>,
+}
+
 impl LintOptions {
     /// # Errors
     ///
     /// * Returns `Err` if there are any errors parsing the configuration file.
-    pub fn derive_rules_and_settings(&self) -> Result<(Vec<RuleEnum>, LintSettings), Error> {
-        let config = self.config_path.as_ref().map(|path| ESLintConfig::new(path)).transpose()?;
+    ///
+    /// The plugins in the `plugins` of the configuration file are enabled in `self`.
+    pub(crate) fn derive_rules_and_settings(&mut self) -> Result<DerivedConfig, Error> {
+        let config = if self.config_paths.is_empty() {
+            None
+        } else {
+            Some(ESLintConfig::from_paths(&self.config_paths)?)
+        };
+        if let Some(config) = &config {
+            self.enable_plugins(config.plugins());
+        }
 
         let mut rules: FxHashSet<RuleEnum> = FxHashSet::default();
         let all_rules = self.get_filtered_rules();
@@ -209,7 +284,35 @@ impl LintOptions {
         let mut rules = rules.into_iter().collect::<Vec<_>>();
         // for stable diagnostics output ordering
         rules.sort_unstable_by_key(RuleEnum::name);
-        Ok((rules, config.map(ESLintConfig::settings).unwrap_or_default()))
+        let rule_configs = rules
+            .iter()
+            .map(|rule| {
+                let options = config
+                    .as_ref()
+                    .and_then(|config| config.rule_config(rule))
+                    .filter(|(severity, _)| severity.is_warn_deny())
+                    .and_then(|(_, options)| options.cloned());
+                (format!("{}/{}", rule.plugin_name(), rule.name()), RuleConfig { options })
+            })
+            .collect();
+        let ignore_patterns =
+            config.as_ref().map(|config| config.ignore_patterns().to_vec()).unwrap_or_default();
+        let settings = config.map(ESLintConfig::settings).unwrap_or_default();
+        Ok(DerivedConfig { rules, rule_configs, settings, ignore_patterns })
+    }
+
+    /// Enables the plugins named as in ESLint configs, the unknown ones are ignored since the
+    /// configs are shared with ESLint
+    fn enable_plugins(&mut self, plugins: &[String]) {
+        for plugin in plugins {
+            match plugin.trim_start_matches("eslint-plugin-") {
+                "import" => self.import_plugin = true,
+                JEST_PLUGIN_NAME => self.jest_plugin = true,
+                "jsx-a11y" | JSX_A11Y_PLUGIN_NAME => self.jsx_a11y_plugin = true,
+                "@next/next" | "next" | NEXTJS_PLUGIN_NAME => self.nextjs_plugin = true,
+                _ => {}
+            }
+        }
     }
 
     // get final filtered rules by reading `self.jest_plugin` and `self.jsx_a11y_plugin`
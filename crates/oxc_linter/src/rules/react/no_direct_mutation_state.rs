@@ -12,12 +12,7 @@ use oxc_diagnostics::{
 use oxc_macros::declare_oxc_lint;
 use oxc_span::{GetSpan, Span};
 
-use crate::{
-    context::LintContext,
-    rule::Rule,
-    utils::{is_es5_component, is_es6_component},
-    AstNode,
-};
+use crate::{context::LintContext, rule::Rule, utils::is_class_component, AstNode};
 
 #[derive(Debug, Error, Diagnostic)]
 #[error("eslint-plugin-react(no-direct-mutation-state): never mutate this.state directly.")]
@@ -161,7 +156,7 @@ fn should_ignore_component<'a, 'b>(node: &'b AstNode<'a>, ctx: &'b LintContext<'
             is_call_expression_node = true;
         }
 
-        if is_es6_component(parent) || is_es5_component(parent) {
+        if is_class_component(parent) {
             is_component = true;
         }
     }
@@ -15,7 +15,7 @@ use crate::{
     rules::eslint::array_callback_return::return_checker::{
         check_statement, StatementReturnStatus,
     },
-    utils::{is_es5_component, is_es6_component},
+    utils::is_class_component,
     AstNode,
 };
 
@@ -56,7 +56,7 @@ declare_oxc_lint!(
 
 impl Rule for RequireRenderReturn {
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
-        if !is_es5_component(node) && !is_es6_component(node) {
+        if !is_class_component(node) {
             return;
         }
 
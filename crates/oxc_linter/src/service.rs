@@ -1,25 +1,33 @@
 use std::{
     collections::HashMap,
     fs,
-    path::Path,
+    path::{Component, Path, PathBuf},
     rc::Rc,
     sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
 };
 
 use dashmap::DashMap;
-use rayon::{iter::ParallelBridge, prelude::ParallelIterator};
-use rustc_hash::FxHashSet;
+use rayon::{
+    iter::{
+        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelBridge,
+    },
+    prelude::ParallelIterator,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use oxc_allocator::Allocator;
-use oxc_diagnostics::{DiagnosticSender, DiagnosticService, Error, FailedToOpenFileError};
+use oxc_allocator::{Allocator, AllocatorPool};
+use oxc_diagnostics::{
+    DiagnosticSender, DiagnosticService, Error, FailedToOpenFileError, FailedToWriteFileError,
+};
+use oxc_module_resolver::{FileSystem, FileSystemOs, ResolveOptions, Resolver};
 use oxc_parser::Parser;
-use oxc_resolver::{ResolveOptions, Resolver};
 use oxc_semantic::{ModuleRecord, SemanticBuilder};
 use oxc_span::{SourceType, VALID_EXTENSIONS};
 
 use crate::{
     partial_loader::{JavaScriptSource, PartialLoader, LINT_PARTIAL_LOADER_EXT},
-    Fixer, LintContext, Linter, Message,
+    FixOutput, Fixer, LintContext, Linter, Message, RuleTiming,
 };
 
 #[derive(Clone)]
@@ -33,6 +41,22 @@ impl LintService {
         Self { runtime }
     }
 
+    /// The files in `file_linters` are linted with their own linters, e.g. the ones of the
+    /// config files found for their directories, and the other files with `linter`.
+    ///
+    /// The options other than the rules and the settings, such as the fixes and the plugins
+    /// which load the dependencies, are the ones of `linter`.
+    pub fn with_file_linters(
+        cwd: Box<Path>,
+        paths: &[Box<Path>],
+        linter: Linter,
+        file_linters: Vec<(Box<Path>, Arc<Linter>)>,
+    ) -> Self {
+        let mut runtime = Runtime::new(cwd, paths, linter);
+        runtime.file_linters = file_linters.into_iter().collect();
+        Self { runtime: Arc::new(runtime) }
+    }
+
     #[cfg(test)]
     pub(crate) fn from_linter(cwd: Box<Path>, paths: &[Box<Path>], linter: Linter) -> Self {
         let runtime = Arc::new(Runtime::new(cwd, paths, linter));
@@ -43,17 +67,68 @@ impl LintService {
         &self.runtime.linter
     }
 
+    /// The files with fixes, sorted by their paths, when the fixes are not written with
+    /// [FixOutput::DryRun]
+    ///
+    /// # Panics
+    pub fn fixed_files(&self) -> Vec<FixedFile> {
+        let mut fixed_files = std::mem::take(&mut *self.runtime.fixed_files.lock().unwrap());
+        fixed_files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+        fixed_files
+    }
+
+    /// The time spent on each of the files to lint, the slowest first, when
+    /// [crate::LintOptions::timing] is enabled
+    ///
+    /// # Panics
+    pub fn file_timings(&self) -> Vec<FileTiming> {
+        let mut file_timings = std::mem::take(&mut *self.runtime.file_timings.lock().unwrap());
+        file_timings.sort_unstable_by(|a, b| b.duration.cmp(&a.duration));
+        file_timings
+    }
+
+    /// The time spent in each of the rules, summed over the linters of the files, the slowest
+    /// first, when [crate::LintOptions::timing] is enabled
+    pub fn rule_timings(&self) -> Vec<RuleTiming> {
+        let mut linters = vec![&self.runtime.linter];
+        for linter in self.runtime.file_linters.values() {
+            if !linters.iter().any(|l| std::ptr::eq(*l, linter.as_ref())) {
+                linters.push(linter);
+            }
+        }
+        let mut durations = FxHashMap::<String, Duration>::default();
+        for timing in linters.into_iter().flat_map(Linter::rule_timings) {
+            *durations.entry(timing.name).or_default() += timing.duration;
+        }
+        let mut rule_timings = durations
+            .into_iter()
+            .map(|(name, duration)| RuleTiming { name, duration })
+            .collect::<Vec<_>>();
+        rule_timings.sort_unstable_by(|a, b| b.duration.cmp(&a.duration).then(a.name.cmp(&b.name)));
+        rule_timings
+    }
+
     pub fn number_of_dependencies(&self) -> usize {
         self.runtime.module_map.len() - self.runtime.paths.len()
     }
 
+    /// The files are linted from the largest to the smallest, each one in its own task, so that
+    /// the large files do not keep a thread busy at the end of the run while the others are idle.
+    ///
     /// # Panics
     pub fn run(&self, tx_error: &DiagnosticSender) {
-        self.runtime
-            .paths
-            .iter()
-            .par_bridge()
-            .for_each_with(&self.runtime, |runtime, path| runtime.process_path(path, tx_error));
+        self.runtime.paths_by_size().into_par_iter().with_max_len(1).for_each_with(
+            &self.runtime,
+            |runtime, path| {
+                let start = Instant::now();
+                runtime.process_path(path, tx_error);
+                if runtime.linter.options().timing {
+                    let path = path.strip_prefix(&runtime.cwd).unwrap_or(path).to_path_buf();
+                    let file_timing = FileTiming { path, duration: start.elapsed() };
+                    runtime.file_timings.lock().unwrap().push(file_timing);
+                }
+            },
+        );
         tx_error.send(None).unwrap();
     }
 
@@ -105,14 +180,37 @@ enum CacheStateEntry {
 /// Keyed by canonicalized path
 type ModuleMap = DashMap<Box<Path>, Arc<ModuleRecord>>;
 
+/// The code of a file and its code with the fixes applied
+#[derive(Debug, Clone)]
+pub struct FixedFile {
+    /// The path relative to the working directory
+    pub path: PathBuf,
+    pub source_text: String,
+    pub fixed_text: String,
+}
+
+/// The time spent on parsing, building the semantic model and running the rules of a file
+#[derive(Debug, Clone)]
+pub struct FileTiming {
+    /// The path relative to the working directory
+    pub path: PathBuf,
+    pub duration: Duration,
+}
+
 pub struct Runtime {
     cwd: Box<Path>,
     /// All paths to lint
     paths: FxHashSet<Box<Path>>,
     linter: Linter,
+    /// The linters of the files which are not linted with `linter`
+    file_linters: FxHashMap<Box<Path>, Arc<Linter>>,
     resolver: Resolver,
     module_map: ModuleMap,
     cache_state: CacheState,
+    fixed_files: Mutex<Vec<FixedFile>>,
+    file_timings: Mutex<Vec<FileTiming>>,
+    /// The arenas of the sources, reused by the threads for the next files
+    allocator_pool: AllocatorPool,
 }
 
 impl Runtime {
@@ -121,15 +219,38 @@ impl Runtime {
             cwd,
             paths: paths.iter().cloned().collect(),
             linter,
+            file_linters: FxHashMap::default(),
             resolver: Self::resolver(),
             module_map: ModuleMap::default(),
             cache_state: CacheState::default(),
+            fixed_files: Mutex::default(),
+            file_timings: Mutex::default(),
+            allocator_pool: AllocatorPool::default(),
         }
     }
 
+    /// The paths to lint sorted by the size of their files, the largest first
+    fn paths_by_size(&self) -> Vec<&Path> {
+        let mut paths = self
+            .paths
+            .par_iter()
+            .map(|path| (fs::metadata(path).map_or(0, |metadata| metadata.len()), path.as_ref()))
+            .collect::<Vec<_>>();
+        paths.sort_unstable_by(|(a_size, a_path), (b_size, b_path)| {
+            b_size.cmp(a_size).then_with(|| a_path.cmp(b_path))
+        });
+        paths.into_iter().map(|(_, path)| path).collect()
+    }
+
     fn resolver() -> Resolver {
         Resolver::new(ResolveOptions {
             extensions: VALID_EXTENSIONS.iter().map(|ext| format!(".{ext}")).collect(),
+            condition_names: vec![
+                "module".into(),
+                "import".into(),
+                "require".into(),
+                "node".into(),
+            ],
             ..ResolveOptions::default()
         })
     }
@@ -139,7 +260,9 @@ impl Runtime {
         ext: &str,
     ) -> Option<Result<(SourceType, String), Error>> {
         let read_file = |path: &Path| -> Result<String, Error> {
+            // The resolved modules of the Yarn PnP installs are in the zip archives of the cache
             fs::read_to_string(path)
+                .or_else(|e| FileSystemOs::default().read_to_string(path).map_err(|_| e))
                 .map_err(|e| Error::new(FailedToOpenFileError(path.to_path_buf(), e)))
         };
         let source_type = SourceType::from_path(path);
@@ -182,14 +305,19 @@ impl Runtime {
             return;
         }
 
-        for JavaScriptSource { source_text, source_type, .. } in sources {
-            let allocator = Allocator::default();
+        // The fixed code of the sources, which are parts of the file for the partial loaders
+        let mut fixed_sources = vec![];
+        for JavaScriptSource { source_text, source_type, start } in sources {
+            let allocator = self.allocator_pool.get();
             let mut messages =
                 self.process_source(path, &allocator, source_text, source_type, true, tx_error);
 
             if self.linter.options().fix {
                 let fix_result = Fixer::new(source_text, messages).fix();
-                fs::write(path, fix_result.fixed_code.as_bytes()).unwrap();
+                if fix_result.fixed {
+                    let end = start + source_text.len();
+                    fixed_sources.push((start, end, fix_result.fixed_code.into_owned()));
+                }
                 messages = fix_result.messages;
             }
 
@@ -200,6 +328,52 @@ impl Runtime {
                 tx_error.send(Some(diagnostics)).unwrap();
             }
         }
+
+        if !fixed_sources.is_empty() {
+            let mut fixed_text = String::with_capacity(source_text.len());
+            let mut offset = 0;
+            for (start, end, fixed_code) in fixed_sources {
+                fixed_text.push_str(&source_text[offset..start]);
+                fixed_text.push_str(&fixed_code);
+                offset = end;
+            }
+            fixed_text.push_str(&source_text[offset..]);
+            if let Err(e) = self.write_fixes(path, source_text, fixed_text) {
+                tx_error.send(Some((path.to_path_buf(), vec![e]))).unwrap();
+            }
+        }
+    }
+
+    fn write_fixes(
+        &self,
+        path: &Path,
+        source_text: String,
+        fixed_text: String,
+    ) -> Result<(), Error> {
+        let relative_path = path.strip_prefix(&self.cwd).unwrap_or(path);
+        match &self.linter.options().fix_output {
+            FixOutput::InPlace => fs::write(path, fixed_text.as_bytes())
+                .map_err(|e| Error::new(FailedToWriteFileError(path.to_path_buf(), e))),
+            FixOutput::DryRun => {
+                let path = relative_path.to_path_buf();
+                let fixed_file = FixedFile { path, source_text, fixed_text };
+                self.fixed_files.lock().unwrap().push(fixed_file);
+                Ok(())
+            }
+            FixOutput::Directory(dir) => {
+                // The paths outside of the working directory are kept from their root
+                let relative_path = relative_path
+                    .components()
+                    .filter(|component| matches!(component, Component::Normal(_)))
+                    .collect::<PathBuf>();
+                let fixed_path = dir.join(relative_path);
+                fixed_path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|()| fs::write(&fixed_path, fixed_text.as_bytes()))
+                    .map_err(|e| Error::new(FailedToWriteFileError(fixed_path, e)))
+            }
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -269,7 +443,7 @@ impl Runtime {
 
         let lint_ctx =
             LintContext::new(path.to_path_buf().into_boxed_path(), &Rc::new(semantic_ret.semantic));
-        self.linter.run(lint_ctx)
+        self.file_linters.get(path).map_or(&self.linter, AsRef::as_ref).run(lint_ctx)
     }
 
     fn init_cache_state(&self, path: &Path) -> bool {
@@ -327,3 +501,75 @@ impl Runtime {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{env, sync::Arc};
+
+    use oxc_diagnostics::DiagnosticService;
+
+    use super::LintService;
+    use crate::{ConfigFinder, LintOptions, Linter};
+
+    fn service(timing: bool) -> LintService {
+        let cwd = env::current_dir().unwrap().join("fixtures/import").into_boxed_path();
+        let paths = ["bar.js", "CaseyKasem.js", "deprecated.js"]
+            .iter()
+            .map(|path| cwd.join(path).into_boxed_path())
+            .collect::<Vec<_>>();
+        let linter = Linter::from_options(LintOptions::default().with_timing(timing)).unwrap();
+        LintService::new(cwd, &paths, linter)
+    }
+
+    #[test]
+    fn paths_by_size() {
+        let service = service(false);
+        let paths = service.runtime.paths_by_size();
+        let sizes = paths.iter().map(|path| path.metadata().unwrap().len()).collect::<Vec<_>>();
+        assert!(sizes.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(paths[0].file_name().unwrap(), "deprecated.js");
+        assert_eq!(paths[2].file_name().unwrap(), "CaseyKasem.js");
+    }
+
+    #[test]
+    fn file_timings() {
+        for timing in [false, true] {
+            let service = service(timing);
+            let diagnostic_service = DiagnosticService::default();
+            service.run(diagnostic_service.sender());
+            let file_timings = service.file_timings();
+            assert_eq!(file_timings.len(), if timing { 3 } else { 0 });
+            assert!(file_timings.windows(2).all(|w| w[0].duration >= w[1].duration));
+            assert!(file_timings.iter().all(|timing| timing.path.is_relative()));
+            let rule_timings = service.rule_timings();
+            assert_eq!(rule_timings.len(), service.linter().number_of_rules());
+            assert!(rule_timings.windows(2).all(|w| w[0].duration >= w[1].duration));
+            assert_eq!(rule_timings.iter().any(|timing| !timing.duration.is_zero()), timing);
+        }
+    }
+
+    #[test]
+    fn file_linters() {
+        let cwd = env::current_dir().unwrap().join("fixtures/config_discovery").into_boxed_path();
+        let paths = ["packages/a/src/a.js", "packages/b/b.js", "packages/c/c.js"]
+            .iter()
+            .map(|path| cwd.join(path).into_boxed_path())
+            .collect::<Vec<_>>();
+        let mut finder = ConfigFinder::default();
+        let file_linters = paths
+            .iter()
+            .map(|path| {
+                let config_paths = finder.find(path.parent().unwrap());
+                let options = LintOptions::default().with_config_paths(config_paths);
+                (path.clone(), Arc::new(Linter::from_options(options).unwrap()))
+            })
+            .collect::<Vec<_>>();
+        let linter = Linter::from_options(LintOptions::default()).unwrap();
+        let service = LintService::with_file_linters(cwd, &paths, linter, file_linters);
+        let diagnostic_service = DiagnosticService::default();
+        service.run(diagnostic_service.sender());
+        diagnostic_service.run();
+        // `no-debugger` is turned off in `packages/a`
+        assert_eq!(diagnostic_service.warnings_count() + diagnostic_service.errors_count(), 2);
+    }
+}
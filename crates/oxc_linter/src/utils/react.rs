@@ -1,23 +1,14 @@
-use oxc_ast::{
-    ast::{
-        CallExpression, Expression, JSXAttributeItem, JSXAttributeName, JSXAttributeValue,
-        JSXChild, JSXElement, JSXElementName, JSXExpression, JSXExpressionContainer,
-        JSXOpeningElement,
-    },
-    AstKind,
+use oxc_ast::ast::{
+    Expression, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement,
+    JSXElementName, JSXExpression, JSXExpressionContainer, JSXOpeningElement,
+};
+pub use oxc_semantic::react::{
+    is_class_component, is_create_element_call, is_es5_component, is_es6_component,
 };
 use oxc_semantic::{AstNode, SymbolFlags};
 
 use crate::{JsxA11y, LintContext, LintSettings};
 
-pub fn is_create_element_call(call_expr: &CallExpression) -> bool {
-    if let Some(member_expr) = call_expr.callee.get_member_expr() {
-        return member_expr.static_property_name() == Some("createElement");
-    }
-
-    false
-}
-
 pub fn has_jsx_prop<'a, 'b>(
     node: &'b JSXOpeningElement<'a>,
     target_prop: &'b str,
@@ -154,49 +145,6 @@ pub fn is_interactive_element(element_type: &str, jsx_opening_el: &JSXOpeningEle
     }
 }
 
-const PRAGMA: &str = "React";
-const CREATE_CLASS: &str = "createReactClass";
-
-pub fn is_es5_component(node: &AstNode) -> bool {
-    let AstKind::CallExpression(call_expr) = node.kind() else { return false };
-
-    if let Expression::MemberExpression(member_expr) = &call_expr.callee {
-        if let Expression::Identifier(ident) = member_expr.object() {
-            return ident.name == PRAGMA
-                && member_expr.static_property_name() == Some(CREATE_CLASS);
-        }
-    }
-
-    if let Some(ident_reference) = call_expr.callee.get_identifier_reference() {
-        return ident_reference.name == CREATE_CLASS;
-    }
-
-    false
-}
-
-const COMPONENT: &str = "Component";
-const PURE_COMPONENT: &str = "PureComponent";
-
-pub fn is_es6_component(node: &AstNode) -> bool {
-    let AstKind::Class(class_expr) = node.kind() else { return false };
-    if let Some(super_class) = &class_expr.super_class {
-        if let Expression::MemberExpression(member_expr) = super_class {
-            if let Expression::Identifier(ident) = member_expr.object() {
-                return ident.name == PRAGMA
-                    && member_expr
-                        .static_property_name()
-                        .is_some_and(|name| name == COMPONENT || name == PURE_COMPONENT);
-            }
-        }
-
-        if let Some(ident_reference) = super_class.get_identifier_reference() {
-            return ident_reference.name == COMPONENT || ident_reference.name == PURE_COMPONENT;
-        }
-    }
-
-    false
-}
-
 pub fn get_parent_es5_component<'a, 'b>(
     node: &'b AstNode<'a>,
     ctx: &'b LintContext<'a>,
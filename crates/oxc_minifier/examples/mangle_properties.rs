@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use oxc_allocator::Allocator;
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_minifier::{ManglePropertiesOptions, PropertyMangler, PropertyNameCache};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use regex::Regex;
+
+use pico_args::Arguments;
+
+// Instruction:
+// create a `test.js`,
+// run `cargo run -p oxc_minifier --example mangle_properties -- --regex "^_" --name-cache cache.json`
+// The name cache is created when it does not exist, and updated with the new names.
+
+fn main() {
+    let mut args = Arguments::from_env();
+
+    let regex: String = args.opt_value_from_str("--regex").unwrap().unwrap_or_else(|| "^_".into());
+    let name_cache_path: Option<String> = args.opt_value_from_str("--name-cache").unwrap();
+    let keep_quoted = args.contains("--keep-quoted");
+    let name = args.free_from_str().unwrap_or_else(|_| String::from("test.js"));
+
+    let path = Path::new(&name);
+    let source_text = std::fs::read_to_string(path).unwrap_or_else(|_| panic!("{name} not found"));
+    let source_type = SourceType::from_path(path).unwrap();
+
+    let name_cache = name_cache_path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| PropertyNameCache::from_json(&text).expect("invalid name cache"))
+        .unwrap_or_default();
+
+    let allocator = Allocator::default();
+    let program = Parser::new(&allocator, &source_text, source_type).parse().program;
+    let program = allocator.alloc(program);
+    let options = ManglePropertiesOptions {
+        keep_quoted,
+        ..ManglePropertiesOptions::new(Regex::new(&regex).expect("invalid regex"))
+    };
+    let mut mangler = PropertyMangler::new(&allocator, options).with_name_cache(name_cache);
+    mangler.build(program);
+    println!(
+        "{}",
+        Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program)
+    );
+
+    if let Some(path) = name_cache_path {
+        std::fs::write(path, mangler.name_cache().to_json()).unwrap();
+    }
+}
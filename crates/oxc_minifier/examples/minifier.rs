@@ -1,8 +1,8 @@
 use std::path::Path;
 
 use oxc_allocator::Allocator;
-use oxc_codegen::{Codegen, CodegenOptions};
-use oxc_minifier::{Minifier, MinifierOptions};
+use oxc_codegen::{Charset, Codegen, CodegenOptions};
+use oxc_minifier::{ManglerOptions, Minifier, MinifierOptions};
 use oxc_parser::Parser;
 use oxc_span::SourceType;
 
@@ -46,11 +46,19 @@ fn minify(source_text: &str, source_type: SourceType, mangle: bool, whitespace:
     let allocator = Allocator::default();
     let program = Parser::new(&allocator, source_text, source_type).parse().program;
     let program = allocator.alloc(program);
-    let options = MinifierOptions { mangle, ..MinifierOptions::default() };
+    let options = MinifierOptions {
+        mangle: mangle.then(ManglerOptions::default),
+        ..MinifierOptions::default()
+    };
     Minifier::new(options).build(&allocator, program);
     if whitespace {
-        Codegen::<true>::new(source_text.len(), CodegenOptions).build(program)
+        let options = CodegenOptions {
+            charset: Charset::Ascii,
+            escape_script_end_tags: true,
+            ..CodegenOptions::default()
+        };
+        Codegen::<true>::new(source_text.len(), options).build(program)
     } else {
-        Codegen::<false>::new(source_text.len(), CodegenOptions).build(program)
+        Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program)
     }
 }
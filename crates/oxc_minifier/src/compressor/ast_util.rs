@@ -6,7 +6,7 @@ use oxc_semantic::ReferenceFlag;
 use oxc_syntax::operator::{AssignmentOperator, LogicalOperator, UnaryOperator};
 
 use oxc_ast::ast::{
-    ArrayExpressionElement, BinaryExpression, Expression, NumberLiteral, ObjectProperty,
+    Argument, ArrayExpressionElement, BinaryExpression, Expression, NumberLiteral, ObjectProperty,
     ObjectPropertyKind, PropertyKey, SpreadElement, UnaryExpression,
 };
 
@@ -158,6 +158,15 @@ impl<'a, 'b> CheckForStateChange<'a, 'b> for Expression<'a> {
                     .iter()
                     .any(|element| element.check_for_state_change(check_for_new_objects))
             }
+            // `/* #__PURE__ */ foo(a)` only evaluates its arguments when its result is unused
+            Self::CallExpression(call_expr) if call_expr.pure => call_expr
+                .arguments
+                .iter()
+                .any(|argument| argument.check_for_state_change(check_for_new_objects)),
+            Self::NewExpression(new_expr) if new_expr.pure => new_expr
+                .arguments
+                .iter()
+                .any(|argument| argument.check_for_state_change(check_for_new_objects)),
             _ => true,
         }
     }
@@ -191,6 +200,15 @@ impl<'a, 'b> CheckForStateChange<'a, 'b> for ArrayExpressionElement<'a> {
     }
 }
 
+impl<'a, 'b> CheckForStateChange<'a, 'b> for Argument<'a> {
+    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+        match self {
+            Self::SpreadElement(element) => element.check_for_state_change(check_for_new_objects),
+            Self::Expression(expr) => expr.check_for_state_change(check_for_new_objects),
+        }
+    }
+}
+
 impl<'a, 'b> CheckForStateChange<'a, 'b> for ObjectPropertyKind<'a> {
     fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
         match self {
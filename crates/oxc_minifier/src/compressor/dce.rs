@@ -0,0 +1,411 @@
+//! Dead Code Elimination
+//!
+//! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeRemoveDeadCode.java>
+//! <https://github.com/terser/terser/blob/master/lib/compress/drop-side-effect-free.js>
+
+use std::collections::HashSet;
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, AstKind, Visit, VisitMut};
+use oxc_semantic::{Reference, ScopeFlags, SemanticBuilder, SymbolId, SymbolTable};
+
+use super::{
+    ast_util::{get_boolean_value, MayHaveSideEffects},
+    CompressOptions,
+};
+
+/// Removes the code which can not be reached or has no observable effect:
+///
+/// * the statements after `return`, `throw`, `break` and `continue`
+/// * the branches of `if` and `while` statements whose tests are constant
+/// * the expression statements without side effects
+/// * the function and variable declarations of function bodies which are never referenced
+///
+/// The calls of the functions annotated with `/* #__NO_SIDE_EFFECTS__ */` are marked as
+/// `/* #__PURE__ */`, so that their statements are removed like the other pure calls.
+///
+/// The symbols are resolved again after each round of removals, until nothing is removed.
+pub struct RemoveDeadCode<'a> {
+    ast: AstBuilder<'a>,
+    options: CompressOptions,
+
+    symbols: SymbolTable,
+    /// The functions annotated with `/* #__NO_SIDE_EFFECTS__ */` which are never reassigned
+    no_side_effects: HashSet<SymbolId>,
+    /// Direct `eval` calls can reference any declaration in scope
+    has_eval: bool,
+    changed: bool,
+}
+
+impl<'a> RemoveDeadCode<'a> {
+    pub fn new(allocator: &'a Allocator, options: CompressOptions) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            options,
+            symbols: SymbolTable::default(),
+            no_side_effects: HashSet::new(),
+            has_eval: false,
+            changed: false,
+        }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        loop {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            self.symbols = semantic.into_symbol_table_and_scope_tree().0;
+            let mut finder = NoSideEffectsFinder { symbols: HashSet::new() };
+            finder.visit_program(program);
+            self.no_side_effects = finder.symbols;
+            self.no_side_effects.retain(|symbol_id| {
+                !self.symbols.get_resolved_references(*symbol_id).any(Reference::is_write)
+            });
+            self.has_eval = self.symbols.references.iter().any(|reference| {
+                reference.symbol_id().is_none() && reference.name().as_str() == "eval"
+            });
+            self.changed = false;
+            self.visit_program(program);
+            if !self.changed {
+                break;
+            }
+        }
+    }
+
+    /// Drop the statements following a statement which completes abruptly.
+    ///
+    /// `return; foo(); var x = 1; function f() {}` -> `return; var x; function f() {}`
+    fn remove_unreachable(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let Some(index) = stmts.iter().position(|stmt| {
+            matches!(
+                stmt,
+                Statement::ReturnStatement(_)
+                    | Statement::ThrowStatement(_)
+                    | Statement::BreakStatement(_)
+                    | Statement::ContinueStatement(_)
+            )
+        }) else {
+            return;
+        };
+        let unreachable = stmts.drain(index + 1..).collect::<std::vec::Vec<_>>();
+        // Function declarations and `var` bindings are hoisted
+        for mut stmt in unreachable {
+            match &mut stmt {
+                Statement::Declaration(Declaration::FunctionDeclaration(_)) => {
+                    stmts.push(stmt);
+                    continue;
+                }
+                Statement::Declaration(Declaration::VariableDeclaration(decl))
+                    if decl.kind.is_var() =>
+                {
+                    for declarator in decl.declarations.iter_mut() {
+                        self.changed |= declarator.init.take().is_some();
+                    }
+                    stmts.push(stmt);
+                    continue;
+                }
+                _ => {}
+            }
+            self.changed = true;
+        }
+    }
+
+    /// `if (false) a; else b;` -> `b;`, `while (false) a;` -> ``, `for (; false; ) a;` -> ``
+    ///
+    /// Returns the statements replacing `stmt`.
+    fn fold_constant_branch(&mut self, stmt: Statement<'a>) -> std::vec::Vec<Statement<'a>> {
+        match stmt {
+            Statement::IfStatement(if_stmt) => {
+                let Some(test) = self.constant_test(&if_stmt.test) else {
+                    return vec![Statement::IfStatement(if_stmt)];
+                };
+                // The declarations of `var` and functions are hoisted out of the removed branch
+                let removed =
+                    if test { if_stmt.alternate.as_ref() } else { Some(&if_stmt.consequent) };
+                if removed.is_some_and(has_hoisted_declaration) {
+                    return vec![Statement::IfStatement(if_stmt)];
+                }
+                self.changed = true;
+                let if_stmt = if_stmt.unbox();
+                let kept = if test { Some(if_stmt.consequent) } else { if_stmt.alternate };
+                kept.map_or_else(std::vec::Vec::new, |stmt| self.unwrap_block(stmt))
+            }
+            Statement::WhileStatement(while_stmt)
+                if self.constant_test(&while_stmt.test) == Some(false)
+                    && !has_hoisted_declaration(&while_stmt.body) =>
+            {
+                self.changed = true;
+                vec![]
+            }
+            // `while` loops are compressed into `for` loops
+            Statement::ForStatement(for_stmt)
+                if for_stmt.init.is_none()
+                    && for_stmt.test.as_ref().and_then(|test| self.constant_test(test))
+                        == Some(false)
+                    && !has_hoisted_declaration(&for_stmt.body) =>
+            {
+                self.changed = true;
+                vec![]
+            }
+            stmt => vec![stmt],
+        }
+    }
+
+    /// The value of a test without side effects
+    fn constant_test(&self, test: &Expression<'a>) -> Option<bool> {
+        if test.may_have_side_effects() {
+            return None;
+        }
+        get_boolean_value(test)
+    }
+
+    /// `{ a; b; }` -> `a; b;`, when the block does not declare lexical bindings
+    fn unwrap_block(&self, stmt: Statement<'a>) -> std::vec::Vec<Statement<'a>> {
+        match stmt {
+            Statement::BlockStatement(block)
+                if !block.body.iter().any(|stmt| {
+                    matches!(
+                        stmt,
+                        Statement::Declaration(
+                            Declaration::VariableDeclaration(_)
+                                | Declaration::UsingDeclaration(_)
+                                | Declaration::FunctionDeclaration(_)
+                                | Declaration::ClassDeclaration(_)
+                        )
+                    )
+                }) =>
+            {
+                block.unbox().body.into_iter().collect()
+            }
+            stmt => vec![stmt],
+        }
+    }
+
+    /// `1; x;` -> `x;`, where `x` may not be declared
+    fn is_side_effect_free_statement(&self, stmt: &Statement<'a>) -> bool {
+        let Statement::ExpressionStatement(expr_stmt) = stmt else { return false };
+        self.is_side_effect_free(&expr_stmt.expression)
+    }
+
+    /// `1, f(), x;` -> `f(), x;`, the values of the expressions of statement level sequences
+    /// are unused
+    fn remove_side_effect_free_expressions(&mut self, stmt: &mut Statement<'a>) {
+        let Statement::ExpressionStatement(expr_stmt) = stmt else { return };
+        let Expression::SequenceExpression(seq) = &mut expr_stmt.expression else { return };
+        let len = seq.expressions.len();
+        seq.expressions.retain(|expr| !self.is_side_effect_free(expr));
+        if seq.expressions.len() == len {
+            return;
+        }
+        self.changed = true;
+        if seq.expressions.len() == 1 {
+            expr_stmt.expression = seq.expressions.pop().unwrap();
+        }
+    }
+
+    fn is_side_effect_free(&self, expr: &Expression<'a>) -> bool {
+        if expr.may_have_side_effects() {
+            return false;
+        }
+        // Reading an undeclared variable throws
+        let mut finder = GlobalReferenceFinder { symbols: &self.symbols, found: false };
+        finder.visit_expression(expr);
+        !finder.found
+    }
+
+    /// Drop the function and variable declarations of a function body which are never referenced.
+    ///
+    /// `function f() { var x = 1; function g() {} return 2; }` -> `function f() { return 2; }`
+    fn remove_unused_declarations(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if self.has_eval {
+            return;
+        }
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            match &mut stmt {
+                Statement::Declaration(Declaration::FunctionDeclaration(func))
+                    if func.id.as_ref().is_some_and(|id| self.is_unused(id)) =>
+                {
+                    self.changed = true;
+                    continue;
+                }
+                Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                    let len = decl.declarations.len();
+                    decl.declarations.retain(|declarator| !self.is_unused_declarator(declarator));
+                    if decl.declarations.len() != len {
+                        self.changed = true;
+                    }
+                    if decl.declarations.is_empty() {
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            stmts.push(stmt);
+        }
+    }
+
+    fn is_unused_declarator(&self, declarator: &VariableDeclarator<'a>) -> bool {
+        let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else { return false };
+        self.is_unused(id)
+            && declarator.init.as_ref().map_or(true, |init| !init.may_have_side_effects())
+    }
+
+    /// Whether the binding is neither read nor written
+    fn is_unused(&self, id: &BindingIdentifier) -> bool {
+        id.symbol_id
+            .get()
+            .is_some_and(|symbol_id| self.symbols.get_resolved_reference_ids(symbol_id).is_empty())
+    }
+}
+
+impl<'a> VisitMut<'a> for RemoveDeadCode<'a> {
+    fn visit_call_expression(&mut self, expr: &mut CallExpression<'a>) {
+        if let Expression::Identifier(ident) = &expr.callee {
+            let symbol_id = ident
+                .reference_id
+                .get()
+                .and_then(|reference_id| self.symbols.get_reference(reference_id).symbol_id());
+            if symbol_id.is_some_and(|symbol_id| self.no_side_effects.contains(&symbol_id)) {
+                expr.pure = true;
+            }
+        }
+        self.visit_expression(&mut expr.callee);
+        for argument in expr.arguments.iter_mut() {
+            self.visit_argument(argument);
+        }
+    }
+
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        for stmt in stmts.iter_mut() {
+            self.visit_statement(stmt);
+        }
+
+        self.remove_unreachable(stmts);
+
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for stmt in old_stmts {
+            for mut stmt in self.fold_constant_branch(stmt) {
+                self.remove_side_effect_free_expressions(&mut stmt);
+                if self.is_side_effect_free_statement(&stmt) {
+                    self.changed = true;
+                    continue;
+                }
+                stmts.push(stmt);
+            }
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &mut FunctionBody<'a>) {
+        self.visit_statements(&mut body.statements);
+        if self.options.unused {
+            self.remove_unused_declarations(&mut body.statements);
+        }
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &mut ArrowExpression<'a>) {
+        self.visit_formal_parameters(&mut expr.params);
+        if !expr.expression {
+            self.visit_function_body(&mut expr.body);
+            return;
+        }
+        // The expression of `() => x` is its return value
+        if let Some(Statement::ExpressionStatement(stmt)) = expr.body.statements.iter_mut().next() {
+            self.visit_expression(&mut stmt.expression);
+        }
+    }
+}
+
+/// Whether `stmt` declares `var` variables or functions outside of nested functions
+fn has_hoisted_declaration(stmt: &Statement) -> bool {
+    let mut finder = HoistedDeclarationFinder { found: false };
+    finder.visit_statement(stmt);
+    finder.found
+}
+
+struct HoistedDeclarationFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for HoistedDeclarationFinder {
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
+        if decl.kind.is_var() {
+            self.found = true;
+        }
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, _flags: Option<ScopeFlags>) {
+        if func.is_declaration() {
+            self.found = true;
+        }
+    }
+
+    fn visit_arrow_expression(&mut self, _expr: &ArrowExpression<'a>) {}
+}
+
+/// Finds the functions annotated with `/* #__NO_SIDE_EFFECTS__ */`
+struct NoSideEffectsFinder {
+    symbols: HashSet<SymbolId>,
+}
+
+impl<'a> Visit<'a> for NoSideEffectsFinder {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(func) if func.pure && func.is_declaration() => {
+                self.symbols.extend(func.id.as_ref().and_then(|id| id.symbol_id.get()));
+            }
+            AstKind::VariableDeclarator(declarator) => {
+                let is_pure = match &declarator.init {
+                    Some(Expression::FunctionExpression(func)) => func.pure,
+                    Some(Expression::ArrowExpression(arrow)) => arrow.pure,
+                    _ => false,
+                };
+                if let (true, BindingPatternKind::BindingIdentifier(id)) =
+                    (is_pure, &declarator.id.kind)
+                {
+                    self.symbols.extend(id.symbol_id.get());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Finds the references to undeclared variables, except `undefined`, `NaN` and `Infinity`,
+/// outside of the callees of pure calls
+struct GlobalReferenceFinder<'b> {
+    symbols: &'b SymbolTable,
+    found: bool,
+}
+
+impl<'a, 'b> Visit<'a> for GlobalReferenceFinder<'b> {
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        if !expr.pure {
+            self.visit_expression(&expr.callee);
+        }
+        for argument in &expr.arguments {
+            self.visit_argument(argument);
+        }
+    }
+
+    fn visit_new_expression(&mut self, expr: &NewExpression<'a>) {
+        if !expr.pure {
+            self.visit_expression(&expr.callee);
+        }
+        for argument in &expr.arguments {
+            self.visit_argument(argument);
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference) {
+        if matches!(ident.name.as_str(), "undefined" | "NaN" | "Infinity") {
+            return;
+        }
+        let is_global = ident
+            .reference_id
+            .get()
+            .map_or(true, |reference_id| self.symbols.is_global_reference(reference_id));
+        if is_global {
+            self.found = true;
+        }
+    }
+}
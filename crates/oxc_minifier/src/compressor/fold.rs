@@ -6,11 +6,14 @@ use std::{cmp::Ordering, mem, ops::Not};
 
 use num_bigint::BigInt;
 #[allow(clippy::wildcard_imports)]
-use oxc_ast::ast::*;
+use oxc_ast::{
+    ast::*,
+    constant_evaluation::{ConstantEvaluation, ConstantValue},
+};
 use oxc_span::{Atom, GetSpan, Span};
 use oxc_syntax::{
     operator::{BinaryOperator, LogicalOperator, UnaryOperator},
-    NumberBase,
+    BigintBase, NumberBase,
 };
 
 use super::ast_util::{
@@ -210,9 +213,25 @@ impl<'a> Compressor<'a> {
                 BinaryOperator::Addition if self.options.evaluate => {
                     self.try_fold_addition(binary_expr.span, &binary_expr.left, &binary_expr.right)
                 }
+                BinaryOperator::Subtraction
+                | BinaryOperator::Multiplication
+                | BinaryOperator::Division
+                | BinaryOperator::Remainder
+                | BinaryOperator::Exponential
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseOR
+                | BinaryOperator::BitwiseXOR
+                    if self.options.evaluate =>
+                {
+                    self.try_fold_arithmetic(binary_expr)
+                        .or_else(|| self.try_fold_unsafe_math(binary_expr))
+                }
                 _ => None,
             },
             Expression::UnaryExpression(unary_expr) => match unary_expr.operator {
+                UnaryOperator::LogicalNot if self.options.booleans && is_triple_not(unary_expr) => {
+                    self.try_fold_triple_not(unary_expr)
+                }
                 UnaryOperator::Typeof => {
                     self.try_fold_typeof(unary_expr.span, &unary_expr.argument)
                 }
@@ -233,6 +252,7 @@ impl<'a> Compressor<'a> {
                 }
                 LogicalOperator::Coalesce => None,
             },
+            Expression::ConditionalExpression(cond_expr) => self.try_fold_conditional(cond_expr),
             _ => None,
         };
         if let Some(folded_expr) = folded_expr {
@@ -240,6 +260,132 @@ impl<'a> Compressor<'a> {
         }
     }
 
+    /// Folds the arithmetic and bitwise operations of constants with the shared constant
+    /// evaluator, when the result is not longer than the operation, e.g.
+    /// `10 * 10` -> `100`, `7 & 3` -> `3`, `2n ** 3n` -> `8n`.
+    /// `1 / 3` and `1 / 0` are kept.
+    fn try_fold_arithmetic(
+        &mut self,
+        binary_expr: &BinaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        let left = binary_expr.left.evaluate_constant()?;
+        let right = binary_expr.right.evaluate_constant()?;
+        let operation_len = left.to_js_string().len()
+            + binary_expr.operator.as_str().len()
+            + right.to_js_string().len();
+        match binary_expr.evaluate_constant()? {
+            ConstantValue::Number(value) if value.is_finite() => {
+                let raw = ConstantValue::Number(value).to_js_string();
+                if raw.len() > operation_len {
+                    return None;
+                }
+                let base =
+                    if value.fract() == 0.0 { NumberBase::Decimal } else { NumberBase::Float };
+                let literal =
+                    self.ast.number_literal(binary_expr.span, value, self.ast.new_str(&raw), base);
+                Some(self.ast.literal_number_expression(literal))
+            }
+            ConstantValue::BigInt(value) if value.to_string().len() <= operation_len => {
+                let literal = self.ast.bigint_literal(binary_expr.span, value, BigintBase::Decimal);
+                Some(self.ast.literal_bigint_expression(literal))
+            }
+            _ => None,
+        }
+    }
+
+    /// `x * 2 * 3` -> `x * 6`, `x / 2 / 3` -> `x / 6`, which may round differently.
+    /// Enabled by `compress.unsafe_math`
+    fn try_fold_unsafe_math(
+        &mut self,
+        binary_expr: &mut BinaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        let operator = binary_expr.operator;
+        if !self.options.unsafe_math
+            || !matches!(operator, BinaryOperator::Multiplication | BinaryOperator::Division)
+        {
+            return None;
+        }
+        let Expression::BinaryExpression(left) = &mut binary_expr.left else { return None };
+        if left.operator != operator {
+            return None;
+        }
+        let (Expression::NumberLiteral(a), Expression::NumberLiteral(b)) =
+            (&left.right, &binary_expr.right)
+        else {
+            return None;
+        };
+        let value = a.value * b.value;
+        let raw = ConstantValue::Number(value).to_js_string();
+        if !value.is_finite() || raw.len() > a.raw.len() + 1 + b.raw.len() {
+            return None;
+        }
+        let base = if value.fract() == 0.0 { NumberBase::Decimal } else { NumberBase::Float };
+        let literal = self.ast.number_literal(b.span, value, self.ast.new_str(&raw), base);
+        let right = self.ast.literal_number_expression(literal);
+        let left = self.ast.move_expression(&mut left.left);
+        Some(self.ast.binary_expression(binary_expr.span, left, operator, right))
+    }
+
+    /// `!!!x` -> `!x`
+    fn try_fold_triple_not(
+        &mut self,
+        unary_expr: &mut UnaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        let Expression::UnaryExpression(inner) = &mut unary_expr.argument else { return None };
+        Some(self.move_out_expression(&mut inner.argument))
+    }
+
+    /// * `true ? a : b` -> `a`, when the test is a constant
+    /// * `a ? true : false` -> `!!a`
+    /// * `a ? false : true` -> `!a`
+    /// * `!!a ? b : c` -> `a ? b : c`
+    fn try_fold_conditional(
+        &mut self,
+        cond_expr: &mut ConditionalExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        if let Some(test) = cond_expr.test.evaluate_constant() {
+            let branch = if test.to_boolean() {
+                &mut cond_expr.consequent
+            } else {
+                &mut cond_expr.alternate
+            };
+            return Some(self.move_out_expression(branch));
+        }
+        if !self.options.booleans {
+            return None;
+        }
+        self.strip_double_not(&mut cond_expr.test);
+        match (literal_boolean(&cond_expr.consequent), literal_boolean(&cond_expr.alternate)) {
+            (Some(true), Some(false)) => {
+                let test = self.move_out_expression(&mut cond_expr.test);
+                let not =
+                    self.ast.unary_expression(cond_expr.span, UnaryOperator::LogicalNot, test);
+                Some(self.ast.unary_expression(cond_expr.span, UnaryOperator::LogicalNot, not))
+            }
+            (Some(false), Some(true)) => {
+                let test = self.move_out_expression(&mut cond_expr.test);
+                Some(self.ast.unary_expression(cond_expr.span, UnaryOperator::LogicalNot, test))
+            }
+            _ => None,
+        }
+    }
+
+    /// `!!a` -> `a`, for the expressions in a boolean context
+    fn strip_double_not(&mut self, expr: &mut Expression<'a>) {
+        let Expression::UnaryExpression(unary_expr) = expr else { return };
+        if unary_expr.operator != UnaryOperator::LogicalNot {
+            return;
+        }
+        let Expression::UnaryExpression(inner) = &mut unary_expr.argument else { return };
+        if inner.operator != UnaryOperator::LogicalNot
+            // Keep `!!0` and `!!1`, which are the compressed booleans
+            || matches!(inner.argument, Expression::NumberLiteral(_))
+        {
+            return;
+        }
+        *expr = self.move_out_expression(&mut inner.argument);
+    }
+
     fn try_fold_addition<'b>(
         &mut self,
         span: Span,
@@ -295,7 +441,18 @@ impl<'a> Compressor<'a> {
         let value = match self.evaluate_comparison(op, left, right) {
             Tri::True => true,
             Tri::False => false,
-            Tri::Unknown => return None,
+            // Fall back to the shared constant evaluator, e.g. for `` `a` === 'a' ``
+            Tri::Unknown => {
+                let left = left.evaluate_constant()?;
+                let right = right.evaluate_constant()?;
+                match op {
+                    BinaryOperator::StrictEquality => left.strict_equals(&right),
+                    BinaryOperator::StrictInequality => !left.strict_equals(&right),
+                    BinaryOperator::Equality => left.loose_equals(&right)?,
+                    BinaryOperator::Inequality => !left.loose_equals(&right)?,
+                    _ => return None,
+                }
+            }
         };
         let boolean_literal = self.ast.boolean_literal(span, value);
         Some(self.ast.literal_boolean_expression(boolean_literal))
@@ -592,7 +749,10 @@ impl<'a> Compressor<'a> {
             }
         }
 
-        None
+        // `typeof (1 + 1)` -> `"number"`, `` typeof `${a}` `` is not a constant
+        let type_name = argument.evaluate_constant()?.type_of();
+        let string_literal = StringLiteral::new(span, Atom::from(type_name));
+        Some(self.ast.literal_string_expression(string_literal))
     }
 
     fn try_fold_unary_operator(
@@ -887,6 +1047,23 @@ impl<'a> Compressor<'a> {
     }
 
     pub(crate) fn fold_condition<'b>(&mut self, stmt: &'b mut Statement<'a>) {
+        if self.options.booleans {
+            match stmt {
+                Statement::IfStatement(if_stmt) => self.strip_double_not(&mut if_stmt.test),
+                Statement::WhileStatement(while_stmt) => {
+                    self.strip_double_not(&mut while_stmt.test);
+                }
+                Statement::DoWhileStatement(do_while_stmt) => {
+                    self.strip_double_not(&mut do_while_stmt.test);
+                }
+                Statement::ForStatement(for_stmt) => {
+                    if let Some(test) = &mut for_stmt.test {
+                        self.strip_double_not(test);
+                    }
+                }
+                _ => {}
+            }
+        }
         match stmt {
             Statement::WhileStatement(while_stmt) => {
                 let minimized_expr = self.fold_expression_in_condition(&mut while_stmt.0.test);
@@ -961,3 +1138,27 @@ impl<'a> Compressor<'a> {
         }
     }
 }
+
+fn is_triple_not(unary_expr: &UnaryExpression) -> bool {
+    matches!(&unary_expr.argument, Expression::UnaryExpression(inner)
+        if inner.operator == UnaryOperator::LogicalNot
+            && matches!(&inner.argument, Expression::UnaryExpression(arg) if arg.operator == UnaryOperator::LogicalNot))
+}
+
+/// The value of `true` and `false`, or of the compressed `!0` and `!1`
+#[allow(clippy::float_cmp)]
+fn literal_boolean(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::BooleanLiteral(lit) => Some(lit.value),
+        Expression::UnaryExpression(unary_expr)
+            if unary_expr.operator == UnaryOperator::LogicalNot =>
+        {
+            match &unary_expr.argument {
+                Expression::NumberLiteral(lit) if lit.value == 0.0 => Some(true),
+                Expression::NumberLiteral(lit) if lit.value == 1.0 => Some(false),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
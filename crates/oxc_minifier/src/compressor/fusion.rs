@@ -0,0 +1,155 @@
+//! Statement Fusion
+//!
+//! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/StatementFusion.java>
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::Span;
+use oxc_syntax::operator::UnaryOperator;
+
+use super::{Compressor, SPAN};
+
+impl<'a> Compressor<'a> {
+    /// `if (x) return a; return b` -> `return x ? a : b`,
+    /// `if (x) return a; else return b` -> `return x ? a : b`
+    pub(crate) fn fold_if_return(&self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if !self.options.if_return {
+            return;
+        }
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            if let Some(ret) = self.if_else_return(&mut stmt) {
+                stmt = ret;
+            }
+            // `if (x) return a; if (y) return b; return c` -> `return x ? a : y ? b : c`
+            while matches!(&stmt, Statement::ReturnStatement(_))
+                && stmts.last().is_some_and(Self::is_if_return)
+            {
+                let Some(Statement::IfStatement(mut if_stmt)) = stmts.pop() else { unreachable!() };
+                let Statement::ReturnStatement(alternate) = &mut stmt else { unreachable!() };
+                let span = Span::new(if_stmt.span.start, alternate.span.end);
+                let Some(ret) =
+                    self.conditional_return(span, &mut if_stmt, alternate.argument.take())
+                else {
+                    stmts.push(Statement::IfStatement(if_stmt));
+                    break;
+                };
+                stmt = ret;
+            }
+            stmts.push(stmt);
+        }
+    }
+
+    /// `if (x) return a` without an `else` branch
+    fn is_if_return(stmt: &Statement<'a>) -> bool {
+        matches!(stmt, Statement::IfStatement(if_stmt)
+            if if_stmt.alternate.is_none()
+                && matches!(if_stmt.consequent, Statement::ReturnStatement(_)))
+    }
+
+    fn if_else_return(&self, stmt: &mut Statement<'a>) -> Option<Statement<'a>> {
+        let Statement::IfStatement(if_stmt) = stmt else { return None };
+        let Some(Statement::ReturnStatement(alternate)) = &mut if_stmt.alternate else {
+            return None;
+        };
+        let alternate = alternate.argument.take();
+        self.conditional_return(if_stmt.span, if_stmt, alternate)
+    }
+
+    /// `return test ? consequent : alternate` of `if (test) return consequent`, a missing argument
+    /// returns `void 0`.
+    /// Returns `None` and leaves the statements unchanged when neither returns a value,
+    /// because `return x ? void 0 : void 0` is longer.
+    fn conditional_return(
+        &self,
+        span: Span,
+        if_stmt: &mut IfStatement<'a>,
+        alternate: Option<Expression<'a>>,
+    ) -> Option<Statement<'a>> {
+        let Statement::ReturnStatement(consequent) = &mut if_stmt.consequent else { return None };
+        if consequent.argument.is_none() && alternate.is_none() {
+            return None;
+        }
+        let consequent = consequent.argument.take().unwrap_or_else(|| self.ast.void_0());
+        let alternate = alternate.unwrap_or_else(|| self.ast.void_0());
+        let test = self.ast.move_expression(&mut if_stmt.test);
+        let expr = self.ast.conditional_expression(SPAN, test, consequent, alternate);
+        Some(self.ast.return_statement(span, Some(expr)))
+    }
+
+    /// Merge the consecutive expression statements into a sequence expression, together with the
+    /// expressions of the following `return`, `throw` or `if` statement:
+    /// `a(); b(); return c` -> `return a(), b(), c`, `a(); return` -> `return void a()`
+    pub(crate) fn fuse_statements(&self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if !self.options.sequences {
+            return;
+        }
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        let mut exprs = self.ast.new_vec();
+        // The span of the merged statements
+        let mut span = SPAN;
+        for mut stmt in old_stmts {
+            if let Statement::ExpressionStatement(expr_stmt) = &mut stmt {
+                if exprs.is_empty() {
+                    span.start = expr_stmt.span.start;
+                }
+                span.end = expr_stmt.span.end;
+                let expr = self.ast.move_expression(&mut expr_stmt.expression);
+                self.push_sequence(&mut exprs, expr);
+                continue;
+            }
+            if !exprs.is_empty() {
+                match &mut stmt {
+                    Statement::ReturnStatement(ret) => {
+                        let argument = if let Some(argument) = ret.argument.take() {
+                            self.sequence(exprs, argument)
+                        } else {
+                            let expr = self.sequence_of(exprs);
+                            self.ast.unary_expression(SPAN, UnaryOperator::Void, expr)
+                        };
+                        ret.argument = Some(argument);
+                    }
+                    Statement::ThrowStatement(throw) => {
+                        let argument = self.ast.move_expression(&mut throw.argument);
+                        throw.argument = self.sequence(exprs, argument);
+                    }
+                    Statement::IfStatement(if_stmt) => {
+                        let test = self.ast.move_expression(&mut if_stmt.test);
+                        if_stmt.test = self.sequence(exprs, test);
+                    }
+                    _ => {
+                        let expr = self.sequence_of(exprs);
+                        stmts.push(self.ast.expression_statement(span, expr));
+                    }
+                }
+                exprs = self.ast.new_vec();
+            }
+            stmts.push(stmt);
+        }
+        if !exprs.is_empty() {
+            let expr = self.sequence_of(exprs);
+            stmts.push(self.ast.expression_statement(span, expr));
+        }
+    }
+
+    /// `(a, b), c` -> `a, b, c`
+    fn push_sequence(&self, exprs: &mut Vec<'a, Expression<'a>>, expr: Expression<'a>) {
+        match expr {
+            Expression::SequenceExpression(mut seq) => exprs.append(&mut seq.expressions),
+            expr => exprs.push(expr),
+        }
+    }
+
+    fn sequence(&self, mut exprs: Vec<'a, Expression<'a>>, last: Expression<'a>) -> Expression<'a> {
+        self.push_sequence(&mut exprs, last);
+        self.sequence_of(exprs)
+    }
+
+    fn sequence_of(&self, mut exprs: Vec<'a, Expression<'a>>) -> Expression<'a> {
+        if exprs.len() == 1 {
+            return exprs.pop().unwrap();
+        }
+        self.ast.sequence_expression(SPAN, exprs)
+    }
+}
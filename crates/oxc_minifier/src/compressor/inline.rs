@@ -0,0 +1,362 @@
+//! Function Inlining
+//!
+//! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/InlineFunctions.java>
+//! <https://github.com/terser/terser/blob/master/lib/compress/inline.js>
+
+use std::collections::HashMap;
+
+use oxc_allocator::{Allocator, Box, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, Visit, VisitMut};
+use oxc_semantic::{Reference, ScopeFlags, SemanticBuilder, SymbolId, SymbolTable};
+use oxc_span::SPAN;
+use oxc_syntax::operator::UnaryOperator;
+
+use super::CompressOptions;
+
+/// Inlines the functions which are called only once:
+///
+/// * the function declarations of the same scope with a single reference are moved into their call:
+///   `function f(a) { g(a); } f(x);` -> `(function(a) { g(a); })(x);`
+/// * the immediately invoked functions which only return an expression are replaced by the
+///   expression, the parameters are replaced by the literal arguments:
+///   `(function(a) { return a + b; })(1)` -> `1 + b`
+///
+/// Only the declarations of function bodies and modules are inlined, the top level declarations
+/// of scripts are global.
+pub struct InlineFunctions<'a> {
+    ast: AstBuilder<'a>,
+    options: CompressOptions,
+
+    symbols: SymbolTable,
+    /// Direct `eval` calls can reference any declaration in scope
+    has_eval: bool,
+}
+
+impl<'a> InlineFunctions<'a> {
+    pub fn new(allocator: &'a Allocator, options: CompressOptions) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            options,
+            symbols: SymbolTable::default(),
+            has_eval: false,
+        }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+        self.symbols = semantic.into_symbol_table_and_scope_tree().0;
+        self.has_eval = self.symbols.references.iter().any(|reference| {
+            reference.symbol_id().is_none() && reference.name().as_str() == "eval"
+        });
+        self.visit_program(program);
+    }
+
+    fn is_referenced(&self, id: &BindingIdentifier) -> bool {
+        id.symbol_id
+            .get()
+            .is_some_and(|symbol_id| !self.symbols.get_resolved_reference_ids(symbol_id).is_empty())
+    }
+
+    /// Moves the function declarations of `stmts` which are called once by the other statements
+    /// into their calls
+    fn inline_single_use_functions(&self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if self.has_eval {
+            return;
+        }
+        let mut i = 0;
+        while i < stmts.len() {
+            let Some(symbol_id) = self.single_use_function(&stmts[i]) else {
+                i += 1;
+                continue;
+            };
+            let Statement::Declaration(Declaration::FunctionDeclaration(func)) = stmts.remove(i)
+            else {
+                unreachable!()
+            };
+            let mut finder = CallSiteFinder { inliner: self, symbol_id, func: Some(func) };
+            for stmt in stmts.iter_mut() {
+                finder.visit_statement_expressions(stmt);
+                if finder.func.is_none() {
+                    break;
+                }
+            }
+            if let Some(func) = finder.func {
+                // The call is not in the same scope
+                stmts.insert(i, Statement::Declaration(Declaration::FunctionDeclaration(func)));
+                i += 1;
+            }
+        }
+    }
+
+    /// The symbol of a function declaration which is only called once, and whose source is not
+    /// longer than [CompressOptions::inline_max_size]
+    fn single_use_function(&self, stmt: &Statement<'a>) -> Option<SymbolId> {
+        let Statement::Declaration(Declaration::FunctionDeclaration(func)) = stmt else {
+            return None;
+        };
+        if func.body.is_none() || func.span.size() as usize > self.options.inline_max_size {
+            return None;
+        }
+        let id = func.id.as_ref()?;
+        let symbol_id = id.symbol_id.get()?;
+        let mut references = self.symbols.get_resolved_references(symbol_id);
+        let is_single_read =
+            references.next().is_some_and(Reference::is_read) && references.next().is_none();
+        is_single_read.then_some(symbol_id)
+    }
+
+    /// `(function(a) { return a + b })(1)` -> `1 + b`, `(() => {})()` -> `void 0`
+    fn flatten_call(&self, call: &mut CallExpression<'a>) -> Option<Expression<'a>> {
+        if call.pure {
+            return None;
+        }
+        let is_arrow_expression =
+            matches!(&call.callee, Expression::ArrowExpression(arrow) if arrow.expression);
+        let (params, body) = match &mut call.callee {
+            Expression::FunctionExpression(func) => {
+                let func = &mut **func;
+                if func.r#async
+                    || func.generator
+                    || func.id.as_ref().is_some_and(|id| self.is_referenced(id))
+                {
+                    return None;
+                }
+                let body = func.body.as_mut()?;
+                if body.statements.first().is_some_and(uses_function_scope) {
+                    return None;
+                }
+                (&mut func.params, body)
+            }
+            Expression::ArrowExpression(arrow) if !arrow.r#async && !arrow.generator => {
+                let arrow = &mut **arrow;
+                (&mut arrow.params, &mut arrow.body)
+            }
+            _ => return None,
+        };
+        if !body.directives.is_empty() || body.statements.len() > 1 || params.rest.is_some() {
+            return None;
+        }
+        if !body.statements.first().map_or(true, |stmt| {
+            matches!(stmt, Statement::ReturnStatement(_) | Statement::ExpressionStatement(_))
+        }) {
+            return None;
+        }
+        let mut parameters = vec![];
+        for param in &params.items {
+            let BindingPatternKind::BindingIdentifier(id) = &param.pattern.kind else {
+                return None;
+            };
+            let symbol_id = id.symbol_id.get()?;
+            let mut references = self.symbols.get_resolved_references(symbol_id);
+            let is_single_read =
+                references.next().map_or(true, Reference::is_read) && references.next().is_none();
+            if !is_single_read || !param.decorators.is_empty() {
+                return None;
+            }
+            parameters.push(symbol_id);
+        }
+        if !call.arguments.iter().all(is_literal_argument) {
+            return None;
+        }
+
+        let mut replacements = HashMap::new();
+        let mut arguments = call.arguments.drain(..);
+        for symbol_id in parameters {
+            let value = match arguments.next() {
+                Some(Argument::Expression(expr)) => expr,
+                _ => self.ast.void_0(),
+            };
+            replacements.insert(symbol_id, value);
+        }
+        drop(arguments);
+        let mut replacer = ReplaceParameters { symbols: &self.symbols, replacements };
+
+        let value = match body.statements.pop() {
+            Some(Statement::ReturnStatement(mut ret)) => ret.argument.take(),
+            Some(Statement::ExpressionStatement(mut stmt)) => {
+                let expr = self.ast.move_expression(&mut stmt.expression);
+                // The body of `() => x` is its return value
+                Some(if is_arrow_expression {
+                    expr
+                } else {
+                    self.ast.unary_expression(SPAN, UnaryOperator::Void, expr)
+                })
+            }
+            _ => None,
+        };
+        Some(value.map_or_else(
+            || self.ast.void_0(),
+            |mut value| {
+                replacer.visit_expression(&mut value);
+                value
+            },
+        ))
+    }
+}
+
+impl<'a> VisitMut<'a> for InlineFunctions<'a> {
+    fn visit_program(&mut self, program: &mut Program<'a>) {
+        self.visit_statements(&mut program.body);
+        if program.source_type.is_module() {
+            self.inline_single_use_functions(&mut program.body);
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &mut FunctionBody<'a>) {
+        self.visit_statements(&mut body.statements);
+        self.inline_single_use_functions(&mut body.statements);
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        if let Expression::CallExpression(call) = expr {
+            if let Some(value) = self.flatten_call(call) {
+                *expr = value;
+            }
+        }
+    }
+}
+
+/// `1`, `"a"`, `true` and `null`, which can be moved and are not evaluated
+fn is_literal_argument(argument: &Argument) -> bool {
+    matches!(
+        argument,
+        Argument::Expression(
+            Expression::NumberLiteral(_)
+                | Expression::StringLiteral(_)
+                | Expression::BooleanLiteral(_)
+                | Expression::NullLiteral(_)
+        )
+    )
+}
+
+/// Whether the statement uses the `this`, `arguments`, `new.target` or `super` of the enclosing
+/// function, which are different once the statement is moved out of the function
+fn uses_function_scope(stmt: &Statement) -> bool {
+    let mut finder = FunctionScopeFinder { found: false };
+    finder.visit_statement(stmt);
+    finder.found
+}
+
+struct FunctionScopeFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for FunctionScopeFinder {
+    fn visit_this_expression(&mut self, _expr: &ThisExpression) {
+        self.found = true;
+    }
+
+    fn visit_super(&mut self, _expr: &Super) {
+        self.found = true;
+    }
+
+    fn visit_meta_property(&mut self, _meta: &MetaProperty) {
+        self.found = true;
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference) {
+        if ident.name == "arguments" {
+            self.found = true;
+        }
+    }
+
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    /// The `this` of class fields and computed keys
+    fn visit_class(&mut self, _class: &Class<'a>) {
+        self.found = true;
+    }
+}
+
+/// Replaces the references of the parameters by their arguments
+struct ReplaceParameters<'b, 'a> {
+    symbols: &'b SymbolTable,
+    replacements: HashMap<SymbolId, Expression<'a>>,
+}
+
+impl<'b, 'a> VisitMut<'a> for ReplaceParameters<'b, 'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if let Expression::Identifier(ident) = expr {
+            let symbol_id = ident
+                .reference_id
+                .get()
+                .and_then(|reference_id| self.symbols.get_reference(reference_id).symbol_id());
+            if let Some(value) =
+                symbol_id.and_then(|symbol_id| self.replacements.remove(&symbol_id))
+            {
+                *expr = value;
+            }
+            return;
+        }
+        self.visit_expression_match(expr);
+    }
+}
+
+/// Finds the call of a function declaration in the expressions of a statement, outside of
+/// nested scopes, and replaces the callee by the function
+struct CallSiteFinder<'b, 'a> {
+    inliner: &'b InlineFunctions<'a>,
+    symbol_id: SymbolId,
+    func: Option<Box<'a, Function<'a>>>,
+}
+
+impl<'b, 'a> CallSiteFinder<'b, 'a> {
+    /// The expressions of a statement which are evaluated in the scope of the statement
+    fn visit_statement_expressions(&mut self, stmt: &mut Statement<'a>) {
+        match stmt {
+            Statement::ExpressionStatement(stmt) => self.visit_expression(&mut stmt.expression),
+            Statement::ReturnStatement(stmt) => {
+                if let Some(argument) = &mut stmt.argument {
+                    self.visit_expression(argument);
+                }
+            }
+            Statement::ThrowStatement(stmt) => self.visit_expression(&mut stmt.argument),
+            Statement::IfStatement(stmt) => self.visit_expression(&mut stmt.test),
+            Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                for declarator in decl.declarations.iter_mut() {
+                    if let Some(init) = &mut declarator.init {
+                        self.visit_expression(init);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn is_callee(&self, callee: &Expression<'a>) -> bool {
+        let Expression::Identifier(ident) = callee else { return false };
+        ident.reference_id.get().is_some_and(|reference_id| {
+            self.inliner.symbols.get_reference(reference_id).symbol_id() == Some(self.symbol_id)
+        })
+    }
+}
+
+impl<'b, 'a> VisitMut<'a> for CallSiteFinder<'b, 'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.func.is_none() {
+            return;
+        }
+        if let Expression::CallExpression(call) = expr {
+            if self.is_callee(&call.callee) {
+                let mut func = self.func.take().unwrap();
+                func.r#type = FunctionType::FunctionExpression;
+                func.id = None;
+                call.pure |= func.pure;
+                call.callee = self.inliner.ast.function_expression(func);
+                if let Some(value) = self.inliner.flatten_call(call) {
+                    *expr = value;
+                }
+                return;
+            }
+        }
+        self.visit_expression_match(expr);
+    }
+
+    fn visit_function(&mut self, _func: &mut Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &mut ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &mut Class<'a>) {}
+}
@@ -1,7 +1,10 @@
 #![allow(clippy::unused_self)]
 
 mod ast_util;
+mod dce;
 mod fold;
+mod fusion;
+mod inline;
 mod options;
 mod prepass;
 mod util;
@@ -17,25 +20,41 @@ use oxc_syntax::{
 };
 
 pub use self::options::CompressOptions;
-use self::prepass::Prepass;
+use self::{dce::RemoveDeadCode, inline::InlineFunctions, prepass::Prepass};
 
 pub struct Compressor<'a> {
     ast: AstBuilder<'a>,
     options: CompressOptions,
 
     prepass: Prepass<'a>,
+    inline: Option<InlineFunctions<'a>>,
+    dce: Option<RemoveDeadCode<'a>>,
 }
 
 const SPAN: Span = Span::new(0, 0);
 
 impl<'a> Compressor<'a> {
     pub fn new(allocator: &'a Allocator, options: CompressOptions) -> Self {
-        Self { ast: AstBuilder::new(allocator), options, prepass: Prepass::new(allocator) }
+        Self {
+            ast: AstBuilder::new(allocator),
+            inline: options.inline.then(|| InlineFunctions::new(allocator, options.clone())),
+            dce: options.dead_code.then(|| RemoveDeadCode::new(allocator, options.clone())),
+            options,
+            prepass: Prepass::new(allocator),
+        }
     }
 
     pub fn build(mut self, program: &mut Program<'a>) {
         self.prepass.build(program);
-        self.visit_program(program);
+        for _ in 0..self.options.passes.max(1) {
+            if let Some(inline) = &mut self.inline {
+                inline.build(program);
+            }
+            self.visit_program(program);
+            if let Some(dce) = &mut self.dce {
+                dce.build(program);
+            }
+        }
     }
 
     /* Utilities */
@@ -88,6 +107,19 @@ impl<'a> Compressor<'a> {
         }
     }
 
+    /// Mark the calls of `compress.pure_funcs` as `/* #__PURE__ */`, so that they are removed
+    /// when their results are unused
+    fn mark_pure_funcs(&self, expr: &mut CallExpression<'a>) {
+        if self.options.pure_funcs.is_empty() {
+            return;
+        }
+        if let Some(path) = util::callee_path(&expr.callee) {
+            if self.options.pure_funcs.iter().any(|name| *name == path) {
+                expr.pure = true;
+            }
+        }
+    }
+
     /// Join consecutive var statements
     fn join_vars(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
         // Collect all the consecutive ranges that contain joinable vars.
@@ -324,6 +356,9 @@ impl<'a> VisitMut<'a> for Compressor<'a> {
         for stmt in stmts.iter_mut() {
             self.visit_statement(stmt);
         }
+
+        self.fold_if_return(stmts);
+        self.fuse_statements(stmts);
     }
 
     fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
@@ -357,6 +392,14 @@ impl<'a> VisitMut<'a> for Compressor<'a> {
         }
     }
 
+    fn visit_call_expression(&mut self, expr: &mut CallExpression<'a>) {
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        self.visit_expression(&mut expr.callee);
+        self.mark_pure_funcs(expr);
+    }
+
     fn visit_binary_expression(&mut self, expr: &mut BinaryExpression<'a>) {
         self.visit_expression(&mut expr.left);
         self.visit_expression(&mut expr.right);
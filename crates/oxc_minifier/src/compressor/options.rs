@@ -1,10 +1,15 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct CompressOptions {
     /// Various optimizations for boolean context, for example `!!a ? b : c` → `a ? b : c`.
     ///
     /// Default `true`
     pub booleans: bool,
 
+    /// Remove unreachable code, constant branches and expression statements without side effects.
+    ///
+    /// Default `true`
+    pub dead_code: bool,
+
     /// Remove `debugger;` statements.
     ///
     /// Default `true`
@@ -20,32 +25,88 @@ pub struct CompressOptions {
     /// Default `true`
     pub evaluate: bool,
 
+    /// Inline the functions which are called once, e.g. `(function(a) { return a + b })(1)` →
+    /// `1 + b`.
+    ///
+    /// Default `true`
+    pub inline: bool,
+
+    /// The maximum source length of the function declarations which are moved into their single
+    /// call by `inline`.
+    ///
+    /// Default `256`
+    pub inline_max_size: usize,
+
     /// Join consecutive var statements.
     ///
     /// Default `true`
     pub join_vars: bool,
 
+    /// Optimizations for `if` statements which return, e.g. `if (x) return a; return b` → `return x ? a : b`.
+    ///
+    /// Default `true`
+    pub if_return: bool,
+
     /// Optimizations for do, while and for loops when we can statically determine the condition
     ///
     /// Default `true`
     pub loops: bool,
 
+    /// The number of times the compressor runs, later passes may compress the output of the
+    /// previous passes further.
+    ///
+    /// Default `1`
+    pub passes: usize,
+
+    /// The functions whose calls have no side effects, e.g. `["Math.floor", "debug"]`.
+    /// Their calls are removed when their results are unused, see `dead_code`.
+    ///
+    /// Default `[]`
+    pub pure_funcs: Vec<String>,
+
+    /// Join consecutive simple statements with the comma operator, e.g. `a(); b(); return c` →
+    /// `return a(), b(), c`.
+    ///
+    /// Default `true`
+    pub sequences: bool,
+
     /// Transforms `typeof foo == "undefined" into `foo === void 0`
     ///
     /// Default `true`
     pub typeofs: bool,
+
+    /// Optimize numerical expressions which may give imprecise floating point results,
+    /// e.g. `x * 2 * 3` → `x * 6`.
+    ///
+    /// Default `false`
+    pub unsafe_math: bool,
+
+    /// Drop the unreferenced functions and variables of function bodies.
+    /// Requires `dead_code`.
+    ///
+    /// Default `true`
+    pub unused: bool,
 }
 
 impl Default for CompressOptions {
     fn default() -> Self {
         Self {
             booleans: true,
+            dead_code: true,
             drop_debugger: true,
             drop_console: false,
             evaluate: true,
+            if_return: true,
+            inline: true,
+            inline_max_size: 256,
             join_vars: true,
             loops: true,
+            passes: 1,
+            pure_funcs: vec![],
+            sequences: true,
             typeofs: true,
+            unsafe_math: false,
+            unused: true,
         }
     }
 }
@@ -54,24 +115,42 @@ impl CompressOptions {
     pub fn all_true() -> Self {
         Self {
             booleans: true,
+            dead_code: true,
             drop_debugger: true,
             drop_console: true,
             evaluate: true,
+            if_return: true,
+            inline: true,
+            inline_max_size: 256,
             join_vars: true,
             loops: true,
+            passes: 1,
+            pure_funcs: vec![],
+            sequences: true,
             typeofs: true,
+            unsafe_math: true,
+            unused: true,
         }
     }
 
     pub fn all_false() -> Self {
         Self {
             booleans: false,
+            dead_code: false,
             drop_debugger: false,
             drop_console: false,
             evaluate: false,
+            if_return: false,
+            inline: false,
+            inline_max_size: 256,
             join_vars: false,
             loops: false,
+            passes: 1,
+            pure_funcs: vec![],
+            sequences: false,
             typeofs: false,
+            unsafe_math: false,
+            unused: false,
         }
     }
 }
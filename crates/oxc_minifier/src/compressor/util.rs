@@ -1,4 +1,4 @@
-use oxc_ast::ast::Expression;
+use oxc_ast::ast::{Expression, MemberExpression};
 
 pub(super) fn is_console(expr: &Expression<'_>) -> bool {
     // let Statement::ExpressionStatement(expr) = stmt else { return false };
@@ -8,3 +8,18 @@ pub(super) fn is_console(expr: &Expression<'_>) -> bool {
     let Some(ident) = obj.get_identifier_reference() else { return false };
     ident.name == "console"
 }
+
+/// The dotted name of a callee, e.g. `foo` or `console.log`
+pub(super) fn callee_path(expr: &Expression<'_>) -> Option<String> {
+    match expr {
+        Expression::Identifier(ident) => Some(ident.name.to_string()),
+        Expression::MemberExpression(member_expr) => {
+            let MemberExpression::StaticMemberExpression(member_expr) = &**member_expr else {
+                return None;
+            };
+            let object = callee_path(&member_expr.object)?;
+            Some(format!("{object}.{}", member_expr.property.name))
+        }
+        _ => None,
+    }
+}
@@ -2,24 +2,30 @@
 
 mod compressor;
 mod mangler;
+mod tree_shaker;
 
 use oxc_allocator::Allocator;
 use oxc_ast::ast::Program;
 
 pub use crate::{
     compressor::{CompressOptions, Compressor},
-    mangler::ManglerBuilder,
+    mangler::{
+        ManglePropertiesOptions, Mangler, ManglerBuilder, ManglerOptions, PropertyMangler,
+        PropertyNameCache,
+    },
+    tree_shaker::{module_has_side_effects, TreeShakeOptions, TreeShaker},
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct MinifierOptions {
-    pub mangle: bool,
+    /// Rename the local variables to short names, `None` keeps all the names
+    pub mangle: Option<ManglerOptions>,
     pub compress: CompressOptions,
 }
 
 impl Default for MinifierOptions {
     fn default() -> Self {
-        Self { mangle: true, compress: CompressOptions::default() }
+        Self { mangle: Some(ManglerOptions::default()), compress: CompressOptions::default() }
     }
 }
 
@@ -34,9 +40,9 @@ impl Minifier {
 
     pub fn build<'a>(self, allocator: &'a Allocator, program: &mut Program<'a>) {
         Compressor::new(allocator, self.options.compress).build(program);
-        // if self.options.mangle {
-        // let mangler = ManglerBuilder.build(program);
-        // printer.with_mangler(mangler);
-        // }
+        if let Some(options) = self.options.mangle {
+            let mangler = ManglerBuilder::new(options).build(program);
+            mangler.mangle(allocator, program);
+        }
     }
 }
@@ -1,10 +1,25 @@
+mod options;
+mod properties;
+
+use std::{cmp::Reverse, collections::HashSet};
+
 use itertools::Itertools;
+use oxc_allocator::Allocator;
 #[allow(clippy::wildcard_imports)]
-use oxc_ast::ast::*;
+use oxc_ast::{
+    ast::*, syntax_directed_operations::BoundNames, AstBuilder, AstKind, Visit, VisitMut,
+};
 use oxc_index::{index_vec, IndexVec};
-use oxc_semantic::{ReferenceId, SemanticBuilder, SymbolId, SymbolTable};
+use oxc_semantic::{
+    ReferenceId, ScopeId, ScopeTree, Semantic, SemanticBuilder, SymbolFlags, SymbolId, SymbolTable,
+};
 use oxc_span::Atom;
 
+pub use self::{
+    options::ManglerOptions,
+    properties::{ManglePropertiesOptions, PropertyMangler, PropertyNameCache},
+};
+
 type Slot = usize;
 
 #[derive(Debug)]
@@ -21,6 +36,12 @@ impl Mangler {
         let symbol_id = self.symbol_table.get_reference(reference_id).symbol_id()?;
         Some(self.symbol_table.get_name(symbol_id))
     }
+
+    /// Rename the bindings and references of `program` to their mangled names.
+    pub fn mangle<'a>(&self, allocator: &'a Allocator, program: &mut Program<'a>) {
+        let mut renamer = Renamer { ast: AstBuilder::new(allocator), mangler: self };
+        renamer.visit_program(program);
+    }
 }
 
 /// # Name Mangler / Symbol Minification
@@ -64,14 +85,74 @@ impl Mangler {
 ///     }
 /// }
 /// ```
-pub struct ManglerBuilder;
+///
+/// Symbols of the root scope are not renamed unless [ManglerOptions::top_level] is set, they can
+/// be referenced by other scripts. The exported names of modules are always kept.
+/// The scopes enclosing a direct `eval` call or a `with` statement keep their names too, because
+/// they can be referenced by any name at runtime.
+///
+/// The characters of the new names are ordered by how often they appear in the rest of the
+/// output, e.g. property names and strings, which makes the gzipped output smaller.
+#[derive(Debug, Default, Clone)]
+pub struct ManglerBuilder {
+    options: ManglerOptions,
+}
 
 impl ManglerBuilder {
+    pub fn new(options: ManglerOptions) -> Self {
+        Self { options }
+    }
+
     #[must_use]
-    pub fn build<'a>(self, program: &'a Program<'a>) -> Mangler {
+    pub fn build(self, program: &Program<'_>) -> Mangler {
         let semantic_ret = SemanticBuilder::new("", program.source_type).build(program);
         let semantic = semantic_ret.semantic;
 
+        let nodes = semantic.nodes();
+
+        // The symbols which keep their names
+        let mut kept: IndexVec<SymbolId, bool> = index_vec![false; semantic.symbols().len()];
+        // Direct `eval` calls and `with` statements can reference the bindings of all enclosing scopes
+        let mut bailouts: IndexVec<ScopeId, bool> = index_vec![false; semantic.scopes().len()];
+        for node in nodes.iter() {
+            let is_dynamic_scope = match node.kind() {
+                AstKind::Function(func) if self.options.keep_fnames => {
+                    if let Some(symbol_id) = func.id.as_ref().and_then(|id| id.symbol_id.get()) {
+                        kept[symbol_id] = true;
+                    }
+                    false
+                }
+                AstKind::Class(class) if self.options.keep_classnames => {
+                    if let Some(symbol_id) = class.id.as_ref().and_then(|id| id.symbol_id.get()) {
+                        kept[symbol_id] = true;
+                    }
+                    false
+                }
+                AstKind::WithStatement(_) => true,
+                AstKind::CallExpression(call) => is_direct_eval(call, semantic.symbols()),
+                _ => false,
+            };
+            if is_dynamic_scope {
+                for scope_id in semantic.scopes().ancestors(node.scope_id()) {
+                    bailouts[scope_id] = true;
+                }
+            }
+        }
+
+        // JSX element names are printed with their original names
+        for reference in &semantic.symbols().references {
+            if let (AstKind::JSXElementName(_), Some(symbol_id)) =
+                (nodes.kind(reference.node_id()), reference.symbol_id())
+            {
+                kept[symbol_id] = true;
+            }
+        }
+
+        if self.options.top_level {
+            Self::keep_exports(&semantic, &mut kept);
+        }
+        let kept_names = self.keep_names(program, &semantic, &mut kept);
+
         // Mangle the symbol table by computing slots from the scope tree.
         // A slot is the occurrence index of a binding identifier inside a scope.
         let (mut symbol_table, scope_tree) = semantic.into_symbol_table_and_scope_tree();
@@ -82,6 +163,9 @@ impl ManglerBuilder {
         // All symbols with their assigned slots
         let mut slots: IndexVec<SymbolId, Slot> = index_vec![0; symbol_table.len()];
 
+        // The symbols which are renamed
+        let mut mangled: IndexVec<SymbolId, bool> = index_vec![false; symbol_table.len()];
+
         // Keep track of the maximum slot number for each scope
         let mut max_slot_for_scope = vec![0; scope_tree.len()];
 
@@ -95,10 +179,22 @@ impl ManglerBuilder {
 
             let mut slot = parent_max_slot;
 
-            // `bindings` are stored in order, traverse and increment slot
-            for symbol_id in bindings.values() {
-                slots[*symbol_id] = slot;
-                slot += 1;
+            let is_root = scope_id == scope_tree.root_scope_id();
+            if (!is_root || self.options.top_level) && !bailouts[scope_id] {
+                // `bindings` are stored in order, traverse and increment slot
+                for symbol_id in bindings.values() {
+                    if kept[*symbol_id]
+                        || !is_mangleable(symbol_table.get_flag(*symbol_id))
+                        // The `var`s of blocks are bindings of the enclosing blocks too, their
+                        // slots are of the scope they are hoisted to
+                        || hoisted_scope_id(&symbol_table, &scope_tree, *symbol_id) != scope_id
+                    {
+                        continue;
+                    }
+                    mangled[*symbol_id] = true;
+                    slots[*symbol_id] = slot;
+                    slot += 1;
+                }
             }
 
             max_slot_for_scope[scope_id.index()] = slot;
@@ -109,24 +205,33 @@ impl ManglerBuilder {
         }
 
         let frequencies =
-            Self::tally_slot_frequencies(&symbol_table, total_number_of_slots, &slots);
+            Self::tally_slot_frequencies(&symbol_table, total_number_of_slots, &slots, &mangled);
+
+        // The new names must not shadow the unresolved references and the names which are kept
+        let mut reserved_names =
+            scope_tree.root_unresolved_references().keys().cloned().collect::<HashSet<_>>();
+        reserved_names.extend(
+            symbol_table
+                .iter()
+                .filter(|symbol_id| !mangled[*symbol_id])
+                .map(|symbol_id| symbol_table.get_name(symbol_id).clone()),
+        );
+        reserved_names.extend(kept_names);
 
-        let unresolved_references = scope_tree
-            .root_unresolved_references()
-            .keys()
-            // It is unlike to get a 5 letter mangled identifier, which is a lot of slots.
-            // .filter(|name| name.len() < 5)
-            .collect::<Vec<_>>();
+        let mut char_frequency =
+            CharFrequency { symbol_table: &symbol_table, mangled: &mangled, counts: [0; 128] };
+        char_frequency.visit_program(program);
+        let base54 = Base54::new(&char_frequency.counts);
 
         let mut names = Vec::with_capacity(total_number_of_slots);
 
         let mut count = 0;
         for _ in 0..total_number_of_slots {
             names.push(loop {
-                let name = Atom::base54(count);
+                let name = base54.name(count);
                 count += 1;
-                // Do not mangle keywords and unresolved references
-                if !is_keyword(&name) && !unresolved_references.iter().any(|n| **n == name) {
+                // Do not mangle keywords, unresolved references and the names which are kept
+                if !is_keyword(&name) && !reserved_names.contains(&name) {
                     break name;
                 }
             });
@@ -179,14 +284,67 @@ impl ManglerBuilder {
         Mangler { symbol_table }
     }
 
+    /// Keeps the symbols named by [ManglerOptions::reserved] or by the words of the strings for
+    /// [ManglerOptions::keep_names_in_strings], and returns these names
+    fn keep_names(
+        &self,
+        program: &Program<'_>,
+        semantic: &Semantic<'_>,
+        kept: &mut IndexVec<SymbolId, bool>,
+    ) -> HashSet<Atom> {
+        let mut names = self
+            .options
+            .reserved
+            .iter()
+            .map(|name| Atom::from(name.as_str()))
+            .collect::<HashSet<_>>();
+        if self.options.keep_names_in_strings {
+            let mut words = StringWords::default();
+            words.visit_program(program);
+            names.extend(words.words);
+        }
+        for symbol_id in semantic.symbols().iter() {
+            if names.contains(semantic.symbols().get_name(symbol_id)) {
+                kept[symbol_id] = true;
+            }
+        }
+        names
+    }
+
+    /// The exported bindings of the top level scope are referenced by their names
+    fn keep_exports(semantic: &Semantic<'_>, kept: &mut IndexVec<SymbolId, bool>) {
+        let root_scope_id = semantic.scopes().root_scope_id();
+        for node in semantic.nodes().iter() {
+            let AstKind::ModuleDeclaration(decl) = node.kind() else { continue };
+            let ModuleDeclaration::ExportNamedDeclaration(export) = decl else { continue };
+            if export.source.is_some() {
+                continue;
+            }
+            if let Some(declaration) = &export.declaration {
+                declaration.bound_names(&mut |ident| {
+                    if let Some(symbol_id) = ident.symbol_id.get() {
+                        kept[symbol_id] = true;
+                    }
+                });
+            }
+            for specifier in &export.specifiers {
+                let name = specifier.local.name();
+                if let Some(symbol_id) = semantic.scopes().get_binding(root_scope_id, name) {
+                    kept[symbol_id] = true;
+                }
+            }
+        }
+    }
+
     fn tally_slot_frequencies(
         symbol_table: &SymbolTable,
         total_number_of_slots: usize,
         slots: &IndexVec<SymbolId, Slot>,
+        mangled: &IndexVec<SymbolId, bool>,
     ) -> Vec<SlotFrequency> {
         let mut frequencies = vec![SlotFrequency::default(); total_number_of_slots];
         for (symbol_id, slot) in slots.iter_enumerated() {
-            if !symbol_table.get_flag(symbol_id).is_variable() {
+            if !mangled[symbol_id] {
                 continue;
             }
             let index = *slot;
@@ -195,7 +353,7 @@ impl ManglerBuilder {
                 symbol_table.get_resolved_reference_ids(symbol_id).len();
             frequencies[index].symbol_ids.push(symbol_id);
         }
-        frequencies.sort_by_key(|x| (std::cmp::Reverse(x.frequency)));
+        frequencies.sort_by_key(|x| (Reverse(x.frequency)));
         frequencies
     }
 }
@@ -214,3 +372,225 @@ fn is_keyword(s: &str) -> bool {
             | "enum" | "from" | "meta" | "null" | "this" | "true" | "type"
             | "void" | "with")
 }
+
+/// Variables, functions, classes and imports, the names of function expressions have no flags
+fn is_mangleable(flag: SymbolFlags) -> bool {
+    flag.is_empty()
+        || flag.intersects(
+            SymbolFlags::Variable
+                | SymbolFlags::CatchVariable
+                | SymbolFlags::Function
+                | SymbolFlags::Class
+                | SymbolFlags::ImportBinding,
+        )
+}
+
+/// The scope which declares `symbol_id`, the nearest function or the root scope for `var`s
+fn hoisted_scope_id(
+    symbol_table: &SymbolTable,
+    scope_tree: &ScopeTree,
+    symbol_id: SymbolId,
+) -> ScopeId {
+    let scope_id = symbol_table.get_scope_id(symbol_id);
+    let flag = symbol_table.get_flag(symbol_id);
+    if !flag.contains(SymbolFlags::FunctionScopedVariable)
+        || flag.contains(SymbolFlags::CatchVariable)
+    {
+        return scope_id;
+    }
+    scope_tree
+        .ancestors(scope_id)
+        .find(|scope_id| scope_tree.get_flags(*scope_id).is_var())
+        .unwrap_or_else(|| scope_tree.root_scope_id())
+}
+
+/// `eval(...)`, where `eval` is not declared
+fn is_direct_eval(call: &CallExpression, symbol_table: &SymbolTable) -> bool {
+    let Expression::Identifier(ident) = &call.callee else { return false };
+    ident.name.as_str() == "eval"
+        && ident
+            .reference_id
+            .get()
+            .map_or(true, |reference_id| symbol_table.is_global_reference(reference_id))
+}
+
+const BASE54_CHARS: &[u8; 64] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+
+/// The characters of mangled names, ordered from the most to the least frequent character
+struct Base54 {
+    /// The characters which can start an identifier
+    leading: Vec<u8>,
+    chars: [u8; 64],
+}
+
+impl Base54 {
+    fn new(counts: &[usize; 128]) -> Self {
+        let mut chars = *BASE54_CHARS;
+        // Characters with the same frequency keep the order of `BASE54_CHARS`
+        chars.sort_by_key(|c| Reverse(counts[*c as usize]));
+        let leading = chars.iter().copied().filter(|c| !c.is_ascii_digit()).collect();
+        Self { leading, chars }
+    }
+
+    /// See [Atom::base54]
+    fn name(&self, n: usize) -> Atom {
+        let mut num = n;
+        let mut ret = String::new();
+        ret.push(self.leading[num % self.leading.len()] as char);
+        num /= self.leading.len();
+        while num > 0 {
+            num -= 1;
+            ret.push(self.chars[num % self.chars.len()] as char);
+            num /= self.chars.len();
+        }
+        Atom::from(ret)
+    }
+}
+
+/// Counts the characters of the output which are not mangled names
+struct CharFrequency<'b> {
+    symbol_table: &'b SymbolTable,
+    mangled: &'b IndexVec<SymbolId, bool>,
+    counts: [usize; 128],
+}
+
+impl<'b> CharFrequency<'b> {
+    fn count(&mut self, s: &str) {
+        for c in s.bytes().filter(u8::is_ascii) {
+            self.counts[c as usize] += 1;
+        }
+    }
+}
+
+impl<'a, 'b> Visit<'a> for CharFrequency<'b> {
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier) {
+        if !ident.symbol_id.get().is_some_and(|symbol_id| self.mangled[symbol_id]) {
+            self.count(&ident.name);
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference) {
+        let symbol_id = ident
+            .reference_id
+            .get()
+            .and_then(|reference_id| self.symbol_table.get_reference(reference_id).symbol_id());
+        if !symbol_id.is_some_and(|symbol_id| self.mangled[symbol_id]) {
+            self.count(&ident.name);
+        }
+    }
+
+    fn visit_identifier_name(&mut self, ident: &IdentifierName) {
+        self.count(&ident.name);
+    }
+
+    fn visit_private_identifier(&mut self, ident: &PrivateIdentifier) {
+        self.count(&ident.name);
+    }
+
+    fn visit_jsx_identifier(&mut self, ident: &JSXIdentifier) {
+        self.count(&ident.name);
+    }
+
+    fn visit_string_literal(&mut self, lit: &StringLiteral) {
+        self.count(&lit.value);
+    }
+}
+
+/// The identifier-like words of the string literals and templates, e.g. `foo` and `bar` of
+/// `"foo(bar)"`
+#[derive(Default)]
+struct StringWords {
+    words: HashSet<Atom>,
+}
+
+impl StringWords {
+    fn add(&mut self, s: &str) {
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '$' || c == '_';
+        self.words
+            .extend(s.split(|c| !is_word_char(c)).filter(|word| !word.is_empty()).map(Atom::from));
+    }
+}
+
+impl<'a> Visit<'a> for StringWords {
+    fn visit_string_literal(&mut self, lit: &StringLiteral) {
+        self.add(&lit.value);
+    }
+
+    fn visit_template_literal(&mut self, lit: &TemplateLiteral<'a>) {
+        for quasi in &lit.quasis {
+            self.add(&quasi.value.raw);
+        }
+        for expr in &lit.expressions {
+            self.visit_expression(expr);
+        }
+    }
+}
+
+/// Replaces the names of the bindings and references with the names of their symbols
+struct Renamer<'a, 'b> {
+    ast: AstBuilder<'a>,
+    mangler: &'b Mangler,
+}
+
+impl<'a, 'b> VisitMut<'a> for Renamer<'a, 'b> {
+    fn visit_binding_identifier(&mut self, ident: &mut BindingIdentifier) {
+        if let Some(symbol_id) = ident.symbol_id.get() {
+            ident.name = self.mangler.get_symbol_name(symbol_id).clone();
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &mut IdentifierReference) {
+        let name = ident
+            .reference_id
+            .get()
+            .and_then(|reference_id| self.mangler.get_reference_name(reference_id));
+        if let Some(name) = name {
+            ident.name = name.clone();
+        }
+    }
+
+    /// `({ x } = y)` -> `({ x: a } = y)`
+    fn visit_assignment_target_property(&mut self, property: &mut AssignmentTargetProperty<'a>) {
+        if let AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) = property {
+            let mut binding = ident.binding.clone();
+            self.visit_identifier_reference(&mut binding);
+            if binding.name != ident.binding.name {
+                let key = ident.binding.name.clone();
+                *property = expand_shorthand(&self.ast, ident, key, binding);
+            }
+        }
+        match property {
+            AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) => {
+                self.visit_assignment_target_property_identifier(ident);
+            }
+            AssignmentTargetProperty::AssignmentTargetPropertyProperty(prop) => {
+                self.visit_assignment_target_property_property(prop);
+            }
+        }
+    }
+}
+
+/// `({ x = 1 } = y)` -> `({ key: binding = 1 } = y)`
+fn expand_shorthand<'a>(
+    ast: &AstBuilder<'a>,
+    ident: &mut AssignmentTargetPropertyIdentifier<'a>,
+    key: Atom,
+    binding: IdentifierReference,
+) -> AssignmentTargetProperty<'a> {
+    let key = IdentifierName { span: ident.binding.span, name: key };
+    let target =
+        AssignmentTarget::SimpleAssignmentTarget(ast.simple_assignment_target_identifier(binding));
+    let target = match ident.init.take() {
+        Some(init) => AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(
+            ast.alloc(AssignmentTargetWithDefault { span: ident.span, binding: target, init }),
+        ),
+        None => AssignmentTargetMaybeDefault::AssignmentTarget(target),
+    };
+    AssignmentTargetProperty::AssignmentTargetPropertyProperty(ast.alloc(
+        AssignmentTargetPropertyProperty {
+            span: ident.span,
+            name: ast.property_key_identifier(key),
+            binding: target,
+        },
+    ))
+}
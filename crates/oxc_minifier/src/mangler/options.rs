@@ -0,0 +1,15 @@
+#[derive(Debug, Default, Clone)]
+pub struct ManglerOptions {
+    /// Keep the names of function declarations and expressions
+    pub keep_fnames: bool,
+    /// Keep the names of class declarations and expressions
+    pub keep_classnames: bool,
+    /// Names which are neither mangled nor used as mangled names, e.g. `["$", "require"]`
+    pub reserved: Vec<String>,
+    /// Mangle the names of the top level scope, which are visible to the other scripts of a page.
+    /// The exported names of modules are always kept.
+    pub top_level: bool,
+    /// Keep the names which appear in string literals, for code evaluated from strings, e.g.
+    /// `new Function("return foo")` and `setTimeout("foo()")`, or read by name, e.g. `this["foo"]`
+    pub keep_names_in_strings: bool,
+}
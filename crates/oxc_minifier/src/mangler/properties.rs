@@ -0,0 +1,278 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::Atom;
+use oxc_syntax::operator::BinaryOperator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::expand_shorthand;
+
+#[derive(Debug, Clone)]
+pub struct ManglePropertiesOptions {
+    /// Only the property names matching this pattern are mangled, e.g. `^_`
+    pub regex: Regex,
+    /// Do not mangle quoted property names, e.g. `o["_foo"]` and `{ "_foo": 1 }`.
+    /// The unquoted occurrences of these names are not mangled either.
+    pub keep_quoted: bool,
+    /// Property names which are never mangled
+    pub reserved: Vec<String>,
+}
+
+impl ManglePropertiesOptions {
+    pub fn new(regex: Regex) -> Self {
+        Self { regex, keep_quoted: false, reserved: vec![] }
+    }
+}
+
+/// The mangled names of the properties, which can be saved and loaded between builds so that
+/// separately minified files use the same names.
+///
+/// ```json
+/// { "props": { "_foo": "a", "_bar": "b" } }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PropertyNameCache {
+    pub props: BTreeMap<String, String>,
+}
+
+impl PropertyNameCache {
+    /// # Errors
+    ///
+    /// * The text is not a valid JSON name cache
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+    }
+
+    /// # Panics
+    ///
+    /// * The cache can always be serialized
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// # Property Mangler
+///
+/// Renames the property names matching [ManglePropertiesOptions::regex] in property accesses,
+/// object literals, classes and destructuring patterns.
+///
+/// ```javascript
+/// class Foo { _count = 0; _increment() { this._count++; } }
+/// ```
+///
+/// ```javascript
+/// class Foo { a = 0; b() { this.a++; } }
+/// ```
+///
+/// The property names which are not mangled are reserved, the new names never collide with them.
+///
+/// See:
+///   * [terser](https://terser.org/docs/cli-usage/#cli-mangling-property-names---mangle-props)
+pub struct PropertyMangler<'a> {
+    ast: AstBuilder<'a>,
+    options: ManglePropertiesOptions,
+    name_cache: PropertyNameCache,
+
+    phase: Phase,
+    /// The names to mangle, with their number of occurrences and first occurrence
+    candidates: HashMap<Atom, (usize, usize)>,
+    /// The names which are not mangled
+    reserved: HashSet<Atom>,
+    renames: HashMap<Atom, Atom>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Collect,
+    Rename,
+}
+
+impl<'a> PropertyMangler<'a> {
+    pub fn new(allocator: &'a Allocator, options: ManglePropertiesOptions) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            options,
+            name_cache: PropertyNameCache::default(),
+            phase: Phase::Collect,
+            candidates: HashMap::new(),
+            reserved: HashSet::new(),
+            renames: HashMap::new(),
+        }
+    }
+
+    /// Reuse the mangled names of a previous build
+    #[must_use]
+    pub fn with_name_cache(mut self, name_cache: PropertyNameCache) -> Self {
+        self.name_cache = name_cache;
+        self
+    }
+
+    /// The mangled names of the previous builds and this build
+    pub fn name_cache(&self) -> &PropertyNameCache {
+        &self.name_cache
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        self.phase = Phase::Collect;
+        self.candidates.clear();
+        self.reserved =
+            self.options.reserved.iter().map(|name| Atom::from(name.as_str())).collect();
+        self.visit_program(program);
+        self.assign_names();
+        self.phase = Phase::Rename;
+        self.visit_program(program);
+    }
+
+    /// The most frequent names get the shortest names, the cached names are reused.
+    fn assign_names(&mut self) {
+        let mut candidates = self
+            .candidates
+            .iter()
+            .filter(|(name, _)| !self.reserved.contains(*name))
+            .map(|(name, (count, first))| (name.clone(), *count, *first))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(_, count, first)| (Reverse(*count), *first));
+
+        let mut used = self
+            .name_cache
+            .props
+            .values()
+            .map(|name| Atom::from(name.as_str()))
+            .collect::<HashSet<_>>();
+        let mut count = 0;
+        self.renames.clear();
+        for (name, _, _) in candidates {
+            let mangled = if let Some(mangled) = self.name_cache.props.get(name.as_str()) {
+                Atom::from(mangled.as_str())
+            } else {
+                let mangled = loop {
+                    let mangled = Atom::base54(count);
+                    count += 1;
+                    if !self.reserved.contains(&mangled) && !used.contains(&mangled) {
+                        break mangled;
+                    }
+                };
+                used.insert(mangled.clone());
+                self.name_cache.props.insert(name.to_string(), mangled.to_string());
+                mangled
+            };
+            self.renames.insert(name, mangled);
+        }
+    }
+
+    fn property_name(&mut self, name: &mut Atom, quoted: bool) {
+        match self.phase {
+            Phase::Collect => {
+                if !self.options.regex.is_match(name.as_str())
+                    || (quoted && self.options.keep_quoted)
+                {
+                    self.reserved.insert(name.clone());
+                    return;
+                }
+                let first = self.candidates.len();
+                self.candidates.entry(name.clone()).or_insert((0, first)).0 += 1;
+            }
+            Phase::Rename => {
+                if let Some(mangled) = self.renames.get(name) {
+                    *name = mangled.clone();
+                }
+            }
+        }
+    }
+
+    fn is_renamed(&self, key: &PropertyKey<'a>) -> bool {
+        self.phase == Phase::Rename
+            && matches!(key, PropertyKey::Identifier(ident) if self.renames.contains_key(&ident.name))
+    }
+}
+
+impl<'a> VisitMut<'a> for PropertyMangler<'a> {
+    fn visit_property_key(&mut self, key: &mut PropertyKey<'a>) {
+        match key {
+            PropertyKey::Identifier(ident) => self.property_name(&mut ident.name, false),
+            PropertyKey::Expression(Expression::StringLiteral(lit)) => {
+                self.property_name(&mut lit.value, true);
+            }
+            PropertyKey::Expression(expr) => self.visit_expression(expr),
+            PropertyKey::PrivateIdentifier(_) => {}
+        }
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &mut StaticMemberExpression<'a>) {
+        self.visit_expression(&mut expr.object);
+        self.property_name(&mut expr.property.name, false);
+    }
+
+    fn visit_computed_member_expression(&mut self, expr: &mut ComputedMemberExpression<'a>) {
+        self.visit_expression(&mut expr.object);
+        match &mut expr.expression {
+            Expression::StringLiteral(lit) => self.property_name(&mut lit.value, true),
+            expr => self.visit_expression(expr),
+        }
+    }
+
+    /// `"_foo" in o`
+    fn visit_binary_expression(&mut self, expr: &mut BinaryExpression<'a>) {
+        match &mut expr.left {
+            Expression::StringLiteral(lit) if expr.operator == BinaryOperator::In => {
+                self.property_name(&mut lit.value, true);
+            }
+            left => self.visit_expression(left),
+        }
+        self.visit_expression(&mut expr.right);
+    }
+
+    /// `{ _foo }` -> `{ a: _foo }`
+    fn visit_object_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        if prop.shorthand && self.is_renamed(&prop.key) {
+            prop.shorthand = false;
+        }
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        if let Some(init) = &mut prop.init {
+            self.visit_expression(init);
+        }
+    }
+
+    /// `const { _foo } = o` -> `const { a: _foo } = o`
+    fn visit_binding_property(&mut self, prop: &mut BindingProperty<'a>) {
+        if prop.shorthand && self.is_renamed(&prop.key) {
+            prop.shorthand = false;
+        }
+        self.visit_property_key(&mut prop.key);
+        self.visit_binding_pattern(&mut prop.value);
+    }
+
+    /// `({ _foo } = o)` -> `({ a: _foo } = o)`
+    fn visit_assignment_target_property(&mut self, property: &mut AssignmentTargetProperty<'a>) {
+        if let AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) = property {
+            let mut key = ident.binding.name.clone();
+            self.property_name(&mut key, false);
+            if key != ident.binding.name {
+                let binding = ident.binding.clone();
+                *property = expand_shorthand(&self.ast, ident, key, binding);
+                // The new key is already mangled
+                if let AssignmentTargetProperty::AssignmentTargetPropertyProperty(prop) = property {
+                    self.visit_assignment_target_maybe_default(&mut prop.binding);
+                }
+                return;
+            }
+        }
+        match property {
+            AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) => {
+                if let Some(init) = &mut ident.init {
+                    self.visit_expression(init);
+                }
+            }
+            AssignmentTargetProperty::AssignmentTargetPropertyProperty(prop) => {
+                self.visit_assignment_target_property_property(prop);
+            }
+        }
+    }
+}
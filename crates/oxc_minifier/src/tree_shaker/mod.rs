@@ -0,0 +1,345 @@
+//! Tree Shaking
+//!
+//! <https://esbuild.github.io/api/#tree-shaking>
+//! <https://webpack.js.org/guides/tree-shaking/>
+
+mod options;
+mod package_json;
+
+use std::collections::HashSet;
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder};
+use oxc_semantic::{Semantic, SemanticBuilder, SymbolId};
+use oxc_span::{Atom, Span, SPAN};
+
+pub use self::{options::TreeShakeOptions, package_json::module_has_side_effects};
+
+/// Removes the exports of a module which are never imported by the other modules, together with
+/// the top level declarations and imports which are no longer referenced:
+///
+/// ```javascript
+/// // `unused_exports: ["b"]`
+/// import { x } from "./x";
+/// const helper = () => x;
+/// export const a = 1;
+/// export const b = helper;
+/// ```
+///
+/// ```javascript
+/// import "./x";
+/// export const a = 1;
+/// ```
+///
+/// Imports of modules without side effects are removed once none of their bindings are
+/// referenced, see [TreeShakeOptions::side_effect_free_imports].
+///
+/// The declarations are removed only when their initializers have no side effects, until no
+/// more declarations can be removed. Only ES modules are tree shaken, the top level declarations
+/// of scripts are global.
+pub struct TreeShaker<'a> {
+    ast: AstBuilder<'a>,
+    options: TreeShakeOptions,
+}
+
+/// The top level bindings and expression statements which can be removed
+#[derive(Default)]
+struct Unused {
+    symbols: HashSet<SymbolId>,
+    expression_statements: HashSet<Span>,
+}
+
+impl<'a> TreeShaker<'a> {
+    pub fn new(allocator: &'a Allocator, options: TreeShakeOptions) -> Self {
+        Self { ast: AstBuilder::new(allocator), options }
+    }
+
+    pub fn build(&self, program: &mut Program<'a>) {
+        if !program.source_type.is_module() {
+            return;
+        }
+        self.remove_unused_exports(&mut program.body);
+        loop {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            let unused = Self::find_unused(&semantic, &program.body);
+            drop(semantic);
+            if !self.remove_unused(&mut program.body, &unused) {
+                break;
+            }
+        }
+    }
+
+    fn is_unused_export(&self, name: &str) -> bool {
+        self.options.unused_exports.contains(name)
+    }
+
+    fn is_side_effect_free_import(&self, source: &StringLiteral) -> bool {
+        self.options.side_effect_free_imports.contains(source.value.as_str())
+    }
+
+    /// `export const a = 1` -> `const a = 1`, `export { a, b }` -> `export { a }`,
+    /// `export default foo()` -> `foo()`
+    fn remove_unused_exports(&self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            if matches!(&stmt, Statement::ModuleDeclaration(decl)
+                if matches!(&**decl, ModuleDeclaration::ExportDefaultDeclaration(_)))
+                && self.is_unused_export("default")
+            {
+                let Statement::ModuleDeclaration(decl) = stmt else { unreachable!() };
+                let ModuleDeclaration::ExportDefaultDeclaration(export) = decl.unbox() else {
+                    unreachable!()
+                };
+                stmts.extend(self.unexport_default(export.unbox()));
+                continue;
+            }
+            if let Statement::ModuleDeclaration(decl) = &mut stmt {
+                if let ModuleDeclaration::ExportNamedDeclaration(export) = &mut **decl {
+                    if let Some(declaration) = &export.declaration {
+                        let mut names = vec![];
+                        declaration.bound_names(&mut |ident| names.push(ident.name.clone()));
+                        if !names.is_empty() && names.iter().all(|name| self.is_unused_export(name))
+                        {
+                            stmts.push(Statement::Declaration(export.declaration.take().unwrap()));
+                            continue;
+                        }
+                    } else if !export.specifiers.is_empty() {
+                        export.specifiers.retain(|specifier| {
+                            !self.is_unused_export(specifier.exported.name().as_str())
+                        });
+                        if export.specifiers.is_empty() {
+                            // `export { a } from "mod"` still evaluates the module
+                            if let Some(source) = export.source.take() {
+                                stmts.extend(self.side_effect_import(source));
+                            }
+                            continue;
+                        }
+                    }
+                }
+            }
+            stmts.push(stmt);
+        }
+    }
+
+    fn unexport_default(&self, export: ExportDefaultDeclaration<'a>) -> Option<Statement<'a>> {
+        match export.declaration {
+            ExportDefaultDeclarationKind::Expression(expr) => {
+                Some(self.ast.expression_statement(export.span, expr))
+            }
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                // Anonymous functions can not be referenced
+                func.id
+                    .is_some()
+                    .then(|| Statement::Declaration(Declaration::FunctionDeclaration(func)))
+            }
+            ExportDefaultDeclarationKind::ClassDeclaration(mut class) => {
+                if class.id.is_some() {
+                    return Some(Statement::Declaration(Declaration::ClassDeclaration(class)));
+                }
+                // The side effects of anonymous classes are kept
+                class.r#type = ClassType::ClassExpression;
+                Some(self.ast.expression_statement(export.span, self.ast.class_expression(class)))
+            }
+            ExportDefaultDeclarationKind::TSInterfaceDeclaration(decl) => {
+                Some(Statement::Declaration(Declaration::TSInterfaceDeclaration(decl)))
+            }
+            ExportDefaultDeclarationKind::TSEnumDeclaration(decl) => {
+                Some(Statement::Declaration(Declaration::TSEnumDeclaration(decl)))
+            }
+        }
+    }
+
+    /// `import "mod"`, unless the module has no side effects
+    fn side_effect_import(&self, source: StringLiteral) -> Option<Statement<'a>> {
+        if self.is_side_effect_free_import(&source) {
+            return None;
+        }
+        let import =
+            self.ast.import_declaration(SPAN, None, source, None, ImportOrExportKind::Value);
+        Some(self.ast.module_declaration(ModuleDeclaration::ImportDeclaration(import)))
+    }
+
+    fn find_unused(semantic: &Semantic<'a>, stmts: &[Statement<'a>]) -> Unused {
+        // The specifiers of `export { a }` are not references
+        let mut exported = HashSet::new();
+        for stmt in stmts {
+            if let Statement::ModuleDeclaration(decl) = stmt {
+                if let ModuleDeclaration::ExportNamedDeclaration(export) = &**decl {
+                    if export.source.is_none() {
+                        exported
+                            .extend(export.specifiers.iter().map(|spec| spec.local.name().clone()));
+                    }
+                }
+            }
+        }
+        let finder = UnusedFinder { semantic, exported };
+
+        let mut unused = Unused::default();
+        for stmt in stmts {
+            match stmt {
+                Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                    for declarator in &decl.declarations {
+                        let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind
+                        else {
+                            continue;
+                        };
+                        let is_pure = declarator
+                            .init
+                            .as_ref()
+                            .map_or(true, |init| semantic.is_side_effect_free(init));
+                        if is_pure {
+                            unused.symbols.extend(finder.unreferenced(ident, None));
+                        }
+                    }
+                }
+                Statement::Declaration(Declaration::FunctionDeclaration(func)) => {
+                    if let Some(ident) = &func.id {
+                        unused.symbols.extend(finder.unreferenced(ident, Some(func.span)));
+                    }
+                }
+                Statement::Declaration(Declaration::ClassDeclaration(class)) => {
+                    if let Some(ident) = &class.id {
+                        if semantic.is_side_effect_free_class(class) {
+                            unused.symbols.extend(finder.unreferenced(ident, Some(class.span)));
+                        }
+                    }
+                }
+                Statement::ExpressionStatement(stmt) => {
+                    if semantic.is_side_effect_free(&stmt.expression) {
+                        unused.expression_statements.insert(stmt.span);
+                    }
+                }
+                Statement::ModuleDeclaration(decl) => {
+                    let ModuleDeclaration::ImportDeclaration(import) = &**decl else { continue };
+                    for specifier in import.specifiers.iter().flatten() {
+                        let ident = match specifier {
+                            ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                                &specifier.local
+                            }
+                            ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                                &specifier.local
+                            }
+                            ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                                &specifier.local
+                            }
+                        };
+                        unused.symbols.extend(finder.unreferenced(ident, None));
+                    }
+                }
+                _ => {}
+            }
+        }
+        unused
+    }
+
+    /// Returns whether any statement was removed or changed
+    fn remove_unused(&self, stmts: &mut Vec<'a, Statement<'a>>, unused: &Unused) -> bool {
+        let is_unused = |ident: &BindingIdentifier| {
+            ident.symbol_id.get().is_some_and(|symbol_id| unused.symbols.contains(&symbol_id))
+        };
+        let mut changed = false;
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            match &mut stmt {
+                Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                    let len = decl.declarations.len();
+                    decl.declarations.retain(|declarator| {
+                        !matches!(&declarator.id.kind,
+                            BindingPatternKind::BindingIdentifier(ident) if is_unused(ident))
+                    });
+                    changed |= decl.declarations.len() != len;
+                    if decl.declarations.is_empty() {
+                        continue;
+                    }
+                }
+                Statement::Declaration(Declaration::FunctionDeclaration(func))
+                    if func.id.as_ref().is_some_and(is_unused) =>
+                {
+                    changed = true;
+                    continue;
+                }
+                Statement::Declaration(Declaration::ClassDeclaration(class))
+                    if class.id.as_ref().is_some_and(is_unused) =>
+                {
+                    changed = true;
+                    continue;
+                }
+                Statement::ExpressionStatement(expr_stmt)
+                    if unused.expression_statements.contains(&expr_stmt.span) =>
+                {
+                    changed = true;
+                    continue;
+                }
+                Statement::ModuleDeclaration(decl) => {
+                    if let ModuleDeclaration::ImportDeclaration(import) = &mut **decl {
+                        let is_side_effect_free = self.is_side_effect_free_import(&import.source);
+                        let Some(specifiers) = &mut import.specifiers else {
+                            // `import "mod"`
+                            if is_side_effect_free {
+                                changed = true;
+                                continue;
+                            }
+                            stmts.push(stmt);
+                            continue;
+                        };
+                        let len = specifiers.len();
+                        specifiers.retain(|specifier| {
+                            let ident = match specifier {
+                                ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                                    &specifier.local
+                                }
+                                ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                                    &specifier.local
+                                }
+                                ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                                    &specifier.local
+                                }
+                            };
+                            !is_unused(ident)
+                        });
+                        if specifiers.len() != len {
+                            changed = true;
+                            if specifiers.is_empty() {
+                                // The module is still evaluated for its side effects
+                                import.specifiers = None;
+                                if is_side_effect_free {
+                                    continue;
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+            stmts.push(stmt);
+        }
+        changed
+    }
+}
+
+struct UnusedFinder<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    /// The local names of `export { a }`
+    exported: HashSet<Atom>,
+}
+
+impl<'s, 'a> UnusedFinder<'s, 'a> {
+    /// The symbol of `ident` if it is never referenced, except from inside `span`, e.g. by
+    /// recursive calls of a function
+    fn unreferenced(&self, ident: &BindingIdentifier, span: Option<Span>) -> Option<SymbolId> {
+        let symbol_id = ident.symbol_id.get()?;
+        if self.exported.contains(&ident.name) {
+            return None;
+        }
+        let symbols = self.semantic.symbols();
+        let is_unreferenced =
+            symbols.get_resolved_reference_ids(symbol_id).iter().all(|reference_id| {
+                let reference_span = symbols.get_reference(*reference_id).span();
+                span.is_some_and(|span| {
+                    span.start <= reference_span.start && reference_span.end <= span.end
+                })
+            });
+        is_unreferenced.then_some(symbol_id)
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use oxc_syntax::{module_graph::UnusedExport, module_record::ModuleRecord};
+
+use super::package_json::module_has_side_effects;
+
+#[derive(Debug, Default, Clone)]
+pub struct TreeShakeOptions {
+    /// The exported names of the module which are never imported, `default` for the default export
+    pub unused_exports: HashSet<String>,
+    /// The specifiers of the imported modules which have no side effects when they are evaluated,
+    /// e.g. the modules of packages with `"sideEffects": false` in their `package.json`
+    pub side_effect_free_imports: HashSet<String>,
+}
+
+impl TreeShakeOptions {
+    /// The options of `module`, with the unused exports found by
+    /// [oxc_syntax::module_graph::find_unused_exports] and the `sideEffects` field of the
+    /// `package.json` of each loaded module.
+    pub fn from_module_graph(module: &ModuleRecord, unused_exports: &[UnusedExport]) -> Self {
+        let unused_exports = unused_exports
+            .iter()
+            .filter(|unused| unused.path == module.resolved_absolute_path)
+            .map(|unused| unused.name.to_string())
+            .collect();
+        let side_effect_free_imports = module
+            .loaded_modules
+            .iter()
+            .filter(|entry| !module_has_side_effects(&entry.value().resolved_absolute_path))
+            .map(|entry| entry.key().to_string())
+            .collect();
+        Self { unused_exports, side_effect_free_imports }
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+use serde_json::Value;
+
+/// Whether the module at `path` may have side effects, according to the `sideEffects` field of
+/// the nearest `package.json`.
+///
+/// * `"sideEffects": false` marks all modules of the package as side-effect free.
+/// * `"sideEffects": ["./src/polyfill.js", "*.css"]` lists the modules with side effects,
+///   patterns without a `/` match the file names in any directory.
+///
+/// Modules are assumed to have side effects when the field is missing or invalid.
+///
+/// See <https://webpack.js.org/guides/tree-shaking/#mark-the-file-as-side-effect-free>
+pub fn module_has_side_effects(path: &Path) -> bool {
+    for dir in path.ancestors().skip(1) {
+        let Ok(text) = std::fs::read_to_string(dir.join("package.json")) else { continue };
+        let Ok(package_json) = serde_json::from_str::<Value>(&text) else { return true };
+        return match package_json.get("sideEffects") {
+            Some(Value::Bool(side_effects)) => *side_effects,
+            Some(Value::Array(patterns)) => {
+                let Ok(relative_path) = path.strip_prefix(dir) else { return true };
+                patterns
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|pattern| matches_pattern(pattern, relative_path))
+            }
+            _ => true,
+        };
+    }
+    true
+}
+
+fn matches_pattern(pattern: &str, relative_path: &Path) -> bool {
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+    let pattern = if pattern.contains('/') { pattern.to_string() } else { format!("**/{pattern}") };
+    let options = MatchOptions { require_literal_separator: true, ..MatchOptions::default() };
+    Pattern::new(&pattern).map_or(true, |pattern| pattern.matches_path_with(relative_path, options))
+}
@@ -19,3 +19,21 @@ fn test_fold_not() {
     // This will be further optimized by PeepholeFoldConstants.
     // test("x = !true", "x=!1");
 }
+
+#[test]
+fn test_fold_conditional() {
+    test("x = true ? a : b", "x=a;");
+    test("x = 0 ? a : b", "x=b;");
+    test("x = a ? true : false", "x=!!a;");
+    test("x = a ? false : true", "x=!a;");
+    test("x = !!a ? b : c", "x=a?b:c;");
+    test("x = a ? b : false", "x=a?b:!1;");
+}
+
+#[test]
+fn test_fold_double_not() {
+    test("if (!!x) foo()", "if(x)foo();");
+    test("while (!!x) foo()", "for(;x;)foo();");
+    test("x = !!!y", "x=!y;");
+    test("x = !!y", "x=!!y;");
+}
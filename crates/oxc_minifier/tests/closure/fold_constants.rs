@@ -632,3 +632,44 @@ fn test_fold_bit_shift() {
     test("1 << -1", "1<<-1;");
     test("1 >> 32", "1>>32;");
 }
+
+#[test]
+fn test_fold_arithmetic() {
+    test("x = 10 + 20", "x=30;");
+    test("x = 2 / 4", "x=.5;");
+    test("x = 2.25 * 3", "x=6.75;");
+    test_same("x=1/3;");
+    test_same("x=1/0;");
+    test("x = 7 % 3", "x=1;");
+    test("x = 2 ** 3", "x=8;");
+    test("x = 2 - 3", "x=-1;");
+    test("x = 1 - 'a'", "x=1-'a';");
+    test("x = 3n * 4n", "x=12n;");
+    test_same("x=1n/0n;");
+    test_same("x=y*1;");
+}
+
+#[test]
+fn test_fold_bitwise_op() {
+    test("x = 1 & 1", "x=1;");
+    test("x = 1 & 2", "x=0;");
+    test("x = 3 & 1", "x=1;");
+    test("x = 1 | 2", "x=3;");
+    test("x = 1 ^ 3", "x=2;");
+    test("x = 0xffffffff | 0", "x=-1;");
+    test_same("x=y&1;");
+}
+
+#[test]
+fn test_fold_typeof_constant() {
+    test("x = typeof (1 + 1)", "x='number';");
+    test("x = typeof `a`", "x='string';");
+    test("x = typeof 1n", "x='bigint';");
+    test_same("x=typeof `${y}`;");
+}
+
+#[test]
+fn test_fold_comparison_with_evaluator() {
+    test("x = `a` === 'a'", "x=!0;");
+    test("x = `a` != 'b'", "x=!0;");
+}
@@ -19,12 +19,21 @@ pub(crate) fn minify(
     let program = Parser::new(&allocator, source_text, source_type).parse().program;
     let program = allocator.alloc(program);
     Minifier::new(options).build(&allocator, program);
-    Codegen::<true>::new(source_text.len(), CodegenOptions).build(program)
+    let codegen_options =
+        CodegenOptions { escape_script_end_tags: true, ..CodegenOptions::default() };
+    Codegen::<true>::new(source_text.len(), codegen_options).build(program)
+}
+
+/// The options of the folding and printing tests, which keep the expression statements without
+/// side effects and the functions
+pub(crate) fn options() -> MinifierOptions {
+    let compress =
+        CompressOptions { dead_code: false, inline: false, ..CompressOptions::default() };
+    MinifierOptions { mangle: None, compress }
 }
 
 pub(crate) fn test(source_text: &str, expected: &str) {
-    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
-    test_with_options(source_text, expected, options);
+    test_with_options(source_text, expected, options());
 }
 
 pub(crate) fn test_with_options(source_text: &str, expected: &str, options: MinifierOptions) {
@@ -39,16 +48,22 @@ pub(crate) fn test_same(source_text: &str) {
 
 pub(crate) fn test_reparse(source_text: &str) {
     let source_type = SourceType::default();
-    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
-    let minified = minify(source_text, source_type, options);
+    let options = options();
+    let minified = minify(source_text, source_type, options.clone());
     let minified2 = minify(&minified, source_type, options);
     assert_eq!(minified, minified2, "for source {source_text}");
 }
 
+/// The printing tests of the expressions which `compress.evaluate` would fold
+pub(crate) fn test_without_evaluate(source_text: &str, expected: &str) {
+    let compress = CompressOptions { evaluate: false, ..options().compress };
+    test_with_options(source_text, expected, MinifierOptions { mangle: None, compress });
+}
+
 pub(crate) fn test_without_compress_booleans(source_text: &str, expected: &str) {
     let source_type = SourceType::default();
-    let compress_options = CompressOptions { booleans: false, ..CompressOptions::default() };
-    let options = MinifierOptions { mangle: false, compress: compress_options };
+    let compress_options = CompressOptions { booleans: false, ..options().compress };
+    let options = MinifierOptions { mangle: None, compress: compress_options };
     let minified = minify(source_text, source_type, options);
     assert_eq!(expected, minified, "for source {source_text}");
 }
@@ -58,11 +73,11 @@ where
     S: IntoIterator<Item = &'static str>,
 {
     let source_type = SourceType::default();
-    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
+    let options = options();
     let snapshot: String = sources
         .into_iter()
         .map(|source| {
-            let minified = minify(source, source_type, options);
+            let minified = minify(source, source_type, options.clone());
             format!(
                 "==================================== SOURCE ====================================
 {source}
@@ -0,0 +1,61 @@
+use crate::{test, test_reparse, test_with_options, MinifierOptions};
+
+fn test_dead_code(source_text: &str, expected: &str) {
+    let options = MinifierOptions { mangle: None, ..MinifierOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn pure_annotations_are_printed() {
+    test("/* #__PURE__ */ foo()", "/*#__PURE__*/foo();");
+    test("/* @__PURE__ */ new Foo(1)", "/*#__PURE__*/new Foo(1);");
+    test("x = /*#__PURE__*/ a.b(c)", "x=/*#__PURE__*/a.b(c);");
+    test("x = /*#__PURE__*/ foo()()", "x=/*#__PURE__*/foo()();");
+    test("/*#__PURE__*/ (function () {})()", "/*#__PURE__*/(function(){})();");
+    test("x = a / /*#__PURE__*/ foo()", "x=a/ /*#__PURE__*/foo();");
+    test("x = /* PURE */ foo()", "x=foo();");
+    test_reparse("/*#__PURE__*/ (function () {})(); /*#__PURE__*/ ({}).a()");
+}
+
+#[test]
+fn no_side_effects_annotations_are_printed() {
+    test("/* #__NO_SIDE_EFFECTS__ */ function f() {}", "/*#__NO_SIDE_EFFECTS__*/function f(){}");
+    test(
+        "/* @__NO_SIDE_EFFECTS__ */ async function f() {}",
+        "/*#__NO_SIDE_EFFECTS__*/async function f(){}",
+    );
+    test("const f = /*#__NO_SIDE_EFFECTS__*/ () => {}", "const f=/*#__NO_SIDE_EFFECTS__*/()=>{};");
+    test(
+        "/*#__NO_SIDE_EFFECTS__*/ const f = function () {}, g = 1",
+        "const f=/*#__NO_SIDE_EFFECTS__*/function(){},g=1;",
+    );
+    test_reparse("/*#__NO_SIDE_EFFECTS__*/ (function () {})");
+}
+
+#[test]
+fn unused_pure_calls_are_removed() {
+    test_dead_code("/*#__PURE__*/ foo()", "");
+    test_dead_code("/*#__PURE__*/ new Foo()", "");
+    test_dead_code("/*#__PURE__*/ foo(1, 'a')", "");
+    test_dead_code("/*#__PURE__*/ foo(bar())", "/*#__PURE__*/foo(bar());");
+    test_dead_code("/*#__PURE__*/ foo(...a)", "/*#__PURE__*/foo(...a);");
+    test_dead_code("x = /*#__PURE__*/ foo()", "x=/*#__PURE__*/foo();");
+}
+
+#[test]
+fn calls_of_no_side_effects_functions_are_removed() {
+    test_dead_code(
+        "/*#__NO_SIDE_EFFECTS__*/ function f() { foo(); } f(); f(1);",
+        "/*#__NO_SIDE_EFFECTS__*/function f(){foo()}",
+    );
+    test_dead_code(
+        "const f = /*#__NO_SIDE_EFFECTS__*/ () => foo(); f(); x = f();",
+        "const f=/*#__NO_SIDE_EFFECTS__*/()=>foo();x=/*#__PURE__*/f();",
+    );
+    // Reassigned functions may have side effects
+    test_dead_code(
+        "/*#__NO_SIDE_EFFECTS__*/ function f() {} f = g; f();",
+        "/*#__NO_SIDE_EFFECTS__*/function f(){}f=g,f();",
+    );
+    test_dead_code("function f() { foo(); } f();", "function f(){foo()}f();");
+}
@@ -21,11 +21,11 @@ fn undefined_return() {
 #[test]
 fn console_removal() {
     let options = MinifierOptions {
-        mangle: false,
+        mangle: None,
         compress: CompressOptions { drop_console: true, ..CompressOptions::default() },
     };
-    test_with_options("console.log('hi')", "", options);
-    test_with_options("let x = console.error('oops')", "let x;", options);
+    test_with_options("console.log('hi')", "", options.clone());
+    test_with_options("let x = console.error('oops')", "let x;", options.clone());
     test_with_options(
         "function f() { return console.warn('problem') }",
         "function f(){return}",
@@ -34,6 +34,6 @@ fn console_removal() {
 
     // console isn't removed when drop_console is `false`. This is also the
     // default value.
-    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
+    let options = MinifierOptions { mangle: None, ..MinifierOptions::default() };
     test_with_options("console.log('hi')", "console.log('hi');", options);
 }
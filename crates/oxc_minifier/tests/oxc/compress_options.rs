@@ -0,0 +1,102 @@
+use crate::{test_with_options, CompressOptions, MinifierOptions};
+
+fn test(source_text: &str, expected: &str, compress: CompressOptions) {
+    test_with_options(source_text, expected, MinifierOptions { mangle: None, compress });
+}
+
+#[test]
+fn drop_debugger() {
+    test("debugger; foo()", "foo();", CompressOptions::default());
+    let options = CompressOptions { drop_debugger: false, ..CompressOptions::default() };
+    test("debugger; foo()", "debugger;foo();", options);
+}
+
+#[test]
+fn pure_funcs() {
+    let options = CompressOptions {
+        pure_funcs: vec!["debug".into(), "Math.floor".into(), "a.b.c".into()],
+        ..CompressOptions::default()
+    };
+    test("debug('a'); Math.floor(1); a.b.c(); foo();", "foo();", options.clone());
+    test("x = Math.floor(y)", "x=/*#__PURE__*/Math.floor(y);", options.clone());
+    // The arguments are still evaluated
+    test("debug(foo())", "/*#__PURE__*/debug(foo());", options.clone());
+    test("Math.ceil(1); a.b(); a['b'].c();", "Math.ceil(1),a.b(),a['b'].c();", options);
+    test("debug('a')", "debug('a');", CompressOptions::default());
+}
+
+#[test]
+fn unsafe_math() {
+    let options = CompressOptions { unsafe_math: true, ..CompressOptions::default() };
+    test("x = y * 2 * 3", "x=y*6;", options.clone());
+    test("x = y / 2 / 5", "x=y/10;", options.clone());
+    test("x = y * 2 / 3", "x=y*2/3;", options.clone());
+    test("x = y / 3 / 3", "x=y/9;", options.clone());
+    // The product is longer than the operation
+    test("x = y * 0.1 * 3", "x=y*.1*3;", options);
+    test("x = y * 2 * 3", "x=y*2*3;", CompressOptions::default());
+}
+
+#[test]
+fn passes() {
+    // The declarations are joined after the dead code between them is removed
+    let source_text = "var a = 1; if (0) {} var b;";
+    test(source_text, "var a=1;var b;", CompressOptions::default());
+    test(source_text, "var a=1,b;", CompressOptions { passes: 2, ..CompressOptions::default() });
+}
+
+#[test]
+fn sequences() {
+    let options = CompressOptions::default();
+    test("a(); b(); c()", "a(),b(),c();", options.clone());
+    test("function f() { a(); b(); return c }", "function f(){return a(),b(),c}", options.clone());
+    test("function f() { a(); return }", "function f(){return void a()}", options.clone());
+    test("a(); throw b", "throw a(),b;", options.clone());
+    test("a(); if (b) c()", "if(a(),b)c();", options.clone());
+    test("a(); var b; c(); d()", "a();var b;c(),d();", options.clone());
+    test("(a(), b()); c()", "a(),b(),c();", options);
+    let options = CompressOptions { sequences: false, ..CompressOptions::default() };
+    test("a(); b()", "a();b();", options);
+}
+
+#[test]
+fn if_return() {
+    let options = CompressOptions::default();
+    test(
+        "function f() { if (a) return b; return c }",
+        "function f(){return a?b:c}",
+        options.clone(),
+    );
+    test(
+        "function f() { if (a) return b; else return c }",
+        "function f(){return a?b:c}",
+        options.clone(),
+    );
+    test(
+        "function f() { if (a) return b; if (c) return d; return e }",
+        "function f(){return a?b:c?d:e}",
+        options.clone(),
+    );
+    test(
+        "function f() { if (a) return; return b }",
+        "function f(){return a?void 0:b}",
+        options.clone(),
+    );
+    test(
+        "function f() { if (a) return; return }",
+        "function f(){if(a)return;return}",
+        options.clone(),
+    );
+    // The expression statements are merged into the conditional return
+    test(
+        "function f() { g(); if (a) return b; return c }",
+        "function f(){return g(),a?b:c}",
+        options,
+    );
+    let options = CompressOptions { if_return: false, ..CompressOptions::default() };
+    test(
+        "function f() { if (a) return b; return c }",
+        "function f(){if(a)return b;return c}",
+        options,
+    );
+}
@@ -0,0 +1,68 @@
+use crate::{test_with_options, CompressOptions, MinifierOptions};
+
+fn test(source_text: &str, expected: &str) {
+    // The called functions are kept
+    let compress = CompressOptions { inline: false, ..CompressOptions::default() };
+    let options = MinifierOptions { mangle: None, compress };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn unreachable() {
+    test("function f() { return 1; foo(); bar(); }", "function f(){return 1}");
+    test("function f() { throw x; foo(); }", "function f(){throw x}");
+    test("for (;;) { break; foo(); }", "for(;;){break}");
+    test("for (;;) { continue; foo(); }", "for(;;){continue}");
+    // Hoisted declarations are kept
+    test(
+        "function f() { return g(x); var x = 1; function g() {} }",
+        "function f(){return g(x);var x;function g(){}}",
+    );
+    test("function f() { return x; let y = 1; }", "function f(){return x}");
+}
+
+#[test]
+fn constant_branches() {
+    test("if (true) foo(); else bar();", "foo();");
+    test("if (false) foo(); else bar();", "bar();");
+    test("if (false) foo();", "");
+    test("if (0) { foo(); } else { bar(); baz(); }", "bar(),baz();");
+    test("if (1) { let x = foo(); }", "{let x=foo()}");
+    test("while (false) foo();", "");
+    test("for (; false; ) foo();", "");
+    test("if ('' + 1 === '1') foo();", "foo();");
+    test_same("if(x)foo();");
+    test_same("if(foo())bar();");
+    // The removed branch declares `x` in the function scope
+    test("if (false) { var x = 1; }", "if(!1){var x=1}");
+}
+
+#[test]
+fn side_effect_free_statements() {
+    test("1; 'a'; true; void 0;", "");
+    test("let x = 1; x; x + 1;", "let x=1;");
+    test("(function () {}); (class {});", "");
+    test_same("x;");
+    test_same("foo();");
+    test_same("x=1;");
+}
+
+#[test]
+fn unused_declarations() {
+    test("function f() { var x = 1; return 2; }", "function f(){return 2}");
+    test("function f() { let x = foo(); return 2; }", "function f(){let x=foo();return 2}");
+    test("function f() { function g() {} return 2; }", "function f(){return 2}");
+    test("function f() { var a = 1, b = 2; return b; }", "function f(){var b=2;return b}");
+    // `h` is only referenced by `g`, which is removed first
+    test("function f() { function g() { h(); } function h() {} }", "function f(){}");
+    test("function f() { var x; x = 1; }", "function f(){var x;x=1}");
+    test("() => { var x = 1; }", "()=>{};");
+    // The top level declarations may be referenced by other scripts
+    test_same("var x=1;function g(){}");
+    // `eval` can reference any variable in scope
+    test_same("function f(){var x=1;eval('x')}");
+}
@@ -8,6 +8,13 @@ fn addition_folding() {
     test("x+''", "x+'';");
 }
 
+#[test]
+fn arithmetic_folding() {
+    test("1 - -0", "1;");
+    test("true ** 2", "1;");
+    test("false ** 2", "0;");
+}
+
 #[test]
 fn typeof_folding() {
     test("typeof x === 'undefined'", "void 0===x;");
@@ -0,0 +1,69 @@
+use oxc_span::SourceType;
+
+use crate::{minify, test_with_options, CompressOptions, MinifierOptions};
+
+fn test(source_text: &str, expected: &str) {
+    test_with_options(
+        source_text,
+        expected,
+        MinifierOptions { mangle: None, ..MinifierOptions::default() },
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn immediately_invoked_functions() {
+    test("x = (function() { return a + b })()", "x=a+b;");
+    test("x = (() => a + b)()", "x=a+b;");
+    test("x = (function(a) { return a + b })(1)", "x=1+b;");
+    test("x = (function(a, c) { return a + c })('a')", "x='aundefined';");
+    test("x = (function() {})()", "x=void 0;");
+    test("x = (() => { a() })()", "x=void a();");
+    // The arguments are evaluated before the body
+    test_same("x=function(a){return a+b}(y());");
+    // The parameters are used more than once or written
+    test_same("x=function(a){return a+a}(1);");
+    test_same("x=function(a){return a=2}(1);");
+    // The function has its own `this` and `arguments`
+    test_same("x=function(){return this}();");
+    test_same("x=function(){return ()=>arguments}();");
+    test("x = (function() { return function() { return this } })()", "x=function(){return this};");
+    test_same("x=function f(){return f}();");
+    test_same("x=async function(){return a}();");
+    test_same("x=function(){'use strict';return a}();");
+    test_same("x=/*#__PURE__*/function(){return a}();");
+}
+
+#[test]
+fn single_use_functions() {
+    test("function f() { function g(a) { h(a); } g(x); }", "function f(){(function(a){h(a)})(x)}");
+    test("function f() { function g() { return 1 } return g() }", "function f(){return 1}");
+    // The function is used more than once or not called
+    test_same("function f(){function g(){h()}g(),g()}");
+    test_same("function f(){function g(){h()}return g}");
+    // The call is in a nested scope
+    test_same("function f(){function g(){h()}return function(){g()}}");
+    test_same("function f(){function g(){h()}{let y=i();g(y)}}");
+    // The top level functions of scripts are global
+    test_same("function g(){h()}g();");
+    let source_type = SourceType::default().with_module(true);
+    let options = MinifierOptions { mangle: None, ..MinifierOptions::default() };
+    assert_eq!(minify("function g() { h() } g()", source_type, options), "void h();");
+}
+
+#[test]
+fn options() {
+    let compress = CompressOptions { inline_max_size: 10, ..CompressOptions::default() };
+    let options = MinifierOptions { mangle: None, compress };
+    test_with_options(
+        "function f(){function g(){h()}g()}",
+        "function f(){function g(){h()}g()}",
+        options,
+    );
+    let compress = CompressOptions { inline: false, ..CompressOptions::default() };
+    let options = MinifierOptions { mangle: None, compress };
+    test_with_options("x = (() => a)()", "x=(()=>a)();", options);
+}
@@ -0,0 +1,80 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_minifier::{ManglePropertiesOptions, PropertyMangler, PropertyNameCache};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use regex::Regex;
+
+fn mangle(
+    source_text: &str,
+    options: ManglePropertiesOptions,
+    name_cache: PropertyNameCache,
+) -> (String, PropertyNameCache) {
+    let allocator = Allocator::default();
+    let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+    let program = allocator.alloc(program);
+    let mut mangler = PropertyMangler::new(&allocator, options).with_name_cache(name_cache);
+    mangler.build(program);
+    let printed = Codegen::<true>::new(source_text.len(), CodegenOptions::default()).build(program);
+    (printed, mangler.name_cache().clone())
+}
+
+fn options() -> ManglePropertiesOptions {
+    ManglePropertiesOptions::new(Regex::new("^_[a-z]").unwrap())
+}
+
+fn test_with_options(source_text: &str, expected: &str, options: ManglePropertiesOptions) {
+    let (printed, _) = mangle(source_text, options, PropertyNameCache::default());
+    assert_eq!(expected, printed, "for source {source_text}");
+}
+
+fn test(source_text: &str, expected: &str) {
+    test_with_options(source_text, expected, options());
+}
+
+#[test]
+fn property_accesses() {
+    test("o._foo = o._bar + o._foo + o.baz", "o.a=o.b+o.a+o.baz;");
+    test("o?._foo; o._foo._bar()", "o?.a;o.a.b();");
+    test("o['_foo']; '_foo' in o", "o['a'];'a'in o;");
+}
+
+#[test]
+fn object_literals_and_patterns() {
+    test("x = { _foo: 1, _bar() {}, get _baz() {} }", "x={a:1,b(){},get c(){}};");
+    test("x = { _foo }", "x={a:_foo};");
+    test("const { _foo, _bar: y = 1 } = o", "const {a:_foo,b:y=1}=o;");
+    test("({ _foo, _bar = 1 } = o)", "({a:_foo,b:_bar=1}=o);");
+}
+
+#[test]
+fn classes() {
+    test(
+        "class Foo { _count = 0; static _instances; _increment() { this._count++; } }",
+        "class Foo{a=0;static b;c(){this.a++}}",
+    );
+}
+
+#[test]
+fn reserved_names() {
+    // The new names do not collide with the other properties
+    test("o._foo = o.a", "o.b=o.a;");
+    let options = ManglePropertiesOptions { reserved: vec!["_bar".into()], ..options() };
+    test_with_options("o._foo = o._bar", "o.a=o._bar;", options);
+}
+
+#[test]
+fn keep_quoted() {
+    let options = ManglePropertiesOptions { keep_quoted: true, ..options() };
+    test_with_options("o['_foo'] = o._foo + o._bar", "o['_foo']=o._foo+o.a;", options.clone());
+    test_with_options("x = { '_foo': 1, _bar: 2 }", "x={'_foo':1,a:2};", options);
+}
+
+#[test]
+fn name_cache() {
+    let (_, name_cache) = mangle("o._foo = o._bar", options(), PropertyNameCache::default());
+    let name_cache = PropertyNameCache::from_json(&name_cache.to_json()).unwrap();
+    let (printed, name_cache) = mangle("o._baz = o._bar", options(), name_cache);
+    assert_eq!(printed, "o.c=o.b;");
+    assert_eq!(name_cache.props.len(), 3);
+}
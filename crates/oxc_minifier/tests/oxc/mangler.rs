@@ -0,0 +1,163 @@
+use oxc_minifier::{CompressOptions, ManglerOptions};
+
+use oxc_span::SourceType;
+
+use crate::{minify, test_with_options, MinifierOptions};
+
+fn test_with_mangler(source_text: &str, expected: &str, mangler: ManglerOptions) {
+    let options = MinifierOptions { mangle: Some(mangler), compress: CompressOptions::all_false() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test(source_text: &str, expected: &str) {
+    test_with_mangler(source_text, expected, ManglerOptions::default());
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn locals() {
+    test("function foo(bar, baz) { return bar + baz; }", "function foo(o,f){return o+f}");
+    test(
+        "function foo() { let first = 1; { let second = 2; } return first; }",
+        "function foo(){let o=1;{let f=2}return o}",
+    );
+    test(
+        "function foo() { try {} catch (error) { throw error; } }",
+        "function foo(){try{}catch(o){throw o}}",
+    );
+    test(
+        "function foo() { const { x, y = 1 } = z; ({ x } = z); return x + y; }",
+        "function foo(){const {x:o,y:f=1}=z;({x:o}=z);return o+f}",
+    );
+    test(
+        "function foo() { var isDone; function check() { isDone = true; } }",
+        "function foo(){var o;function f(){o=true}}",
+    );
+}
+
+#[test]
+fn hoisted_vars() {
+    // The `var`s of blocks are declared by the function, they have different names
+    test(
+        "function foo() { { var bar = 2; } if (a) { var baz = 3; } return [bar, baz]; }",
+        "function foo(){{var o=2}if(a){var f=3}return [o,f]}",
+    );
+    test(
+        "function foo() { try {} catch (e) { var ex = e; } { var bar = 2; } return [ex, bar]; }",
+        "function foo(){try{}catch(a){var o=a}{var f=2}return [o,f]}",
+    );
+}
+
+#[test]
+fn function_expression_names() {
+    test("var foo = function bar() { return bar; };", "var foo=function o(){return o};");
+    let keep_fnames = ManglerOptions { keep_fnames: true, ..ManglerOptions::default() };
+    test_with_mangler(
+        "var foo = function bar() { return bar; };",
+        "var foo=function bar(){return bar};",
+        keep_fnames,
+    );
+}
+
+#[test]
+fn top_level() {
+    test_same("var foo=1;function bar(){}");
+    test_same("let foo=1;class Bar{}");
+    // The `var`s of the top level blocks are globals too
+    test(
+        "try {} catch (e) { var ex = e; } { var foo = 2; } if (a) { var bar = 3; }",
+        "try{}catch(o){var ex=o}{var foo=2}if(a){var bar=3}",
+    );
+}
+
+#[test]
+fn unresolved_references() {
+    // The new names never shadow the globals
+    test("function foo(bar) { return o(bar, f); }", "function foo(a){return o(a,f)}");
+}
+
+#[test]
+fn eval_and_with() {
+    test(
+        "function foo(bar) { function baz(qux) { return qux; } eval('bar'); }",
+        "function foo(bar){function baz(a){return a}eval('bar')}",
+    );
+    test(
+        "function foo(bar){with(obj)bar;return function(qux){return qux}}",
+        "function foo(bar){with(obj)bar;return function(b){return b}}",
+    );
+    // A declared `eval` is not a direct eval
+    test("function foo(eval, bar) { eval(bar); }", "function foo(o,f){o(f)}");
+}
+
+#[test]
+fn keep_names() {
+    let source_text = "function foo() { function bar() {} class Baz {} return [bar, Baz]; }";
+    test(source_text, "function foo(){function o(){}class f{}return [o,f]}");
+    let keep_fnames = ManglerOptions { keep_fnames: true, ..ManglerOptions::default() };
+    test_with_mangler(
+        source_text,
+        "function foo(){function bar(){}class a{}return [bar,a]}",
+        keep_fnames,
+    );
+    let keep_classnames = ManglerOptions { keep_classnames: true, ..ManglerOptions::default() };
+    test_with_mangler(
+        source_text,
+        "function foo(){function a(){}class Baz{}return [a,Baz]}",
+        keep_classnames,
+    );
+}
+
+#[test]
+fn reserved() {
+    let reserved =
+        ManglerOptions { reserved: vec!["bar".into(), "o".into()], ..ManglerOptions::default() };
+    test_with_mangler(
+        "function foo(bar, baz) { return bar + baz; }",
+        "function foo(bar,a){return bar+a}",
+        reserved,
+    );
+}
+
+#[test]
+fn mangle_top_level() {
+    let top_level = ManglerOptions { top_level: true, ..ManglerOptions::default() };
+    test_with_mangler(
+        "var foo = 1; function bar() { return foo; }",
+        "var a=1;function b(){return a}",
+        top_level.clone(),
+    );
+    // Unless the top level scope can be referenced by `eval`
+    test_with_mangler("var foo = 1; eval('foo');", "var foo=1;eval('foo');", top_level.clone());
+
+    let source_type = SourceType::default().with_module(true);
+    let options =
+        MinifierOptions { mangle: Some(top_level), compress: CompressOptions::all_false() };
+    let minified = minify(
+        "import { imported } from 'a'; export const foo = imported; const bar = 1, baz = 2; export { bar as qux };",
+        source_type,
+        options,
+    );
+    assert_eq!(
+        minified,
+        "import{imported as a}from'a';export const foo=a;const bar=1,o=2;export{bar as qux};"
+    );
+}
+
+#[test]
+fn keep_names_in_strings() {
+    let options = ManglerOptions { keep_names_in_strings: true, ..ManglerOptions::default() };
+    test_with_mangler(
+        "function foo(bar, baz) { setTimeout('bar()'); return this[`baz`] + baz; }",
+        "function foo(bar,baz){setTimeout('bar()');return this[`baz`]+baz}",
+        options.clone(),
+    );
+    test_with_mangler(
+        "function foo(bar, baz) { return 'bars'; }",
+        "function foo(o,a){return 'bars'}",
+        options,
+    );
+}
@@ -1,3 +1,11 @@
+mod annotations;
 mod code_removal;
+mod compress_options;
+mod dead_code;
 mod folding;
+mod inline;
+mod mangle_properties;
+mod mangler;
 mod precedence;
+mod tree_shaking;
+mod whitespace;
@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use oxc_allocator::Allocator;
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_minifier::{module_has_side_effects, TreeShakeOptions, TreeShaker};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test_with_options(source_text: &str, expected: &str, options: TreeShakeOptions) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let program = allocator.alloc(program);
+    TreeShaker::new(&allocator, options).build(program);
+    let printed = Codegen::<true>::new(source_text.len(), CodegenOptions::default()).build(program);
+    assert_eq!(expected, printed, "for source {source_text}");
+}
+
+fn test(source_text: &str, expected: &str, unused_exports: &[&str]) {
+    let options = TreeShakeOptions {
+        unused_exports: unused_exports.iter().map(ToString::to_string).collect(),
+        ..TreeShakeOptions::default()
+    };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn unused_exports() {
+    test("export const a = 1; export const b = 2;", "export const a=1;", &["b"]);
+    test("export function f() {} export class C {}", "", &["f", "C"]);
+    test("const a = 1, b = 2; export { a, b as c };", "const a=1;export{a};", &["c"]);
+    test("export default 1; export const a = 1;", "export const a=1;", &["default"]);
+    // Exports with side effects are kept as statements
+    test(
+        "export const a = foo(); export default bar();",
+        "const a=foo();bar();",
+        &["a", "default"],
+    );
+    test("export class C { static x = foo(); }", "class C{static x=foo()}", &["C"]);
+}
+
+#[test]
+fn transitive_dependencies() {
+    test(
+        "const x = 1; const helper = () => x; function g() { return g(); } export const a = helper; export const b = g;",
+        "",
+        &["a", "b"],
+    );
+    test(
+        "import { x, y } from './x'; const helper = () => x; export const a = helper; export const b = y;",
+        "import{y}from'./x';export const b=y;",
+        &["a"],
+    );
+    // The imported module is still evaluated
+    test("import { x } from './x'; export const a = x;", "import'./x';", &["a"]);
+    test("export { a } from './a'; export * from './b';", "import'./a';export*from'./b';", &["a"]);
+}
+
+#[test]
+fn side_effect_free_imports() {
+    let options = TreeShakeOptions {
+        unused_exports: std::iter::once("a".to_string()).collect(),
+        side_effect_free_imports: std::iter::once("./x".to_string()).collect(),
+    };
+    test_with_options("import { x } from './x'; import './x'; export const a = x;", "", options);
+}
+
+#[test]
+fn scripts_are_not_tree_shaken() {
+    let allocator = Allocator::default();
+    let source_text = "var a = 1;";
+    let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+    let program = allocator.alloc(program);
+    TreeShaker::new(&allocator, TreeShakeOptions::default()).build(program);
+    let printed = Codegen::<true>::new(source_text.len(), CodegenOptions::default()).build(program);
+    assert_eq!(printed, "var a=1;");
+}
+
+#[test]
+fn package_json_side_effects() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/side_effects");
+    assert!(!module_has_side_effects(&fixtures.join("pure/src/index.js")));
+    assert!(module_has_side_effects(&fixtures.join("partial/src/polyfill.js")));
+    assert!(module_has_side_effects(&fixtures.join("partial/src/styles/main.css")));
+    assert!(!module_has_side_effects(&fixtures.join("partial/src/index.js")));
+    assert!(module_has_side_effects(&fixtures.join("none/index.js")));
+}
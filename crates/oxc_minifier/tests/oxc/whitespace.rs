@@ -0,0 +1,44 @@
+use oxc_span::SourceType;
+
+use crate::{minify, options, test};
+
+fn test_module(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    assert_eq!(minify(source_text, source_type, options()), expected, "for source {source_text}");
+}
+
+#[test]
+fn modules() {
+    test_module("import a from 'path'", "import a from'path';");
+    test_module("import * as ns from 'path'", "import*as ns from'path';");
+    test_module("import a, {b, c as d} from 'path'", "import a,{b,c as d}from'path';");
+    test_module("export * from 'path'", "export*from'path';");
+    test_module("export * as ns from 'path'", "export*as ns from'path';");
+    test_module("export {a, b as c} from 'path'", "export{a,b as c}from'path';");
+    test_module("let a, b; export {a, b as c}", "let a,b;export{a,b as c};");
+}
+
+#[test]
+fn statements() {
+    test("if (a) { b(); c() } else { d(); e() }", "if(a){b(),c()}else{d(),e()}");
+    test("if (a) b(); else if (c) d()", "if(a)b();else if(c)d();");
+    test("if (a) b(); else c()", "if(a)b();else c();");
+}
+
+#[test]
+fn strings() {
+    test("x = 'it\\'s'", "x=\"it's\";");
+    test("x = \"say \\\"hi\\\"\"", "x='say \"hi\"';");
+    test("x = '\\'\"'", "x='\\'\"';");
+    test("x = '</script>'", "x='<\\/script>';");
+    test("x = '</SCRIPT>'", "x='<\\/SCRIPT>';");
+}
+
+#[test]
+fn operators() {
+    test("a + +b", "a+ +b;");
+    test("a - -b", "a- -b;");
+    test("a + ++b", "a+ ++b;");
+    test("a-- > b", "a-- >b;");
+    test("a < !--b", "a<! --b;");
+}
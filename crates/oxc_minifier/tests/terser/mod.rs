@@ -67,7 +67,7 @@ impl TestCase {
         }
 
         let source_type = SourceType::default();
-        let options = MinifierOptions { mangle: false, compress: self.compress_options };
+        let options = MinifierOptions { mangle: None, compress: self.compress_options.clone() };
         let minified_source_text = minify(self.input.as_ref(), source_type, options);
         assert_eq!(
             remove_whitespace(minified_source_text.as_str()),
@@ -132,13 +132,38 @@ impl TestCase {
         Self { name, compress_options: options, input, expect }
     }
 
-    #[allow(clippy::single_match)]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn parse_options<'a>(object_expr: &'a ObjectExpression<'a>) -> CompressOptions {
         let mut options = CompressOptions::default();
         for object_property in &object_expr.properties {
             if let ObjectPropertyKind::ObjectProperty(property) = object_property {
-                if property.key.is_specific_static_name("drop_debugger") {
-                    options.drop_debugger = Self::get_boolean(&property.value);
+                let Some(name) = property.key.static_name() else { continue };
+                match name.as_str() {
+                    "drop_console" => options.drop_console = Self::get_boolean(&property.value),
+                    "drop_debugger" => options.drop_debugger = Self::get_boolean(&property.value),
+                    "if_return" => options.if_return = Self::get_boolean(&property.value),
+                    "sequences" => options.sequences = Self::get_boolean(&property.value),
+                    "unsafe_math" => options.unsafe_math = Self::get_boolean(&property.value),
+                    "passes" => {
+                        if let Expression::NumberLiteral(literal) = &property.value {
+                            options.passes = literal.value as usize;
+                        }
+                    }
+                    "pure_funcs" => {
+                        if let Expression::ArrayExpression(array) = &property.value {
+                            options.pure_funcs = array
+                                .elements
+                                .iter()
+                                .filter_map(|element| match element {
+                                    ArrayExpressionElement::Expression(
+                                        Expression::StringLiteral(literal),
+                                    ) => Some(literal.value.to_string()),
+                                    _ => None,
+                                })
+                                .collect();
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
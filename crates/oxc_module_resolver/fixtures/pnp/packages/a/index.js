@@ -0,0 +1 @@
+import 'exports-pkg';
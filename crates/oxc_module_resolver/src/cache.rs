@@ -0,0 +1,203 @@
+use std::{
+    hash::BuildHasherDefault,
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use dashmap::DashMap;
+use rustc_hash::FxHasher;
+
+use crate::{FileMetadata, FileSystem, PackageJson, PnpManifest, ResolveError, TsConfig};
+
+pub type FxDashMap<K, V> = DashMap<K, V, BuildHasherDefault<FxHasher>>;
+
+/// The cache of the file system queries and of the parsed `package.json` files, which is shared
+/// by the threads resolving at the same time.
+pub struct Cache<Fs> {
+    fs: Fs,
+    /// `None` for the paths which do not exist
+    metadata: FxDashMap<PathBuf, Option<FileMetadata>>,
+    /// Keyed by the directory, `None` for the directories without a `package.json`
+    package_jsons: FxDashMap<PathBuf, Option<Arc<PackageJson>>>,
+    /// Keyed by the path of the tsconfig
+    tsconfigs: FxDashMap<PathBuf, Arc<TsConfig>>,
+    /// Keyed by the directory, `None` for the directories without a Yarn PnP manifest
+    pnp_manifests: FxDashMap<PathBuf, Option<Arc<PnpManifest>>>,
+    /// The paths with their symlinks resolved, `None` for the paths which do not exist
+    canonical_paths: FxDashMap<PathBuf, Option<PathBuf>>,
+    /// Incremented when the cache is cleared, the caches of the resolutions of the resolvers
+    /// sharing the cache are stale when their generation is older
+    generation: AtomicU64,
+}
+
+impl<Fs: FileSystem> Cache<Fs> {
+    pub fn new(fs: Fs) -> Self {
+        Self {
+            fs,
+            metadata: FxDashMap::default(),
+            package_jsons: FxDashMap::default(),
+            tsconfigs: FxDashMap::default(),
+            pnp_manifests: FxDashMap::default(),
+            canonical_paths: FxDashMap::default(),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.metadata.clear();
+        self.package_jsons.clear();
+        self.tsconfigs.clear();
+        self.pnp_manifests.clear();
+        self.canonical_paths.clear();
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of times the cache has been cleared
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Clears the cache when the file system has changed since it was cached, e.g. a
+    /// `package.json` has been edited or a file has been created. The cached metadata is the
+    /// snapshot of the file system which is compared with the current metadata.
+    ///
+    /// Returns whether the cache has been cleared.
+    pub fn refresh(&self) -> bool {
+        let snapshot = self
+            .metadata
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect::<Vec<_>>();
+        let changed =
+            snapshot.iter().any(|(path, metadata)| self.fs.metadata(path).ok() != *metadata);
+        if changed {
+            self.clear();
+        }
+        changed
+    }
+
+    /// `path` with its symlinks resolved, or `path` itself when it cannot be resolved
+    pub fn canonicalize(&self, path: &Path) -> PathBuf {
+        if let Some(canonical_path) = self.canonical_paths.get(path) {
+            return canonical_path.clone().unwrap_or_else(|| path.to_path_buf());
+        }
+        let canonical_path = self.fs.canonicalize(path).ok();
+        self.canonical_paths.insert(path.to_path_buf(), canonical_path.clone());
+        canonical_path.unwrap_or_else(|| path.to_path_buf())
+    }
+
+    pub fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path).is_some_and(|metadata| metadata.is_file)
+    }
+
+    pub fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).is_some_and(|metadata| metadata.is_dir)
+    }
+
+    fn metadata(&self, path: &Path) -> Option<FileMetadata> {
+        if let Some(metadata) = self.metadata.get(path) {
+            return *metadata;
+        }
+        let metadata = self.fs.metadata(path).ok();
+        self.metadata.insert(path.to_path_buf(), metadata);
+        metadata
+    }
+
+    /// # Errors
+    ///
+    /// * The file does not exist or cannot be read
+    pub fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    pub fn tsconfig(&self, path: &Path) -> Option<Arc<TsConfig>> {
+        self.tsconfigs.get(path).map(|tsconfig| Arc::clone(&tsconfig))
+    }
+
+    pub fn insert_tsconfig(&self, path: PathBuf, tsconfig: Arc<TsConfig>) {
+        self.tsconfigs.insert(path, tsconfig);
+    }
+
+    /// The `package.json` of `directory`
+    ///
+    /// # Errors
+    ///
+    /// * The `package.json` cannot be parsed
+    pub fn package_json(&self, directory: &Path) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        if let Some(package_json) = self.package_jsons.get(directory) {
+            return Ok(package_json.clone());
+        }
+        let path = directory.join("package.json");
+        let package_json = if self.is_file(&path) {
+            let json = self.fs.read_to_string(&path).map_err(|err| ResolveError::Json {
+                path: path.clone(),
+                message: err.to_string(),
+            })?;
+            Some(Arc::new(PackageJson::parse(path, &json)?))
+        } else {
+            None
+        };
+        self.package_jsons.insert(directory.to_path_buf(), package_json.clone());
+        Ok(package_json)
+    }
+
+    /// The nearest `package.json` of `directory` and of its ancestors
+    ///
+    /// # Errors
+    ///
+    /// * A `package.json` cannot be parsed
+    pub fn find_package_json(
+        &self,
+        directory: &Path,
+    ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        for directory in directory.ancestors() {
+            if let Some(package_json) = self.package_json(directory)? {
+                return Ok(Some(package_json));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The nearest Yarn PnP manifest of `directory` and of its ancestors
+    ///
+    /// # Errors
+    ///
+    /// * A manifest cannot be read or parsed
+    pub fn find_pnp_manifest(
+        &self,
+        directory: &Path,
+    ) -> Result<Option<Arc<PnpManifest>>, ResolveError> {
+        for directory in directory.ancestors() {
+            if let Some(manifest) = self.pnp_manifest(directory)? {
+                return Ok(Some(manifest));
+            }
+        }
+        Ok(None)
+    }
+
+    fn pnp_manifest(&self, directory: &Path) -> Result<Option<Arc<PnpManifest>>, ResolveError> {
+        if let Some(manifest) = self.pnp_manifests.get(directory) {
+            return Ok(manifest.clone());
+        }
+        let path = [".pnp.data.json", ".pnp.cjs"]
+            .into_iter()
+            .map(|file_name| directory.join(file_name))
+            .find(|path| self.is_file(path));
+        let manifest = match path {
+            Some(path) => {
+                let content = self.fs.read_to_string(&path).map_err(|err| ResolveError::Json {
+                    path: path.clone(),
+                    message: err.to_string(),
+                })?;
+                Some(Arc::new(PnpManifest::from_file(&path, &content)?))
+            }
+            None => None,
+        };
+        self.pnp_manifests.insert(directory.to_path_buf(), manifest.clone());
+        Ok(manifest)
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use oxc_diagnostics::thiserror::{self, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ResolveError {
+    #[error("Cannot find module '{0}'")]
+    NotFound(String),
+
+    #[error("'{0}' is a Node.js builtin module")]
+    Builtin(String),
+
+    #[error("The specifier is empty")]
+    EmptySpecifier,
+
+    #[error("Invalid module specifier '{0}'")]
+    InvalidModuleSpecifier(String),
+
+    #[error("Package subpath '{subpath}' is not defined by \"exports\" in {}", package_json.display())]
+    PackagePathNotExported { subpath: String, package_json: PathBuf },
+
+    #[error("Package import specifier '{specifier}' is not defined in {}", package_json.display())]
+    PackageImportNotDefined { specifier: String, package_json: PathBuf },
+
+    #[error("Invalid target {target} defined in {}", package_json.display())]
+    InvalidPackageTarget { target: String, package_json: PathBuf },
+
+    #[error("Invalid package config {}, \"exports\" cannot contain both the keys starting with '.' and the conditions", .0.display())]
+    InvalidPackageConfig(PathBuf),
+
+    #[error("Cannot find the tsconfig {}", .0.display())]
+    TsconfigNotFound(PathBuf),
+
+    #[error("The tsconfig {} extends itself", .0.display())]
+    TsconfigCircularExtend(PathBuf),
+
+    #[error("Failed to parse {}: {message}", path.display())]
+    Json { path: PathBuf, message: String },
+}
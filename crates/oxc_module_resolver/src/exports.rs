@@ -0,0 +1,330 @@
+//! package.json `exports` and `imports`
+//!
+//! References:
+//! * <https://nodejs.org/api/packages.html#package-entry-points>
+//! * <https://nodejs.org/api/esm.html#resolution-algorithm-specification>
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::{FileSystem, PackageJson, ResolveError, ResolverGeneric};
+
+/// The part of a subpath matched by a key of an `exports` or `imports` map
+#[derive(Clone, Copy)]
+enum SubpathMatch<'a> {
+    /// Replaces the `*` of the target, e.g. `./lib/*.js` of `./*`
+    Pattern(&'a str),
+    /// Appended to the target of the deprecated folder mappings, e.g. `./lib/` of `./`
+    Folder(&'a str),
+}
+
+impl<Fs: FileSystem> ResolverGeneric<Fs> {
+    /// LOAD_PACKAGE_IMPORTS(X, DIR), the `imports` of the package of `directory` for the
+    /// specifiers starting with `#`
+    pub(crate) fn load_package_imports(
+        &self,
+        directory: &Path,
+        request: &str,
+    ) -> Result<PathBuf, ResolveError> {
+        if request == "#" || request.starts_with("#/") {
+            return Err(ResolveError::InvalidModuleSpecifier(request.to_string()));
+        }
+        let Some(package_json) = self.cache.find_package_json(directory)? else {
+            return Err(ResolveError::NotFound(request.to_string()));
+        };
+        if let Some(imports) = package_json.imports() {
+            if let Some(path) =
+                self.package_imports_exports_resolve(request, imports, &package_json, true)?
+            {
+                return self.load_exports_target(path, request);
+            }
+        }
+        Err(ResolveError::PackageImportNotDefined {
+            specifier: request.to_string(),
+            package_json: package_json.path().to_path_buf(),
+        })
+    }
+
+    /// LOAD_PACKAGE_SELF(X, DIR), the `exports` of the package of `directory` when `request`
+    /// starts with its name
+    pub(crate) fn load_package_self(
+        &self,
+        directory: &Path,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        let Some((name, subpath)) = parse_package_specifier(request) else { return Ok(None) };
+        let Some(package_json) = self.cache.find_package_json(directory)? else { return Ok(None) };
+        if package_json.name() != Some(name) {
+            return Ok(None);
+        }
+        let Some(exports) = package_json.exports() else { return Ok(None) };
+        let path = self.package_exports_resolve(&package_json, &subpath, exports)?;
+        self.load_exports_target(path, request).map(Some)
+    }
+
+    /// LOAD_PACKAGE_EXPORTS(X, DIR), the `exports` of the package of `request` in `modules_dir`.
+    /// The other files of a package with `exports` cannot be resolved.
+    pub(crate) fn load_package_exports(
+        &self,
+        modules_dir: &Path,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        let Some((name, subpath)) = parse_package_specifier(request) else { return Ok(None) };
+        let package_dir = modules_dir.join(name);
+        if !self.cache.is_dir(&package_dir) {
+            return Ok(None);
+        }
+        self.load_package_dir_exports(&package_dir, &subpath, request)
+    }
+
+    /// The `subpath` of the `exports` of the package in `package_dir`, `None` when the package
+    /// does not have `exports`
+    pub(crate) fn load_package_dir_exports(
+        &self,
+        package_dir: &Path,
+        subpath: &str,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        let Some(package_json) = self.cache.package_json(package_dir)? else { return Ok(None) };
+        let Some(exports) = package_json.exports() else { return Ok(None) };
+        let path = self.package_exports_resolve(&package_json, subpath, exports)?;
+        self.load_exports_target(path, request).map(Some)
+    }
+
+    /// The targets are the paths of the files, the extensions and the index files are not
+    /// attempted
+    fn load_exports_target(&self, path: PathBuf, request: &str) -> Result<PathBuf, ResolveError> {
+        if self.cache.is_file(&path) {
+            Ok(path)
+        } else {
+            Err(ResolveError::NotFound(request.to_string()))
+        }
+    }
+
+    /// PACKAGE_EXPORTS_RESOLVE(packageURL, subpath, exports, conditions)
+    fn package_exports_resolve(
+        &self,
+        package_json: &PackageJson,
+        subpath: &str,
+        exports: &Value,
+    ) -> Result<PathBuf, ResolveError> {
+        let subpath_keys = match exports {
+            Value::Object(map) => {
+                let dot_keys = map.keys().filter(|key| key.starts_with('.')).count();
+                if dot_keys != 0 && dot_keys != map.len() {
+                    return Err(ResolveError::InvalidPackageConfig(package_json.path().into()));
+                }
+                (dot_keys != 0).then_some(map)
+            }
+            _ => None,
+        };
+        let resolved = if subpath == "." {
+            let main_export = subpath_keys.map_or(Some(exports), |map| map.get("."));
+            match main_export {
+                Some(target) => self.package_target_resolve(package_json, target, None, false)?,
+                None => None,
+            }
+        } else if let Some(map) = subpath_keys {
+            self.package_imports_exports_resolve(subpath, map, package_json, false)?
+        } else {
+            None
+        };
+        resolved.ok_or_else(|| ResolveError::PackagePathNotExported {
+            subpath: subpath.to_string(),
+            package_json: package_json.path().to_path_buf(),
+        })
+    }
+
+    /// PACKAGE_IMPORTS_EXPORTS_RESOLVE(matchKey, matchObj, packageURL, isImports, conditions)
+    fn package_imports_exports_resolve(
+        &self,
+        match_key: &str,
+        match_obj: &Map<String, Value>,
+        package_json: &PackageJson,
+        is_imports: bool,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        if !match_key.contains('*') {
+            if let Some(target) = match_obj.get(match_key) {
+                return self.package_target_resolve(package_json, target, None, is_imports);
+            }
+        }
+        // PATTERN_KEY_COMPARE, the key with the longest prefix before the `*` first, the folder
+        // mappings after the patterns of the same prefix.
+        let mut expansion_keys = match_obj
+            .keys()
+            .filter_map(|key| match key.split_once('*') {
+                Some((base, trailer)) if !trailer.contains('*') => Some((key, base, Some(trailer))),
+                None if key.ends_with('/') => Some((key, key.as_str(), None)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        expansion_keys.sort_by(|(a, a_base, a_trailer), (b, b_base, b_trailer)| {
+            b_base
+                .len()
+                .cmp(&a_base.len())
+                .then_with(|| a_trailer.is_none().cmp(&b_trailer.is_none()))
+                .then_with(|| b.len().cmp(&a.len()))
+        });
+        for (key, base, trailer) in expansion_keys {
+            let Some(rest) = match_key.strip_prefix(base) else { continue };
+            let subpath_match = match trailer {
+                Some(_) if rest.is_empty() => continue,
+                Some(trailer) => {
+                    let Some(pattern_match) = rest.strip_suffix(trailer) else { continue };
+                    SubpathMatch::Pattern(pattern_match)
+                }
+                None => SubpathMatch::Folder(rest),
+            };
+            let target = &match_obj[key];
+            return self.package_target_resolve(
+                package_json,
+                target,
+                Some(subpath_match),
+                is_imports,
+            );
+        }
+        Ok(None)
+    }
+
+    /// PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions), `None`
+    /// for the targets which are excluded by `null` or by the conditions
+    fn package_target_resolve(
+        &self,
+        package_json: &PackageJson,
+        target: &Value,
+        subpath_match: Option<SubpathMatch>,
+        is_imports: bool,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        let invalid_target = || ResolveError::InvalidPackageTarget {
+            target: target.to_string(),
+            package_json: package_json.path().to_path_buf(),
+        };
+        match target {
+            Value::String(target) => {
+                let target = match subpath_match {
+                    Some(SubpathMatch::Pattern(pattern_match)) => {
+                        if has_invalid_segments(pattern_match) {
+                            return Err(invalid_target());
+                        }
+                        target.replace('*', pattern_match)
+                    }
+                    Some(SubpathMatch::Folder(subpath)) => {
+                        if !target.ends_with('/') || has_invalid_segments(subpath) {
+                            return Err(invalid_target());
+                        }
+                        format!("{target}{subpath}")
+                    }
+                    None => target.clone(),
+                };
+                let Some(relative) = target.strip_prefix("./") else {
+                    // The bare targets of `imports` are resolved from the package, e.g. `#dep` to
+                    // `dep`
+                    let is_bare = !target.starts_with("../")
+                        && !target.starts_with('/')
+                        && !target.starts_with('#')
+                        && !target.contains(':');
+                    if is_imports && is_bare {
+                        return self.require(package_json.directory(), &target).map(Some);
+                    }
+                    return Err(invalid_target());
+                };
+                if has_invalid_segments(relative.trim_end_matches('/')) {
+                    return Err(invalid_target());
+                }
+                Ok(Some(package_json.directory().join(relative)))
+            }
+            Value::Object(conditions) => {
+                for (condition, target) in conditions {
+                    if condition == "default" || self.options.condition_names.contains(condition) {
+                        let resolved = self.package_target_resolve(
+                            package_json,
+                            target,
+                            subpath_match,
+                            is_imports,
+                        )?;
+                        if resolved.is_some() {
+                            return Ok(resolved);
+                        }
+                    }
+                }
+                Ok(None)
+            }
+            // The first valid target, the targets of the array are fallbacks
+            Value::Array(targets) => {
+                let mut last_error = None;
+                for target in targets {
+                    match self.package_target_resolve(
+                        package_json,
+                        target,
+                        subpath_match,
+                        is_imports,
+                    ) {
+                        Ok(Some(path)) => return Ok(Some(path)),
+                        Ok(None) => {}
+                        Err(error @ ResolveError::InvalidPackageTarget { .. }) => {
+                            last_error = Some(error);
+                        }
+                        Err(error) => return Err(error),
+                    }
+                }
+                last_error.map_or(Ok(None), Err)
+            }
+            Value::Null => Ok(None),
+            _ => Err(invalid_target()),
+        }
+    }
+}
+
+/// The name and the subpath of a package specifier, e.g. `@scope/name` and `./path` of
+/// `@scope/name/path`
+pub fn parse_package_specifier(request: &str) -> Option<(&str, String)> {
+    let mut separators = request.match_indices('/').map(|(index, _)| index);
+    let name_end = if request.starts_with('@') { separators.nth(1) } else { separators.next() }
+        .unwrap_or(request.len());
+    let name = &request[..name_end];
+    if name.is_empty()
+        || name.starts_with('.')
+        || name.contains(['\\', '%'])
+        || (name.starts_with('@') && !name.contains('/'))
+    {
+        return None;
+    }
+    Some((name, format!(".{}", &request[name_end..])))
+}
+
+/// The `.`, `..` and `node_modules` segments, which would escape the package
+fn has_invalid_segments(path: &str) -> bool {
+    !path.is_empty()
+        && path.split(['/', '\\']).any(|segment| {
+            segment.is_empty()
+                || segment == "."
+                || segment == ".."
+                || segment.eq_ignore_ascii_case("node_modules")
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{has_invalid_segments, parse_package_specifier};
+
+    #[test]
+    fn package_specifier() {
+        let parse = |request| parse_package_specifier(request);
+        assert_eq!(parse("a"), Some(("a", ".".to_string())));
+        assert_eq!(parse("a/b/c"), Some(("a", "./b/c".to_string())));
+        assert_eq!(parse("@a/b"), Some(("@a/b", ".".to_string())));
+        assert_eq!(parse("@a/b/c"), Some(("@a/b", "./c".to_string())));
+        assert_eq!(parse("@a"), None);
+        assert_eq!(parse(".a"), None);
+    }
+
+    #[test]
+    fn invalid_segments() {
+        assert!(!has_invalid_segments(""));
+        assert!(!has_invalid_segments("a/b.js"));
+        assert!(has_invalid_segments("a/../b"));
+        assert!(has_invalid_segments("a//b"));
+        assert!(has_invalid_segments("Node_Modules/a"));
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    borrow::Cow,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use crate::{cache::FxDashMap, zip::ZipArchive};
+
+/// The file system queries of the resolver, implemented by an in-memory file system for
+/// embedding the resolver, e.g. a virtual file system of an editor or of the tests.
+pub trait FileSystem: Send + Sync {
+    /// # Errors
+    ///
+    /// * The file does not exist or cannot be read
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    /// The metadata of `path`, following symlinks
+    ///
+    /// # Errors
+    ///
+    /// * The path does not exist
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata>;
+
+    /// The path with the symlinks of its components resolved
+    ///
+    /// # Errors
+    ///
+    /// * The path does not exist
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub is_file: bool,
+    pub is_dir: bool,
+    /// The modification time, which tells whether a file has changed since it was cached
+    pub modified: Option<SystemTime>,
+}
+
+impl FileMetadata {
+    pub fn new(is_file: bool, is_dir: bool) -> Self {
+        Self { is_file, is_dir, modified: None }
+    }
+}
+
+impl From<fs::Metadata> for FileMetadata {
+    fn from(metadata: fs::Metadata) -> Self {
+        let modified = metadata.modified().ok();
+        Self { modified, ..Self::new(metadata.is_file(), metadata.is_dir()) }
+    }
+}
+
+/// The file system of the operating system, with the zip archives and the virtual directories
+/// of the Yarn PnP installs
+#[derive(Debug, Default)]
+pub struct FileSystemOs {
+    /// `None` for the archives which cannot be read
+    archives: FxDashMap<PathBuf, Option<Arc<ZipArchive>>>,
+}
+
+impl FileSystemOs {
+    /// The archive containing `path` and the path in the archive, when `path` is not on the disk
+    fn zip_archive(&self, path: &Path) -> Option<(Arc<ZipArchive>, String)> {
+        let (archive_path, name) = crate::zip::split_zip_path(path)?;
+        if let Some(archive) = self.archives.get(archive_path) {
+            return archive.clone().map(|archive| (archive, name));
+        }
+        let archive = ZipArchive::open(archive_path).ok().map(Arc::new);
+        self.archives.insert(archive_path.to_path_buf(), archive.clone());
+        archive.map(|archive| (archive, name))
+    }
+}
+
+impl FileSystem for FileSystemOs {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let path = resolve_virtual_path(path);
+        fs::read_to_string(&path).or_else(|error| match self.zip_archive(&path) {
+            Some((archive, name)) => archive.read_to_string(&name),
+            None => Err(error),
+        })
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let path = resolve_virtual_path(path);
+        fs::metadata(&path).map(FileMetadata::from).or_else(|error| {
+            self.zip_archive(&path).and_then(|(archive, name)| archive.metadata(&name)).ok_or(error)
+        })
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let path = resolve_virtual_path(path);
+        // The paths in the zip archives do not have symlinks
+        fs::canonicalize(&path).or_else(|error| {
+            if self.zip_archive(&path).is_some() {
+                Ok(path.into_owned())
+            } else {
+                Err(error)
+            }
+        })
+    }
+}
+
+/// The real path of a path in the virtual directories of Yarn, which are the copies of the
+/// packages with peer dependencies, e.g. `/.yarn/cache/a.zip` of
+/// `/.yarn/__virtual__/a-virtual-0123/1/.yarn/cache/a.zip`, where `1` is the number of the
+/// parent directories of `/.yarn/__virtual__` to go up to.
+fn resolve_virtual_path(path: &Path) -> Cow<'_, Path> {
+    let mut components = path.components();
+    let mut base = PathBuf::new();
+    while let Some(component) = components.next() {
+        if component.as_os_str() != "__virtual__" {
+            base.push(component);
+            continue;
+        }
+        let (Some(Component::Normal(_)), Some(Component::Normal(depth))) =
+            (components.next(), components.next())
+        else {
+            break;
+        };
+        let Some(depth) = depth.to_str().and_then(|depth| depth.parse::<usize>().ok()) else {
+            break;
+        };
+        for _ in 0..depth {
+            base.pop();
+        }
+        base.extend(components);
+        return Cow::Owned(base);
+    }
+    Cow::Borrowed(path)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::resolve_virtual_path;
+
+    #[test]
+    fn virtual_path() {
+        let resolve = |path| resolve_virtual_path(Path::new(path)).into_owned();
+        assert_eq!(
+            resolve("/app/.yarn/__virtual__/a-virtual-0123/0/cache/a.zip/index.js"),
+            Path::new("/app/.yarn/cache/a.zip/index.js")
+        );
+        assert_eq!(
+            resolve("/app/.yarn/__virtual__/a-virtual-0123/2/packages/a/index.js"),
+            Path::new("/packages/a/index.js")
+        );
+        assert_eq!(resolve("/app/__virtual__/index.js"), Path::new("/app/__virtual__/index.js"));
+    }
+}
@@ -0,0 +1,286 @@
+//! Module Resolution
+//!
+//! The Node.js CommonJS and ES module resolution algorithm, configured like webpack's
+//! [enhanced-resolve].
+//!
+//! References:
+//! * <https://nodejs.org/api/modules.html#all-together>
+//! * <https://nodejs.org/api/esm.html#resolution-algorithm>
+//!
+//! [enhanced-resolve]: https://github.com/webpack/enhanced-resolve
+//!
+//! ```ignore
+//! let resolver = Resolver::new(ResolveOptions::default());
+//! let resolution = resolver.resolve("/path/to/dir", "./module")?;
+//! assert_eq!(resolution.path(), Path::new("/path/to/dir/module.js"));
+//! ```
+
+mod builtins;
+mod cache;
+mod error;
+mod exports;
+mod file_system;
+mod options;
+mod package_json;
+mod path;
+mod pnp;
+mod resolution;
+mod specifier;
+mod tsconfig;
+mod zip;
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{builtins::is_builtin_module, cache::FxDashMap, path::PathUtil, specifier::Specifier};
+pub use crate::{
+    cache::Cache,
+    error::ResolveError,
+    file_system::{FileMetadata, FileSystem, FileSystemOs},
+    options::ResolveOptions,
+    package_json::PackageJson,
+    pnp::PnpManifest,
+    resolution::Resolution,
+    tsconfig::{TsConfig, TsconfigOptions},
+};
+
+/// The resolver of the operating system file system
+pub type Resolver = ResolverGeneric<FileSystemOs>;
+
+/// A resolver over the file system `Fs`.
+///
+/// The file system queries and the `package.json` files are cached, the cache can be shared by
+/// the resolvers of different options with [ResolverGeneric::clone_with_options]. The
+/// resolutions are cached by each resolver until the shared cache is cleared.
+pub struct ResolverGeneric<Fs> {
+    options: ResolveOptions,
+    cache: Arc<Cache<Fs>>,
+    /// The results of [ResolverGeneric::resolve] with the generation of the cache they were
+    /// resolved with, which depend on the options and are not shared
+    resolutions: FxDashMap<(PathBuf, String), (u64, Result<Resolution, ResolveError>)>,
+}
+
+impl<Fs> std::fmt::Debug for ResolverGeneric<Fs> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resolver").field("options", &self.options).finish_non_exhaustive()
+    }
+}
+
+impl<Fs: FileSystem + Default> Default for ResolverGeneric<Fs> {
+    fn default() -> Self {
+        Self::new(ResolveOptions::default())
+    }
+}
+
+impl<Fs: FileSystem + Default> ResolverGeneric<Fs> {
+    pub fn new(options: ResolveOptions) -> Self {
+        Self::new_with_file_system(Fs::default(), options)
+    }
+}
+
+impl<Fs: FileSystem> ResolverGeneric<Fs> {
+    pub fn new_with_file_system(file_system: Fs, options: ResolveOptions) -> Self {
+        Self {
+            options,
+            cache: Arc::new(Cache::new(file_system)),
+            resolutions: FxDashMap::default(),
+        }
+    }
+
+    /// A resolver of `options` which shares the cache of `self`
+    #[must_use]
+    pub fn clone_with_options(&self, options: ResolveOptions) -> Self {
+        Self { options, cache: Arc::clone(&self.cache), resolutions: FxDashMap::default() }
+    }
+
+    pub fn options(&self) -> &ResolveOptions {
+        &self.options
+    }
+
+    /// Clears the cache, e.g. after the file system has changed
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+        self.resolutions.clear();
+    }
+
+    /// Clears the cache when a path which has been queried has changed, see [Cache::refresh]
+    pub fn refresh_cache(&self) -> bool {
+        let changed = self.cache.refresh();
+        if changed {
+            self.resolutions.clear();
+        }
+        changed
+    }
+
+    /// Resolve `specifier` from the absolute path of the `directory` of the importing module.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when no file matches the specifier
+    /// * [ResolveError::Builtin] for the Node.js builtin modules when
+    ///   [ResolveOptions::builtin_modules] is enabled
+    /// * [ResolveError::Json] when a `package.json` or a tsconfig on the way cannot be parsed
+    /// * [ResolveError::PackagePathNotExported] and [ResolveError::PackageImportNotDefined] for
+    ///   the specifiers which are not in the `exports` and the `imports` of their package
+    /// * [ResolveError::InvalidPackageTarget] and [ResolveError::InvalidPackageConfig] for the
+    ///   `exports` and `imports` which are not valid
+    /// * [ResolveError::TsconfigNotFound] and [ResolveError::TsconfigCircularExtend] for the
+    ///   tsconfig files which cannot be loaded
+    pub fn resolve<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<Resolution, ResolveError> {
+        let directory = directory.as_ref();
+        let generation = self.cache.generation();
+        let key = (directory.to_path_buf(), specifier.to_string());
+        if let Some(entry) = self.resolutions.get(&key) {
+            let (resolved_generation, resolution) = &*entry;
+            if *resolved_generation == generation {
+                return resolution.clone();
+            }
+        }
+        let resolution = self.resolve_uncached(directory, specifier);
+        self.resolutions.insert(key, (generation, resolution.clone()));
+        resolution
+    }
+
+    fn resolve_uncached(
+        &self,
+        directory: &Path,
+        specifier: &str,
+    ) -> Result<Resolution, ResolveError> {
+        let Specifier { path: request, query, fragment } = Specifier::parse(specifier)?;
+        let path = self.require(directory, request)?;
+        let path =
+            if self.options.preserve_symlinks { path } else { self.cache.canonicalize(&path) };
+        let package_json = match path.parent() {
+            Some(directory) => self.cache.find_package_json(directory)?,
+            None => None,
+        };
+        Ok(Resolution { path, query, fragment, package_json })
+    }
+
+    /// `require(X)` from a module at path `Y`
+    pub(crate) fn require(&self, directory: &Path, request: &str) -> Result<PathBuf, ResolveError> {
+        if self.options.builtin_modules && is_builtin_module(request) {
+            return Err(ResolveError::Builtin(request.to_string()));
+        }
+        let not_found = || ResolveError::NotFound(request.to_string());
+        let path = Path::new(request);
+        if path.is_absolute() {
+            return self
+                .load_as_file_or_directory(&path.normalize(), request)?
+                .ok_or_else(not_found);
+        }
+        if is_relative(request) {
+            let path = directory.normalize_with(request);
+            return self.load_as_file_or_directory(&path, request)?.ok_or_else(not_found);
+        }
+        if request.starts_with('#') {
+            return self.load_package_imports(directory, request);
+        }
+        if let Some(path) = self.load_package_self(directory, request)? {
+            return Ok(path);
+        }
+        if let Some(path) = self.load_tsconfig_paths(directory, request)? {
+            return Ok(path);
+        }
+        if let Some(path) = self.load_pnp(directory, request)? {
+            return Ok(path);
+        }
+        self.load_node_modules(directory, request)?.ok_or_else(not_found)
+    }
+
+    pub(crate) fn load_as_file_or_directory(
+        &self,
+        path: &Path,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        // `./dir/` only resolves to a directory
+        if !request.ends_with('/') {
+            if let Some(path) = self.load_as_file(path) {
+                return Ok(Some(path));
+            }
+        }
+        self.load_as_directory(path)
+    }
+
+    /// LOAD_AS_FILE(X), `X` itself or `X` with one of the extensions
+    fn load_as_file(&self, path: &Path) -> Option<PathBuf> {
+        if self.cache.is_file(path) {
+            return Some(path.to_path_buf());
+        }
+        self.options.extensions.iter().find_map(|extension| {
+            let mut path_with_extension = path.as_os_str().to_os_string();
+            path_with_extension.push(extension);
+            let path_with_extension = PathBuf::from(path_with_extension);
+            self.cache.is_file(&path_with_extension).then_some(path_with_extension)
+        })
+    }
+
+    /// LOAD_AS_DIRECTORY(X), the main fields of `X/package.json` and then the index files
+    fn load_as_directory(&self, path: &Path) -> Result<Option<PathBuf>, ResolveError> {
+        if !self.cache.is_dir(path) {
+            return Ok(None);
+        }
+        if let Some(package_json) = self.cache.package_json(path)? {
+            for main_field in package_json.main_fields(&self.options.main_fields) {
+                let main_path = path.normalize_with(main_field);
+                if let Some(path) = self.load_as_file(&main_path) {
+                    return Ok(Some(path));
+                }
+                if let Some(path) = self.load_index(&main_path) {
+                    return Ok(Some(path));
+                }
+            }
+        }
+        Ok(self.load_index(path))
+    }
+
+    /// LOAD_INDEX(X)
+    fn load_index(&self, path: &Path) -> Option<PathBuf> {
+        if !self.cache.is_dir(path) {
+            return None;
+        }
+        self.options
+            .main_files
+            .iter()
+            .find_map(|main_file| self.load_as_file(&path.join(main_file)))
+    }
+
+    /// LOAD_NODE_MODULES(X, dirname(Y)), the module directories of `directory` and of its
+    /// ancestors
+    fn load_node_modules(
+        &self,
+        directory: &Path,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        for dir in directory.ancestors() {
+            for module in &self.options.modules {
+                let modules_dir = dir.join(module);
+                if !self.cache.is_dir(&modules_dir) {
+                    continue;
+                }
+                if let Some(path) = self.load_package_exports(&modules_dir, request)? {
+                    return Ok(Some(path));
+                }
+                let path = modules_dir.normalize_with(request);
+                if let Some(path) = self.load_as_file_or_directory(&path, request)? {
+                    return Ok(Some(path));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// `./`, `../`, `.` and `..`
+pub(crate) fn is_relative(request: &str) -> bool {
+    request == "." || request == ".." || request.starts_with("./") || request.starts_with("../")
+}
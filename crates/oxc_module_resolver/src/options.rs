@@ -0,0 +1,71 @@
+use crate::TsconfigOptions;
+
+/// Resolve Options
+///
+/// References:
+/// * <https://github.com/webpack/enhanced-resolve#resolver-options>
+#[derive(Debug, Clone)]
+pub struct ResolveOptions {
+    /// Attempt to resolve these extensions in order, including the leading dot.
+    /// Default: `[".js", ".json", ".node"]`
+    pub extensions: Vec<String>,
+
+    /// The fields of `package.json` which are checked in order for the entry of a directory,
+    /// e.g. `["module", "main"]` for the ES module builds.
+    /// Default: `["main"]`
+    pub main_fields: Vec<String>,
+
+    /// The file names of a directory without a main field, the extensions are attempted on them.
+    /// Default: `["index"]`
+    pub main_files: Vec<String>,
+
+    /// The directory names which are searched in the importing directory and its ancestors for
+    /// the bare specifiers.
+    /// Default: `["node_modules"]`
+    pub modules: Vec<String>,
+
+    /// The conditions of the `exports` and the `imports` of `package.json` which are matched, in
+    /// addition to `default`, e.g. `["node", "import"]` for Node.js ES modules and
+    /// `["browser", "import", "types"]` for a bundler of TypeScript.
+    /// The order of the conditions in the `package.json` decides which target is used.
+    /// Default: `[]`
+    pub condition_names: Vec<String>,
+
+    /// Fail with [crate::ResolveError::Builtin] for the Node.js builtin modules, e.g. `fs` and
+    /// `node:fs`, instead of searching them in the module directories.
+    /// Default: `false`
+    pub builtin_modules: bool,
+
+    /// Keep the symlinks of the resolved paths instead of resolving them to the real paths, like
+    /// `--preserve-symlinks` of Node.js.
+    /// The real paths are the ones whose dependencies are found by the package managers with the
+    /// symlinked `node_modules`, e.g. `node_modules/.pnpm/a@1.0.0/node_modules/a` of pnpm.
+    /// Default: `false`
+    pub preserve_symlinks: bool,
+
+    /// Resolve the packages with the Yarn PnP manifest, `.pnp.data.json` or `.pnp.cjs`, of the
+    /// importing module instead of searching the module directories.
+    /// Default: `true`
+    pub yarn_pnp: bool,
+
+    /// Resolve the bare specifiers with the `paths` and the `baseUrl` of the tsconfig of the
+    /// importing module before searching the module directories.
+    /// Default: `None`
+    pub tsconfig: Option<TsconfigOptions>,
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec![".js".into(), ".json".into(), ".node".into()],
+            main_fields: vec!["main".into()],
+            main_files: vec!["index".into()],
+            modules: vec!["node_modules".into()],
+            condition_names: vec![],
+            builtin_modules: false,
+            preserve_symlinks: false,
+            yarn_pnp: true,
+            tsconfig: None,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::ResolveError;
+
+/// A parsed `package.json`
+#[derive(Debug)]
+pub struct PackageJson {
+    path: PathBuf,
+    name: Option<String>,
+    raw: Value,
+}
+
+impl PackageJson {
+    /// # Errors
+    ///
+    /// * The JSON is not valid, or is not an object
+    pub fn parse(path: PathBuf, json: &str) -> Result<Self, ResolveError> {
+        let raw = match serde_json::from_str::<Value>(json) {
+            Ok(raw) if raw.is_object() => raw,
+            Ok(_) => {
+                let message = "the package.json is not an object".to_string();
+                return Err(ResolveError::Json { path, message });
+            }
+            Err(err) => return Err(ResolveError::Json { path, message: err.to_string() }),
+        };
+        let name = raw.get("name").and_then(Value::as_str).map(ToString::to_string);
+        Ok(Self { path, name, raw })
+    }
+
+    /// The path of the `package.json` file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The directory of the `package.json` file
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(&self.path)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The `package.json` itself, for the fields which are not used by the resolver, e.g. `type`
+    pub fn raw(&self) -> &Value {
+        &self.raw
+    }
+
+    /// The `exports` field, which is `None` when it is `null`
+    pub fn exports(&self) -> Option<&Value> {
+        self.raw.get("exports").filter(|exports| !exports.is_null())
+    }
+
+    /// The `imports` field, when it is an object
+    pub fn imports(&self) -> Option<&serde_json::Map<String, Value>> {
+        self.raw.get("imports").and_then(Value::as_object)
+    }
+
+    /// The string values of `main_fields`, in order
+    pub fn main_fields<'a>(&'a self, main_fields: &'a [String]) -> impl Iterator<Item = &'a str> {
+        main_fields.iter().filter_map(|field| self.raw.get(field).and_then(Value::as_str))
+    }
+}
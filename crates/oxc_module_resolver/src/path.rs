@@ -0,0 +1,49 @@
+use std::path::{Component, Path, PathBuf};
+
+pub trait PathUtil {
+    /// Removes the `.` and `..` components without reading the file system
+    fn normalize(&self) -> PathBuf;
+
+    /// `self.join(subpath).normalize()`
+    fn normalize_with<P: AsRef<Path>>(&self, subpath: P) -> PathBuf;
+}
+
+impl PathUtil for Path {
+    fn normalize(&self) -> PathBuf {
+        let mut normalized = PathBuf::new();
+        for component in self.components() {
+            match component {
+                Component::CurDir => {}
+                // `/..` is `/`, the leading `..` of a relative path are kept
+                Component::ParentDir => match normalized.components().next_back() {
+                    Some(Component::Normal(_)) => {
+                        normalized.pop();
+                    }
+                    Some(Component::RootDir | Component::Prefix(_)) => {}
+                    _ => normalized.push(component),
+                },
+                _ => normalized.push(component),
+            }
+        }
+        normalized
+    }
+
+    fn normalize_with<P: AsRef<Self>>(&self, subpath: P) -> PathBuf {
+        self.join(subpath).normalize()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::PathUtil;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(Path::new("/a/./b/../c").normalize(), Path::new("/a/c"));
+        assert_eq!(Path::new("/a").normalize_with("../../b"), Path::new("/b"));
+        assert_eq!(Path::new("/a/b").normalize_with("./c/"), Path::new("/a/b/c"));
+        assert_eq!(Path::new("../../a/..").normalize(), Path::new("../.."));
+    }
+}
@@ -0,0 +1,234 @@
+//! Yarn Plug'n'Play
+//!
+//! The packages of a PnP install are not in `node_modules`, the manifest in `.pnp.data.json` or
+//! in `.pnp.cjs` maps the packages to their dependencies and to their locations.
+//!
+//! References:
+//! * <https://yarnpkg.com/advanced/pnp-spec>
+
+use std::path::{Path, PathBuf};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::Value;
+
+use crate::{
+    exports::parse_package_specifier, path::PathUtil, FileSystem, ResolveError, ResolverGeneric,
+};
+
+/// The name and the reference of a package, e.g. `lodash` and `npm:4.17.21`. The top-level
+/// package is the empty name and reference.
+type Locator = (String, String);
+
+/// A Yarn PnP manifest
+#[derive(Debug)]
+pub struct PnpManifest {
+    enable_top_level_fallback: bool,
+    fallback_exclusion_list: FxHashSet<Locator>,
+    fallback_pool: FxHashMap<String, Option<Locator>>,
+    packages: FxHashMap<Locator, PackageInformation>,
+    /// The absolute package locations, the longest first, for the lookup of the package which
+    /// contains a path
+    locations: Vec<(PathBuf, Locator)>,
+}
+
+#[derive(Debug)]
+struct PackageInformation {
+    location: PathBuf,
+    /// `None` for the peer dependencies which are not provided
+    dependencies: FxHashMap<String, Option<Locator>>,
+}
+
+impl PnpManifest {
+    /// Parse the manifest of `.pnp.data.json`, the locations are relative to `directory`
+    fn parse(directory: &Path, raw: &Value) -> Self {
+        let locator = |name: &Value, reference: &Value| {
+            let string = |value: &Value| value.as_str().unwrap_or_default().to_string();
+            (string(name), string(reference))
+        };
+        // A dependency is a reference, an alias with its name and reference, or `null`
+        let dependency = |name: &Value, target: &Value| match target {
+            Value::String(_) => Some(locator(name, target)),
+            Value::Array(alias) => Some(locator(alias.first()?, alias.get(1)?)),
+            _ => None,
+        };
+
+        let mut packages = FxHashMap::default();
+        for (name, references) in pairs(raw.get("packageRegistryData")) {
+            for (reference, information) in pairs(Some(references)) {
+                let location =
+                    information.get("packageLocation").and_then(Value::as_str).map_or_else(
+                        || directory.to_path_buf(),
+                        |location| directory.normalize_with(location),
+                    );
+                let dependencies = pairs(information.get("packageDependencies"))
+                    .map(|(dependency_name, target)| {
+                        let name = dependency_name.as_str().unwrap_or_default().to_string();
+                        (name, dependency(dependency_name, target))
+                    })
+                    .collect();
+                packages.insert(
+                    locator(name, reference),
+                    PackageInformation { location, dependencies },
+                );
+            }
+        }
+
+        let mut locations = packages
+            .iter()
+            .map(|(locator, package)| (package.location.clone(), locator.clone()))
+            .collect::<Vec<_>>();
+        locations.sort_unstable_by(|(a, _), (b, _)| b.as_os_str().len().cmp(&a.as_os_str().len()));
+
+        let fallback_exclusion_list = pairs(raw.get("fallbackExclusionList"))
+            .flat_map(|(name, references)| {
+                references
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(move |reference| locator(name, reference))
+            })
+            .collect();
+        let fallback_pool = pairs(raw.get("fallbackPool"))
+            .map(|(name, target)| {
+                (name.as_str().unwrap_or_default().to_string(), dependency(name, target))
+            })
+            .collect();
+        let enable_top_level_fallback =
+            raw.get("enableTopLevelFallback").and_then(Value::as_bool).unwrap_or_default();
+
+        Self {
+            enable_top_level_fallback,
+            fallback_exclusion_list,
+            fallback_pool,
+            packages,
+            locations,
+        }
+    }
+
+    /// Parse the manifest of `.pnp.data.json` or the manifest embedded in `.pnp.cjs`
+    ///
+    /// # Errors
+    ///
+    /// * The manifest is not valid JSON, or the manifest of `.pnp.cjs` cannot be found
+    pub fn from_file(path: &Path, content: &str) -> Result<Self, ResolveError> {
+        let json_error = |message: String| ResolveError::Json { path: path.to_path_buf(), message };
+        let json = if path.extension().is_some_and(|extension| extension == "cjs") {
+            extract_runtime_state(content)
+                .ok_or_else(|| json_error("the PnP runtime state is not found".to_string()))?
+        } else {
+            content.to_string()
+        };
+        let raw =
+            serde_json::from_str::<Value>(&json).map_err(|err| json_error(err.to_string()))?;
+        let directory = path.parent().unwrap_or(path);
+        Ok(Self::parse(directory, &raw))
+    }
+
+    /// The package which contains `path`
+    fn find_locator(&self, path: &Path) -> Option<&Locator> {
+        self.locations
+            .iter()
+            .find(|(location, _)| path.starts_with(location))
+            .map(|(_, locator)| locator)
+    }
+
+    /// The location of the dependency `name` of the package which contains `directory`
+    fn resolve_dependency(&self, directory: &Path, name: &str) -> Dependency {
+        let Some(issuer_locator) = self.find_locator(directory) else {
+            return Dependency::Unmanaged;
+        };
+        let Some(issuer) = self.packages.get(issuer_locator) else {
+            return Dependency::Unmanaged;
+        };
+        let dependency = issuer.dependencies.get(name).cloned().or_else(|| {
+            let fallback = self.enable_top_level_fallback
+                && !self.fallback_exclusion_list.contains(issuer_locator);
+            fallback.then(|| self.fallback_pool.get(name).cloned()).flatten()
+        });
+        dependency
+            .flatten()
+            .and_then(|locator| self.packages.get(&locator))
+            .map_or(Dependency::Undeclared, |package| Dependency::Location(&package.location))
+    }
+}
+
+enum Dependency<'a> {
+    /// The importing module is not in a package of the manifest
+    Unmanaged,
+    /// The dependency is not declared, or is a peer dependency which is not provided
+    Undeclared,
+    Location(&'a Path),
+}
+
+/// The entries of the arrays of pairs, which are the maps of the manifest
+fn pairs(value: Option<&Value>) -> impl Iterator<Item = (&Value, &Value)> {
+    value.and_then(Value::as_array).into_iter().flatten().filter_map(|pair| {
+        let pair = pair.as_array()?;
+        Some((pair.first()?, pair.get(1)?))
+    })
+}
+
+/// The JSON of the runtime state in `.pnp.cjs`, which is a string literal passed to `JSON.parse`
+fn extract_runtime_state(content: &str) -> Option<String> {
+    let start = content.find("RAW_RUNTIME_STATE").or_else(|| content.find("JSON.parse("))?;
+    let quote_start = start + content[start..].find(['\'', '"'])?;
+    let quote = content[quote_start..].chars().next()?;
+    let mut json = String::new();
+    let mut chars = content[quote_start + 1..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => json.push('\n'),
+                't' => json.push('\t'),
+                'r' => json.push('\r'),
+                // A line continuation
+                '\n' => {}
+                c => json.push(c),
+            },
+            c if c == quote => return Some(json),
+            c => json.push(c),
+        }
+    }
+    None
+}
+
+impl<Fs: FileSystem> ResolverGeneric<Fs> {
+    /// The package of `request` in the Yarn PnP manifest of `directory`, `None` when there is no
+    /// manifest or when the importing module is not in a package of the manifest
+    pub(crate) fn load_pnp(
+        &self,
+        directory: &Path,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        if !self.options.yarn_pnp {
+            return Ok(None);
+        }
+        let Some((name, subpath)) = parse_package_specifier(request) else { return Ok(None) };
+        let Some(manifest) = self.cache.find_pnp_manifest(directory)? else { return Ok(None) };
+        let not_found = || ResolveError::NotFound(request.to_string());
+        let package_dir = match manifest.resolve_dependency(directory, name) {
+            Dependency::Unmanaged => return Ok(None),
+            Dependency::Undeclared => return Err(not_found()),
+            Dependency::Location(package_dir) => package_dir,
+        };
+        if let Some(path) = self.load_package_dir_exports(package_dir, &subpath, request)? {
+            return Ok(Some(path));
+        }
+        let path = package_dir.normalize_with(&subpath);
+        self.load_as_file_or_directory(&path, request)?.ok_or_else(not_found).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::extract_runtime_state;
+
+    #[test]
+    fn runtime_state() {
+        let content = "#!/usr/bin/env node\n/* eslint-disable */\n\"use strict\";\n\nfunction $$SETUP_STATE(hydrateRuntimeState, basePath) {\n  return hydrateRuntimeState(JSON.parse('{\\\n  \"__info\": [\"it\\'s\"],\\\n  \"a\": \"\\\\\\\\b\"\\\n}'), {basePath: basePath || __dirname});\n}\n";
+        let json = extract_runtime_state(content).unwrap();
+        assert_eq!(json, "{  \"__info\": [\"it's\"],  \"a\": \"\\\\b\"}");
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(value["a"], "\\b");
+    }
+}
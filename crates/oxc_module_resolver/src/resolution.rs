@@ -0,0 +1,61 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::PackageJson;
+
+/// The result of a resolution
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub(crate) path: PathBuf,
+    /// The query of the specifier, including the leading `?`
+    pub(crate) query: Option<String>,
+    /// The fragment of the specifier, including the leading `#`
+    pub(crate) fragment: Option<String>,
+    /// The nearest `package.json` of the resolved file
+    pub(crate) package_json: Option<Arc<PackageJson>>,
+}
+
+impl Resolution {
+    /// The resolved file, without the query and the fragment
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn into_path_buf(self) -> PathBuf {
+        self.path
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    pub fn package_json(&self) -> Option<&Arc<PackageJson>> {
+        self.package_json.as_ref()
+    }
+
+    /// The resolved file with the query and the fragment
+    pub fn full_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        if let Some(query) = &self.query {
+            path.push(query);
+        }
+        if let Some(fragment) = &self.fragment {
+            path.push(fragment);
+        }
+        PathBuf::from(path)
+    }
+}
+
+impl PartialEq for Resolution {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.query == other.query && self.fragment == other.fragment
+    }
+}
+
+impl Eq for Resolution {}
@@ -0,0 +1,52 @@
+use crate::ResolveError;
+
+/// A specifier split into the request and its query and fragment, e.g. `./a.js?raw#b`
+#[derive(Debug, PartialEq, Eq)]
+pub struct Specifier<'a> {
+    pub path: &'a str,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+
+impl<'a> Specifier<'a> {
+    pub fn parse(specifier: &'a str) -> Result<Self, ResolveError> {
+        if specifier.is_empty() {
+            return Err(ResolveError::EmptySpecifier);
+        }
+        // A leading `#` is the specifier of a package import, not a fragment
+        let split = specifier.char_indices().skip(1).find(|(_, c)| matches!(c, '?' | '#'));
+        let Some((index, _)) = split else {
+            return Ok(Self { path: specifier, query: None, fragment: None });
+        };
+        let (path, rest) = specifier.split_at(index);
+        let (query, fragment) = match rest.find('#') {
+            Some(0) => (None, Some(rest)),
+            Some(i) => (Some(&rest[..i]), Some(&rest[i..])),
+            None => (Some(rest), None),
+        };
+        Ok(Self {
+            path,
+            query: query.map(ToString::to_string),
+            fragment: fragment.map(ToString::to_string),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Specifier;
+    use crate::ResolveError;
+
+    #[test]
+    fn parse() {
+        let parse = |s| Specifier::parse(s).unwrap();
+        assert_eq!(parse("./a").path, "./a");
+        let specifier = parse("./a.js?raw#b");
+        assert_eq!(specifier.path, "./a.js");
+        assert_eq!(specifier.query.as_deref(), Some("?raw"));
+        assert_eq!(specifier.fragment.as_deref(), Some("#b"));
+        assert_eq!(parse("a#b?c").fragment.as_deref(), Some("#b?c"));
+        assert_eq!(parse("#a").path, "#a");
+        assert_eq!(Specifier::parse(""), Err(ResolveError::EmptySpecifier));
+    }
+}
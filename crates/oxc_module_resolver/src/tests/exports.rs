@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use super::{resolve, resolver};
+use crate::{ResolveError, ResolveOptions};
+
+fn options(condition_names: &[&str]) -> ResolveOptions {
+    ResolveOptions {
+        condition_names: condition_names.iter().map(ToString::to_string).collect(),
+        ..ResolveOptions::default()
+    }
+}
+
+const PACKAGE: &str = r#"{
+    "name": "pkg",
+    "main": "./main.js",
+    "exports": {
+        ".": {
+            "types": "./index.d.ts",
+            "import": "./index.mjs",
+            "require": "./index.cjs",
+            "default": "./index.js"
+        },
+        "./feature": { "browser": "./feature-browser.js", "default": "./feature.js" },
+        "./lib/*": "./src/*.js",
+        "./lib/*.css": "./styles/*.css",
+        "./internal/*": null,
+        "./dir/": "./folder/",
+        "./fallback": ["invalid:target", "./fallback.js"],
+        "./package.json": "./package.json"
+    }
+}"#;
+
+fn package_files() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("/node_modules/pkg/package.json", PACKAGE),
+        ("/node_modules/pkg/index.d.ts", ""),
+        ("/node_modules/pkg/index.mjs", ""),
+        ("/node_modules/pkg/index.cjs", ""),
+        ("/node_modules/pkg/index.js", ""),
+        ("/node_modules/pkg/main.js", ""),
+        ("/node_modules/pkg/feature.js", ""),
+        ("/node_modules/pkg/feature-browser.js", ""),
+        ("/node_modules/pkg/src/a.js", ""),
+        ("/node_modules/pkg/src/internal/b.js", ""),
+        ("/node_modules/pkg/styles/c.css", ""),
+        ("/node_modules/pkg/folder/d.js", ""),
+        ("/node_modules/pkg/fallback.js", ""),
+        ("/node_modules/pkg/other.js", ""),
+    ]
+}
+
+#[test]
+fn conditions() {
+    let files = package_files();
+    let pass = [
+        (&[][..], "pkg", "index.js"),
+        (&["import"][..], "pkg", "index.mjs"),
+        (&["require"][..], "pkg", "index.cjs"),
+        // The order of the conditions in the package decides
+        (&["require", "import"][..], "pkg", "index.mjs"),
+        (&["types", "import"][..], "pkg", "index.d.ts"),
+        (&[][..], "pkg/feature", "feature.js"),
+        (&["browser"][..], "pkg/feature", "feature-browser.js"),
+    ];
+    for (conditions, specifier, expected) in pass {
+        let resolver = resolver(&files, options(conditions));
+        let expected = PathBuf::from(format!("/node_modules/pkg/{expected}"));
+        assert_eq!(resolve(&resolver, "/", specifier), Ok(expected), "{specifier} {conditions:?}");
+    }
+}
+
+#[test]
+fn subpaths() {
+    let resolver = resolver(&package_files(), options(&[]));
+    let pass = [
+        ("pkg/lib/a", "src/a.js"),
+        ("pkg/lib/internal/b", "src/internal/b.js"),
+        // The pattern with the longest prefix wins
+        ("pkg/lib/c.css", "styles/c.css"),
+        ("pkg/dir/d.js", "folder/d.js"),
+        ("pkg/fallback", "fallback.js"),
+        ("pkg/package.json", "package.json"),
+    ];
+    for (specifier, expected) in pass {
+        let expected = PathBuf::from(format!("/node_modules/pkg/{expected}"));
+        assert_eq!(resolve(&resolver, "/", specifier), Ok(expected), "{specifier}");
+    }
+
+    let not_exported = |subpath: &str| ResolveError::PackagePathNotExported {
+        subpath: subpath.to_string(),
+        package_json: PathBuf::from("/node_modules/pkg/package.json"),
+    };
+    assert_eq!(resolve(&resolver, "/", "pkg/other.js"), Err(not_exported("./other.js")));
+    assert_eq!(resolve(&resolver, "/", "pkg/internal/x"), Err(not_exported("./internal/x")));
+    // The targets are not completed with the extensions
+    assert_eq!(
+        resolve(&resolver, "/", "pkg/lib/x"),
+        Err(ResolveError::NotFound("pkg/lib/x".into()))
+    );
+}
+
+#[test]
+fn sugar_and_self_reference() {
+    let resolver = resolver(
+        &[
+            ("/node_modules/a/package.json", r#"{ "exports": "./a.js" }"#),
+            ("/node_modules/a/a.js", ""),
+            ("/node_modules/b/package.json", r#"{ "exports": { "import": "./b.mjs" } }"#),
+            ("/node_modules/b/b.mjs", ""),
+            (
+                "/node_modules/c/package.json",
+                r#"{ "exports": { ".": "./c.js", "import": "./c.mjs" } }"#,
+            ),
+            ("/node_modules/d/package.json", r#"{ "exports": { ".": "../d.js" } }"#),
+            ("/app/package.json", r#"{ "name": "app", "exports": { "./util": "./src/util.js" } }"#),
+            ("/app/src/util.js", ""),
+        ],
+        options(&["import"]),
+    );
+    assert_eq!(resolve(&resolver, "/", "a"), Ok(PathBuf::from("/node_modules/a/a.js")));
+    assert_eq!(resolve(&resolver, "/", "b"), Ok(PathBuf::from("/node_modules/b/b.mjs")));
+    assert_eq!(resolve(&resolver, "/app/src", "app/util"), Ok(PathBuf::from("/app/src/util.js")));
+    assert_eq!(
+        resolve(&resolver, "/", "c"),
+        Err(ResolveError::InvalidPackageConfig(PathBuf::from("/node_modules/c/package.json")))
+    );
+    assert_eq!(
+        resolve(&resolver, "/", "d"),
+        Err(ResolveError::InvalidPackageTarget {
+            target: "\"../d.js\"".into(),
+            package_json: PathBuf::from("/node_modules/d/package.json")
+        })
+    );
+}
+
+#[test]
+fn imports() {
+    let package = r##"{
+        "imports": {
+            "#dep": { "node": "dep-node", "default": "./dep-polyfill.js" },
+            "#internal/*": "./src/internal/*.js",
+            "#null": null
+        }
+    }"##;
+    let files = [
+        ("/app/package.json", package),
+        ("/app/dep-polyfill.js", ""),
+        ("/app/src/internal/a.js", ""),
+        ("/app/node_modules/dep-node/index.js", ""),
+    ];
+    let resolver = resolver(&files, options(&[]));
+    assert_eq!(resolve(&resolver, "/app/src", "#dep"), Ok(PathBuf::from("/app/dep-polyfill.js")));
+    assert_eq!(
+        resolve(&resolver, "/app/src", "#internal/a"),
+        Ok(PathBuf::from("/app/src/internal/a.js"))
+    );
+    let not_defined = |specifier: &str| ResolveError::PackageImportNotDefined {
+        specifier: specifier.into(),
+        package_json: PathBuf::from("/app/package.json"),
+    };
+    assert_eq!(resolve(&resolver, "/app", "#null"), Err(not_defined("#null")));
+    assert_eq!(resolve(&resolver, "/app", "#missing"), Err(not_defined("#missing")));
+    assert_eq!(
+        resolve(&resolver, "/app", "#/a"),
+        Err(ResolveError::InvalidModuleSpecifier("#/a".into()))
+    );
+
+    // The bare targets are resolved from the package
+    let resolver = resolver.clone_with_options(options(&["node"]));
+    assert_eq!(
+        resolve(&resolver, "/app/src", "#dep"),
+        Ok(PathBuf::from("/app/node_modules/dep-node/index.js"))
+    );
+}
@@ -0,0 +1,268 @@
+mod exports;
+mod pnp;
+mod tsconfig;
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{FileMetadata, FileSystem, ResolveError, ResolveOptions, ResolverGeneric};
+
+/// A file system of the files in memory, the directories are the ancestors of the files. The
+/// clones share the files.
+#[derive(Default, Clone)]
+pub struct MemoryFileSystem {
+    files: Arc<RwLock<FxHashMap<PathBuf, String>>>,
+    /// The symlinks to their targets
+    symlinks: FxHashMap<PathBuf, PathBuf>,
+}
+
+impl MemoryFileSystem {
+    pub fn new(files: &[(&str, &str)]) -> Self {
+        let files = files.iter().map(|&(path, content)| (PathBuf::from(path), content.to_string()));
+        Self { files: Arc::new(RwLock::new(files.collect())), symlinks: FxHashMap::default() }
+    }
+
+    #[must_use]
+    pub fn with_symlinks(mut self, symlinks: &[(&str, &str)]) -> Self {
+        self.symlinks = symlinks
+            .iter()
+            .map(|&(path, target)| (PathBuf::from(path), PathBuf::from(target)))
+            .collect();
+        self
+    }
+
+    pub fn write(&self, path: &str, content: &str) {
+        self.files.write().unwrap().insert(PathBuf::from(path), content.to_string());
+    }
+
+    fn real_path(&self, path: &Path) -> PathBuf {
+        let Some((link, target)) = self.symlinks.iter().find(|(link, _)| path.starts_with(link))
+        else {
+            return path.to_path_buf();
+        };
+        self.real_path(&target.join(path.strip_prefix(link).unwrap()))
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let files = self.files.read().unwrap();
+        files.get(&self.real_path(path)).cloned().ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let path = self.real_path(path);
+        let (is_file, is_dir) = {
+            let files = self.files.read().unwrap();
+            (files.contains_key(&path), files.keys().any(|file| file.starts_with(&path)))
+        };
+        if is_file || is_dir {
+            return Ok(FileMetadata::new(is_file, !is_file));
+        }
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.metadata(path).map(|_| self.real_path(path))
+    }
+}
+
+fn resolver(files: &[(&str, &str)], options: ResolveOptions) -> ResolverGeneric<MemoryFileSystem> {
+    ResolverGeneric::new_with_file_system(MemoryFileSystem::new(files), options)
+}
+
+fn resolve(
+    resolver: &ResolverGeneric<MemoryFileSystem>,
+    directory: &str,
+    specifier: &str,
+) -> Result<PathBuf, ResolveError> {
+    resolver.resolve(directory, specifier).map(|resolution| resolution.full_path())
+}
+
+#[test]
+fn relative() {
+    let resolver = resolver(
+        &[("/a/b.js", ""), ("/a/c.json", ""), ("/a/d/index.js", ""), ("/a/e.js/index.js", "")],
+        ResolveOptions::default(),
+    );
+    let pass = [
+        ("./b", "/a/b.js"),
+        ("./b.js", "/a/b.js"),
+        ("./c", "/a/c.json"),
+        ("./d", "/a/d/index.js"),
+        ("./d/", "/a/d/index.js"),
+        ("../a/b", "/a/b.js"),
+        ("./e.js/", "/a/e.js/index.js"),
+        ("/a/b", "/a/b.js"),
+        (".", "/a/d/index.js"),
+    ];
+    for (specifier, expected) in pass {
+        let directory = if specifier == "." { "/a/d" } else { "/a" };
+        assert_eq!(
+            resolve(&resolver, directory, specifier),
+            Ok(PathBuf::from(expected)),
+            "{specifier}"
+        );
+    }
+    assert_eq!(resolve(&resolver, "/a", "./f"), Err(ResolveError::NotFound("./f".into())));
+    assert_eq!(resolve(&resolver, "/a", "./b.js/"), Err(ResolveError::NotFound("./b.js/".into())));
+    assert_eq!(resolve(&resolver, "/a", ""), Err(ResolveError::EmptySpecifier));
+}
+
+#[test]
+fn extensions() {
+    let options = ResolveOptions {
+        extensions: vec![".ts".into(), ".js".into()],
+        ..ResolveOptions::default()
+    };
+    let resolver = resolver(&[("/a.js", ""), ("/a.ts", ""), ("/b.json", "")], options);
+    assert_eq!(resolve(&resolver, "/", "./a"), Ok(PathBuf::from("/a.ts")));
+    assert_eq!(resolve(&resolver, "/", "./b"), Err(ResolveError::NotFound("./b".into())));
+}
+
+#[test]
+fn query_and_fragment() {
+    let resolver = resolver(&[("/a.js", ""), ("/b#c.js", "")], ResolveOptions::default());
+    let resolution = resolver.resolve("/", "./a?raw#b").unwrap();
+    assert_eq!(resolution.path(), Path::new("/a.js"));
+    assert_eq!(resolution.query(), Some("?raw"));
+    assert_eq!(resolution.fragment(), Some("#b"));
+    assert_eq!(resolution.full_path(), PathBuf::from("/a.js?raw#b"));
+}
+
+#[test]
+fn node_modules() {
+    let resolver = resolver(
+        &[
+            ("/node_modules/a/index.js", ""),
+            ("/node_modules/b/package.json", r#"{ "name": "b", "main": "lib/main" }"#),
+            ("/node_modules/b/lib/main.js", ""),
+            ("/node_modules/b/sub.js", ""),
+            ("/x/node_modules/a/package.json", r#"{ "main": "./dist" }"#),
+            ("/x/node_modules/a/dist/index.js", ""),
+            ("/x/y/z.js", ""),
+        ],
+        ResolveOptions::default(),
+    );
+    let pass = [
+        ("/", "a", "/node_modules/a/index.js"),
+        ("/x/y", "a", "/x/node_modules/a/dist/index.js"),
+        ("/x/y", "b", "/node_modules/b/lib/main.js"),
+        ("/x/y", "b/sub", "/node_modules/b/sub.js"),
+    ];
+    for (directory, specifier, expected) in pass {
+        assert_eq!(
+            resolve(&resolver, directory, specifier),
+            Ok(PathBuf::from(expected)),
+            "{specifier}"
+        );
+    }
+    assert_eq!(resolve(&resolver, "/", "c"), Err(ResolveError::NotFound("c".into())));
+
+    let resolution = resolver.resolve("/x/y", "b/sub").unwrap();
+    let package_json = resolution.package_json().unwrap();
+    assert_eq!(package_json.name(), Some("b"));
+    assert_eq!(package_json.directory(), Path::new("/node_modules/b"));
+}
+
+#[test]
+fn main_fields() {
+    let files = [
+        ("/node_modules/a/package.json", r#"{ "main": "main.js", "module": "module.js" }"#),
+        ("/node_modules/a/main.js", ""),
+        ("/node_modules/a/module.js", ""),
+        ("/node_modules/b/package.json", r#"{ "main": "missing.js" }"#),
+        ("/node_modules/b/index.js", ""),
+    ];
+    let resolver = resolver(&files, ResolveOptions::default());
+    assert_eq!(resolve(&resolver, "/", "a"), Ok(PathBuf::from("/node_modules/a/main.js")));
+    assert_eq!(resolve(&resolver, "/", "b"), Ok(PathBuf::from("/node_modules/b/index.js")));
+
+    let options = ResolveOptions {
+        main_fields: vec!["module".into(), "main".into()],
+        ..ResolveOptions::default()
+    };
+    let resolver = resolver.clone_with_options(options);
+    assert_eq!(resolve(&resolver, "/", "a"), Ok(PathBuf::from("/node_modules/a/module.js")));
+}
+
+#[test]
+fn builtin_modules() {
+    let resolver = resolver(&[("/node_modules/fs/index.js", "")], ResolveOptions::default());
+    assert_eq!(resolve(&resolver, "/", "fs"), Ok(PathBuf::from("/node_modules/fs/index.js")));
+
+    let resolver = resolver
+        .clone_with_options(ResolveOptions { builtin_modules: true, ..ResolveOptions::default() });
+    for specifier in ["fs", "fs/promises", "node:fs", "node:test"] {
+        assert_eq!(
+            resolve(&resolver, "/", specifier),
+            Err(ResolveError::Builtin(specifier.into()))
+        );
+    }
+}
+
+#[test]
+fn invalid_package_json() {
+    let resolver = resolver(
+        &[("/node_modules/a/package.json", "{"), ("/node_modules/a/index.js", "")],
+        ResolveOptions::default(),
+    );
+    assert!(matches!(
+        resolve(&resolver, "/", "a"),
+        Err(ResolveError::Json { path, .. }) if path == Path::new("/node_modules/a/package.json")
+    ));
+}
+
+#[test]
+fn symlinks() {
+    let fs = MemoryFileSystem::new(&[
+        ("/node_modules/.pnpm/a@1.0.0/node_modules/a/index.js", ""),
+        ("/node_modules/.pnpm/a@1.0.0/node_modules/b/index.js", ""),
+    ])
+    .with_symlinks(&[
+        ("/node_modules/a", "/node_modules/.pnpm/a@1.0.0/node_modules/a"),
+        (
+            "/node_modules/.pnpm/a@1.0.0/node_modules/b",
+            "/node_modules/.pnpm/b@1.0.0/node_modules/b",
+        ),
+    ]);
+    fs.write("/node_modules/.pnpm/b@1.0.0/node_modules/b/index.js", "");
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    let a = resolver.resolve("/", "a").unwrap();
+    assert_eq!(a.path(), Path::new("/node_modules/.pnpm/a@1.0.0/node_modules/a/index.js"));
+    // The dependencies of `a` are next to its real path
+    let b = resolver.resolve(a.path().parent().unwrap(), "b").unwrap();
+    assert_eq!(b.path(), Path::new("/node_modules/.pnpm/b@1.0.0/node_modules/b/index.js"));
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        preserve_symlinks: true,
+        ..ResolveOptions::default()
+    });
+    let a = resolver.resolve("/", "a").unwrap();
+    assert_eq!(a.path(), Path::new("/node_modules/a/index.js"));
+}
+
+#[test]
+fn refresh_cache() {
+    let fs = MemoryFileSystem::new(&[("/a.js", "")]);
+    let resolver = ResolverGeneric::new_with_file_system(fs.clone(), ResolveOptions::default());
+    assert_eq!(resolve(&resolver, "/", "./b"), Err(ResolveError::NotFound("./b".into())));
+    // The resolution is cached until the file system is refreshed
+    fs.write("/b.js", "");
+    assert_eq!(resolve(&resolver, "/", "./b"), Err(ResolveError::NotFound("./b".into())));
+    assert!(resolver.refresh_cache());
+    assert_eq!(resolve(&resolver, "/", "./b"), Ok(PathBuf::from("/b.js")));
+    assert!(!resolver.refresh_cache());
+
+    // The resolvers sharing the cache are refreshed together
+    let other = resolver.clone_with_options(ResolveOptions::default());
+    assert_eq!(resolve(&other, "/", "./c"), Err(ResolveError::NotFound("./c".into())));
+    fs.write("/c.js", "");
+    assert!(resolver.refresh_cache());
+    assert_eq!(resolve(&other, "/", "./c"), Ok(PathBuf::from("/c.js")));
+}
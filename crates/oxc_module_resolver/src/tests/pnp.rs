@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use crate::{Resolution, ResolveError, ResolveOptions, Resolver};
+
+fn fixture() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/pnp")
+}
+
+#[test]
+fn pnp() {
+    let fixture = fixture();
+    let lodash = fixture
+        .join(".yarn/cache/lodash-npm-4.17.21-6382451519-eb835a2e51.zip/node_modules/lodash");
+    let exports_pkg =
+        fixture.join(".yarn/unplugged/exports-pkg-npm-1.0.0/node_modules/exports-pkg");
+    let workspace_a = fixture.join("packages/a");
+    let resolver = Resolver::new(ResolveOptions::default());
+    let pass = [
+        // The files in the zip archives of the cache
+        (fixture.as_path(), "lodash", lodash.join("lodash.js")),
+        (fixture.as_path(), "lodash/fp", lodash.join("fp/index.js")),
+        (fixture.as_path(), "workspace-a", workspace_a.join("index.js")),
+        (workspace_a.as_path(), "exports-pkg", exports_pkg.join("index.cjs")),
+        // The top-level dependencies are the fallback of the undeclared dependencies
+        (workspace_a.as_path(), "lodash/lodash.js", lodash.join("lodash.js")),
+    ];
+    for (directory, specifier, expected) in pass {
+        assert_eq!(
+            resolver.resolve(directory, specifier).map(Resolution::into_path_buf),
+            Ok(expected)
+        );
+    }
+
+    let resolution = resolver.resolve(&fixture, "lodash").unwrap();
+    let package_json = resolution.package_json().unwrap();
+    assert_eq!(package_json.name(), Some("lodash"));
+    assert_eq!(package_json.directory(), lodash);
+
+    // `exports-pkg` is excluded from the fallback
+    assert_eq!(
+        resolver.resolve(&exports_pkg, "lodash"),
+        Err(ResolveError::NotFound("lodash".into()))
+    );
+    assert_eq!(
+        resolver.resolve(&workspace_a, "undeclared"),
+        Err(ResolveError::NotFound("undeclared".into()))
+    );
+}
+
+#[test]
+fn disabled() {
+    let resolver = Resolver::new(ResolveOptions { yarn_pnp: false, ..ResolveOptions::default() });
+    assert_eq!(resolver.resolve(fixture(), "lodash"), Err(ResolveError::NotFound("lodash".into())));
+}
@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+
+use super::{resolve, resolver};
+use crate::{ResolveError, ResolveOptions, TsconfigOptions};
+
+fn options(references: bool) -> ResolveOptions {
+    let tsconfig = TsconfigOptions { config_file: None, references };
+    ResolveOptions {
+        extensions: vec![".ts".into(), ".js".into()],
+        tsconfig: Some(tsconfig),
+        ..ResolveOptions::default()
+    }
+}
+
+#[test]
+fn paths() {
+    let tsconfig = r#"{
+        // jsonc
+        "compilerOptions": {
+            "paths": {
+                "@/*": ["./src/*", "./generated/*"],
+                "@/components/*": ["./src/ui/*"],
+                "config": ["./src/config.ts"],
+            },
+        },
+    }"#;
+    let resolver = resolver(
+        &[
+            ("/app/tsconfig.json", tsconfig),
+            ("/app/src/a.ts", ""),
+            ("/app/generated/b.ts", ""),
+            ("/app/src/ui/button.ts", ""),
+            ("/app/src/config.ts", ""),
+            ("/app/node_modules/@/c/index.js", ""),
+            ("/app/src/nested/d.ts", ""),
+        ],
+        options(false),
+    );
+    let pass = [
+        ("@/a", "/app/src/a.ts"),
+        ("@/b", "/app/generated/b.ts"),
+        ("@/components/button", "/app/src/ui/button.ts"),
+        ("config", "/app/src/config.ts"),
+        // None of the targets exists
+        ("@/c", "/app/node_modules/@/c/index.js"),
+    ];
+    for (specifier, expected) in pass {
+        assert_eq!(
+            resolve(&resolver, "/app/src/nested", specifier),
+            Ok(PathBuf::from(expected)),
+            "{specifier}"
+        );
+    }
+    // Relative specifiers are not mapped
+    assert_eq!(resolve(&resolver, "/app", "./a"), Err(ResolveError::NotFound("./a".into())));
+}
+
+#[test]
+fn base_url() {
+    let tsconfig = r#"{
+        "compilerOptions": {
+            "baseUrl": "./src",
+            "paths": { "~/*": ["./*"] }
+        }
+    }"#;
+    let resolver = resolver(
+        &[("/app/tsconfig.json", tsconfig), ("/app/src/a.ts", ""), ("/app/src/b/index.ts", "")],
+        options(false),
+    );
+    assert_eq!(resolve(&resolver, "/app", "a"), Ok(PathBuf::from("/app/src/a.ts")));
+    assert_eq!(resolve(&resolver, "/app", "b"), Ok(PathBuf::from("/app/src/b/index.ts")));
+    // The paths are relative to the `baseUrl`
+    assert_eq!(resolve(&resolver, "/app", "~/a"), Ok(PathBuf::from("/app/src/a.ts")));
+
+    // Without a tsconfig option the tsconfig is not loaded
+    let resolver = resolver.clone_with_options(ResolveOptions::default());
+    assert_eq!(resolve(&resolver, "/app", "a"), Err(ResolveError::NotFound("a".into())));
+}
+
+#[test]
+fn extends() {
+    let base = r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "~/*": ["lib/*"] } } }"#;
+    let strict = r#"{ "compilerOptions": { "paths": { "t": ["./types/t.ts"] } } }"#;
+    let resolver = resolver(
+        &[
+            ("/app/tsconfig.json", r#"{ "extends": ["./base", "@tsconfig/strict"] }"#),
+            ("/app/base.json", base),
+            ("/app/node_modules/@tsconfig/strict/tsconfig.json", strict),
+            ("/app/types/t.ts", ""),
+            ("/app/lib/a.ts", ""),
+            ("/app/packages/p/tsconfig.json", r#"{ "extends": "../../tsconfig.json" }"#),
+        ],
+        options(false),
+    );
+    // The `paths` of the last extended tsconfig are relative to the `baseUrl` of the first one
+    assert_eq!(resolve(&resolver, "/app/packages/p", "t"), Ok(PathBuf::from("/app/types/t.ts")));
+    assert_eq!(resolve(&resolver, "/app/packages/p", "lib/a"), Ok(PathBuf::from("/app/lib/a.ts")));
+    assert_eq!(
+        resolve(&resolver, "/app/packages/p", "~/a"),
+        Err(ResolveError::NotFound("~/a".into()))
+    );
+}
+
+#[test]
+fn extends_errors() {
+    let resolver = resolver(
+        &[
+            ("/a/tsconfig.json", r#"{ "extends": "./b.json" }"#),
+            ("/a/b.json", r#"{ "extends": "./tsconfig.json" }"#),
+            ("/c/tsconfig.json", r#"{ "extends": "./missing" }"#),
+            ("/d/tsconfig.json", "{ \"compilerOptions\": "),
+        ],
+        options(false),
+    );
+    assert_eq!(
+        resolve(&resolver, "/a", "x"),
+        Err(ResolveError::TsconfigCircularExtend(PathBuf::from("/a/tsconfig.json")))
+    );
+    assert_eq!(
+        resolve(&resolver, "/c", "x"),
+        Err(ResolveError::TsconfigNotFound(PathBuf::from("./missing")))
+    );
+    assert!(matches!(resolve(&resolver, "/d", "x"), Err(ResolveError::Json { .. })));
+}
+
+#[test]
+fn config_file() {
+    let resolver = resolver(
+        &[
+            ("/tsconfig.base.json", r#"{ "compilerOptions": { "baseUrl": "./src" } }"#),
+            ("/src/a.ts", ""),
+        ],
+        ResolveOptions {
+            extensions: vec![".ts".into()],
+            tsconfig: Some(TsconfigOptions {
+                config_file: Some(PathBuf::from("/tsconfig.base.json")),
+                references: false,
+            }),
+            ..ResolveOptions::default()
+        },
+    );
+    assert_eq!(resolve(&resolver, "/other", "a"), Ok(PathBuf::from("/src/a.ts")));
+}
+
+#[test]
+fn references() {
+    let solution = r#"{
+        "files": [],
+        "references": [{ "path": "./tsconfig.app.json" }, { "path": "./tsconfig.node.json" }]
+    }"#;
+    let app =
+        r#"{ "include": ["src/**/*"], "compilerOptions": { "paths": { "@/*": ["./src/*"] } } }"#;
+    let node =
+        r#"{ "include": ["vite.config.ts"], "compilerOptions": { "baseUrl": "./scripts" } }"#;
+    let files = [
+        ("/app/tsconfig.json", solution),
+        ("/app/tsconfig.app.json", app),
+        ("/app/tsconfig.node.json", node),
+        ("/app/src/a.ts", ""),
+        ("/app/src/b/c.ts", ""),
+        ("/app/scripts/d.ts", ""),
+    ];
+    let resolver = resolver(&files, options(true));
+    assert_eq!(resolve(&resolver, "/app/src/b", "@/a"), Ok(PathBuf::from("/app/src/a.ts")));
+    assert_eq!(resolve(&resolver, "/app", "d"), Ok(PathBuf::from("/app/scripts/d.ts")));
+    assert_eq!(resolve(&resolver, "/app", "@/a"), Err(ResolveError::NotFound("@/a".into())));
+
+    // The solution tsconfig itself does not map anything
+    let resolver = resolver.clone_with_options(options(false));
+    assert_eq!(resolve(&resolver, "/app/src/b", "@/a"), Err(ResolveError::NotFound("@/a".into())));
+}
@@ -0,0 +1,390 @@
+//! tsconfig.json
+//!
+//! The `compilerOptions.paths` and `compilerOptions.baseUrl` of the projects, and the lookup of
+//! the project of a module.
+//!
+//! References:
+//! * <https://www.typescriptlang.org/tsconfig#paths>
+//! * <https://www.typescriptlang.org/docs/handbook/module-resolution.html#path-mapping>
+//! * <https://www.typescriptlang.org/docs/handbook/project-references.html>
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde_json::Value;
+
+use crate::{path::PathUtil, FileSystem, ResolveError, ResolverGeneric};
+
+/// The tsconfig options of the resolver
+#[derive(Debug, Clone, Default)]
+pub struct TsconfigOptions {
+    /// The tsconfig of all the modules, the nearest `tsconfig.json` of the directory of the
+    /// importing module is used when `None`.
+    pub config_file: Option<PathBuf>,
+
+    /// Use the referenced project which contains the importing module instead of the project
+    /// which references it, e.g. for a solution `tsconfig.json` at the root of a monorepo.
+    pub references: bool,
+}
+
+/// A tsconfig with its `extends` chain applied
+#[derive(Debug)]
+pub struct TsConfig {
+    path: PathBuf,
+    /// The absolute `compilerOptions.baseUrl`
+    base_url: Option<PathBuf>,
+    paths: Option<CompilerPaths>,
+    /// The files and the directories of the modules included in the project, from `files` and the
+    /// static part of the `include` patterns
+    roots: Option<Vec<PathBuf>>,
+    /// The tsconfig files of the referenced projects, which are not inherited by `extends`
+    references: Vec<PathBuf>,
+}
+
+/// `compilerOptions.paths`, in order
+#[derive(Debug, Clone)]
+struct CompilerPaths {
+    /// The directory of the tsconfig which declares the paths, the targets are relative to it
+    /// when there is no `baseUrl`
+    directory: PathBuf,
+    mappings: Vec<(String, Vec<String>)>,
+}
+
+impl TsConfig {
+    /// Parse the tsconfig at `path`, the `extends` of the tsconfig are returned to be applied with
+    /// [TsConfig::extend].
+    fn parse(path: PathBuf, json: &str) -> Result<(Self, Vec<String>), ResolveError> {
+        let raw = match serde_json::from_str::<Value>(&strip_jsonc(json)) {
+            Ok(raw) if raw.is_object() => raw,
+            Ok(_) => {
+                let message = "the tsconfig is not an object".to_string();
+                return Err(ResolveError::Json { path, message });
+            }
+            Err(err) => return Err(ResolveError::Json { path, message: err.to_string() }),
+        };
+        let directory = path.parent().unwrap_or(&path).to_path_buf();
+
+        let extends = match raw.get("extends") {
+            Some(Value::String(extends)) => vec![extends.clone()],
+            Some(Value::Array(extends)) => {
+                extends.iter().filter_map(Value::as_str).map(ToString::to_string).collect()
+            }
+            _ => vec![],
+        };
+
+        let compiler_options = raw.get("compilerOptions");
+        let base_url = compiler_options
+            .and_then(|options| options.get("baseUrl"))
+            .and_then(Value::as_str)
+            .map(|base_url| directory.normalize_with(base_url));
+        let paths = compiler_options.and_then(|options| options.get("paths")).and_then(|paths| {
+            let mappings = paths
+                .as_object()?
+                .iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets.as_array().map_or_else(Vec::new, |targets| {
+                        targets.iter().filter_map(Value::as_str).map(ToString::to_string).collect()
+                    });
+                    (pattern.clone(), targets)
+                })
+                .collect();
+            Some(CompilerPaths { directory: directory.clone(), mappings })
+        });
+
+        let strings = |field: &str| {
+            raw.get(field)
+                .and_then(Value::as_array)
+                .map(|values| values.iter().filter_map(Value::as_str).collect::<std::vec::Vec<_>>())
+        };
+        let files = strings("files");
+        let include = strings("include");
+        let roots = (files.is_some() || include.is_some()).then(|| {
+            let files = files
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|file| directory.normalize_with(file).parent().map(Path::to_path_buf));
+            let include = include
+                .unwrap_or_default()
+                .into_iter()
+                .map(|pattern| directory.normalize_with(static_prefix(pattern)));
+            files.chain(include).collect()
+        });
+
+        let references = raw
+            .get("references")
+            .and_then(Value::as_array)
+            .map(|references| {
+                references
+                    .iter()
+                    .filter_map(|reference| reference.get("path").and_then(Value::as_str))
+                    .map(|reference| directory.normalize_with(reference))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok((Self { path, base_url, paths, roots, references }, extends))
+    }
+
+    /// Inherit the options which are not set by `self` from the extended tsconfig
+    fn extend(&mut self, extended: &Self) {
+        if self.base_url.is_none() {
+            self.base_url = extended.base_url.clone();
+        }
+        if self.paths.is_none() {
+            self.paths = extended.paths.clone();
+        }
+        if self.roots.is_none() {
+            self.roots = extended.roots.clone();
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(&self.path)
+    }
+
+    /// The length of the root of the project which contains the modules of `directory`, the
+    /// projects without `files` and `include` contain the directory of the tsconfig.
+    /// A root may be a file, which is contained by its directory.
+    fn contains(&self, directory: &Path) -> Option<usize> {
+        let default_roots = [self.directory().to_path_buf()];
+        let roots = self.roots.as_deref().unwrap_or(&default_roots);
+        roots
+            .iter()
+            .filter(|root| directory.starts_with(root) || root.parent() == Some(directory))
+            .map(|root| root.as_os_str().len())
+            .max()
+    }
+
+    /// The paths which `request` is mapped to by `paths` and `baseUrl`, in the order they are
+    /// attempted
+    fn resolve_request(&self, request: &str) -> Vec<PathBuf> {
+        let mut candidates = vec![];
+        if let Some(paths) = &self.paths {
+            let base = self.base_url.as_deref().unwrap_or(&paths.directory);
+            if let Some((targets, matched)) = paths.matching_targets(request) {
+                for target in targets {
+                    candidates.push(base.normalize_with(target.replacen('*', matched, 1)));
+                }
+            }
+        }
+        if let Some(base_url) = &self.base_url {
+            candidates.push(base_url.normalize_with(request));
+        }
+        candidates
+    }
+}
+
+impl CompilerPaths {
+    /// The targets of the pattern matching `request` and the part of `request` matched by its
+    /// `*`. An exact pattern takes precedence, and then the pattern with the longest prefix.
+    fn matching_targets<'a>(&'a self, request: &'a str) -> Option<(&'a [String], &'a str)> {
+        if let Some((_, targets)) = self.mappings.iter().find(|(pattern, _)| pattern == request) {
+            return Some((targets, ""));
+        }
+        self.mappings
+            .iter()
+            .filter_map(|(pattern, targets)| {
+                let (prefix, suffix) = pattern.split_once('*')?;
+                let matched = request.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                Some((prefix.len(), targets.as_slice(), matched))
+            })
+            .max_by_key(|(prefix_len, _, _)| *prefix_len)
+            .map(|(_, targets, matched)| (targets, matched))
+    }
+}
+
+/// The directories of a pattern before its first wildcard, e.g. `src` of `src/**/*.ts`
+fn static_prefix(pattern: &str) -> &str {
+    let Some(wildcard) = pattern.find(['*', '?']) else { return pattern };
+    pattern[..wildcard].rfind('/').map_or("", |slash| &pattern[..slash])
+}
+
+/// Replaces the comments and the trailing commas of JSON with whitespace, so that the locations
+/// of the parse errors are kept
+fn strip_jsonc(json: &str) -> String {
+    let mut bytes = json.as_bytes().to_vec();
+    let mut i = 0;
+    // The index of the last comma which may be trailing
+    let mut comma = None;
+    while let Some(&byte) = bytes.get(i) {
+        match byte {
+            b'"' => {
+                comma = None;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    bytes[i] = b' ';
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = json[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+                for byte in &mut bytes[i..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+                continue;
+            }
+            b',' => comma = Some(i),
+            b'}' | b']' => {
+                if let Some(comma) = comma.take() {
+                    bytes[comma] = b' ';
+                }
+            }
+            byte if byte.is_ascii_whitespace() => {}
+            _ => comma = None,
+        }
+        i += 1;
+    }
+    // Only ASCII bytes outside of the strings have been replaced
+    String::from_utf8(bytes).unwrap_or_else(|_| json.to_string())
+}
+
+impl<Fs: FileSystem> ResolverGeneric<Fs> {
+    /// The `paths` and `baseUrl` mappings of the tsconfig of the module in `directory`
+    pub(crate) fn load_tsconfig_paths(
+        &self,
+        directory: &Path,
+        request: &str,
+    ) -> Result<Option<PathBuf>, ResolveError> {
+        let Some(tsconfig) = self.find_tsconfig(directory)? else { return Ok(None) };
+        for path in tsconfig.resolve_request(request) {
+            if let Some(path) = self.load_as_file_or_directory(&path, request)? {
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The tsconfig of the module in `directory`
+    fn find_tsconfig(&self, directory: &Path) -> Result<Option<Arc<TsConfig>>, ResolveError> {
+        let Some(options) = &self.options.tsconfig else { return Ok(None) };
+        let tsconfig = if let Some(config_file) = &options.config_file {
+            self.load_tsconfig(config_file, &mut vec![])?
+        } else {
+            let path = directory
+                .ancestors()
+                .map(|dir| dir.join("tsconfig.json"))
+                .find(|path| self.cache.is_file(path));
+            let Some(path) = path else { return Ok(None) };
+            self.load_tsconfig(&path, &mut vec![])?
+        };
+        if !options.references || tsconfig.references.is_empty() {
+            return Ok(Some(tsconfig));
+        }
+        // The innermost referenced project which contains the module
+        let mut project = None;
+        for reference in &tsconfig.references {
+            let path = if self.cache.is_dir(reference) {
+                reference.join("tsconfig.json")
+            } else {
+                reference.clone()
+            };
+            let referenced = self.load_tsconfig(&path, &mut vec![])?;
+            if let Some(len) = referenced.contains(directory) {
+                if project.as_ref().map_or(true, |(project_len, _)| len > *project_len) {
+                    project = Some((len, referenced));
+                }
+            }
+        }
+        Ok(Some(project.map_or(tsconfig, |(_, project)| project)))
+    }
+
+    /// Load the tsconfig at `path` and the tsconfig files it extends, `visited` is the `extends`
+    /// chain to `path`
+    fn load_tsconfig(
+        &self,
+        path: &Path,
+        visited: &mut Vec<PathBuf>,
+    ) -> Result<Arc<TsConfig>, ResolveError> {
+        if let Some(tsconfig) = self.cache.tsconfig(path) {
+            return Ok(tsconfig);
+        }
+        if visited.iter().any(|visited| visited == path) {
+            return Err(ResolveError::TsconfigCircularExtend(path.to_path_buf()));
+        }
+        let json = self
+            .cache
+            .read_to_string(path)
+            .map_err(|_| ResolveError::TsconfigNotFound(path.to_path_buf()))?;
+        let (mut tsconfig, extends) = TsConfig::parse(path.to_path_buf(), &json)?;
+        visited.push(path.to_path_buf());
+        // The later tsconfig files of `extends` override the earlier ones
+        for specifier in extends.iter().rev() {
+            let extended_path = self.resolve_tsconfig_extends(tsconfig.directory(), specifier)?;
+            let extended = self.load_tsconfig(&extended_path, visited)?;
+            tsconfig.extend(&extended);
+        }
+        visited.pop();
+        let tsconfig = Arc::new(tsconfig);
+        self.cache.insert_tsconfig(path.to_path_buf(), Arc::clone(&tsconfig));
+        Ok(tsconfig)
+    }
+
+    /// The path of `extends`, which is a path relative to the tsconfig or a package name with an
+    /// optional path, e.g. `@tsconfig/node18` or `@tsconfig/node18/tsconfig.json`
+    fn resolve_tsconfig_extends(
+        &self,
+        directory: &Path,
+        specifier: &str,
+    ) -> Result<PathBuf, ResolveError> {
+        let not_found = || ResolveError::TsconfigNotFound(PathBuf::from(specifier));
+        let load = |path: PathBuf| {
+            if self.cache.is_file(&path) {
+                return Some(path);
+            }
+            let mut path_with_extension = path.clone().into_os_string();
+            path_with_extension.push(".json");
+            let path_with_extension = PathBuf::from(path_with_extension);
+            if self.cache.is_file(&path_with_extension) {
+                return Some(path_with_extension);
+            }
+            let index = path.join("tsconfig.json");
+            self.cache.is_file(&index).then_some(index)
+        };
+        if Path::new(specifier).is_absolute() || crate::is_relative(specifier) {
+            return load(directory.normalize_with(specifier)).ok_or_else(not_found);
+        }
+        directory
+            .ancestors()
+            .flat_map(|dir| self.options.modules.iter().map(move |module| dir.join(module)))
+            .filter(|modules_dir| self.cache.is_dir(modules_dir))
+            .find_map(|modules_dir| load(modules_dir.normalize_with(specifier)))
+            .ok_or_else(not_found)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{static_prefix, strip_jsonc};
+
+    #[test]
+    fn jsonc() {
+        let json = "{\n  // comment\n  \"a\": \"//b/*\", /* c */\n  \"d\": [1, 2,],\n}";
+        let stripped = strip_jsonc(json);
+        assert_eq!(stripped.len(), json.len());
+        let value = serde_json::from_str::<serde_json::Value>(&stripped).unwrap();
+        assert_eq!(value, serde_json::json!({ "a": "//b/*", "d": [1, 2] }));
+    }
+
+    #[test]
+    fn include_prefix() {
+        assert_eq!(static_prefix("src/**/*.ts"), "src");
+        assert_eq!(static_prefix("**/*"), "");
+        assert_eq!(static_prefix("src/a.ts"), "src/a.ts");
+        assert_eq!(static_prefix("src/a?.ts"), "src");
+    }
+}
@@ -0,0 +1,165 @@
+//! The zip archives of the Yarn cache, which contain the packages of the Yarn PnP installs
+//!
+//! References:
+//! * <https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT>
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::DeflateDecoder;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::FileMetadata;
+
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+const CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+const LOCAL_FILE_HEADER_SIZE: usize = 30;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+/// The index of a zip archive, the files are read from the archive on demand
+#[derive(Debug)]
+pub struct ZipArchive {
+    path: PathBuf,
+    files: FxHashMap<String, ZipEntry>,
+    directories: FxHashSet<String>,
+}
+
+#[derive(Debug)]
+struct ZipEntry {
+    local_header_offset: u64,
+    compressed_size: u64,
+    compression_method: u16,
+}
+
+impl ZipArchive {
+    /// Read the central directory of the archive at `path`
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        // The end of central directory record is followed by a comment of at most 64KiB
+        let tail_len = len.min((END_OF_CENTRAL_DIRECTORY_SIZE + u16::MAX as usize) as u64);
+        file.seek(SeekFrom::Start(len - tail_len))?;
+        let mut tail = vec![];
+        file.by_ref().take(tail_len).read_to_end(&mut tail)?;
+        let end = (0..=tail.len().saturating_sub(END_OF_CENTRAL_DIRECTORY_SIZE))
+            .rev()
+            .find(|&i| u32_at(&tail, i) == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+            .ok_or_else(|| invalid_data("the end of central directory record is missing"))?;
+        let entries = u16_at(&tail, end + 10).unwrap_or_default();
+        let directory_size = u32_at(&tail, end + 12).unwrap_or_default();
+        let directory_offset = u32_at(&tail, end + 16).unwrap_or_default();
+
+        file.seek(SeekFrom::Start(u64::from(directory_offset)))?;
+        let mut directory = vec![];
+        file.take(u64::from(directory_size)).read_to_end(&mut directory)?;
+
+        let mut archive = Self {
+            path: path.to_path_buf(),
+            files: FxHashMap::default(),
+            directories: FxHashSet::default(),
+        };
+        let mut offset = 0;
+        for _ in 0..entries {
+            if u32_at(&directory, offset) != Some(CENTRAL_DIRECTORY_SIGNATURE) {
+                return Err(invalid_data("the central directory is not valid"));
+            }
+            let field =
+                |i: usize| u16_at(&directory, offset + i).map(usize::from).unwrap_or_default();
+            let compression_method = u16_at(&directory, offset + 10).unwrap_or_default();
+            let compressed_size = u32_at(&directory, offset + 20).unwrap_or_default();
+            let (name_len, extra_len, comment_len) = (field(28), field(30), field(32));
+            let local_header_offset = u32_at(&directory, offset + 42).unwrap_or_default();
+            let name_start = offset + CENTRAL_DIRECTORY_HEADER_SIZE;
+            let name = directory
+                .get(name_start..name_start + name_len)
+                .ok_or_else(|| invalid_data("the central directory is not valid"))?;
+            let name = String::from_utf8_lossy(name).into_owned();
+            offset = name_start + name_len + extra_len + comment_len;
+
+            archive.insert_directories(&name);
+            if !name.ends_with('/') {
+                let entry = ZipEntry {
+                    local_header_offset: u64::from(local_header_offset),
+                    compressed_size: u64::from(compressed_size),
+                    compression_method,
+                };
+                archive.files.insert(name, entry);
+            }
+        }
+        Ok(archive)
+    }
+
+    /// The directories of an entry, the archives do not always have the entries of the
+    /// directories
+    fn insert_directories(&mut self, name: &str) {
+        if let Some(directory) = name.strip_suffix('/') {
+            self.directories.insert(directory.to_string());
+        }
+        let name = name.trim_end_matches('/');
+        for (i, _) in name.match_indices('/') {
+            self.directories.insert(name[..i].to_string());
+        }
+    }
+
+    /// The metadata of the path `name` relative to the root of the archive
+    pub fn metadata(&self, name: &str) -> Option<FileMetadata> {
+        if name.is_empty() || self.directories.contains(name) {
+            Some(FileMetadata::new(false, true))
+        } else {
+            self.files.contains_key(name).then(|| FileMetadata::new(true, false))
+        }
+    }
+
+    pub fn read_to_string(&self, name: &str) -> io::Result<String> {
+        let entry = self.files.get(name).ok_or(io::ErrorKind::NotFound)?;
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(entry.local_header_offset))?;
+        let mut header = [0; LOCAL_FILE_HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        if u32_at(&header, 0) != Some(LOCAL_FILE_HEADER_SIGNATURE) {
+            return Err(invalid_data("the local file header is not valid"));
+        }
+        let name_len = u16_at(&header, 26).unwrap_or_default();
+        let extra_len = u16_at(&header, 28).unwrap_or_default();
+        file.seek(SeekFrom::Current(i64::from(name_len) + i64::from(extra_len)))?;
+        let mut data = file.take(entry.compressed_size);
+        let mut content = String::new();
+        match entry.compression_method {
+            STORED => data.read_to_string(&mut content)?,
+            DEFLATED => DeflateDecoder::new(data).read_to_string(&mut content)?,
+            _ => return Err(invalid_data("the compression method is not supported")),
+        };
+        Ok(content)
+    }
+}
+
+/// The path of the archive and the path in the archive of a path into a zip archive, e.g.
+/// `/.yarn/cache/a.zip` and `node_modules/a/index.js` of `/.yarn/cache/a.zip/node_modules/a/index.js`
+pub fn split_zip_path(path: &Path) -> Option<(&Path, String)> {
+    let archive = path.ancestors().find(|ancestor| {
+        ancestor.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+    })?;
+    let name = path.strip_prefix(archive).ok()?;
+    let name = name.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>();
+    Some((archive, name.join("/")))
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    bytes.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
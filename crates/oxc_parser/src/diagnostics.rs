@@ -2,7 +2,7 @@ use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::{self, Error},
 };
-use oxc_span::Span;
+use oxc_span::{Atom, Span};
 
 #[derive(Debug, Error, Diagnostic)]
 #[error("Source length exceeds 4 GiB limit")]
@@ -336,3 +336,33 @@ pub struct UsingDeclarationsMustBeInitialized(#[label] pub Span);
 #[error("TS1089: `static` modifier cannot appear on a constructor declaration.")]
 #[diagnostic()]
 pub struct StaticConstructor(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Expected an expression")]
+#[diagnostic()]
+pub struct TemplateExpectedExpression(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Expected a single statement")]
+#[diagnostic()]
+pub struct TemplateExpectedStatement(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Missing replacement `{0}`")]
+#[diagnostic()]
+pub struct TemplateMissingReplacement(pub Atom, #[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Unused replacement `{0}`")]
+#[diagnostic()]
+pub struct TemplateUnusedReplacement(pub Atom);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Replacement `{0}` is used more than once")]
+#[diagnostic(help("Only the names can be used more than once"))]
+pub struct TemplateReusedReplacement(pub Atom, #[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Replacement `{0}` is used in the place of a name")]
+#[diagnostic()]
+pub struct TemplateExpectedName(pub Atom, #[label] pub Span);
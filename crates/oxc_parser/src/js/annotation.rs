@@ -0,0 +1,59 @@
+//! Annotation comments for bundlers and minifiers
+//!
+//! * `/* #__PURE__ */` marks the following call or `new` expression as removable when unused
+//! * `/* #__NO_SIDE_EFFECTS__ */` marks the following function as having no side effects
+//!
+//! See <https://github.com/javascript-compiler-hints/compiler-notations-spec>
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+
+use crate::Parser;
+
+impl<'a> Parser<'a> {
+    /// `/* #__PURE__ */` or `/* @__PURE__ */` before `start`
+    pub(crate) fn has_pure_annotation(&self, start: u32) -> bool {
+        self.has_annotation(start, "__PURE__")
+    }
+
+    /// `/* #__NO_SIDE_EFFECTS__ */` or `/* @__NO_SIDE_EFFECTS__ */` before `start`
+    pub(crate) fn has_no_side_effects_annotation(&self, start: u32) -> bool {
+        self.has_annotation(start, "__NO_SIDE_EFFECTS__")
+    }
+
+    /// The annotation may be followed by whitespace and parentheses, e.g. `/* #__PURE__ */ (f())`
+    fn has_annotation(&self, start: u32, name: &str) -> bool {
+        let Some((comment_start, comment_end)) =
+            self.lexer.trivia_builder.last_multi_line_comment_before(start)
+        else {
+            return false;
+        };
+        let text = self.source_text[comment_start as usize..comment_end as usize].trim();
+        let is_annotation = text
+            .strip_prefix('#')
+            .or_else(|| text.strip_prefix('@'))
+            .is_some_and(|annotation| annotation == name);
+        is_annotation
+            && self.source_text[comment_end as usize + 2..start as usize]
+                .chars()
+                .all(|c| c.is_whitespace() || c == '(')
+    }
+
+    /// `/* #__NO_SIDE_EFFECTS__ */ export function f() {}` and
+    /// `/* #__NO_SIDE_EFFECTS__ */ const f = () => {}` annotate the declared functions
+    pub(crate) fn annotate_no_side_effects_declaration(declaration: &mut Declaration<'a>) {
+        match declaration {
+            Declaration::FunctionDeclaration(func) => func.pure = true,
+            Declaration::VariableDeclaration(decl) => {
+                for declarator in decl.declarations.iter_mut() {
+                    match &mut declarator.init {
+                        Some(Expression::FunctionExpression(func)) => func.pure = true,
+                        Some(Expression::ArrowExpression(arrow)) => arrow.pure = true,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
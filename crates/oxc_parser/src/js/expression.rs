@@ -480,9 +480,16 @@ impl<'a> Parser<'a> {
     /// Section 13.3 Left-Hand-Side Expression
     pub(crate) fn parse_lhs_expression(&mut self) -> Result<Expression<'a>> {
         let span = self.start_span();
+        let pure = self.has_pure_annotation(span.start);
         let mut in_optional_chain = false;
         let lhs = self.parse_member_expression_base(&mut in_optional_chain)?;
-        let lhs = self.parse_call_expression(span, lhs, &mut in_optional_chain)?;
+        let mut lhs = self.parse_call_expression(span, lhs, &mut in_optional_chain)?;
+        if pure {
+            // `/* #__PURE__ */ a()()` annotates the outermost call
+            if let Expression::CallExpression(call) = &mut lhs {
+                call.pure = true;
+            }
+        }
         if in_optional_chain {
             let span = self.end_span(span);
             Ok(self.map_to_chain_expression(span, lhs))
@@ -651,6 +658,7 @@ impl<'a> Parser<'a> {
     /// [NewExpression](https://tc39.es/ecma262/#sec-new-operator)
     fn parse_new_expression(&mut self) -> Result<Expression<'a>> {
         let span = self.start_span();
+        let pure = self.has_pure_annotation(span.start);
         let identifier = self.parse_keyword_identifier(Kind::New);
         if self.at(Kind::Dot) {
             return self.parse_meta_property(span, identifier);
@@ -686,7 +694,11 @@ impl<'a> Parser<'a> {
             self.error(diagnostics::NewOptionalChain(span));
         }
 
-        Ok(self.ast.new_expression(span, callee, arguments, type_parameter))
+        let mut expr = self.ast.new_expression(span, callee, arguments, type_parameter);
+        if let Expression::NewExpression(new_expr) = &mut expr {
+            new_expr.pure = pure;
+        }
+        Ok(expr)
     }
 
     /// Section 13.3 Call Expression
@@ -86,6 +86,7 @@ impl<'a> Parser<'a> {
         func_kind: FunctionKind,
         modifiers: Modifiers<'a>,
     ) -> Result<Box<'a, Function<'a>>> {
+        let no_side_effects = self.has_no_side_effects_annotation(span.start);
         let ctx = self.ctx;
         self.ctx = self.ctx.and_in(true).and_await(r#async).and_yield(generator);
 
@@ -121,7 +122,7 @@ impl<'a> Parser<'a> {
             self.asi()?;
         }
 
-        Ok(self.ast.function(
+        let mut func = self.ast.function(
             function_type,
             self.end_span(span),
             id,
@@ -134,7 +135,9 @@ impl<'a> Parser<'a> {
             type_parameters,
             return_type,
             modifiers,
-        ))
+        );
+        func.pure = no_side_effects;
+        Ok(func)
     }
 
     /// [Function Declaration](https://tc39.es/ecma262/#prod-FunctionDeclaration)
@@ -239,6 +242,7 @@ impl<'a> Parser<'a> {
 
         self.expect(Kind::Arrow)?;
 
+        let no_side_effects = self.has_no_side_effects_annotation(span.start);
         self.ctx = self.ctx.and_await(r#async).and_yield(generator);
         let expression = !self.at(Kind::LCurly);
         let body = if expression {
@@ -251,7 +255,7 @@ impl<'a> Parser<'a> {
         };
         self.ctx = self.ctx.and_await(has_await).and_yield(has_yield);
 
-        Ok(self.ast.arrow_expression(
+        let mut arrow = self.ast.arrow_expression(
             self.end_span(span),
             expression,
             false,
@@ -260,7 +264,11 @@ impl<'a> Parser<'a> {
             body,
             None,
             None,
-        ))
+        );
+        if let Expression::ArrowExpression(arrow) = &mut arrow {
+            arrow.pure = no_side_effects;
+        }
+        Ok(arrow)
     }
 
     /// Section 15.4 Method Definitions
@@ -501,6 +509,7 @@ impl<'a> Parser<'a> {
         return_type: Option<Box<'a, TSTypeAnnotation<'a>>>,
         r#async: bool,
     ) -> Result<Expression<'a>> {
+        let no_side_effects = self.has_no_side_effects_annotation(span.start);
         let has_await = self.ctx.has_await();
         let has_yield = self.ctx.has_yield();
         self.ctx = self.ctx.and_await(r#async).and_yield(false);
@@ -517,7 +526,7 @@ impl<'a> Parser<'a> {
 
         self.ctx = self.ctx.and_await(has_await).and_yield(has_yield);
 
-        Ok(self.ast.arrow_expression(
+        let mut arrow = self.ast.arrow_expression(
             self.end_span(span),
             expression,
             false,
@@ -526,7 +535,11 @@ impl<'a> Parser<'a> {
             body,
             type_parameters,
             return_type,
-        ))
+        );
+        if let Expression::ArrowExpression(arrow) = &mut arrow {
+            arrow.pure = no_side_effects;
+        }
+        Ok(arrow)
     }
 
     /// Section [Arrow Function](https://tc39.es/ecma262/#sec-arrow-function-definitions)
@@ -2,6 +2,7 @@
 
 #![allow(clippy::missing_errors_doc)]
 
+mod annotation;
 mod grammar;
 pub mod list;
 
@@ -282,6 +282,7 @@ impl<'a> Parser<'a> {
         &mut self,
         span: Span,
     ) -> Result<Box<'a, ExportNamedDeclaration<'a>>> {
+        let no_side_effects = self.has_no_side_effects_annotation(span.start);
         let decl_span = self.start_span();
         // For tc39/proposal-decorators
         // For more information, please refer to <https://babeljs.io/docs/babel-plugin-proposal-decorators#decoratorsbeforeexport>
@@ -292,7 +293,10 @@ impl<'a> Parser<'a> {
             Modifiers::empty()
         };
 
-        let declaration = self.parse_declaration(decl_span, modifiers)?;
+        let mut declaration = self.parse_declaration(decl_span, modifiers)?;
+        if no_side_effects {
+            Self::annotate_no_side_effects_declaration(&mut declaration);
+        }
         let span = self.end_span(span);
         Ok(self.ast.export_named_declaration(
             span,
@@ -310,12 +314,13 @@ impl<'a> Parser<'a> {
         &mut self,
         span: Span,
     ) -> Result<Box<'a, ExportDefaultDeclaration<'a>>> {
+        let no_side_effects = self.has_no_side_effects_annotation(span.start);
         let exported = self.parse_keyword_identifier(Kind::Default);
         let decl_span = self.start_span();
         // For tc39/proposal-decorators
         // For more information, please refer to <https://babeljs.io/docs/babel-plugin-proposal-decorators#decoratorsbeforeexport>
         self.eat_decorators()?;
-        let declaration = match self.cur_kind() {
+        let mut declaration = match self.cur_kind() {
             Kind::Class => self
                 .parse_class_declaration(decl_span, /* modifiers */ Modifiers::empty())
                 .map(ExportDefaultDeclarationKind::ClassDeclaration)?,
@@ -349,6 +354,18 @@ impl<'a> Parser<'a> {
                 decl
             }
         };
+        if no_side_effects {
+            match &mut declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func)
+                | ExportDefaultDeclarationKind::Expression(Expression::FunctionExpression(func)) => {
+                    func.pure = true;
+                }
+                ExportDefaultDeclarationKind::Expression(Expression::ArrowExpression(arrow)) => {
+                    arrow.pure = true;
+                }
+                _ => {}
+            }
+        }
         let exported = ModuleExportName::Identifier(exported);
         let span = self.end_span(span);
         Ok(self.ast.export_default_declaration(span, declaration, exported))
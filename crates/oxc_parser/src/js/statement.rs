@@ -181,6 +181,7 @@ impl<'a> Parser<'a> {
         stmt_ctx: StatementContext,
     ) -> Result<Statement<'a>> {
         let start_span = self.start_span();
+        let no_side_effects = self.has_no_side_effects_annotation(start_span.start);
         let decl = self.parse_variable_declaration(
             start_span,
             VariableDeclarationContext::new(VariableDeclarationParent::Statement),
@@ -191,7 +192,11 @@ impl<'a> Parser<'a> {
             self.error(diagnostics::LexicalDeclarationSingleStatement(decl.span));
         }
 
-        Ok(Statement::Declaration(Declaration::VariableDeclaration(decl)))
+        let mut declaration = Declaration::VariableDeclaration(decl);
+        if no_side_effects {
+            Self::annotate_no_side_effects_declaration(&mut declaration);
+        }
+        Ok(Statement::Declaration(declaration))
     }
 
     /// Section 14.4 Empty Statement
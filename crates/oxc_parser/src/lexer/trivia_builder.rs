@@ -21,6 +21,19 @@ impl TriviaBuilder {
         self.trivias.comments.push((start + 2, end - 2, CommentKind::MultiLine));
     }
 
+    /// The multi line comment which ends last before `pos`, as `(start, end)` without `/*` and `*/`
+    pub fn last_multi_line_comment_before(&self, pos: u32) -> Option<(u32, u32)> {
+        // Comments lexed again after a rewind are pushed again, but always in source order
+        // after the rewind position, so the first comment before `pos` from the back is the last.
+        self.trivias
+            .comments
+            .iter()
+            .rev()
+            .find(|(_, end, _)| *end + 2 <= pos)
+            .filter(|(_, _, kind)| kind.is_multi_line())
+            .map(|(start, end, _)| (*start, *end))
+    }
+
     pub fn add_irregular_whitespace(&mut self, start: u32, end: u32) {
         self.trivias.irregular_whitespaces.push(Span::new(start, end));
     }
@@ -72,6 +72,7 @@ mod ts;
 
 mod diagnostics;
 mod lexer;
+pub mod template;
 
 use context::{Context, StatementContext};
 use oxc_allocator::Allocator;
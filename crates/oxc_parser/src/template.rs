@@ -0,0 +1,382 @@
+//! Templates, which build nodes from source text snippets
+//!
+//! The snippets are parsed into the arena of the nodes they are inserted in, and their
+//! placeholders `%%name%%` are replaced with nodes, as the syntactic placeholders of
+//! `@babel/template`:
+//!
+//! ```ignore
+//! let template = Template::new(allocator);
+//! let expr = template.expression(
+//!     "Object.defineProperty(%%object%%, %%name%%, { value: true })",
+//!     [("object", Replacement::Expression(object)), ("name", Replacement::Expression(name))],
+//! )?;
+//! ```
+//!
+//! The spans of the nodes are the offsets of the snippet.
+
+use oxc_allocator::{Allocator, Vec};
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_diagnostics::Error;
+use oxc_span::{Atom, SourceType, Span};
+use rustc_hash::FxHashMap;
+
+use crate::{diagnostics, Parser};
+
+/// The node of a placeholder
+pub enum Replacement<'a> {
+    /// Replaces a placeholder used once as an expression
+    Expression(Expression<'a>),
+    /// Replaces a placeholder used once as an expression statement, `%%body%%;`
+    Statements(Vec<'a, Statement<'a>>),
+    /// Renames the placeholders used as identifiers, references, bindings or property names
+    Name(Atom),
+}
+
+pub struct Template<'a> {
+    ast: AstBuilder<'a>,
+    source_type: SourceType,
+}
+
+impl<'a> Template<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self { ast: AstBuilder::new(allocator), source_type: SourceType::default() }
+    }
+
+    /// The source type of the snippets, a JavaScript script by default
+    #[must_use]
+    pub fn with_source_type(mut self, source_type: SourceType) -> Self {
+        self.source_type = source_type;
+        self
+    }
+
+    /// # Errors
+    ///
+    /// * The snippet has a syntax error, or is not a single expression
+    /// * A placeholder has no replacement, or a replacement is unused or used more than once
+    pub fn expression<'r, R: IntoIterator<Item = (&'r str, Replacement<'a>)>>(
+        &self,
+        source_text: &str,
+        replacements: R,
+    ) -> Result<Expression<'a>, std::vec::Vec<Error>> {
+        let mut stmts = self.parse(&format!("({source_text}\n)"), replacements)?;
+        let stmt = stmts.pop().filter(|_| stmts.is_empty());
+        let Some(Statement::ExpressionStatement(stmt)) = stmt else {
+            return Err(vec![diagnostics::TemplateExpectedExpression(Span::default()).into()]);
+        };
+        match stmt.unbox().expression {
+            Expression::ParenthesizedExpression(expr) => Ok(expr.unbox().expression),
+            expr => Ok(expr),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// * The snippet has a syntax error, or is not a single statement
+    /// * A placeholder has no replacement, or a replacement is unused or used more than once
+    pub fn statement<'r, R: IntoIterator<Item = (&'r str, Replacement<'a>)>>(
+        &self,
+        source_text: &str,
+        replacements: R,
+    ) -> Result<Statement<'a>, std::vec::Vec<Error>> {
+        let mut stmts = self.parse(source_text, replacements)?;
+        match stmts.pop() {
+            Some(stmt) if stmts.is_empty() => Ok(stmt),
+            _ => Err(vec![diagnostics::TemplateExpectedStatement(Span::default()).into()]),
+        }
+    }
+
+    /// The statements of the snippet, which may `return` outside of functions
+    ///
+    /// # Errors
+    ///
+    /// * The snippet has a syntax error
+    /// * A placeholder has no replacement, or a replacement is unused or used more than once
+    pub fn statements<'r, R: IntoIterator<Item = (&'r str, Replacement<'a>)>>(
+        &self,
+        source_text: &str,
+        replacements: R,
+    ) -> Result<Vec<'a, Statement<'a>>, std::vec::Vec<Error>> {
+        self.parse(source_text, replacements)
+    }
+
+    fn parse<'r, R: IntoIterator<Item = (&'r str, Replacement<'a>)>>(
+        &self,
+        source_text: &str,
+        replacements: R,
+    ) -> Result<Vec<'a, Statement<'a>>, std::vec::Vec<Error>> {
+        let (source_text, placeholders) = replace_placeholders(source_text);
+        let source_text = self.ast.new_str(&source_text);
+        let ret = Parser::new(self.ast.allocator, source_text, self.source_type)
+            .allow_return_outside_function(true)
+            .parse();
+        if !ret.errors.is_empty() {
+            return Err(ret.errors);
+        }
+        let mut program = ret.program;
+        // The directives of the snippet are its string expression statements
+        if !program.directives.is_empty() {
+            let mut body = self.ast.new_vec_with_capacity(program.directives.len());
+            for directive in program.directives {
+                let expr = self.ast.literal_string_expression(directive.expression);
+                body.push(self.ast.expression_statement(directive.span, expr));
+            }
+            body.extend(program.body);
+            program.body = body;
+        }
+
+        let mut replacers = FxHashMap::default();
+        for (name, replacement) in replacements {
+            replacers.insert(Atom::from(name), (Some(replacement), false));
+        }
+        let mut replacer =
+            Replacer { ast: &self.ast, placeholders, replacements: replacers, errors: vec![] };
+        replacer.visit_statements(&mut program.body);
+        let Replacer { replacements, mut errors, .. } = replacer;
+        for (name, (_, used)) in replacements {
+            if !used {
+                errors.push(diagnostics::TemplateUnusedReplacement(name).into());
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(program.body)
+    }
+}
+
+/// Replaces the placeholders `%%name%%` with the identifiers `$$oxc_template_name`, and returns
+/// the new source text with the names of the identifiers
+fn replace_placeholders(source_text: &str) -> (String, FxHashMap<Atom, Atom>) {
+    let mut output = String::with_capacity(source_text.len());
+    let mut placeholders = FxHashMap::default();
+    let mut rest = source_text;
+    while let Some(start) = rest.find("%%") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("%%") else { break };
+        let name = &after[..end];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            output.push_str(&rest[..start + 2]);
+            rest = after;
+            continue;
+        }
+        let identifier = format!("$$oxc_template_{name}");
+        output.push_str(&rest[..start]);
+        output.push_str(&identifier);
+        placeholders.insert(Atom::from(identifier), Atom::from(name));
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    (output, placeholders)
+}
+
+struct Replacer<'a, 'b> {
+    ast: &'b AstBuilder<'a>,
+    /// The names of the placeholders by their identifiers
+    placeholders: FxHashMap<Atom, Atom>,
+    /// The replacements by their names, taken when they are used, and whether they are used
+    replacements: FxHashMap<Atom, (Option<Replacement<'a>>, bool)>,
+    errors: std::vec::Vec<Error>,
+}
+
+impl<'a, 'b> Replacer<'a, 'b> {
+    /// The replacement of `ident`, `None` when it is not a placeholder or when the error of its
+    /// replacement is reported
+    fn replacement(&mut self, ident: &Atom, span: Span) -> Option<Replacement<'a>> {
+        let name = self.placeholders.get(ident)?;
+        let Some((replacement, used)) = self.replacements.get_mut(name) else {
+            self.errors.push(diagnostics::TemplateMissingReplacement(name.clone(), span).into());
+            return None;
+        };
+        *used = true;
+        if let Some(Replacement::Name(name)) = replacement {
+            return Some(Replacement::Name(name.clone()));
+        }
+        let replacement = replacement.take();
+        if replacement.is_none() {
+            self.errors.push(diagnostics::TemplateReusedReplacement(name.clone(), span).into());
+        }
+        replacement
+    }
+
+    /// Renames a placeholder used where only a name fits
+    fn rename(&mut self, name: &mut Atom, span: Span) {
+        match self.replacement(name, span) {
+            Some(Replacement::Name(new_name)) => *name = new_name,
+            Some(_) => {
+                let name = self.placeholders[name].clone();
+                self.errors.push(diagnostics::TemplateExpectedName(name, span).into());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a> for Replacer<'a, 'b> {
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            if let Statement::ExpressionStatement(expr_stmt) = &stmt {
+                if let Expression::Identifier(ident) = &expr_stmt.expression {
+                    if self.placeholders.contains_key(&ident.name) {
+                        let span = ident.span;
+                        match self.replacement(&ident.name.clone(), span) {
+                            Some(Replacement::Statements(replacement)) => {
+                                stmts.extend(replacement);
+                            }
+                            Some(Replacement::Expression(expr)) => {
+                                stmts.push(self.ast.expression_statement(span, expr));
+                            }
+                            Some(Replacement::Name(name)) => {
+                                let expr = self.ast.identifier_expression(span, name);
+                                stmts.push(self.ast.expression_statement(span, expr));
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+                }
+            }
+            self.visit_statement(&mut stmt);
+            stmts.push(stmt);
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        let Expression::Identifier(ident) = expr else {
+            self.visit_expression_match(expr);
+            return;
+        };
+        let span = ident.span;
+        match self.replacement(&ident.name.clone(), span) {
+            Some(Replacement::Expression(replacement)) => *expr = replacement,
+            Some(Replacement::Name(name)) => ident.name = name,
+            Some(Replacement::Statements(_)) => {
+                self.errors.push(diagnostics::TemplateExpectedExpression(span).into());
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &mut IdentifierReference) {
+        self.rename(&mut ident.name, ident.span);
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &mut BindingIdentifier) {
+        self.rename(&mut ident.name, ident.span);
+    }
+
+    fn visit_identifier_name(&mut self, ident: &mut IdentifierName) {
+        self.rename(&mut ident.name, ident.span);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::{ast::*, AstBuilder};
+    use oxc_codegen::{Codegen, CodegenOptions};
+    use oxc_span::{Atom, SourceType, Span};
+
+    use super::{Replacement, Template};
+
+    fn codegen<'a>(ast: &AstBuilder<'a>, body: oxc_allocator::Vec<'a, Statement<'a>>) -> String {
+        let program =
+            ast.program(Span::default(), SourceType::default(), ast.new_vec(), None, body);
+        Codegen::<false>::new(0, CodegenOptions::default()).build(&program)
+    }
+
+    #[test]
+    fn expression() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let template = Template::new(&allocator);
+        let object = ast.identifier_expression(Span::default(), "exports".into());
+        let name = ast.string_expression(Span::default(), "__esModule".into());
+        let expr = template
+            .expression(
+                "Object.defineProperty(%%object%%, %%name%%, { value: %%value%% })",
+                [
+                    ("object", Replacement::Expression(object)),
+                    ("name", Replacement::Expression(name)),
+                    (
+                        "value",
+                        Replacement::Expression(ast.number_expression(Span::default(), -1.0)),
+                    ),
+                ],
+            )
+            .unwrap();
+        let stmt = ast.expression_statement(Span::default(), expr);
+        assert_eq!(
+            codegen(&ast, ast.new_vec_single(stmt)),
+            "Object.defineProperty(exports, '__esModule', {\n\tvalue: -1\n});\n"
+        );
+    }
+
+    #[test]
+    fn statements() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let template = Template::new(&allocator);
+        let mut body = ast.new_vec();
+        body.push(ast.variable_statement(
+            Span::default(),
+            VariableDeclarationKind::Let,
+            "a".into(),
+            Some(ast.call(Span::default(), ast.member_path(Span::default(), "foo.bar"), [])),
+        ));
+        let stmts = template
+            .statements(
+                "function %%name%%() { %%body%%; return %%name%%; }",
+                [
+                    ("name", Replacement::Name(Atom::from("f"))),
+                    ("body", Replacement::Statements(body)),
+                ],
+            )
+            .unwrap();
+        assert_eq!(codegen(&ast, stmts), "function f() {\n\tlet a = foo.bar();\n\treturn f;\n}\n");
+
+        let stmt = template
+            .statement("if (%%test%%) {}", [("test", Replacement::Name("x".into()))])
+            .unwrap();
+        assert_eq!(codegen(&ast, ast.new_vec_single(stmt)), "if (x) {\n}\n");
+    }
+
+    #[test]
+    fn errors() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let template = Template::new(&allocator);
+        let error = |result: Result<Expression, Vec<oxc_diagnostics::Error>>| {
+            result.err().unwrap().iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+        assert_eq!(error(template.expression("%%a%%", [])), ["Missing replacement `a`"]);
+        assert_eq!(
+            error(template.expression(
+                "1",
+                [("a", Replacement::Expression(ast.null_expression(Span::default())))]
+            )),
+            ["Unused replacement `a`"]
+        );
+        assert_eq!(
+            error(template.expression(
+                "%%a%% + %%a%%",
+                [("a", Replacement::Expression(ast.null_expression(Span::default())))]
+            )),
+            ["Replacement `a` is used more than once"]
+        );
+        let result = template.statements(
+            "let %%a%% = 1",
+            [("a", Replacement::Expression(ast.null_expression(Span::default())))],
+        );
+        assert_eq!(
+            result.err().unwrap().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["Replacement `a` is used in the place of a name"]
+        );
+        assert_eq!(
+            error(template.expression("%%a%%", [("a", Replacement::Statements(ast.new_vec()))])),
+            ["Expected an expression"]
+        );
+        assert!(template.statement("a; b", []).is_err());
+        assert!(template.expression("a +", []).is_err());
+    }
+}
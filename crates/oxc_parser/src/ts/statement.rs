@@ -34,7 +34,7 @@ impl<'a> Parser<'a> {
 
         let id = self.parse_binding_identifier()?;
         let members = TSEnumMemberList::parse(self)?.members;
-        Ok(self.ast.ts_enum_declaration(span, id, members, modifiers))
+        Ok(self.ast.ts_enum_declaration(self.end_span(span), id, members, modifiers))
     }
 
     pub(crate) fn parse_ts_enum_member(&mut self) -> Result<TSEnumMember<'a>> {
@@ -111,7 +111,13 @@ impl<'a> Parser<'a> {
         let annotation = self.parse_ts_type()?;
 
         self.asi()?;
-        Ok(self.ast.ts_type_alias_declaration(span, id, annotation, params, modifiers))
+        Ok(self.ast.ts_type_alias_declaration(
+            self.end_span(span),
+            id,
+            annotation,
+            params,
+            modifiers,
+        ))
     }
 
     /** ---------------------  Interface  ------------------------ */
@@ -1,10 +1,11 @@
-use oxc_ast::ast::*;
+use oxc_ast::{ast::*, AstKind};
+use oxc_span::GetSpan;
 
 use crate::{
     binaryish::{BinaryishLeft, BinaryishOperator},
     comments::CommentFlags,
     doc::{Doc, DocBuilder, Group},
-    group, line, ss, Format, Prettier,
+    group, indent, line, softline, ss, Format, Prettier,
 };
 
 pub(super) fn print_binaryish_expression<'a>(
@@ -13,7 +14,101 @@ pub(super) fn print_binaryish_expression<'a>(
     operator: BinaryishOperator,
     right: &Expression<'a>,
 ) -> Doc<'a> {
-    print_binaryish_expressions(p, left, operator, right)
+    let parent_kind = p.parent_kind();
+    let doc = print_binaryish_expressions(p, left, operator, right);
+    let is_inside_parenthesis = matches!(
+        parent_kind,
+        AstKind::IfStatement(_)
+            | AstKind::WhileStatement(_)
+            | AstKind::SwitchStatement(_)
+            | AstKind::DoWhileStatement(_)
+    );
+    if is_inside_parenthesis {
+        return doc;
+    }
+
+    // Break between the parens of `!(a && b)`, `(a && b).c` and `(a && b)()`
+    let node_span = p.current_kind().span();
+    let is_wrapped_operand = match parent_kind {
+        AstKind::CallExpression(call_expr) => call_expr.callee.span() == node_span,
+        AstKind::UnaryExpression(_) => true,
+        AstKind::MemberExpression(member_expr) => !member_expr.is_computed(),
+        _ => false,
+    };
+    if is_wrapped_operand {
+        return group!(p, indent!(p, softline!(), doc), softline!());
+    }
+
+    if operator.is_binary() {
+        return doc;
+    }
+    let Doc::Array(nested_parts) = doc else { return doc };
+    let mut parts = p.vec();
+    flatten_logical_parts(nested_parts, &mut parts);
+
+    let should_not_indent = match parent_kind {
+        AstKind::ReturnStatement(_)
+        | AstKind::ThrowStatement(_)
+        | AstKind::ForStatement(_)
+        | AstKind::TemplateLiteral(_) => true,
+        AstKind::ConditionalExpression(_) => !matches!(
+            p.parent_parent_kind(),
+            Some(
+                AstKind::ReturnStatement(_)
+                    | AstKind::ThrowStatement(_)
+                    | AstKind::CallExpression(_)
+                    | AstKind::MetaProperty(_)
+            )
+        ),
+        AstKind::JSXExpressionContainer(_) => {
+            matches!(p.parent_parent_kind(), Some(AstKind::JSXAttributeItem(_)))
+        }
+        _ => false,
+    };
+    let should_indent_if_inlining = matches!(
+        parent_kind,
+        AstKind::AssignmentExpression(_)
+            | AstKind::VariableDeclarator(_)
+            | AstKind::PropertyDefinition(_)
+            | AstKind::ObjectProperty(_)
+    );
+    let same_precedence_sub_expression =
+        left.operator().is_some_and(|left_operator| operator.should_flatten(left_operator));
+    let should_inline = should_inline_logical_right(operator, right);
+    if should_not_indent
+        || (should_inline && !same_precedence_sub_expression)
+        || (!should_inline && should_indent_if_inlining)
+    {
+        return Doc::Group(Group::new(parts, false));
+    }
+
+    // Indent the operators of the chain after the first operand
+    let first_group_index = parts.iter().position(|part| matches!(part, Doc::Group(_)));
+    let head_len = first_group_index.map_or(1, |index| index + 1).min(parts.len());
+    let mut head = p.vec();
+    let mut rest = p.vec();
+    for (i, part) in parts.into_iter().enumerate() {
+        if i < head_len {
+            head.push(part);
+        } else {
+            rest.push(part);
+        }
+    }
+    head.push(Doc::Indent(rest));
+    Doc::Group(Group::new(head, false))
+}
+
+/// `a && b && c` is printed as the nested parts `[[a, " ", [&&, line, b]], " ", [&&, line, c]]`
+fn flatten_logical_parts<'a>(
+    nested_parts: oxc_allocator::Vec<'a, Doc<'a>>,
+    parts: &mut oxc_allocator::Vec<'a, Doc<'a>>,
+) {
+    for (i, part) in nested_parts.into_iter().enumerate() {
+        match part {
+            Doc::Array(nested) if i == 0 => flatten_logical_parts(nested, parts),
+            part => parts.push(part),
+        }
+    }
 }
 
 fn print_binaryish_expressions<'a>(
@@ -37,7 +132,7 @@ fn print_binaryish_expressions<'a>(
     } else {
         parts.push(group!(p, left.format(p)));
     }
-    let should_inline = should_inline_logical_expression(right);
+    let should_inline = should_inline_logical_right(operator, right);
     let mut right_parts = p.vec();
     right_parts.push(ss!(operator.as_str()));
     right_parts.push(if should_inline { ss!(" ") } else { line!() });
@@ -62,20 +157,28 @@ fn print_binaryish_expressions<'a>(
 
 pub(super) fn should_inline_logical_expression(expr: &Expression) -> bool {
     let Expression::LogicalExpression(logical_expr) = expr else { return false };
+    should_inline_logical_right(logical_expr.operator.into(), &logical_expr.right)
+}
+
+/// `a || {}` and `a && [b]` keep the object and the array after the operator
+fn should_inline_logical_right(operator: BinaryishOperator, right: &Expression) -> bool {
+    if operator.is_binary() {
+        return false;
+    }
 
-    if let Expression::ObjectExpression(obj_expr) = &logical_expr.right {
+    if let Expression::ObjectExpression(obj_expr) = right {
         if obj_expr.properties.len() > 0 {
             return true;
         }
     }
 
-    if let Expression::ArrayExpression(array_expr) = &logical_expr.right {
+    if let Expression::ArrayExpression(array_expr) = right {
         if array_expr.elements.len() > 0 {
             return true;
         }
     }
 
-    if matches!(logical_expr.right, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
+    if matches!(right, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
         return true;
     }
 
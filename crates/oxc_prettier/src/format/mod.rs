@@ -32,6 +32,7 @@ use oxc_syntax::identifier::is_identifier_name;
 
 use crate::{
     array,
+    comments::CommentFlags,
     doc::{Doc, DocBuilder, Group, Separator},
     format, group, hardline, indent, line, softline, ss, string, wrap, Prettier,
 };
@@ -52,6 +53,13 @@ where
     }
 }
 
+impl<'a> Prettier<'a> {
+    /// The statements of a range formatting, see [Prettier::build_range]
+    pub(crate) fn print_statements(&mut self, stmts: &[Statement<'a>]) -> Doc<'a> {
+        Doc::Array(statement::print_statement_sequence(self, stmts, false, false))
+    }
+}
+
 impl<'a> Format<'a> for Program<'a> {
     fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
         p.enter_node(AstKind::Program(p.alloc(self)));
@@ -163,7 +171,12 @@ impl<'a> Format<'a> for IfStatement<'a> {
             parts.push(opening);
 
             if let Some(alternate) = &self.alternate {
-                let else_on_same_line = matches!(alternate, Statement::BlockStatement(_));
+                let comment_on_own_line = p.has_comment(
+                    self.consequent.span(),
+                    CommentFlags::Trailing | CommentFlags::Line,
+                );
+                let else_on_same_line =
+                    matches!(self.consequent, Statement::BlockStatement(_)) && !comment_on_own_line;
                 if else_on_same_line {
                     parts.push(ss!(" "));
                 } else {
@@ -182,7 +195,7 @@ impl<'a> Format<'a> for IfStatement<'a> {
                 ));
             }
 
-            Doc::Array(parts)
+            Doc::Group(Group::new(parts, false))
         })
     }
 }
@@ -1625,17 +1638,12 @@ impl<'a> Format<'a> for UnaryExpression<'a> {
 impl<'a> Format<'a> for BinaryExpression<'a> {
     fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
         wrap!(p, self, BinaryExpression, {
-            let doc = binaryish::print_binaryish_expression(
+            binaryish::print_binaryish_expression(
                 p,
                 (&self.left).into(),
                 self.operator.into(),
                 &self.right,
-            );
-            if misc::in_parentheses(p.parent_kind(), p.source_text, self.span) {
-                group!(p, indent!(p, softline!(), doc), softline!())
-            } else {
-                doc
-            }
+            )
         })
     }
 }
@@ -1656,18 +1664,12 @@ impl<'a> Format<'a> for PrivateInExpression<'a> {
 impl<'a> Format<'a> for LogicalExpression<'a> {
     fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
         wrap!(p, self, LogicalExpression, {
-            let doc = binaryish::print_binaryish_expression(
+            binaryish::print_binaryish_expression(
                 p,
                 (&self.left).into(),
                 self.operator.into(),
                 &self.right,
-            );
-
-            if misc::in_parentheses(p.parent_kind(), p.source_text, self.span) {
-                group!(p, indent!(p, softline!(), doc), softline!())
-            } else {
-                doc
-            }
+            )
         })
     }
 }
@@ -12,6 +12,7 @@ mod macros;
 mod needs_parens;
 mod options;
 mod printer;
+mod range;
 mod utils;
 
 use std::{iter::Peekable, vec};
@@ -23,7 +24,10 @@ use oxc_syntax::identifier::is_line_terminator;
 
 use crate::{doc::Doc, doc::DocBuilder, format::Format, printer::Printer};
 
-pub use crate::options::{ArrowParens, EndOfLine, PrettierOptions, QuoteProps, TrailingComma};
+pub use crate::{
+    options::{ArrowParens, EndOfLine, PrettierOptions, QuoteProps, TrailingComma},
+    range::{lines_to_span, FormattedRange},
+};
 
 type GroupId = u32;
 #[derive(Default)]
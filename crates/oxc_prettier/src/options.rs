@@ -81,7 +81,7 @@ impl Default for PrettierOptions {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum EndOfLine {
     #[default]
     Lf,
@@ -114,7 +114,7 @@ impl FromStr for EndOfLine {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum QuoteProps {
     /// Only add quotes around object properties where required.
     #[default]
@@ -185,7 +185,7 @@ impl FromStr for TrailingComma {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ArrowParens {
     /// Always include parens. `Example: (x) => x`
     #[default]
@@ -0,0 +1,230 @@
+//! Range Formatting
+//!
+//! References:
+//! * <https://github.com/prettier/prettier/blob/main/src/main/range-util.js>
+
+use std::ops::Range;
+
+use oxc_allocator::Vec;
+use oxc_ast::{
+    ast::{Program, Statement},
+    AstKind, Visit,
+};
+use oxc_span::{GetSpan, Span};
+use oxc_syntax::identifier::is_line_terminator;
+
+use crate::{comments::Comment, doc::Doc, indent, printer::Printer, Prettier};
+
+/// The formatted code for the part of the source text at `span`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedRange {
+    /// The span of the source text to be replaced by `text`, which covers the formatted
+    /// statements and their comments
+    pub span: Span,
+    /// The formatted statements, the lines after the first one are indented by the indentation
+    /// of the first line in the source text
+    pub text: String,
+}
+
+/// The span of the zero based `lines` of `source_text`, the end of the range is exclusive.
+pub fn lines_to_span(source_text: &str, lines: Range<usize>) -> Span {
+    let line_start = |line: usize| {
+        if line == 0 {
+            return 0;
+        }
+        source_text
+            .match_indices('\n')
+            .nth(line - 1)
+            .map_or(source_text.len(), |(offset, _)| offset + 1)
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    Span::new(line_start(lines.start) as u32, line_start(lines.end.max(lines.start)) as u32)
+}
+
+impl<'a> Prettier<'a> {
+    /// Format the part of `program` around `range`, which is the smallest sequence of statements
+    /// of the innermost statement list that covers the range, e.g. a selection in an editor.
+    ///
+    /// Returns `None` when no statement overlaps `range`.
+    pub fn build_range(mut self, program: &Program<'a>, range: Span) -> Option<FormattedRange> {
+        let range = self.trim_range(range);
+        let mut finder = StatementsFinder { range, nodes: vec![], found: None };
+        finder.visit_program(program);
+        let FoundStatements { stmts, start, end, ancestors } = finder.found?;
+
+        let first = stmts[start].span();
+        let last = stmts[end].span();
+        let parent = ancestors.last().map(GetSpan::span);
+
+        // Only the comments around the statements are printed, the comments before them belong to
+        // the previous statement and the comments after them belong to the next statement.
+        #[allow(clippy::cast_possible_truncation)]
+        let source_len = self.source_text.len() as u32;
+        let lower =
+            if start > 0 { stmts[start - 1].span().end } else { parent.map_or(0, |p| p.start) };
+        let upper = stmts
+            .get(end + 1)
+            .map_or_else(|| parent.map_or(source_len, |p| p.end), |stmt| stmt.span().start);
+        let comments = self
+            .trivias
+            .clone()
+            .filter(|&(start_offset, end_offset, kind)| {
+                let comment = Comment::new(start_offset, end_offset, kind);
+                lower <= comment.start
+                    && comment.end <= upper
+                    && (start == 0 || self.has_line_terminator(lower, comment.start))
+                    && (comment.end <= last.end
+                        || !self.has_line_terminator(last.end, comment.start))
+            })
+            .collect::<std::vec::Vec<_>>();
+        let number_of_comments = comments.len();
+        self.trivias = comments.clone().into_iter().peekable();
+        self.nodes = ancestors;
+
+        let doc = self.print_statements(&stmts.as_slice()[start..=end]);
+
+        // The replaced span is extended to the comments which have been printed
+        let printed = &comments[..number_of_comments - self.trivias.len()];
+        let printed_span = |&(start, end, kind)| {
+            let comment = Comment::new(start, end, kind);
+            Span::new(comment.start, comment.end)
+        };
+        let span_start =
+            printed.first().map_or(first.start, |c| printed_span(c).start.min(first.start));
+        let span_end = printed.last().map_or(last.end, |c| printed_span(c).end.max(last.end));
+
+        let indentation = self.line_indentation(span_start);
+        let doc = self.indent_to(indentation, doc);
+        let text = Printer::new(doc, self.source_text, self.options, self.allocator).build();
+        let text = text.strip_prefix(indentation).unwrap_or(&text).trim_end().to_string();
+
+        Some(FormattedRange { span: Span::new(span_start, span_end), text })
+    }
+
+    fn has_line_terminator(&self, start: u32, end: u32) -> bool {
+        self.source_text[start as usize..end as usize].chars().any(is_line_terminator)
+    }
+
+    /// The whitespace before `offset` on its line, or an empty string when there is code before
+    fn line_indentation(&self, offset: u32) -> &'a str {
+        let before = &self.source_text[..offset as usize];
+        let line_start = before.rfind(is_line_terminator).map_or(0, |i| i + 1);
+        let indentation = &before[line_start..];
+        if indentation.chars().all(|c| c == ' ' || c == '\t') {
+            indentation
+        } else {
+            ""
+        }
+    }
+
+    /// Starts the doc at the column of `indentation`, and indents its lines by the levels of
+    /// indentation it contains
+    fn indent_to(&self, indentation: &'a str, doc: Doc<'a>) -> Doc<'a> {
+        let width = indentation
+            .chars()
+            .map(|c| if c == '\t' { self.options.tab_width } else { 1 })
+            .sum::<usize>();
+        let mut doc = doc;
+        for _ in 0..width / self.options.tab_width.max(1) {
+            doc = indent!(self, doc);
+        }
+        let mut parts = Vec::new_in(self.allocator);
+        parts.push(Doc::Str(indentation));
+        parts.push(doc);
+        Doc::Array(parts)
+    }
+}
+
+impl<'a> Prettier<'a> {
+    /// Shrinks `range` to the code it contains, without the whitespace and the comments at its
+    /// ends, so that the selected lines are covered by the statements on them
+    fn trim_range(&self, range: Span) -> Span {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut end = range.end.min(self.source_text.len() as u32);
+        let mut start = range.start.min(end);
+        let comments = self
+            .trivias
+            .clone()
+            .map(|(start, end, kind)| Comment::new(start, end, kind))
+            .collect::<std::vec::Vec<_>>();
+        loop {
+            let text = &self.source_text[start as usize..end as usize];
+            #[allow(clippy::cast_possible_truncation)]
+            let trimmed_start = start + (text.len() - text.trim_start().len()) as u32;
+            #[allow(clippy::cast_possible_truncation)]
+            let trimmed_end = end - (text.len() - text.trim_end().len()) as u32;
+            let (mut new_start, mut new_end) = (trimmed_start, trimmed_end.max(trimmed_start));
+            for comment in &comments {
+                if comment.start == new_start && comment.end <= new_end {
+                    new_start = comment.end;
+                }
+                if comment.end == new_end && new_start <= comment.start {
+                    new_end = comment.start;
+                }
+            }
+            if (new_start, new_end) == (start, end) {
+                return Span::new(start, end);
+            }
+            (start, end) = (new_start, new_end);
+        }
+    }
+}
+
+struct FoundStatements<'a> {
+    /// The statement list which contains the statements to be formatted
+    stmts: &'a Vec<'a, Statement<'a>>,
+    /// The index of the first statement to be formatted
+    start: usize,
+    /// The index of the last statement to be formatted
+    end: usize,
+    /// The nodes from the [Program] to the parent of the statement list
+    ancestors: std::vec::Vec<AstKind<'a>>,
+}
+
+/// Finds the innermost statement list whose statements cover the range
+struct StatementsFinder<'a> {
+    range: Span,
+    nodes: std::vec::Vec<AstKind<'a>>,
+    found: Option<FoundStatements<'a>>,
+}
+
+impl<'a> StatementsFinder<'a> {
+    fn overlaps(&self, span: Span) -> bool {
+        if self.range.size() == 0 {
+            span.start <= self.range.start && self.range.start <= span.end
+        } else {
+            span.start < self.range.end && self.range.start < span.end
+        }
+    }
+}
+
+impl<'a> Visit<'a> for StatementsFinder<'a> {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        self.nodes.push(kind);
+    }
+
+    fn leave_node(&mut self, _kind: AstKind<'a>) {
+        self.nodes.pop();
+    }
+
+    fn visit_statements(&mut self, stmts: &Vec<'a, Statement<'a>>) {
+        let Some(start) = stmts.iter().position(|stmt| self.overlaps(stmt.span())) else {
+            return;
+        };
+        let end = stmts.iter().rposition(|stmt| self.overlaps(stmt.span())).unwrap_or(start);
+        let covers = stmts[start].span().start <= self.range.start
+            && self.range.end <= stmts[end].span().end;
+        // A nested statement list replaces the outer one only when the range is inside of it
+        if self.found.is_none() || covers {
+            self.found = Some(FoundStatements {
+                stmts: self.alloc(stmts),
+                start,
+                end,
+                ancestors: self.nodes.clone(),
+            });
+        }
+        if start == end && covers {
+            self.visit_statement(&stmts[start]);
+        }
+    }
+}
@@ -1,6 +1,11 @@
 //! Semantic Builder
 
-use std::{cell::RefCell, path::PathBuf, rc::Rc, sync::Arc};
+use std::{
+    cell::{OnceCell, RefCell},
+    path::PathBuf,
+    rc::Rc,
+    sync::Arc,
+};
 
 use itertools::Itertools;
 #[allow(clippy::wildcard_imports)]
@@ -14,6 +19,7 @@ use crate::{
     binder::Binder,
     checker::{EarlyErrorJavaScript, EarlyErrorTypeScript},
     class::ClassTableBuilder,
+    control_flow::{ControlFlowGraphBuilder, EdgeType},
     diagnostics::Redeclaration,
     jsdoc::JSDocBuilder,
     module_record::ModuleRecordBuilder,
@@ -87,6 +93,8 @@ pub struct SemanticBuilder<'a> {
 
     redeclare_variables: RedeclareVariables,
     pub class_table_builder: ClassTableBuilder,
+
+    cfg: ControlFlowGraphBuilder,
 }
 
 pub struct SemanticBuilderReturn<'a> {
@@ -121,6 +129,7 @@ impl<'a> SemanticBuilder<'a> {
             check_syntax_error: false,
             redeclare_variables: RedeclareVariables { variables: vec![] },
             class_table_builder: ClassTableBuilder::new(),
+            cfg: ControlFlowGraphBuilder::new(),
         }
     }
 
@@ -175,10 +184,12 @@ impl<'a> SemanticBuilder<'a> {
             scopes: self.scope,
             symbols: self.symbols,
             classes: self.class_table_builder.build(),
+            cfg: self.cfg.build(),
             module_record: Arc::clone(&self.module_record),
             jsdoc: self.jsdoc.build(),
             unused_labels: self.unused_labels.labels,
             redeclare_variables: self.redeclare_variables.variables,
+            call_graph: OnceCell::new(),
         };
         SemanticBuilderReturn { semantic, errors: self.errors.into_inner() }
     }
@@ -192,10 +203,12 @@ impl<'a> SemanticBuilder<'a> {
             scopes: self.scope,
             symbols: self.symbols,
             classes: self.class_table_builder.build(),
+            cfg: self.cfg.build(),
             module_record: Arc::new(ModuleRecord::default()),
             jsdoc: self.jsdoc.build(),
             unused_labels: self.unused_labels.labels,
             redeclare_variables: self.redeclare_variables.variables,
+            call_graph: OnceCell::new(),
         }
     }
 
@@ -213,6 +226,7 @@ impl<'a> SemanticBuilder<'a> {
         let parent_node_id =
             if matches!(kind, AstKind::Program(_)) { None } else { Some(self.current_node_id) };
         self.current_node_id = self.nodes.add_node(ast_node, parent_node_id);
+        self.cfg.add_element(self.current_node_id);
     }
 
     fn pop_ast_node(&mut self) {
@@ -407,6 +421,319 @@ impl<'a> Visit<'a> for SemanticBuilder<'a> {
         self.leave_kind(kind);
         self.pop_ast_node();
     }
+
+    /* ----------  Control flow ---------- */
+
+    fn visit_if_statement(&mut self, stmt: &IfStatement<'a>) {
+        let kind = AstKind::IfStatement(self.alloc(stmt));
+        self.enter_node(kind);
+        self.visit_expression(&stmt.test);
+        let test_block = self.cfg.current_block();
+        let after_block = self.cfg.new_block();
+
+        let consequent_block = self.cfg.new_block();
+        self.cfg.add_edge(test_block, consequent_block, EdgeType::Normal);
+        self.cfg.switch_to(consequent_block);
+        self.visit_statement(&stmt.consequent);
+        self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+
+        if let Some(alternate) = &stmt.alternate {
+            let alternate_block = self.cfg.new_block();
+            self.cfg.add_edge(test_block, alternate_block, EdgeType::Normal);
+            self.cfg.switch_to(alternate_block);
+            self.visit_statement(alternate);
+            self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+        } else {
+            self.cfg.add_edge(test_block, after_block, EdgeType::Normal);
+        }
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+    }
+
+    fn visit_labeled_statement(&mut self, stmt: &LabeledStatement<'a>) {
+        let kind = AstKind::LabeledStatement(self.alloc(stmt));
+        self.enter_node(kind);
+        self.visit_label_identifier(&stmt.label);
+        if matches!(
+            stmt.body,
+            Statement::WhileStatement(_)
+                | Statement::DoWhileStatement(_)
+                | Statement::ForStatement(_)
+                | Statement::ForInStatement(_)
+                | Statement::ForOfStatement(_)
+        ) {
+            // The loop pushes the jump target so `continue label` jumps to the loop.
+            self.cfg.set_pending_label(stmt.label.name.clone());
+            self.visit_statement(&stmt.body);
+        } else {
+            let after_block = self.cfg.new_block();
+            self.cfg.push_label_target(stmt.label.name.clone(), after_block);
+            self.visit_statement(&stmt.body);
+            self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+            self.cfg.pop_jump_target();
+            self.cfg.switch_to(after_block);
+        }
+        self.leave_node(kind);
+    }
+
+    fn visit_while_statement(&mut self, stmt: &WhileStatement<'a>) {
+        let kind = AstKind::WhileStatement(self.alloc(stmt));
+        let label = self.cfg.take_pending_label();
+        self.enter_node(kind);
+
+        let header_block = self.cfg.new_block();
+        self.cfg.add_edge(self.cfg.current_block(), header_block, EdgeType::Normal);
+        self.cfg.switch_to(header_block);
+        self.visit_expression(&stmt.test);
+        let test_block = self.cfg.current_block();
+
+        let body_block = self.cfg.new_block();
+        let after_block = self.cfg.new_block();
+        self.cfg.add_edge(test_block, body_block, EdgeType::Normal);
+        if !is_always_true(&stmt.test) {
+            self.cfg.add_edge(test_block, after_block, EdgeType::Normal);
+        }
+
+        self.cfg.push_jump_target(label, after_block, Some(header_block));
+        self.cfg.switch_to(body_block);
+        self.visit_statement(&stmt.body);
+        self.cfg.add_edge(self.cfg.current_block(), header_block, EdgeType::Backedge);
+        self.cfg.pop_jump_target();
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+    }
+
+    fn visit_do_while_statement(&mut self, stmt: &DoWhileStatement<'a>) {
+        let kind = AstKind::DoWhileStatement(self.alloc(stmt));
+        let label = self.cfg.take_pending_label();
+        self.enter_node(kind);
+
+        let body_block = self.cfg.new_block();
+        self.cfg.add_edge(self.cfg.current_block(), body_block, EdgeType::Normal);
+        let test_block = self.cfg.new_block();
+        let after_block = self.cfg.new_block();
+
+        self.cfg.push_jump_target(label, after_block, Some(test_block));
+        self.cfg.switch_to(body_block);
+        self.visit_statement(&stmt.body);
+        self.cfg.add_edge(self.cfg.current_block(), test_block, EdgeType::Normal);
+        self.cfg.pop_jump_target();
+
+        self.cfg.switch_to(test_block);
+        self.visit_expression(&stmt.test);
+        self.cfg.add_edge(self.cfg.current_block(), body_block, EdgeType::Backedge);
+        if !is_always_true(&stmt.test) {
+            self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+        }
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+    }
+
+    fn visit_for_statement(&mut self, stmt: &ForStatement<'a>) {
+        let kind = AstKind::ForStatement(self.alloc(stmt));
+        let label = self.cfg.take_pending_label();
+        let is_lexical_declaration =
+            stmt.init.as_ref().is_some_and(ForStatementInit::is_lexical_declaration);
+        if is_lexical_declaration {
+            self.enter_scope(ScopeFlags::empty());
+        }
+        self.enter_node(kind);
+        if let Some(init) = &stmt.init {
+            self.visit_for_statement_init(init);
+        }
+
+        let header_block = self.cfg.new_block();
+        self.cfg.add_edge(self.cfg.current_block(), header_block, EdgeType::Normal);
+        self.cfg.switch_to(header_block);
+        if let Some(test) = &stmt.test {
+            self.visit_expression(test);
+        }
+        let test_block = self.cfg.current_block();
+
+        let body_block = self.cfg.new_block();
+        let update_block = self.cfg.new_block();
+        let after_block = self.cfg.new_block();
+        self.cfg.add_edge(test_block, body_block, EdgeType::Normal);
+        if stmt.test.as_ref().is_some_and(|test| !is_always_true(test)) {
+            self.cfg.add_edge(test_block, after_block, EdgeType::Normal);
+        }
+
+        // The body is visited before the update to keep the nodes in source order, the update
+        // block is already allocated for the `continue`s of the body
+        self.cfg.push_jump_target(label, after_block, Some(update_block));
+        self.cfg.switch_to(body_block);
+        self.visit_statement(&stmt.body);
+        self.cfg.add_edge(self.cfg.current_block(), update_block, EdgeType::Normal);
+        self.cfg.pop_jump_target();
+
+        self.cfg.switch_to(update_block);
+        if let Some(update) = &stmt.update {
+            self.visit_expression(update);
+        }
+        self.cfg.add_edge(self.cfg.current_block(), header_block, EdgeType::Backedge);
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+        if is_lexical_declaration {
+            self.leave_scope();
+        }
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &ForInStatement<'a>) {
+        let kind = AstKind::ForInStatement(self.alloc(stmt));
+        let label = self.cfg.take_pending_label();
+        let is_lexical_declaration = stmt.left.is_lexical_declaration();
+        if is_lexical_declaration {
+            self.enter_scope(ScopeFlags::empty());
+        }
+        self.enter_node(kind);
+        self.visit_for_each(label, &stmt.left, &stmt.right, &stmt.body);
+        self.leave_node(kind);
+        if is_lexical_declaration {
+            self.leave_scope();
+        }
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        let kind = AstKind::ForOfStatement(self.alloc(stmt));
+        let label = self.cfg.take_pending_label();
+        let is_lexical_declaration = stmt.left.is_lexical_declaration();
+        if is_lexical_declaration {
+            self.enter_scope(ScopeFlags::empty());
+        }
+        self.enter_node(kind);
+        self.visit_for_each(label, &stmt.left, &stmt.right, &stmt.body);
+        self.leave_node(kind);
+        if is_lexical_declaration {
+            self.leave_scope();
+        }
+    }
+
+    fn visit_switch_statement(&mut self, stmt: &SwitchStatement<'a>) {
+        let kind = AstKind::SwitchStatement(self.alloc(stmt));
+        self.enter_scope(ScopeFlags::empty());
+        self.enter_node(kind);
+        self.visit_expression(&stmt.discriminant);
+        let discriminant_block = self.cfg.current_block();
+        let after_block = self.cfg.new_block();
+
+        self.cfg.push_jump_target(None, after_block, None);
+        let mut fallthrough_block = None;
+        for case in &stmt.cases {
+            let case_block = self.cfg.new_block();
+            self.cfg.add_edge(discriminant_block, case_block, EdgeType::Normal);
+            if let Some(fallthrough_block) = fallthrough_block {
+                self.cfg.add_edge(fallthrough_block, case_block, EdgeType::Normal);
+            }
+            self.cfg.switch_to(case_block);
+            self.visit_switch_case(case);
+            fallthrough_block = Some(self.cfg.current_block());
+        }
+        if !stmt.cases.iter().any(SwitchCase::is_default_case) {
+            self.cfg.add_edge(discriminant_block, after_block, EdgeType::Normal);
+        }
+        if let Some(fallthrough_block) = fallthrough_block {
+            self.cfg.add_edge(fallthrough_block, after_block, EdgeType::Normal);
+        }
+        self.cfg.pop_jump_target();
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+        self.leave_scope();
+    }
+
+    fn visit_try_statement(&mut self, stmt: &TryStatement<'a>) {
+        let kind = AstKind::TryStatement(self.alloc(stmt));
+        self.enter_node(kind);
+
+        let after_block = self.cfg.new_block();
+        let finally_block = stmt.finalizer.as_ref().map(|_| self.cfg.new_block());
+        let catch_block = stmt.handler.as_ref().map(|_| self.cfg.new_block());
+        if let (Some(catch_block), Some(finally_block)) = (catch_block, finally_block) {
+            self.cfg.add_edge(catch_block, finally_block, EdgeType::Error);
+        }
+        if let Some(finally_block) = finally_block {
+            self.cfg.push_finally_handler(finally_block);
+        }
+        if let Some(catch_block) = catch_block {
+            self.cfg.push_catch_handler(catch_block);
+        }
+
+        let try_block = self.cfg.new_block();
+        self.cfg.add_edge(self.cfg.current_block(), try_block, EdgeType::Normal);
+        self.cfg.switch_to(try_block);
+        self.visit_block_statement(&stmt.block);
+        let mut completion_blocks = vec![self.cfg.current_block()];
+
+        if let (Some(handler), Some(catch_block)) = (&stmt.handler, catch_block) {
+            self.cfg.pop_catch_handler();
+            self.cfg.switch_to(catch_block);
+            self.visit_catch_clause(handler);
+            completion_blocks.push(self.cfg.current_block());
+        }
+
+        let normal_completion = completion_blocks.iter().any(|block| self.cfg.is_reachable(*block));
+        let completion_target = finally_block.unwrap_or(after_block);
+        for block in completion_blocks {
+            self.cfg.add_edge(block, completion_target, EdgeType::Normal);
+        }
+
+        if let (Some(finalizer), Some(finally_block)) = (&stmt.finalizer, finally_block) {
+            let finally = self.cfg.pop_finally_handler();
+            self.cfg.switch_to(finally_block);
+            self.visit_finally_clause(finalizer);
+            if let Some(finally) = finally {
+                self.cfg.resume_after_finally(finally, normal_completion, after_block);
+            }
+        }
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+    }
+
+    fn visit_conditional_expression(&mut self, expr: &ConditionalExpression<'a>) {
+        let kind = AstKind::ConditionalExpression(self.alloc(expr));
+        self.enter_node(kind);
+        self.visit_expression(&expr.test);
+        let test_block = self.cfg.current_block();
+        let after_block = self.cfg.new_block();
+
+        let consequent_block = self.cfg.new_block();
+        self.cfg.add_edge(test_block, consequent_block, EdgeType::Normal);
+        self.cfg.switch_to(consequent_block);
+        self.visit_expression(&expr.consequent);
+        self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+
+        let alternate_block = self.cfg.new_block();
+        self.cfg.add_edge(test_block, alternate_block, EdgeType::Normal);
+        self.cfg.switch_to(alternate_block);
+        self.visit_expression(&expr.alternate);
+        self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+    }
+
+    fn visit_logical_expression(&mut self, expr: &LogicalExpression<'a>) {
+        let kind = AstKind::LogicalExpression(self.alloc(expr));
+        self.enter_node(kind);
+        self.visit_expression(&expr.left);
+        let left_block = self.cfg.current_block();
+        let after_block = self.cfg.new_block();
+
+        let right_block = self.cfg.new_block();
+        self.cfg.add_edge(left_block, right_block, EdgeType::Normal);
+        self.cfg.switch_to(right_block);
+        self.visit_expression(&expr.right);
+        self.cfg.add_edge(self.cfg.current_block(), after_block, EdgeType::Normal);
+        self.cfg.add_edge(left_block, after_block, EdgeType::Normal);
+
+        self.cfg.switch_to(after_block);
+        self.leave_node(kind);
+    }
 }
 
 impl<'a> SemanticBuilder<'a> {
@@ -422,15 +749,20 @@ impl<'a> SemanticBuilder<'a> {
             }
             AstKind::Function(func) => {
                 self.function_stack.push(self.current_node_id);
+                self.cfg.enter_function(self.current_node_id);
                 func.bind(self);
                 self.add_current_node_id_to_current_scope();
                 self.make_all_namespaces_valuelike();
             }
             AstKind::ArrowExpression(_) => {
                 self.function_stack.push(self.current_node_id);
+                self.cfg.enter_function(self.current_node_id);
                 self.add_current_node_id_to_current_scope();
                 self.make_all_namespaces_valuelike();
             }
+            AstKind::StaticBlock(_) => {
+                self.cfg.enter_function(self.current_node_id);
+            }
             AstKind::Class(class) => {
                 self.current_node_flags |= NodeFlags::Class;
                 class.bind(self);
@@ -443,6 +775,13 @@ impl<'a> SemanticBuilder<'a> {
                     &self.nodes,
                 );
             }
+            AstKind::MemberExpression(member) => {
+                self.class_table_builder.add_member_reference(
+                    member,
+                    self.current_node_id,
+                    &self.nodes,
+                );
+            }
             AstKind::PrivateIdentifier(ident) => {
                 self.class_table_builder.add_private_identifier_reference(
                     ident,
@@ -530,7 +869,6 @@ impl<'a> SemanticBuilder<'a> {
         }
     }
 
-    #[allow(clippy::single_match)]
     fn leave_kind(&mut self, kind: AstKind<'a>) {
         match kind {
             AstKind::Class(_) => {
@@ -549,6 +887,22 @@ impl<'a> SemanticBuilder<'a> {
             }
             AstKind::Function(_) | AstKind::ArrowExpression(_) => {
                 self.function_stack.pop();
+                self.cfg.leave_function();
+            }
+            AstKind::StaticBlock(_) => {
+                self.cfg.leave_function();
+            }
+            AstKind::ReturnStatement(_) => {
+                self.cfg.add_return();
+            }
+            AstKind::ThrowStatement(_) => {
+                self.cfg.add_throw();
+            }
+            AstKind::BreakStatement(stmt) => {
+                self.cfg.add_break(stmt.label.as_ref().map(|label| &label.name));
+            }
+            AstKind::ContinueStatement(stmt) => {
+                self.cfg.add_continue(stmt.label.as_ref().map(|label| &label.name));
             }
             AstKind::TSModuleBlock(_) => {
                 self.namespace_stack.pop();
@@ -565,6 +919,36 @@ impl<'a> SemanticBuilder<'a> {
         }
     }
 
+    /// Shared control flow of `for...in` and `for...of` loops.
+    /// The iteration variable is assigned in the loop header on every iteration.
+    fn visit_for_each(
+        &mut self,
+        label: Option<Atom>,
+        left: &ForStatementLeft<'a>,
+        right: &Expression<'a>,
+        body: &Statement<'a>,
+    ) {
+        let header_block = self.cfg.new_block();
+        self.cfg.add_edge(self.cfg.current_block(), header_block, EdgeType::Normal);
+        self.cfg.switch_to(header_block);
+        self.visit_for_statement_left(left);
+        self.visit_expression(right);
+        let test_block = self.cfg.current_block();
+
+        let body_block = self.cfg.new_block();
+        let after_block = self.cfg.new_block();
+        self.cfg.add_edge(test_block, body_block, EdgeType::Normal);
+        self.cfg.add_edge(test_block, after_block, EdgeType::Normal);
+
+        self.cfg.push_jump_target(label, after_block, Some(header_block));
+        self.cfg.switch_to(body_block);
+        self.visit_statement(body);
+        self.cfg.add_edge(self.cfg.current_block(), header_block, EdgeType::Backedge);
+        self.cfg.pop_jump_target();
+
+        self.cfg.switch_to(after_block);
+    }
+
     fn add_current_node_id_to_current_scope(&mut self) {
         self.scope.add_node_id(self.current_scope_id, self.current_node_id);
     }
@@ -689,3 +1073,8 @@ impl<'a> SemanticBuilder<'a> {
         }
     }
 }
+
+/// Loops with a `true` test can only be exited with a jump.
+fn is_always_true(expr: &Expression) -> bool {
+    matches!(expr.get_inner_expression(), Expression::BooleanLiteral(lit) if lit.value)
+}
@@ -0,0 +1,220 @@
+//! Intra-module call graph
+//!
+//! Calls are resolved statically through the symbol table:
+//! * `foo()` where `foo` is a function declaration, or a never reassigned variable initialized
+//!   with a function or arrow function expression
+//! * immediately invoked function and arrow function expressions
+//! * `this.method()` and `this.#method()` inside of a class method
+//! * `Foo.method()` where `Foo` is a class and `method` is a static method
+//!
+//! Calls which cannot be resolved, such as calls to imports, globals or computed members, are
+//! not part of the graph.
+
+use oxc_ast::{
+    ast::{Class, ClassElement, Expression, IdentifierReference, MemberExpression},
+    AstKind,
+};
+use oxc_span::{Atom, GetSpan, Span};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{AstNodeId, Reference, Semantic};
+
+/// A call expression whose callee was resolved to a function in the same module.
+#[derive(Debug, Clone, Copy)]
+pub struct CallSite {
+    /// The `CallExpression` node
+    pub node_id: AstNodeId,
+    /// The function containing the call, or the `Program` node for top-level calls
+    pub caller: AstNodeId,
+    /// The `Function` or `ArrowExpression` node being called
+    pub callee: AstNodeId,
+}
+
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    /// All `Function` and `ArrowExpression` nodes, in source order
+    functions: Vec<AstNodeId>,
+    call_sites: Vec<CallSite>,
+    callees: FxHashMap<AstNodeId, Vec<AstNodeId>>,
+    callers: FxHashMap<AstNodeId, Vec<AstNodeId>>,
+}
+
+impl CallGraph {
+    pub fn new(semantic: &Semantic) -> Self {
+        CallGraphBuilder::new(semantic).build()
+    }
+
+    pub fn functions(&self) -> &[AstNodeId] {
+        &self.functions
+    }
+
+    pub fn call_sites(&self) -> &[CallSite] {
+        &self.call_sites
+    }
+
+    /// Functions called directly from `caller`, which is a function or the `Program` node
+    pub fn callees(&self, caller: AstNodeId) -> &[AstNodeId] {
+        self.callees.get(&caller).map_or(&[], Vec::as_slice)
+    }
+
+    /// Functions (or the `Program` node) directly calling `callee`
+    pub fn callers(&self, callee: AstNodeId) -> &[AstNodeId] {
+        self.callers.get(&callee).map_or(&[], Vec::as_slice)
+    }
+
+    /// All functions transitively called from `roots`, including the roots themselves.
+    pub fn reachable_from(&self, roots: &[AstNodeId]) -> FxHashSet<AstNodeId> {
+        let mut visited = FxHashSet::default();
+        let mut stack = roots.to_vec();
+        while let Some(node_id) = stack.pop() {
+            if visited.insert(node_id) {
+                stack.extend_from_slice(self.callees(node_id));
+            }
+        }
+        visited
+    }
+
+    /// Whether calling `from` can transitively call `to`
+    pub fn is_reachable(&self, from: AstNodeId, to: AstNodeId) -> bool {
+        self.callees(from).iter().any(|callee| self.reachable_from(&[*callee]).contains(&to))
+    }
+
+    /// Whether the function calls itself, directly or through other functions
+    pub fn is_recursive(&self, function: AstNodeId) -> bool {
+        self.is_reachable(function, function)
+    }
+}
+
+struct CallGraphBuilder<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    graph: CallGraph,
+    /// Maps the span of each function to its node, for resolving function expressions
+    function_spans: FxHashMap<Span, AstNodeId>,
+}
+
+impl<'s, 'a> CallGraphBuilder<'s, 'a> {
+    fn new(semantic: &'s Semantic<'a>) -> Self {
+        let mut graph = CallGraph::default();
+        let mut function_spans = FxHashMap::default();
+        for node in semantic.nodes().iter() {
+            if matches!(node.kind(), AstKind::Function(_) | AstKind::ArrowExpression(_)) {
+                graph.functions.push(node.id());
+                function_spans.insert(node.kind().span(), node.id());
+            }
+        }
+        Self { semantic, graph, function_spans }
+    }
+
+    fn build(mut self) -> CallGraph {
+        for node in self.semantic.nodes().iter() {
+            let AstKind::CallExpression(call) = node.kind() else { continue };
+            let Some(target) = self.resolve_callee(&call.callee, node.id()) else { continue };
+            let source = self.enclosing_function(node.id());
+            self.graph.call_sites.push(CallSite {
+                node_id: node.id(),
+                caller: source,
+                callee: target,
+            });
+
+            let callees = self.graph.callees.entry(source).or_default();
+            if !callees.contains(&target) {
+                callees.push(target);
+                self.graph.callers.entry(target).or_default().push(source);
+            }
+        }
+        self.graph
+    }
+
+    /// The nearest `Function` or `ArrowExpression` containing `node_id`, or the `Program` node
+    fn enclosing_function(&self, node_id: AstNodeId) -> AstNodeId {
+        let nodes = self.semantic.nodes();
+        nodes
+            .ancestors(node_id)
+            .skip(1)
+            .find(|id| {
+                matches!(nodes.kind(*id), AstKind::Function(_) | AstKind::ArrowExpression(_))
+            })
+            .unwrap_or_else(|| nodes.ancestors(node_id).last().unwrap_or(node_id))
+    }
+
+    fn resolve_callee(&self, callee: &Expression<'a>, call_id: AstNodeId) -> Option<AstNodeId> {
+        match callee.without_parenthesized() {
+            Expression::FunctionExpression(func) => self.function_spans.get(&func.span).copied(),
+            Expression::ArrowExpression(arrow) => self.function_spans.get(&arrow.span).copied(),
+            Expression::Identifier(ident) => self.resolve_identifier(ident),
+            Expression::MemberExpression(member) => self.resolve_method(member, call_id),
+            _ => None,
+        }
+    }
+
+    fn resolve_identifier(&self, ident: &IdentifierReference) -> Option<AstNodeId> {
+        match self.resolve_declaration(ident)? {
+            AstKind::Function(func) => self.function_spans.get(&func.span).copied(),
+            AstKind::VariableDeclarator(decl) => {
+                match decl.init.as_ref()?.without_parenthesized() {
+                    Expression::FunctionExpression(func) => {
+                        self.function_spans.get(&func.span).copied()
+                    }
+                    Expression::ArrowExpression(arrow) => {
+                        self.function_spans.get(&arrow.span).copied()
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the declaration of a referenced symbol, unless the symbol is ever reassigned.
+    fn resolve_declaration(&self, ident: &IdentifierReference) -> Option<AstKind<'a>> {
+        let symbols = self.semantic.symbols();
+        let symbol_id = symbols.get_reference(ident.reference_id.get()?).symbol_id()?;
+        if symbols.get_resolved_references(symbol_id).any(Reference::is_write) {
+            return None;
+        }
+        Some(self.semantic.symbol_declaration(symbol_id).kind())
+    }
+
+    fn resolve_method(
+        &self,
+        member: &MemberExpression<'a>,
+        call_id: AstNodeId,
+    ) -> Option<AstNodeId> {
+        let (class, is_static) = match member.object().without_parenthesized() {
+            Expression::ThisExpression(_) => self.this_class(call_id)?,
+            Expression::Identifier(ident) => match self.resolve_declaration(ident)? {
+                AstKind::Class(class) => (class, true),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let (name, is_private) = match member {
+            MemberExpression::PrivateFieldExpression(expr) => (expr.field.name.clone(), true),
+            _ => (Atom::from(member.static_property_name()?), false),
+        };
+        class.body.body.iter().find_map(|element| {
+            let ClassElement::MethodDefinition(method) = element else { return None };
+            let key_name =
+                if is_private { method.key.private_name() } else { method.key.static_name() };
+            (method.kind.is_method() && method.r#static == is_static && key_name? == name)
+                .then(|| self.function_spans.get(&method.value.span).copied())
+                .flatten()
+        })
+    }
+
+    /// The class `this` refers to at `node_id`, and whether `this` is the class itself because
+    /// the enclosing method is static.
+    fn this_class(&self, node_id: AstNodeId) -> Option<(&'a Class<'a>, bool)> {
+        let nodes = self.semantic.nodes();
+        let function_id =
+            nodes.ancestors(node_id).find(|id| matches!(nodes.kind(*id), AstKind::Function(_)))?;
+        let AstKind::MethodDefinition(method) = nodes.parent_kind(function_id)? else {
+            return None;
+        };
+        let class = nodes.ancestors(function_id).find_map(|id| match nodes.kind(id) {
+            AstKind::Class(class) => Some(class),
+            _ => None,
+        })?;
+        Some((class, method.r#static))
+    }
+}
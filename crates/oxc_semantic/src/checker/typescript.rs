@@ -3,7 +3,7 @@ use oxc_ast::syntax_directed_operations::BoundNames;
 use oxc_ast::{ast::*, AstKind};
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
-    thiserror::Error,
+    thiserror::{self, Error},
 };
 use oxc_span::{Atom, GetSpan, Span};
 use rustc_hash::FxHashMap;
@@ -16,11 +16,23 @@ impl EarlyErrorTypeScript {
     pub fn run<'a>(node: &AstNode<'a>, ctx: &SemanticBuilder<'a>) {
         let kind = node.kind();
 
-        // should be removed when add more matches.
-        #[allow(clippy::single_match)]
         match kind {
             AstKind::SimpleAssignmentTarget(target) => check_simple_assignment_target(target, ctx),
             AstKind::FormalParameters(params) => check_formal_parameters(params, ctx),
+            AstKind::VariableDeclaration(decl) => check_modifiers(&decl.modifiers, ctx),
+            AstKind::Function(func) => check_modifiers(&func.modifiers, ctx),
+            AstKind::Class(class) => {
+                check_modifiers(&class.modifiers, ctx);
+                check_abstract_members(class, ctx);
+            }
+            AstKind::TSEnumDeclaration(decl) => check_modifiers(&decl.modifiers, ctx),
+            AstKind::TSTypeAliasDeclaration(decl) => check_modifiers(&decl.modifiers, ctx),
+            AstKind::TSModuleDeclaration(decl) => check_modifiers(&decl.modifiers, ctx),
+            AstKind::TSInterfaceDeclaration(decl) => check_modifiers(&decl.modifiers, ctx),
+            AstKind::ModuleDeclaration(ModuleDeclaration::TSExportAssignment(decl)) => {
+                check_export_assignment(decl, node, ctx);
+            }
+            AstKind::Program(program) => check_program_export_assignment(program, ctx),
             _ => {}
         }
     }
@@ -30,6 +42,124 @@ fn check_formal_parameters(params: &FormalParameters, ctx: &SemanticBuilder<'_>)
     if !params.is_empty() && params.kind == FormalParameterKind::Signature {
         check_duplicate_bound_names(params, ctx);
     }
+    check_required_parameter_after_optional(params, ctx);
+}
+
+fn check_required_parameter_after_optional(params: &FormalParameters, ctx: &SemanticBuilder<'_>) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("TS1016: A required parameter cannot follow an optional parameter.")]
+    #[diagnostic()]
+    struct RequiredParameterAfterOptional(
+        #[label("Optional parameter")] Span,
+        #[label("Required parameter")] Span,
+    );
+
+    let mut optional = None;
+    for param in &params.items {
+        if param.pattern.optional {
+            optional.get_or_insert(param.span);
+        } else if let Some(optional) = optional {
+            // Parameters with an initializer are optional as well
+            if !matches!(param.pattern.kind, BindingPatternKind::AssignmentPattern(_)) {
+                ctx.error(RequiredParameterAfterOptional(optional, param.span));
+                return;
+            }
+        }
+    }
+}
+
+fn check_modifiers(modifiers: &Modifiers, ctx: &SemanticBuilder<'_>) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("TS1030: '{0}' modifier already seen.")]
+    #[diagnostic()]
+    struct DuplicateModifier(String, #[label("It is first used here")] Span, #[label] Span);
+
+    let mut seen: FxHashMap<ModifierKind, Span> = FxHashMap::default();
+    for modifier in modifiers.iter() {
+        if let Some(first) = seen.insert(modifier.kind, modifier.span) {
+            let name = modifier.span.source_text(ctx.source_text).to_string();
+            ctx.error(DuplicateModifier(name, first, modifier.span));
+        }
+    }
+}
+
+fn check_abstract_members(class: &Class, ctx: &SemanticBuilder<'_>) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("TS1244: Abstract methods can only appear within an abstract class.")]
+    #[diagnostic()]
+    struct AbstractMethodInNonAbstractClass(
+        #[label] Span,
+        #[label("Add `abstract` to the class")] Span,
+    );
+
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("TS1253: Abstract properties can only appear within an abstract class.")]
+    #[diagnostic()]
+    struct AbstractPropertyInNonAbstractClass(
+        #[label] Span,
+        #[label("Add `abstract` to the class")] Span,
+    );
+
+    if class.modifiers.contains(ModifierKind::Abstract) {
+        return;
+    }
+    let class_span = class.id.as_ref().map_or(class.span, |id| id.span);
+    for element in &class.body.body {
+        match element {
+            ClassElement::TSAbstractMethodDefinition(def) => {
+                let span = def.method_definition.key.span();
+                ctx.error(AbstractMethodInNonAbstractClass(span, class_span));
+            }
+            ClassElement::TSAbstractPropertyDefinition(def) => {
+                let span = def.property_definition.key.span();
+                ctx.error(AbstractPropertyInNonAbstractClass(span, class_span));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_export_assignment(
+    decl: &TSExportAssignment,
+    node: &AstNode<'_>,
+    ctx: &SemanticBuilder<'_>,
+) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("TS1063: An export assignment cannot be used in a namespace.")]
+    #[diagnostic()]
+    struct ExportAssignmentInNamespace(#[label] Span);
+
+    if matches!(ctx.nodes.parent_kind(node.id()), Some(AstKind::TSModuleBlock(_))) {
+        ctx.error(ExportAssignmentInNamespace(decl.span));
+    }
+}
+
+fn check_program_export_assignment(program: &Program, ctx: &SemanticBuilder<'_>) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error(
+        "TS2309: An export assignment cannot be used in a module with other exported elements."
+    )]
+    #[diagnostic()]
+    struct ExportAssignmentWithOtherExports(#[label] Span, #[label("Other exported element")] Span);
+
+    let mut export_assignment = None;
+    let mut other_export = None;
+    for stmt in &program.body {
+        let Statement::ModuleDeclaration(decl) = stmt else { continue };
+        match &**decl {
+            ModuleDeclaration::TSExportAssignment(decl) => {
+                export_assignment.get_or_insert(decl.span);
+            }
+            ModuleDeclaration::ImportDeclaration(_)
+            | ModuleDeclaration::TSNamespaceExportDeclaration(_) => {}
+            decl => {
+                other_export.get_or_insert(decl.span());
+            }
+        }
+    }
+    if let (Some(export_assignment), Some(other_export)) = (export_assignment, other_export) {
+        ctx.error(ExportAssignmentWithOtherExports(export_assignment, other_export));
+    }
 }
 
 fn check_duplicate_bound_names<T: BoundNames>(bound_names: &T, ctx: &SemanticBuilder<'_>) {
@@ -1,17 +1,20 @@
 use oxc_ast::{
     ast::{
-        AccessorProperty, ClassBody, ClassElement, MethodDefinition, PrivateIdentifier,
-        PropertyDefinition,
+        AccessorProperty, ClassBody, ClassElement, Expression, MemberExpression, MethodDefinition,
+        PrivateIdentifier, PropertyDefinition,
     },
     AstKind,
 };
-use oxc_span::GetSpan;
-use oxc_syntax::class::{ClassId, ElementKind};
+use oxc_span::{Atom, GetSpan};
+use oxc_syntax::{
+    class::{ClassId, ElementKind, ElementReferenceFlag},
+    operator::AssignmentOperator,
+};
 
 use crate::{AstNodeId, AstNodes};
 
 use super::{
-    table::{Element, PrivateIdentifierReference},
+    table::{Element, MemberReference, PrivateIdentifierReference},
     ClassTable,
 };
 
@@ -97,12 +100,19 @@ impl ClassTableBuilder {
             {
                 if let Some(class_id) = self.current_class_id {
                     let element_ids = self.classes.get_element_ids(class_id, &ident.name);
+                    let flag = match nodes.parent_id(current_node_id) {
+                        Some(parent_id) if matches!(parent_kind, AstKind::MemberExpression(_)) => {
+                            element_reference_flag(parent_id, nodes)
+                        }
+                        _ => ElementReferenceFlag::Read,
+                    };
 
                     let reference = PrivateIdentifierReference::new(
                         current_node_id,
                         ident.name.clone(),
                         ident.span,
                         element_ids,
+                        flag,
                     );
                     self.classes.add_private_identifier_reference(class_id, reference);
                 }
@@ -110,6 +120,67 @@ impl ClassTableBuilder {
         }
     }
 
+    /// Record `this.name` and `this["name"]` references to the class `this` refers to.
+    /// Private members are recorded by [`Self::add_private_identifier_reference`].
+    pub fn add_member_reference(
+        &mut self,
+        member: &MemberExpression,
+        current_node_id: AstNodeId,
+        nodes: &AstNodes,
+    ) {
+        if matches!(member, MemberExpression::PrivateFieldExpression(_))
+            || !matches!(member.object().without_parenthesized(), Expression::ThisExpression(_))
+        {
+            return;
+        }
+        let Some(name) = member.static_property_name() else { return };
+        let Some(class_id) = self.this_class_id(current_node_id, nodes) else { return };
+
+        let name = Atom::from(name);
+        let element_ids = self.classes.elements[class_id]
+            .iter_enumerated()
+            .filter(|(_, element)| !element.is_private && element.name == name)
+            .map(|(element_id, _)| element_id)
+            .collect();
+        let flag = element_reference_flag(current_node_id, nodes);
+        let reference =
+            MemberReference::new(current_node_id, name, member.span(), element_ids, flag);
+        self.classes.add_member_reference(class_id, reference);
+    }
+
+    /// Find the class `this` refers to at `node_id`: inside of a method, a property initializer
+    /// or a static block, but not inside of a nested function.
+    fn this_class_id(&self, node_id: AstNodeId, nodes: &AstNodes) -> Option<ClassId> {
+        let mut ancestors = nodes.ancestors(node_id);
+        let mut child_id = ancestors.next()?;
+        for ancestor_id in ancestors.by_ref() {
+            match nodes.kind(ancestor_id) {
+                AstKind::Function(_) => {
+                    if !matches!(nodes.parent_kind(ancestor_id), Some(AstKind::MethodDefinition(_)))
+                    {
+                        return None;
+                    }
+                    break;
+                }
+                AstKind::PropertyDefinition(prop) => {
+                    let child_span = nodes.kind(child_id).span();
+                    if !prop.value.as_ref().is_some_and(|value| value.span() == child_span) {
+                        return None;
+                    }
+                    break;
+                }
+                AstKind::StaticBlock(_) => break,
+                AstKind::Class(_) | AstKind::Program(_) => return None,
+                _ => {}
+            }
+            child_id = ancestor_id;
+        }
+        let class_node_id = ancestors.find(|id| matches!(nodes.kind(*id), AstKind::Class(_)))?;
+        self.classes
+            .iter_enumerated()
+            .find_map(|(class_id, node_id)| (*node_id == class_node_id).then_some(class_id))
+    }
+
     pub fn declare_class_method(&mut self, method: &MethodDefinition) {
         if method.kind.is_constructor() {
             return;
@@ -132,3 +203,39 @@ impl ClassTableBuilder {
             .and_then(|current_class_id| self.classes.parent_ids.get(&current_class_id).copied());
     }
 }
+
+/// How the class element referenced by the `MemberExpression` at `member_id` is accessed.
+fn element_reference_flag(member_id: AstNodeId, nodes: &AstNodes) -> ElementReferenceFlag {
+    let mut current_id = member_id;
+    for parent_id in nodes.ancestors(member_id).skip(1) {
+        match nodes.kind(parent_id) {
+            AstKind::ParenthesizedExpression(_) => current_id = parent_id,
+            AstKind::CallExpression(call)
+                if call.callee.span() == nodes.kind(current_id).span() =>
+            {
+                return ElementReferenceFlag::Read | ElementReferenceFlag::Call;
+            }
+            AstKind::SimpleAssignmentTarget(_) => {
+                return match nodes.parent_kind(parent_id) {
+                    Some(AstKind::UpdateExpression(_)) => {
+                        ElementReferenceFlag::Read | ElementReferenceFlag::Write
+                    }
+                    Some(AstKind::AssignmentTarget(_)) => {
+                        let target_id = nodes.parent_id(parent_id);
+                        match target_id.and_then(|id| nodes.parent_kind(id)) {
+                            Some(AstKind::AssignmentExpression(expr))
+                                if expr.operator != AssignmentOperator::Assign =>
+                            {
+                                ElementReferenceFlag::Read | ElementReferenceFlag::Write
+                            }
+                            _ => ElementReferenceFlag::Write,
+                        }
+                    }
+                    _ => ElementReferenceFlag::Write,
+                };
+            }
+            _ => return ElementReferenceFlag::Read,
+        }
+    }
+    ElementReferenceFlag::Read
+}
@@ -1,6 +1,6 @@
 use oxc_index::IndexVec;
 use oxc_span::{Atom, Span};
-use oxc_syntax::class::{ClassId, ElementId, ElementKind};
+use oxc_syntax::class::{ClassId, ElementId, ElementKind, ElementReferenceFlag};
 use rustc_hash::FxHashMap;
 
 use crate::node::AstNodeId;
@@ -25,11 +25,41 @@ pub struct PrivateIdentifierReference {
     pub name: Atom,
     pub span: Span,
     pub element_ids: Vec<ElementId>,
+    pub flag: ElementReferenceFlag,
 }
 
 impl PrivateIdentifierReference {
-    pub fn new(id: AstNodeId, name: Atom, span: Span, element_ids: Vec<ElementId>) -> Self {
-        Self { id, name, span, element_ids }
+    pub fn new(
+        id: AstNodeId,
+        name: Atom,
+        span: Span,
+        element_ids: Vec<ElementId>,
+        flag: ElementReferenceFlag,
+    ) -> Self {
+        Self { id, name, span, element_ids, flag }
+    }
+}
+
+/// A reference to a non-private class element through `this`, e.g. `this.foo` or `this["foo"]`
+#[derive(Debug)]
+pub struct MemberReference {
+    /// The `MemberExpression` node
+    pub id: AstNodeId,
+    pub name: Atom,
+    pub span: Span,
+    pub element_ids: Vec<ElementId>,
+    pub flag: ElementReferenceFlag,
+}
+
+impl MemberReference {
+    pub fn new(
+        id: AstNodeId,
+        name: Atom,
+        span: Span,
+        element_ids: Vec<ElementId>,
+        flag: ElementReferenceFlag,
+    ) -> Self {
+        Self { id, name, span, element_ids, flag }
     }
 }
 
@@ -43,6 +73,8 @@ pub struct ClassTable {
     pub elements: IndexVec<ClassId, IndexVec<ElementId, Element>>,
     // PrivateIdentifier reference
     pub private_identifiers: IndexVec<ClassId, Vec<PrivateIdentifierReference>>,
+    // `this.member` reference
+    pub member_references: IndexVec<ClassId, Vec<MemberReference>>,
 }
 
 impl ClassTable {
@@ -61,6 +93,33 @@ impl ClassTable {
         self.private_identifiers[class_id].iter()
     }
 
+    pub fn iter_member_references(
+        &self,
+        class_id: ClassId,
+    ) -> impl Iterator<Item = &MemberReference> + '_ {
+        self.member_references[class_id].iter()
+    }
+
+    /// Get how an element is accessed by all of its private identifier and `this` member
+    /// references. An empty flag means the element is never referenced.
+    pub fn get_element_reference_flag(
+        &self,
+        class_id: ClassId,
+        element_id: ElementId,
+    ) -> ElementReferenceFlag {
+        let private_flags = self
+            .iter_private_identifiers(class_id)
+            .filter(|reference| reference.element_ids.contains(&element_id))
+            .map(|reference| reference.flag);
+        let member_flags = self
+            .iter_member_references(class_id)
+            .filter(|reference| reference.element_ids.contains(&element_id))
+            .map(|reference| reference.flag);
+        private_flags
+            .chain(member_flags)
+            .fold(ElementReferenceFlag::empty(), |acc, flag| acc | flag)
+    }
+
     pub fn get_node_id(&self, class_id: ClassId) -> AstNodeId {
         self.declarations[class_id]
     }
@@ -97,6 +156,7 @@ impl ClassTable {
         };
         self.elements.push(IndexVec::default());
         self.private_identifiers.push(Vec::new());
+        self.member_references.push(Vec::new());
         class_id
     }
 
@@ -111,4 +171,8 @@ impl ClassTable {
     ) {
         self.private_identifiers[class_id].push(private_identifier_reference);
     }
+
+    pub fn add_member_reference(&mut self, class_id: ClassId, member_reference: MemberReference) {
+        self.member_references[class_id].push(member_reference);
+    }
 }
@@ -0,0 +1,343 @@
+use oxc_span::Atom;
+
+use super::{BasicBlock, BasicBlockId, ControlFlowGraph, EdgeType, FunctionFlow};
+use crate::node::AstNodeId;
+
+#[derive(Debug)]
+struct JumpTarget {
+    label: Option<Atom>,
+    break_to: BasicBlockId,
+    continue_to: Option<BasicBlockId>,
+    /// Labeled blocks can only be the target of a labeled `break`
+    labeled_only: bool,
+    /// Number of exception handlers active when the target was pushed
+    handler_depth: usize,
+}
+
+/// A `finally` block, taken off the handler stack while the block itself is visited.
+#[derive(Debug)]
+pub struct Finally {
+    block: BasicBlockId,
+    /// `break` and `continue` targets which have to be resumed after the `finally` block
+    pending_jumps: Vec<(BasicBlockId, usize)>,
+    pending_return: bool,
+    pending_throw: bool,
+}
+
+#[derive(Debug)]
+enum Handler {
+    Catch(BasicBlockId),
+    Finally(Finally),
+}
+
+impl Handler {
+    fn block(&self) -> BasicBlockId {
+        match self {
+            Self::Catch(block) => *block,
+            Self::Finally(finally) => finally.block,
+        }
+    }
+}
+
+/// Jump targets and exception handlers never cross a function boundary.
+#[derive(Debug, Default)]
+struct FunctionContext {
+    jump_targets: Vec<JumpTarget>,
+    handlers: Vec<Handler>,
+    /// Label of a `LabeledStatement` whose body is a loop, consumed by that loop
+    pending_label: Option<Atom>,
+}
+
+#[derive(Debug)]
+struct SavedFunction {
+    node_id: AstNodeId,
+    entry: BasicBlockId,
+    outer_block: BasicBlockId,
+    outer_context: FunctionContext,
+}
+
+#[derive(Debug)]
+pub struct ControlFlowGraphBuilder {
+    cfg: ControlFlowGraph,
+    current_block: BasicBlockId,
+    context: FunctionContext,
+    function_stack: Vec<SavedFunction>,
+}
+
+impl Default for ControlFlowGraphBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControlFlowGraphBuilder {
+    pub fn new() -> Self {
+        let mut builder = Self {
+            cfg: ControlFlowGraph::default(),
+            current_block: BasicBlockId::new(0),
+            context: FunctionContext::default(),
+            function_stack: vec![],
+        };
+        builder.current_block = builder.new_root_block();
+        builder
+    }
+
+    pub fn build(self) -> ControlFlowGraph {
+        self.cfg
+    }
+
+    pub fn current_block(&self) -> BasicBlockId {
+        self.current_block
+    }
+
+    pub fn is_reachable(&self, block_id: BasicBlockId) -> bool {
+        self.cfg.basic_blocks[block_id].reachable
+    }
+
+    /// Record that `node_id` is evaluated in the current basic block.
+    pub fn add_element(&mut self, node_id: AstNodeId) {
+        debug_assert_eq!(self.cfg.node_blocks.len(), node_id.index());
+        self.cfg.node_blocks.push(self.current_block);
+        self.cfg.basic_blocks[self.current_block].elements.push(node_id);
+    }
+
+    /// Create a new basic block. Blocks created inside a `try` region implicitly flow into the
+    /// innermost `catch` or `finally` block.
+    pub fn new_block(&mut self) -> BasicBlockId {
+        let block_id = self.push_block(false);
+        if let Some(handler) = self.context.handlers.last() {
+            let handler_block = handler.block();
+            self.add_edge(block_id, handler_block, EdgeType::Error);
+        }
+        block_id
+    }
+
+    fn new_root_block(&mut self) -> BasicBlockId {
+        self.push_block(true)
+    }
+
+    fn push_block(&mut self, reachable: bool) -> BasicBlockId {
+        self.cfg.successors.push(vec![]);
+        self.cfg.predecessors.push(vec![]);
+        self.cfg.basic_blocks.push(BasicBlock { elements: vec![], reachable })
+    }
+
+    pub fn add_edge(&mut self, from: BasicBlockId, to: BasicBlockId, edge_type: EdgeType) {
+        self.cfg.successors[from].push((to, edge_type));
+        self.cfg.predecessors[to].push((from, edge_type));
+        if self.cfg.basic_blocks[from].reachable {
+            self.mark_reachable(to);
+        }
+    }
+
+    fn mark_reachable(&mut self, block_id: BasicBlockId) {
+        let mut stack = vec![block_id];
+        while let Some(block_id) = stack.pop() {
+            let block = &mut self.cfg.basic_blocks[block_id];
+            if block.reachable {
+                continue;
+            }
+            block.reachable = true;
+            stack.extend(self.cfg.successors[block_id].iter().map(|(id, _)| *id));
+        }
+    }
+
+    pub fn switch_to(&mut self, block_id: BasicBlockId) {
+        self.current_block = block_id;
+    }
+
+    /// Continue in a fresh block with no predecessors, used after abrupt completions.
+    fn start_unreachable(&mut self) {
+        self.current_block = self.new_block();
+    }
+
+    /* Functions */
+
+    pub fn enter_function(&mut self, node_id: AstNodeId) {
+        let entry = self.new_root_block();
+        self.function_stack.push(SavedFunction {
+            node_id,
+            entry,
+            outer_block: self.current_block,
+            outer_context: std::mem::take(&mut self.context),
+        });
+        self.current_block = entry;
+    }
+
+    pub fn leave_function(&mut self) {
+        let Some(saved) = self.function_stack.pop() else { return };
+        let flow = FunctionFlow { entry: saved.entry, end: self.current_block };
+        self.cfg.functions.insert(saved.node_id, flow);
+        self.current_block = saved.outer_block;
+        self.context = saved.outer_context;
+    }
+
+    /* Jump targets */
+
+    pub fn set_pending_label(&mut self, label: Atom) {
+        self.context.pending_label = Some(label);
+    }
+
+    pub fn take_pending_label(&mut self) -> Option<Atom> {
+        self.context.pending_label.take()
+    }
+
+    /// Push the target of `break` and `continue` statements for a loop or `switch` statement.
+    pub fn push_jump_target(
+        &mut self,
+        label: Option<Atom>,
+        break_to: BasicBlockId,
+        continue_to: Option<BasicBlockId>,
+    ) {
+        let handler_depth = self.context.handlers.len();
+        self.context.jump_targets.push(JumpTarget {
+            label,
+            break_to,
+            continue_to,
+            labeled_only: false,
+            handler_depth,
+        });
+    }
+
+    /// Push the target of labeled `break` statements for a labeled statement that is not a loop.
+    pub fn push_label_target(&mut self, label: Atom, break_to: BasicBlockId) {
+        let handler_depth = self.context.handlers.len();
+        self.context.jump_targets.push(JumpTarget {
+            label: Some(label),
+            break_to,
+            continue_to: None,
+            labeled_only: true,
+            handler_depth,
+        });
+    }
+
+    pub fn pop_jump_target(&mut self) {
+        self.context.jump_targets.pop();
+    }
+
+    pub fn add_break(&mut self, label: Option<&Atom>) {
+        let target = self.context.jump_targets.iter().rev().find(|target| {
+            label.map_or(!target.labeled_only, |label| target.label.as_ref() == Some(label))
+        });
+        if let Some(target) = target {
+            let (to, depth) = (target.break_to, target.handler_depth);
+            self.route_jump(to, depth);
+        }
+        self.start_unreachable();
+    }
+
+    pub fn add_continue(&mut self, label: Option<&Atom>) {
+        let target = self.context.jump_targets.iter().rev().find(|target| {
+            target.continue_to.is_some()
+                && label.map_or(true, |label| target.label.as_ref() == Some(label))
+        });
+        if let Some(JumpTarget { continue_to: Some(to), handler_depth, .. }) = target {
+            let (to, depth) = (*to, *handler_depth);
+            self.route_jump(to, depth);
+        }
+        self.start_unreachable();
+    }
+
+    pub fn add_return(&mut self) {
+        self.route_return();
+        self.start_unreachable();
+    }
+
+    pub fn add_throw(&mut self) {
+        self.route_throw();
+        self.start_unreachable();
+    }
+
+    /// Jump to `to`, passing through every `finally` block entered after the target was pushed.
+    fn route_jump(&mut self, to: BasicBlockId, handler_depth: usize) {
+        let from = self.current_block;
+        let finally =
+            self.context.handlers[handler_depth..].iter_mut().rev().find_map(
+                |handler| match handler {
+                    Handler::Finally(finally) => Some(finally),
+                    Handler::Catch(_) => None,
+                },
+            );
+        if let Some(finally) = finally {
+            finally.pending_jumps.push((to, handler_depth));
+            let block = finally.block;
+            self.add_edge(from, block, EdgeType::Jump);
+        } else {
+            self.add_edge(from, to, EdgeType::Jump);
+        }
+    }
+
+    fn route_return(&mut self) {
+        let from = self.current_block;
+        let finally = self.context.handlers.iter_mut().rev().find_map(|handler| match handler {
+            Handler::Finally(finally) => Some(finally),
+            Handler::Catch(_) => None,
+        });
+        if let Some(finally) = finally {
+            finally.pending_return = true;
+            let block = finally.block;
+            self.add_edge(from, block, EdgeType::Jump);
+        }
+    }
+
+    fn route_throw(&mut self) {
+        let from = self.current_block;
+        let Some(handler) = self.context.handlers.last_mut() else { return };
+        if let Handler::Finally(finally) = handler {
+            finally.pending_throw = true;
+        }
+        let block = handler.block();
+        self.add_edge(from, block, EdgeType::Error);
+    }
+
+    /* Exception handlers */
+
+    pub fn push_catch_handler(&mut self, block_id: BasicBlockId) {
+        self.context.handlers.push(Handler::Catch(block_id));
+    }
+
+    pub fn push_finally_handler(&mut self, block_id: BasicBlockId) {
+        self.context.handlers.push(Handler::Finally(Finally {
+            block: block_id,
+            pending_jumps: vec![],
+            pending_return: false,
+            pending_throw: false,
+        }));
+    }
+
+    pub fn pop_catch_handler(&mut self) {
+        debug_assert!(matches!(self.context.handlers.last(), Some(Handler::Catch(_))));
+        self.context.handlers.pop();
+    }
+
+    /// Pop the innermost `finally` handler before its block is visited, so exceptions thrown
+    /// inside of it flow to the outer handlers.
+    pub fn pop_finally_handler(&mut self) -> Option<Finally> {
+        let handler = self.context.handlers.pop();
+        debug_assert!(matches!(handler, Some(Handler::Finally(_))));
+        let Some(Handler::Finally(finally)) = handler else { return None };
+        Some(finally)
+    }
+
+    /// Connect the end of a visited `finally` block to `after` when the `try` or `catch` block
+    /// completes normally, and resume all the abrupt completions that were routed through it.
+    pub fn resume_after_finally(
+        &mut self,
+        finally: Finally,
+        normal_completion: bool,
+        after: BasicBlockId,
+    ) {
+        if normal_completion {
+            self.add_edge(self.current_block, after, EdgeType::Normal);
+        }
+        for (to, handler_depth) in finally.pending_jumps {
+            self.route_jump(to, handler_depth);
+        }
+        if finally.pending_return {
+            self.route_return();
+        }
+        if finally.pending_throw {
+            self.route_throw();
+        }
+    }
+}
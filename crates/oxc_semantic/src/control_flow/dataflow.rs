@@ -0,0 +1,141 @@
+//! A generic iterative dataflow solver over the [`ControlFlowGraph`]
+//!
+//! An analysis describes a lattice ([`Analysis::Domain`]), how states from several edges are
+//! combined ([`Analysis::join`]) and the effect of a basic block ([`Analysis::transfer`]).
+//! [`solve`] computes the fixed point with a worklist.
+
+use std::collections::VecDeque;
+
+use oxc_index::IndexVec;
+
+use super::{BasicBlockId, ControlFlowGraph, EdgeType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// States flow from predecessors to successors, e.g. reaching definitions
+    Forward,
+    /// States flow from successors to predecessors, e.g. liveness
+    Backward,
+}
+
+pub trait Analysis {
+    type Domain: Clone + Eq;
+
+    fn direction(&self) -> Direction;
+
+    /// The initial state of every block, also the state flowing into entry blocks for forward
+    /// analyses and out of exit blocks for backward analyses.
+    fn bottom(&self) -> Self::Domain;
+
+    /// Combine `other` into `state`.
+    fn join(&self, state: &mut Self::Domain, other: &Self::Domain);
+
+    /// Apply the effect of `block_id` to `state`, in the direction of the analysis.
+    fn transfer(&self, block_id: BasicBlockId, state: &mut Self::Domain);
+
+    /// Replace `state`, the state at the start of `block_id`, with the join of the states at every
+    /// point of the block, which is what flows along [`EdgeType::Error`] edges in a forward
+    /// analysis.
+    ///
+    /// The default joins the entry and exit states, which is only correct if the states inside
+    /// the block are ordered between them.
+    fn transfer_error(&self, block_id: BasicBlockId, state: &mut Self::Domain) {
+        let mut exit = state.clone();
+        self.transfer(block_id, &mut exit);
+        self.join(state, &exit);
+    }
+}
+
+/// The fixed point states at the start and end of every basic block
+#[derive(Debug)]
+pub struct DataflowResults<D> {
+    entry: IndexVec<BasicBlockId, D>,
+    exit: IndexVec<BasicBlockId, D>,
+}
+
+impl<D> DataflowResults<D> {
+    /// The state before the first element of the block
+    pub fn entry_state(&self, block_id: BasicBlockId) -> &D {
+        &self.entry[block_id]
+    }
+
+    /// The state after the last element of the block
+    pub fn exit_state(&self, block_id: BasicBlockId) -> &D {
+        &self.exit[block_id]
+    }
+}
+
+/// Run `analysis` to a fixed point.
+///
+/// An [`EdgeType::Error`] edge may be taken at any point of its source block, so the states at
+/// every point of the source block flow along it, see [`Analysis::transfer_error`].
+pub fn solve<A: Analysis>(cfg: &ControlFlowGraph, analysis: &A) -> DataflowResults<A::Domain> {
+    let len = cfg.len();
+    let mut entry: IndexVec<BasicBlockId, A::Domain> =
+        std::iter::repeat_with(|| analysis.bottom()).take(len).collect();
+    let mut exit = entry.clone();
+
+    let mut queued = vec![true; len];
+    let mut worklist: VecDeque<BasicBlockId> = match analysis.direction() {
+        Direction::Forward => (0..len).map(BasicBlockId::new).collect(),
+        Direction::Backward => (0..len).rev().map(BasicBlockId::new).collect(),
+    };
+
+    while let Some(block_id) = worklist.pop_front() {
+        queued[block_id.index()] = false;
+        let changed = match analysis.direction() {
+            Direction::Forward => {
+                let mut state = analysis.bottom();
+                for (pred, edge_type) in cfg.predecessors(block_id) {
+                    if edge_type == EdgeType::Error {
+                        let mut error_state = entry[pred].clone();
+                        analysis.transfer_error(pred, &mut error_state);
+                        analysis.join(&mut state, &error_state);
+                    } else {
+                        analysis.join(&mut state, &exit[pred]);
+                    }
+                }
+                entry[block_id] = state.clone();
+                analysis.transfer(block_id, &mut state);
+                update(&mut exit[block_id], state)
+            }
+            Direction::Backward => {
+                let mut state = analysis.bottom();
+                let mut error_state = analysis.bottom();
+                for (succ, edge_type) in cfg.successors(block_id) {
+                    analysis.join(&mut state, &entry[succ]);
+                    if edge_type == EdgeType::Error {
+                        analysis.join(&mut error_state, &entry[succ]);
+                    }
+                }
+                exit[block_id] = state.clone();
+                analysis.transfer(block_id, &mut state);
+                analysis.join(&mut state, &error_state);
+                update(&mut entry[block_id], state)
+            }
+        };
+        if !changed {
+            continue;
+        }
+        let next: Vec<BasicBlockId> = match analysis.direction() {
+            Direction::Forward => cfg.successors(block_id).map(|(id, _)| id).collect(),
+            Direction::Backward => cfg.predecessors(block_id).map(|(id, _)| id).collect(),
+        };
+        for block_id in next {
+            if !std::mem::replace(&mut queued[block_id.index()], true) {
+                worklist.push_back(block_id);
+            }
+        }
+    }
+
+    DataflowResults { entry, exit }
+}
+
+/// Replace `old` with `new`, returns whether the value changed
+fn update<D: Eq>(old: &mut D, new: D) -> bool {
+    if *old == new {
+        return false;
+    }
+    *old = new;
+    true
+}
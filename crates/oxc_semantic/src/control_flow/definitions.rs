@@ -0,0 +1,392 @@
+//! Reaching definitions and liveness of variables
+//!
+//! Both analyses work on the definitions and uses of symbols recorded in each basic block.
+//! Symbols which are referenced from another function than the one declaring them, or which are
+//! declared in the root scope and may be read by other modules or scripts, cannot be tracked
+//! precisely: every definition reaches their uses and they are always live.
+
+use oxc_ast::{ast::VariableDeclarationKind, AstKind};
+use oxc_index::{define_index_type, IndexVec};
+use oxc_span::{GetSpan, Span};
+use oxc_syntax::scope::ScopeFlags;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::{
+    dataflow::{solve, Analysis, DataflowResults, Direction},
+    BasicBlockId, EdgeType,
+};
+use crate::{node::AstNodeId, reference::ReferenceId, symbol::SymbolId, Semantic};
+
+define_index_type! {
+    pub struct DefinitionId = u32;
+}
+
+/// A point where a value is assigned to a symbol
+#[derive(Debug, Clone, Copy)]
+pub struct Definition {
+    pub symbol_id: SymbolId,
+    /// The declaration, e.g. a `VariableDeclarator`, or the `IdentifierReference` written to
+    pub node_id: AstNodeId,
+    /// The assigned identifier
+    pub span: Span,
+    /// The write reference, `None` for declarations
+    pub reference_id: Option<ReferenceId>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    /// `kills` is `false` for conditional assignments such as `a ||= b`
+    Def {
+        definition_id: DefinitionId,
+        kills: bool,
+    },
+    Use(ReferenceId),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BlockEvent {
+    /// Position in the source text the event happens at, uses happen before definitions at the
+    /// same position
+    position: u32,
+    symbol_id: SymbolId,
+    event: Event,
+}
+
+impl BlockEvent {
+    fn sort_key(&self) -> (u32, bool) {
+        (self.position, matches!(self.event, Event::Def { .. }))
+    }
+}
+
+/// The definitions and uses of symbols in each basic block
+#[derive(Debug, Default)]
+pub struct DefUse {
+    definitions: IndexVec<DefinitionId, Definition>,
+    symbol_definitions: FxHashMap<SymbolId, Vec<DefinitionId>>,
+    /// Events of each block, in evaluation order
+    block_events: FxHashMap<BasicBlockId, Vec<BlockEvent>>,
+    reference_blocks: FxHashMap<ReferenceId, BasicBlockId>,
+    definition_blocks: IndexVec<DefinitionId, BasicBlockId>,
+    /// Symbols which cannot be tracked precisely
+    escaping: FxHashSet<SymbolId>,
+}
+
+impl DefUse {
+    pub fn new(semantic: &Semantic) -> Self {
+        let mut def_use = Self::default();
+        let nodes = semantic.nodes();
+        let cfg = semantic.cfg();
+        let symbols = semantic.symbols();
+        let scopes = semantic.scopes();
+        let function_scope = |scope_id| {
+            scopes
+                .ancestors(scope_id)
+                .find(|scope_id| scopes.get_flags(*scope_id).intersects(ScopeFlags::Var))
+        };
+
+        for symbol_id in symbols.iter() {
+            let scope_id = symbols.get_scope_id(symbol_id);
+            if scope_id == scopes.root_scope_id() {
+                def_use.escaping.insert(symbol_id);
+            }
+            let declaration = semantic.symbol_declaration(symbol_id);
+            let span = symbols.get_span(symbol_id);
+            let position = match declaration.kind() {
+                AstKind::VariableDeclarator(decl) => {
+                    let is_loop_variable =
+                        nodes.ancestors(declaration.id()).nth(2).is_some_and(|id| {
+                            matches!(
+                                nodes.kind(id),
+                                AstKind::ForInStatement(_) | AstKind::ForOfStatement(_)
+                            )
+                        });
+                    if is_loop_variable {
+                        span.end
+                    } else if decl.init.is_some() || decl.kind != VariableDeclarationKind::Var {
+                        decl.span.end
+                    } else {
+                        // `var a;` does not assign a value
+                        continue;
+                    }
+                }
+                // Hoisted to the start of the block
+                AstKind::Function(_) | AstKind::ModuleDeclaration(_) => 0,
+                _ => span.end,
+            };
+            def_use.add_definition(
+                Definition { symbol_id, node_id: declaration.id(), span, reference_id: None },
+                cfg.node_block(declaration.id()),
+                position,
+                true,
+            );
+        }
+
+        for (reference_id, reference) in symbols.references.iter_enumerated() {
+            let Some(symbol_id) = reference.symbol_id() else { continue };
+            if reference.is_type() {
+                continue;
+            }
+            let node_id = reference.node_id();
+            let block_id = cfg.node_block(node_id);
+            let declared_in = function_scope(symbols.get_scope_id(symbol_id));
+            if function_scope(nodes.get_node(node_id).scope_id()) != declared_in {
+                def_use.escaping.insert(symbol_id);
+            }
+            if reference.is_read() {
+                def_use.reference_blocks.insert(reference_id, block_id);
+                def_use.block_events.entry(block_id).or_default().push(BlockEvent {
+                    position: reference.span().start,
+                    symbol_id,
+                    event: Event::Use(reference_id),
+                });
+            }
+            if reference.is_write() {
+                let (position, kills) = write_position(semantic, node_id, reference.span());
+                let definition = Definition {
+                    symbol_id,
+                    node_id,
+                    span: reference.span(),
+                    reference_id: Some(reference_id),
+                };
+                def_use.add_definition(definition, block_id, position, kills);
+            }
+        }
+
+        for events in def_use.block_events.values_mut() {
+            events.sort_by_key(BlockEvent::sort_key);
+        }
+        def_use
+    }
+
+    fn add_definition(
+        &mut self,
+        definition: Definition,
+        block_id: BasicBlockId,
+        position: u32,
+        kills: bool,
+    ) {
+        let symbol_id = definition.symbol_id;
+        let definition_id = self.definitions.push(definition);
+        self.definition_blocks.push(block_id);
+        self.symbol_definitions.entry(symbol_id).or_default().push(definition_id);
+        self.block_events.entry(block_id).or_default().push(BlockEvent {
+            position,
+            symbol_id,
+            event: Event::Def { definition_id, kills },
+        });
+    }
+
+    pub fn definitions(&self) -> &IndexVec<DefinitionId, Definition> {
+        &self.definitions
+    }
+
+    pub fn get_definition(&self, definition_id: DefinitionId) -> &Definition {
+        &self.definitions[definition_id]
+    }
+
+    /// All definitions of a symbol, in source order of declarations followed by writes
+    pub fn symbol_definitions(&self, symbol_id: SymbolId) -> &[DefinitionId] {
+        self.symbol_definitions.get(&symbol_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether the symbol is referenced from another function or declared in the root scope
+    pub fn is_escaping(&self, symbol_id: SymbolId) -> bool {
+        self.escaping.contains(&symbol_id)
+    }
+
+    fn events(&self, block_id: BasicBlockId) -> &[BlockEvent] {
+        self.block_events.get(&block_id).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Where the write at `node_id` happens, and whether it always overwrites the previous value.
+///
+/// In `a = a + 1` the read happens before the write, so the write is placed at the end of the
+/// assignment expression.
+fn write_position(semantic: &Semantic, node_id: AstNodeId, span: Span) -> (u32, bool) {
+    let assignment =
+        semantic.nodes().ancestors(node_id).find_map(|id| match semantic.nodes().kind(id) {
+            AstKind::AssignmentExpression(expr) => Some(expr),
+            _ => None,
+        });
+    match assignment {
+        Some(expr) if expr.left.span().start <= span.start && span.end <= expr.left.span().end => {
+            (expr.span.end, !expr.operator.is_logical())
+        }
+        _ => (span.end, true),
+    }
+}
+
+/// Which definitions may reach each point of the program
+#[derive(Debug)]
+pub struct ReachingDefinitions<'s> {
+    def_use: &'s DefUse,
+    results: DataflowResults<FxHashSet<DefinitionId>>,
+}
+
+impl<'s> Analysis for &'s DefUse {
+    type Domain = FxHashSet<DefinitionId>;
+
+    fn direction(&self) -> Direction {
+        Direction::Forward
+    }
+
+    fn bottom(&self) -> Self::Domain {
+        FxHashSet::default()
+    }
+
+    fn join(&self, state: &mut Self::Domain, other: &Self::Domain) {
+        state.extend(other.iter().copied());
+    }
+
+    fn transfer(&self, block_id: BasicBlockId, state: &mut Self::Domain) {
+        for event in self.events(block_id) {
+            apply_definition(self, event, state);
+        }
+    }
+
+    fn transfer_error(&self, block_id: BasicBlockId, state: &mut Self::Domain) {
+        let mut current = state.clone();
+        for event in self.events(block_id) {
+            apply_definition(self, event, &mut current);
+            state.extend(current.iter().copied());
+        }
+    }
+}
+
+fn apply_definition(def_use: &DefUse, event: &BlockEvent, state: &mut FxHashSet<DefinitionId>) {
+    if let Event::Def { definition_id, kills } = event.event {
+        if kills {
+            for id in def_use.symbol_definitions(event.symbol_id) {
+                state.remove(id);
+            }
+        }
+        state.insert(definition_id);
+    }
+}
+
+impl<'s> ReachingDefinitions<'s> {
+    pub fn new(semantic: &Semantic, def_use: &'s DefUse) -> Self {
+        let results = solve(semantic.cfg(), &def_use);
+        Self { def_use, results }
+    }
+
+    /// The definitions whose value may be read by the read reference `reference_id`.
+    pub fn reaching(&self, reference_id: ReferenceId) -> Vec<DefinitionId> {
+        let Some(block_id) = self.def_use.reference_blocks.get(&reference_id) else {
+            return vec![];
+        };
+        let mut state = self.results.entry_state(*block_id).clone();
+        let mut symbol_id = None;
+        for event in self.def_use.events(*block_id) {
+            if matches!(event.event, Event::Use(id) if id == reference_id) {
+                symbol_id = Some(event.symbol_id);
+                break;
+            }
+            apply_definition(self.def_use, event, &mut state);
+        }
+        let Some(symbol_id) = symbol_id else { return vec![] };
+        if self.def_use.is_escaping(symbol_id) {
+            return self.def_use.symbol_definitions(symbol_id).to_vec();
+        }
+        let mut reaching: Vec<DefinitionId> = self
+            .def_use
+            .symbol_definitions(symbol_id)
+            .iter()
+            .copied()
+            .filter(|id| state.contains(id))
+            .collect();
+        reaching.sort_unstable();
+        reaching
+    }
+}
+
+/// Which symbols may still be read at each point of the program
+#[derive(Debug)]
+pub struct Liveness<'s> {
+    def_use: &'s DefUse,
+    results: DataflowResults<FxHashSet<SymbolId>>,
+    /// Symbols live at the start of the exception handlers of each block
+    handler_live: FxHashMap<BasicBlockId, FxHashSet<SymbolId>>,
+}
+
+struct LivenessAnalysis<'s>(&'s DefUse);
+
+impl<'s> Analysis for LivenessAnalysis<'s> {
+    type Domain = FxHashSet<SymbolId>;
+
+    fn direction(&self) -> Direction {
+        Direction::Backward
+    }
+
+    fn bottom(&self) -> Self::Domain {
+        FxHashSet::default()
+    }
+
+    fn join(&self, state: &mut Self::Domain, other: &Self::Domain) {
+        state.extend(other.iter().copied());
+    }
+
+    fn transfer(&self, block_id: BasicBlockId, state: &mut Self::Domain) {
+        for event in self.0.events(block_id).iter().rev() {
+            apply_liveness(event, state);
+        }
+    }
+}
+
+fn apply_liveness(event: &BlockEvent, state: &mut FxHashSet<SymbolId>) {
+    match event.event {
+        Event::Def { kills: true, .. } => {
+            state.remove(&event.symbol_id);
+        }
+        Event::Def { kills: false, .. } => {}
+        Event::Use(_) => {
+            state.insert(event.symbol_id);
+        }
+    }
+}
+
+impl<'s> Liveness<'s> {
+    pub fn new(semantic: &Semantic, def_use: &'s DefUse) -> Self {
+        let cfg = semantic.cfg();
+        let results = solve(cfg, &LivenessAnalysis(def_use));
+        let mut handler_live: FxHashMap<BasicBlockId, FxHashSet<SymbolId>> = FxHashMap::default();
+        for (block_id, _) in cfg.iter_basic_blocks() {
+            for (succ, edge_type) in cfg.successors(block_id) {
+                if edge_type == EdgeType::Error {
+                    let live = results.entry_state(succ).iter().copied();
+                    handler_live.entry(block_id).or_default().extend(live);
+                }
+            }
+        }
+        Self { def_use, results, handler_live }
+    }
+
+    /// Whether the value assigned by `definition_id` may be read later.
+    pub fn is_live(&self, definition_id: DefinitionId) -> bool {
+        let symbol_id = self.def_use.get_definition(definition_id).symbol_id;
+        if self.def_use.is_escaping(symbol_id) {
+            return true;
+        }
+        let block_id = self.def_use.definition_blocks[definition_id];
+        let mut state = self.results.exit_state(block_id).clone();
+        for event in self.def_use.events(block_id).iter().rev() {
+            if matches!(event.event, Event::Def { definition_id: id, .. } if id == definition_id) {
+                // An exception may be thrown at any point after the definition
+                return state.contains(&symbol_id)
+                    || self
+                        .handler_live
+                        .get(&block_id)
+                        .is_some_and(|live| live.contains(&symbol_id));
+            }
+            apply_liveness(event, &mut state);
+        }
+        false
+    }
+
+    /// Whether `symbol_id` may be read after the end of the basic block
+    pub fn is_live_at_exit(&self, block_id: BasicBlockId, symbol_id: SymbolId) -> bool {
+        self.def_use.is_escaping(symbol_id)
+            || self.results.exit_state(block_id).contains(&symbol_id)
+    }
+}
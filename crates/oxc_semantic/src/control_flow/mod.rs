@@ -0,0 +1,140 @@
+mod builder;
+mod dataflow;
+mod definitions;
+
+use oxc_index::{define_index_type, IndexVec};
+use rustc_hash::FxHashMap;
+
+pub use builder::ControlFlowGraphBuilder;
+pub use dataflow::{solve, Analysis, DataflowResults, Direction};
+pub use definitions::{DefUse, Definition, DefinitionId, Liveness, ReachingDefinitions};
+
+use crate::node::AstNodeId;
+
+define_index_type! {
+    pub struct BasicBlockId = u32;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeType {
+    /// Sequential flow, or one side of a conditional branch
+    Normal,
+    /// Flow from the end of a loop iteration back to the loop header
+    Backedge,
+    /// `break`, `continue` and `return`, including jumps routed through a `finally` block
+    Jump,
+    /// Implicit or explicit exception flow into a `catch` or `finally` block
+    Error,
+}
+
+#[derive(Debug, Default)]
+pub struct BasicBlock {
+    /// AST nodes evaluated in this block, in the order they were visited
+    elements: Vec<AstNodeId>,
+    reachable: bool,
+}
+
+impl BasicBlock {
+    pub fn elements(&self) -> &Vec<AstNodeId> {
+        &self.elements
+    }
+
+    pub fn is_reachable(&self) -> bool {
+        self.reachable
+    }
+}
+
+/// Entry and exit information of a function, arrow function or class static block.
+#[derive(Debug, Clone, Copy)]
+pub struct FunctionFlow {
+    pub entry: BasicBlockId,
+    /// The block control is in after the last statement of the body
+    pub end: BasicBlockId,
+}
+
+/// Control Flow Graph
+///
+/// Every function, arrow function and class static block has its own entry block, which is
+/// always considered reachable. The program entry is [`ControlFlowGraph::program_entry`].
+#[derive(Debug, Default)]
+pub struct ControlFlowGraph {
+    basic_blocks: IndexVec<BasicBlockId, BasicBlock>,
+    successors: IndexVec<BasicBlockId, Vec<(BasicBlockId, EdgeType)>>,
+    predecessors: IndexVec<BasicBlockId, Vec<(BasicBlockId, EdgeType)>>,
+    /// The basic block each AST node is evaluated in
+    node_blocks: IndexVec<AstNodeId, BasicBlockId>,
+    functions: FxHashMap<AstNodeId, FunctionFlow>,
+}
+
+impl ControlFlowGraph {
+    pub fn len(&self) -> usize {
+        self.basic_blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn program_entry(&self) -> BasicBlockId {
+        BasicBlockId::new(0)
+    }
+
+    pub fn basic_block(&self, block_id: BasicBlockId) -> &BasicBlock {
+        &self.basic_blocks[block_id]
+    }
+
+    pub fn iter_basic_blocks(&self) -> impl Iterator<Item = (BasicBlockId, &BasicBlock)> + '_ {
+        self.basic_blocks.iter_enumerated()
+    }
+
+    pub fn successors(
+        &self,
+        block_id: BasicBlockId,
+    ) -> impl Iterator<Item = (BasicBlockId, EdgeType)> + '_ {
+        self.successors[block_id].iter().copied()
+    }
+
+    pub fn predecessors(
+        &self,
+        block_id: BasicBlockId,
+    ) -> impl Iterator<Item = (BasicBlockId, EdgeType)> + '_ {
+        self.predecessors[block_id].iter().copied()
+    }
+
+    /// Get the basic block the given AST node is evaluated in
+    pub fn node_block(&self, node_id: AstNodeId) -> BasicBlockId {
+        self.node_blocks[node_id]
+    }
+
+    /// Whether any path from a program or function entry reaches the given AST node
+    pub fn is_reachable(&self, node_id: AstNodeId) -> bool {
+        self.basic_blocks[self.node_block(node_id)].reachable
+    }
+
+    /// Get the entry and end blocks of a `Function`, `ArrowExpression` or `StaticBlock` node
+    pub fn function_flow(&self, node_id: AstNodeId) -> Option<FunctionFlow> {
+        self.functions.get(&node_id).copied()
+    }
+
+    /// Whether control can run off the end of a function body without an explicit
+    /// `return` or `throw`.
+    pub fn is_function_end_reachable(&self, node_id: AstNodeId) -> bool {
+        self.function_flow(node_id).is_some_and(|flow| self.basic_blocks[flow.end].reachable)
+    }
+
+    /// Whether `to` can be reached by following edges starting from `from`
+    pub fn is_reachable_from(&self, from: BasicBlockId, to: BasicBlockId) -> bool {
+        let mut visited = vec![false; self.len()];
+        let mut stack = vec![from];
+        while let Some(block_id) = stack.pop() {
+            if block_id == to {
+                return true;
+            }
+            if std::mem::replace(&mut visited[block_id.index()], true) {
+                continue;
+            }
+            stack.extend(self.successors[block_id].iter().map(|(id, _)| *id));
+        }
+        false
+    }
+}
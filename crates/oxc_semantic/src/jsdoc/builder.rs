@@ -24,7 +24,9 @@ impl<'a> JSDocBuilder<'a> {
 
     /// Save the span if the given kind has a jsdoc comment attached
     pub fn retrieve_jsdoc_comment(&mut self, kind: AstKind<'a>) -> bool {
-        if !kind.is_declaration() {
+        if !kind.is_declaration()
+            && !matches!(kind, AstKind::MethodDefinition(_) | AstKind::ObjectProperty(_))
+        {
             return false;
         }
         let span = kind.span();
@@ -37,7 +39,7 @@ impl<'a> JSDocBuilder<'a> {
 
     /// Find the jsdoc doc in front of this span, a.k.a leading comment
     fn find_jsdoc_comment(&self, span: Span) -> Option<&'a str> {
-        let (start, comment) = self.trivias.comments().range(..span.start).next()?;
+        let (start, comment) = self.trivias.comments().range(..span.start).next_back()?;
 
         if comment.kind().is_single_line() {
             return None;
@@ -69,9 +71,12 @@ impl<'a> JSDocBuilder<'a> {
 mod test {
     use oxc_allocator::Allocator;
     use oxc_parser::Parser;
-    use oxc_span::{SourceType, Span};
+    use oxc_span::{Atom, SourceType, Span};
 
-    use crate::{jsdoc::JSDocComment, SemanticBuilder};
+    use crate::{
+        jsdoc::{JSDocComment, Param, ParamType},
+        SemanticBuilder,
+    };
 
     #[allow(clippy::cast_possible_truncation)]
     fn get_jsdoc<'a>(
@@ -153,4 +158,48 @@ mod test {
         let source_type = SourceType::default().with_typescript(true);
         test_jsdoc(source, "bar: string;", Some(source_type));
     }
+
+    #[test]
+    fn found_on_method_definition() {
+        let source = "class Foo {
+            /** jsdoc */
+            bar() {}
+        }";
+        test_jsdoc(source, "bar() {}", None);
+    }
+
+    #[test]
+    fn found_by_symbol() {
+        let allocator = Allocator::default();
+        let source_text = "
+            /** @deprecated */
+            export const a = 1;
+            /**
+             * @param {string} x
+             * @returns {number}
+             */
+            function b(x) {}
+            const c = 1;
+        ";
+        let source_type = SourceType::default().with_module(true);
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let semantic = SemanticBuilder::new(source_text, source_type)
+            .with_trivias(ret.trivias)
+            .build(program)
+            .semantic;
+        let symbol_jsdoc = |name: &str| {
+            let symbol_id = semantic.symbols().get_symbol_id_from_name(&Atom::from(name)).unwrap();
+            semantic.symbol_jsdoc(symbol_id)
+        };
+
+        assert!(symbol_jsdoc("a").is_some_and(|jsdoc| jsdoc.is_deprecated()));
+        let jsdoc = symbol_jsdoc("b").unwrap();
+        assert_eq!(
+            jsdoc.param("x").and_then(Param::r#type).as_ref().map(ParamType::value),
+            Some("string")
+        );
+        assert_eq!(jsdoc.returns().as_ref().map(ParamType::value), Some("number"));
+        assert!(symbol_jsdoc("c").is_none());
+    }
 }
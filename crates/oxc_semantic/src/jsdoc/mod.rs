@@ -6,7 +6,7 @@ pub use builder::JSDocBuilder;
 use oxc_span::{GetSpan, Span};
 
 use self::parser::JSDocParser;
-pub use self::parser::JSDocTag;
+pub use self::parser::{JSDocTag, JSDocTagKind, Param, ParamType, ParamTypeKind};
 use crate::AstNode;
 
 mod parser;
@@ -50,4 +50,21 @@ impl<'a> JSDocComment<'a> {
     pub fn tags<'b>(&'b self) -> &'b Vec<JSDocTag<'a>> {
         self.tags.get_or_init(|| JSDocParser::new(self.comment).parse())
     }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.tags().iter().any(JSDocTag::is_deprecated)
+    }
+
+    /// Get the `@param` tag documenting the parameter `name`
+    pub fn param(&self, name: &str) -> Option<&Param<'a>> {
+        self.tags().iter().filter_map(JSDocTag::param).find(|param| param.name() == name)
+    }
+
+    /// Get the type expression of the `@returns` tag
+    pub fn returns(&self) -> Option<ParamType<'a>> {
+        self.tags().iter().find_map(|tag| match tag.kind {
+            JSDocTagKind::Returns(r#type) => r#type,
+            _ => None,
+        })
+    }
 }
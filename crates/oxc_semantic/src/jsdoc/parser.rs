@@ -12,7 +12,11 @@ pub struct ParamType<'a> {
 }
 
 impl<'a> ParamType<'a> {
-    #[allow(unused)]
+    /// The type expression without the surrounding braces, e.g. `Array<string>`
+    pub fn value(&self) -> &'a str {
+        self.value
+    }
+
     pub fn kind(&self) -> Option<ParamTypeKind> {
         ParamTypeKind::from_str(self.value).map(Option::Some).unwrap_or_default()
     }
@@ -41,10 +45,25 @@ pub struct Param<'a> {
     r#type: Option<ParamType<'a>>,
 }
 
+impl<'a> Param<'a> {
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn r#type(&self) -> Option<ParamType<'a>> {
+        self.r#type
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JSDocTagKind<'a> {
     Deprecated,
     Param(Param<'a>),
+    /// `@returns` or `@return`
+    Returns(Option<ParamType<'a>>),
+    Type(Option<ParamType<'a>>),
+    /// Any other tag, e.g. `@since` or `@example`
+    Unknown(&'a str),
 }
 
 impl<'a> FromStr for JSDocTagKind<'a> {
@@ -53,7 +72,9 @@ impl<'a> FromStr for JSDocTagKind<'a> {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "deprecated" => Ok(Self::Deprecated),
-            "param" => Ok(Self::Param(Param::default())),
+            "param" | "arg" | "argument" => Ok(Self::Param(Param::default())),
+            "returns" | "return" => Ok(Self::Returns(None)),
+            "type" => Ok(Self::Type(None)),
             _ => Err(()),
         }
     }
@@ -69,6 +90,22 @@ impl<'a> JSDocTag<'a> {
     pub fn is_deprecated(&self) -> bool {
         matches!(self.kind, JSDocTagKind::Deprecated)
     }
+
+    pub fn param(&self) -> Option<&Param<'a>> {
+        match &self.kind {
+            JSDocTagKind::Param(param) => Some(param),
+            _ => None,
+        }
+    }
+
+    /// The type expression of a `@param`, `@returns` or `@type` tag
+    pub fn type_expression(&self) -> Option<ParamType<'a>> {
+        match self.kind {
+            JSDocTagKind::Param(param) => param.r#type,
+            JSDocTagKind::Returns(r#type) | JSDocTagKind::Type(r#type) => r#type,
+            JSDocTagKind::Deprecated | JSDocTagKind::Unknown(_) => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -127,11 +164,10 @@ impl<'a> JSDocParser<'a> {
 
         while let Some(c) = comment.chars().nth(self.current) {
             match c {
-                '@' => {
+                // `@` only starts a tag at the beginning of a line, not in `{@link}` or emails
+                '@' if Self::is_line_start(&comment[..self.current]) => {
                     self.current += 1;
-                    let Some(tag) = self.parse_tag(comment) else { break };
-                    self.current += tag.description.len();
-                    tags.push(tag);
+                    tags.push(self.parse_tag(comment));
                 }
                 _ => {
                     self.current += 1;
@@ -142,35 +178,83 @@ impl<'a> JSDocParser<'a> {
         tags
     }
 
-    fn parse_tag(&mut self, comment: &'a str) -> Option<JSDocTag<'a>> {
+    /// Whether only whitespace and the leading `/**` or `*` of a line precede the end of `text`
+    fn is_line_start(text: &str) -> bool {
+        text.rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .all(|c| matches!(c, ' ' | '\t' | '*' | '/'))
+    }
+
+    fn parse_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
         let tag = self.take_until(comment, |c| c == ' ' || c == '\n');
-        JSDocTagKind::from_str(tag).map_or(None, |kind| match kind {
-            JSDocTagKind::Deprecated => Some(self.parse_deprecated_tag(comment)),
-            JSDocTagKind::Param { .. } => Some(self.parse_param_tag(comment)),
-        })
+        match JSDocTagKind::from_str(tag) {
+            Ok(JSDocTagKind::Param(_)) => self.parse_param_tag(comment),
+            Ok(JSDocTagKind::Returns(_)) => {
+                let r#type = self.parse_type(comment);
+                JSDocTag {
+                    kind: JSDocTagKind::Returns(r#type),
+                    description: self.parse_description(comment),
+                }
+            }
+            Ok(JSDocTagKind::Type(_)) => {
+                let r#type = self.parse_type(comment);
+                JSDocTag {
+                    kind: JSDocTagKind::Type(r#type),
+                    description: self.parse_description(comment),
+                }
+            }
+            Ok(JSDocTagKind::Deprecated) => JSDocTag {
+                kind: JSDocTagKind::Deprecated,
+                description: self.parse_description(comment),
+            },
+            Ok(JSDocTagKind::Unknown(_)) | Err(()) => JSDocTag {
+                kind: JSDocTagKind::Unknown(tag),
+                description: self.parse_description(comment),
+            },
+        }
     }
 
-    fn parse_deprecated_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
+    fn parse_description(&mut self, comment: &'a str) -> &'a str {
         self.skip_whitespace(comment);
-        let description = self.take_until(comment, |c| c == '\n' || c == '*');
-        JSDocTag { kind: JSDocTagKind::Deprecated, description }
+        self.take_until(comment, |c| c == '\n' || c == '*').trim_end()
     }
 
-    fn parse_param_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
+    /// Parse a type expression such as `{Array<{ a: string }>}`, with balanced braces.
+    fn parse_type(&mut self, comment: &'a str) -> Option<ParamType<'a>> {
         self.skip_whitespace(comment);
+        if !self.at('{') {
+            return None;
+        }
 
-        let mut r#type = None;
-
-        if self.at('{') {
-            // If we hit a space, then treat it as the end of the type annotation.
-            let type_annotation = self.take_until(comment, |c| c == '}' || c == ' ');
-            r#type = Some(ParamType { value: type_annotation });
-            if self.at('}') {
-                self.skip_whitespace(comment);
+        let start = self.current;
+        let mut depth = 0;
+        for (offset, c) in comment[start..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    self.current = start + offset + 1;
+                    self.skip_whitespace(comment);
+                    return Some(ParamType { value: &comment[start..start + offset] });
+                }
+                '}' => depth -= 1,
+                '\n' => break,
+                _ => {}
             }
-            self.skip_whitespace(comment);
         }
 
+        // Unterminated type expression, treat a space as the end of the type annotation.
+        let type_annotation = self.take_until(comment, |c| c == '}' || c == ' ');
+        self.at('}');
+        self.skip_whitespace(comment);
+        Some(ParamType { value: type_annotation })
+    }
+
+    fn parse_param_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
+        let r#type = self.parse_type(comment);
+        self.skip_whitespace(comment);
+
         let name = self.take_until(comment, |c| c == ' ' || c == '\n');
 
         self.skip_whitespace(comment);
@@ -178,7 +262,7 @@ impl<'a> JSDocParser<'a> {
             self.skip_whitespace(comment);
         }
 
-        let description = self.take_until(comment, |c| c == '\n' || c == '*');
+        let description = self.take_until(comment, |c| c == '\n' || c == '*').trim_end();
 
         JSDocTag { kind: JSDocTagKind::Param(Param { name, r#type }), description }
     }
@@ -303,4 +387,72 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn parses_returns_and_type_tags() {
+        let source = r"/**
+        * @returns {Promise<string>} the result
+        * @type {number}
+        */
+       ";
+
+        let tags = JSDocParser::new(source).parse();
+        assert_eq!(
+            tags,
+            vec![
+                JSDocTag {
+                    kind: JSDocTagKind::Returns(Some(ParamType { value: "Promise<string>" })),
+                    description: "the result"
+                },
+                JSDocTag {
+                    kind: JSDocTagKind::Type(Some(ParamType { value: "number" })),
+                    description: ""
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_nested_type_expression() {
+        let source = "/** @param {{ a: string, b: { c: number } }} options - the options */";
+
+        let tags = JSDocParser::new(source).parse();
+        assert_eq!(
+            tags,
+            vec![JSDocTag {
+                kind: JSDocTagKind::Param(Param {
+                    name: "options",
+                    r#type: Some(ParamType { value: "{ a: string, b: { c: number } }" })
+                }),
+                description: "the options"
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_unknown_tags() {
+        let source = r"/**
+        * Contact me@example.com, see {@link foo}
+        * @since 1.0
+        * @deprecated use something else instead
+        * @param a
+        */
+       ";
+
+        let tags = JSDocParser::new(source).parse();
+        assert_eq!(
+            tags,
+            vec![
+                JSDocTag { kind: JSDocTagKind::Unknown("since"), description: "1.0" },
+                JSDocTag {
+                    kind: JSDocTagKind::Deprecated,
+                    description: "use something else instead"
+                },
+                JSDocTag {
+                    kind: JSDocTagKind::Param(Param { name: "a", r#type: None }),
+                    description: ""
+                },
+            ]
+        );
+    }
 }
@@ -1,32 +1,48 @@
 mod binder;
 mod builder;
+mod call_graph;
 mod checker;
 mod class;
+mod control_flow;
 mod diagnostics;
 mod jsdoc;
 mod module_record;
 mod node;
+pub mod react;
 mod reference;
+mod rename;
 mod scope;
+pub mod selector;
+mod side_effects;
 mod symbol;
 
-use std::{rc::Rc, sync::Arc};
+use std::{cell::OnceCell, rc::Rc, sync::Arc};
 
 pub use builder::{SemanticBuilder, SemanticBuilderReturn};
 use class::ClassTable;
-pub use jsdoc::{JSDoc, JSDocComment, JSDocTag};
-use oxc_ast::{ast::IdentifierReference, AstKind, TriviasMap};
-use oxc_span::SourceType;
+pub use jsdoc::{JSDoc, JSDocComment, JSDocTag, JSDocTagKind, Param, ParamType, ParamTypeKind};
+use oxc_ast::{
+    ast::{Class, Expression, IdentifierReference},
+    AstKind, TriviasMap,
+};
+use oxc_span::{SourceType, Span};
 pub use oxc_syntax::{
     module_record::ModuleRecord,
     scope::{ScopeFlags, ScopeId},
     symbol::{SymbolFlags, SymbolId},
 };
+use side_effects::SideEffects;
 
 pub use crate::{
     builder::VariableInfo,
+    call_graph::{CallGraph, CallSite},
+    control_flow::{
+        solve, Analysis, BasicBlock, BasicBlockId, ControlFlowGraph, DataflowResults, DefUse,
+        Definition, DefinitionId, Direction, EdgeType, FunctionFlow, Liveness, ReachingDefinitions,
+    },
     node::{AstNode, AstNodeId, AstNodes, NodeFlags},
     reference::{Reference, ReferenceFlag, ReferenceId},
+    rename::{RenameEdit, RenameError},
     scope::ScopeTree,
     symbol::SymbolTable,
 };
@@ -44,6 +60,8 @@ pub struct Semantic<'a> {
 
     classes: ClassTable,
 
+    cfg: ControlFlowGraph,
+
     trivias: Rc<TriviasMap>,
 
     module_record: Arc<ModuleRecord>,
@@ -53,6 +71,9 @@ pub struct Semantic<'a> {
     unused_labels: Vec<AstNodeId>,
 
     redeclare_variables: Vec<VariableInfo>,
+
+    /// Lazily built on first access
+    call_graph: OnceCell<CallGraph>,
 }
 
 impl<'a> Semantic<'a> {
@@ -80,6 +101,14 @@ impl<'a> Semantic<'a> {
         &self.classes
     }
 
+    pub fn cfg(&self) -> &ControlFlowGraph {
+        &self.cfg
+    }
+
+    pub fn call_graph(&self) -> &CallGraph {
+        self.call_graph.get_or_init(|| CallGraph::new(self))
+    }
+
     pub fn scopes_mut(&mut self) -> &mut ScopeTree {
         &mut self.scopes
     }
@@ -126,6 +155,21 @@ impl<'a> Semantic<'a> {
         self.nodes.get_node(self.symbols.get_declaration(symbol_id))
     }
 
+    /// Get the JSDoc comment documenting a symbol, e.g. the comment in front of the
+    /// `VariableDeclaration` or the `export` keyword for `export const a = 1`.
+    pub fn symbol_jsdoc(&self, symbol_id: SymbolId) -> Option<JSDocComment<'a>> {
+        let mut node = self.symbol_declaration(symbol_id);
+        if matches!(node.kind(), AstKind::VariableDeclarator(_)) {
+            node = self.nodes.parent_node(node.id())?;
+        }
+        self.jsdoc.get_by_node(node).or_else(|| {
+            let parent = self.nodes.parent_node(node.id())?;
+            matches!(parent.kind(), AstKind::ModuleDeclaration(_))
+                .then(|| self.jsdoc.get_by_node(parent))
+                .flatten()
+        })
+    }
+
     pub fn is_reference_to_global_variable(&self, ident: &IdentifierReference) -> bool {
         self.scopes().root_unresolved_references().contains_key(&ident.name)
     }
@@ -133,6 +177,56 @@ impl<'a> Semantic<'a> {
     pub fn redeclare_variables(&self) -> &Vec<VariableInfo> {
         &self.redeclare_variables
     }
+
+    /// Get the edits for renaming a symbol at all of its declarations and references.
+    ///
+    /// # Errors
+    ///
+    /// When the new name is not a valid identifier or conflicts with another binding
+    pub fn rename_symbol(
+        &self,
+        symbol_id: SymbolId,
+        new_name: &str,
+    ) -> Result<Vec<RenameEdit>, RenameError> {
+        rename::rename_symbol(self, symbol_id, new_name)
+    }
+
+    /// Whether evaluating `expr` has no side effects, so it can be removed if its value is unused.
+    ///
+    /// Respects `/*#__PURE__*/` annotations and a list of known-pure global builtins, and is
+    /// conservative otherwise, e.g. property accesses may run getters and are not pure.
+    pub fn is_side_effect_free(&self, expr: &Expression<'a>) -> bool {
+        SideEffects::new(self).is_pure_expression(expr)
+    }
+
+    /// Whether evaluating the declaration of `class` has no side effects, e.g. from its static
+    /// properties and blocks, computed keys, decorators or super class.
+    pub fn is_side_effect_free_class(&self, class: &Class<'a>) -> bool {
+        SideEffects::new(self).is_pure_class(class)
+    }
+
+    /// Whether calling the [`AstKind::Function`] or [`AstKind::ArrowExpression`] at `node_id`
+    /// with side-effect free arguments has no side effects, or the function is annotated with
+    /// `/*#__NO_SIDE_EFFECTS__*/`.
+    pub fn is_pure_function(&self, node_id: AstNodeId) -> bool {
+        let mut side_effects = SideEffects::new(self);
+        match self.nodes.kind(node_id) {
+            AstKind::Function(func) => {
+                func.pure
+                    || side_effects.is_pure_function(func.span, &func.params, func.body.as_deref())
+            }
+            AstKind::ArrowExpression(arrow) => {
+                arrow.pure
+                    || side_effects.is_pure_function(arrow.span, &arrow.params, Some(&arrow.body))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the expression at `span` is preceded by a `/*#__PURE__*/` annotation.
+    pub fn has_pure_annotation(&self, span: Span) -> bool {
+        SideEffects::has_pure_annotation(self, span)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,247 @@
+//! Detection of React components and hooks
+//!
+//! Shared by lint rules and transforms which need to agree on what React considers a
+//! component or a hook.
+
+use oxc_ast::{
+    ast::{
+        ArrowExpression, AssignmentTarget, BindingPatternKind, CallExpression, Class, Expression,
+        ExpressionStatement, Function, FunctionBody, ReturnStatement, SimpleAssignmentTarget,
+    },
+    AstKind, Visit,
+};
+use oxc_span::Atom;
+use oxc_syntax::scope::ScopeFlags;
+
+use crate::{AstNode, AstNodes};
+
+pub fn is_create_element_call(call_expr: &CallExpression) -> bool {
+    if let Some(member_expr) = call_expr.callee.get_member_expr() {
+        return member_expr.static_property_name() == Some("createElement");
+    }
+
+    false
+}
+
+const PRAGMA: &str = "React";
+const CREATE_CLASS: &str = "createReactClass";
+
+pub fn is_es5_component(node: &AstNode) -> bool {
+    let AstKind::CallExpression(call_expr) = node.kind() else { return false };
+
+    if let Expression::MemberExpression(member_expr) = &call_expr.callee {
+        if let Expression::Identifier(ident) = member_expr.object() {
+            return ident.name == PRAGMA
+                && member_expr.static_property_name() == Some(CREATE_CLASS);
+        }
+    }
+
+    if let Some(ident_reference) = call_expr.callee.get_identifier_reference() {
+        return ident_reference.name == CREATE_CLASS;
+    }
+
+    false
+}
+
+const COMPONENT: &str = "Component";
+const PURE_COMPONENT: &str = "PureComponent";
+
+pub fn is_es6_component(node: &AstNode) -> bool {
+    let AstKind::Class(class_expr) = node.kind() else { return false };
+    if let Some(super_class) = &class_expr.super_class {
+        if let Expression::MemberExpression(member_expr) = super_class {
+            if let Expression::Identifier(ident) = member_expr.object() {
+                return ident.name == PRAGMA
+                    && member_expr
+                        .static_property_name()
+                        .is_some_and(|name| name == COMPONENT || name == PURE_COMPONENT);
+            }
+        }
+
+        if let Some(ident_reference) = super_class.get_identifier_reference() {
+            return ident_reference.name == COMPONENT || ident_reference.name == PURE_COMPONENT;
+        }
+    }
+
+    false
+}
+
+/// Whether `node` is a class component, i.e. `class extends React.Component` or
+/// `createReactClass({})`
+pub fn is_class_component(node: &AstNode) -> bool {
+    is_es6_component(node) || is_es5_component(node)
+}
+
+/// React's naming convention for hooks: `use` followed by an uppercase letter or digit, e.g.
+/// `useState`
+pub fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use").is_some_and(|rest| {
+        rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_uppercase() || c.is_ascii_digit())
+    })
+}
+
+/// React's naming convention for components: an uppercase first letter, e.g. `App`
+pub fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// `useState()`, `React.useState()` or `Namespace.useCustom()`
+pub fn is_hook_call(call_expr: &CallExpression) -> bool {
+    match call_expr.callee.without_parenthesized() {
+        Expression::Identifier(ident) => is_hook_name(&ident.name),
+        Expression::MemberExpression(member_expr) => {
+            let is_namespace = matches!(member_expr.object(),
+                Expression::Identifier(ident) if is_component_name(&ident.name));
+            is_namespace && member_expr.static_property_name().is_some_and(is_hook_name)
+        }
+        _ => false,
+    }
+}
+
+const MEMO: &str = "memo";
+const FORWARD_REF: &str = "forwardRef";
+
+/// `memo(Component)`, `forwardRef(Component)`, `React.memo(Component)` or
+/// `React.forwardRef(Component)`
+pub fn is_memo_or_forward_ref_call(call_expr: &CallExpression) -> bool {
+    let name = match call_expr.callee.without_parenthesized() {
+        Expression::Identifier(ident) => Some(ident.name.as_str()),
+        Expression::MemberExpression(member_expr) => match member_expr.object() {
+            Expression::Identifier(ident) if ident.name == PRAGMA => {
+                member_expr.static_property_name()
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    matches!(name, Some(MEMO | FORWARD_REF))
+}
+
+/// Whether the [`AstKind::Function`] or [`AstKind::ArrowExpression`] `node` is a function
+/// component: it returns JSX, and is either named like a component or wrapped in `memo` or
+/// `forwardRef`.
+pub fn is_function_component<'a>(node: &AstNode<'a>, nodes: &AstNodes<'a>) -> bool {
+    let returns_jsx = match node.kind() {
+        AstKind::Function(func) => {
+            // Class methods such as `render` are not components
+            if matches!(nodes.parent_kind(node.id()), Some(AstKind::MethodDefinition(_))) {
+                return false;
+            }
+            func.body.as_ref().is_some_and(|body| returns_jsx(body, false))
+        }
+        AstKind::ArrowExpression(arrow) => returns_jsx(&arrow.body, arrow.expression),
+        _ => return false,
+    };
+    if !returns_jsx {
+        return false;
+    }
+    get_function_like_name(node, nodes).is_some_and(|name| is_component_name(name))
+        || is_wrapped_in_memo_or_forward_ref(node, nodes)
+}
+
+/// Whether the [`AstKind::Function`] or [`AstKind::ArrowExpression`] `node` is a custom hook,
+/// i.e. a function named like a hook.
+pub fn is_hook_function<'a>(node: &AstNode<'a>, nodes: &AstNodes<'a>) -> bool {
+    matches!(node.kind(), AstKind::Function(_) | AstKind::ArrowExpression(_))
+        && get_function_like_name(node, nodes).is_some_and(|name| is_hook_name(name))
+}
+
+/// The name of a function, or of the variable it is assigned to, looking through `memo` and
+/// `forwardRef`, e.g. `Foo` for `function Foo() {}` and `const Foo = memo(() => {})`.
+pub fn get_function_like_name<'a>(node: &AstNode<'a>, nodes: &AstNodes<'a>) -> Option<&'a Atom> {
+    if let AstKind::Function(func) = node.kind() {
+        if let Some(id) = &func.id {
+            return Some(&id.name);
+        }
+    }
+    let parent = outer_wrapped_node(node, nodes);
+    match nodes.parent_kind(parent.id())? {
+        AstKind::VariableDeclarator(decl) => match &decl.id.kind {
+            BindingPatternKind::BindingIdentifier(ident) => Some(&ident.name),
+            _ => None,
+        },
+        AstKind::AssignmentExpression(expr) => match &expr.left {
+            AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(ident),
+            ) => Some(&ident.name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `node` is the argument of `memo()` or `forwardRef()`
+pub fn is_wrapped_in_memo_or_forward_ref<'a>(node: &AstNode<'a>, nodes: &AstNodes<'a>) -> bool {
+    outer_wrapped_node(node, nodes).id() != node.id()
+}
+
+/// The outermost `memo()` or `forwardRef()` call wrapping `node`, or `node` itself
+fn outer_wrapped_node<'a, 'b>(node: &'b AstNode<'a>, nodes: &'b AstNodes<'a>) -> &'b AstNode<'a> {
+    let mut outer = node;
+    let mut current = node;
+    while let Some(parent) = nodes.parent_node(current.id()) {
+        match parent.kind() {
+            AstKind::ParenthesizedExpression(_) | AstKind::Argument(_) => {}
+            AstKind::CallExpression(call_expr) if is_memo_or_forward_ref_call(call_expr) => {
+                outer = parent;
+            }
+            _ => break,
+        }
+        current = parent;
+    }
+    outer
+}
+
+/// Whether any `return` of the function body, or the body of an arrow expression, returns JSX
+fn returns_jsx(body: &FunctionBody, expression: bool) -> bool {
+    let mut visitor = ReturnsJsx { expression, returns_jsx: false };
+    visitor.visit_function_body(body);
+    visitor.returns_jsx
+}
+
+struct ReturnsJsx {
+    /// Whether the body is the expression of an arrow function, e.g. `() => <div />`
+    expression: bool,
+    returns_jsx: bool,
+}
+
+impl ReturnsJsx {
+    fn is_jsx(expr: &Expression) -> bool {
+        match expr {
+            Expression::JSXElement(_) | Expression::JSXFragment(_) => true,
+            Expression::CallExpression(call_expr) => is_create_element_call(call_expr),
+            Expression::ParenthesizedExpression(expr) => Self::is_jsx(&expr.expression),
+            Expression::ConditionalExpression(expr) => {
+                Self::is_jsx(&expr.consequent) || Self::is_jsx(&expr.alternate)
+            }
+            Expression::LogicalExpression(expr) => {
+                Self::is_jsx(&expr.left) || Self::is_jsx(&expr.right)
+            }
+            Expression::SequenceExpression(expr) => {
+                expr.expressions.last().is_some_and(Self::is_jsx)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Visit<'a> for ReturnsJsx {
+    fn visit_return_statement(&mut self, stmt: &ReturnStatement<'a>) {
+        if stmt.argument.as_ref().is_some_and(Self::is_jsx) {
+            self.returns_jsx = true;
+        }
+    }
+
+    fn visit_expression_statement(&mut self, stmt: &ExpressionStatement<'a>) {
+        if self.expression && Self::is_jsx(&stmt.expression) {
+            self.returns_jsx = true;
+        }
+    }
+
+    // `return` in nested functions and classes does not return from the component
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &Class<'a>) {}
+}
@@ -0,0 +1,265 @@
+//! Symbol renaming
+//!
+//! [`rename_symbol`] computes the text edits for renaming a symbol at its declarations and all of
+//! its references, and refuses renames which would change the meaning of the program.
+
+use oxc_ast::{
+    ast::{
+        AssignmentTarget, AssignmentTargetPattern, AssignmentTargetProperty, BindingPattern,
+        BindingPatternKind, ImportDeclarationSpecifier, ModuleDeclaration, ModuleExportName,
+    },
+    AstKind,
+};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_span::{Atom, GetSpan, Span};
+use oxc_syntax::{identifier::is_identifier_name, scope::ScopeId, symbol::SymbolId};
+
+use crate::{Reference, Semantic};
+
+/// Replace the source text at `span` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl RenameEdit {
+    fn new(span: Span, replacement: String) -> Self {
+        Self { span, replacement }
+    }
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum RenameError {
+    #[error("`{0}` is not a valid identifier")]
+    #[diagnostic()]
+    InvalidName(Atom),
+
+    #[error("Symbol `{0}` cannot be renamed")]
+    #[diagnostic()]
+    Unsupported(Atom, #[label] Span),
+
+    #[error("Identifier `{0}` has already been declared")]
+    #[diagnostic()]
+    Redeclaration(Atom, #[label("`{0}` is declared here")] Span),
+
+    #[error("Renaming to `{0}` changes which binding a reference refers to")]
+    #[diagnostic()]
+    Shadowed(Atom, #[label("This reference would refer to a different binding")] Span),
+}
+
+/// Compute the edits renaming `symbol_id` to `new_name`, sorted by their position.
+///
+/// Shorthand properties, shorthand import specifiers and local export specifiers are expanded
+/// so that property, import and export names stay the same, e.g. `{ a }` becomes `{ a: b }`.
+///
+/// # Errors
+///
+/// * `new_name` is not a valid identifier
+/// * `new_name` is already declared in the scope of the symbol
+/// * a reference to the symbol would resolve to another binding named `new_name`, or an
+///   existing reference to `new_name` would resolve to the renamed symbol
+pub fn rename_symbol(
+    semantic: &Semantic,
+    symbol_id: SymbolId,
+    new_name: &str,
+) -> Result<Vec<RenameEdit>, RenameError> {
+    let new_name = Atom::from(new_name);
+    if !is_identifier_name(&new_name) {
+        return Err(RenameError::InvalidName(new_name));
+    }
+
+    let symbols = semantic.symbols();
+    let old_name = symbols.get_name(symbol_id);
+    let span = symbols.get_span(symbol_id);
+    if span.source_text(semantic.source_text()) != old_name.as_str() {
+        // e.g. enum members and namespaces, which are declared with the span of the whole node
+        return Err(RenameError::Unsupported(old_name.clone(), span));
+    }
+    if *old_name == new_name {
+        return Ok(vec![]);
+    }
+
+    check_conflicts(semantic, symbol_id, &new_name)?;
+
+    let mut edits = vec![declaration_edit(semantic, symbol_id, &new_name)];
+    edits.extend(
+        semantic
+            .redeclare_variables()
+            .iter()
+            .filter(|variable| variable.symbol_id == symbol_id)
+            .map(|variable| RenameEdit::new(variable.span, new_name.to_string())),
+    );
+    edits.extend(
+        semantic
+            .symbol_references(symbol_id)
+            .map(|reference| reference_edit(semantic, reference, old_name, &new_name)),
+    );
+    if semantic.symbol_scope(symbol_id) == semantic.scopes().root_scope_id() {
+        export_specifier_edits(semantic, old_name, &new_name, &mut edits);
+    }
+
+    edits.sort_by_key(|edit| edit.span.start);
+    edits.dedup();
+    Ok(edits)
+}
+
+fn check_conflicts(
+    semantic: &Semantic,
+    symbol_id: SymbolId,
+    new_name: &Atom,
+) -> Result<(), RenameError> {
+    let scopes = semantic.scopes();
+    let symbols = semantic.symbols();
+    let symbol_scope = semantic.symbol_scope(symbol_id);
+
+    if let Some(other_id) = scopes.get_binding(symbol_scope, new_name) {
+        return Err(RenameError::Redeclaration(new_name.clone(), symbols.get_span(other_id)));
+    }
+
+    // A binding of `new_name` between a reference and the declaration would capture the reference
+    for reference in semantic.symbol_references(symbol_id) {
+        let reference_scope = semantic.nodes().get_node(reference.node_id()).scope_id();
+        let shadowed = scopes
+            .ancestors(reference_scope)
+            .take_while(|scope_id| *scope_id != symbol_scope)
+            .any(|scope_id| scopes.has_binding(scope_id, new_name));
+        if shadowed {
+            return Err(RenameError::Shadowed(new_name.clone(), reference.span()));
+        }
+    }
+
+    // A reference to an outer `new_name` inside of the symbol's scope would now resolve to the
+    // renamed symbol
+    for reference in symbols.references.iter().filter(|reference| reference.name() == new_name) {
+        let target_scope: Option<ScopeId> =
+            reference.symbol_id().map(|id| symbols.get_scope_id(id));
+        let reference_scope = semantic.nodes().get_node(reference.node_id()).scope_id();
+        for scope_id in scopes.ancestors(reference_scope) {
+            if Some(scope_id) == target_scope {
+                break;
+            }
+            if scope_id == symbol_scope {
+                return Err(RenameError::Shadowed(new_name.clone(), reference.span()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn declaration_edit(semantic: &Semantic, symbol_id: SymbolId, new_name: &Atom) -> RenameEdit {
+    let span = semantic.symbols().get_span(symbol_id);
+    let old_name = semantic.symbols().get_name(symbol_id);
+    let pattern = match semantic.symbol_declaration(symbol_id).kind() {
+        AstKind::VariableDeclarator(decl) => Some(&decl.id),
+        AstKind::FormalParameter(param) => Some(&param.pattern),
+        AstKind::CatchClause(clause) => clause.param.as_ref(),
+        AstKind::BindingRestElement(rest) => Some(&rest.argument),
+        AstKind::ModuleDeclaration(ModuleDeclaration::ImportDeclaration(decl)) => {
+            // `import { a } from "mod"` -> `import { a as b } from "mod"`
+            let is_shorthand = decl.specifiers.iter().flatten().any(|specifier| {
+                matches!(specifier, ImportDeclarationSpecifier::ImportSpecifier(specifier)
+                    if specifier.local.span == span && specifier.imported.span() == span)
+            });
+            if is_shorthand {
+                return RenameEdit::new(span, format!("{old_name} as {new_name}"));
+            }
+            None
+        }
+        _ => None,
+    };
+    if pattern.is_some_and(|pattern| is_shorthand_binding(pattern, span)) {
+        return RenameEdit::new(span, format!("{old_name}: {new_name}"));
+    }
+    RenameEdit::new(span, new_name.to_string())
+}
+
+/// Whether the binding identifier at `span` is the value of a shorthand property, e.g. `{ a }`
+/// or `{ a = 1 }`.
+fn is_shorthand_binding(pattern: &BindingPattern, span: Span) -> bool {
+    match &pattern.kind {
+        BindingPatternKind::BindingIdentifier(_) => false,
+        BindingPatternKind::ObjectPattern(pattern) => {
+            pattern.properties.iter().any(|property| {
+                let value = match &property.value.kind {
+                    BindingPatternKind::AssignmentPattern(pattern) => &pattern.left,
+                    _ => &property.value,
+                };
+                let is_ident = matches!(&value.kind,
+                    BindingPatternKind::BindingIdentifier(ident) if ident.span == span);
+                (property.shorthand && is_ident) || is_shorthand_binding(&property.value, span)
+            }) || pattern
+                .rest
+                .as_ref()
+                .is_some_and(|rest| is_shorthand_binding(&rest.argument, span))
+        }
+        BindingPatternKind::ArrayPattern(pattern) => {
+            pattern.elements.iter().flatten().any(|element| is_shorthand_binding(element, span))
+                || pattern
+                    .rest
+                    .as_ref()
+                    .is_some_and(|rest| is_shorthand_binding(&rest.argument, span))
+        }
+        BindingPatternKind::AssignmentPattern(pattern) => is_shorthand_binding(&pattern.left, span),
+    }
+}
+
+fn reference_edit(
+    semantic: &Semantic,
+    reference: &Reference,
+    old_name: &Atom,
+    new_name: &Atom,
+) -> RenameEdit {
+    let span = reference.span();
+    let is_shorthand = match semantic.nodes().parent_kind(reference.node_id()) {
+        // `({ a })`
+        Some(AstKind::ObjectProperty(property)) => property.shorthand,
+        // `({ a } = obj)`
+        Some(AstKind::AssignmentTarget(AssignmentTarget::AssignmentTargetPattern(
+            AssignmentTargetPattern::ObjectAssignmentTarget(target),
+        ))) => target.properties.iter().any(|property| {
+            matches!(property, AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident)
+                if ident.binding.span == span)
+        }),
+        _ => false,
+    };
+    if is_shorthand {
+        RenameEdit::new(span, format!("{old_name}: {new_name}"))
+    } else {
+        RenameEdit::new(span, new_name.to_string())
+    }
+}
+
+/// `export { a }` is not a reference, rewrite it to `export { b as a }`.
+fn export_specifier_edits(
+    semantic: &Semantic,
+    old_name: &Atom,
+    new_name: &Atom,
+    edits: &mut Vec<RenameEdit>,
+) {
+    for node in semantic.nodes().iter() {
+        let AstKind::ModuleDeclaration(ModuleDeclaration::ExportNamedDeclaration(decl)) =
+            node.kind()
+        else {
+            continue;
+        };
+        if decl.source.is_some() {
+            continue;
+        }
+        for specifier in &decl.specifiers {
+            let ModuleExportName::Identifier(local) = &specifier.local else { continue };
+            if local.name != *old_name {
+                continue;
+            }
+            if specifier.exported.span() == local.span {
+                edits.push(RenameEdit::new(local.span, format!("{new_name} as {old_name}")));
+            } else {
+                edits.push(RenameEdit::new(local.span, new_name.to_string()));
+            }
+        }
+    }
+}
@@ -6,6 +6,8 @@ use oxc_index::IndexVec;
 use oxc_span::Atom;
 pub use oxc_syntax::scope::{ScopeFlags, ScopeId};
 use rustc_hash::{FxHashMap, FxHasher};
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 use crate::{reference::ReferenceId, symbol::SymbolId, AstNodeId};
 
@@ -18,6 +20,7 @@ type UnresolvedReferences = FxHashMap<Atom, Vec<ReferenceId>>;
 ///
 /// `SoA` (Struct of Arrays) for memory efficiency.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
 pub struct ScopeTree {
     /// Maps a scope to the parent scope it belongs in
     parent_ids: IndexVec<ScopeId, Option<ScopeId>>,
@@ -0,0 +1,408 @@
+//! AST selectors
+//!
+//! [`Selector`] matches the nodes with the selectors of [ESQuery], as in the `no-restricted-syntax`
+//! rule and the selector keys of the rule listeners of ESLint, e.g.
+//! `CallExpression > Identifier[name="require"]` or `FunctionDeclaration:not(:has(ReturnStatement))`.
+//!
+//! The node types are the ESTree types, e.g. `Identifier` matches all the identifiers and
+//! `Literal` all the literals, or the names of the [AstKind] variants, e.g. `IdentifierReference`.
+//! The attributes are the ESTree properties of the nodes, like `name`, `operator`, `callee.name`
+//! or `regex.flags`.
+//!
+//! Supported:
+//! * `*`, `Type`, `[attr]`, `[attr="value"]`, `[attr=/regex/i]`, `[attr!=value]`, `[attr>=1]`
+//! * the combinators ` `, `>`, `~` and `+`, and the lists `a, b`
+//! * `:not(...)`, `:matches(...)`, `:is(...)`, `:has(...)`, `:has(> ...)`
+//! * `:first-child`, `:last-child`, `:nth-child(n)`, `:nth-last-child(n)`
+//! * `:statement`, `:expression`, `:declaration`, `:function`, `:pattern`
+//!
+//! The nodes of the oxc AST which only wrap a node and are not in ESTree, like
+//! [AstKind::Argument], are not matched and are skipped by the combinators.
+//!
+//! The children of a node, for the siblings and the positions, are all its child nodes in source
+//! order, not only the ones of the same property as in ESQuery.
+//!
+//! [ESQuery]: https://github.com/estools/esquery
+
+mod node;
+mod parser;
+
+use std::str::FromStr;
+
+use oxc_ast::AstKind;
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_span::GetSpan;
+use regex::Regex;
+
+use self::node::{is_transparent, kind_name, node_type, AttributeValue};
+use crate::{AstNode, AstNodeId, AstNodes};
+
+/// A parsed selector, which matches the nodes of a semantic
+#[derive(Debug, Clone)]
+pub struct Selector {
+    source: String,
+    selectors: Vec<Complex>,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid selector `{selector}`: {message} at offset {offset}")]
+#[diagnostic()]
+pub struct SelectorError {
+    pub message: String,
+    pub selector: String,
+    pub offset: usize,
+}
+
+/// The compounds separated by the combinators, `combinators[i]` is between `compounds[i]` and
+/// `compounds[i + 1]`
+#[derive(Debug, Clone)]
+struct Complex {
+    compounds: Vec<Compound>,
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+    /// `a ~ b`
+    Sibling,
+    /// `a + b`
+    Adjacent,
+}
+
+/// The simple selectors of a node, e.g. `Identifier[name="a"]:first-child`
+#[derive(Debug, Clone)]
+struct Compound {
+    simples: Vec<Simple>,
+}
+
+#[derive(Debug, Clone)]
+enum Simple {
+    Wildcard,
+    Type(String),
+    Attribute(Attribute),
+    Not(Vec<Complex>),
+    Matches(Vec<Complex>),
+    /// The selectors are relative to the node, and start with [Simple::Scope]
+    Has(Vec<Complex>),
+    /// One-based
+    NthChild(usize),
+    NthLastChild(usize),
+    Class(NodeClass),
+    /// The node tested by the enclosing `:has`
+    Scope,
+}
+
+#[derive(Debug, Clone)]
+struct Attribute {
+    path: Vec<String>,
+    /// `None` for `[attr]`, which tests that the attribute exists and is not null
+    test: Option<(AttributeOperator, AttributeOperand)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AttributeOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug, Clone)]
+enum AttributeOperand {
+    String(String),
+    /// An unquoted value, e.g. `[operator=typeof]`
+    Name(String),
+    Number(f64),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NodeClass {
+    Statement,
+    Expression,
+    Declaration,
+    Function,
+    Pattern,
+}
+
+impl Selector {
+    /// # Errors
+    ///
+    /// * The selector has a syntax error, an unknown pseudo-class or an invalid regular
+    ///   expression
+    pub fn parse(source: &str) -> Result<Self, SelectorError> {
+        let selectors = parser::Parser::new(source).parse()?;
+        Ok(Self { source: source.to_string(), selectors })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether `node` matches one of the selectors of the list
+    pub fn matches(&self, node: &AstNode, nodes: &AstNodes) -> bool {
+        if is_transparent(node.kind()) {
+            return false;
+        }
+        let matcher = Matcher { nodes, len: nodes.len() };
+        self.selectors.iter().any(|complex| matcher.matches_complex(complex, node.id(), None))
+    }
+
+    /// The matching nodes, in the order of [AstNodes::iter]
+    pub fn query<'s, 'a>(
+        &'s self,
+        nodes: &'s AstNodes<'a>,
+    ) -> impl Iterator<Item = &'s AstNode<'a>> + 's {
+        nodes.iter().filter(|node| self.matches(node, nodes))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+struct Matcher<'s, 'a> {
+    nodes: &'s AstNodes<'a>,
+    len: usize,
+}
+
+impl<'s, 'a> Matcher<'s, 'a> {
+    fn matches_complex(
+        &self,
+        complex: &Complex,
+        node_id: AstNodeId,
+        scope: Option<AstNodeId>,
+    ) -> bool {
+        self.matches_from(complex, complex.compounds.len() - 1, node_id, scope)
+    }
+
+    /// Whether `node_id` matches `complex.compounds[index]`, and its relatives match the
+    /// compounds before it, from right to left
+    fn matches_from(
+        &self,
+        complex: &Complex,
+        index: usize,
+        node_id: AstNodeId,
+        scope: Option<AstNodeId>,
+    ) -> bool {
+        if !self.matches_compound(&complex.compounds[index], node_id, scope) {
+            return false;
+        }
+        if index == 0 {
+            return true;
+        }
+        let index = index - 1;
+        match &complex.combinators[index] {
+            Combinator::Child => self
+                .parent(node_id)
+                .is_some_and(|parent_id| self.matches_from(complex, index, parent_id, scope)),
+            Combinator::Descendant => {
+                let mut current = self.parent(node_id);
+                while let Some(ancestor_id) = current {
+                    if self.matches_from(complex, index, ancestor_id, scope) {
+                        return true;
+                    }
+                    current = self.parent(ancestor_id);
+                }
+                false
+            }
+            Combinator::Adjacent => self
+                .previous_siblings(node_id)
+                .last()
+                .is_some_and(|sibling_id| self.matches_from(complex, index, *sibling_id, scope)),
+            Combinator::Sibling => self
+                .previous_siblings(node_id)
+                .iter()
+                .any(|sibling_id| self.matches_from(complex, index, *sibling_id, scope)),
+        }
+    }
+
+    fn matches_compound(
+        &self,
+        compound: &Compound,
+        node_id: AstNodeId,
+        scope: Option<AstNodeId>,
+    ) -> bool {
+        compound.simples.iter().all(|simple| self.matches_simple(simple, node_id, scope))
+    }
+
+    fn matches_simple(
+        &self,
+        simple: &Simple,
+        node_id: AstNodeId,
+        scope: Option<AstNodeId>,
+    ) -> bool {
+        let kind = self.nodes.kind(node_id);
+        match simple {
+            Simple::Wildcard => true,
+            Simple::Type(name) => {
+                node_type(kind).eq_ignore_ascii_case(name)
+                    || kind_name(kind).eq_ignore_ascii_case(name)
+            }
+            Simple::Attribute(attribute) => matches_attribute(attribute, kind),
+            Simple::Not(selectors) => {
+                !selectors.iter().any(|complex| self.matches_complex(complex, node_id, scope))
+            }
+            Simple::Matches(selectors) => {
+                selectors.iter().any(|complex| self.matches_complex(complex, node_id, scope))
+            }
+            Simple::Has(selectors) => self.descendants(node_id).into_iter().any(|descendant_id| {
+                selectors
+                    .iter()
+                    .any(|complex| self.matches_complex(complex, descendant_id, Some(node_id)))
+            }),
+            Simple::NthChild(n) => {
+                self.siblings(node_id).is_some_and(|(_, position)| position + 1 == *n)
+            }
+            Simple::NthLastChild(n) => self
+                .siblings(node_id)
+                .is_some_and(|(siblings, position)| siblings.len() - position == *n),
+            Simple::Class(class) => self.matches_class(*class, node_id),
+            Simple::Scope => scope == Some(node_id),
+        }
+    }
+
+    /// The classes of ESQuery, from the suffixes of the ESTree types
+    fn matches_class(&self, class: NodeClass, node_id: AstNodeId) -> bool {
+        let node_type = node_type(self.nodes.kind(node_id));
+        let is_declaration = || node_type.ends_with("Declaration");
+        let is_expression = || {
+            node_type.ends_with("Expression")
+                || node_type.ends_with("Literal")
+                || node_type == "MetaProperty"
+                || (node_type == "Identifier"
+                    && !self.parent(node_id).is_some_and(|parent_id| {
+                        matches!(self.nodes.kind(parent_id), AstKind::MetaProperty(_))
+                    }))
+        };
+        match class {
+            NodeClass::Statement => node_type.ends_with("Statement") || is_declaration(),
+            NodeClass::Declaration => is_declaration(),
+            NodeClass::Expression => is_expression(),
+            NodeClass::Pattern => node_type.ends_with("Pattern") || is_expression(),
+            NodeClass::Function => matches!(
+                node_type,
+                "FunctionDeclaration" | "FunctionExpression" | "ArrowFunctionExpression"
+            ),
+        }
+    }
+
+    /// The nearest ancestor of `node_id` which is not transparent
+    fn parent(&self, node_id: AstNodeId) -> Option<AstNodeId> {
+        let mut current = self.nodes.parent_id(node_id);
+        while let Some(parent_id) = current {
+            if !is_transparent(self.nodes.kind(parent_id)) {
+                return Some(parent_id);
+            }
+            current = self.nodes.parent_id(parent_id);
+        }
+        None
+    }
+
+    /// The nodes are in preorder, so the descendants of a node are the nodes after it, up to the
+    /// first one which is not in its subtree
+    fn subtree_end(&self, node_id: AstNodeId) -> usize {
+        let start = node_id.index();
+        (start + 1..self.len)
+            .find(|index| {
+                self.nodes
+                    .parent_id(AstNodeId::new(*index))
+                    .map_or(true, |parent_id| parent_id.index() < start)
+            })
+            .unwrap_or(self.len)
+    }
+
+    fn descendants(&self, node_id: AstNodeId) -> Vec<AstNodeId> {
+        (node_id.index() + 1..self.subtree_end(node_id))
+            .map(AstNodeId::new)
+            .filter(|descendant_id| !is_transparent(self.nodes.kind(*descendant_id)))
+            .collect()
+    }
+
+    /// The children which are not transparent, and the children of the transparent ones
+    fn children(&self, node_id: AstNodeId) -> Vec<AstNodeId> {
+        let mut children = vec![];
+        let end = self.subtree_end(node_id);
+        let mut index = node_id.index() + 1;
+        while index < end {
+            let child_id = AstNodeId::new(index);
+            if is_transparent(self.nodes.kind(child_id)) {
+                index += 1;
+            } else {
+                children.push(child_id);
+                index = self.subtree_end(child_id);
+            }
+        }
+        // The nodes are not always visited in source order, e.g. the arguments of the calls are
+        // visited before the callee
+        children.sort_by_key(|child_id| self.nodes.kind(*child_id).span().start);
+        children
+    }
+
+    /// The children of the parent of `node_id` and its position in them, `None` for the root
+    fn siblings(&self, node_id: AstNodeId) -> Option<(Vec<AstNodeId>, usize)> {
+        let siblings = self.children(self.parent(node_id)?);
+        let position = siblings.iter().position(|id| *id == node_id)?;
+        Some((siblings, position))
+    }
+
+    fn previous_siblings(&self, node_id: AstNodeId) -> Vec<AstNodeId> {
+        self.siblings(node_id).map_or_else(Vec::new, |(mut siblings, position)| {
+            siblings.truncate(position);
+            siblings
+        })
+    }
+}
+
+fn matches_attribute(attribute: &Attribute, kind: AstKind) -> bool {
+    let mut path = attribute.path.iter();
+    let Some(mut value) = path.next().and_then(|name| node::attribute(kind, name)) else {
+        return false;
+    };
+    for name in path {
+        let Some(next) = value.get(name) else { return false };
+        value = next;
+    }
+    let Some((operator, operand)) = &attribute.test else {
+        return !matches!(value, AttributeValue::Null);
+    };
+    match (operator, operand) {
+        (AttributeOperator::Equal | AttributeOperator::NotEqual, operand) => {
+            let equal = match operand {
+                AttributeOperand::Regex(regex) => {
+                    matches!(&value, AttributeValue::String(string) if regex.is_match(string))
+                }
+                AttributeOperand::String(string) | AttributeOperand::Name(string) => {
+                    value.to_text().is_some_and(|text| text == string.as_str())
+                }
+                AttributeOperand::Number(number) => {
+                    value.to_text().is_some_and(|text| text == number.to_string())
+                }
+            };
+            equal == matches!(operator, AttributeOperator::Equal)
+        }
+        (operator, AttributeOperand::Number(number)) => {
+            let AttributeValue::Number(value) = value else { return false };
+            match operator {
+                AttributeOperator::Less => value < *number,
+                AttributeOperator::LessEqual => value <= *number,
+                AttributeOperator::Greater => value > *number,
+                _ => value >= *number,
+            }
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,455 @@
+//! The types and the attributes of the nodes, in the ESTree names of ESLint
+//!
+//! <https://github.com/estree/estree>
+
+use std::borrow::Cow;
+
+use oxc_ast::{
+    ast::{
+        AssignmentTarget, BindingPatternKind, Expression, MemberExpression, ModuleDeclaration,
+        PropertyKey, RegExp, SimpleAssignmentTarget,
+    },
+    AstKind,
+};
+
+/// The value of an attribute of a node
+#[derive(Debug, Clone)]
+pub enum AttributeValue<'a> {
+    Node(AstKind<'a>),
+    Regex(&'a RegExp),
+    String(Cow<'a, str>),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+impl<'a> AttributeValue<'a> {
+    pub fn get(&self, name: &str) -> Option<Self> {
+        match self {
+            Self::Node(kind) => attribute(*kind, name),
+            Self::Regex(regex) => match name {
+                "pattern" => Some(Self::String(Cow::Borrowed(regex.pattern.as_str()))),
+                "flags" => Some(Self::String(Cow::Owned(regex.flags.to_string()))),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The text of the value in the comparisons, as in JavaScript
+    pub fn to_text(&self) -> Option<Cow<'a, str>> {
+        match self {
+            Self::String(string) => Some(string.clone()),
+            Self::Number(number) => Some(Cow::Owned(number.to_string())),
+            Self::Bool(value) => Some(Cow::Borrowed(if *value { "true" } else { "false" })),
+            Self::Null => Some(Cow::Borrowed("null")),
+            Self::Regex(regex) => Some(Cow::Owned(regex.to_string())),
+            Self::Node(_) => None,
+        }
+    }
+}
+
+/// The name of the [AstKind] variant of a node
+pub fn kind_name(kind: AstKind) -> &'static str {
+    match kind {
+        AstKind::Program(_) => "Program",
+        AstKind::Directive(_) => "Directive",
+        AstKind::Hashbang(_) => "Hashbang",
+        AstKind::BlockStatement(_) => "BlockStatement",
+        AstKind::BreakStatement(_) => "BreakStatement",
+        AstKind::ContinueStatement(_) => "ContinueStatement",
+        AstKind::DebuggerStatement(_) => "DebuggerStatement",
+        AstKind::DoWhileStatement(_) => "DoWhileStatement",
+        AstKind::EmptyStatement(_) => "EmptyStatement",
+        AstKind::ExpressionStatement(_) => "ExpressionStatement",
+        AstKind::ForInStatement(_) => "ForInStatement",
+        AstKind::ForOfStatement(_) => "ForOfStatement",
+        AstKind::ForStatement(_) => "ForStatement",
+        AstKind::ForStatementInit(_) => "ForStatementInit",
+        AstKind::IfStatement(_) => "IfStatement",
+        AstKind::LabeledStatement(_) => "LabeledStatement",
+        AstKind::ReturnStatement(_) => "ReturnStatement",
+        AstKind::SwitchStatement(_) => "SwitchStatement",
+        AstKind::ThrowStatement(_) => "ThrowStatement",
+        AstKind::TryStatement(_) => "TryStatement",
+        AstKind::WhileStatement(_) => "WhileStatement",
+        AstKind::WithStatement(_) => "WithStatement",
+        AstKind::SwitchCase(_) => "SwitchCase",
+        AstKind::CatchClause(_) => "CatchClause",
+        AstKind::FinallyClause(_) => "FinallyClause",
+        AstKind::VariableDeclaration(_) => "VariableDeclaration",
+        AstKind::VariableDeclarator(_) => "VariableDeclarator",
+        AstKind::UsingDeclaration(_) => "UsingDeclaration",
+        AstKind::IdentifierName(_) => "IdentifierName",
+        AstKind::IdentifierReference(_) => "IdentifierReference",
+        AstKind::BindingIdentifier(_) => "BindingIdentifier",
+        AstKind::LabelIdentifier(_) => "LabelIdentifier",
+        AstKind::PrivateIdentifier(_) => "PrivateIdentifier",
+        AstKind::NumberLiteral(_) => "NumberLiteral",
+        AstKind::StringLiteral(_) => "StringLiteral",
+        AstKind::BooleanLiteral(_) => "BooleanLiteral",
+        AstKind::NullLiteral(_) => "NullLiteral",
+        AstKind::BigintLiteral(_) => "BigintLiteral",
+        AstKind::RegExpLiteral(_) => "RegExpLiteral",
+        AstKind::TemplateLiteral(_) => "TemplateLiteral",
+        AstKind::MetaProperty(_) => "MetaProperty",
+        AstKind::Super(_) => "Super",
+        AstKind::ArrayExpression(_) => "ArrayExpression",
+        AstKind::ArrowExpression(_) => "ArrowExpression",
+        AstKind::AssignmentExpression(_) => "AssignmentExpression",
+        AstKind::AwaitExpression(_) => "AwaitExpression",
+        AstKind::BinaryExpression(_) => "BinaryExpression",
+        AstKind::CallExpression(_) => "CallExpression",
+        AstKind::ChainExpression(_) => "ChainExpression",
+        AstKind::ConditionalExpression(_) => "ConditionalExpression",
+        AstKind::LogicalExpression(_) => "LogicalExpression",
+        AstKind::MemberExpression(_) => "MemberExpression",
+        AstKind::NewExpression(_) => "NewExpression",
+        AstKind::ObjectExpression(_) => "ObjectExpression",
+        AstKind::ParenthesizedExpression(_) => "ParenthesizedExpression",
+        AstKind::SequenceExpression(_) => "SequenceExpression",
+        AstKind::TaggedTemplateExpression(_) => "TaggedTemplateExpression",
+        AstKind::ThisExpression(_) => "ThisExpression",
+        AstKind::UnaryExpression(_) => "UnaryExpression",
+        AstKind::UpdateExpression(_) => "UpdateExpression",
+        AstKind::YieldExpression(_) => "YieldExpression",
+        AstKind::ImportExpression(_) => "ImportExpression",
+        AstKind::PrivateInExpression(_) => "PrivateInExpression",
+        AstKind::ObjectProperty(_) => "ObjectProperty",
+        AstKind::PropertyKey(_) => "PropertyKey",
+        AstKind::Argument(_) => "Argument",
+        AstKind::AssignmentTarget(_) => "AssignmentTarget",
+        AstKind::SimpleAssignmentTarget(_) => "SimpleAssignmentTarget",
+        AstKind::AssignmentTargetWithDefault(_) => "AssignmentTargetWithDefault",
+        AstKind::ArrayExpressionElement(_) => "ArrayExpressionElement",
+        AstKind::Elision(_) => "Elision",
+        AstKind::ExpressionArrayElement(_) => "ExpressionArrayElement",
+        AstKind::SpreadElement(_) => "SpreadElement",
+        AstKind::BindingRestElement(_) => "BindingRestElement",
+        AstKind::Function(_) => "Function",
+        AstKind::FunctionBody(_) => "FunctionBody",
+        AstKind::FormalParameters(_) => "FormalParameters",
+        AstKind::FormalParameter(_) => "FormalParameter",
+        AstKind::Class(_) => "Class",
+        AstKind::ClassBody(_) => "ClassBody",
+        AstKind::ClassHeritage(_) => "ClassHeritage",
+        AstKind::StaticBlock(_) => "StaticBlock",
+        AstKind::PropertyDefinition(_) => "PropertyDefinition",
+        AstKind::MethodDefinition(_) => "MethodDefinition",
+        AstKind::ArrayPattern(_) => "ArrayPattern",
+        AstKind::ObjectPattern(_) => "ObjectPattern",
+        AstKind::AssignmentPattern(_) => "AssignmentPattern",
+        AstKind::Decorator(_) => "Decorator",
+        AstKind::ModuleDeclaration(_) => "ModuleDeclaration",
+        AstKind::JSXElement(_) => "JSXElement",
+        AstKind::JSXFragment(_) => "JSXFragment",
+        AstKind::JSXOpeningElement(_) => "JSXOpeningElement",
+        AstKind::JSXClosingElement(_) => "JSXClosingElement",
+        AstKind::JSXElementName(_) => "JSXElementName",
+        AstKind::JSXExpressionContainer(_) => "JSXExpressionContainer",
+        AstKind::JSXAttributeItem(_) => "JSXAttributeItem",
+        AstKind::JSXSpreadAttribute(_) => "JSXSpreadAttribute",
+        AstKind::JSXText(_) => "JSXText",
+        AstKind::JSXIdentifier(_) => "JSXIdentifier",
+        AstKind::JSXMemberExpression(_) => "JSXMemberExpression",
+        AstKind::JSXMemberExpressionObject(_) => "JSXMemberExpressionObject",
+        AstKind::JSXNamespacedName(_) => "JSXNamespacedName",
+        AstKind::TSModuleBlock(_) => "TSModuleBlock",
+        AstKind::TSAnyKeyword(_) => "TSAnyKeyword",
+        AstKind::TSIntersectionType(_) => "TSIntersectionType",
+        AstKind::TSLiteralType(_) => "TSLiteralType",
+        AstKind::TSMethodSignature(_) => "TSMethodSignature",
+        AstKind::TSNullKeyword(_) => "TSNullKeyword",
+        AstKind::TSTypeLiteral(_) => "TSTypeLiteral",
+        AstKind::TSTypeReference(_) => "TSTypeReference",
+        AstKind::TSUnionType(_) => "TSUnionType",
+        AstKind::TSVoidKeyword(_) => "TSVoidKeyword",
+        AstKind::TSIndexedAccessType(_) => "TSIndexedAccessType",
+        AstKind::TSAsExpression(_) => "TSAsExpression",
+        AstKind::TSSatisfiesExpression(_) => "TSSatisfiesExpression",
+        AstKind::TSNonNullExpression(_) => "TSNonNullExpression",
+        AstKind::TSInstantiationExpression(_) => "TSInstantiationExpression",
+        AstKind::TSEnumDeclaration(_) => "TSEnumDeclaration",
+        AstKind::TSEnumMember(_) => "TSEnumMember",
+        AstKind::TSEnumBody(_) => "TSEnumBody",
+        AstKind::TSImportEqualsDeclaration(_) => "TSImportEqualsDeclaration",
+        AstKind::TSTypeName(_) => "TSTypeName",
+        AstKind::TSExternalModuleReference(_) => "TSExternalModuleReference",
+        AstKind::TSQualifiedName(_) => "TSQualifiedName",
+        AstKind::TSInterfaceDeclaration(_) => "TSInterfaceDeclaration",
+        AstKind::TSModuleDeclaration(_) => "TSModuleDeclaration",
+        AstKind::TSTypeAliasDeclaration(_) => "TSTypeAliasDeclaration",
+        AstKind::TSTypeAnnotation(_) => "TSTypeAnnotation",
+        AstKind::TSTypeQuery(_) => "TSTypeQuery",
+        AstKind::TSTypeAssertion(_) => "TSTypeAssertion",
+        AstKind::TSTypeParameter(_) => "TSTypeParameter",
+        AstKind::TSTypeParameterDeclaration(_) => "TSTypeParameterDeclaration",
+        AstKind::TSTypeParameterInstantiation(_) => "TSTypeParameterInstantiation",
+        AstKind::TSPropertySignature(_) => "TSPropertySignature",
+    }
+}
+
+/// The ESTree type of a node, the nodes of the same type in ESTree have the same type, e.g. all
+/// the identifiers are `Identifier` and all the literals are `Literal`
+pub fn node_type(kind: AstKind) -> &'static str {
+    match kind {
+        AstKind::Directive(_) => "ExpressionStatement",
+        AstKind::FinallyClause(_) | AstKind::FunctionBody(_) => "BlockStatement",
+        AstKind::IdentifierName(_)
+        | AstKind::IdentifierReference(_)
+        | AstKind::BindingIdentifier(_)
+        | AstKind::LabelIdentifier(_) => "Identifier",
+        AstKind::NumberLiteral(_)
+        | AstKind::StringLiteral(_)
+        | AstKind::BooleanLiteral(_)
+        | AstKind::NullLiteral(_)
+        | AstKind::BigintLiteral(_)
+        | AstKind::RegExpLiteral(_) => "Literal",
+        AstKind::ArrowExpression(_) => "ArrowFunctionExpression",
+        AstKind::PrivateInExpression(_) => "BinaryExpression",
+        AstKind::ObjectProperty(_) => "Property",
+        AstKind::AssignmentTargetWithDefault(_) => "AssignmentPattern",
+        AstKind::BindingRestElement(_) => "RestElement",
+        AstKind::Function(function) if function.is_declaration() => "FunctionDeclaration",
+        AstKind::Function(_) => "FunctionExpression",
+        AstKind::Class(class) if class.is_declaration() => "ClassDeclaration",
+        AstKind::Class(_) => "ClassExpression",
+        AstKind::ModuleDeclaration(decl) => match decl {
+            ModuleDeclaration::ImportDeclaration(_) => "ImportDeclaration",
+            ModuleDeclaration::ExportAllDeclaration(_) => "ExportAllDeclaration",
+            ModuleDeclaration::ExportDefaultDeclaration(_) => "ExportDefaultDeclaration",
+            ModuleDeclaration::ExportNamedDeclaration(_) => "ExportNamedDeclaration",
+            ModuleDeclaration::TSExportAssignment(_) => "TSExportAssignment",
+            ModuleDeclaration::TSNamespaceExportDeclaration(_) => "TSNamespaceExportDeclaration",
+        },
+        kind => kind_name(kind),
+    }
+}
+
+/// The nodes which wrap a node in the oxc AST and are not nodes in ESTree, e.g. the arguments of
+/// the calls. They are skipped by the combinators, so that `CallExpression > Identifier` matches
+/// the identifiers passed as arguments.
+pub fn is_transparent(kind: AstKind) -> bool {
+    matches!(
+        kind,
+        AstKind::ForStatementInit(_)
+            | AstKind::PropertyKey(_)
+            | AstKind::Argument(_)
+            | AstKind::AssignmentTarget(_)
+            | AstKind::SimpleAssignmentTarget(_)
+            | AstKind::ArrayExpressionElement(_)
+            | AstKind::ExpressionArrayElement(_)
+            | AstKind::ClassHeritage(_)
+            | AstKind::FormalParameters(_)
+            | AstKind::FormalParameter(_)
+            | AstKind::ParenthesizedExpression(_)
+            | AstKind::JSXElementName(_)
+            | AstKind::JSXAttributeItem(_)
+            | AstKind::JSXMemberExpressionObject(_)
+            | AstKind::TSTypeName(_)
+    )
+}
+
+/// The attributes of the nodes which are their ESTree properties, the children are
+/// [AttributeValue::Node] for the nested attributes like `callee.name`
+#[allow(clippy::too_many_lines)]
+pub fn attribute<'a>(kind: AstKind<'a>, name: &str) -> Option<AttributeValue<'a>> {
+    use AttributeValue::{Bool, Node, Null, Number, String};
+    let str = |s: &'a str| Some(String(Cow::Borrowed(s)));
+    let expr = |e: &'a Expression<'a>| Some(Node(expression_kind(e)));
+    match (name, kind) {
+        ("type", kind) => str(node_type(kind)),
+
+        ("name", AstKind::IdentifierName(ident)) => str(ident.name.as_str()),
+        ("name", AstKind::IdentifierReference(ident)) => str(ident.name.as_str()),
+        ("name", AstKind::BindingIdentifier(ident)) => str(ident.name.as_str()),
+        ("name", AstKind::LabelIdentifier(ident)) => str(ident.name.as_str()),
+        ("name", AstKind::PrivateIdentifier(ident)) => str(ident.name.as_str()),
+        ("name", AstKind::JSXIdentifier(ident)) => str(ident.name.as_str()),
+
+        ("value", AstKind::StringLiteral(lit)) => str(lit.value.as_str()),
+        ("value", AstKind::NumberLiteral(lit)) => Some(Number(lit.value)),
+        ("value", AstKind::BooleanLiteral(lit)) => Some(Bool(lit.value)),
+        (
+            "value",
+            AstKind::NullLiteral(_) | AstKind::BigintLiteral(_) | AstKind::RegExpLiteral(_),
+        ) => Some(Null),
+        ("value", AstKind::JSXText(text)) => str(text.value.as_str()),
+        ("value", AstKind::ObjectProperty(prop)) => expr(&prop.value),
+        ("value", AstKind::PropertyDefinition(prop)) => prop.value.as_ref().and_then(expr),
+        ("value", AstKind::MethodDefinition(method)) => {
+            Some(Node(AstKind::Function(&method.value)))
+        }
+        ("regex", AstKind::RegExpLiteral(lit)) => Some(AttributeValue::Regex(&lit.regex)),
+        ("bigint", AstKind::BigintLiteral(lit)) => Some(String(Cow::Owned(lit.value.to_string()))),
+
+        ("operator", AstKind::BinaryExpression(e)) => str(e.operator.as_str()),
+        ("operator", AstKind::LogicalExpression(e)) => str(e.operator.as_str()),
+        ("operator", AstKind::UnaryExpression(e)) => str(e.operator.as_str()),
+        ("operator", AstKind::UpdateExpression(e)) => str(e.operator.as_str()),
+        ("operator", AstKind::AssignmentExpression(e)) => str(e.operator.as_str()),
+        ("operator", AstKind::PrivateInExpression(_)) => str("in"),
+        ("prefix", AstKind::UpdateExpression(e)) => Some(Bool(e.prefix)),
+        ("prefix", AstKind::UnaryExpression(_)) => Some(Bool(true)),
+
+        ("kind", AstKind::VariableDeclaration(decl)) => str(decl.kind.as_str()),
+        ("kind", AstKind::MethodDefinition(method)) => str(match method.kind {
+            oxc_ast::ast::MethodDefinitionKind::Constructor => "constructor",
+            oxc_ast::ast::MethodDefinitionKind::Method => "method",
+            oxc_ast::ast::MethodDefinitionKind::Get => "get",
+            oxc_ast::ast::MethodDefinitionKind::Set => "set",
+        }),
+        ("kind", AstKind::ObjectProperty(prop)) => str(match prop.kind {
+            oxc_ast::ast::PropertyKind::Init => "init",
+            oxc_ast::ast::PropertyKind::Get => "get",
+            oxc_ast::ast::PropertyKind::Set => "set",
+        }),
+
+        ("computed", AstKind::MemberExpression(e)) => Some(Bool(e.is_computed())),
+        ("computed", AstKind::ObjectProperty(prop)) => Some(Bool(prop.computed)),
+        ("computed", AstKind::MethodDefinition(method)) => Some(Bool(method.computed)),
+        ("computed", AstKind::PropertyDefinition(prop)) => Some(Bool(prop.computed)),
+        ("optional", AstKind::CallExpression(call)) => Some(Bool(call.optional)),
+        ("optional", AstKind::MemberExpression(e)) => Some(Bool(e.optional())),
+        ("async", AstKind::Function(function)) => Some(Bool(function.r#async)),
+        ("async", AstKind::ArrowExpression(arrow)) => Some(Bool(arrow.r#async)),
+        ("generator", AstKind::Function(function)) => Some(Bool(function.generator)),
+        ("generator", AstKind::ArrowExpression(_)) => Some(Bool(false)),
+        ("static", AstKind::MethodDefinition(method)) => Some(Bool(method.r#static)),
+        ("static", AstKind::PropertyDefinition(prop)) => Some(Bool(prop.r#static)),
+        ("delegate", AstKind::YieldExpression(e)) => Some(Bool(e.delegate)),
+        ("method", AstKind::ObjectProperty(prop)) => Some(Bool(prop.method)),
+        ("shorthand", AstKind::ObjectProperty(prop)) => Some(Bool(prop.shorthand)),
+
+        ("callee", AstKind::CallExpression(call)) => expr(&call.callee),
+        ("callee", AstKind::NewExpression(new)) => expr(&new.callee),
+        ("object", AstKind::MemberExpression(e)) => expr(e.object()),
+        ("property", AstKind::MemberExpression(e)) => Some(Node(match e {
+            MemberExpression::ComputedMemberExpression(e) => expression_kind(&e.expression),
+            MemberExpression::StaticMemberExpression(e) => AstKind::IdentifierName(&e.property),
+            MemberExpression::PrivateFieldExpression(e) => AstKind::PrivateIdentifier(&e.field),
+        })),
+        ("left", AstKind::BinaryExpression(e)) => expr(&e.left),
+        ("left", AstKind::LogicalExpression(e)) => expr(&e.left),
+        ("left", AstKind::AssignmentExpression(e)) => match &e.left {
+            AssignmentTarget::SimpleAssignmentTarget(target) => simple_target_kind(target),
+            AssignmentTarget::AssignmentTargetPattern(_) => None,
+        },
+        ("right", AstKind::BinaryExpression(e)) => expr(&e.right),
+        ("right", AstKind::LogicalExpression(e)) => expr(&e.right),
+        ("right", AstKind::AssignmentExpression(e)) => expr(&e.right),
+        ("argument", AstKind::UnaryExpression(e)) => expr(&e.argument),
+        ("argument", AstKind::UpdateExpression(e)) => simple_target_kind(&e.argument),
+        ("argument", AstKind::AwaitExpression(e)) => expr(&e.argument),
+        ("argument", AstKind::SpreadElement(e)) => expr(&e.argument),
+        ("argument", AstKind::ThrowStatement(stmt)) => expr(&stmt.argument),
+        ("argument", AstKind::ReturnStatement(stmt)) => stmt.argument.as_ref().and_then(expr),
+        ("argument", AstKind::YieldExpression(e)) => e.argument.as_ref().and_then(expr),
+        ("test", AstKind::IfStatement(stmt)) => expr(&stmt.test),
+        ("test", AstKind::WhileStatement(stmt)) => expr(&stmt.test),
+        ("test", AstKind::DoWhileStatement(stmt)) => expr(&stmt.test),
+        ("test", AstKind::ForStatement(stmt)) => stmt.test.as_ref().and_then(expr),
+        ("test", AstKind::SwitchCase(case)) => case.test.as_ref().and_then(expr),
+        ("test", AstKind::ConditionalExpression(e)) => expr(&e.test),
+        ("consequent", AstKind::ConditionalExpression(e)) => expr(&e.consequent),
+        ("alternate", AstKind::ConditionalExpression(e)) => expr(&e.alternate),
+        ("expression", AstKind::ExpressionStatement(stmt)) => expr(&stmt.expression),
+        ("expression", AstKind::ParenthesizedExpression(e)) => expr(&e.expression),
+        ("id", AstKind::VariableDeclarator(decl)) => Some(Node(match &decl.id.kind {
+            BindingPatternKind::BindingIdentifier(ident) => AstKind::BindingIdentifier(ident),
+            BindingPatternKind::ObjectPattern(pattern) => AstKind::ObjectPattern(pattern),
+            BindingPatternKind::ArrayPattern(pattern) => AstKind::ArrayPattern(pattern),
+            BindingPatternKind::AssignmentPattern(pattern) => AstKind::AssignmentPattern(pattern),
+        })),
+        ("id", AstKind::Function(function)) => {
+            function.id.as_ref().map(|ident| Node(AstKind::BindingIdentifier(ident)))
+        }
+        ("id", AstKind::Class(class)) => {
+            class.id.as_ref().map(|ident| Node(AstKind::BindingIdentifier(ident)))
+        }
+        ("init", AstKind::VariableDeclarator(decl)) => decl.init.as_ref().and_then(expr),
+        ("key", AstKind::ObjectProperty(prop)) => Some(property_key_kind(&prop.key)),
+        ("key", AstKind::MethodDefinition(method)) => Some(property_key_kind(&method.key)),
+        ("key", AstKind::PropertyDefinition(prop)) => Some(property_key_kind(&prop.key)),
+        ("tag", AstKind::TaggedTemplateExpression(e)) => expr(&e.tag),
+        ("source", AstKind::ImportExpression(e)) => expr(&e.source),
+        ("source", AstKind::ModuleDeclaration(decl)) => match decl {
+            ModuleDeclaration::ImportDeclaration(decl) => {
+                Some(Node(AstKind::StringLiteral(&decl.source)))
+            }
+            ModuleDeclaration::ExportAllDeclaration(decl) => {
+                Some(Node(AstKind::StringLiteral(&decl.source)))
+            }
+            ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                decl.source.as_ref().map(|source| Node(AstKind::StringLiteral(source)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn expression_kind<'a>(expr: &'a Expression<'a>) -> AstKind<'a> {
+    match expr {
+        Expression::Identifier(e) => AstKind::IdentifierReference(e),
+        Expression::ClassExpression(e) => AstKind::Class(e),
+        Expression::FunctionExpression(e) => AstKind::Function(e),
+        Expression::BooleanLiteral(e) => AstKind::BooleanLiteral(e),
+        Expression::NullLiteral(e) => AstKind::NullLiteral(e),
+        Expression::NumberLiteral(e) => AstKind::NumberLiteral(e),
+        Expression::BigintLiteral(e) => AstKind::BigintLiteral(e),
+        Expression::RegExpLiteral(e) => AstKind::RegExpLiteral(e),
+        Expression::StringLiteral(e) => AstKind::StringLiteral(e),
+        Expression::TemplateLiteral(e) => AstKind::TemplateLiteral(e),
+        Expression::MetaProperty(e) => AstKind::MetaProperty(e),
+        Expression::Super(e) => AstKind::Super(e),
+        Expression::ArrayExpression(e) => AstKind::ArrayExpression(e),
+        Expression::ArrowExpression(e) => AstKind::ArrowExpression(e),
+        Expression::AssignmentExpression(e) => AstKind::AssignmentExpression(e),
+        Expression::AwaitExpression(e) => AstKind::AwaitExpression(e),
+        Expression::BinaryExpression(e) => AstKind::BinaryExpression(e),
+        Expression::CallExpression(e) => AstKind::CallExpression(e),
+        Expression::ChainExpression(e) => AstKind::ChainExpression(e),
+        Expression::ConditionalExpression(e) => AstKind::ConditionalExpression(e),
+        Expression::ImportExpression(e) => AstKind::ImportExpression(e),
+        Expression::LogicalExpression(e) => AstKind::LogicalExpression(e),
+        Expression::MemberExpression(e) => AstKind::MemberExpression(e),
+        Expression::NewExpression(e) => AstKind::NewExpression(e),
+        Expression::ObjectExpression(e) => AstKind::ObjectExpression(e),
+        Expression::ParenthesizedExpression(e) => AstKind::ParenthesizedExpression(e),
+        Expression::SequenceExpression(e) => AstKind::SequenceExpression(e),
+        Expression::TaggedTemplateExpression(e) => AstKind::TaggedTemplateExpression(e),
+        Expression::ThisExpression(e) => AstKind::ThisExpression(e),
+        Expression::UnaryExpression(e) => AstKind::UnaryExpression(e),
+        Expression::UpdateExpression(e) => AstKind::UpdateExpression(e),
+        Expression::YieldExpression(e) => AstKind::YieldExpression(e),
+        Expression::PrivateInExpression(e) => AstKind::PrivateInExpression(e),
+        Expression::JSXElement(e) => AstKind::JSXElement(e),
+        Expression::JSXFragment(e) => AstKind::JSXFragment(e),
+        Expression::TSAsExpression(e) => AstKind::TSAsExpression(e),
+        Expression::TSSatisfiesExpression(e) => AstKind::TSSatisfiesExpression(e),
+        Expression::TSTypeAssertion(e) => AstKind::TSTypeAssertion(e),
+        Expression::TSNonNullExpression(e) => AstKind::TSNonNullExpression(e),
+        Expression::TSInstantiationExpression(e) => AstKind::TSInstantiationExpression(e),
+    }
+}
+
+fn simple_target_kind<'a>(target: &'a SimpleAssignmentTarget<'a>) -> Option<AttributeValue<'a>> {
+    match target {
+        SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+            Some(AttributeValue::Node(AstKind::IdentifierReference(ident)))
+        }
+        SimpleAssignmentTarget::MemberAssignmentTarget(e) => {
+            Some(AttributeValue::Node(AstKind::MemberExpression(e)))
+        }
+        _ => None,
+    }
+}
+
+fn property_key_kind<'a>(key: &'a PropertyKey<'a>) -> AttributeValue<'a> {
+    AttributeValue::Node(match key {
+        PropertyKey::Identifier(ident) => AstKind::IdentifierName(ident),
+        PropertyKey::PrivateIdentifier(ident) => AstKind::PrivateIdentifier(ident),
+        PropertyKey::Expression(e) => expression_kind(e),
+    })
+}
@@ -0,0 +1,331 @@
+//! A recursive descent parser of the selectors
+
+use regex::Regex;
+
+use super::{
+    Attribute, AttributeOperand, AttributeOperator, Combinator, Complex, Compound, NodeClass,
+    SelectorError, Simple,
+};
+
+pub struct Parser<'s> {
+    source: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    pub fn new(source: &'s str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    /// `a, b`, the whole source
+    pub fn parse(mut self) -> Result<Vec<Complex>, SelectorError> {
+        let selectors = self.parse_list(false)?;
+        self.skip_whitespace();
+        if self.pos < self.source.len() {
+            return Err(self.error("Unexpected character"));
+        }
+        Ok(selectors)
+    }
+
+    /// The selectors separated by commas, `relative` ones may start with a combinator as in
+    /// `:has(> Identifier)`
+    fn parse_list(&mut self, relative: bool) -> Result<Vec<Complex>, SelectorError> {
+        let mut selectors = vec![self.parse_complex(relative)?];
+        loop {
+            self.skip_whitespace();
+            if !self.eat(',') {
+                return Ok(selectors);
+            }
+            selectors.push(self.parse_complex(relative)?);
+        }
+    }
+
+    fn parse_complex(&mut self, relative: bool) -> Result<Complex, SelectorError> {
+        self.skip_whitespace();
+        let mut compounds = vec![];
+        let mut combinators = vec![];
+        if relative {
+            // The scope of `:has` is the node which is tested
+            compounds.push(Compound { simples: vec![Simple::Scope] });
+            combinators.push(self.parse_combinator().unwrap_or(Combinator::Descendant));
+            self.skip_whitespace();
+        }
+        compounds.push(self.parse_compound()?);
+        loop {
+            let start = self.pos;
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.parse_combinator() {
+                Some(combinator) => combinator,
+                None if had_whitespace && self.peek().is_some_and(starts_compound) => {
+                    Combinator::Descendant
+                }
+                None => {
+                    self.pos = start;
+                    return Ok(Complex { compounds, combinators });
+                }
+            };
+            combinators.push(combinator);
+            self.skip_whitespace();
+            compounds.push(self.parse_compound()?);
+        }
+    }
+
+    fn parse_combinator(&mut self) -> Option<Combinator> {
+        let combinator = match self.peek()? {
+            '>' => Combinator::Child,
+            '~' => Combinator::Sibling,
+            '+' => Combinator::Adjacent,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(combinator)
+    }
+
+    fn parse_compound(&mut self) -> Result<Compound, SelectorError> {
+        let mut simples = vec![];
+        if self.eat('*') {
+            simples.push(Simple::Wildcard);
+        } else if self.peek().is_some_and(is_identifier_start) {
+            simples.push(Simple::Type(self.parse_identifier().to_string()));
+        }
+        loop {
+            match self.peek() {
+                Some('[') => simples.push(Simple::Attribute(self.parse_attribute()?)),
+                Some(':') => simples.push(self.parse_pseudo()?),
+                _ => break,
+            }
+        }
+        if simples.is_empty() {
+            return Err(self.error("Expected a selector"));
+        }
+        Ok(Compound { simples })
+    }
+
+    /// `[name]`, `[name="foo"]`, `[callee.name=/^require$/i]`, `[value>=1]`
+    fn parse_attribute(&mut self) -> Result<Attribute, SelectorError> {
+        self.expect('[')?;
+        self.skip_whitespace();
+        let mut path = vec![self.parse_path_segment()?];
+        while self.eat('.') {
+            path.push(self.parse_path_segment()?);
+        }
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Attribute { path, test: None });
+        }
+        let operator = if self.eat_str("!=") {
+            AttributeOperator::NotEqual
+        } else if self.eat_str("<=") {
+            AttributeOperator::LessEqual
+        } else if self.eat_str(">=") {
+            AttributeOperator::GreaterEqual
+        } else if self.eat('=') {
+            AttributeOperator::Equal
+        } else if self.eat('<') {
+            AttributeOperator::Less
+        } else if self.eat('>') {
+            AttributeOperator::Greater
+        } else {
+            return Err(self.error("Expected an attribute operator"));
+        };
+        self.skip_whitespace();
+        let operand = self.parse_operand()?;
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Attribute { path, test: Some((operator, operand)) })
+    }
+
+    fn parse_path_segment(&mut self) -> Result<String, SelectorError> {
+        if !self.peek().is_some_and(is_identifier_start) {
+            return Err(self.error("Expected an attribute name"));
+        }
+        Ok(self.parse_identifier().to_string())
+    }
+
+    fn parse_operand(&mut self) -> Result<AttributeOperand, SelectorError> {
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let mut value = String::new();
+                loop {
+                    match self.next_char() {
+                        None => return Err(self.error("Unterminated string")),
+                        Some(c) if c == quote => break,
+                        Some('\\') => match self.next_char() {
+                            Some(c) => value.push(c),
+                            None => return Err(self.error("Unterminated string")),
+                        },
+                        Some(c) => value.push(c),
+                    }
+                }
+                Ok(AttributeOperand::String(value))
+            }
+            Some('/') => {
+                self.pos += 1;
+                let start = self.pos;
+                let mut escaped = false;
+                loop {
+                    match self.next_char() {
+                        None => return Err(self.error("Unterminated regular expression")),
+                        Some('\\') if !escaped => escaped = true,
+                        Some('/') if !escaped => break,
+                        Some(_) => escaped = false,
+                    }
+                }
+                let pattern = &self.source[start..self.pos - 1];
+                let flags_start = self.pos;
+                while self.peek().is_some_and(|c| matches!(c, 'i' | 'm' | 's' | 'u')) {
+                    self.pos += 1;
+                }
+                // `u` is always on in the regex crate
+                let flags = self.source[flags_start..self.pos].replace('u', "");
+                let pattern = if flags.is_empty() {
+                    pattern.to_string()
+                } else {
+                    format!("(?{flags}){pattern}")
+                };
+                let regex = Regex::new(&pattern)
+                    .map_err(|error| self.error(&format!("Invalid regular expression: {error}")))?;
+                Ok(AttributeOperand::Regex(regex))
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let start = self.pos;
+                self.pos += 1;
+                while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '.') {
+                    self.pos += 1;
+                }
+                let text = &self.source[start..self.pos];
+                text.parse::<f64>()
+                    .map(AttributeOperand::Number)
+                    .map_err(|_| self.error(&format!("Invalid number `{text}`")))
+            }
+            Some(c) if is_identifier_start(c) => {
+                Ok(AttributeOperand::Name(self.parse_identifier().to_string()))
+            }
+            _ => Err(self.error("Expected an attribute value")),
+        }
+    }
+
+    /// `:not(...)`, `:matches(...)`, `:is(...)`, `:has(...)`, `:first-child`, `:nth-child(2)`,
+    /// and the classes like `:function`
+    fn parse_pseudo(&mut self) -> Result<Simple, SelectorError> {
+        self.expect(':')?;
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '-') {
+            self.pos += 1;
+        }
+        let name = &self.source[start..self.pos];
+        let simple = match name {
+            "not" | "matches" | "is" | "has" => {
+                self.expect('(')?;
+                let selectors = self.parse_list(name == "has")?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                match name {
+                    "not" => Simple::Not(selectors),
+                    "has" => Simple::Has(selectors),
+                    _ => Simple::Matches(selectors),
+                }
+            }
+            "nth-child" | "nth-last-child" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let index = self.source[start..self.pos]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| *index > 0)
+                    .ok_or_else(|| self.error("Expected a positive integer"))?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                if name == "nth-child" {
+                    Simple::NthChild(index)
+                } else {
+                    Simple::NthLastChild(index)
+                }
+            }
+            "first-child" => Simple::NthChild(1),
+            "last-child" => Simple::NthLastChild(1),
+            "statement" => Simple::Class(NodeClass::Statement),
+            "expression" => Simple::Class(NodeClass::Expression),
+            "declaration" => Simple::Class(NodeClass::Declaration),
+            "function" => Simple::Class(NodeClass::Function),
+            "pattern" => Simple::Class(NodeClass::Pattern),
+            _ => {
+                self.pos = start;
+                return Err(self.error(&format!("Unknown pseudo-class `:{name}`")));
+            }
+        };
+        Ok(simple)
+    }
+
+    fn parse_identifier(&mut self) -> &'s str {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| is_identifier_start(c) || c.is_ascii_digit() || c == '-')
+        {
+            self.pos += self.peek().map_or(1, char::len_utf8);
+        }
+        &self.source[start..self.pos]
+    }
+
+    /// Whether some whitespace was skipped
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            return true;
+        }
+        false
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        if self.source[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SelectorError> {
+        if self.eat(c) {
+            return Ok(());
+        }
+        Err(self.error(&format!("Expected `{c}`")))
+    }
+
+    fn error(&self, message: &str) -> SelectorError {
+        SelectorError {
+            message: message.to_string(),
+            selector: self.source.to_string(),
+            offset: self.pos,
+        }
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn starts_compound(c: char) -> bool {
+    is_identifier_start(c) || matches!(c, '*' | '[' | ':')
+}
@@ -0,0 +1,442 @@
+//! Side effect analysis
+//!
+//! An expression is side-effect free when evaluating it can neither throw nor change observable
+//! state, so it can be removed if its value is unused. The analysis is conservative: anything it
+//! cannot prove pure, such as property accesses which may run getters, is treated as having side
+//! effects.
+//!
+//! Calls and `new` expressions are pure when they are annotated with `/*#__PURE__*/` or
+//! `/*@__PURE__*/`, call a known-pure global builtin, or call a local function which is never
+//! reassigned and whose body is pure.
+
+use oxc_ast::{
+    ast::{
+        Argument, ArrayExpressionElement, AssignmentTarget, BindingPatternKind, ChainElement,
+        Class, ClassElement, Declaration, Expression, FormalParameters, FunctionBody,
+        IdentifierReference, MemberExpression, ObjectPropertyKind, PropertyKey,
+        SimpleAssignmentTarget, Statement, VariableDeclaration,
+    },
+    AstKind,
+};
+use oxc_span::{GetSpan, Span};
+use oxc_syntax::{
+    operator::{BinaryOperator, UnaryOperator},
+    symbol::SymbolId,
+};
+use phf::{phf_set, Set};
+use rustc_hash::FxHashSet;
+
+use crate::{Reference, Semantic};
+
+/// Global values which can be read without side effects
+const PURE_GLOBALS: Set<&'static str> = phf_set! {
+    "undefined", "NaN", "Infinity", "globalThis",
+    "Array", "Boolean", "Date", "Error", "Map", "Math", "Number", "Object", "Promise",
+    "RegExp", "Set", "String", "Symbol", "WeakMap", "WeakSet",
+    "isFinite", "isNaN", "parseFloat", "parseInt",
+};
+
+/// Static members of globals which can be read without side effects
+const PURE_GLOBAL_MEMBERS: Set<&'static str> = phf_set! {
+    "Math.E", "Math.LN10", "Math.LN2", "Math.LOG10E", "Math.LOG2E", "Math.PI", "Math.SQRT1_2",
+    "Math.SQRT2",
+    "Number.EPSILON", "Number.MAX_SAFE_INTEGER", "Number.MAX_VALUE", "Number.MIN_SAFE_INTEGER",
+    "Number.MIN_VALUE", "Number.NaN", "Number.NEGATIVE_INFINITY", "Number.POSITIVE_INFINITY",
+    "Symbol.asyncIterator", "Symbol.iterator",
+};
+
+/// Global functions which have no side effects when called with primitive arguments
+const PURE_GLOBAL_FUNCTIONS: Set<&'static str> = phf_set! {
+    "Boolean", "Number", "String", "Symbol", "isFinite", "isNaN", "parseFloat", "parseInt",
+    "Array.isArray", "Number.isFinite", "Number.isInteger", "Number.isNaN", "Number.isSafeInteger",
+    "Number.parseFloat", "Number.parseInt", "Object.is", "String.fromCharCode",
+    "Math.abs", "Math.acos", "Math.acosh", "Math.asin", "Math.asinh", "Math.atan", "Math.atan2",
+    "Math.atanh", "Math.cbrt", "Math.ceil", "Math.clz32", "Math.cos", "Math.cosh", "Math.exp",
+    "Math.expm1", "Math.floor", "Math.fround", "Math.hypot", "Math.imul", "Math.log",
+    "Math.log10", "Math.log1p", "Math.log2", "Math.max", "Math.min", "Math.pow", "Math.round",
+    "Math.sign", "Math.sin", "Math.sinh", "Math.sqrt", "Math.tan", "Math.tanh", "Math.trunc",
+};
+
+/// Global constructors which have no side effects when called without arguments
+const PURE_GLOBAL_CONSTRUCTORS: Set<&'static str> = phf_set! {
+    "Array", "Date", "Error", "Map", "Object", "Set", "WeakMap", "WeakSet",
+};
+
+pub struct SideEffects<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    /// Span of the function whose body is being analyzed, writes to its own bindings are pure
+    function_span: Option<Span>,
+    /// Functions currently being analyzed, recursive calls are treated as side effects
+    visiting: FxHashSet<SymbolId>,
+}
+
+impl<'s, 'a> SideEffects<'s, 'a> {
+    pub fn new(semantic: &'s Semantic<'a>) -> Self {
+        Self { semantic, function_span: None, visiting: FxHashSet::default() }
+    }
+
+    /// Whether the source text right before `span` is a `/*#__PURE__*/` comment.
+    pub fn has_pure_annotation(semantic: &Semantic<'a>, span: Span) -> bool {
+        let source_text = semantic.source_text();
+        let Some((start, comment)) = semantic.trivias().comments().range(..span.start).next_back()
+        else {
+            return false;
+        };
+        if !comment.is_multi_line() {
+            return false;
+        }
+        let text = source_text[*start as usize..comment.end() as usize].trim();
+        if !matches!(text, "#__PURE__" | "@__PURE__") {
+            return false;
+        }
+        // Skip the closing `*/`, and allow the annotation before parentheses
+        let between = source_text.get(comment.end() as usize + 2..span.start as usize);
+        between.is_some_and(|text| text.chars().all(|c| c.is_whitespace() || c == '('))
+    }
+
+    pub fn is_pure_expression(&mut self, expr: &Expression<'a>) -> bool {
+        match expr {
+            Expression::BooleanLiteral(_)
+            | Expression::NullLiteral(_)
+            | Expression::NumberLiteral(_)
+            | Expression::BigintLiteral(_)
+            | Expression::RegExpLiteral(_)
+            | Expression::StringLiteral(_)
+            | Expression::ThisExpression(_)
+            | Expression::MetaProperty(_)
+            | Expression::FunctionExpression(_)
+            | Expression::ArrowExpression(_) => true,
+            Expression::TemplateLiteral(lit) => {
+                lit.expressions.iter().all(|expr| self.is_pure_expression(expr))
+            }
+            Expression::Identifier(ident) => self.is_pure_identifier(ident),
+            Expression::ArrayExpression(array) => {
+                array.elements.iter().all(|element| match element {
+                    ArrayExpressionElement::Expression(expr) => self.is_pure_expression(expr),
+                    ArrayExpressionElement::Elision(_) => true,
+                    // Spreading runs the iterator
+                    ArrayExpressionElement::SpreadElement(_) => false,
+                })
+            }
+            Expression::ObjectExpression(object) => {
+                object.properties.iter().all(|property| match property {
+                    ObjectPropertyKind::ObjectProperty(property) => {
+                        self.is_pure_property_key(&property.key)
+                            && self.is_pure_expression(&property.value)
+                    }
+                    // Spreading runs getters
+                    ObjectPropertyKind::SpreadProperty(_) => false,
+                })
+            }
+            Expression::ClassExpression(class) => self.is_pure_class(class),
+            Expression::ParenthesizedExpression(expr) => self.is_pure_expression(&expr.expression),
+            Expression::SequenceExpression(expr) => {
+                expr.expressions.iter().all(|expr| self.is_pure_expression(expr))
+            }
+            Expression::ConditionalExpression(expr) => {
+                self.is_pure_expression(&expr.test)
+                    && self.is_pure_expression(&expr.consequent)
+                    && self.is_pure_expression(&expr.alternate)
+            }
+            Expression::LogicalExpression(expr) => {
+                self.is_pure_expression(&expr.left) && self.is_pure_expression(&expr.right)
+            }
+            Expression::BinaryExpression(expr) => {
+                // `in` and `instanceof` throw when the right side is not an object
+                !matches!(expr.operator, BinaryOperator::In | BinaryOperator::Instanceof)
+                    && self.is_pure_expression(&expr.left)
+                    && self.is_pure_expression(&expr.right)
+            }
+            Expression::UnaryExpression(expr) => match expr.operator {
+                UnaryOperator::Delete => false,
+                // `typeof x` does not throw for undeclared variables
+                UnaryOperator::Typeof if matches!(expr.argument, Expression::Identifier(_)) => true,
+                _ => self.is_pure_expression(&expr.argument),
+            },
+            Expression::MemberExpression(member) => self.is_pure_member_expression(member),
+            Expression::CallExpression(call) => {
+                self.is_pure_call(call.span, call.pure, &call.callee, &call.arguments, false)
+            }
+            Expression::NewExpression(new) => {
+                self.is_pure_call(new.span, new.pure, &new.callee, &new.arguments, true)
+            }
+            Expression::ChainExpression(chain) => match &chain.expression {
+                ChainElement::CallExpression(call) => {
+                    self.is_pure_call(call.span, call.pure, &call.callee, &call.arguments, false)
+                }
+                ChainElement::MemberExpression(member) => self.is_pure_member_expression(member),
+            },
+            Expression::AssignmentExpression(expr) => {
+                let AssignmentTarget::SimpleAssignmentTarget(
+                    SimpleAssignmentTarget::AssignmentTargetIdentifier(ident),
+                ) = &expr.left
+                else {
+                    return false;
+                };
+                self.is_local_write(ident) && self.is_pure_expression(&expr.right)
+            }
+            Expression::UpdateExpression(expr) => match &expr.argument {
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                    self.is_local_write(ident)
+                }
+                _ => false,
+            },
+            Expression::TSAsExpression(expr) => self.is_pure_expression(&expr.expression),
+            Expression::TSSatisfiesExpression(expr) => self.is_pure_expression(&expr.expression),
+            Expression::TSTypeAssertion(expr) => self.is_pure_expression(&expr.expression),
+            Expression::TSNonNullExpression(expr) => self.is_pure_expression(&expr.expression),
+            Expression::TSInstantiationExpression(expr) => {
+                self.is_pure_expression(&expr.expression)
+            }
+            Expression::Super(_)
+            | Expression::AwaitExpression(_)
+            | Expression::YieldExpression(_)
+            | Expression::ImportExpression(_)
+            | Expression::TaggedTemplateExpression(_)
+            | Expression::PrivateInExpression(_)
+            | Expression::JSXElement(_)
+            | Expression::JSXFragment(_) => false,
+        }
+    }
+
+    /// Whether calling the function has no side effects, given pure arguments.
+    pub fn is_pure_function(
+        &mut self,
+        span: Span,
+        params: &FormalParameters<'a>,
+        body: Option<&FunctionBody<'a>>,
+    ) -> bool {
+        let outer_span = self.function_span.replace(span);
+        let is_pure = self.is_pure_params(params)
+            && body.map_or(true, |body| {
+                body.statements.iter().all(|stmt| self.is_pure_statement(stmt))
+            });
+        self.function_span = outer_span;
+        is_pure
+    }
+
+    fn is_pure_params(&mut self, params: &FormalParameters<'a>) -> bool {
+        let is_simple = |kind: &BindingPatternKind<'a>| {
+            matches!(kind, BindingPatternKind::BindingIdentifier(_))
+        };
+        params.items.iter().all(|param| match &param.pattern.kind {
+            BindingPatternKind::BindingIdentifier(_) => true,
+            BindingPatternKind::AssignmentPattern(pattern) => {
+                is_simple(&pattern.left.kind) && self.is_pure_expression(&pattern.right)
+            }
+            // Destructuring runs getters and iterators
+            _ => false,
+        }) && params.rest.as_ref().map_or(true, |rest| is_simple(&rest.argument.kind))
+    }
+
+    fn is_pure_statement(&mut self, stmt: &Statement<'a>) -> bool {
+        match stmt {
+            Statement::EmptyStatement(_) => true,
+            Statement::BlockStatement(block) => {
+                block.body.iter().all(|stmt| self.is_pure_statement(stmt))
+            }
+            Statement::ExpressionStatement(stmt) => self.is_pure_expression(&stmt.expression),
+            Statement::ReturnStatement(stmt) => {
+                stmt.argument.as_ref().map_or(true, |expr| self.is_pure_expression(expr))
+            }
+            Statement::IfStatement(stmt) => {
+                self.is_pure_expression(&stmt.test)
+                    && self.is_pure_statement(&stmt.consequent)
+                    && stmt.alternate.as_ref().map_or(true, |stmt| self.is_pure_statement(stmt))
+            }
+            Statement::Declaration(decl) => match decl {
+                Declaration::VariableDeclaration(decl) => self.is_pure_variable_declaration(decl),
+                Declaration::FunctionDeclaration(_)
+                | Declaration::TSTypeAliasDeclaration(_)
+                | Declaration::TSInterfaceDeclaration(_) => true,
+                Declaration::ClassDeclaration(class) => self.is_pure_class(class),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_pure_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) -> bool {
+        decl.declarations.iter().all(|declarator| {
+            matches!(declarator.id.kind, BindingPatternKind::BindingIdentifier(_))
+                && declarator.init.as_ref().map_or(true, |init| self.is_pure_expression(init))
+        })
+    }
+
+    pub fn is_pure_class(&mut self, class: &Class<'a>) -> bool {
+        if !class.decorators.is_empty() {
+            return false;
+        }
+        if class
+            .super_class
+            .as_ref()
+            .is_some_and(|super_class| !self.is_pure_expression(super_class))
+        {
+            return false;
+        }
+        class.body.body.iter().all(|element| {
+            if element.computed()
+                && element.property_key().is_some_and(|key| !self.is_pure_property_key(key))
+            {
+                return false;
+            }
+            match element {
+                ClassElement::StaticBlock(block) => {
+                    block.body.iter().all(|stmt| self.is_pure_statement(stmt))
+                }
+                ClassElement::PropertyDefinition(def) if def.r#static => {
+                    def.decorators.is_empty()
+                        && def.value.as_ref().map_or(true, |value| self.is_pure_expression(value))
+                }
+                ClassElement::AccessorProperty(def) if def.r#static => {
+                    def.value.as_ref().map_or(true, |value| self.is_pure_expression(value))
+                }
+                ClassElement::MethodDefinition(def) => def.decorators.is_empty(),
+                ClassElement::PropertyDefinition(def) => def.decorators.is_empty(),
+                _ => true,
+            }
+        })
+    }
+
+    fn is_pure_property_key(&mut self, key: &PropertyKey<'a>) -> bool {
+        match key {
+            PropertyKey::Identifier(_) | PropertyKey::PrivateIdentifier(_) => true,
+            // Computed keys are converted with `ToPropertyKey`, which only throws for objects
+            PropertyKey::Expression(expr) => {
+                self.is_pure_expression(expr) && !matches!(expr, Expression::ObjectExpression(_))
+            }
+        }
+    }
+
+    fn is_pure_identifier(&self, ident: &IdentifierReference) -> bool {
+        match self.resolve(ident) {
+            // Reading a declared variable is pure, TDZ errors are ignored
+            Some(_) => true,
+            None => PURE_GLOBALS.contains(ident.name.as_str()) || self.is_arguments(ident),
+        }
+    }
+
+    /// `arguments` inside of a function
+    fn is_arguments(&self, ident: &IdentifierReference) -> bool {
+        ident.name.as_str() == "arguments" && self.function_span.is_some()
+    }
+
+    fn is_pure_member_expression(&mut self, member: &MemberExpression<'a>) -> bool {
+        self.global_path(member).is_some_and(|path| PURE_GLOBAL_MEMBERS.contains(&path))
+    }
+
+    /// `pure` is set for calls annotated with `/* #__PURE__ */` by the parser.
+    fn is_pure_call(
+        &mut self,
+        span: Span,
+        pure: bool,
+        callee: &Expression<'a>,
+        arguments: &[Argument<'a>],
+        is_new: bool,
+    ) -> bool {
+        let arguments_are_pure = arguments.iter().all(|argument| match argument {
+            Argument::Expression(expr) => self.is_pure_expression(expr),
+            Argument::SpreadElement(_) => false,
+        });
+        if !arguments_are_pure {
+            return false;
+        }
+        if pure || Self::has_pure_annotation(self.semantic, span) {
+            return true;
+        }
+
+        let callee = callee.without_parenthesized();
+        let path = match callee {
+            Expression::Identifier(ident) if self.resolve(ident).is_none() => {
+                Some(ident.name.to_string())
+            }
+            Expression::MemberExpression(member) => self.global_path(member),
+            _ => None,
+        };
+        if let Some(path) = path {
+            return if is_new {
+                arguments.is_empty() && PURE_GLOBAL_CONSTRUCTORS.contains(&path)
+            } else {
+                PURE_GLOBAL_FUNCTIONS.contains(&path)
+            };
+        }
+
+        if is_new {
+            return false;
+        }
+        match callee {
+            Expression::FunctionExpression(func) => {
+                func.pure || self.is_pure_function(func.span, &func.params, func.body.as_deref())
+            }
+            Expression::ArrowExpression(arrow) => {
+                arrow.pure || self.is_pure_function(arrow.span, &arrow.params, Some(&*arrow.body))
+            }
+            Expression::Identifier(ident) => {
+                self.resolve(ident).is_some_and(|symbol_id| self.is_pure_local_function(symbol_id))
+            }
+            _ => false,
+        }
+    }
+
+    /// A function declared as `function f() {}` or `const f = () => {}` which is never reassigned,
+    /// and either has no side effects or is annotated with `/* #__NO_SIDE_EFFECTS__ */`.
+    fn is_pure_local_function(&mut self, symbol_id: SymbolId) -> bool {
+        if self.semantic.symbol_references(symbol_id).any(Reference::is_write) {
+            return false;
+        }
+        if !self.visiting.insert(symbol_id) {
+            return false;
+        }
+        let is_pure = match self.semantic.symbol_declaration(symbol_id).kind() {
+            AstKind::Function(func) => {
+                func.pure || self.is_pure_function(func.span, &func.params, func.body.as_deref())
+            }
+            AstKind::VariableDeclarator(decl) => match &decl.init {
+                Some(Expression::FunctionExpression(func)) => {
+                    func.pure
+                        || self.is_pure_function(func.span, &func.params, func.body.as_deref())
+                }
+                Some(Expression::ArrowExpression(arrow)) => {
+                    arrow.pure
+                        || self.is_pure_function(arrow.span, &arrow.params, Some(&*arrow.body))
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+        self.visiting.remove(&symbol_id);
+        is_pure
+    }
+
+    /// Whether assigning to `ident` only changes a binding declared in the analyzed function.
+    fn is_local_write(&self, ident: &IdentifierReference) -> bool {
+        let (Some(function_span), Some(symbol_id)) = (self.function_span, self.resolve(ident))
+        else {
+            return false;
+        };
+        let symbols = self.semantic.symbols();
+        let declaration = self.semantic.symbol_declaration(symbol_id).kind();
+        let span = symbols.get_span(symbol_id);
+        // The name of a function declaration is bound outside of the function
+        function_span.start <= span.start
+            && span.end <= function_span.end
+            && declaration.span() != function_span
+    }
+
+    /// The dotted path of a static member expression on an unshadowed global, e.g. `Math.PI`.
+    fn global_path(&self, member: &MemberExpression<'a>) -> Option<String> {
+        let MemberExpression::StaticMemberExpression(member) = member else { return None };
+        let Expression::Identifier(object) = member.object.without_parenthesized() else {
+            return None;
+        };
+        if self.resolve(object).is_some() {
+            return None;
+        }
+        Some(format!("{}.{}", object.name, member.property.name))
+    }
+
+    fn resolve(&self, ident: &IdentifierReference) -> Option<SymbolId> {
+        let reference_id = ident.reference_id.get()?;
+        self.semantic.symbols().get_reference(reference_id).symbol_id()
+    }
+}
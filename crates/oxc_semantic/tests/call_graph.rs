@@ -0,0 +1,139 @@
+mod util;
+
+use oxc_ast::{
+    ast::{BindingPatternKind, PropertyKey},
+    AstKind,
+};
+use oxc_semantic::{AstNode, AstNodeId, Semantic};
+use oxc_span::{Atom, GetSpan};
+use util::SemanticTester;
+
+fn binding_name<'a>(kind: &'a BindingPatternKind) -> Option<&'a str> {
+    match kind {
+        BindingPatternKind::BindingIdentifier(id) => Some(id.name.as_str()),
+        _ => None,
+    }
+}
+
+fn method_name(key: &PropertyKey) -> Option<Atom> {
+    key.static_name().or_else(|| key.private_name())
+}
+
+/// Find the function or method named `name`
+fn function(semantic: &Semantic, name: &str) -> AstNodeId {
+    semantic
+        .nodes()
+        .iter()
+        .find_map(|node| match node.kind() {
+            AstKind::Function(func) if func.id.as_ref().is_some_and(|id| id.name == name) => {
+                Some(node.id())
+            }
+            AstKind::VariableDeclarator(decl) if binding_name(&decl.id.kind) == Some(name) => {
+                let init = decl.init.as_ref()?.span();
+                semantic.nodes().iter().find(|node| node.kind().span() == init).map(AstNode::id)
+            }
+            AstKind::MethodDefinition(method)
+                if method_name(&method.key).as_deref() == Some(name) =>
+            {
+                let value = method.value.span;
+                semantic.nodes().iter().find(|node| node.kind().span() == value).map(AstNode::id)
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("Cannot find function {name}"))
+}
+
+#[test]
+fn test_direct_calls() {
+    let tester = SemanticTester::js(
+        "
+        function a() { b(); c(); }
+        function b() {}
+        const c = () => b();
+        function unused() { a(); }
+        a();
+        ",
+    );
+    let semantic = tester.build();
+    let graph = semantic.call_graph();
+    let id = |name| function(&semantic, name);
+    let program = semantic.nodes().iter().next().unwrap().id();
+
+    assert_eq!(graph.callees(id("a")), &[id("b"), id("c")]);
+    assert_eq!(graph.callees(id("c")), &[id("b")]);
+    assert_eq!(graph.callers(id("b")), &[id("a"), id("c")]);
+    assert_eq!(graph.callers(id("a")), &[id("unused"), program]);
+
+    let reachable = graph.reachable_from(&[program]);
+    assert!(["a", "b", "c"].into_iter().all(|name| reachable.contains(&id(name))));
+    assert!(!reachable.contains(&id("unused")));
+    assert!(graph.is_reachable(id("a"), id("b")));
+    assert!(!graph.is_reachable(id("b"), id("a")));
+}
+
+#[test]
+fn test_unresolved_calls() {
+    let tester = SemanticTester::js(
+        "
+        import { imported } from 'mod';
+        let reassigned = function () {};
+        reassigned = other;
+        function a() { imported(); reassigned(); globalFn(); obj.method(); }
+        ",
+    );
+    let semantic = tester.build();
+    assert!(semantic.call_graph().callees(function(&semantic, "a")).is_empty());
+    assert!(semantic.call_graph().call_sites().is_empty());
+}
+
+#[test]
+fn test_recursion() {
+    let tester = SemanticTester::js(
+        "
+        function direct() { direct(); }
+        function ping() { pong(); }
+        function pong() { ping(); }
+        function plain() { direct(); }
+        ",
+    );
+    let semantic = tester.build();
+    let graph = semantic.call_graph();
+    assert!(graph.is_recursive(function(&semantic, "direct")));
+    assert!(graph.is_recursive(function(&semantic, "ping")));
+    assert!(graph.is_recursive(function(&semantic, "pong")));
+    assert!(!graph.is_recursive(function(&semantic, "plain")));
+}
+
+#[test]
+fn test_method_calls() {
+    let tester = SemanticTester::js(
+        "
+        class Foo {
+            a() { this.b(); this.#c(); Foo.d(); }
+            b() {}
+            #c() {}
+            static d() { this.e(); }
+            static e() {}
+            f() { const g = () => this.b(); function h() { this.b(); } }
+        }
+        ",
+    );
+    let semantic = tester.build();
+    let graph = semantic.call_graph();
+    let id = |name| function(&semantic, name);
+    assert_eq!(graph.callees(id("a")), &[id("b"), id("c"), id("d")]);
+    assert_eq!(graph.callees(id("d")), &[id("e")]);
+    // `this` in an arrow function refers to the method's `this`, but not in a nested function
+    assert_eq!(graph.callees(id("g")), &[id("b")]);
+    assert!(graph.callees(id("h")).is_empty());
+    assert!(graph.callees(id("f")).is_empty());
+}
+
+#[test]
+fn test_iife() {
+    let tester = SemanticTester::js("(function iife() {})(); (() => {})();");
+    let semantic = tester.build();
+    let graph = semantic.call_graph();
+    assert_eq!(graph.call_sites().len(), 2);
+    assert!(graph.call_sites().iter().any(|site| site.callee == function(&semantic, "iife")));
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::SourceType;
+
+/// The messages of the semantic errors in the TypeScript `source_text`
+fn errors(source_text: &'static str) -> Vec<String> {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_typescript(true).with_module(true);
+    let parse = Parser::new(&allocator, source_text, source_type).parse();
+    assert!(parse.errors.is_empty(), "Failed to parse {source_text}");
+    let program = allocator.alloc(parse.program);
+    let ret = SemanticBuilder::new(source_text, source_type)
+        .with_check_syntax_error(true)
+        .build_module_record(PathBuf::new(), program)
+        .build(program);
+    ret.errors.into_iter().map(|error| error.to_string()).collect()
+}
+
+#[test]
+fn test_typescript_grammar_errors() {
+    let cases = [
+        ("declare declare const a: number;", "TS1030: 'declare' modifier already seen."),
+        (
+            "class A { abstract foo(): void }",
+            "TS1244: Abstract methods can only appear within an abstract class.",
+        ),
+        (
+            "class A { abstract foo: number }",
+            "TS1253: Abstract properties can only appear within an abstract class.",
+        ),
+        (
+            "function foo(a?: number, b: number) {}",
+            "TS1016: A required parameter cannot follow an optional parameter.",
+        ),
+        (
+            "declare namespace N { export = a }",
+            "TS1063: An export assignment cannot be used in a namespace.",
+        ),
+        (
+            "export const a = 1; export = a;",
+            "TS2309: An export assignment cannot be used in a module with other exported elements.",
+        ),
+    ];
+    for (source_text, message) in cases {
+        assert_eq!(errors(source_text), [message], "{source_text}");
+    }
+}
+
+#[test]
+fn test_typescript_grammar_valid() {
+    let cases = [
+        "export declare const a: number;",
+        "abstract class A { abstract foo(): void; abstract bar: number }",
+        "function foo(a?: number, b = 1, ...c: number[]) {}",
+        "import a = require('a'); export = a;",
+    ];
+    for source_text in cases {
+        assert_eq!(errors(source_text), Vec::<String>::new(), "{source_text}");
+    }
+}
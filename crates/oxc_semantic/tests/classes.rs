@@ -1,5 +1,6 @@
 mod util;
 
+use oxc_syntax::class::ElementReferenceFlag;
 use util::SemanticTester;
 
 #[test]
@@ -41,3 +42,73 @@ fn test_class_with_ts() {
     .has_accessor("ap")
     .has_accessor("pap");
 }
+
+#[test]
+fn test_class_element_references() {
+    let read = ElementReferenceFlag::Read;
+    let write = ElementReferenceFlag::Write;
+    let call = ElementReferenceFlag::Call;
+
+    SemanticTester::js(
+        "
+      class Foo {
+        #unused = 1;
+        #read = 1;
+        #written = 1;
+        #updated = 1;
+        #compound = 1;
+        #method() {}
+        public = 1;
+        computed = 1;
+        destructured = 1;
+        other() {}
+        static #checked = 1;
+
+        constructor() {
+          console.log(this.#read, (this.public));
+          this.#written = 1;
+          this.#updated++;
+          this.#compound += 1;
+          (this.#method)();
+          this['computed'] = 2;
+          [this.destructured] = [1];
+          const arrow = () => this.other();
+          function nested() { return this.unused; }
+          #checked in this;
+        }
+      }
+    ",
+    )
+    .has_class("Foo")
+    .has_element_reference_flag("unused", ElementReferenceFlag::empty())
+    .has_element_reference_flag("read", read)
+    .has_element_reference_flag("written", write)
+    .has_element_reference_flag("updated", read | write)
+    .has_element_reference_flag("compound", read | write)
+    .has_element_reference_flag("method", read | call)
+    .has_element_reference_flag("public", read)
+    .has_element_reference_flag("computed", write)
+    .has_element_reference_flag("destructured", write)
+    .has_element_reference_flag("other", read | call)
+    .has_element_reference_flag("checked", read);
+}
+
+#[test]
+fn test_class_element_references_in_initializers() {
+    SemanticTester::js(
+        "
+      class Foo {
+        a = 1;
+        b = this.a;
+        static { this.c(); }
+        static c() {}
+        [this.d] = 1;
+        d = 1;
+      }
+    ",
+    )
+    .has_class("Foo")
+    .has_element_reference_flag("a", ElementReferenceFlag::Read)
+    .has_element_reference_flag("c", ElementReferenceFlag::Read | ElementReferenceFlag::Call)
+    .has_element_reference_flag("d", ElementReferenceFlag::empty());
+}
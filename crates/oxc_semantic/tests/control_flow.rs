@@ -0,0 +1,133 @@
+mod util;
+
+use oxc_ast::{ast::Expression, AstKind};
+use oxc_semantic::Semantic;
+use util::SemanticTester;
+
+/// Assert that every call to `reachable()` is reachable and every call to `unreachable()` is not.
+fn test_reachability(source_text: &'static str) {
+    let tester = SemanticTester::js(source_text);
+    let semantic = tester.build();
+    let mut markers = 0;
+    for node in semantic.nodes().iter() {
+        let AstKind::CallExpression(call) = node.kind() else { continue };
+        let Expression::Identifier(ident) = &call.callee else { continue };
+        let expected = match ident.name.as_str() {
+            "reachable" => true,
+            "unreachable" => false,
+            _ => continue,
+        };
+        markers += 1;
+        assert_eq!(
+            semantic.cfg().is_reachable(node.id()),
+            expected,
+            "expected `{}()` to be {}reachable in:\n{source_text}",
+            ident.name,
+            if expected { "" } else { "un" }
+        );
+    }
+    assert!(markers > 0, "no markers found in:\n{source_text}");
+}
+
+fn function_end_reachable(semantic: &Semantic, name: &str) -> bool {
+    let node = semantic
+        .nodes()
+        .iter()
+        .find(|node| {
+            matches!(node.kind(), AstKind::Function(func) if func.id.as_ref().is_some_and(|id| id.name == name))
+        })
+        .unwrap_or_else(|| panic!("Cannot find function {name}"));
+    semantic.cfg().is_function_end_reachable(node.id())
+}
+
+#[test]
+fn test_abrupt_completions() {
+    test_reachability("function foo() { reachable(); return; unreachable(); }");
+    test_reachability("function foo() { throw new Error(); unreachable(); }");
+    test_reachability("while (x) { break; unreachable(); } reachable();");
+    test_reachability("while (x) { continue; unreachable(); } reachable();");
+    test_reachability("function foo() { return; function bar() { reachable(); } }");
+}
+
+#[test]
+fn test_branches() {
+    test_reachability("if (x) { throw x; } else { reachable(); } reachable();");
+    test_reachability("if (x) { throw x; } else { throw y; } unreachable();");
+    test_reachability("if (x) throw x; reachable();");
+    test_reachability("x ? reachable() : reachable(); x && reachable(); x ?? reachable();");
+    test_reachability(
+        "switch (x) { case 1: reachable(); break; case 2: reachable(); } reachable();",
+    );
+    test_reachability("switch (x) { case 1: throw x; default: throw y; } unreachable();");
+    test_reachability("switch (x) { case 1: throw x; case 2: throw y; } reachable();");
+}
+
+#[test]
+fn test_loops() {
+    test_reachability("while (true) {} unreachable();");
+    test_reachability("while (true) { if (x) break; } reachable();");
+    test_reachability("for (;;) {} unreachable();");
+    test_reachability("for (;;) { break; } reachable();");
+    test_reachability("for (let i = 0; i < 10; i++) {} reachable();");
+    test_reachability("do { reachable(); } while (true); unreachable();");
+    test_reachability("for (const x of y) { reachable(); } reachable();");
+    test_reachability("for (const x in y) { continue; } reachable();");
+    test_reachability("for (;; reachable()) { if (x) continue; break; }");
+    test_reachability("for (;; unreachable()) { break; }");
+}
+
+#[test]
+fn test_for_statement_source_order() {
+    let tester = SemanticTester::js("for (let i = 0; i < 10; i++) { body; }");
+    let semantic = tester.build();
+    let names = semantic
+        .nodes()
+        .iter()
+        .filter_map(|node| match node.kind() {
+            AstKind::IdentifierReference(ident) => Some(ident.name.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["i", "body", "i"]);
+}
+
+#[test]
+fn test_labels() {
+    test_reachability(
+        "outer: while (true) { while (true) { break outer; } unreachable(); } reachable();",
+    );
+    test_reachability("outer: for (;;) { for (;;) { continue outer; } unreachable(); }");
+    test_reachability("block: { break block; unreachable(); } reachable();");
+}
+
+#[test]
+fn test_try_catch_finally() {
+    test_reachability("try { foo(); } catch { reachable(); } reachable();");
+    test_reachability("try { throw x; } catch { reachable(); } reachable();");
+    test_reachability("try { throw x; unreachable(); } finally { reachable(); } unreachable();");
+    test_reachability("function foo() { try { return; } finally { reachable(); } unreachable(); }");
+    test_reachability(
+        "function foo() { try { return; } catch { reachable(); } finally { reachable(); } reachable(); }",
+    );
+    test_reachability("for (;;) { try { break; } finally { reachable(); } } reachable();");
+    test_reachability("function foo() { try { return; } finally { return; } unreachable(); }");
+}
+
+#[test]
+fn test_function_end() {
+    let tester = SemanticTester::js(
+        "
+        function a() { return 1; }
+        function b() { if (x) return 1; }
+        function c() { if (x) { return 1; } else { throw 2; } }
+        function d() { while (true) {} }
+        function e() {}
+        ",
+    );
+    let semantic = tester.build();
+    assert!(!function_end_reachable(&semantic, "a"));
+    assert!(function_end_reachable(&semantic, "b"));
+    assert!(!function_end_reachable(&semantic, "c"));
+    assert!(!function_end_reachable(&semantic, "d"));
+    assert!(function_end_reachable(&semantic, "e"));
+}
@@ -0,0 +1,92 @@
+mod util;
+
+use oxc_semantic::{DefUse, Liveness, ReachingDefinitions, Semantic, SymbolId};
+use util::SemanticTester;
+
+/// The symbol named `a` declared inside a function
+fn symbol_a(semantic: &Semantic) -> SymbolId {
+    semantic
+        .symbols()
+        .iter()
+        .find(|symbol_id| {
+            semantic.symbols().get_name(*symbol_id).as_str() == "a"
+                && semantic.symbols().get_scope_id(*symbol_id) != semantic.scopes().root_scope_id()
+        })
+        .expect("No symbol `a`")
+}
+
+/// Whether each definition of `a`, in source order, may be read later
+fn live_definitions(source_text: &'static str) -> Vec<bool> {
+    let tester = SemanticTester::js(source_text);
+    let semantic = tester.build();
+    let def_use = DefUse::new(&semantic);
+    let liveness = Liveness::new(&semantic, &def_use);
+    let mut definitions = def_use.symbol_definitions(symbol_a(&semantic)).to_vec();
+    definitions.sort_by_key(|id| def_use.get_definition(*id).span.start);
+    definitions.into_iter().map(|id| liveness.is_live(id)).collect()
+}
+
+/// The start offsets of the definitions of `a` reaching each read of `a`, in source order
+fn reaching_definitions(source_text: &'static str) -> Vec<Vec<u32>> {
+    let tester = SemanticTester::js(source_text);
+    let semantic = tester.build();
+    let def_use = DefUse::new(&semantic);
+    let reaching = ReachingDefinitions::new(&semantic, &def_use);
+    let symbols = semantic.symbols();
+    let mut reads: Vec<_> = symbols
+        .get_resolved_reference_ids(symbol_a(&semantic))
+        .iter()
+        .copied()
+        .filter(|id| symbols.get_reference(*id).is_read())
+        .collect();
+    reads.sort_by_key(|id| symbols.get_reference(*id).span().start);
+    reads
+        .into_iter()
+        .map(|id| {
+            let mut starts: Vec<u32> = reaching
+                .reaching(id)
+                .into_iter()
+                .map(|def| def_use.get_definition(def).span.start)
+                .collect();
+            starts.sort_unstable();
+            starts
+        })
+        .collect()
+}
+
+#[test]
+fn test_dead_stores() {
+    assert_eq!(live_definitions("function f() { let a = 1; a = 2; g(a) }"), [false, true]);
+    assert_eq!(live_definitions("function f() { let a = 1; a = a + 1; }"), [true, false]);
+    assert_eq!(live_definitions("function f(c) { let a = 1; if (c) a = 2; g(a) }"), [true, true]);
+    assert_eq!(live_definitions("function f() { let a = 1; while (g(a)) a = 2; }"), [true, true]);
+    assert_eq!(live_definitions("function f() { let a = 1; a ||= 2; g(a) }"), [true, true]);
+    // Captured by a closure
+    assert_eq!(live_definitions("function f() { let a = 1; return () => a; }"), [true]);
+    // Read in the catch clause after a throwing call
+    assert_eq!(
+        live_definitions("function f() { let a = 1; try { a = 2; g(); a = 3 } catch { g(a) } }"),
+        [true, true, true]
+    );
+}
+
+#[test]
+fn test_reaching_definitions() {
+    // The offsets of `a` in `let a` and the assignments
+    assert_eq!(
+        reaching_definitions("function f() { let a = 1; g(a); a = 2; g(a) }"),
+        [vec![19], vec![32]]
+    );
+    assert_eq!(
+        reaching_definitions("function f(c) { let a; if (c) { a = 1 } else { a = 2 } g(a) }"),
+        [vec![32, 47]]
+    );
+    assert_eq!(
+        reaching_definitions("function f() { let a = 0; for (;;) { g(a); a = 1 } }"),
+        [vec![19, 43]]
+    );
+    assert_eq!(
+        reaching_definitions("function f() { let a = 0; a ??= 1; g(a) }"),
+        [vec![19], vec![19, 26]]
+    );
+}
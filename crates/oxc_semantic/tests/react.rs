@@ -0,0 +1,84 @@
+mod util;
+
+use oxc_ast::AstKind;
+use oxc_semantic::{
+    react::{
+        get_function_like_name, is_class_component, is_function_component, is_hook_call,
+        is_hook_function, is_hook_name,
+    },
+    AstNode, AstNodes,
+};
+use util::SemanticTester;
+
+/// The names of the functions in `source_text` matching `predicate`
+fn functions(
+    source_text: &'static str,
+    predicate: for<'a> fn(&AstNode<'a>, &AstNodes<'a>) -> bool,
+) -> Vec<String> {
+    let tester = SemanticTester::js(source_text).with_jsx(true);
+    let semantic = tester.build();
+    semantic
+        .nodes()
+        .iter()
+        .filter(|node| predicate(node, semantic.nodes()))
+        .map(|node| {
+            get_function_like_name(node, semantic.nodes())
+                .map_or_else(|| "<anonymous>".to_string(), ToString::to_string)
+        })
+        .collect()
+}
+
+#[test]
+fn test_hook_names() {
+    assert!(is_hook_name("use"));
+    assert!(is_hook_name("useState"));
+    assert!(is_hook_name("use2D"));
+    assert!(!is_hook_name("user"));
+    assert!(!is_hook_name("Use"));
+
+    let tester = SemanticTester::js("useState(); React.useEffect(); user(); obj.useFoo();");
+    let semantic = tester.build();
+    let hook_calls = semantic
+        .nodes()
+        .iter()
+        .filter(|node| matches!(node.kind(), AstKind::CallExpression(call) if is_hook_call(call)))
+        .count();
+    assert_eq!(hook_calls, 2);
+}
+
+#[test]
+fn test_function_components() {
+    let source_text = "
+        function App() { return <div />; }
+        const Arrow = () => <></>;
+        const Conditional = (props) => props.a ? <div /> : null;
+        const Memo = React.memo(function () { return <div />; });
+        const ForwardRef = memo(forwardRef((props, ref) => <div ref={ref} />));
+        export default memo(() => <div />);
+        function Nested() { return [1].map(() => <div />); }
+        function lowercase() { return <div />; }
+        function NoJsx() { return null; }
+        class Foo extends React.Component { render() { return <div />; } }
+    ";
+    assert_eq!(
+        functions(source_text, is_function_component),
+        vec!["App", "Arrow", "Conditional", "Memo", "ForwardRef", "<anonymous>"]
+    );
+}
+
+#[test]
+fn test_hooks_and_class_components() {
+    let source_text = "
+        function useCounter() { return useState(0); }
+        const useToggle = () => {};
+        function user() {}
+    ";
+    assert_eq!(functions(source_text, is_hook_function), vec!["useCounter", "useToggle"]);
+
+    let tester = SemanticTester::js(
+        "class A extends React.Component {} class B extends PureComponent {} class C {}
+         createReactClass({});",
+    );
+    let semantic = tester.build();
+    assert_eq!(semantic.nodes().iter().filter(|node| is_class_component(node)).count(), 3);
+}
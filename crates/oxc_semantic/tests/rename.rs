@@ -0,0 +1,71 @@
+mod util;
+
+use oxc_semantic::RenameError;
+use oxc_span::Atom;
+use util::SemanticTester;
+
+/// Rename the first symbol declared as `name` and apply the edits to the source text.
+fn rename(source_text: &'static str, name: &str, new_name: &str) -> Result<String, RenameError> {
+    let tester = SemanticTester::js(source_text);
+    let semantic = tester.build();
+    let symbol_id = semantic
+        .symbols()
+        .get_symbol_id_from_name(&Atom::from(name))
+        .unwrap_or_else(|| panic!("Cannot find symbol {name}"));
+    let edits = semantic.rename_symbol(symbol_id, new_name)?;
+
+    let mut output = source_text.to_string();
+    for edit in edits.iter().rev() {
+        output.replace_range(edit.span.start as usize..edit.span.end as usize, &edit.replacement);
+    }
+    Ok(output)
+}
+
+#[test]
+fn test_rename_declaration_and_references() {
+    assert_eq!(rename("let a = 1; a++; f(a);", "a", "b").unwrap(), "let b = 1; b++; f(b);");
+    assert_eq!(
+        rename("function foo(x) { return x * 2; } foo(1);", "x", "y").unwrap(),
+        "function foo(y) { return y * 2; } foo(1);"
+    );
+    assert_eq!(rename("var a; var a; a;", "a", "b").unwrap(), "var b; var b; b;");
+    assert_eq!(rename("let a = 1;", "a", "a").unwrap(), "let a = 1;");
+}
+
+#[test]
+fn test_rename_shorthand() {
+    assert_eq!(rename("let a = 1; f({ a });", "a", "b").unwrap(), "let b = 1; f({ a: b });");
+    assert_eq!(rename("let a; ({ a } = obj);", "a", "b").unwrap(), "let b; ({ a: b } = obj);");
+    assert_eq!(
+        rename("const { a, c: [d] } = obj; a;", "a", "b").unwrap(),
+        "const { a: b, c: [d] } = obj; b;"
+    );
+    assert_eq!(rename("const { a = 1 } = obj;", "a", "b").unwrap(), "const { a: b = 1 } = obj;");
+    assert_eq!(
+        rename("import { a } from 'mod'; a();", "a", "b").unwrap(),
+        "import { a as b } from 'mod'; b();"
+    );
+    assert_eq!(
+        rename("let a; export { a, a as c };", "a", "b").unwrap(),
+        "let b; export { b as a, b as c };"
+    );
+}
+
+#[test]
+fn test_rename_conflicts() {
+    assert!(matches!(rename("let a, b;", "a", "b"), Err(RenameError::Redeclaration(..))));
+    assert!(matches!(
+        rename("let a; function f() { let b; a; }", "a", "b"),
+        Err(RenameError::Shadowed(..))
+    ));
+    assert!(matches!(
+        rename("let b; function f() { let a; b; }", "a", "b"),
+        Err(RenameError::Shadowed(..))
+    ));
+    assert!(rename("function f() { let a; } b;", "a", "b").is_ok());
+    assert!(matches!(
+        rename("function f() { let a; console; }", "a", "console"),
+        Err(RenameError::Shadowed(..))
+    ));
+    assert!(matches!(rename("let a;", "a", "1a"), Err(RenameError::InvalidName(_))));
+}
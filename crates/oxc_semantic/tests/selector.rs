@@ -0,0 +1,98 @@
+mod util;
+
+use oxc_semantic::selector::Selector;
+use oxc_span::GetSpan;
+use util::SemanticTester;
+
+/// The source texts of the nodes of `source_text` matching `selector`
+fn query(source_text: &'static str, selector: &str) -> Vec<&'static str> {
+    let selector = Selector::parse(selector).unwrap();
+    let tester = SemanticTester::js(source_text).with_jsx(true);
+    let semantic = tester.build();
+    selector
+        .query(semantic.nodes())
+        .map(|node| node.kind().span().source_text(source_text))
+        .collect()
+}
+
+#[test]
+fn test_types_and_attributes() {
+    let source_text = "const a = require('a'); foo(require); b.require('c');";
+    assert_eq!(
+        query(source_text, "CallExpression > Identifier[name=\"require\"]"),
+        ["require", "require"]
+    );
+    assert_eq!(query(source_text, "CallExpression[callee.name='require']"), ["require('a')"]);
+    assert_eq!(query(source_text, "[callee.property.name=require]"), ["b.require('c')"]);
+    assert_eq!(query(source_text, "Literal[value=/^[ac]$/]"), ["'a'", "'c'"]);
+    assert_eq!(query(source_text, "VariableDeclaration[kind!=let]"), ["const a = require('a');"]);
+    assert_eq!(query("let a = 1, b = 10;", "Literal[value>=2]"), ["10"]);
+    assert_eq!(query("a === b; a + b;", "BinaryExpression[operator='==='] > *"), ["a", "b"]);
+    assert_eq!(query("/a/g; /b/;", "Literal[regex.flags=g]"), ["/a/g"]);
+    assert_eq!(query("foo?.(); foo();", "CallExpression[optional=true]"), ["foo?.()"]);
+    assert_eq!(query("x; 1;", "Identifier, Literal"), ["x", "1"]);
+    assert_eq!(query("if (a) {}", "identifier"), ["a"]);
+    assert_eq!(query("a.b", "IdentifierReference"), ["a"]);
+}
+
+#[test]
+fn test_combinators() {
+    let source_text = "function f() { a; if (b) { c; } } d;";
+    assert_eq!(query(source_text, "FunctionDeclaration Identifier"), ["f", "a", "b", "c"]);
+    assert_eq!(query(source_text, "IfStatement > BlockStatement ExpressionStatement"), ["c;"]);
+    assert_eq!(query(source_text, "ExpressionStatement + IfStatement > Identifier"), ["b"]);
+    assert_eq!(query("a; b; c;", "ExpressionStatement ~ ExpressionStatement"), ["b;", "c;"]);
+    assert_eq!(query("a; b; c;", "ExpressionStatement + ExpressionStatement"), ["b;", "c;"]);
+    // The arguments are children of the calls
+    assert_eq!(query("f(a, 'b')", "CallExpression > Literal"), ["'b'"]);
+    assert_eq!(query("f(a, b)", "CallExpression > :first-child"), ["f"]);
+}
+
+#[test]
+fn test_pseudo_classes() {
+    let source_text = "function f() { return 1; } function g() { h(); } const k = () => {};";
+    assert_eq!(query(source_text, "FunctionDeclaration:has(ReturnStatement) > Identifier"), ["f"]);
+    assert_eq!(
+        query(source_text, "FunctionDeclaration:not(:has(ReturnStatement)) > Identifier"),
+        ["g"]
+    );
+    assert_eq!(
+        query(source_text, ":function"),
+        ["function f() { return 1; }", "function g() { h(); }", "() => {}"]
+    );
+    assert_eq!(query("a(b(c));", "CallExpression:has(> CallExpression)"), ["a(b(c))"]);
+    assert_eq!(query("a(b(c));", "CallExpression:has(CallExpression)"), ["a(b(c))"]);
+    assert_eq!(query("x; 'y';", ":matches(Identifier, Literal)"), ["x", "'y'"]);
+    assert_eq!(query("a; b; c;", "ExpressionStatement:first-child"), ["a;"]);
+    assert_eq!(query("a; b; c;", "ExpressionStatement:last-child"), ["c;"]);
+    assert_eq!(query("a; b; c;", "ExpressionStatement:nth-child(2)"), ["b;"]);
+    assert_eq!(query("a; b; c;", "ExpressionStatement:nth-last-child(3)"), ["a;"]);
+    assert_eq!(query("let a = b;", ":declaration"), ["let a = b;"]);
+    assert_eq!(query("if (x) y;", ":statement"), ["if (x) y;", "y;"]);
+}
+
+#[test]
+fn test_matches() {
+    let selector: Selector = "ThrowStatement > Literal".parse().unwrap();
+    let tester = SemanticTester::js("throw 'a'; throw new Error('b');");
+    let semantic = tester.build();
+    let nodes = semantic.nodes();
+    let matched = nodes.iter().filter(|node| selector.matches(node, nodes)).count();
+    assert_eq!(matched, 1);
+    assert_eq!(selector.as_str(), "ThrowStatement > Literal");
+}
+
+#[test]
+fn test_errors() {
+    for selector in
+        ["", "Identifier >", "[name=", "[name='a]", ":unknown", ":nth-child(0)", "[a=/(/]", "a)"]
+    {
+        assert!(Selector::parse(selector).is_err(), "{selector}");
+    }
+    let error = Selector::parse("Identifier:foo").unwrap_err();
+    assert_eq!(error.offset, 11);
+    assert_eq!(
+        error.to_string(),
+        "Invalid selector `Identifier:foo`: Unknown pseudo-class `:foo` at offset 11"
+    );
+}
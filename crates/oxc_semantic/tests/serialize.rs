@@ -0,0 +1,27 @@
+#![cfg(feature = "serde")]
+
+mod util;
+
+use serde_json::json;
+use util::SemanticTester;
+
+#[test]
+fn test_serialize_scopes_and_symbols() {
+    let tester = SemanticTester::js("let a = 1; function f(b) { return a + b; }");
+    let semantic = tester.build();
+
+    let scopes = serde_json::to_value(semantic.scopes()).unwrap();
+    assert_eq!(scopes["parentIds"], json!([null, 0]));
+    assert_eq!(scopes["childIds"], json!({ "0": [1] }));
+    assert_eq!(scopes["bindings"], json!([{ "a": 0, "f": 1 }, { "b": 2 }]));
+    assert_eq!(scopes["flags"], json!(["StrictMode | Top", "StrictMode | Function"]));
+
+    let symbols = serde_json::to_value(semantic.symbols()).unwrap();
+    assert_eq!(symbols["names"], json!(["a", "f", "b"]));
+    assert_eq!(symbols["scopeIds"], json!([0, 0, 1]));
+    assert_eq!(symbols["spans"][0], json!({ "start": 4, "end": 5 }));
+    assert_eq!(symbols["resolvedReferences"], json!([[0], [], [1]]));
+    assert_eq!(symbols["references"][0]["name"], json!("a"));
+    assert_eq!(symbols["references"][0]["symbolId"], json!(0));
+    assert_eq!(symbols["references"][0]["flag"], json!("Read"));
+}
@@ -0,0 +1,115 @@
+mod util;
+
+use oxc_ast::AstKind;
+use oxc_semantic::AstNode;
+use util::SemanticTester;
+
+/// Whether the expression of the last top level expression statement in `source_text` is
+/// side-effect free
+fn is_side_effect_free(source_text: &'static str) -> bool {
+    let tester = SemanticTester::js(source_text);
+    let semantic = tester.build();
+    let stmt = semantic
+        .nodes()
+        .iter()
+        .filter(|node| matches!(semantic.nodes().parent_kind(node.id()), Some(AstKind::Program(_))))
+        .filter_map(|node| match node.kind() {
+            AstKind::ExpressionStatement(stmt) => Some(stmt),
+            _ => None,
+        })
+        .last()
+        .unwrap_or_else(|| panic!("No expression statement in {source_text}"));
+    semantic.is_side_effect_free(&stmt.expression)
+}
+
+#[test]
+fn test_pure_expressions() {
+    let pure = [
+        "1 + 2 * 'a'",
+        "`a${1}`",
+        "[1, , 'a'], { a: 1, [1 + 1]: 2, b() {} }",
+        "let a; a, typeof b",
+        "() => foo(), function () { foo() }",
+        "undefined, NaN, Math",
+        "Math.PI",
+        "Math.max(1, 2), String(1)",
+        "new Map()",
+        "(class { static a = 1; b = foo(); })",
+        "!0 ? void 0 : -1",
+    ];
+    for source_text in pure {
+        assert!(is_side_effect_free(source_text), "{source_text} should be pure");
+    }
+
+    let impure = [
+        "foo()",
+        "a.b",
+        "a = 1",
+        "let a; a = 1",
+        "unknownGlobal",
+        "[...a]",
+        "({ ...a })",
+        "delete a.b",
+        "'a' in b",
+        "new Map(a)",
+        "let Math = {}; Math.max(1)",
+        "(class { static a = foo(); })",
+        "(class extends foo() {})",
+        "foo`a`",
+    ];
+    for source_text in impure {
+        assert!(!is_side_effect_free(source_text), "{source_text} should have side effects");
+    }
+}
+
+#[test]
+fn test_pure_annotations() {
+    assert!(is_side_effect_free("/*#__PURE__*/ foo()"));
+    assert!(is_side_effect_free("/* @__PURE__ */ new Foo(1)"));
+    assert!(is_side_effect_free("/*#__PURE__*/ (foo())"));
+    assert!(!is_side_effect_free("/*#__PURE__*/ foo(bar())"));
+    assert!(!is_side_effect_free("// #__PURE__\nfoo()"));
+    assert!(!is_side_effect_free("/* PURE */ foo()"));
+    assert!(is_side_effect_free("/*#__PURE__*/ foo()()"));
+    assert!(is_side_effect_free("/*#__PURE__*/ foo?.()"));
+}
+
+#[test]
+fn test_no_side_effects_annotations() {
+    assert!(is_side_effect_free("/*#__NO_SIDE_EFFECTS__*/ function f() { foo(); } f()"));
+    assert!(is_side_effect_free("/* @__NO_SIDE_EFFECTS__ */ const f = () => foo(); f()"));
+    assert!(is_side_effect_free("const f = /*#__NO_SIDE_EFFECTS__*/ async () => foo(); f()"));
+    assert!(is_side_effect_free("(/*#__NO_SIDE_EFFECTS__*/ function () { foo(); })()"));
+    assert!(!is_side_effect_free("/*#__NO_SIDE_EFFECTS__*/ function f() { foo(); } f(bar())"));
+    assert!(!is_side_effect_free("/*#__NO_SIDE_EFFECTS__*/ let f = () => foo(); f = g; f()"));
+    assert!(!is_side_effect_free("function f() { foo(); } /*#__NO_SIDE_EFFECTS__*/ f()"));
+}
+
+#[test]
+fn test_pure_functions() {
+    assert!(is_side_effect_free("function f(a, b = 1) { let c = a + b; c++; return c; } f(1)"));
+    assert!(is_side_effect_free("const f = () => 1; f()"));
+    assert!(is_side_effect_free("(function () { return 1; })()"));
+    assert!(!is_side_effect_free("function f() { foo(); } f()"));
+    assert!(!is_side_effect_free("let a; function f() { a = 1; } f()"));
+    assert!(!is_side_effect_free("function f() { f = 1; } f()"));
+    assert!(!is_side_effect_free("function f() { return f(); } f()"));
+    assert!(!is_side_effect_free("let f = () => 1; f = foo; f()"));
+    assert!(!is_side_effect_free("function f({ a }) {} f(1)"));
+
+    let tester = SemanticTester::js("function pure() { return 1; } function impure() { foo(); }");
+    let semantic = tester.build();
+    let function = |name: &str| {
+        semantic
+            .nodes()
+            .iter()
+            .find(|node| {
+                matches!(node.kind(), AstKind::Function(func)
+                    if func.id.as_ref().is_some_and(|id| id.name == name))
+            })
+            .map(AstNode::id)
+            .unwrap()
+    };
+    assert!(semantic.is_pure_function(function("pure")));
+    assert!(!semantic.is_pure_function(function("impure")));
+}
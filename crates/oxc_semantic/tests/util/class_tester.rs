@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use oxc_ast::AstKind;
 use oxc_semantic::Semantic;
-use oxc_syntax::class::ClassId;
+use oxc_syntax::class::{ClassId, ElementReferenceFlag};
 
 pub struct ClassTester<'a> {
     /// Reference to semantic analysis results, from [`SemanticTester`]
@@ -57,4 +57,19 @@ impl<'a> ClassTester<'a> {
         debug_assert!(method.is_some(), "Expected accessor `{name}` not found");
         self
     }
+
+    /// Assert how all references to the element `name` (private or not) access it
+    pub fn has_element_reference_flag(&self, name: &str, flag: ElementReferenceFlag) -> &Self {
+        let classes = self.semantic.classes();
+        let element_id = classes.elements[self.class_id]
+            .iter_enumerated()
+            .find_map(|(element_id, element)| (element.name == name).then_some(element_id))
+            .unwrap_or_else(|| panic!("Expected element `{name}` not found"));
+        let actual = classes.get_element_reference_flag(self.class_id, element_id);
+        debug_assert!(
+            actual == flag,
+            "Expected `{name}` to be referenced as {flag:?}, found {actual:?}"
+        );
+        self
+    }
 }
@@ -0,0 +1,206 @@
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use serde_json::Value;
+
+use crate::{SourceMap, Token};
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum SourceMapError {
+    #[error("The source map is not valid JSON: {0}")]
+    #[diagnostic()]
+    InvalidJson(String),
+
+    #[error("The source map version {0} is not supported, only the version 3 is supported")]
+    #[diagnostic()]
+    UnsupportedVersion(String),
+
+    #[error("The source map does not have the `{0}` field")]
+    #[diagnostic()]
+    MissingField(&'static str),
+
+    #[error("The index source maps with `sections` are not supported")]
+    #[diagnostic()]
+    IndexMap,
+
+    #[error("The segment at the offset {0} of the mappings is not valid")]
+    #[diagnostic()]
+    InvalidMappings(usize),
+}
+
+impl SourceMap {
+    /// Parse a source map, the `sourceRoot` is joined to the `sources`.
+    ///
+    /// # Errors
+    ///
+    /// * The JSON or the mappings are not valid
+    /// * The source map is not a version 3 source map, or is an index source map
+    pub fn from_json_string(json: &str) -> Result<Self, SourceMapError> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| SourceMapError::InvalidJson(e.to_string()))?;
+        let version = value.get("version").ok_or(SourceMapError::MissingField("version"))?;
+        if version.as_u64() != Some(3) {
+            return Err(SourceMapError::UnsupportedVersion(version.to_string()));
+        }
+        if value.get("sections").is_some() {
+            return Err(SourceMapError::IndexMap);
+        }
+        let source_root = value.get("sourceRoot").and_then(Value::as_str).unwrap_or_default();
+        let sources = strings(value.get("sources").ok_or(SourceMapError::MissingField("sources"))?)
+            .into_iter()
+            .map(|source| {
+                let source = source.unwrap_or_default();
+                if source_root.is_empty() || source_root.ends_with('/') {
+                    format!("{source_root}{source}")
+                } else {
+                    format!("{source_root}/{source}")
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut sources_content = value.get("sourcesContent").map(strings).unwrap_or_default();
+        sources_content.resize(sources.len(), None);
+        let names = value.get("names").map(strings).unwrap_or_default();
+        let names = names.into_iter().map(Option::unwrap_or_default).collect();
+        let mappings = value
+            .get("mappings")
+            .and_then(Value::as_str)
+            .ok_or(SourceMapError::MissingField("mappings"))?;
+        let tokens = decode_mappings(mappings)?;
+        Ok(Self { sources, sources_content, names, tokens })
+    }
+}
+
+/// An array of strings or nulls
+fn strings(value: &Value) -> Vec<Option<String>> {
+    value.as_array().map_or_else(Vec::new, |values| {
+        values.iter().map(|value| value.as_str().map(ToString::to_string)).collect()
+    })
+}
+
+fn decode_mappings(mappings: &str) -> Result<Vec<Token>, SourceMapError> {
+    let mut tokens = vec![];
+    // The fields of the previous segment, the segments are relative to it
+    let mut prev = [0i64; 5];
+    let mut offset = 0;
+    for (dst_line, line) in mappings.split(';').enumerate() {
+        prev[0] = 0;
+        for segment in line.split(',') {
+            let segment_offset = offset;
+            offset += segment.len() + 1;
+            if segment.is_empty() {
+                continue;
+            }
+            let fields = decode_vlq_segment(segment)
+                .ok_or(SourceMapError::InvalidMappings(segment_offset))?;
+            if !matches!(fields.len(), 1 | 4 | 5) {
+                return Err(SourceMapError::InvalidMappings(segment_offset));
+            }
+            for (i, field) in fields.iter().enumerate() {
+                prev[i] += field;
+            }
+            let field = |i: usize| {
+                u32::try_from(prev[i]).map_err(|_| SourceMapError::InvalidMappings(segment_offset))
+            };
+            let has_source = fields.len() > 1;
+            tokens.push(Token {
+                dst_line: u32::try_from(dst_line)
+                    .map_err(|_| SourceMapError::InvalidMappings(segment_offset))?,
+                dst_col: field(0)?,
+                src_line: if has_source { field(2)? } else { 0 },
+                src_col: if has_source { field(3)? } else { 0 },
+                source_id: if has_source { Some(field(1)?) } else { None },
+                name_id: if fields.len() == 5 { Some(field(4)?) } else { None },
+            });
+        }
+    }
+    // The segments of a line are usually sorted but the spec does not require it
+    tokens.sort_by_key(|token| (token.dst_line, token.dst_col));
+    Ok(tokens)
+}
+
+fn decode_vlq_segment(segment: &str) -> Option<Vec<i64>> {
+    let mut fields = vec![];
+    let mut value = 0i64;
+    let mut shift = 0;
+    for byte in segment.bytes() {
+        let digit = i64::from(match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        });
+        if shift > 60 {
+            return None;
+        }
+        value |= (digit & 0b1_1111) << shift;
+        if digit & 0b10_0000 == 0 {
+            let negative = value & 1 == 1;
+            value >>= 1;
+            fields.push(if negative { -value } else { value });
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 5;
+        }
+    }
+    // The last field is not finished
+    (shift == 0).then_some(fields)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_vlq_segment, SourceMapError};
+    use crate::SourceMap;
+
+    #[test]
+    fn vlq() {
+        assert_eq!(decode_vlq_segment("ACDegBhBw+B"), Some(vec![0, 1, -1, 15, 16, -16, 1000]));
+        assert_eq!(decode_vlq_segment("g"), None);
+        assert_eq!(decode_vlq_segment("A!"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"version":3,"sources":["a.js"],"sourcesContent":["let foo = 1;\nbar(foo);"],"names":["foo","bar"],"mappings":"AAAA,IAAIA;AACJC,IAAID,C"}"#;
+        let map = SourceMap::from_json_string(json).unwrap();
+        assert_eq!(map.tokens.len(), 5);
+        assert_eq!(map.tokens[3].src_line, 1);
+        assert_eq!(map.tokens[3].src_col, 4);
+        assert_eq!(map.tokens[3].name_id, Some(0));
+        assert_eq!(map.tokens[4].source_id, None);
+        assert_eq!(map.to_json_string(), json);
+    }
+
+    #[test]
+    fn source_root() {
+        let json = r#"{"version":3,"sourceRoot":"src","sources":["a.ts",null],"mappings":""}"#;
+        let map = SourceMap::from_json_string(json).unwrap();
+        assert_eq!(map.sources, vec!["src/a.ts", "src/"]);
+        assert_eq!(map.sources_content, vec![None, None]);
+    }
+
+    #[test]
+    fn errors() {
+        let error =
+            |json| SourceMapError::to_string(&SourceMap::from_json_string(json).unwrap_err());
+        assert_eq!(
+            error("{"),
+            "The source map is not valid JSON: EOF while parsing an object at line 1 column 1"
+        );
+        assert_eq!(
+            error(r#"{"version":2}"#),
+            "The source map version 2 is not supported, only the version 3 is supported"
+        );
+        assert_eq!(
+            error(r#"{"version":3,"sections":[]}"#),
+            "The index source maps with `sections` are not supported"
+        );
+        assert_eq!(
+            error(r#"{"version":3,"sources":[],"mappings":"AAAA;AA"}"#),
+            "The segment at the offset 5 of the mappings is not valid"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::SourceMap;
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl SourceMap {
+    /// # Panics
+    ///
+    /// * The source map can always be serialized
+    pub fn to_json_string(&self) -> String {
+        format!(
+            r#"{{"version":3,"sources":{},"sourcesContent":{},"names":{},"mappings":{}}}"#,
+            serde_json::to_string(&self.sources).unwrap(),
+            serde_json::to_string(&self.sources_content).unwrap(),
+            serde_json::to_string(&self.names).unwrap(),
+            serde_json::to_string(&self.encode_mappings()).unwrap(),
+        )
+    }
+
+    /// `data:application/json;charset=utf-8;base64,...`
+    pub fn to_data_url(&self) -> String {
+        format!(
+            "data:application/json;charset=utf-8;base64,{}",
+            base64(self.to_json_string().as_bytes())
+        )
+    }
+
+    /// The Base64 VLQ encoded `mappings`, each segment is relative to the previous one
+    pub fn encode_mappings(&self) -> String {
+        let mut mappings = String::new();
+        let mut prev_line = 0;
+        let mut prev_col = 0;
+        let mut prev_source_id = 0;
+        let mut prev_src_line = 0;
+        let mut prev_src_col = 0;
+        let mut prev_name_id = 0;
+        for (i, token) in self.tokens.iter().enumerate() {
+            if token.dst_line > prev_line {
+                for _ in prev_line..token.dst_line {
+                    mappings.push(';');
+                }
+                prev_line = token.dst_line;
+                prev_col = 0;
+            } else if i > 0 {
+                mappings.push(',');
+            }
+            encode_vlq(&mut mappings, i64::from(token.dst_col) - i64::from(prev_col));
+            prev_col = token.dst_col;
+            let Some(source_id) = token.source_id else { continue };
+            encode_vlq(&mut mappings, i64::from(source_id) - i64::from(prev_source_id));
+            encode_vlq(&mut mappings, i64::from(token.src_line) - i64::from(prev_src_line));
+            encode_vlq(&mut mappings, i64::from(token.src_col) - i64::from(prev_src_col));
+            prev_source_id = source_id;
+            prev_src_line = token.src_line;
+            prev_src_col = token.src_col;
+            if let Some(name_id) = token.name_id {
+                encode_vlq(&mut mappings, i64::from(name_id) - i64::from(prev_name_id));
+                prev_name_id = name_id;
+            }
+        }
+        mappings
+    }
+}
+
+fn encode_vlq(out: &mut String, value: i64) {
+    let mut vlq = if value < 0 { ((-value) << 1) | 1 } else { value << 1 };
+    loop {
+        let mut digit = vlq & 0b1_1111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b10_0000;
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        out.push(BASE64_CHARS[digit as usize] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | u32::from(*byte) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_CHARS[(n >> (18 - 6 * i) & 0b11_1111) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{base64, encode_vlq};
+
+    #[test]
+    fn vlq() {
+        let mut out = String::new();
+        for value in [0, 1, -1, 15, 16, -16, 1000] {
+            encode_vlq(&mut out, value);
+            out.push(',');
+        }
+        assert_eq!(out, "A,C,D,e,gB,hB,w+B,");
+    }
+
+    #[test]
+    fn base64_encoding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
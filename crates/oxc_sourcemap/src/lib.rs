@@ -0,0 +1,21 @@
+//! Source Map Revision 3
+//!
+//! <https://sourcemaps.info/spec.html>
+//!
+//! Supports
+//!
+//! * parsing the source maps of other tools, [SourceMap::from_json_string]
+//! * printing, [SourceMap::to_json_string] and [SourceMap::to_data_url]
+//! * composing the source maps of the pipeline steps, [SourceMap::compose]
+//! * remapping the diagnostics of the generated code to the original sources, [SourceMapRemapper]
+
+mod decode;
+mod encode;
+mod remap;
+mod sourcemap;
+
+pub use crate::{
+    decode::SourceMapError,
+    remap::{OriginalSpan, SourceMapRemapper},
+    sourcemap::{SourceMap, Token},
+};
@@ -0,0 +1,227 @@
+use std::fmt;
+
+use oxc_diagnostics::{
+    miette::{Diagnostic, LabeledSpan, NamedSource, Severity, SourceCode},
+    Error,
+};
+use oxc_span::{LineColumn, LineIndex, Span};
+
+use crate::SourceMap;
+
+/// A span of one of the sources of a source map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OriginalSpan {
+    /// The index of [SourceMap::sources]
+    pub source_id: u32,
+    pub span: Span,
+}
+
+/// Maps the spans and the diagnostics of a generated file to the original sources, so that the
+/// diagnostics of the generated code point at the code which was written
+pub struct SourceMapRemapper<'a> {
+    source_map: &'a SourceMap,
+    generated: LineIndex,
+    /// The line indexes of [SourceMap::sources_content]
+    originals: Vec<Option<LineIndex>>,
+}
+
+impl<'a> SourceMapRemapper<'a> {
+    pub fn new(source_map: &'a SourceMap, generated_text: &'a str) -> Self {
+        let originals = source_map
+            .sources_content
+            .iter()
+            .map(|content| content.as_deref().map(LineIndex::new))
+            .collect();
+        Self { source_map, generated: LineIndex::new(generated_text), originals }
+    }
+
+    /// The source and the byte offset of the original code at `offset` of the generated code
+    pub fn remap_offset(&self, offset: u32) -> Option<(u32, u32)> {
+        if offset > self.generated.text_len() {
+            return None;
+        }
+        let LineColumn { line, column } = self.generated.line_column_utf16(offset);
+        let token = self.source_map.lookup_token(line, column)?;
+        let source_id = token.source_id?;
+        let original = self.originals.get(source_id as usize)?.as_ref()?;
+        let position = LineColumn { line: token.src_line, column: token.src_col };
+        Some((source_id, original.offset_utf16(position)?))
+    }
+
+    /// The start and the end of `span` are mapped separately, the end is kept at the same length
+    /// from the start when it maps to a different place.
+    pub fn remap_span(&self, span: Span) -> Option<OriginalSpan> {
+        let (source_id, start) = self.remap_offset(span.start)?;
+        let end = match self.remap_offset(span.end) {
+            Some((end_source_id, end))
+                if end_source_id == source_id && (end > start || span.size() == 0) =>
+            {
+                end
+            }
+            _ => {
+                let original = self.originals[source_id as usize].as_ref()?;
+                (start + span.size()).min(original.text_len())
+            }
+        };
+        Some(OriginalSpan { source_id, span: Span::new(start, end) })
+    }
+
+    /// The diagnostic with its labels in the original source, or `error` itself when its first
+    /// label does not map to a source with a content. The labels which map to other sources are
+    /// dropped.
+    pub fn remap_diagnostic(&self, error: Error) -> Error {
+        let Some(labels) = error.labels() else { return error };
+        let mut source_id = None;
+        let mut remapped_labels = vec![];
+        for label in labels {
+            #[allow(clippy::cast_possible_truncation)]
+            let span = Span::new(label.offset() as u32, (label.offset() + label.len()) as u32);
+            let Some(original) = self.remap_span(span) else { continue };
+            if *source_id.get_or_insert(original.source_id) != original.source_id {
+                continue;
+            }
+            let span = original.span;
+            remapped_labels.push(LabeledSpan::new_with_span(label.label().map(String::from), span));
+        }
+        let Some(source_id) = source_id else { return error };
+        let (Some(name), Some(content)) =
+            (self.source_map.get_source(source_id), self.source_map.get_source_content(source_id))
+        else {
+            return error;
+        };
+        let source_code = NamedSource::new(name, content.to_string());
+        let message = error.to_string();
+        RemappedDiagnostic { message, inner: error, labels: remapped_labels, source_code }.into()
+    }
+}
+
+/// A diagnostic of the generated code with the labels and the source code of the original source
+struct RemappedDiagnostic {
+    message: String,
+    inner: Error,
+    labels: Vec<LabeledSpan>,
+    source_code: NamedSource,
+}
+
+impl fmt::Debug for RemappedDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemappedDiagnostic")
+            .field("message", &self.message)
+            .field("labels", &self.labels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for RemappedDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for RemappedDiagnostic {}
+
+impl Diagnostic for RemappedDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.inner.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.inner.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.inner.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.inner.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source_code)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.labels.iter().cloned()))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.inner.related()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_diagnostics::{
+        miette::{self, Diagnostic},
+        thiserror::Error,
+    };
+    use oxc_span::Span;
+
+    use super::{OriginalSpan, SourceMapRemapper};
+    use crate::{SourceMap, Token};
+
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("`bar` is not defined")]
+    #[diagnostic(help("Define `bar`"))]
+    struct NotDefined(#[label("here")] Span);
+
+    fn token(dst: (u32, u32), src: (u32, u32)) -> Token {
+        Token {
+            dst_line: dst.0,
+            dst_col: dst.1,
+            src_line: src.0,
+            src_col: src.1,
+            source_id: Some(0),
+            name_id: None,
+        }
+    }
+
+    // `const a: number = 1;\nbar(a);` printed as `const a=1;bar(a);`
+    fn source_map() -> SourceMap {
+        SourceMap {
+            sources: vec!["a.ts".into()],
+            sources_content: vec![Some("const a: number = 1;\nbar(a);".into())],
+            names: vec![],
+            tokens: vec![
+                token((0, 0), (0, 0)),
+                token((0, 6), (0, 6)),
+                token((0, 10), (1, 0)),
+                token((0, 13), (1, 3)),
+                token((0, 14), (1, 4)),
+            ],
+        }
+    }
+
+    #[test]
+    fn remap_span() {
+        let source_map = source_map();
+        let remapper = SourceMapRemapper::new(&source_map, "const a=1;bar(a);");
+        assert_eq!(
+            remapper.remap_span(Span::new(10, 13)),
+            Some(OriginalSpan { source_id: 0, span: Span::new(21, 24) })
+        );
+        // The end of `a` maps to its start, the end is kept at the same length
+        assert_eq!(
+            remapper.remap_span(Span::new(6, 7)),
+            Some(OriginalSpan { source_id: 0, span: Span::new(6, 7) })
+        );
+    }
+
+    #[test]
+    fn remap_diagnostic() {
+        let source_map = source_map();
+        let remapper = SourceMapRemapper::new(&source_map, "const a=1;bar(a);");
+        let error = remapper.remap_diagnostic(NotDefined(Span::new(10, 13)).into());
+        assert_eq!(error.to_string(), "`bar` is not defined");
+        assert_eq!(error.help().unwrap().to_string(), "Define `bar`");
+        let labels = error.labels().unwrap().collect::<Vec<_>>();
+        assert_eq!(labels.len(), 1);
+        assert_eq!((labels[0].offset(), labels[0].len()), (21, 3));
+        assert_eq!(labels[0].label(), Some("here"));
+        let source_code = error.source_code().unwrap();
+        let contents = source_code.read_span(&labels[0].inner().clone(), 0, 0).unwrap();
+        assert_eq!(contents.data(), b"bar");
+        assert_eq!(contents.name(), Some("a.ts"));
+    }
+}
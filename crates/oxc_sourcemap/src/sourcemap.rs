@@ -0,0 +1,150 @@
+/// A decoded source map
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    pub sources: Vec<String>,
+    /// The content of each of the `sources`, `None` when the source map does not have it
+    pub sources_content: Vec<Option<String>>,
+    pub names: Vec<String>,
+    /// The mappings sorted by their generated positions
+    pub tokens: Vec<Token>,
+}
+
+/// A mapping from a position of the generated code to a position of one of the sources.
+///
+/// The lines and the UTF-16 columns start from 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub dst_line: u32,
+    pub dst_col: u32,
+    pub src_line: u32,
+    pub src_col: u32,
+    /// The index of [SourceMap::sources], `None` for the generated code without an original
+    pub source_id: Option<u32>,
+    /// The index of [SourceMap::names]
+    pub name_id: Option<u32>,
+}
+
+impl SourceMap {
+    pub fn get_source(&self, source_id: u32) -> Option<&str> {
+        self.sources.get(source_id as usize).map(String::as_str)
+    }
+
+    pub fn get_source_content(&self, source_id: u32) -> Option<&str> {
+        self.sources_content.get(source_id as usize).and_then(Option::as_deref)
+    }
+
+    pub fn get_name(&self, name_id: u32) -> Option<&str> {
+        self.names.get(name_id as usize).map(String::as_str)
+    }
+
+    /// The token of the generated code at `line` and `col`, which is the last token of the line
+    /// starting at or before `col`
+    pub fn lookup_token(&self, line: u32, col: u32) -> Option<&Token> {
+        let index =
+            self.tokens.partition_point(|token| (token.dst_line, token.dst_col) <= (line, col));
+        let token = self.tokens.get(index.checked_sub(1)?)?;
+        (token.dst_line == line).then_some(token)
+    }
+
+    /// The source map of two steps, where `self` maps the generated code to the code which
+    /// `original` maps to the original sources.
+    ///
+    /// e.g. the minifier source map composed with the transformer source map maps the minified
+    /// code to the TypeScript sources. The tokens of `self` which do not map into `original` are
+    /// dropped.
+    #[must_use]
+    pub fn compose(&self, original: &Self) -> Self {
+        let mut composed = Self {
+            sources: original.sources.clone(),
+            sources_content: original.sources_content.clone(),
+            names: vec![],
+            tokens: Vec::with_capacity(self.tokens.len()),
+        };
+        let mut name_ids = std::collections::HashMap::new();
+        for token in &self.tokens {
+            if token.source_id.is_none() {
+                continue;
+            }
+            let Some(original_token) = original.lookup_token(token.src_line, token.src_col) else {
+                continue;
+            };
+            if original_token.source_id.is_none() {
+                continue;
+            }
+            // The name of the original source is the name before the renaming of the later steps
+            let name = original_token
+                .name_id
+                .and_then(|id| original.get_name(id))
+                .or_else(|| token.name_id.and_then(|id| self.get_name(id)));
+            let name_id = name.map(|name| {
+                *name_ids.entry(name.to_string()).or_insert_with(|| {
+                    composed.names.push(name.to_string());
+                    #[allow(clippy::cast_possible_truncation)]
+                    let id = composed.names.len() as u32 - 1;
+                    id
+                })
+            });
+            composed.tokens.push(Token {
+                dst_line: token.dst_line,
+                dst_col: token.dst_col,
+                src_line: original_token.src_line,
+                src_col: original_token.src_col,
+                source_id: original_token.source_id,
+                name_id,
+            });
+        }
+        composed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SourceMap, Token};
+
+    fn token(dst: (u32, u32), src: (u32, u32), name_id: Option<u32>) -> Token {
+        Token {
+            dst_line: dst.0,
+            dst_col: dst.1,
+            src_line: src.0,
+            src_col: src.1,
+            source_id: Some(0),
+            name_id,
+        }
+    }
+
+    #[test]
+    fn lookup_token() {
+        let map = SourceMap {
+            sources: vec!["a.js".into()],
+            tokens: vec![token((0, 0), (0, 0), None), token((0, 4), (1, 2), None)],
+            ..SourceMap::default()
+        };
+        assert_eq!(map.lookup_token(0, 0), Some(&map.tokens[0]));
+        assert_eq!(map.lookup_token(0, 3), Some(&map.tokens[0]));
+        assert_eq!(map.lookup_token(0, 10), Some(&map.tokens[1]));
+        assert_eq!(map.lookup_token(1, 0), None);
+    }
+
+    #[test]
+    fn compose() {
+        // a.ts -> a.js
+        let transformer = SourceMap {
+            sources: vec!["a.ts".into()],
+            sources_content: vec![Some("let foo: number = 1;".into())],
+            names: vec!["foo".into()],
+            tokens: vec![token((0, 0), (0, 0), None), token((0, 4), (0, 4), Some(0))],
+        };
+        // a.js -> a.min.js, `foo` is renamed to `a`
+        let minifier = SourceMap {
+            sources: vec!["a.js".into()],
+            sources_content: vec![None],
+            names: vec!["a".into()],
+            tokens: vec![token((0, 0), (0, 0), None), token((0, 4), (0, 4), Some(0))],
+        };
+        let composed = minifier.compose(&transformer);
+        assert_eq!(composed.sources, vec!["a.ts"]);
+        assert_eq!(composed.get_source_content(0), Some("let foo: number = 1;"));
+        assert_eq!(composed.names, vec!["foo"]);
+        assert_eq!(composed.tokens, transformer.tokens);
+    }
+}
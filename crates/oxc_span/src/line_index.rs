@@ -0,0 +1,182 @@
+/// The line and the column of a position in a text, both zero-based
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineColumn {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A character longer than one byte, whose UTF-16 length differs from its UTF-8 length
+#[derive(Debug, Clone, Copy)]
+struct WideChar {
+    /// The byte offset of the character from the start of its line
+    start: u32,
+    len_utf8: u8,
+}
+
+impl WideChar {
+    fn len_utf16(self) -> u32 {
+        if self.len_utf8 == 4 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// The conversions between the byte offsets of a text and its lines and columns, built once per
+/// text. The columns are in bytes, or in UTF-16 code units as in the Language Server Protocol
+/// and the source maps.
+///
+/// The lines end at `\n`, `\r\n` and `\r`. The conversions do a binary search of the lines, and
+/// the UTF-16 ones also go through the non-ASCII characters of their line.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// The byte offsets of the line starts
+    line_starts: Vec<u32>,
+    /// The byte offsets of the line ends, before their line terminators
+    line_ends: Vec<u32>,
+    /// The non-ASCII characters of the lines, only for the lines which have some
+    wide_chars: Vec<(u32, Vec<WideChar>)>,
+}
+
+impl LineIndex {
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut line_ends = vec![];
+        let mut wide_chars = vec![];
+        let mut line_wide_chars = vec![];
+        let bytes = text.as_bytes();
+        let mut line_start = 0;
+        let mut chars = text.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\n' | '\r' => {
+                    line_ends.push(i as u32);
+                    let len = if c == '\r' && bytes.get(i + 1) == Some(&b'\n') {
+                        chars.next();
+                        2
+                    } else {
+                        1
+                    };
+                    if !line_wide_chars.is_empty() {
+                        let line = line_starts.len() as u32 - 1;
+                        wide_chars.push((line, std::mem::take(&mut line_wide_chars)));
+                    }
+                    line_start = (i + len) as u32;
+                    line_starts.push(line_start);
+                }
+                c if !c.is_ascii() => line_wide_chars
+                    .push(WideChar { start: i as u32 - line_start, len_utf8: c.len_utf8() as u8 }),
+                _ => {}
+            }
+        }
+        line_ends.push(text.len() as u32);
+        if !line_wide_chars.is_empty() {
+            wide_chars.push((line_starts.len() as u32 - 1, line_wide_chars));
+        }
+        Self { line_starts, line_ends, wide_chars }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    pub fn line_start(&self, line: u32) -> Option<u32> {
+        self.line_starts.get(line as usize).copied()
+    }
+
+    /// The line and the byte column of `offset`. The offsets past the end of the text are at the
+    /// end of the last line.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn line_column(&self, offset: u32) -> LineColumn {
+        let offset = offset.min(self.text_len());
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        LineColumn { line: line as u32, column: offset - self.line_starts[line] }
+    }
+
+    /// The line and the UTF-16 column of `offset`
+    pub fn line_column_utf16(&self, offset: u32) -> LineColumn {
+        let LineColumn { line, column } = self.line_column(offset);
+        let extra: u32 = self
+            .line_wide_chars(line)
+            .iter()
+            .take_while(|c| c.start < column)
+            .map(|c| u32::from(c.len_utf8) - c.len_utf16())
+            .sum();
+        LineColumn { line, column: column - extra }
+    }
+
+    /// The byte offset of a line and byte column, `None` when the line does not exist. The
+    /// columns past the end of the line are at its end.
+    pub fn offset(&self, position: LineColumn) -> Option<u32> {
+        let start = self.line_start(position.line)?;
+        let end = self.line_ends[position.line as usize];
+        Some((start + position.column).min(end))
+    }
+
+    /// The byte offset of a line and UTF-16 column, `None` when the line does not exist
+    pub fn offset_utf16(&self, position: LineColumn) -> Option<u32> {
+        let mut column = position.column;
+        for c in self.line_wide_chars(position.line) {
+            if c.start >= column {
+                break;
+            }
+            column += u32::from(c.len_utf8) - c.len_utf16();
+        }
+        self.offset(LineColumn { line: position.line, column })
+    }
+
+    /// The length of the text in bytes
+    pub fn text_len(&self) -> u32 {
+        self.line_ends[self.line_ends.len() - 1]
+    }
+
+    fn line_wide_chars(&self, line: u32) -> &[WideChar] {
+        self.wide_chars
+            .binary_search_by_key(&line, |(line, _)| *line)
+            .map_or(&[], |index| &self.wide_chars[index].1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LineColumn, LineIndex};
+
+    fn position(line: u32, column: u32) -> LineColumn {
+        LineColumn { line, column }
+    }
+
+    #[test]
+    fn lines() {
+        let index = LineIndex::new("a\nbc\r\nd\re");
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_column(0), position(0, 0));
+        assert_eq!(index.line_column(1), position(0, 1));
+        assert_eq!(index.line_column(4), position(1, 2));
+        assert_eq!(index.line_column(6), position(2, 0));
+        assert_eq!(index.line_column(8), position(3, 0));
+        assert_eq!(index.line_column(100), position(3, 1));
+        assert_eq!(index.offset(position(1, 1)), Some(3));
+        assert_eq!(index.offset(position(1, 10)), Some(4));
+        assert_eq!(index.offset(position(3, 1)), Some(9));
+        assert_eq!(index.offset(position(4, 0)), None);
+    }
+
+    #[test]
+    fn utf16() {
+        // `😀` is 4 bytes and 2 UTF-16 code units, `é` is 2 bytes and 1 code unit
+        let index = LineIndex::new("a😀b\nc\néé");
+        assert_eq!(index.line_column_utf16(5), position(0, 3));
+        assert_eq!(index.line_column_utf16(6), position(0, 4));
+        assert_eq!(index.line_column_utf16(7), position(1, 0));
+        assert_eq!(index.line_column_utf16(11), position(2, 1));
+        assert_eq!(index.line_column(11), position(2, 2));
+        assert_eq!(index.offset_utf16(position(0, 3)), Some(5));
+        assert_eq!(index.offset_utf16(position(0, 10)), Some(6));
+        assert_eq!(index.offset_utf16(position(1, 1)), Some(8));
+        assert_eq!(index.offset_utf16(position(2, 1)), Some(11));
+        assert_eq!(index.offset_utf16(position(2, 2)), Some(13));
+        assert_eq!(index.offset_utf16(position(3, 0)), None);
+    }
+}
@@ -12,4 +12,17 @@ pub struct CompilerAssumptions {
     /// See <https://babeljs.io/docs/assumptions#nodocumentall>.
     #[cfg_attr(feature = "serde", serde(default))]
     pub no_document_all: bool,
+
+    /// When using private fields or methods, assume that they are only accessed on instances of
+    /// the class, and store them in non-enumerable properties instead of `WeakMap`s.
+    /// See <https://babeljs.io/docs/assumptions#privatefieldsasproperties>.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub private_fields_as_properties: bool,
+
+    /// When using public class fields, assume that they don't shadow any getter in the current
+    /// class, in its subclasses or in its superclass, so it's safe to assign them rather than
+    /// using `Object.defineProperty`.
+    /// See <https://babeljs.io/docs/assumptions#setpublicclassfields>.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub set_public_class_fields: bool,
 }
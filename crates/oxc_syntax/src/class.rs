@@ -30,3 +30,28 @@ impl ElementKind {
         self.contains(Self::Accessor)
     }
 }
+
+bitflags! {
+    /// How a class element is accessed by a reference
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ElementReferenceFlag: u8 {
+        const Read = 1 << 0;
+        const Write = 1 << 1;
+        /// The element is the callee of a call expression, e.g. `this.#method()`
+        const Call = 1 << 2;
+    }
+}
+
+impl ElementReferenceFlag {
+    pub fn is_read(self) -> bool {
+        self.contains(Self::Read)
+    }
+
+    pub fn is_write(self) -> bool {
+        self.contains(Self::Write)
+    }
+
+    pub fn is_call(self) -> bool {
+        self.contains(Self::Call)
+    }
+}
@@ -3,6 +3,7 @@
 pub mod assumptions;
 pub mod class;
 pub mod identifier;
+pub mod module_graph;
 pub mod module_record;
 pub mod operator;
 pub mod precedence;
@@ -0,0 +1,326 @@
+//! Analysis across the module graph formed by [`ModuleRecord::loaded_modules`]
+
+use std::{path::PathBuf, sync::Arc};
+
+use oxc_span::{Atom, Span};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::module_record::{
+    ExportEntry, ExportExportName, ExportImportName, ImportImportName, ModuleRecord,
+};
+
+/// An exported binding which is never imported by any module reachable from the entry points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedExport {
+    /// Resolved absolute path of the exporting module
+    pub path: PathBuf,
+    /// Exported name, `default` for default exports
+    pub name: Atom,
+    /// Span of the exported name
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+enum Usage {
+    Name(Atom),
+    /// Namespace imports `import * as ns` and `export * as ns`, or the exports of an entry point
+    All,
+    /// Re-exported through `export * from "mod"`, which does not include the default export
+    AllButDefault,
+}
+
+#[derive(Debug, Default)]
+struct UsedExports {
+    all: bool,
+    all_but_default: bool,
+    names: FxHashSet<Atom>,
+}
+
+impl UsedExports {
+    fn contains(&self, name: &Atom) -> bool {
+        self.all
+            || (self.all_but_default && name.as_str() != "default")
+            || self.names.contains(name)
+    }
+
+    /// Record the usage, returns `false` if it was already recorded
+    fn insert(&mut self, usage: &Usage) -> bool {
+        match usage {
+            Usage::Name(name) => !self.contains(name) && self.names.insert(name.clone()),
+            Usage::All => !std::mem::replace(&mut self.all, true),
+            Usage::AllButDefault => {
+                !self.all && !std::mem::replace(&mut self.all_but_default, true)
+            }
+        }
+    }
+}
+
+/// Find all exports of the modules reachable from `entries` which are never imported.
+///
+/// All exports of the entry points are considered used. Imports are followed through
+/// `export { a } from "mod"`, `export * as ns from "mod"` and `export * from "mod"`, so a
+/// re-export only marks the original export as used when the re-export itself is used.
+///
+/// Only modules in [`ModuleRecord::loaded_modules`] are part of the graph, unresolved imports
+/// are ignored. The result is sorted by path and span.
+pub fn find_unused_exports(entries: &[Arc<ModuleRecord>]) -> Vec<UnusedExport> {
+    let modules = collect_modules(entries);
+
+    let mut used: FxHashMap<PathBuf, UsedExports> = FxHashMap::default();
+    let mut queue: Vec<(Arc<ModuleRecord>, Usage)> =
+        entries.iter().map(|entry| (Arc::clone(entry), Usage::All)).collect();
+    for module in modules.values() {
+        for entry in &module.import_entries {
+            let Some(target) = loaded_module(module, entry.module_request.name()) else {
+                continue;
+            };
+            let usage = match &entry.import_name {
+                ImportImportName::Name(name) => Usage::Name(name.name().clone()),
+                ImportImportName::Default(_) => Usage::Name(Atom::from("default")),
+                ImportImportName::NamespaceObject => Usage::All,
+            };
+            queue.push((target, usage));
+        }
+    }
+
+    while let Some((module, usage)) = queue.pop() {
+        let path = &module.resolved_absolute_path;
+        if !used.entry(path.clone()).or_default().insert(&usage) {
+            continue;
+        }
+        propagate_usage(&module, &usage, &mut queue);
+    }
+
+    let mut unused = vec![];
+    for (path, module) in &modules {
+        if entries.iter().any(|entry| &entry.resolved_absolute_path == path) {
+            continue;
+        }
+        let used = used.get(path);
+        for entry in module.local_export_entries.iter().chain(&module.indirect_export_entries) {
+            let Some(name) = export_name(entry) else { continue };
+            if !used.is_some_and(|used| used.contains(&name)) {
+                let span = entry.export_name.span().unwrap_or(entry.span);
+                unused.push(UnusedExport { path: path.clone(), name, span });
+            }
+        }
+    }
+    unused.sort_by(|a, b| a.path.cmp(&b.path).then(a.span.start.cmp(&b.span.start)));
+    unused
+}
+
+/// Mark the imports behind the re-exports of `module` matching `usage` as used
+fn propagate_usage(
+    module: &ModuleRecord,
+    usage: &Usage,
+    queue: &mut Vec<(Arc<ModuleRecord>, Usage)>,
+) {
+    for entry in &module.indirect_export_entries {
+        let is_used = match usage {
+            Usage::Name(name) => export_name(entry).is_some_and(|export| export == *name),
+            Usage::All => true,
+            Usage::AllButDefault => !entry.export_name.is_default(),
+        };
+        if !is_used {
+            continue;
+        }
+        let Some(target) = entry_module(module, entry) else { continue };
+        match &entry.import_name {
+            ExportImportName::Name(name) => queue.push((target, Usage::Name(name.name().clone()))),
+            ExportImportName::All => queue.push((target, Usage::All)),
+            ExportImportName::AllButDefault | ExportImportName::Null => {}
+        }
+    }
+
+    // `export * from "mod"` provides every name not exported by the module itself
+    let star_usage = match usage {
+        Usage::Name(name) if name.as_str() == "default" => return,
+        Usage::Name(name) => {
+            let is_own_export = module
+                .local_export_entries
+                .iter()
+                .chain(&module.indirect_export_entries)
+                .any(|entry| export_name(entry).is_some_and(|export| export == *name));
+            if is_own_export {
+                return;
+            }
+            Usage::Name(name.clone())
+        }
+        Usage::All | Usage::AllButDefault => Usage::AllButDefault,
+    };
+    for entry in &module.star_export_entries {
+        if let Some(target) = entry_module(module, entry) {
+            queue.push((target, star_usage.clone()));
+        }
+    }
+}
+
+/// All modules reachable from `entries`, keyed by their resolved path
+fn collect_modules(entries: &[Arc<ModuleRecord>]) -> FxHashMap<PathBuf, Arc<ModuleRecord>> {
+    let mut modules = FxHashMap::default();
+    let mut stack = entries.to_vec();
+    while let Some(module) = stack.pop() {
+        if modules.contains_key(&module.resolved_absolute_path) {
+            continue;
+        }
+        stack.extend(module.loaded_modules.iter().map(|entry| Arc::clone(entry.value())));
+        modules.insert(module.resolved_absolute_path.clone(), module);
+    }
+    modules
+}
+
+fn loaded_module(module: &ModuleRecord, specifier: &Atom) -> Option<Arc<ModuleRecord>> {
+    module.loaded_modules.get(specifier).map(|entry| Arc::clone(entry.value()))
+}
+
+fn entry_module(module: &ModuleRecord, entry: &ExportEntry) -> Option<Arc<ModuleRecord>> {
+    loaded_module(module, entry.module_request.as_ref()?.name())
+}
+
+fn export_name(entry: &ExportEntry) -> Option<Atom> {
+    match &entry.export_name {
+        ExportExportName::Name(name) => Some(name.name().clone()),
+        ExportExportName::Default(_) => Some(Atom::from("default")),
+        ExportExportName::Null => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::PathBuf, sync::Arc};
+
+    use oxc_span::{Atom, Span};
+
+    use super::find_unused_exports;
+    use crate::module_record::{
+        ExportEntry, ExportExportName, ExportImportName, ImportEntry, ImportImportName,
+        ModuleRecord, NameSpan,
+    };
+
+    fn name(name: &str) -> NameSpan {
+        NameSpan::new(Atom::from(name), Span::default())
+    }
+
+    fn module(path: &str) -> ModuleRecord {
+        ModuleRecord::new(PathBuf::from(path))
+    }
+
+    fn export(module: &mut ModuleRecord, export_name: &str, start: u32) {
+        let span = Span::new(start, start + 1);
+        module.local_export_entries.push(ExportEntry {
+            export_name: ExportExportName::Name(NameSpan::new(Atom::from(export_name), span)),
+            ..ExportEntry::default()
+        });
+    }
+
+    fn import(
+        module: &mut ModuleRecord,
+        source: &Arc<ModuleRecord>,
+        import_name: ImportImportName,
+    ) {
+        let specifier = source.resolved_absolute_path.to_string_lossy().to_string();
+        module.import_entries.push(ImportEntry {
+            module_request: name(&specifier),
+            import_name,
+            local_name: name("local"),
+        });
+        module.loaded_modules.insert(Atom::from(specifier), Arc::clone(source));
+    }
+
+    fn re_export(
+        module: &mut ModuleRecord,
+        source: &Arc<ModuleRecord>,
+        import_name: ExportImportName,
+        export_name: ExportExportName,
+    ) {
+        let specifier = source.resolved_absolute_path.to_string_lossy().to_string();
+        let entry = ExportEntry {
+            module_request: Some(name(&specifier)),
+            import_name,
+            export_name,
+            ..ExportEntry::default()
+        };
+        if entry.export_name.is_null() {
+            module.star_export_entries.push(entry);
+        } else {
+            module.indirect_export_entries.push(entry);
+        }
+        module.loaded_modules.insert(Atom::from(specifier), Arc::clone(source));
+    }
+
+    fn unused_names(entries: &[Arc<ModuleRecord>]) -> Vec<(String, String)> {
+        find_unused_exports(entries)
+            .into_iter()
+            .map(|unused| (unused.path.to_string_lossy().to_string(), unused.name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn named_imports() {
+        let mut lib = module("/lib.js");
+        export(&mut lib, "used", 0);
+        export(&mut lib, "unused", 1);
+        let lib = Arc::new(lib);
+
+        let mut entry = module("/entry.js");
+        export(&mut entry, "api", 0);
+        import(&mut entry, &lib, ImportImportName::Name(name("used")));
+
+        let unused = unused_names(&[Arc::new(entry)]);
+        assert_eq!(unused, vec![("/lib.js".into(), "unused".into())]);
+    }
+
+    #[test]
+    fn namespace_imports() {
+        let mut lib = module("/lib.js");
+        export(&mut lib, "a", 0);
+        export(&mut lib, "b", 1);
+        let lib = Arc::new(lib);
+
+        let mut entry = module("/entry.js");
+        import(&mut entry, &lib, ImportImportName::NamespaceObject);
+
+        assert!(unused_names(&[Arc::new(entry)]).is_empty());
+    }
+
+    #[test]
+    fn re_exports() {
+        let mut lib = module("/lib.js");
+        export(&mut lib, "a", 0);
+        export(&mut lib, "b", 1);
+        export(&mut lib, "c", 2);
+        let lib = Arc::new(lib);
+
+        // export { a as renamed } from './lib'; export * from './lib'
+        let mut index = module("/index.js");
+        re_export(
+            &mut index,
+            &lib,
+            ExportImportName::Name(name("a")),
+            ExportExportName::Name(name("renamed")),
+        );
+        re_export(&mut index, &lib, ExportImportName::AllButDefault, ExportExportName::Null);
+        let index = Arc::new(index);
+
+        let mut entry = module("/entry.js");
+        import(&mut entry, &index, ImportImportName::Name(name("renamed")));
+        import(&mut entry, &index, ImportImportName::Name(name("b")));
+
+        let unused = unused_names(&[Arc::new(entry)]);
+        assert_eq!(unused, vec![("/lib.js".into(), "c".into())]);
+    }
+
+    #[test]
+    fn entry_exports_are_used() {
+        let mut lib = module("/lib.js");
+        export(&mut lib, "a", 0);
+        let lib = Arc::new(lib);
+
+        // export * from './lib'
+        let mut entry = module("/entry.js");
+        export(&mut entry, "unused_in_graph", 0);
+        re_export(&mut entry, &lib, ExportImportName::AllButDefault, ExportExportName::Null);
+
+        assert!(unused_names(&[Arc::new(entry)]).is_empty());
+    }
+}
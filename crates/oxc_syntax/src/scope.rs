@@ -1,5 +1,7 @@
 use bitflags::bitflags;
 use oxc_index::define_index_type;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 define_index_type! {
     pub struct ScopeId = u32;
@@ -7,6 +9,7 @@ define_index_type! {
 
 bitflags! {
     #[derive(Debug, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(Serialize))]
     pub struct ScopeFlags: u16 {
         const StrictMode       = 1 << 0;
         const Top              = 1 << 1;
@@ -51,7 +51,8 @@ fn main() {
     };
     Transformer::new(&allocator, source_type, semantic, transform_options).build(program).unwrap();
 
-    let printed = Codegen::<false>::new(source_text.len(), CodegenOptions).build(program);
+    let printed =
+        Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program);
     println!("Transformed:\n");
     println!("{printed}");
 }
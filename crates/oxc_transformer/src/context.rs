@@ -4,21 +4,36 @@ use std::{
     rc::Rc,
 };
 
-use oxc_ast::AstBuilder;
+use oxc_ast::{
+    ast::{Expression, Program},
+    AstBuilder,
+};
 use oxc_diagnostics::Error;
 use oxc_semantic::{ScopeId, ScopeTree, Semantic, SymbolId, SymbolTable};
 use oxc_span::{Atom, SourceType};
 
+use crate::helpers::HelperLoader;
+
 #[derive(Clone)]
 pub struct TransformerCtx<'a> {
     pub ast: Rc<AstBuilder<'a>>,
     semantic: Rc<RefCell<Semantic<'a>>>,
     errors: Rc<RefCell<Vec<Error>>>,
+    helpers: Rc<RefCell<HelperLoader>>,
 }
 
 impl<'a> TransformerCtx<'a> {
-    pub fn new(ast: Rc<AstBuilder<'a>>, semantic: Rc<RefCell<Semantic<'a>>>) -> Self {
-        Self { ast, semantic, errors: Rc::new(RefCell::new(vec![])) }
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        semantic: Rc<RefCell<Semantic<'a>>>,
+        helpers: HelperLoader,
+    ) -> Self {
+        Self {
+            ast,
+            semantic,
+            errors: Rc::new(RefCell::new(vec![])),
+            helpers: Rc::new(RefCell::new(helpers)),
+        }
     }
 
     pub fn semantic(&self) -> Ref<'_, Semantic<'a>> {
@@ -46,6 +61,16 @@ impl<'a> TransformerCtx<'a> {
         Ref::map(self.semantic.borrow(), |semantic| semantic.source_type())
     }
 
+    /// The expression of the runtime helper `name`, e.g. `babelHelpers.asyncToGenerator`
+    pub fn helper(&self, name: &str) -> Expression<'a> {
+        self.helpers.borrow_mut().load(self, name)
+    }
+
+    /// Insert the imports of the helpers requested by the transforms of `program`
+    pub fn inject_helpers(&self, program: &mut Program<'a>) {
+        self.helpers.borrow_mut().inject(&self.ast, program);
+    }
+
     pub fn errors(&self) -> Vec<Error> {
         mem::take(&mut self.errors.borrow_mut())
     }
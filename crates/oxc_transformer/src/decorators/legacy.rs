@@ -0,0 +1,245 @@
+use std::mem;
+
+use oxc_allocator::{Box, Vec};
+use oxc_ast::ast::*;
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::NumberBase;
+
+use super::{ClassExport, Decorators};
+use crate::utils::CreateVars;
+
+/// Legacy decorators, like TypeScript's `experimentalDecorators`
+///
+/// ```TypeScript
+/// @dec
+/// class Foo {
+///   @m method() {}
+///   @p static x = 1;
+/// }
+/// ```
+/// ```JavaScript
+/// let Foo = class Foo {
+///   method() {}
+///   static x = 1;
+/// };
+/// babelHelpers.decorate([m], Foo.prototype, "method", null);
+/// babelHelpers.decorate([p], Foo, "x", void 0);
+/// Foo = babelHelpers.decorate([dec], Foo);
+/// ```
+///
+/// References:
+/// * <https://www.typescriptlang.org/docs/handbook/decorators.html>
+/// * <https://babeljs.io/docs/babel-plugin-proposal-decorators#legacy>
+impl<'a> Decorators<'a> {
+    pub(super) fn transform_legacy_class_declaration(
+        &mut self,
+        mut class: Box<'a, Class<'a>>,
+        export: ClassExport,
+        stmts: &mut Vec<'a, Statement<'a>>,
+    ) {
+        let name = class
+            .id
+            .get_or_insert_with(|| {
+                // `export default @dec class {}`
+                let name = self.ctx.scopes().generate_uid("default");
+                self.ctx.add_binding(name.clone());
+                BindingIdentifier::new(SPAN, name)
+            })
+            .name
+            .clone();
+
+        let member_decorates = self.legacy_member_decorates(&mut class, &name);
+        let class_decorators = self.legacy_class_decorators(&mut class);
+        if class_decorators.is_empty() {
+            stmts.push(self.class_statement(class, export));
+            stmts.extend(
+                member_decorates.into_iter().map(|expr| self.ast.expression_statement(SPAN, expr)),
+            );
+            return;
+        }
+
+        // `let Foo = class Foo {}`
+        let class = self.ast.class_expression(class);
+        let binding = self.ast.binding_pattern(
+            self.ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, name.clone())),
+            None,
+            false,
+        );
+        let kind = VariableDeclarationKind::Let;
+        let declarator = self.ast.variable_declarator(SPAN, kind, binding, Some(class), false);
+        let decl = self.ast.variable_declaration(
+            SPAN,
+            kind,
+            self.ast.new_vec_single(declarator),
+            Modifiers::empty(),
+        );
+        stmts.push(Statement::Declaration(Declaration::VariableDeclaration(decl)));
+        for expr in member_decorates {
+            stmts.push(self.ast.expression_statement(SPAN, expr));
+        }
+
+        // `Foo = babelHelpers.decorate([dec], Foo)`
+        let decorate = self.legacy_decorate(class_decorators, self.identifier(&name), None);
+        let assignment = self.assign(IdentifierReference::new(SPAN, name.clone()), decorate);
+        stmts.push(self.ast.expression_statement(SPAN, assignment));
+
+        match export {
+            ClassExport::None => {}
+            ClassExport::Named => stmts.push(self.export_specifier(name.clone(), name)),
+            ClassExport::Default => stmts.push(self.export_specifier(name, "default".into())),
+        }
+    }
+
+    /// ```JavaScript
+    /// let Foo = @dec class { @m method() {} };
+    /// ```
+    /// ```JavaScript
+    /// var _Class;
+    /// let Foo = (_Class = class { method() {} }, babelHelpers.decorate([m], _Class.prototype, "method", null), _Class = babelHelpers.decorate([dec], _Class));
+    /// ```
+    pub(super) fn transform_legacy_class_expression(
+        &mut self,
+        expr: &mut Expression<'a>,
+    ) -> Expression<'a> {
+        let Expression::ClassExpression(class) = expr else { unreachable!() };
+        let name = class.id.as_ref().map_or_else(|| "Class".into(), |id| id.name.clone());
+        let binding = self.generate_uid(&name);
+        let member_decorates = self.legacy_member_decorates(class, &binding);
+        let class_decorators = self.legacy_class_decorators(class);
+        let class = self.ast.move_expression(expr);
+
+        // `babelHelpers.decorate([dec], class {})`
+        if member_decorates.is_empty() {
+            return self.legacy_decorate(class_decorators, class, None);
+        }
+
+        let ident = self.declare_var(binding.clone());
+        let mut exprs = self.ast.new_vec();
+        exprs.push(self.assign(ident.clone(), class));
+        exprs.extend(member_decorates);
+        if class_decorators.is_empty() {
+            exprs.push(self.identifier(&binding));
+        } else {
+            let decorate = self.legacy_decorate(class_decorators, self.identifier(&binding), None);
+            exprs.push(self.assign(ident, decorate));
+        }
+        self.sequence(exprs)
+    }
+
+    /// The decorators of the class with the decorators of the constructor parameters and the
+    /// metadata of the class, removed from the class.
+    fn legacy_class_decorators(&mut self, class: &mut Class<'a>) -> std::vec::Vec<Expression<'a>> {
+        let mut decorators = mem::replace(&mut class.decorators, self.ast.new_vec())
+            .into_iter()
+            .map(|decorator| decorator.expression)
+            .collect::<std::vec::Vec<_>>();
+        let constructor = class.body.body.iter_mut().find_map(|element| match element {
+            ClassElement::MethodDefinition(def)
+                if def.kind == MethodDefinitionKind::Constructor =>
+            {
+                Some(def)
+            }
+            _ => None,
+        });
+        if let Some(constructor) = constructor {
+            decorators.extend(self.parameter_decorators(&mut constructor.value.params));
+        }
+        if !decorators.is_empty() {
+            decorators.extend(self.class_metadata(class));
+        }
+        decorators
+    }
+
+    /// The `babelHelpers.decorate` calls of the decorated elements of the class named `name`
+    fn legacy_member_decorates(
+        &mut self,
+        class: &mut Class<'a>,
+        name: &Atom,
+    ) -> std::vec::Vec<Expression<'a>> {
+        let mut decorates = vec![];
+        for element in class.body.body.iter_mut() {
+            let (decorators, span, r#static, is_field, key) = match element {
+                ClassElement::MethodDefinition(def)
+                    if def.kind != MethodDefinitionKind::Constructor =>
+                {
+                    let mut decorators = mem::replace(&mut def.decorators, self.ast.new_vec())
+                        .into_iter()
+                        .map(|decorator| decorator.expression)
+                        .collect::<std::vec::Vec<_>>();
+                    decorators.extend(self.parameter_decorators(&mut def.value.params));
+                    if decorators.is_empty() {
+                        continue;
+                    }
+                    decorators.extend(self.method_metadata(def));
+                    (decorators, def.span, def.r#static, false, &mut def.key)
+                }
+                ClassElement::PropertyDefinition(def) if !def.decorators.is_empty() => {
+                    let mut decorators = mem::replace(&mut def.decorators, self.ast.new_vec())
+                        .into_iter()
+                        .map(|decorator| decorator.expression)
+                        .collect::<std::vec::Vec<_>>();
+                    decorators.extend(self.property_metadata(def.type_annotation.as_deref()));
+                    (decorators, def.span, def.r#static, true, &mut def.key)
+                }
+                ClassElement::AccessorProperty(def) if !def.decorators.is_empty() => {
+                    let decorators = mem::replace(&mut def.decorators, self.ast.new_vec())
+                        .into_iter()
+                        .map(|decorator| decorator.expression)
+                        .collect::<std::vec::Vec<_>>();
+                    (decorators, def.span, def.r#static, false, &mut def.key)
+                }
+                _ => continue,
+            };
+            let Some(key) = self.decorated_key(key, span) else { continue };
+
+            // `Foo.prototype` or `Foo`
+            let target = self.identifier(name);
+            let target = if r#static { target } else { self.member(target, "prototype") };
+            // Fields are defined on the instances, so there is no descriptor on the target
+            let descriptor = if is_field {
+                self.ast.void_0()
+            } else {
+                self.ast.literal_null_expression(NullLiteral::new(SPAN))
+            };
+            decorates.push(self.legacy_decorate(decorators, target, Some((key, descriptor))));
+        }
+        decorates
+    }
+
+    /// `@dec x` -> `babelHelpers.decorateParam(0, dec)`
+    #[allow(clippy::cast_precision_loss)]
+    fn parameter_decorators(
+        &self,
+        params: &mut FormalParameters<'a>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        let mut decorators = vec![];
+        for (index, param) in params.items.iter_mut().enumerate() {
+            for decorator in mem::replace(&mut param.decorators, self.ast.new_vec()) {
+                let index = self.ast.literal_number_expression(NumberLiteral::new(
+                    SPAN,
+                    index as f64,
+                    self.ast.new_str(&index.to_string()),
+                    NumberBase::Decimal,
+                ));
+                let callee = self.helper("decorateParam");
+                decorators.push(self.call(callee, vec![index, decorator.expression]));
+            }
+        }
+        decorators
+    }
+
+    /// `babelHelpers.decorate([decorators], target, key, descriptor)`
+    fn legacy_decorate(
+        &self,
+        decorators: std::vec::Vec<Expression<'a>>,
+        target: Expression<'a>,
+        member: Option<(Expression<'a>, Expression<'a>)>,
+    ) -> Expression<'a> {
+        let mut args = vec![self.array(decorators), target];
+        if let Some((key, descriptor)) = member {
+            args.push(key);
+            args.push(descriptor);
+        }
+        self.call(self.helper("decorate"), args)
+    }
+}
@@ -0,0 +1,196 @@
+use oxc_ast::ast::*;
+use oxc_semantic::SymbolFlags;
+use oxc_span::SPAN;
+use oxc_syntax::operator::{BinaryOperator, UnaryOperator};
+
+use super::Decorators;
+
+/// Emit the design-time types of decorated declarations, like TypeScript's `emitDecoratorMetadata`
+///
+/// ```TypeScript
+/// class Foo {
+///   @dec method(x: string): number {}
+/// }
+/// ```
+/// ```JavaScript
+/// babelHelpers.decorate([dec, babelHelpers.decorateMetadata("design:type", Function), babelHelpers.decorateMetadata("design:paramtypes", [String]), babelHelpers.decorateMetadata("design:returntype", Number)], Foo.prototype, "method", null);
+/// ```
+///
+/// References:
+/// * <https://github.com/microsoft/TypeScript/blob/main/src/compiler/transformers/ts.ts>
+/// * <https://github.com/leonardfactory/babel-plugin-transform-typescript-metadata>
+impl<'a> Decorators<'a> {
+    pub(super) fn property_metadata(
+        &self,
+        type_annotation: Option<&TSTypeAnnotation<'a>>,
+    ) -> Vec<Expression<'a>> {
+        if !self.options.emit_decorator_metadata {
+            return vec![];
+        }
+        vec![self.metadata("design:type", self.serialize_type(type_annotation))]
+    }
+
+    pub(super) fn method_metadata(&self, def: &MethodDefinition<'a>) -> Vec<Expression<'a>> {
+        if !self.options.emit_decorator_metadata {
+            return vec![];
+        }
+        let func = &def.value;
+        match def.kind {
+            MethodDefinitionKind::Get => {
+                vec![self.metadata("design:type", self.serialize_type(func.return_type.as_deref()))]
+            }
+            MethodDefinitionKind::Set => {
+                let ty =
+                    func.params.items.first().and_then(|p| p.pattern.type_annotation.as_deref());
+                vec![
+                    self.metadata("design:type", self.serialize_type(ty)),
+                    self.metadata("design:paramtypes", self.serialize_parameters(&func.params)),
+                ]
+            }
+            MethodDefinitionKind::Method | MethodDefinitionKind::Constructor => {
+                let return_type = match &func.return_type {
+                    Some(return_type) => self.serialize_type(Some(return_type)),
+                    None if func.r#async => self.identifier(&"Promise".into()),
+                    None => self.ast.void_0(),
+                };
+                vec![
+                    self.metadata("design:type", self.identifier(&"Function".into())),
+                    self.metadata("design:paramtypes", self.serialize_parameters(&func.params)),
+                    self.metadata("design:returntype", return_type),
+                ]
+            }
+        }
+    }
+
+    /// The parameter types of the constructor of a decorated class
+    pub(super) fn class_metadata(&self, class: &Class<'a>) -> Vec<Expression<'a>> {
+        if !self.options.emit_decorator_metadata {
+            return vec![];
+        }
+        class
+            .body
+            .body
+            .iter()
+            .find_map(|element| match element {
+                ClassElement::MethodDefinition(def)
+                    if def.kind == MethodDefinitionKind::Constructor =>
+                {
+                    Some(self.metadata(
+                        "design:paramtypes",
+                        self.serialize_parameters(&def.value.params),
+                    ))
+                }
+                _ => None,
+            })
+            .into_iter()
+            .collect()
+    }
+
+    /// `babelHelpers.decorateMetadata(key, value)`
+    fn metadata(&self, key: &str, value: Expression<'a>) -> Expression<'a> {
+        self.call(self.helper("decorateMetadata"), vec![self.string(key), value])
+    }
+
+    fn serialize_parameters(&self, params: &FormalParameters<'a>) -> Expression<'a> {
+        let mut types = params
+            .items
+            .iter()
+            .map(|param| self.serialize_type(param.pattern.type_annotation.as_deref()))
+            .collect::<Vec<_>>();
+        if let Some(rest) = &params.rest {
+            // The rest parameter is serialized with the type of its elements
+            let ty = rest.argument.type_annotation.as_deref().map(|ty| match &ty.type_annotation {
+                TSType::TSArrayType(array) => self.serialize_type_node(&array.element_type),
+                _ => self.identifier(&"Object".into()),
+            });
+            types.push(ty.unwrap_or_else(|| self.identifier(&"Object".into())));
+        }
+        self.array(types)
+    }
+
+    fn serialize_type(&self, type_annotation: Option<&TSTypeAnnotation<'a>>) -> Expression<'a> {
+        type_annotation.map_or_else(
+            || self.identifier(&"Object".into()),
+            |ty| self.serialize_type_node(&ty.type_annotation),
+        )
+    }
+
+    fn serialize_type_node(&self, ty: &TSType<'a>) -> Expression<'a> {
+        let name = match ty {
+            TSType::TSNumberKeyword(_) => "Number",
+            TSType::TSStringKeyword(_) | TSType::TSTemplateLiteralType(_) => "String",
+            TSType::TSBooleanKeyword(_) => "Boolean",
+            TSType::TSBigIntKeyword(_) => "BigInt",
+            TSType::TSSymbolKeyword(_) => "Symbol",
+            TSType::TSVoidKeyword(_)
+            | TSType::TSUndefinedKeyword(_)
+            | TSType::TSNullKeyword(_)
+            | TSType::TSNeverKeyword(_) => return self.ast.void_0(),
+            TSType::TSArrayType(_) | TSType::TSTupleType(_) => "Array",
+            TSType::TSFunctionType(_) | TSType::TSConstructorType(_) => "Function",
+            TSType::TSLiteralType(ty) => match &ty.literal {
+                TSLiteral::BooleanLiteral(_) => "Boolean",
+                TSLiteral::NumberLiteral(_) | TSLiteral::UnaryExpression(_) => "Number",
+                TSLiteral::BigintLiteral(_) => "BigInt",
+                TSLiteral::StringLiteral(_) | TSLiteral::TemplateLiteral(_) => "String",
+                TSLiteral::NullLiteral(_) => return self.ast.void_0(),
+                TSLiteral::RegExpLiteral(_) => "Object",
+            },
+            TSType::TSTypeReference(reference) => return self.serialize_type_reference(reference),
+            _ => "Object",
+        };
+        self.identifier(&name.into())
+    }
+
+    /// * A class or enum declared in the file is referenced directly.
+    /// * A type-only declaration is serialized as `Object`.
+    /// * Other names may not exist at runtime: `typeof Foo === "undefined" ? Object : Foo`.
+    fn serialize_type_reference(&self, reference: &TSTypeReference<'a>) -> Expression<'a> {
+        let type_name = &reference.type_name;
+        let ident = TSTypeName::get_first_name(type_name);
+        let symbol_id = ident
+            .reference_id
+            .get()
+            .and_then(|reference_id| self.ctx.symbols().get_reference(reference_id).symbol_id());
+        let flag = symbol_id.map(|symbol_id| self.ctx.symbols().get_flag(symbol_id));
+        match flag {
+            Some(flag)
+                if flag.intersects(SymbolFlags::Value)
+                    && !flag.intersects(SymbolFlags::Ambient | SymbolFlags::ImportBinding) =>
+            {
+                self.type_name_expression(type_name)
+            }
+            Some(flag) if !flag.intersects(SymbolFlags::Value | SymbolFlags::ImportBinding) => {
+                self.identifier(&"Object".into())
+            }
+            _ => {
+                let test = self.ast.binary_expression(
+                    SPAN,
+                    self.ast.unary_expression(
+                        SPAN,
+                        UnaryOperator::Typeof,
+                        self.type_name_expression(type_name),
+                    ),
+                    BinaryOperator::StrictEquality,
+                    self.string("undefined"),
+                );
+                self.ast.conditional_expression(
+                    SPAN,
+                    test,
+                    self.identifier(&"Object".into()),
+                    self.type_name_expression(type_name),
+                )
+            }
+        }
+    }
+
+    /// `A.B.C`
+    fn type_name_expression(&self, type_name: &TSTypeName<'a>) -> Expression<'a> {
+        match type_name {
+            TSTypeName::IdentifierReference(ident) => self.identifier(&ident.name),
+            TSTypeName::QualifiedName(name) => {
+                self.member(self.type_name_expression(&name.left), &name.right.name)
+            }
+        }
+    }
+}
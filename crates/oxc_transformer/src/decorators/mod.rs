@@ -0,0 +1,968 @@
+mod legacy;
+mod metadata;
+mod options;
+
+use std::{mem, rc::Rc};
+
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::Error,
+};
+use oxc_semantic::SymbolId;
+use oxc_span::{Atom, Span, SPAN};
+use oxc_syntax::{operator::AssignmentOperator, NumberBase};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+pub use self::options::{DecoratorsOptions, DecoratorsVersion};
+use crate::{
+    context::TransformerCtx,
+    options::TransformOptions,
+    utils::{insert_constructor_inits, statement_class, CreateVars},
+};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Decorators on private elements are not supported.")]
+#[diagnostic(help("Remove the decorator or make the element public."))]
+struct PrivateElementDecorator(#[label] Span);
+
+/// The kinds of the decorated elements passed to `applyDecs2305`
+const FIELD: u8 = 0;
+const ACCESSOR: u8 = 1;
+const METHOD: u8 = 2;
+const GETTER: u8 = 3;
+const SETTER: u8 = 4;
+/// Added to the kind of static elements
+const STATIC: u8 = 5;
+
+/// Proposal: Decorators
+///
+/// With the `2023-05` version, the decorators are applied by a static block running first in the
+/// class, and auto-accessors are lowered to a getter and setter of a private field.
+///
+/// ```JavaScript
+/// @dec
+/// class Foo {
+///   @f x = 1;
+///   @m method() {}
+///   accessor y = 2;
+/// }
+/// ```
+/// ```JavaScript
+/// var _Foo, _init_x, _initProto, _initClass;
+/// class Foo {
+///   static { ({ e: [_init_x, _initProto], c: [_Foo, _initClass] } = babelHelpers.applyDecs2305(this, [[f, 0, "x"], [m, 2, "method"]], [dec])); }
+///   constructor() { _initProto(this); }
+///   x = _init_x(this, 1);
+///   method() {}
+///   #y = 2;
+///   get y() { return this.#y; }
+///   set y(v) { this.#y = v; }
+///   static { _initClass(); }
+/// }
+/// ```
+///
+/// The references to a decorated class are renamed to the binding of the decorated class.
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-proposal-decorators>
+/// * <https://github.com/babel/babel/tree/main/packages/babel-plugin-proposal-decorators>
+/// * <https://github.com/tc39/proposal-decorators>
+/// * <https://www.typescriptlang.org/docs/handbook/decorators.html>
+pub struct Decorators<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+    options: DecoratorsOptions,
+
+    vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
+}
+
+impl<'a> CreateVars<'a> for Decorators<'a> {
+    fn ctx(&self) -> &TransformerCtx<'a> {
+        &self.ctx
+    }
+
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>> {
+        &mut self.vars
+    }
+}
+
+/// How a class declaration is exported
+#[derive(Debug, Clone, Copy)]
+enum ClassExport {
+    None,
+    Named,
+    Default,
+}
+
+impl<'a> Decorators<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        options.decorators.map(|options| Self { ast, ctx, options, vars: vec![] })
+    }
+
+    /// Transform the decorated class declarations of `stmts`
+    pub fn transform_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if !stmts.iter().any(|stmt| statement_class(stmt).is_some_and(|c| self.needs_transform(c)))
+        {
+            return;
+        }
+
+        let mut renamer = ClassBindingRenamer {
+            ctx: &self.ctx.clone(),
+            symbols: FxHashMap::default(),
+            exports: FxHashMap::default(),
+        };
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for stmt in old_stmts {
+            if !statement_class(&stmt).is_some_and(|class| self.needs_transform(class)) {
+                stmts.push(stmt);
+                continue;
+            }
+            let (class, export) = split_class_statement(stmt);
+            match self.options.version {
+                DecoratorsVersion::Legacy => {
+                    self.transform_legacy_class_declaration(class, export, stmts);
+                }
+                DecoratorsVersion::V2023_05 => {
+                    self.transform_class_declaration(class, export, stmts, &mut renamer);
+                }
+            }
+        }
+        if !renamer.symbols.is_empty() {
+            for stmt in stmts.iter_mut() {
+                renamer.visit_statement(stmt);
+            }
+        }
+    }
+
+    /// Transform a decorated class expression
+    ///
+    /// ```JavaScript
+    /// let Foo = @dec class {};
+    /// ```
+    /// ```JavaScript
+    /// var _Class, _initClass;
+    /// let Foo = (class { static { ({ c: [_Class, _initClass] } = babelHelpers.applyDecs2305(this, [], [dec])); } static { _initClass(); } }, _Class);
+    /// ```
+    pub fn transform_expression(&mut self, expr: &mut Expression<'a>) {
+        let Expression::ClassExpression(class) = expr else { return };
+        if !self.needs_transform(class) {
+            return;
+        }
+        if self.options.version == DecoratorsVersion::Legacy {
+            *expr = self.transform_legacy_class_expression(expr);
+            return;
+        }
+
+        let binding = (!class.decorators.is_empty()).then(|| {
+            let name = class.id.as_ref().map_or_else(|| "Class".into(), |id| id.name.clone());
+            let binding = self.generate_uid(&name);
+            self.declare_var(binding.clone());
+            binding
+        });
+        if let (Some(binding), Some(symbol_id)) =
+            (&binding, class.id.as_ref().and_then(|id| id.symbol_id.get()))
+        {
+            // The name of the class expression refers to the decorated class
+            let mut renamer = ClassBindingRenamer {
+                ctx: &self.ctx.clone(),
+                symbols: FxHashMap::from_iter([(symbol_id, binding.clone())]),
+                exports: FxHashMap::default(),
+            };
+            renamer.visit_class_body(&mut class.body);
+        }
+        let mut exprs = self.ast.new_vec();
+        exprs.extend(self.transform_class(class, binding.as_ref()));
+        exprs.push(self.ast.move_expression(expr));
+        if let Some(binding) = &binding {
+            exprs.push(self.identifier(binding));
+        }
+        *expr = self.sequence(exprs);
+    }
+
+    /// Whether the class has decorators or auto-accessors to transform
+    fn needs_transform(&self, class: &Class<'a>) -> bool {
+        !class.decorators.is_empty()
+            || class.body.body.iter().any(|element| match element {
+                ClassElement::MethodDefinition(def) => {
+                    !def.decorators.is_empty()
+                        || def.value.params.items.iter().any(|param| !param.decorators.is_empty())
+                }
+                ClassElement::PropertyDefinition(def) => !def.decorators.is_empty(),
+                ClassElement::AccessorProperty(def) => {
+                    !def.decorators.is_empty()
+                        || self.options.version == DecoratorsVersion::V2023_05
+                }
+                _ => false,
+            })
+    }
+
+    /// ```JavaScript
+    /// @dec export class Foo {}
+    /// ```
+    /// ```JavaScript
+    /// var _Foo, _initClass;
+    /// class Foo { static { ({ c: [_Foo, _initClass] } = babelHelpers.applyDecs2305(this, [], [dec])); } static { _initClass(); } }
+    /// export { _Foo as Foo };
+    /// ```
+    fn transform_class_declaration(
+        &mut self,
+        mut class: oxc_allocator::Box<'a, Class<'a>>,
+        export: ClassExport,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        renamer: &mut ClassBindingRenamer<'a, '_>,
+    ) {
+        let id = class.id.get_or_insert_with(|| {
+            // `export default @dec class {}`
+            let name = self.ctx.scopes().generate_uid("default");
+            self.ctx.add_binding(name.clone());
+            BindingIdentifier::new(SPAN, name)
+        });
+        let (name, symbol_id) = (id.name.clone(), id.symbol_id.get());
+
+        let binding = (!class.decorators.is_empty()).then(|| {
+            let binding = self.generate_uid(&name);
+            self.declare_var(binding.clone());
+            binding
+        });
+        for expr in self.transform_class(&mut class, binding.as_ref()) {
+            stmts.push(self.ast.expression_statement(SPAN, expr));
+        }
+
+        let Some(binding) = binding else {
+            stmts.push(self.class_statement(class, export));
+            return;
+        };
+        if let Some(symbol_id) = symbol_id {
+            renamer.symbols.insert(symbol_id, binding.clone());
+            if self.ctx.symbols().get_scope_id(symbol_id) == self.ctx.scopes().root_scope_id() {
+                renamer.exports.insert(name.clone(), binding.clone());
+            }
+        }
+        stmts.push(self.class_statement(class, ClassExport::None));
+        match export {
+            ClassExport::None => {}
+            ClassExport::Named => stmts.push(self.export_specifier(binding, name)),
+            ClassExport::Default => stmts.push(self.export_specifier(binding, "default".into())),
+        }
+    }
+
+    /// Lower the decorators and auto-accessors of `class`.
+    ///
+    /// `binding` is assigned the decorated class when the class has decorators.
+    /// Returns the assignments evaluating the decorators before the class.
+    fn transform_class(
+        &mut self,
+        class: &mut Class<'a>,
+        binding: Option<&Atom>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        let mut before = vec![];
+        let class_decorators = mem::replace(&mut class.decorators, self.ast.new_vec());
+        let class_decs = self.decorator_expressions(class_decorators, &mut before);
+
+        let mut private_names: FxHashSet<Atom> = class
+            .body
+            .body
+            .iter()
+            .filter_map(ClassElement::property_key)
+            .filter_map(PropertyKey::private_name)
+            .collect();
+        let mut member_decs = vec![];
+        // The variables assigned the initializers of the elements
+        let mut inits = vec![];
+        let mut has_proto_decorators = false;
+        let mut has_static_decorators = false;
+
+        let old_elements = mem::replace(&mut class.body.body, self.ast.new_vec());
+        for element in old_elements {
+            match element {
+                ClassElement::MethodDefinition(mut def) if !def.decorators.is_empty() => {
+                    let decorators = mem::replace(&mut def.decorators, self.ast.new_vec());
+                    let span = def.span;
+                    let decorators = self.decorator_expressions(decorators, &mut before);
+                    if let Some(name) = self.decorated_key(&mut def.key, span) {
+                        let kind = match def.kind {
+                            MethodDefinitionKind::Get => GETTER,
+                            MethodDefinitionKind::Set => SETTER,
+                            _ => METHOD,
+                        };
+                        member_decs.push(self.member_decorators(
+                            decorators,
+                            kind,
+                            def.r#static,
+                            name,
+                        ));
+                        if def.r#static {
+                            has_static_decorators = true;
+                        } else {
+                            has_proto_decorators = true;
+                        }
+                    }
+                    class.body.body.push(ClassElement::MethodDefinition(def));
+                }
+                // `@dec x = 1` -> `x = _init_x(this, 1)`
+                ClassElement::PropertyDefinition(mut def) if !def.decorators.is_empty() => {
+                    let decorators = mem::replace(&mut def.decorators, self.ast.new_vec());
+                    let span = def.span;
+                    let decorators = self.decorator_expressions(decorators, &mut before);
+                    let base_name = key_base_name(&def.key);
+                    if let Some(name) = self.decorated_key(&mut def.key, span) {
+                        let init = self.generate_uid(&format!("init_{base_name}"));
+                        self.declare_var(init.clone());
+                        def.value = Some(self.call_init(&init, def.value.take()));
+                        inits.push(init);
+                        member_decs.push(self.member_decorators(
+                            decorators,
+                            FIELD,
+                            def.r#static,
+                            name,
+                        ));
+                    }
+                    class.body.body.push(ClassElement::PropertyDefinition(def));
+                }
+                ClassElement::AccessorProperty(accessor) => {
+                    let mut accessor = accessor.unbox();
+                    let decorators = mem::replace(&mut accessor.decorators, self.ast.new_vec());
+                    let storage =
+                        unique_private_name(&mut private_names, &key_base_name(&accessor.key));
+                    let mut value = accessor.value.take();
+                    if !decorators.is_empty() {
+                        let decorators = self.decorator_expressions(decorators, &mut before);
+                        let base_name = key_base_name(&accessor.key);
+                        if let Some(name) = self.decorated_key(&mut accessor.key, accessor.span) {
+                            let init = self.generate_uid(&format!("init_{base_name}"));
+                            self.declare_var(init.clone());
+                            value = Some(self.call_init(&init, value));
+                            inits.push(init);
+                            member_decs.push(self.member_decorators(
+                                decorators,
+                                ACCESSOR,
+                                accessor.r#static,
+                                name,
+                            ));
+                        }
+                    }
+                    self.lower_accessor(accessor, storage, value, &mut class.body.body);
+                }
+                element => class.body.body.push(element),
+            }
+        }
+
+        if has_proto_decorators {
+            let init_proto = self.generate_uid("initProto");
+            self.declare_var(init_proto.clone());
+            let init = self.call_init(&init_proto, None);
+            insert_constructor_inits(&self.ctx, class, vec![init]);
+            inits.push(init_proto);
+        }
+        let init_static = has_static_decorators.then(|| {
+            let init_static = self.generate_uid("initStatic");
+            self.declare_var(init_static.clone());
+            inits.push(init_static.clone());
+            init_static
+        });
+        let init_class = binding.map(|_| {
+            let init_class = self.generate_uid("initClass");
+            self.declare_var(init_class.clone());
+            init_class
+        });
+        if member_decs.is_empty() && class_decs.is_empty() {
+            return before;
+        }
+
+        // ({ e: [..inits], c: [binding, _initClass] } = babelHelpers.applyDecs2305(this, memberDecs, classDecs))
+        let mut args = vec![self.ast.this_expression(SPAN), self.array(member_decs)];
+        if !class_decs.is_empty() {
+            args.push(self.array(class_decs));
+        }
+        let apply = self.call(self.helper("applyDecs2305"), args);
+        let mut targets = vec![];
+        if !inits.is_empty() {
+            targets.push(("e", inits));
+        }
+        if let (Some(binding), Some(init_class)) = (binding, &init_class) {
+            targets.push(("c", vec![binding.clone(), init_class.clone()]));
+        }
+        let assignment = self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            self.destructuring_target(targets),
+            apply,
+        );
+        let mut statements = self.ast.new_vec_single(
+            self.ast
+                .expression_statement(SPAN, self.ast.parenthesized_expression(SPAN, assignment)),
+        );
+        if let Some(init_static) = &init_static {
+            let init = self.call_init(init_static, None);
+            statements.push(self.ast.expression_statement(SPAN, init));
+        }
+        class.body.body.insert(0, self.ast.static_block(SPAN, statements));
+
+        // The class is initialized after its static elements
+        if let Some(init_class) = &init_class {
+            let init = self.call(self.identifier(init_class), vec![]);
+            let statements = self.ast.new_vec_single(self.ast.expression_statement(SPAN, init));
+            class.body.body.push(self.ast.static_block(SPAN, statements));
+        }
+        before
+    }
+
+    /// `accessor x = 1` -> `#x = 1; get x() { return this.#x; } set x(v) { this.#x = v; }`
+    fn lower_accessor(
+        &mut self,
+        mut accessor: AccessorProperty<'a>,
+        storage: Atom,
+        value: Option<Expression<'a>>,
+        elements: &mut Vec<'a, ClassElement<'a>>,
+    ) {
+        // The computed key is evaluated once by the getter
+        let setter_key = match &mut accessor.key {
+            PropertyKey::Identifier(ident) => self.ast.property_key_identifier((**ident).clone()),
+            PropertyKey::PrivateIdentifier(ident) => {
+                PropertyKey::PrivateIdentifier(self.ast.alloc((**ident).clone()))
+            }
+            PropertyKey::Expression(expr) => {
+                let key = if is_literal(expr) {
+                    self.ast.copy(expr)
+                } else if let Some(key) = memoised_key(expr) {
+                    self.identifier(&key)
+                } else {
+                    self.memoise_key(expr)
+                };
+                self.ast.property_key_expression(key)
+            }
+        };
+
+        let storage_key = PropertyKey::PrivateIdentifier(
+            self.ast.alloc(PrivateIdentifier { span: SPAN, name: storage.clone() }),
+        );
+        elements.push(self.ast.class_property(
+            SPAN,
+            storage_key,
+            value,
+            false,
+            accessor.r#static,
+            self.ast.new_vec(),
+        ));
+
+        let field = self.storage_field(&storage);
+        let body = self.ast.new_vec_single(self.ast.return_statement(SPAN, Some(field)));
+        let getter = self.method(accessor.key, MethodDefinitionKind::Get, None, body);
+        elements.push(self.class_method(getter, accessor.computed, accessor.r#static));
+
+        let target = SimpleAssignmentTarget::MemberAssignmentTarget(self.ast.alloc(
+            MemberExpression::PrivateFieldExpression(PrivateFieldExpression {
+                span: SPAN,
+                object: self.ast.this_expression(SPAN),
+                field: PrivateIdentifier { span: SPAN, name: storage },
+                optional: false,
+            }),
+        ));
+        let assignment = self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            self.identifier(&"v".into()),
+        );
+        let body = self.ast.new_vec_single(self.ast.expression_statement(SPAN, assignment));
+        let setter = self.method(setter_key, MethodDefinitionKind::Set, Some("v".into()), body);
+        elements.push(self.class_method(setter, accessor.computed, accessor.r#static));
+    }
+
+    /// `this.#storage`
+    fn storage_field(&self, storage: &Atom) -> Expression<'a> {
+        self.ast.private_field_expression(
+            SPAN,
+            self.ast.this_expression(SPAN),
+            PrivateIdentifier { span: SPAN, name: storage.clone() },
+            false,
+        )
+    }
+
+    /// A method with an optional parameter
+    fn method(
+        &self,
+        key: PropertyKey<'a>,
+        kind: MethodDefinitionKind,
+        param: Option<Atom>,
+        statements: Vec<'a, Statement<'a>>,
+    ) -> (PropertyKey<'a>, MethodDefinitionKind, oxc_allocator::Box<'a, Function<'a>>) {
+        let mut items = self.ast.new_vec();
+        if let Some(param) = param {
+            let pattern = self.ast.binding_pattern(
+                self.ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, param)),
+                None,
+                false,
+            );
+            items.push(self.ast.formal_parameter(SPAN, pattern, None, false, self.ast.new_vec()));
+        }
+        let params = self.ast.formal_parameters(
+            SPAN,
+            FormalParameterKind::UniqueFormalParameters,
+            items,
+            None,
+        );
+        let body = self.ast.function_body(SPAN, self.ast.new_vec(), statements);
+        let function = self.ast.function(
+            FunctionType::FunctionExpression,
+            SPAN,
+            None,
+            false,
+            false,
+            false,
+            None,
+            params,
+            Some(body),
+            None,
+            None,
+            Modifiers::empty(),
+        );
+        (key, kind, function)
+    }
+
+    fn class_method(
+        &self,
+        (key, kind, value): (
+            PropertyKey<'a>,
+            MethodDefinitionKind,
+            oxc_allocator::Box<'a, Function<'a>>,
+        ),
+        computed: bool,
+        r#static: bool,
+    ) -> ClassElement<'a> {
+        ClassElement::MethodDefinition(self.ast.alloc(MethodDefinition {
+            span: SPAN,
+            key,
+            value,
+            kind,
+            computed,
+            r#static,
+            r#override: false,
+            optional: false,
+            accessibility: None,
+            decorators: self.ast.new_vec(),
+        }))
+    }
+
+    /// `[dec, kind, name]`, or `[[dec1, dec2], kind, name]` for multiple decorators
+    fn member_decorators(
+        &self,
+        mut decorators: std::vec::Vec<Expression<'a>>,
+        kind: u8,
+        r#static: bool,
+        name: Expression<'a>,
+    ) -> Expression<'a> {
+        let decorators =
+            if decorators.len() == 1 { decorators.pop().unwrap() } else { self.array(decorators) };
+        let kind = if r#static { kind + STATIC } else { kind };
+        let kind = self.ast.literal_number_expression(NumberLiteral::new(
+            SPAN,
+            f64::from(kind),
+            self.ast.new_str(&kind.to_string()),
+            NumberBase::Decimal,
+        ));
+        self.array(vec![decorators, kind, name])
+    }
+
+    /// `{ e: [...], c: [...] }` as an assignment target
+    fn destructuring_target(
+        &self,
+        properties: std::vec::Vec<(&str, std::vec::Vec<Atom>)>,
+    ) -> AssignmentTarget<'a> {
+        let mut props = self.ast.new_vec_with_capacity(properties.len());
+        for (name, targets) in properties {
+            let mut elements = self.ast.new_vec_with_capacity(targets.len());
+            for target in targets {
+                let target = self
+                    .ast
+                    .simple_assignment_target_identifier(IdentifierReference::new(SPAN, target));
+                elements.push(Some(AssignmentTargetMaybeDefault::AssignmentTarget(
+                    AssignmentTarget::SimpleAssignmentTarget(target),
+                )));
+            }
+            let array = AssignmentTargetPattern::ArrayAssignmentTarget(self.ast.alloc(
+                ArrayAssignmentTarget { span: SPAN, elements, rest: None, trailing_comma: None },
+            ));
+            let key = self.ast.property_key_identifier(IdentifierName::new(SPAN, name.into()));
+            props.push(AssignmentTargetProperty::AssignmentTargetPropertyProperty(self.ast.alloc(
+                AssignmentTargetPropertyProperty {
+                    span: SPAN,
+                    name: key,
+                    binding: AssignmentTargetMaybeDefault::AssignmentTarget(
+                        AssignmentTarget::AssignmentTargetPattern(array),
+                    ),
+                },
+            )));
+        }
+        AssignmentTarget::AssignmentTargetPattern(AssignmentTargetPattern::ObjectAssignmentTarget(
+            self.ast.alloc(ObjectAssignmentTarget { span: SPAN, properties: props, rest: None }),
+        ))
+    }
+
+    /// `init(this)` or `init(this, value)`
+    fn call_init(&self, init: &Atom, value: Option<Expression<'a>>) -> Expression<'a> {
+        let mut args = vec![self.ast.this_expression(SPAN)];
+        args.extend(value);
+        self.call(self.identifier(init), args)
+    }
+
+    /// The decorator expressions, evaluated in order before the class unless they are identifiers.
+    fn decorator_expressions(
+        &mut self,
+        decorators: Vec<'a, Decorator<'a>>,
+        before: &mut std::vec::Vec<Expression<'a>>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        decorators
+            .into_iter()
+            .map(|decorator| {
+                if matches!(decorator.expression, Expression::Identifier(_)) {
+                    return decorator.expression;
+                }
+                let name = self.generate_uid("dec");
+                let ident = self.declare_var(name.clone());
+                before.push(self.assign(ident, decorator.expression));
+                self.identifier(&name)
+            })
+            .collect()
+    }
+
+    /// The name of a decorated element passed to the decorators, the private elements are an
+    /// error since their decorators cannot be applied without their names
+    fn decorated_key(&mut self, key: &mut PropertyKey<'a>, span: Span) -> Option<Expression<'a>> {
+        match key {
+            PropertyKey::Identifier(ident) => Some(self.string(&ident.name)),
+            PropertyKey::PrivateIdentifier(_) => {
+                self.ctx.error(PrivateElementDecorator(span));
+                None
+            }
+            PropertyKey::Expression(expr) if is_literal(expr) => Some(self.ast.copy(expr)),
+            PropertyKey::Expression(expr) => Some(self.memoise_key(expr)),
+        }
+    }
+
+    /// `[key]` -> `[_computedKey = key]`
+    ///
+    /// Returns the reference to `_computedKey`.
+    fn memoise_key(&mut self, expr: &mut Expression<'a>) -> Expression<'a> {
+        let name = self.generate_uid("computedKey");
+        let ident = self.declare_var(name.clone());
+        let key = self.ast.move_expression(expr);
+        *expr = self.assign(ident, key);
+        self.identifier(&name)
+    }
+
+    fn class_statement(
+        &self,
+        class: oxc_allocator::Box<'a, Class<'a>>,
+        export: ClassExport,
+    ) -> Statement<'a> {
+        let decl = Declaration::ClassDeclaration(class);
+        match export {
+            ClassExport::None => Statement::Declaration(decl),
+            ClassExport::Named => {
+                let decl = self.ast.export_named_declaration(
+                    SPAN,
+                    Some(decl),
+                    self.ast.new_vec(),
+                    None,
+                    ImportOrExportKind::Value,
+                );
+                self.ast.module_declaration(ModuleDeclaration::ExportNamedDeclaration(decl))
+            }
+            ClassExport::Default => {
+                let Declaration::ClassDeclaration(class) = decl else { unreachable!() };
+                let decl = self.ast.export_default_declaration(
+                    SPAN,
+                    ExportDefaultDeclarationKind::ClassDeclaration(class),
+                    ModuleExportName::Identifier(IdentifierName::new(SPAN, "default".into())),
+                );
+                self.ast.module_declaration(ModuleDeclaration::ExportDefaultDeclaration(decl))
+            }
+        }
+    }
+
+    /// `export { local as exported };`
+    fn export_specifier(&self, local: Atom, exported: Atom) -> Statement<'a> {
+        let specifier = ExportSpecifier {
+            span: SPAN,
+            local: ModuleExportName::Identifier(IdentifierName::new(SPAN, local)),
+            exported: ModuleExportName::Identifier(IdentifierName::new(SPAN, exported)),
+            export_kind: ImportOrExportKind::Value,
+        };
+        let decl = self.ast.export_named_declaration(
+            SPAN,
+            None,
+            self.ast.new_vec_single(specifier),
+            None,
+            ImportOrExportKind::Value,
+        );
+        self.ast.module_declaration(ModuleDeclaration::ExportNamedDeclaration(decl))
+    }
+
+    fn generate_uid(&self, name: &str) -> Atom {
+        let name = self.ctx.scopes().generate_uid(name);
+        self.ctx.add_binding(name.clone());
+        name
+    }
+
+    /// `babelHelpers.name`
+    fn helper(&self, name: &str) -> Expression<'a> {
+        self.ctx.helper(name)
+    }
+
+    fn assign(&self, ident: IdentifierReference, value: Expression<'a>) -> Expression<'a> {
+        let target = self.ast.simple_assignment_target_identifier(ident);
+        self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            value,
+        )
+    }
+
+    /// `(a, b)`, or `a` when there is a single expression
+    fn sequence(&self, mut exprs: Vec<'a, Expression<'a>>) -> Expression<'a> {
+        if exprs.len() == 1 {
+            return exprs.pop().unwrap();
+        }
+        let sequence = self.ast.sequence_expression(SPAN, exprs);
+        self.ast.parenthesized_expression(SPAN, sequence)
+    }
+
+    fn identifier(&self, name: &Atom) -> Expression<'a> {
+        self.ast.identifier_reference_expression(IdentifierReference::new(SPAN, name.clone()))
+    }
+
+    fn string(&self, value: &str) -> Expression<'a> {
+        self.ast.literal_string_expression(StringLiteral::new(SPAN, value.into()))
+    }
+
+    fn member(&self, object: Expression<'a>, property: &str) -> Expression<'a> {
+        self.ast.static_member_expression(
+            SPAN,
+            object,
+            IdentifierName::new(SPAN, property.into()),
+            false,
+        )
+    }
+
+    fn call(&self, callee: Expression<'a>, args: std::vec::Vec<Expression<'a>>) -> Expression<'a> {
+        let mut arguments = self.ast.new_vec_with_capacity(args.len());
+        arguments.extend(args.into_iter().map(Argument::Expression));
+        self.ast.call_expression(SPAN, callee, arguments, false, None)
+    }
+
+    fn array(&self, elements: std::vec::Vec<Expression<'a>>) -> Expression<'a> {
+        let mut array = self.ast.new_vec_with_capacity(elements.len());
+        array.extend(elements.into_iter().map(ArrayExpressionElement::Expression));
+        self.ast.array_expression(SPAN, array, None)
+    }
+}
+
+/// Move the class out of a class declaration statement checked by [`statement_class`]
+fn split_class_statement(stmt: Statement<'_>) -> (oxc_allocator::Box<'_, Class<'_>>, ClassExport) {
+    let class = match stmt {
+        Statement::Declaration(Declaration::ClassDeclaration(class)) => {
+            return (class, ClassExport::None);
+        }
+        Statement::ModuleDeclaration(decl) => match decl.unbox() {
+            ModuleDeclaration::ExportNamedDeclaration(decl) => match decl.unbox().declaration {
+                Some(Declaration::ClassDeclaration(class)) => return (class, ClassExport::Named),
+                _ => None,
+            },
+            ModuleDeclaration::ExportDefaultDeclaration(decl) => match decl.unbox().declaration {
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => Some(class),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+    (class.expect("the statement declares a class"), ClassExport::Default)
+}
+
+/// The base of the names generated for an element
+fn key_base_name(key: &PropertyKey) -> Atom {
+    match key {
+        PropertyKey::Identifier(ident) => ident.name.clone(),
+        PropertyKey::PrivateIdentifier(ident) => ident.name.clone(),
+        PropertyKey::Expression(_) => "computedKey".into(),
+    }
+}
+
+/// The variable of a key memoised by [`Decorators::memoise_key`]
+fn memoised_key(expr: &Expression) -> Option<Atom> {
+    let Expression::AssignmentExpression(assignment) = expr else { return None };
+    match &assignment.left {
+        AssignmentTarget::SimpleAssignmentTarget(
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident),
+        ) => Some(ident.name.clone()),
+        _ => None,
+    }
+}
+
+/// `name`, or `name2`, `name3`... when the class already has the private name
+fn unique_private_name(names: &mut FxHashSet<Atom>, base: &str) -> Atom {
+    let mut name = Atom::from(base);
+    let mut suffix = 2;
+    while names.contains(&name) {
+        name = format!("{base}{suffix}").into();
+        suffix += 1;
+    }
+    names.insert(name.clone());
+    name
+}
+
+fn is_literal(expr: &Expression) -> bool {
+    matches!(expr, Expression::StringLiteral(_) | Expression::NumberLiteral(_))
+}
+
+/// Rename the references to decorated classes to the bindings of the decorated classes
+struct ClassBindingRenamer<'a, 'b> {
+    ctx: &'b TransformerCtx<'a>,
+    symbols: FxHashMap<SymbolId, Atom>,
+    /// The renamed bindings of the program scope, which are also renamed in `export { Foo }`
+    exports: FxHashMap<Atom, Atom>,
+}
+
+impl<'a, 'b> VisitMut<'a> for ClassBindingRenamer<'a, 'b> {
+    fn visit_identifier_reference(&mut self, ident: &mut IdentifierReference) {
+        let symbol_id = ident
+            .reference_id
+            .get()
+            .and_then(|id| self.ctx.symbols().get_reference(id).symbol_id());
+        if let Some(name) = symbol_id.and_then(|symbol_id| self.symbols.get(&symbol_id)) {
+            ident.name = name.clone();
+        }
+    }
+
+    fn visit_object_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        // `{ Foo }` -> `{ Foo: _Foo }`
+        if prop.shorthand
+            && matches!(&prop.value, Expression::Identifier(ident) if !prop.key.is_specific_static_name(&ident.name))
+        {
+            prop.shorthand = false;
+        }
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &mut ExportNamedDeclaration<'a>) {
+        if decl.source.is_none() {
+            for specifier in decl.specifiers.iter_mut() {
+                let ModuleExportName::Identifier(local) = &mut specifier.local else { continue };
+                if let Some(name) = self.exports.get(&local.name) {
+                    local.name = name.clone();
+                }
+            }
+        }
+        if let Some(decl) = &mut decl.declaration {
+            self.visit_declaration(decl);
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = TransformOptions {
+        decorators: Some(DecoratorsOptions::default()),
+        ..TransformOptions::default()
+    };
+
+    let tests = &[
+        (
+            "class A { @f x = 1; @m static method() {} @a accessor y; }",
+            r#"var _init_x, _init_y, _initStatic;
+            class A {
+              static { ({ e: [_init_x, _init_y, _initStatic] } = babelHelpers.applyDecs2305(this, [[f, 0, "x"], [m, 7, "method"], [a, 1, "y"]])); _initStatic(this); }
+              x = _init_x(this, 1);
+              static method() {}
+              #y = _init_y(this);
+              get y() { return this.#y; }
+              set y(v) { this.#y = v; }
+            }"#,
+        ),
+        (
+            "@dec(1) export class A { @m method() { return A; } } new A();",
+            r#"var _A, _dec, _initProto, _initClass;
+            _dec = dec(1);
+            class A {
+              static { ({ e: [_initProto], c: [_A, _initClass] } = babelHelpers.applyDecs2305(this, [[m, 2, "method"]], [_dec])); }
+              constructor() { _initProto(this); }
+              method() { return _A; }
+              static { _initClass(); }
+            }
+            export { _A as A };
+            new _A();"#,
+        ),
+        (
+            "let B = @dec class { @a @b [k()] = 1; accessor #x = 2; };",
+            r"var _Class, _computedKey, _init_computedKey, _initClass;
+            let B = (class {
+              static { ({ e: [_init_computedKey], c: [_Class, _initClass] } = babelHelpers.applyDecs2305(this, [[[a, b], 0, _computedKey]], [dec])); }
+              [_computedKey = k()] = _init_computedKey(this, 1);
+              #x2 = 2;
+              get #x() { return this.#x2; }
+              set #x(v) { this.#x2 = v; }
+              static { _initClass(); }
+            }, _Class);",
+        ),
+    ];
+
+    let tester = Tester::new("test.js", options);
+    tester.test(tests);
+    tester.test_error(
+        "class A { @dec #x = 1; @dec #m() {} }",
+        "Decorators on private elements are not supported.",
+    );
+
+    let legacy_options = |emit_decorator_metadata| TransformOptions {
+        decorators: Some(DecoratorsOptions {
+            version: DecoratorsVersion::Legacy,
+            emit_decorator_metadata,
+        }),
+        ..TransformOptions::default()
+    };
+
+    let legacy_tests = &[
+        (
+            "@dec export class A { @m method(@p x) {} @f static x = 1; }",
+            r#"let A = class A { method(x) {} static x = 1; };
+            babelHelpers.decorate([m, babelHelpers.decorateParam(0, p)], A.prototype, "method", null);
+            babelHelpers.decorate([f], A, "x", void 0);
+            A = babelHelpers.decorate([dec], A);
+            export { A };"#,
+        ),
+        (
+            "class A { @f [k()] = 1; }",
+            r"var _computedKey;
+            class A { [_computedKey = k()] = 1; }
+            babelHelpers.decorate([f], A.prototype, _computedKey, void 0);",
+        ),
+        (
+            "let B = @dec class { @m method() {} }; let C = @dec class {};",
+            r#"var _Class;
+            let B = (_Class = class { method() {} }, babelHelpers.decorate([m], _Class.prototype, "method", null), _Class = babelHelpers.decorate([dec], _Class));
+            let C = babelHelpers.decorate([dec], class {});"#,
+        ),
+    ];
+    Tester::new("test.js", legacy_options(false)).test(legacy_tests);
+
+    let metadata_tests = &[(
+        "interface I {} class Service {} @dec class A { constructor(s: Service, d: Dep, i: I) {} @m async method(x: number, ...rest: string[]) {} @p prop: boolean; }",
+        r#"class Service {}
+        let A = class A { constructor(s, d, i) {} async method(x, ...rest) {} prop; };
+        babelHelpers.decorate([m, babelHelpers.decorateMetadata("design:type", Function), babelHelpers.decorateMetadata("design:paramtypes", [Number, String]), babelHelpers.decorateMetadata("design:returntype", Promise)], A.prototype, "method", null);
+        babelHelpers.decorate([p, babelHelpers.decorateMetadata("design:type", Boolean)], A.prototype, "prop", void 0);
+        A = babelHelpers.decorate([dec, babelHelpers.decorateMetadata("design:paramtypes", [Service, typeof Dep === "undefined" ? Object : Dep, Object])], A);"#,
+    )];
+    Tester::new("test.ts", legacy_options(true)).test(metadata_tests);
+}
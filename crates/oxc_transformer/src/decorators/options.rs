@@ -0,0 +1,26 @@
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecoratorsOptions {
+    /// The version of the decorators proposal to implement, defaults to `"2023-05"`.
+    #[serde(default)]
+    pub version: DecoratorsVersion,
+
+    /// Emit the `design:type`, `design:paramtypes` and `design:returntype` metadata of decorated
+    /// declarations, like TypeScript's `emitDecoratorMetadata`.
+    /// Only applies to the legacy decorators.
+    #[serde(default)]
+    pub emit_decorator_metadata: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum DecoratorsVersion {
+    /// The decorators of TypeScript's `experimentalDecorators` and Babel's legacy mode
+    #[serde(rename = "legacy")]
+    Legacy,
+    /// The stage 3 decorators proposal as of the May 2023 TC39 meeting
+    #[default]
+    #[serde(rename = "2023-05")]
+    V2023_05,
+}
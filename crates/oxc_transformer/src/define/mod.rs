@@ -0,0 +1,307 @@
+mod options;
+
+use std::rc::Rc;
+
+use oxc_allocator::Vec;
+use oxc_ast::{
+    ast::*,
+    constant_evaluation::{ConstantEvaluation, ConstantValue},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_parser::Parser;
+use oxc_semantic::ScopeFlags;
+use oxc_span::SourceType;
+use oxc_syntax::{identifier::is_identifier_name, operator::LogicalOperator};
+
+pub use self::options::DefineOptions;
+use crate::{context::TransformerCtx, TransformOptions};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid define key `{0}`, expected an identifier or a member expression such as `process.env.NODE_ENV`.")]
+#[diagnostic(severity(warning))]
+struct InvalidDefineKey(String);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid define value `{1}` for `{0}`, expected an expression.")]
+#[diagnostic(severity(warning))]
+struct InvalidDefineValue(String, String);
+
+/// Replace global identifiers and member expressions with constant expressions
+///
+/// The branches of `if` statements, conditional and logical expressions which can no longer be
+/// taken after a replacement are removed.
+///
+/// ```JavaScript
+/// // process.env.NODE_ENV: "\"production\""
+/// if (process.env.NODE_ENV !== "production") {
+///   check();
+/// } else {
+///   run();
+/// }
+/// ```
+/// ```JavaScript
+/// {
+///   run();
+/// }
+/// ```
+///
+/// References:
+/// * <https://esbuild.github.io/api/#define>
+/// * <https://github.com/evanw/esbuild/blob/main/internal/config/globals.go>
+pub struct Define<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+
+    /// The parts of the replaced names, and the source texts of the replacements
+    defines: std::vec::Vec<(std::vec::Vec<String>, String)>,
+}
+
+impl<'a> Define<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        let options = options.define.as_ref()?;
+        let mut define = Self { ast, ctx, defines: vec![] };
+        let mut replacements = options.replacements.iter().collect::<std::vec::Vec<_>>();
+        replacements.sort_unstable();
+        for (key, value) in replacements {
+            let parts = key.split('.').map(str::to_string).collect::<std::vec::Vec<_>>();
+            if !parts.iter().all(|part| is_identifier_name(part)) {
+                define.ctx.error(InvalidDefineKey(key.clone()));
+                continue;
+            }
+            if define.parse(value).is_none() {
+                define.ctx.error(InvalidDefineValue(key.clone(), value.clone()));
+                continue;
+            }
+            define.defines.push((parts, value.clone()));
+        }
+        Some(define)
+    }
+
+    /// Replace the defined names, and remove the branches which are no longer taken.
+    pub fn transform_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.replace(expr) {
+            return;
+        }
+        match expr {
+            // `true ? a : b` -> `a`
+            Expression::ConditionalExpression(cond) => {
+                let Some((truthy, _)) = self.replaced_constant(&mut cond.test) else { return };
+                let branch = if truthy { &mut cond.consequent } else { &mut cond.alternate };
+                *expr = self.ast.move_expression(branch);
+                self.transform_expression(expr);
+            }
+            // `false && a` -> `false`, `true && a` -> `a`
+            Expression::LogicalExpression(logical) => {
+                let Some((truthy, nullish)) = self.replaced_constant(&mut logical.left) else {
+                    return;
+                };
+                let take_right = match logical.operator {
+                    LogicalOperator::And => truthy,
+                    LogicalOperator::Or => !truthy,
+                    LogicalOperator::Coalesce => nullish,
+                };
+                let side = if take_right { &mut logical.right } else { &mut logical.left };
+                *expr = self.ast.move_expression(side);
+                self.transform_expression(expr);
+            }
+            // `{ DEBUG }` -> `{ DEBUG: true }`
+            Expression::ObjectExpression(object) => {
+                for property in object.properties.iter_mut() {
+                    if let ObjectPropertyKind::ObjectProperty(property) = property {
+                        if property.shorthand && self.find(&property.value).is_some() {
+                            property.shorthand = false;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Remove the `if` statements of `stmts` whose tests are constant after a replacement.
+    pub fn transform_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for stmt in old_stmts {
+            stmts.extend(self.transform_if_statement(stmt));
+        }
+    }
+
+    /// `if (true) a; else b;` -> `a;`
+    fn transform_if_statement(&mut self, mut stmt: Statement<'a>) -> Option<Statement<'a>> {
+        loop {
+            let Statement::IfStatement(if_stmt) = &mut stmt else { return Some(stmt) };
+            let Some((truthy, _)) = self.replaced_constant(&mut if_stmt.test) else {
+                return Some(stmt);
+            };
+            // The declarations of `var` and functions are hoisted out of the removed branch
+            let removed =
+                if truthy { if_stmt.alternate.as_ref() } else { Some(&if_stmt.consequent) };
+            if removed.is_some_and(has_hoisted_declaration) {
+                return Some(stmt);
+            }
+            let Statement::IfStatement(if_stmt) = stmt else { unreachable!() };
+            let if_stmt = if_stmt.unbox();
+            stmt = if truthy { if_stmt.consequent } else { if_stmt.alternate? };
+        }
+    }
+
+    /// Replace the defined names in `expr`, and evaluate it when a name was replaced.
+    ///
+    /// Returns whether the value is truthy, and whether it is nullish.
+    fn replaced_constant(&mut self, expr: &mut Expression<'a>) -> Option<(bool, bool)> {
+        let mut replacer = Replacer { define: self, replaced: false };
+        replacer.visit_expression(expr);
+        if !replacer.replaced {
+            return None;
+        }
+        let value = expr.evaluate_constant()?;
+        let nullish = matches!(value, ConstantValue::Undefined | ConstantValue::Null);
+        Some((value.to_boolean(), nullish))
+    }
+
+    /// Replace `expr` if it is a defined name
+    fn replace(&self, expr: &mut Expression<'a>) -> bool {
+        let Some(value) = self.find(expr) else { return false };
+        let Some(replacement) = self.parse(value) else { return false };
+        *expr = replacement;
+        true
+    }
+
+    /// The source text of the replacement of `expr`
+    fn find(&self, expr: &Expression<'a>) -> Option<&str> {
+        self.defines
+            .iter()
+            .find(|(parts, _)| self.is_define(expr, parts))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether `expr` is the global name `parts`, e.g. `process.env.NODE_ENV`
+    fn is_define(&self, expr: &Expression<'a>, parts: &[String]) -> bool {
+        match expr {
+            Expression::Identifier(ident) => {
+                parts.len() == 1 && ident.name == parts[0] && self.is_global(ident)
+            }
+            Expression::MemberExpression(member) => {
+                let Some((property, object)) = parts.split_last() else { return false };
+                !member.optional()
+                    && member.static_property_name() == Some(property)
+                    && self.is_define(member.object(), object)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `ident` is an unresolved reference of the source text
+    fn is_global(&self, ident: &IdentifierReference) -> bool {
+        ident.reference_id.get().is_some_and(|reference_id| {
+            self.ctx.symbols().get_reference(reference_id).symbol_id().is_none()
+        })
+    }
+
+    /// Each replacement is parsed into its own nodes.
+    fn parse(&self, value: &str) -> Option<Expression<'a>> {
+        let source_text = self.ast.new_str(&format!("({value})"));
+        let ret = Parser::new(self.ast.allocator, source_text, SourceType::default()).parse();
+        if !ret.errors.is_empty() || ret.program.body.len() != 1 {
+            return None;
+        }
+        let Some(Statement::ExpressionStatement(stmt)) = ret.program.body.into_iter().next() else {
+            return None;
+        };
+        match stmt.unbox().expression {
+            Expression::ParenthesizedExpression(expr) => Some(expr.unbox().expression),
+            _ => None,
+        }
+    }
+}
+
+/// Replace the defined names of an expression
+struct Replacer<'a, 'b> {
+    define: &'b Define<'a>,
+    replaced: bool,
+}
+
+impl<'a, 'b> VisitMut<'a> for Replacer<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.define.replace(expr) {
+            self.replaced = true;
+        } else {
+            self.visit_expression_match(expr);
+        }
+    }
+}
+
+/// Whether `stmt` declares `var` variables or functions outside of nested functions
+fn has_hoisted_declaration(stmt: &Statement) -> bool {
+    let mut finder = HoistedDeclarationFinder { found: false };
+    finder.visit_statement(stmt);
+    finder.found
+}
+
+struct HoistedDeclarationFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for HoistedDeclarationFinder {
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
+        if decl.kind.is_var() {
+            self.found = true;
+        }
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, _flags: Option<ScopeFlags>) {
+        if func.is_declaration() {
+            self.found = true;
+        }
+    }
+
+    fn visit_arrow_expression(&mut self, _expr: &ArrowExpression<'a>) {}
+}
+
+#[test]
+fn test() {
+    use rustc_hash::FxHashMap;
+
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let replacements = [
+        ("process.env.NODE_ENV", "'production'"),
+        ("__DEV__", "false"),
+        ("DEBUG", "true"),
+        ("VERSION", "{ major: 1 }"),
+    ];
+    let options = TransformOptions {
+        define: Some(DefineOptions {
+            replacements: replacements
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<FxHashMap<_, _>>(),
+        }),
+        ..TransformOptions::default()
+    };
+
+    let tests = &[
+        ("if (process.env.NODE_ENV !== 'production') { check(); } else { run(); }", "{ run(); }"),
+        ("if (__DEV__) warn(); foo();", "foo();"),
+        ("if (__DEV__) { var x = 1; }", "if (false) { var x = 1; }"),
+        ("let a = __DEV__ ? dev() : prod(), b = DEBUG && log();", "let a = prod(), b = log();"),
+        (
+            "f({ DEBUG }, VERSION.major, process.env['NODE_ENV']);",
+            "f({ DEBUG: true }, { major: 1 }.major, 'production');",
+        ),
+        (
+            "function f(__DEV__) { return __DEV__ && process.env.x; }",
+            "function f(__DEV__) { return __DEV__ && process.env.x; }",
+        ),
+    ];
+
+    Tester::new("test.js", options).test(tests);
+}
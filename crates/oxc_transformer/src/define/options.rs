@@ -0,0 +1,10 @@
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct DefineOptions {
+    /// The identifiers or member expressions to replace, e.g. `process.env.NODE_ENV`, and the
+    /// source text of their replacements, e.g. `"\"production\""`.
+    pub replacements: FxHashMap<String, String>,
+}
@@ -0,0 +1,390 @@
+use std::str::FromStr;
+
+/// The engines of the compatibility table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Engine {
+    Chrome,
+    Edge,
+    Firefox,
+    Safari,
+    Ios,
+    Opera,
+    Samsung,
+    Ie,
+    Node,
+    Deno,
+    Electron,
+}
+
+impl Engine {
+    /// The engine named `name`, including the browserslist aliases
+    pub fn from_name(name: &str) -> Option<Self> {
+        let engine = match name.to_ascii_lowercase().as_str() {
+            "chrome" | "and_chr" | "chromeandroid" => Self::Chrome,
+            "edge" => Self::Edge,
+            "firefox" | "ff" | "and_ff" | "firefoxandroid" => Self::Firefox,
+            "safari" => Self::Safari,
+            "ios" | "ios_saf" => Self::Ios,
+            "opera" => Self::Opera,
+            "samsung" => Self::Samsung,
+            "ie" | "explorer" => Self::Ie,
+            "node" => Self::Node,
+            "deno" => Self::Deno,
+            "electron" => Self::Electron,
+            _ => return None,
+        };
+        Some(engine)
+    }
+}
+
+/// A `major.minor.patch` version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(pub u32, pub u32, pub u32);
+
+impl FromStr for Version {
+    type Err = ();
+
+    /// `13`, `13.1` or `16.11.0`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+        let mut next = |required: bool| match parts.next() {
+            Some(part) => part.parse::<u32>().map_err(|_| ()),
+            None if required => Err(()),
+            None => Ok(0),
+        };
+        let version = Self(next(true)?, next(false)?, next(false)?);
+        if parts.next().is_some() {
+            return Err(());
+        }
+        Ok(version)
+    }
+}
+
+/// The syntax features lowered by the transformer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    // es2024
+    UnicodeSetsRegex,
+    // es2022
+    ClassStaticBlock,
+    ClassProperties,
+    // es2021
+    LogicalAssignmentOperators,
+    // es2020
+    NullishCoalescingOperator,
+    OptionalChaining,
+    // es2019
+    OptionalCatchBinding,
+    // es2018
+    DotallRegex,
+    AsyncGeneratorFunctions,
+    // es2017
+    AsyncToGenerator,
+    // es2016
+    ExponentiationOperator,
+    // es2015
+    ArrowFunctions,
+    DuplicateKeys,
+    FunctionName,
+    Instanceof,
+    NewTarget,
+    ShorthandProperties,
+    StickyRegex,
+    TemplateLiterals,
+    UnicodeRegex,
+    // es3
+    PropertyLiterals,
+}
+
+impl Feature {
+    pub const ALL: [Self; 21] = [
+        Self::UnicodeSetsRegex,
+        Self::ClassStaticBlock,
+        Self::ClassProperties,
+        Self::LogicalAssignmentOperators,
+        Self::NullishCoalescingOperator,
+        Self::OptionalChaining,
+        Self::OptionalCatchBinding,
+        Self::DotallRegex,
+        Self::AsyncGeneratorFunctions,
+        Self::AsyncToGenerator,
+        Self::ExponentiationOperator,
+        Self::ArrowFunctions,
+        Self::DuplicateKeys,
+        Self::FunctionName,
+        Self::Instanceof,
+        Self::NewTarget,
+        Self::ShorthandProperties,
+        Self::StickyRegex,
+        Self::TemplateLiterals,
+        Self::UnicodeRegex,
+        Self::PropertyLiterals,
+    ];
+
+    /// The first version of each engine supporting the feature.
+    /// An engine missing from the list does not support the feature.
+    ///
+    /// From <https://github.com/babel/babel/blob/main/packages/babel-compat-data/data/plugins.json>
+    #[allow(clippy::too_many_lines)]
+    pub fn support(self) -> &'static [(Engine, Version)] {
+        use Engine::{
+            Chrome, Deno, Edge, Electron, Firefox, Ie, Ios, Node, Opera, Safari, Samsung,
+        };
+        match self {
+            Self::UnicodeSetsRegex => &[
+                (Chrome, Version(112, 0, 0)),
+                (Edge, Version(112, 0, 0)),
+                (Firefox, Version(116, 0, 0)),
+                (Safari, Version(17, 0, 0)),
+                (Ios, Version(17, 0, 0)),
+                (Opera, Version(98, 0, 0)),
+                (Samsung, Version(23, 0, 0)),
+                (Node, Version(20, 0, 0)),
+                (Deno, Version(1, 32, 0)),
+                (Electron, Version(24, 0, 0)),
+            ],
+            Self::ClassStaticBlock => &[
+                (Chrome, Version(94, 0, 0)),
+                (Edge, Version(94, 0, 0)),
+                (Firefox, Version(93, 0, 0)),
+                (Safari, Version(16, 4, 0)),
+                (Ios, Version(16, 4, 0)),
+                (Opera, Version(80, 0, 0)),
+                (Samsung, Version(17, 0, 0)),
+                (Node, Version(16, 11, 0)),
+                (Deno, Version(1, 14, 0)),
+                (Electron, Version(15, 0, 0)),
+            ],
+            // Including the private methods, which are lowered by the same pass
+            Self::ClassProperties => &[
+                (Chrome, Version(84, 0, 0)),
+                (Edge, Version(84, 0, 0)),
+                (Firefox, Version(90, 0, 0)),
+                (Safari, Version(15, 0, 0)),
+                (Ios, Version(15, 0, 0)),
+                (Opera, Version(70, 0, 0)),
+                (Samsung, Version(14, 0, 0)),
+                (Node, Version(14, 6, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(10, 0, 0)),
+            ],
+            Self::LogicalAssignmentOperators => &[
+                (Chrome, Version(85, 0, 0)),
+                (Edge, Version(85, 0, 0)),
+                (Firefox, Version(79, 0, 0)),
+                (Safari, Version(14, 0, 0)),
+                (Ios, Version(14, 0, 0)),
+                (Opera, Version(71, 0, 0)),
+                (Samsung, Version(14, 0, 0)),
+                (Node, Version(15, 0, 0)),
+                (Deno, Version(1, 2, 0)),
+                (Electron, Version(10, 0, 0)),
+            ],
+            Self::NullishCoalescingOperator => &[
+                (Chrome, Version(80, 0, 0)),
+                (Edge, Version(80, 0, 0)),
+                (Firefox, Version(72, 0, 0)),
+                (Safari, Version(13, 1, 0)),
+                (Ios, Version(13, 4, 0)),
+                (Opera, Version(67, 0, 0)),
+                (Samsung, Version(13, 0, 0)),
+                (Node, Version(14, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(8, 0, 0)),
+            ],
+            Self::OptionalChaining => &[
+                (Chrome, Version(91, 0, 0)),
+                (Edge, Version(91, 0, 0)),
+                (Firefox, Version(74, 0, 0)),
+                (Safari, Version(13, 1, 0)),
+                (Ios, Version(13, 4, 0)),
+                (Opera, Version(77, 0, 0)),
+                (Samsung, Version(16, 0, 0)),
+                (Node, Version(16, 9, 0)),
+                (Deno, Version(1, 9, 0)),
+                (Electron, Version(13, 0, 0)),
+            ],
+            Self::OptionalCatchBinding => &[
+                (Chrome, Version(66, 0, 0)),
+                (Edge, Version(79, 0, 0)),
+                (Firefox, Version(58, 0, 0)),
+                (Safari, Version(11, 1, 0)),
+                (Ios, Version(11, 3, 0)),
+                (Opera, Version(53, 0, 0)),
+                (Samsung, Version(9, 0, 0)),
+                (Node, Version(10, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(3, 0, 0)),
+            ],
+            Self::DotallRegex => &[
+                (Chrome, Version(62, 0, 0)),
+                (Edge, Version(79, 0, 0)),
+                (Firefox, Version(78, 0, 0)),
+                (Safari, Version(11, 1, 0)),
+                (Ios, Version(11, 3, 0)),
+                (Opera, Version(49, 0, 0)),
+                (Samsung, Version(8, 0, 0)),
+                (Node, Version(8, 10, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(3, 0, 0)),
+            ],
+            Self::AsyncGeneratorFunctions => &[
+                (Chrome, Version(63, 0, 0)),
+                (Edge, Version(79, 0, 0)),
+                (Firefox, Version(57, 0, 0)),
+                (Safari, Version(12, 0, 0)),
+                (Ios, Version(12, 0, 0)),
+                (Opera, Version(50, 0, 0)),
+                (Samsung, Version(8, 0, 0)),
+                (Node, Version(10, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(3, 0, 0)),
+            ],
+            Self::AsyncToGenerator => &[
+                (Chrome, Version(55, 0, 0)),
+                (Edge, Version(15, 0, 0)),
+                (Firefox, Version(52, 0, 0)),
+                (Safari, Version(11, 0, 0)),
+                (Ios, Version(11, 0, 0)),
+                (Opera, Version(42, 0, 0)),
+                (Samsung, Version(6, 0, 0)),
+                (Node, Version(7, 6, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(1, 6, 0)),
+            ],
+            Self::ExponentiationOperator => &[
+                (Chrome, Version(52, 0, 0)),
+                (Edge, Version(14, 0, 0)),
+                (Firefox, Version(52, 0, 0)),
+                (Safari, Version(10, 1, 0)),
+                (Ios, Version(10, 3, 0)),
+                (Opera, Version(39, 0, 0)),
+                (Samsung, Version(6, 0, 0)),
+                (Node, Version(7, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(1, 3, 0)),
+            ],
+            Self::ArrowFunctions => &[
+                (Chrome, Version(47, 0, 0)),
+                (Edge, Version(13, 0, 0)),
+                (Firefox, Version(43, 0, 0)),
+                (Safari, Version(10, 0, 0)),
+                (Ios, Version(10, 0, 0)),
+                (Opera, Version(34, 0, 0)),
+                (Samsung, Version(5, 0, 0)),
+                (Node, Version(6, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 36, 0)),
+            ],
+            Self::DuplicateKeys => &[
+                (Chrome, Version(42, 0, 0)),
+                (Edge, Version(12, 0, 0)),
+                (Firefox, Version(34, 0, 0)),
+                (Safari, Version(9, 0, 0)),
+                (Ios, Version(9, 0, 0)),
+                (Opera, Version(29, 0, 0)),
+                (Samsung, Version(3, 4, 0)),
+                (Node, Version(4, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 21, 0)),
+            ],
+            Self::FunctionName => &[
+                (Chrome, Version(51, 0, 0)),
+                (Edge, Version(79, 0, 0)),
+                (Firefox, Version(53, 0, 0)),
+                (Safari, Version(10, 0, 0)),
+                (Ios, Version(10, 0, 0)),
+                (Opera, Version(38, 0, 0)),
+                (Samsung, Version(5, 0, 0)),
+                (Node, Version(6, 5, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(1, 2, 0)),
+            ],
+            Self::Instanceof => &[
+                (Chrome, Version(50, 0, 0)),
+                (Edge, Version(13, 0, 0)),
+                (Firefox, Version(49, 0, 0)),
+                (Safari, Version(10, 0, 0)),
+                (Ios, Version(10, 0, 0)),
+                (Opera, Version(37, 0, 0)),
+                (Samsung, Version(5, 0, 0)),
+                (Node, Version(6, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(1, 1, 0)),
+            ],
+            Self::NewTarget => &[
+                (Chrome, Version(46, 0, 0)),
+                (Edge, Version(14, 0, 0)),
+                (Firefox, Version(41, 0, 0)),
+                (Safari, Version(10, 0, 0)),
+                (Ios, Version(10, 0, 0)),
+                (Opera, Version(33, 0, 0)),
+                (Samsung, Version(5, 0, 0)),
+                (Node, Version(5, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 36, 0)),
+            ],
+            Self::ShorthandProperties => &[
+                (Chrome, Version(43, 0, 0)),
+                (Edge, Version(12, 0, 0)),
+                (Firefox, Version(33, 0, 0)),
+                (Safari, Version(9, 0, 0)),
+                (Ios, Version(9, 0, 0)),
+                (Opera, Version(30, 0, 0)),
+                (Samsung, Version(4, 0, 0)),
+                (Node, Version(4, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 27, 0)),
+            ],
+            Self::StickyRegex => &[
+                (Chrome, Version(49, 0, 0)),
+                (Edge, Version(13, 0, 0)),
+                (Firefox, Version(3, 0, 0)),
+                (Safari, Version(10, 0, 0)),
+                (Ios, Version(10, 0, 0)),
+                (Opera, Version(36, 0, 0)),
+                (Samsung, Version(5, 0, 0)),
+                (Node, Version(6, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 37, 0)),
+            ],
+            Self::TemplateLiterals => &[
+                (Chrome, Version(41, 0, 0)),
+                (Edge, Version(13, 0, 0)),
+                (Firefox, Version(34, 0, 0)),
+                (Safari, Version(13, 0, 0)),
+                (Ios, Version(13, 0, 0)),
+                (Opera, Version(28, 0, 0)),
+                (Samsung, Version(3, 4, 0)),
+                (Node, Version(4, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 21, 0)),
+            ],
+            Self::UnicodeRegex => &[
+                (Chrome, Version(50, 0, 0)),
+                (Edge, Version(13, 0, 0)),
+                (Firefox, Version(46, 0, 0)),
+                (Safari, Version(12, 0, 0)),
+                (Ios, Version(12, 0, 0)),
+                (Opera, Version(37, 0, 0)),
+                (Samsung, Version(5, 0, 0)),
+                (Node, Version(6, 0, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(1, 1, 0)),
+            ],
+            Self::PropertyLiterals => &[
+                (Chrome, Version(7, 0, 0)),
+                (Edge, Version(12, 0, 0)),
+                (Firefox, Version(2, 0, 0)),
+                (Safari, Version(5, 1, 0)),
+                (Ios, Version(6, 0, 0)),
+                (Opera, Version(12, 0, 0)),
+                (Samsung, Version(1, 0, 0)),
+                (Ie, Version(9, 0, 0)),
+                (Node, Version(0, 4, 0)),
+                (Deno, Version(1, 0, 0)),
+                (Electron, Version(0, 20, 0)),
+            ],
+        }
+    }
+}
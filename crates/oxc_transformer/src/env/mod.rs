@@ -0,0 +1,161 @@
+mod data;
+mod options;
+mod query;
+
+use rustc_hash::FxHashMap;
+
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+
+pub use self::options::TargetsOptions;
+use self::{
+    data::{Engine, Feature, Version},
+    query::parse_query,
+};
+use crate::{context::TransformerCtx, TransformOptions};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid target `{0}`, expected an engine and its version such as `chrome 80`.")]
+#[diagnostic(severity(warning))]
+struct InvalidTarget(String);
+
+/// Enable the transforms of the syntax features which are not supported by `options.targets`,
+/// like Babel's `preset-env`
+///
+/// ```JavaScript
+/// // targets: "chrome 79"
+/// a ?? b;
+/// ```
+/// ```JavaScript
+/// var _a;
+/// (_a = a) !== null && _a !== void 0 ? _a : b;
+/// ```
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-preset-env>
+/// * <https://github.com/babel/babel/tree/main/packages/babel-compat-data>
+pub fn enable_unsupported_features(options: &mut TransformOptions, ctx: &mut TransformerCtx) {
+    let Some(targets) = &options.targets else { return };
+    let targets = match Targets::new(targets) {
+        Ok(targets) => targets,
+        Err(error) => {
+            ctx.error(error);
+            return;
+        }
+    };
+    for feature in Feature::ALL {
+        if targets.supports(feature) {
+            continue;
+        }
+        match feature {
+            Feature::UnicodeSetsRegex => options.unicode_sets_regex = true,
+            Feature::ClassStaticBlock => options.class_static_block = true,
+            Feature::ClassProperties => {
+                options.class_properties.get_or_insert_with(Default::default);
+            }
+            Feature::LogicalAssignmentOperators => options.logical_assignment_operators = true,
+            Feature::NullishCoalescingOperator => {
+                options.nullish_coalescing_operator.get_or_insert_with(Default::default);
+            }
+            Feature::OptionalChaining => {
+                options.optional_chaining.get_or_insert_with(Default::default);
+            }
+            Feature::OptionalCatchBinding => options.optional_catch_binding = true,
+            Feature::DotallRegex => options.dotall_regex = true,
+            Feature::AsyncGeneratorFunctions => options.async_generator_functions = true,
+            Feature::AsyncToGenerator => options.async_to_generator = true,
+            Feature::ExponentiationOperator => options.exponentiation_operator = true,
+            Feature::ArrowFunctions => {
+                options.arrow_functions.get_or_insert_with(Default::default);
+            }
+            Feature::DuplicateKeys => options.duplicate_keys = true,
+            Feature::FunctionName => options.function_name = true,
+            Feature::Instanceof => options.instanceof = true,
+            Feature::NewTarget => options.new_target = true,
+            Feature::ShorthandProperties => options.shorthand_properties = true,
+            Feature::StickyRegex => options.sticky_regex = true,
+            Feature::TemplateLiterals => options.template_literals = true,
+            Feature::UnicodeRegex => options.unicode_regex = true,
+            Feature::PropertyLiterals => options.property_literals = true,
+        }
+    }
+}
+
+/// The minimum version of each targeted engine
+#[derive(Debug)]
+struct Targets(FxHashMap<Engine, Version>);
+
+impl Targets {
+    fn new(options: &TargetsOptions) -> Result<Self, InvalidTarget> {
+        let versions = match options {
+            TargetsOptions::Query(query) => parse_query(query).map_err(InvalidTarget)?,
+            TargetsOptions::Engines(engines) => engines
+                .iter()
+                .map(|(name, version)| {
+                    Engine::from_name(name)
+                        .zip(version.parse::<Version>().ok())
+                        .ok_or_else(|| InvalidTarget(format!("{name} {version}")))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        let mut targets = FxHashMap::default();
+        for (engine, version) in versions {
+            targets
+                .entry(engine)
+                .and_modify(|min: &mut Version| *min = (*min).min(version))
+                .or_insert(version);
+        }
+        Ok(Self(targets))
+    }
+
+    /// Whether all the targeted engines support `feature`.
+    /// Without any target, the oldest engines are assumed.
+    fn supports(&self, feature: Feature) -> bool {
+        let support = feature.support();
+        !self.0.is_empty()
+            && self.0.iter().all(|(engine, version)| {
+                support.iter().any(|(supported, since)| supported == engine && version >= since)
+            })
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = TransformOptions {
+        targets: Some(TargetsOptions::Query("chrome >= 79, firefox 91".to_string())),
+        ..TransformOptions::default()
+    };
+    let tests = &[
+        ("a ?? b;", "var _a;\n(_a = a) !== null && _a !== void 0 ? _a : b;"),
+        ("a **= 2; const f = (x) => x;", "a **= 2; const f = (x) => x;"),
+        ("a ||= b;", "a || (a = b);"),
+    ];
+    Tester::new("test.js", options).test(tests);
+
+    let options = TransformOptions {
+        targets: Some(TargetsOptions::Engines(
+            [("node", "16.11"), ("chrome", "94")]
+                .into_iter()
+                .map(|(engine, version)| (engine.to_string(), version.to_string()))
+                .collect(),
+        )),
+        ..TransformOptions::default()
+    };
+    let tests = &[(
+        "class A { static { init(); } } a ||= b; a?.b;",
+        "class A { static { init(); } } a ||= b; a?.b;",
+    )];
+    Tester::new("test.js", options).test(tests);
+
+    let query = "chrome 80, ios_saf 15.2-15.3 or node >= 14.17.1";
+    let targets = Targets::new(&TargetsOptions::Query(query.to_string())).unwrap();
+    assert_eq!(targets.0.get(&Engine::Ios), Some(&Version(15, 2, 0)));
+    assert_eq!(targets.0.get(&Engine::Node), Some(&Version(14, 17, 1)));
+    assert!(targets.supports(Feature::NullishCoalescingOperator));
+    assert!(!targets.supports(Feature::OptionalChaining));
+    assert!(Targets::new(&TargetsOptions::Query("defaults".to_string())).is_err());
+}
@@ -0,0 +1,16 @@
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+
+/// The environments to support, like Babel's `targets`
+///
+/// References:
+/// * <https://babeljs.io/docs/options#targets>
+/// * <https://github.com/browserslist/browserslist#queries>
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TargetsOptions {
+    /// A browserslist query, e.g. `chrome >= 80, safari 13.1, node 14`
+    Query(String),
+    /// The minimum version of each engine, e.g. `{ "chrome": "80", "node": "14.17" }`
+    Engines(FxHashMap<String, String>),
+}
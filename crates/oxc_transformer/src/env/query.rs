@@ -0,0 +1,30 @@
+use super::data::{Engine, Version};
+
+/// Parse the subset of browserslist queries naming the engines and their versions:
+///
+/// * `chrome 80`, `safari 13.1`, `ios_saf 15.2-15.3`
+/// * `chrome >= 80`
+/// * Unions of the above separated by `,` or `or`
+///
+/// Returns the query which could not be parsed on failure.
+///
+/// Reference: <https://github.com/browserslist/browserslist#queries>
+pub fn parse_query(query: &str) -> Result<Vec<(Engine, Version)>, String> {
+    query
+        .split(',')
+        .flat_map(|part| part.split(" or "))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| parse_engine_version(part).ok_or_else(|| part.to_string()))
+        .collect()
+}
+
+fn parse_engine_version(query: &str) -> Option<(Engine, Version)> {
+    let (name, version) = query.split_once(char::is_whitespace)?;
+    let engine = Engine::from_name(name)?;
+    let version = version.trim();
+    let version = version.strip_prefix(">=").map_or(version, str::trim_start);
+    // The first version of a range
+    let version = version.split_once('-').map_or(version, |(start, _)| start);
+    Some((engine, version.parse().ok()?))
+}
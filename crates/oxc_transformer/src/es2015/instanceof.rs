@@ -53,15 +53,7 @@ impl<'a> Instanceof<'a> {
                     return;
                 }
 
-                let object = self.ast.identifier_reference_expression(IdentifierReference::new(
-                    SPAN,
-                    "babelHelpers".into(),
-                ));
-
-                let property = IdentifierName::new(SPAN, "instanceof".into());
-                let helper = self.ast.member_expression(MemberExpression::StaticMemberExpression(
-                    StaticMemberExpression { span: SPAN, object, property, optional: false },
-                ));
+                let helper = self.ctx.helper("instanceof");
 
                 let left = self.ast.copy(left);
                 let right = self.ast.copy(right);
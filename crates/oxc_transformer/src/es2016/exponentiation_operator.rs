@@ -20,7 +20,7 @@ use crate::{
 pub struct ExponentiationOperator<'a> {
     ast: Rc<AstBuilder<'a>>,
     ctx: TransformerCtx<'a>,
-    vars: Vec<'a, VariableDeclarator<'a>>,
+    vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
 }
 
 struct Exploded<'a> {
@@ -33,7 +33,7 @@ impl<'a> CreateVars<'a> for ExponentiationOperator<'a> {
         &self.ctx
     }
 
-    fn vars_mut(&mut self) -> &mut Vec<'a, VariableDeclarator<'a>> {
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>> {
         &mut self.vars
     }
 }
@@ -45,7 +45,7 @@ impl<'a> ExponentiationOperator<'a> {
         options: &TransformOptions,
     ) -> Option<Self> {
         (options.target < TransformTarget::ES2016 || options.exponentiation_operator).then(|| {
-            let vars = ast.new_vec();
+            let vars = vec![];
             Self { ast, ctx, vars }
         })
     }
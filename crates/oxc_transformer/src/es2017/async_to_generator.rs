@@ -0,0 +1,248 @@
+use std::rc::Rc;
+
+use oxc_ast::{ast::*, AstBuilder, Visit, VisitMut};
+use oxc_diagnostics::miette::{self, Diagnostic};
+use oxc_diagnostics::thiserror::Error;
+use oxc_semantic::ScopeFlags;
+use oxc_span::{Span, SPAN};
+
+use crate::{
+    context::TransformerCtx,
+    options::{TransformOptions, TransformTarget},
+    utils::SuperFinder,
+};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("`super` is not supported in async functions lowered to generators.")]
+#[diagnostic(severity(warning))]
+struct SuperInAsyncFunction(#[label] Span);
+
+/// ES2017: Async To Generator
+///
+/// ```JavaScript
+/// async function f(a) {
+///   await g(a);
+/// }
+/// const h = async () => await this.i();
+/// ```
+/// ```JavaScript
+/// function f(a) {
+///   return babelHelpers.asyncToGenerator(function* () {
+///     yield g(a);
+///   }).apply(this, arguments);
+/// }
+/// const h = () => babelHelpers.asyncToGenerator(function* () {
+///   return yield this.i();
+/// }).call(this);
+/// ```
+///
+/// The parameters stay on the async function, and `this` and `arguments` are forwarded to the
+/// generator.
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-transform-async-to-generator>
+/// * <https://github.com/babel/babel/blob/main/packages/babel-helpers/src/helpers/asyncToGenerator.ts>
+pub struct AsyncToGenerator<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+}
+
+impl<'a> AsyncToGenerator<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        (options.target < TransformTarget::ES2017 || options.async_to_generator)
+            .then(|| Self { ast, ctx })
+    }
+
+    pub fn transform_function(&mut self, func: &mut Function<'a>) {
+        if !func.r#async || func.generator {
+            return;
+        }
+        let Some(body) = &mut func.body else { return };
+        func.r#async = false;
+        wrap_async_body(&self.ast, &mut self.ctx, body, false, true);
+    }
+
+    pub fn transform_arrow_expression(&mut self, arrow: &mut ArrowExpression<'a>) {
+        if !arrow.r#async {
+            return;
+        }
+        arrow.r#async = false;
+        if arrow.expression {
+            // `async () => x` -> `async () => { return x; }`
+            if let Some(Statement::ExpressionStatement(stmt)) = arrow.body.statements.pop() {
+                let argument = stmt.unbox().expression;
+                arrow.body.statements.push(self.ast.return_statement(SPAN, Some(argument)));
+            }
+        }
+        // `arguments` is forwarded only when it is used, as it may not exist outside of functions
+        let mut finder = ArgumentsFinder { found: false };
+        finder.visit_function_body(&arrow.body);
+        wrap_async_body(&self.ast, &mut self.ctx, &mut arrow.body, false, finder.found);
+        arrow.expression = true;
+        if let Some(Statement::ReturnStatement(stmt)) = arrow.body.statements.pop() {
+            let expr = stmt.unbox().argument.unwrap();
+            arrow.body.statements.push(self.ast.expression_statement(SPAN, expr));
+        }
+    }
+}
+
+/// Move the statements of `body` into a generator called by a helper:
+///
+/// * `return babelHelpers.asyncToGenerator(function* () { body }).apply(this, arguments);`
+/// * `return babelHelpers.wrapAsyncGenerator(function* () { body }).apply(this, arguments);`
+///   for async generators
+pub fn wrap_async_body<'a>(
+    ast: &AstBuilder<'a>,
+    ctx: &mut TransformerCtx<'a>,
+    body: &mut FunctionBody<'a>,
+    async_generator: bool,
+    forward_arguments: bool,
+) {
+    let mut statements = ast.move_statement_vec(&mut body.statements);
+    // `super` can not be moved into a generator
+    let mut finder = SuperFinder { span: None };
+    for stmt in &statements {
+        finder.visit_statement(stmt);
+    }
+    if let Some(span) = finder.span {
+        ctx.error(SuperInAsyncFunction(span));
+    }
+    let mut replacer = AwaitReplacer { ast, ctx, async_generator };
+    for stmt in statements.iter_mut() {
+        replacer.visit_statement(stmt);
+    }
+
+    let params =
+        ast.formal_parameters(SPAN, FormalParameterKind::FormalParameter, ast.new_vec(), None);
+    let generator = ast.function(
+        FunctionType::FunctionExpression,
+        SPAN,
+        None,
+        false,
+        true,
+        false,
+        None,
+        params,
+        Some(ast.function_body(SPAN, ast.new_vec(), statements)),
+        None,
+        None,
+        Modifiers::empty(),
+    );
+    let helper = if async_generator { "wrapAsyncGenerator" } else { "asyncToGenerator" };
+    let wrapped = call(ast, ctx.helper(helper), vec![ast.function_expression(generator)]);
+    let mut args = vec![ast.this_expression(SPAN)];
+    if forward_arguments {
+        args.push(identifier(ast, "arguments"));
+    }
+    let method = if forward_arguments { "apply" } else { "call" };
+    let call = call(ast, member(ast, wrapped, method), args);
+    body.statements.push(ast.return_statement(SPAN, Some(call)));
+}
+
+/// `await x` -> `yield x`
+///
+/// In async generators:
+/// * `await x` -> `yield babelHelpers.awaitAsyncGenerator(x)`
+/// * `yield* x` -> `yield* babelHelpers.asyncGeneratorDelegate(babelHelpers.asyncIterator(x))`
+struct AwaitReplacer<'a, 'b> {
+    ast: &'b AstBuilder<'a>,
+    ctx: &'b TransformerCtx<'a>,
+    async_generator: bool,
+}
+
+impl<'a, 'b> VisitMut<'a> for AwaitReplacer<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        let ast = self.ast;
+        match expr {
+            Expression::AwaitExpression(await_expr) => {
+                let mut argument = ast.move_expression(&mut await_expr.argument);
+                if self.async_generator {
+                    let callee = self.ctx.helper("awaitAsyncGenerator");
+                    argument = call(ast, callee, vec![argument]);
+                }
+                *expr = ast.yield_expression(await_expr.span, false, Some(argument));
+            }
+            Expression::YieldExpression(yield_expr)
+                if self.async_generator && yield_expr.delegate =>
+            {
+                if let Some(argument) = &mut yield_expr.argument {
+                    let iterator = ast.move_expression(argument);
+                    let iterator = call(ast, self.ctx.helper("asyncIterator"), vec![iterator]);
+                    let callee = self.ctx.helper("asyncGeneratorDelegate");
+                    *argument = call(ast, callee, vec![iterator]);
+                }
+            }
+            _ => {}
+        }
+        self.visit_expression_match(expr);
+    }
+
+    // The nested async functions have already been lowered
+    fn visit_function(&mut self, _func: &mut Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &mut ArrowExpression<'a>) {}
+}
+
+/// Finds `arguments` outside of nested functions
+struct ArgumentsFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for ArgumentsFinder {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference) {
+        if ident.name == "arguments" {
+            self.found = true;
+        }
+    }
+
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: Option<ScopeFlags>) {}
+}
+
+fn identifier<'a>(ast: &AstBuilder<'a>, name: &str) -> Expression<'a> {
+    ast.identifier_reference_expression(IdentifierReference::new(SPAN, name.into()))
+}
+
+fn member<'a>(ast: &AstBuilder<'a>, object: Expression<'a>, property: &str) -> Expression<'a> {
+    ast.static_member_expression(SPAN, object, IdentifierName::new(SPAN, property.into()), false)
+}
+
+fn call<'a>(
+    ast: &AstBuilder<'a>,
+    callee: Expression<'a>,
+    args: std::vec::Vec<Expression<'a>>,
+) -> Expression<'a> {
+    let mut arguments = ast.new_vec_with_capacity(args.len());
+    arguments.extend(args.into_iter().map(Argument::Expression));
+    ast.call_expression(SPAN, callee, arguments, false, None)
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = TransformOptions { async_to_generator: true, ..TransformOptions::default() };
+    let tests = &[
+        (
+            "async function f(a) { await g(a); }",
+            "function f(a) { return babelHelpers.asyncToGenerator(function* () { yield g(a); }).apply(this, arguments); }",
+        ),
+        (
+            "const h = async () => await this.i();",
+            "const h = () => babelHelpers.asyncToGenerator(function* () { return yield this.i(); }).call(this);",
+        ),
+        (
+            "function f() { return async (x) => { await arguments[0]; }; }",
+            "function f() { return (x) => babelHelpers.asyncToGenerator(function* () { yield arguments[0]; }).apply(this, arguments); }",
+        ),
+        (
+            "const o = { async m() { const f = async () => { await 1; }; await f(); } };",
+            "const o = { m() { return babelHelpers.asyncToGenerator(function* () { const f = () => babelHelpers.asyncToGenerator(function* () { yield 1; }).call(this); yield f(); }).apply(this, arguments); } };",
+        ),
+        ("function* g() { yield 1; }", "function* g() { yield 1; }"),
+    ];
+    Tester::new("test.js", options).test(tests);
+}
@@ -0,0 +1,3 @@
+mod async_to_generator;
+
+pub use async_to_generator::{wrap_async_body, AsyncToGenerator};
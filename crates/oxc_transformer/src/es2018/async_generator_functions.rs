@@ -0,0 +1,344 @@
+use std::rc::Rc;
+
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator};
+
+use crate::{
+    context::TransformerCtx,
+    es2017::wrap_async_body,
+    options::{TransformOptions, TransformTarget},
+};
+
+/// ES2018: Async Generator Functions
+///
+/// Async generators become generators driven by `babelHelpers.wrapAsyncGenerator`:
+///
+/// ```JavaScript
+/// async function* f() {
+///   yield await g();
+/// }
+/// ```
+/// ```JavaScript
+/// function f() {
+///   return babelHelpers.wrapAsyncGenerator(function* () {
+///     yield yield babelHelpers.awaitAsyncGenerator(g());
+///   }).apply(this, arguments);
+/// }
+/// ```
+///
+/// `for await` loops iterate with `await` over `babelHelpers.asyncIterator`, and close the
+/// iterator when the loop is exited early:
+///
+/// ```JavaScript
+/// for await (const x of y) f(x);
+/// ```
+/// ```JavaScript
+/// {
+///   var _iteratorAbruptCompletion = false;
+///   var _didIteratorError = false;
+///   var _iteratorError;
+///   try {
+///     for (var _iterator = babelHelpers.asyncIterator(y), _step; _iteratorAbruptCompletion = !(_step = await _iterator.next()).done; _iteratorAbruptCompletion = false) {
+///       const x = _step.value;
+///       f(x);
+///     }
+///   } catch (_err) {
+///     _didIteratorError = true;
+///     _iteratorError = _err;
+///   } finally {
+///     try {
+///       if (_iteratorAbruptCompletion && _iterator.return != null) {
+///         await _iterator.return();
+///       }
+///     } finally {
+///       if (_didIteratorError) {
+///         throw _iteratorError;
+///       }
+///     }
+///   }
+/// }
+/// ```
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-transform-async-generator-functions>
+/// * <https://github.com/babel/babel/tree/main/packages/babel-plugin-transform-async-generator-functions>
+pub struct AsyncGeneratorFunctions<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+}
+
+impl<'a> AsyncGeneratorFunctions<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        (options.target < TransformTarget::ES2018 || options.async_generator_functions)
+            .then(|| Self { ast, ctx })
+    }
+
+    pub fn transform_function(&mut self, func: &mut Function<'a>) {
+        if !func.r#async || !func.generator {
+            return;
+        }
+        let Some(body) = &mut func.body else { return };
+        func.r#async = false;
+        func.generator = false;
+        wrap_async_body(&self.ast, &mut self.ctx, body, true, true);
+    }
+
+    pub fn transform_statement(&mut self, stmt: &mut Statement<'a>) {
+        match stmt {
+            Statement::ForOfStatement(for_of) if for_of.r#await => {
+                let Statement::ForOfStatement(for_of) = self.ast.move_statement(stmt) else {
+                    unreachable!()
+                };
+                *stmt = self.transform_for_await(for_of.unbox(), None);
+            }
+            // The label is kept on the loop for `continue label`
+            Statement::LabeledStatement(labeled) => {
+                if !matches!(&labeled.body, Statement::ForOfStatement(for_of) if for_of.r#await) {
+                    return;
+                }
+                let Statement::LabeledStatement(labeled) = self.ast.move_statement(stmt) else {
+                    unreachable!()
+                };
+                let labeled = labeled.unbox();
+                let Statement::ForOfStatement(for_of) = labeled.body else { unreachable!() };
+                *stmt = self.transform_for_await(for_of.unbox(), Some(labeled.label));
+            }
+            _ => {}
+        }
+    }
+
+    fn transform_for_await(
+        &mut self,
+        for_of: ForOfStatement<'a>,
+        label: Option<LabelIdentifier>,
+    ) -> Statement<'a> {
+        let abrupt_completion = self.generate_uid("iteratorAbruptCompletion");
+        let did_iterator_error = self.generate_uid("didIteratorError");
+        let iterator_error = self.generate_uid("iteratorError");
+        let iterator = self.generate_uid("iterator");
+        let step = self.generate_uid("step");
+        let err = self.generate_uid("err");
+
+        // `const x = _step.value; body`
+        let value = self.member(self.identifier(&step), "value");
+        let mut body = self.ast.new_vec();
+        body.push(match for_of.left {
+            ForStatementLeft::VariableDeclaration(mut decl) => {
+                if let Some(declarator) = decl.declarations.iter_mut().next() {
+                    declarator.init = Some(value);
+                }
+                Statement::Declaration(Declaration::VariableDeclaration(decl))
+            }
+            ForStatementLeft::UsingDeclaration(mut decl) => {
+                if let Some(declarator) = decl.declarations.iter_mut().next() {
+                    declarator.init = Some(value);
+                }
+                Statement::Declaration(Declaration::UsingDeclaration(decl))
+            }
+            ForStatementLeft::AssignmentTarget(target) => self.ast.expression_statement(
+                SPAN,
+                self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, target, value),
+            ),
+        });
+        body.push(for_of.body);
+
+        // `for (var _iterator = babelHelpers.asyncIterator(y), _step; _iteratorAbruptCompletion = !(_step = await _iterator.next()).done; _iteratorAbruptCompletion = false)`
+        let mut declarations = self.ast.new_vec();
+        let async_iterator = self.call(self.helper("asyncIterator"), vec![for_of.right]);
+        declarations.push(self.declarator(&iterator, Some(async_iterator)));
+        declarations.push(self.declarator(&step, None));
+        let init = self.ast.variable_declaration(
+            SPAN,
+            VariableDeclarationKind::Var,
+            declarations,
+            Modifiers::empty(),
+        );
+        let next = self.call(self.member(self.identifier(&iterator), "next"), vec![]);
+        let step_result = self.assign(&step, self.ast.await_expression(SPAN, next));
+        let done = self.member(self.ast.parenthesized_expression(SPAN, step_result), "done");
+        let test = self.assign(
+            &abrupt_completion,
+            self.ast.unary_expression(SPAN, UnaryOperator::LogicalNot, done),
+        );
+        let update = self.assign(&abrupt_completion, self.boolean(false));
+        let mut for_stmt = self.ast.for_statement(
+            for_of.span,
+            Some(ForStatementInit::VariableDeclaration(init)),
+            Some(test),
+            Some(update),
+            self.block(body),
+        );
+        if let Some(label) = label {
+            for_stmt = self.ast.labeled_statement(SPAN, label, for_stmt);
+        }
+
+        // `catch (err) { _didIteratorError = true; _iteratorError = err; }`
+        let mut catch_body = self.ast.new_vec();
+        catch_body.push(self.assign_statement(&did_iterator_error, self.boolean(true)));
+        catch_body.push(self.assign_statement(&iterator_error, self.identifier(&err)));
+        let param = self.ast.binding_pattern(
+            self.ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, err)),
+            None,
+            false,
+        );
+        let handler = self.ast.catch_clause(SPAN, Some(param), self.block_statement(catch_body));
+
+        // `if (_iteratorAbruptCompletion && _iterator.return != null) { await _iterator.return(); }`
+        let return_method = self.member(self.identifier(&iterator), "return");
+        let has_return = self.ast.binary_expression(
+            SPAN,
+            return_method,
+            BinaryOperator::Inequality,
+            self.ast.literal_null_expression(NullLiteral::new(SPAN)),
+        );
+        let close_test = self.ast.logical_expression(
+            SPAN,
+            self.identifier(&abrupt_completion),
+            LogicalOperator::And,
+            has_return,
+        );
+        let close = self.call(self.member(self.identifier(&iterator), "return"), vec![]);
+        let close = self.ast.expression_statement(SPAN, self.ast.await_expression(SPAN, close));
+        let close = self.ast.if_statement(
+            SPAN,
+            close_test,
+            self.block(self.ast.new_vec_single(close)),
+            None,
+        );
+        // `if (_didIteratorError) { throw _iteratorError; }`
+        let rethrow = self.ast.throw_statement(SPAN, self.identifier(&iterator_error));
+        let rethrow = self.ast.if_statement(
+            SPAN,
+            self.identifier(&did_iterator_error),
+            self.block(self.ast.new_vec_single(rethrow)),
+            None,
+        );
+        let finalizer = self.ast.try_statement(
+            SPAN,
+            self.block_statement(self.ast.new_vec_single(close)),
+            None,
+            Some(self.block_statement(self.ast.new_vec_single(rethrow))),
+        );
+
+        let mut stmts = self.ast.new_vec();
+        stmts.push(self.var_statement(&abrupt_completion, Some(self.boolean(false))));
+        stmts.push(self.var_statement(&did_iterator_error, Some(self.boolean(false))));
+        stmts.push(self.var_statement(&iterator_error, None));
+        stmts.push(self.ast.try_statement(
+            SPAN,
+            self.block_statement(self.ast.new_vec_single(for_stmt)),
+            Some(handler),
+            Some(self.block_statement(self.ast.new_vec_single(finalizer))),
+        ));
+        self.block(stmts)
+    }
+
+    fn generate_uid(&self, name: &str) -> Atom {
+        let name = self.ctx.scopes().generate_uid(name);
+        self.ctx.add_binding(name.clone());
+        name
+    }
+
+    fn declarator(&self, name: &Atom, init: Option<Expression<'a>>) -> VariableDeclarator<'a> {
+        let binding = self.ast.binding_pattern(
+            self.ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, name.clone())),
+            None,
+            false,
+        );
+        self.ast.variable_declarator(SPAN, VariableDeclarationKind::Var, binding, init, false)
+    }
+
+    /// `var name = init;`
+    fn var_statement(&self, name: &Atom, init: Option<Expression<'a>>) -> Statement<'a> {
+        let decl = self.ast.variable_declaration(
+            SPAN,
+            VariableDeclarationKind::Var,
+            self.ast.new_vec_single(self.declarator(name, init)),
+            Modifiers::empty(),
+        );
+        Statement::Declaration(Declaration::VariableDeclaration(decl))
+    }
+
+    fn assign(&self, name: &Atom, value: Expression<'a>) -> Expression<'a> {
+        let target = self
+            .ast
+            .simple_assignment_target_identifier(IdentifierReference::new(SPAN, name.clone()));
+        self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            value,
+        )
+    }
+
+    fn assign_statement(&self, name: &Atom, value: Expression<'a>) -> Statement<'a> {
+        self.ast.expression_statement(SPAN, self.assign(name, value))
+    }
+
+    fn block_statement(
+        &self,
+        body: Vec<'a, Statement<'a>>,
+    ) -> oxc_allocator::Box<'a, BlockStatement<'a>> {
+        self.ast.alloc(BlockStatement { span: SPAN, body })
+    }
+
+    fn block(&self, body: Vec<'a, Statement<'a>>) -> Statement<'a> {
+        self.ast.block_statement(self.block_statement(body))
+    }
+
+    fn boolean(&self, value: bool) -> Expression<'a> {
+        self.ast.literal_boolean_expression(BooleanLiteral { span: SPAN, value })
+    }
+
+    fn helper(&self, name: &str) -> Expression<'a> {
+        self.ctx.helper(name)
+    }
+
+    fn identifier(&self, name: &Atom) -> Expression<'a> {
+        self.ast.identifier_reference_expression(IdentifierReference::new(SPAN, name.clone()))
+    }
+
+    fn member(&self, object: Expression<'a>, property: &str) -> Expression<'a> {
+        self.ast.static_member_expression(
+            SPAN,
+            object,
+            IdentifierName::new(SPAN, property.into()),
+            false,
+        )
+    }
+
+    fn call(&self, callee: Expression<'a>, args: std::vec::Vec<Expression<'a>>) -> Expression<'a> {
+        let mut arguments = self.ast.new_vec_with_capacity(args.len());
+        arguments.extend(args.into_iter().map(Argument::Expression));
+        self.ast.call_expression(SPAN, callee, arguments, false, None)
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options =
+        TransformOptions { async_generator_functions: true, ..TransformOptions::default() };
+    let tests = &[
+        (
+            "async function* f() { yield await g(); yield* h(); }",
+            "function f() { return babelHelpers.wrapAsyncGenerator(function* () { yield yield babelHelpers.awaitAsyncGenerator(g()); yield* babelHelpers.asyncGeneratorDelegate(babelHelpers.asyncIterator(h())); }).apply(this, arguments); }",
+        ),
+        (
+            "async function f() { for await (const x of y) g(x); }",
+            "async function f() { { var _iteratorAbruptCompletion = false; var _didIteratorError = false; var _iteratorError; try { for (var _iterator = babelHelpers.asyncIterator(y), _step; _iteratorAbruptCompletion = !(_step = await _iterator.next()).done; _iteratorAbruptCompletion = false) { const x = _step.value; g(x); } } catch (_err) { _didIteratorError = true; _iteratorError = _err; } finally { try { if (_iteratorAbruptCompletion && _iterator.return != null) { await _iterator.return(); } } finally { if (_didIteratorError) { throw _iteratorError; } } } } }",
+        ),
+        (
+            "async function* f() { outer: for await (x of y) { continue outer; } }",
+            "function f() { return babelHelpers.wrapAsyncGenerator(function* () { { var _iteratorAbruptCompletion = false; var _didIteratorError = false; var _iteratorError; try { outer: for (var _iterator = babelHelpers.asyncIterator(y), _step; _iteratorAbruptCompletion = !(_step = yield babelHelpers.awaitAsyncGenerator(_iterator.next())).done; _iteratorAbruptCompletion = false) { x = _step.value; { continue outer; } } } catch (_err) { _didIteratorError = true; _iteratorError = _err; } finally { try { if (_iteratorAbruptCompletion && _iterator.return != null) { yield babelHelpers.awaitAsyncGenerator(_iterator.return()); } } finally { if (_didIteratorError) { throw _iteratorError; } } } } }).apply(this, arguments); }",
+        ),
+    ];
+    Tester::new("test.js", options).test(tests);
+}
@@ -31,7 +31,7 @@ pub struct NullishCoalescingOperator<'a> {
     ast: Rc<AstBuilder<'a>>,
     ctx: TransformerCtx<'a>,
 
-    vars: Vec<'a, VariableDeclarator<'a>>,
+    vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
 }
 
 impl<'a> CreateVars<'a> for NullishCoalescingOperator<'a> {
@@ -39,7 +39,7 @@ impl<'a> CreateVars<'a> for NullishCoalescingOperator<'a> {
         &self.ctx
     }
 
-    fn vars_mut(&mut self) -> &mut Vec<'a, VariableDeclarator<'a>> {
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>> {
         &mut self.vars
     }
 }
@@ -54,7 +54,7 @@ impl<'a> NullishCoalescingOperator<'a> {
             .then(|| {
                 let no_document_all = options.assumptions.no_document_all
                     || options.nullish_coalescing_operator.is_some_and(|o| o.loose);
-                let vars = ast.new_vec();
+                let vars = vec![];
                 Self { no_document_all, ast, ctx, vars }
             })
     }
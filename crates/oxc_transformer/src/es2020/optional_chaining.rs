@@ -0,0 +1,349 @@
+use serde::Deserialize;
+use std::rc::Rc;
+
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::SPAN;
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator};
+
+use crate::{
+    context::TransformerCtx,
+    options::{TransformOptions, TransformTarget},
+    utils::CreateVars,
+};
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct OptionalChainingOptions {
+    /// When true, this transform will pretend `document.all` does not exist,
+    /// and perform loose equality checks with null instead of strict equality checks against both null and undefined.
+    #[serde(default)]
+    loose: bool,
+}
+
+/// ES2020: Optional Chaining
+///
+/// ```JavaScript
+/// a?.b.c?.();
+/// ```
+/// ```JavaScript
+/// var _a$b, _a$b$c;
+/// a === null || a === void 0 ? void 0 : (_a$b$c = (_a$b = a.b).c) === null || _a$b$c === void 0 ? void 0 : _a$b$c.call(_a$b);
+/// ```
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-transform-optional-chaining>
+/// * <https://github.com/babel/babel/tree/main/packages/babel-plugin-transform-optional-chaining>
+pub struct OptionalChaining<'a> {
+    no_document_all: bool,
+
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+
+    vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
+}
+
+impl<'a> CreateVars<'a> for OptionalChaining<'a> {
+    fn ctx(&self) -> &TransformerCtx<'a> {
+        &self.ctx
+    }
+
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>> {
+        &mut self.vars
+    }
+}
+
+impl<'a> OptionalChaining<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        (options.target < TransformTarget::ES2020 || options.optional_chaining.is_some()).then(
+            || {
+                let no_document_all = options.assumptions.no_document_all
+                    || options.optional_chaining.is_some_and(|o| o.loose);
+                Self { no_document_all, ast, ctx, vars: vec![] }
+            },
+        )
+    }
+
+    pub fn transform_expression(&mut self, expr: &mut Expression<'a>) {
+        match expr {
+            Expression::ChainExpression(_) => {
+                let chain = self.chain_element_expression(expr);
+                *expr = self.transform_chain(chain, None);
+            }
+            // `delete a?.b` -> `a == null ? true : delete a.b`
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::Delete
+                    && matches!(unary_expr.argument, Expression::ChainExpression(_)) =>
+            {
+                let chain = self.chain_element_expression(&mut unary_expr.argument);
+                let delete = self.ast.unary_expression(SPAN, UnaryOperator::Delete, chain);
+                let short_circuit =
+                    self.ast.literal_boolean_expression(BooleanLiteral::new(SPAN, true));
+                *expr = self.transform_chain(delete, Some(short_circuit));
+            }
+            // `(a?.b)()` -> `(a == null ? void 0 : a.b).call(a)`
+            Expression::CallExpression(call) => {
+                if let Some((callee, this_arg)) = self.transform_chain_callee(&mut call.callee) {
+                    let property = IdentifierName::new(SPAN, "call".into());
+                    call.callee = self.ast.static_member_expression(SPAN, callee, property, false);
+                    call.arguments.insert(0, Argument::Expression(this_arg));
+                }
+            }
+            // ``(a?.b)`x` `` -> ``(a == null ? void 0 : a.b).bind(a)`x` ``, the template object
+            // is kept for the tag
+            Expression::TaggedTemplateExpression(tagged) => {
+                if let Some((tag, this_arg)) = self.transform_chain_callee(&mut tagged.tag) {
+                    let property = IdentifierName::new(SPAN, "bind".into());
+                    let bind = self.ast.static_member_expression(SPAN, tag, property, false);
+                    let arguments = self.ast.new_vec_single(Argument::Expression(this_arg));
+                    tagged.tag = self.ast.call_expression(SPAN, bind, arguments, false, None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Lower a parenthesized chain ending with a member, which is called with the object of the
+    /// member as `this`.
+    ///
+    /// Returns the lowered chain and the reference to its `this`.
+    fn transform_chain_callee(
+        &mut self,
+        callee: &mut Expression<'a>,
+    ) -> Option<(Expression<'a>, Expression<'a>)> {
+        let Expression::ParenthesizedExpression(paren) = callee else { return None };
+        if !matches!(&paren.expression, Expression::ChainExpression(chain) if matches!(chain.expression, ChainElement::MemberExpression(_)))
+        {
+            return None;
+        }
+        let chain = self.chain_element_expression(&mut paren.expression);
+        let mut expr = self.transform_chain(chain, None);
+        let Expression::MemberExpression(member) = last_alternate(&mut expr) else {
+            unreachable!()
+        };
+        let this_arg = self.memoise_object(member);
+        Some((self.ast.parenthesized_expression(SPAN, expr), this_arg))
+    }
+
+    /// Move the chain out of the [`ChainExpression`], the optional flags are kept.
+    fn chain_element_expression(&self, expr: &mut Expression<'a>) -> Expression<'a> {
+        let Expression::ChainExpression(chain) = self.ast.move_expression(expr) else {
+            unreachable!()
+        };
+        match chain.unbox().expression {
+            ChainElement::CallExpression(call) => Expression::CallExpression(call),
+            ChainElement::MemberExpression(member) => Expression::MemberExpression(member),
+        }
+    }
+
+    /// Lower the optional links of `expr` from the innermost one outwards.
+    ///
+    /// `expr` evaluates to `short_circuit` (`void 0` by default) when a link is nullish.
+    fn transform_chain(
+        &mut self,
+        mut expr: Expression<'a>,
+        short_circuit: Option<Expression<'a>>,
+    ) -> Expression<'a> {
+        let link = match &mut expr {
+            Expression::UnaryExpression(unary_expr) => {
+                self.split_innermost(&mut unary_expr.argument)
+            }
+            expr => self.split_innermost(expr),
+        };
+        let Some(test) = link else { return expr };
+        let short_circuit_copy = short_circuit.as_ref().map(|e| self.ast.copy(e));
+        let consequent = short_circuit.unwrap_or_else(|| self.ast.void_0());
+        let alternate = self.transform_chain(expr, short_circuit_copy);
+        self.ast.conditional_expression(SPAN, test, consequent, alternate)
+    }
+
+    /// Replace the object of the innermost optional link of the chain `expr` with a reference and
+    /// clear its optional flag.
+    ///
+    /// Returns the nullish check of the object.
+    fn split_innermost(&mut self, expr: &mut Expression<'a>) -> Option<Expression<'a>> {
+        let inner = match expr {
+            Expression::MemberExpression(member) => member.object_mut(),
+            Expression::CallExpression(call) => &mut call.callee,
+            _ => return None,
+        };
+        if let Some(test) = self.split_innermost(inner) {
+            return Some(test);
+        }
+
+        match expr {
+            // `a?.b` -> `a == null ? void 0 : a.b`
+            Expression::MemberExpression(member) if member.optional() => {
+                set_optional(member, false);
+                let object = self.ast.move_expression(member.object_mut());
+                let (test, reference) = self.nullish_check(object);
+                *member.object_mut() = reference;
+                Some(test)
+            }
+            Expression::CallExpression(call) if call.optional => {
+                call.optional = false;
+                let callee = self.ast.move_expression(&mut call.callee);
+                let Expression::MemberExpression(mut member) = callee else {
+                    // `a?.()` -> `a == null ? void 0 : a()`
+                    let (test, reference) = self.nullish_check(callee);
+                    call.callee = reference;
+                    return Some(test);
+                };
+
+                // `a.b?.()` -> `(_a$b = a.b) == null ? void 0 : _a$b.call(a)`
+                let this_arg = self.memoise_object(&mut member);
+                let (test, reference) = self.nullish_check(Expression::MemberExpression(member));
+                let property = IdentifierName::new(SPAN, "call".into());
+                call.callee = self.ast.static_member_expression(SPAN, reference, property, false);
+                call.arguments.insert(0, Argument::Expression(this_arg));
+                Some(test)
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace the object of `member` with an assignment to a new variable unless it is static.
+    ///
+    /// Returns the reference to the object for the `this` of a call of `member`.
+    fn memoise_object(&mut self, member: &mut MemberExpression<'a>) -> Expression<'a> {
+        let object = self.ast.move_expression(member.object_mut());
+        if matches!(object, Expression::Super(_)) {
+            *member.object_mut() = object;
+            self.ast.this_expression(SPAN)
+        } else if self.is_static(&object) {
+            *member.object_mut() = self.ast.copy(&object);
+            object
+        } else {
+            let ident = self.create_new_var(&object);
+            *member.object_mut() = self.assign(ident.clone(), object);
+            self.ast.identifier_reference_expression(ident)
+        }
+    }
+
+    /// Whether `expr` evaluates to the same value each time without side effects, `import.meta`
+    /// and `new.target` are static like the bindings which are never assigned
+    fn is_static(&self, expr: &Expression<'a>) -> bool {
+        matches!(expr, Expression::Identifier(_) | Expression::MetaProperty(_))
+            || self.ctx.symbols().is_static(expr)
+    }
+
+    /// Returns the check whether `expr` is nullish, and a reference to the value of `expr`.
+    ///
+    /// * `(_a = a) === null || _a === void 0`
+    /// * `(_a = a) == null` with `noDocumentAll`
+    fn nullish_check(&mut self, expr: Expression<'a>) -> (Expression<'a>, Expression<'a>) {
+        // skip creating extra reference when `expr` is an identifier or static
+        let (assignment, reference) = if self.is_static(&expr) {
+            (self.ast.copy(&expr), expr)
+        } else {
+            let ident = self.create_new_var(&expr);
+            let reference = self.ast.identifier_reference_expression(ident.clone());
+            (self.assign(ident, expr), reference)
+        };
+
+        let null = self.ast.literal_null_expression(NullLiteral::new(SPAN));
+        let test = if self.no_document_all {
+            self.ast.binary_expression(SPAN, assignment, BinaryOperator::Equality, null)
+        } else {
+            let op = BinaryOperator::StrictEquality;
+            let left = self.ast.binary_expression(SPAN, assignment, op, null);
+            let right =
+                self.ast.binary_expression(SPAN, self.ast.copy(&reference), op, self.ast.void_0());
+            self.ast.logical_expression(SPAN, left, LogicalOperator::Or, right)
+        };
+        (test, reference)
+    }
+
+    /// `ident = expr`
+    fn assign(&self, ident: IdentifierReference, expr: Expression<'a>) -> Expression<'a> {
+        let target = self.ast.simple_assignment_target_identifier(ident);
+        let assignment = self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            expr,
+        );
+        self.ast.parenthesized_expression(SPAN, assignment)
+    }
+}
+
+/// The expression evaluated when none of the links of a lowered chain is nullish
+fn last_alternate<'b, 'a>(expr: &'b mut Expression<'a>) -> &'b mut Expression<'a> {
+    match expr {
+        Expression::ConditionalExpression(conditional) => {
+            last_alternate(&mut conditional.alternate)
+        }
+        expr => expr,
+    }
+}
+
+fn set_optional(member: &mut MemberExpression, optional: bool) {
+    match member {
+        MemberExpression::ComputedMemberExpression(expr) => expr.optional = optional,
+        MemberExpression::StaticMemberExpression(expr) => expr.optional = optional,
+        MemberExpression::PrivateFieldExpression(expr) => expr.optional = optional,
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = |loose| TransformOptions {
+        optional_chaining: Some(OptionalChainingOptions { loose }),
+        ..TransformOptions::default()
+    };
+
+    let tests = &[
+        ("a?.b;", "a === null || a === void 0 ? void 0 : a.b;"),
+        (
+            "foo()?.b.c?.d;",
+            "var _ref, _ref$b$c; (_ref = foo()) === null || _ref === void 0 ? void 0 : (_ref$b$c = _ref.b.c) === null || _ref$b$c === void 0 ? void 0 : _ref$b$c.d;",
+        ),
+        (
+            "a.b?.(c);",
+            "var _a$b; (_a$b = a.b) === null || _a$b === void 0 ? void 0 : _a$b.call(a, c);",
+        ),
+        ("delete a?.b;", "a === null || a === void 0 ? true : delete a.b;"),
+        (
+            "function f() { return () => g()?.x; }",
+            "function f() { var _ref; return () => (_ref = g()) === null || _ref === void 0 ? void 0 : _ref.x; }",
+        ),
+    ];
+    Tester::new("test.js", options(false)).test(tests);
+
+    let tests = &[
+        ("(a?.b)();", "(a === null || a === void 0 ? void 0 : a.b).call(a);"),
+        (
+            "(a.b?.c)(1);",
+            "var _a$b; ((_a$b = a.b) === null || _a$b === void 0 ? void 0 : _a$b.c).call(_a$b, 1);",
+        ),
+        ("(a?.b)`x${c}`;", "(a === null || a === void 0 ? void 0 : a.b).bind(a)`x${c}`;"),
+        (
+            "(foo()?.b)`x`;",
+            "var _ref; ((_ref = foo()) === null || _ref === void 0 ? void 0 : _ref.b).bind(_ref)`x`;",
+        ),
+        (
+            "import.meta?.url;",
+            "import.meta === null || import.meta === void 0 ? void 0 : import.meta.url;",
+        ),
+        (
+            "import.meta.resolve?.(x);",
+            "var _import$meta$resolve; (_import$meta$resolve = import.meta.resolve) === null || _import$meta$resolve === void 0 ? void 0 : _import$meta$resolve.call(import.meta, x);",
+        ),
+        (
+            "import { f } from 'm'; f?.(); f.g?.(); (f?.g)``;",
+            "var _f$g; import { f } from 'm'; f === null || f === void 0 ? void 0 : f(); (_f$g = f.g) === null || _f$g === void 0 ? void 0 : _f$g.call(f); (f === null || f === void 0 ? void 0 : f.g).bind(f)``;",
+        ),
+    ];
+    Tester::new("test.mjs", options(false)).test(tests);
+
+    let loose_tests = &[
+        ("(a?.b).c;", "(a == null ? void 0 : a.b).c;"),
+        ("a()?.b?.();", "var _ref, _ref$b; (_ref = a()) == null ? void 0 : (_ref$b = _ref.b) == null ? void 0 : _ref$b.call(_ref);"),
+    ];
+    Tester::new("test.js", options(true)).test(loose_tests);
+}
@@ -20,7 +20,7 @@ pub struct LogicalAssignmentOperators<'a> {
     ast: Rc<AstBuilder<'a>>,
     ctx: TransformerCtx<'a>,
 
-    vars: Vec<'a, VariableDeclarator<'a>>,
+    vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
 }
 
 impl<'a> CreateVars<'a> for LogicalAssignmentOperators<'a> {
@@ -28,7 +28,7 @@ impl<'a> CreateVars<'a> for LogicalAssignmentOperators<'a> {
         &self.ctx
     }
 
-    fn vars_mut(&mut self) -> &mut Vec<'a, VariableDeclarator<'a>> {
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>> {
         &mut self.vars
     }
 }
@@ -41,7 +41,7 @@ impl<'a> LogicalAssignmentOperators<'a> {
     ) -> Option<Self> {
         (options.target < TransformTarget::ES2021 || options.logical_assignment_operators).then(
             || {
-                let vars = ast.new_vec();
+                let vars = vec![];
                 Self { ast, ctx, vars }
             },
         )
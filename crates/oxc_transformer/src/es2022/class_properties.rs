@@ -0,0 +1,998 @@
+use std::{collections::HashMap, mem, rc::Rc};
+
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, AstBuilder, Visit, VisitMut};
+use oxc_diagnostics::miette::{self, Diagnostic};
+use oxc_diagnostics::thiserror::Error;
+use oxc_semantic::{ScopeFlags, SymbolId};
+use oxc_span::{Atom, Span, SPAN};
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator};
+use serde::Deserialize;
+
+use crate::{
+    context::TransformerCtx,
+    options::{TransformOptions, TransformTarget},
+    utils::{insert_constructor_inits, statement_class, CreateVars, SuperFinder},
+};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error(
+    "`super` is not supported in private methods and static initializers moved out of the class."
+)]
+#[diagnostic(help("Move the use of `super` into a public method."))]
+struct SuperOutsideClass(#[label] Span);
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct ClassPropertiesOptions {
+    /// When true, public fields are assigned instead of defined with `Object.defineProperty`,
+    /// and private members are stored in non-enumerable properties instead of `WeakMap`s.
+    #[serde(default)]
+    loose: bool,
+}
+
+/// ES2022: Class Properties and Private Methods
+///
+/// Instance fields are initialized in the constructor and static fields and blocks run after
+/// the class. Private members are stored in a `WeakMap` per name, which maps each instance to a
+/// descriptor whose `value` property holds the member.
+///
+/// ```JavaScript
+/// class Foo {
+///   #x = 1;
+///   static y = 2;
+///   getX() { return this.#x; }
+/// }
+/// ```
+/// ```JavaScript
+/// var _x = new WeakMap();
+/// class Foo {
+///   constructor() { _x.set(this, { value: 1 }); }
+///   getX() { return _x.get(this).value; }
+/// }
+/// Object.defineProperty(Foo, "y", { enumerable: true, configurable: true, writable: true, value: 2 });
+/// ```
+///
+/// References:
+/// * <https://babel.dev/docs/babel-plugin-transform-class-properties>
+/// * <https://babel.dev/docs/babel-plugin-transform-private-methods>
+/// * <https://github.com/babel/babel/tree/main/packages/babel-helper-create-class-features-plugin>
+pub struct ClassProperties<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+
+    set_public_class_fields: bool,
+    private_fields_as_properties: bool,
+
+    vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
+    /// Makes the property keys of private members unique in loose mode
+    private_key_uid: usize,
+}
+
+impl<'a> CreateVars<'a> for ClassProperties<'a> {
+    fn ctx(&self) -> &TransformerCtx<'a> {
+        &self.ctx
+    }
+
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>> {
+        &mut self.vars
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PrivateMember {
+    r#static: bool,
+    /// The `WeakMap` or descriptor variable, or the property key in loose mode
+    binding: Atom,
+}
+
+/// The code emitted around a lowered class
+#[derive(Default)]
+struct ClassOutput<'a> {
+    /// Variables initialized before the class
+    before: std::vec::Vec<(Atom, Expression<'a>)>,
+    /// Static initialization after the class, optionally assigned to a new variable
+    after: std::vec::Vec<(Option<Atom>, Expression<'a>)>,
+}
+
+impl<'a> ClassProperties<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        (options.target < TransformTarget::ES2022 || options.class_properties.is_some()).then(
+            || {
+                let loose = options.class_properties.is_some_and(|o| o.loose);
+                Self {
+                    ast,
+                    ctx,
+                    set_public_class_fields: loose || options.assumptions.set_public_class_fields,
+                    private_fields_as_properties: loose
+                        || options.assumptions.private_fields_as_properties,
+                    vars: vec![],
+                    private_key_uid: 0,
+                }
+            },
+        )
+    }
+
+    /// Lower the class declarations of `stmts`
+    pub fn transform_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if !stmts.iter().any(|stmt| statement_class(stmt).is_some_and(needs_transform)) {
+            return;
+        }
+
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            let output = match &mut stmt {
+                Statement::Declaration(Declaration::ClassDeclaration(class)) => {
+                    self.transform_class_declaration(class)
+                }
+                Statement::ModuleDeclaration(decl) => match &mut **decl {
+                    ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                        match &mut decl.declaration {
+                            Some(Declaration::ClassDeclaration(class)) => {
+                                self.transform_class_declaration(class)
+                            }
+                            _ => None,
+                        }
+                    }
+                    ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                        match &mut decl.declaration {
+                            ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                                self.transform_class_declaration(class)
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            let Some(output) = output else {
+                stmts.push(stmt);
+                continue;
+            };
+            for (name, init) in output.before {
+                stmts.push(self.var_statement(name, init));
+            }
+            stmts.push(stmt);
+            for (name, expr) in output.after {
+                let stmt = match name {
+                    Some(name) => self.var_statement(name, expr),
+                    None => self.ast.expression_statement(SPAN, expr),
+                };
+                stmts.push(stmt);
+            }
+        }
+    }
+
+    /// ```JavaScript
+    /// let Foo = class { #x = 1; static #y = 2; }
+    /// ```
+    /// ```JavaScript
+    /// var _x, _Class, _y;
+    /// let Foo = (_x = new WeakMap(), _Class = class { constructor() { _x.set(this, { value: 1 }); } }, _y = { value: 2 }, _Class);
+    /// ```
+    pub fn transform_expression(&mut self, expr: &mut Expression<'a>) {
+        let Expression::ClassExpression(class) = expr else { return };
+        if !needs_transform(class) {
+            return;
+        }
+
+        let class_ref = has_static_members(class).then(|| self.generate_uid("Class"));
+        let inner_symbol = class.id.as_ref().and_then(|id| id.symbol_id.get());
+        let output = self.transform_class(class, class_ref.as_ref(), inner_symbol);
+
+        let class_expr = self.ast.move_expression(expr);
+        let mut exprs = self.ast.new_vec();
+        for (name, init) in output.before {
+            exprs.push(self.assign_new_var(name, init));
+        }
+        if let Some(class_ref) = class_ref {
+            exprs.push(self.assign_new_var(class_ref.clone(), class_expr));
+            for (name, expr) in output.after {
+                exprs.push(match name {
+                    Some(name) => self.assign_new_var(name, expr),
+                    None => expr,
+                });
+            }
+            exprs.push(self.identifier(&class_ref));
+        } else {
+            exprs.push(class_expr);
+        }
+
+        *expr = if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            let sequence = self.ast.sequence_expression(SPAN, exprs);
+            self.ast.parenthesized_expression(SPAN, sequence)
+        };
+    }
+
+    fn transform_class_declaration(&mut self, class: &mut Class<'a>) -> Option<ClassOutput<'a>> {
+        if !needs_transform(class) {
+            return None;
+        }
+        // `export default class {}` needs a name for its static members
+        let class_ref = match &class.id {
+            Some(id) => id.name.clone(),
+            None => {
+                let name = self.generate_uid("default");
+                class.id = Some(BindingIdentifier::new(SPAN, name.clone()));
+                name
+            }
+        };
+        Some(self.transform_class(class, Some(&class_ref), None))
+    }
+
+    /// Move the fields, static blocks and private methods out of `class`.
+    ///
+    /// `class_ref` refers to the class after it is defined and is present if the class has static
+    /// members. `inner_symbol` is the name of a class expression, which is not visible in the code
+    /// moved out of the class.
+    fn transform_class(
+        &mut self,
+        class: &mut Class<'a>,
+        class_ref: Option<&Atom>,
+        inner_symbol: Option<SymbolId>,
+    ) -> ClassOutput<'a> {
+        let mut output = ClassOutput::default();
+        let members = self.collect_private_members(&class.body, &mut output);
+
+        // Private methods are installed before the fields are initialized
+        let mut instance_private_inits = vec![];
+        let mut instance_inits = vec![];
+        let mut static_private_inits = vec![];
+        let mut static_inits = vec![];
+        // The getter and setter of each private accessor
+        let mut accessors: std::vec::Vec<(Atom, Option<Atom>, Option<Atom>)> = vec![];
+
+        let old_elements = mem::replace(&mut class.body.body, self.ast.new_vec());
+        for element in old_elements {
+            match element {
+                ClassElement::PropertyDefinition(def) if !def.declare => {
+                    let def = def.unbox();
+                    if def.r#static {
+                        if let Some(value) = &def.value {
+                            self.check_super(|finder| finder.visit_expression(value));
+                        }
+                        let value = def.value.map(|value| {
+                            self.replace_class_references(value, class_ref, inner_symbol, true)
+                        });
+                        let object = self.class_reference(class_ref);
+                        let init = self.field_init(&members, object, def.key, value, &mut output);
+                        static_inits.push(init);
+                    } else {
+                        let object = self.ast.this_expression(SPAN);
+                        let (_, init) =
+                            self.field_init(&members, object, def.key, def.value, &mut output);
+                        instance_inits.push(init);
+                    }
+                }
+                ClassElement::StaticBlock(mut block) => {
+                    self.check_super(|finder| finder.visit_statements(&block.body));
+                    if let Some(expr) = self.static_block_expression(&mut block.body) {
+                        let expr =
+                            self.replace_class_references(expr, class_ref, inner_symbol, true);
+                        static_inits.push((None, expr));
+                    }
+                }
+                ClassElement::MethodDefinition(def) if def.key.is_private_identifier() => {
+                    let def = def.unbox();
+                    let name = def.key.private_name().unwrap();
+                    let member = &members[&name];
+                    let mut func = def.value;
+                    self.check_super(|finder| {
+                        finder.visit_formal_parameters(&func.params);
+                        if let Some(body) = &func.body {
+                            finder.visit_function_body(body);
+                        }
+                    });
+                    func.id = None;
+                    let func = self.ast.function_expression(func);
+                    let func = self.replace_class_references(func, class_ref, inner_symbol, false);
+
+                    if def.kind == MethodDefinitionKind::Method {
+                        // Static private methods do not need a brand check in the default mode
+                        if def.r#static && !self.private_fields_as_properties {
+                            let descriptor = self.object(vec![("value", func)]);
+                            output.before.push((member.binding.clone(), descriptor));
+                            continue;
+                        }
+                        let func_name = self.generate_uid(&name);
+                        output.before.push((func_name.clone(), func));
+                        let descriptor = self.object(vec![("value", self.identifier(&func_name))]);
+                        if def.r#static {
+                            let object = self.class_reference(class_ref);
+                            static_private_inits
+                                .push(self.private_init(member, object, descriptor));
+                        } else {
+                            let object = self.ast.this_expression(SPAN);
+                            instance_private_inits
+                                .push(self.private_init(member, object, descriptor));
+                        }
+                        continue;
+                    }
+
+                    let is_getter = def.kind == MethodDefinitionKind::Get;
+                    let func_name = self
+                        .generate_uid(&format!("{}_{name}", if is_getter { "get" } else { "set" }));
+                    output.before.push((func_name.clone(), func));
+                    let index =
+                        accessors.iter().position(|(n, _, _)| *n == name).unwrap_or_else(|| {
+                            accessors.push((name, None, None));
+                            accessors.len() - 1
+                        });
+                    if is_getter {
+                        accessors[index].1 = Some(func_name);
+                    } else {
+                        accessors[index].2 = Some(func_name);
+                    }
+                }
+                element => class.body.body.push(element),
+            }
+        }
+
+        for (name, getter, setter) in accessors {
+            let member = &members[&name];
+            let object = if member.r#static {
+                self.class_reference(class_ref)
+            } else {
+                self.ast.this_expression(SPAN)
+            };
+            let init = self.accessor_init(member, object, getter.as_ref(), setter.as_ref());
+            if member.r#static {
+                static_private_inits.push(init);
+            } else {
+                instance_private_inits.push(init);
+            }
+        }
+
+        let inits: std::vec::Vec<_> =
+            instance_private_inits.into_iter().chain(instance_inits).collect();
+        if !inits.is_empty() {
+            insert_constructor_inits(&self.ctx, class, inits);
+        }
+        output.after = static_private_inits.into_iter().map(|init| (None, init)).collect();
+        output.after.extend(static_inits);
+
+        let mut replacer =
+            PrivateReplacer { transform: self, members, class_ref: class_ref.cloned() };
+        replacer.visit_class_body(&mut class.body);
+        for (_, expr) in &mut output.before {
+            replacer.visit_expression(expr);
+        }
+        for (_, expr) in &mut output.after {
+            replacer.visit_expression(expr);
+        }
+
+        output
+    }
+
+    /// Report `super` in code which is moved out of the class, where it is a syntax error
+    fn check_super(&mut self, visit: impl FnOnce(&mut SuperFinder)) {
+        let mut finder = SuperFinder { span: None };
+        visit(&mut finder);
+        if let Some(span) = finder.span {
+            self.ctx.error(SuperOutsideClass(span));
+        }
+    }
+
+    /// Assign a binding or property key to the private names of `body`, and create the `WeakMap`s
+    /// of the instance members.
+    fn collect_private_members(
+        &mut self,
+        body: &ClassBody<'a>,
+        output: &mut ClassOutput<'a>,
+    ) -> HashMap<Atom, PrivateMember> {
+        let mut members = HashMap::new();
+        for element in &body.body {
+            let (r#static, key) = match element {
+                ClassElement::PropertyDefinition(def) if !def.declare => (def.r#static, &def.key),
+                ClassElement::MethodDefinition(def) => (def.r#static, &def.key),
+                _ => continue,
+            };
+            let Some(name) = key.private_name() else { continue };
+            if members.contains_key(&name) {
+                // The other half of an accessor pair
+                continue;
+            }
+            let binding = if self.private_fields_as_properties {
+                self.private_key_uid += 1;
+                Atom::from(format!("__private_{}_{name}", self.private_key_uid - 1))
+            } else {
+                let binding = self.generate_uid(&name);
+                if !r#static {
+                    output.before.push((binding.clone(), self.new_weak_map()));
+                }
+                binding
+            };
+            members.insert(name, PrivateMember { r#static, binding });
+        }
+        members
+    }
+
+    /// Initialize a field on `object`.
+    ///
+    /// Returns the variable of the descriptor of a static private field.
+    fn field_init(
+        &mut self,
+        members: &HashMap<Atom, PrivateMember>,
+        object: Expression<'a>,
+        key: PropertyKey<'a>,
+        value: Option<Expression<'a>>,
+        output: &mut ClassOutput<'a>,
+    ) -> (Option<Atom>, Expression<'a>) {
+        let value = value.unwrap_or_else(|| self.ast.void_0());
+
+        let key = match key {
+            PropertyKey::PrivateIdentifier(ident) => {
+                let member = &members[&ident.name];
+                if self.private_fields_as_properties {
+                    let writable = self.boolean(true);
+                    let descriptor = self.object(vec![("writable", writable), ("value", value)]);
+                    return (None, self.private_init(member, object, descriptor));
+                }
+                let descriptor = self.object(vec![("value", value)]);
+                if member.r#static {
+                    return (Some(member.binding.clone()), descriptor);
+                }
+                return (None, self.private_init(member, object, descriptor));
+            }
+            PropertyKey::Identifier(ident) => {
+                if self.set_public_class_fields {
+                    let member = self.ast.static_member(SPAN, object, (*ident).clone(), false);
+                    return (None, self.assign(member, value));
+                }
+                self.string(&ident.name)
+            }
+            PropertyKey::Expression(expr) => {
+                // Computed keys are evaluated once when the class is defined
+                let expr = if is_literal(&expr) {
+                    expr
+                } else {
+                    let name = self.generate_uid("key");
+                    output.before.push((name.clone(), expr));
+                    self.identifier(&name)
+                };
+                if self.set_public_class_fields {
+                    let member = self.ast.computed_member(SPAN, object, expr, false);
+                    return (None, self.assign(member, value));
+                }
+                expr
+            }
+        };
+
+        // `Object.defineProperty(object, key, { enumerable: true, configurable: true, writable: true, value })`
+        let descriptor = self.object(vec![
+            ("enumerable", self.boolean(true)),
+            ("configurable", self.boolean(true)),
+            ("writable", self.boolean(true)),
+            ("value", value),
+        ]);
+        (None, self.define_property(object, key, descriptor))
+    }
+
+    /// Install the private member on `object` with `descriptor`
+    ///
+    /// * `_x.set(object, descriptor)`
+    /// * `Object.defineProperty(object, "__private_0_x", descriptor)` in loose mode
+    fn private_init(
+        &self,
+        member: &PrivateMember,
+        object: Expression<'a>,
+        descriptor: Expression<'a>,
+    ) -> Expression<'a> {
+        if self.private_fields_as_properties {
+            return self.define_property(object, self.string(&member.binding), descriptor);
+        }
+        let callee = self.member(self.identifier(&member.binding), "set");
+        self.call(callee, vec![object, descriptor])
+    }
+
+    /// * `_a.set(object, Object.defineProperty({}, "value", { get: _get_a.bind(object), set: _set_a.bind(object) }))`
+    /// * `Object.defineProperty(object, "__private_0_a", { get: _get_a, set: _set_a })` in loose mode
+    ///
+    /// The descriptor of a static accessor is assigned to its variable instead.
+    fn accessor_init(
+        &self,
+        member: &PrivateMember,
+        object: Expression<'a>,
+        getter: Option<&Atom>,
+        setter: Option<&Atom>,
+    ) -> Expression<'a> {
+        let mut properties = vec![];
+        for (name, func) in [("get", getter), ("set", setter)] {
+            let Some(func) = func else { continue };
+            let mut func = self.identifier(func);
+            if !self.private_fields_as_properties {
+                let callee = self.member(func, "bind");
+                func = self.call(callee, vec![self.ast.copy(&object)]);
+            }
+            properties.push((name, func));
+        }
+        let descriptor = self.object(properties);
+
+        if self.private_fields_as_properties {
+            return self.private_init(member, object, descriptor);
+        }
+        let empty = self.object(vec![]);
+        let descriptor = self.define_property(empty, self.string("value"), descriptor);
+        if member.r#static {
+            let target = self.ast.simple_assignment_target_identifier(IdentifierReference::new(
+                SPAN,
+                member.binding.clone(),
+            ));
+            return self.ast.assignment_expression(
+                SPAN,
+                AssignmentOperator::Assign,
+                AssignmentTarget::SimpleAssignmentTarget(target),
+                descriptor,
+            );
+        }
+        self.private_init(member, object, descriptor)
+    }
+
+    /// The single expression or immediately invoked arrow function running a static block
+    fn static_block_expression(&self, body: &mut Vec<'a, Statement<'a>>) -> Option<Expression<'a>> {
+        match body.len() {
+            0 => None,
+            1 if matches!(body[0], Statement::ExpressionStatement(_)) => {
+                let Statement::ExpressionStatement(mut stmt) = body.remove(0) else {
+                    unreachable!()
+                };
+                Some(self.ast.move_expression(&mut stmt.expression))
+            }
+            _ => {
+                let params = self.ast.formal_parameters(
+                    SPAN,
+                    FormalParameterKind::ArrowFormalParameters,
+                    self.ast.new_vec(),
+                    None,
+                );
+                let statements = self.ast.move_statement_vec(body);
+                let function_body = self.ast.function_body(SPAN, self.ast.new_vec(), statements);
+                let arrow = self.ast.arrow_expression(
+                    SPAN,
+                    false,
+                    false,
+                    false,
+                    params,
+                    function_body,
+                    None,
+                    None,
+                );
+                let callee = self.ast.parenthesized_expression(SPAN, arrow);
+                Some(self.call(callee, vec![]))
+            }
+        }
+    }
+
+    /// Replace `this` (if `replace_this`) and the name of a class expression in code moved out of
+    /// the class.
+    fn replace_class_references(
+        &self,
+        mut expr: Expression<'a>,
+        class_ref: Option<&Atom>,
+        inner_symbol: Option<SymbolId>,
+        replace_this: bool,
+    ) -> Expression<'a> {
+        let Some(class_ref) = class_ref else { return expr };
+        if replace_this || inner_symbol.is_some() {
+            let mut replacer = ClassReferenceReplacer {
+                ast: &self.ast,
+                ctx: &self.ctx,
+                class_ref,
+                inner_symbol,
+                replace_this,
+            };
+            replacer.visit_expression(&mut expr);
+        }
+        expr
+    }
+
+    /// `obj.#x`
+    ///
+    /// * `_x.get(obj).value`
+    /// * `_x.value` for static members
+    /// * `obj["__private_0_x"]` in loose mode
+    fn private_member(
+        &self,
+        member: &PrivateMember,
+        object: Expression<'a>,
+        optional: bool,
+    ) -> MemberExpression<'a> {
+        if self.private_fields_as_properties {
+            return self.ast.computed_member(SPAN, object, self.string(&member.binding), optional);
+        }
+        let descriptor = if member.r#static {
+            self.identifier(&member.binding)
+        } else {
+            let callee = self.member(self.identifier(&member.binding), "get");
+            self.call(callee, vec![object])
+        };
+        self.ast.static_member(
+            SPAN,
+            descriptor,
+            IdentifierName::new(SPAN, "value".into()),
+            optional,
+        )
+    }
+
+    /// `#x in obj`
+    ///
+    /// * `_x.has(obj)`
+    /// * `obj === Foo` for static members
+    /// * `Object.prototype.hasOwnProperty.call(obj, "__private_0_x")` in loose mode
+    fn private_in(
+        &self,
+        member: &PrivateMember,
+        object: Expression<'a>,
+        class_ref: Option<&Atom>,
+    ) -> Expression<'a> {
+        if self.private_fields_as_properties {
+            let prototype = self.member(self.identifier(&"Object".into()), "prototype");
+            let callee = self.member(self.member(prototype, "hasOwnProperty"), "call");
+            return self.call(callee, vec![object, self.string(&member.binding)]);
+        }
+        if member.r#static {
+            let class = self.class_reference(class_ref);
+            return self.ast.binary_expression(SPAN, object, BinaryOperator::StrictEquality, class);
+        }
+        let callee = self.member(self.identifier(&member.binding), "has");
+        self.call(callee, vec![object])
+    }
+
+    fn class_reference(&self, class_ref: Option<&Atom>) -> Expression<'a> {
+        self.identifier(class_ref.expect("classes with static members have a reference"))
+    }
+
+    fn generate_uid(&self, name: &str) -> Atom {
+        let name = self.ctx.scopes().generate_uid(name);
+        self.ctx.add_binding(name.clone());
+        name
+    }
+
+    /// `var name = init;`
+    fn var_statement(&self, name: Atom, init: Expression<'a>) -> Statement<'a> {
+        let kind = VariableDeclarationKind::Var;
+        let binding = self.ast.binding_pattern(
+            self.ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, name)),
+            None,
+            false,
+        );
+        let declarator = self.ast.variable_declarator(SPAN, kind, binding, Some(init), false);
+        let decl = self.ast.variable_declaration(
+            SPAN,
+            kind,
+            self.ast.new_vec_single(declarator),
+            Modifiers::empty(),
+        );
+        Statement::Declaration(Declaration::VariableDeclaration(decl))
+    }
+
+    /// Declare `var name` in the current statement list and return `name = value`
+    fn assign_new_var(&mut self, name: Atom, value: Expression<'a>) -> Expression<'a> {
+        let ident = self.declare_var(name);
+        let target = self.ast.simple_assignment_target_identifier(ident);
+        self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            value,
+        )
+    }
+
+    fn assign(&self, member: MemberExpression<'a>, value: Expression<'a>) -> Expression<'a> {
+        let target = AssignmentTarget::SimpleAssignmentTarget(
+            self.ast.simple_assignment_target_member_expression(member),
+        );
+        self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, target, value)
+    }
+
+    fn identifier(&self, name: &Atom) -> Expression<'a> {
+        self.ast.identifier_reference_expression(IdentifierReference::new(SPAN, name.clone()))
+    }
+
+    fn string(&self, value: &str) -> Expression<'a> {
+        self.ast.literal_string_expression(StringLiteral::new(SPAN, value.into()))
+    }
+
+    fn boolean(&self, value: bool) -> Expression<'a> {
+        self.ast.literal_boolean_expression(self.ast.boolean_literal(SPAN, value))
+    }
+
+    fn member(&self, object: Expression<'a>, property: &str) -> Expression<'a> {
+        self.ast.static_member_expression(
+            SPAN,
+            object,
+            IdentifierName::new(SPAN, property.into()),
+            false,
+        )
+    }
+
+    fn call(&self, callee: Expression<'a>, args: std::vec::Vec<Expression<'a>>) -> Expression<'a> {
+        let mut arguments = self.ast.new_vec_with_capacity(args.len());
+        arguments.extend(args.into_iter().map(Argument::Expression));
+        self.ast.call_expression(SPAN, callee, arguments, false, None)
+    }
+
+    /// `{ name: value, ... }`
+    fn object(&self, properties: std::vec::Vec<(&str, Expression<'a>)>) -> Expression<'a> {
+        let mut props = self.ast.new_vec_with_capacity(properties.len());
+        for (name, value) in properties {
+            let key = self.ast.property_key_identifier(IdentifierName::new(SPAN, name.into()));
+            let prop = self.ast.object_property(
+                SPAN,
+                PropertyKind::Init,
+                key,
+                value,
+                None,
+                false,
+                false,
+                false,
+            );
+            props.push(ObjectPropertyKind::ObjectProperty(prop));
+        }
+        self.ast.object_expression(SPAN, props, None)
+    }
+
+    /// `Object.defineProperty(object, key, descriptor)`
+    fn define_property(
+        &self,
+        object: Expression<'a>,
+        key: Expression<'a>,
+        descriptor: Expression<'a>,
+    ) -> Expression<'a> {
+        let callee = self.member(self.identifier(&"Object".into()), "defineProperty");
+        self.call(callee, vec![object, key, descriptor])
+    }
+
+    /// `new WeakMap()`
+    fn new_weak_map(&self) -> Expression<'a> {
+        let callee = self.identifier(&"WeakMap".into());
+        self.ast.new_expression(SPAN, callee, self.ast.new_vec(), None)
+    }
+}
+
+/// Whether the class has fields, static blocks or private methods
+fn needs_transform(class: &Class) -> bool {
+    class.body.body.iter().any(|element| match element {
+        ClassElement::PropertyDefinition(def) => !def.declare,
+        ClassElement::StaticBlock(_) => true,
+        ClassElement::MethodDefinition(def) => def.key.is_private_identifier(),
+        _ => false,
+    })
+}
+
+/// Whether lowering the class moves code referencing the class out of it
+fn has_static_members(class: &Class) -> bool {
+    class.body.body.iter().any(|element| match element {
+        ClassElement::PropertyDefinition(def) => def.r#static && !def.declare,
+        ClassElement::StaticBlock(_) => true,
+        ClassElement::MethodDefinition(def) => def.r#static && def.key.is_private_identifier(),
+        _ => false,
+    })
+}
+
+fn is_literal(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::StringLiteral(_)
+            | Expression::NumberLiteral(_)
+            | Expression::BigintLiteral(_)
+            | Expression::BooleanLiteral(_)
+            | Expression::NullLiteral(_)
+    )
+}
+
+/// Rewrite the uses of the private names of a class
+struct PrivateReplacer<'a, 'b> {
+    transform: &'b mut ClassProperties<'a>,
+    members: HashMap<Atom, PrivateMember>,
+    class_ref: Option<Atom>,
+}
+
+impl<'a, 'b> VisitMut<'a> for PrivateReplacer<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        self.visit_expression_match(expr);
+
+        let Expression::PrivateInExpression(private_in) = expr else { return };
+        let Some(member) = self.members.get(&private_in.left.name) else { return };
+        let object = self.transform.ast.move_expression(&mut private_in.right);
+        *expr = self.transform.private_in(member, object, self.class_ref.as_ref());
+    }
+
+    fn visit_member_expression(&mut self, expr: &mut MemberExpression<'a>) {
+        let field = match expr {
+            MemberExpression::ComputedMemberExpression(expr) => {
+                return self.visit_computed_member_expression(expr);
+            }
+            MemberExpression::StaticMemberExpression(expr) => {
+                return self.visit_static_member_expression(expr);
+            }
+            MemberExpression::PrivateFieldExpression(field) => field,
+        };
+        self.visit_expression(&mut field.object);
+
+        let Some(member) = self.members.get(&field.field.name) else { return };
+        let optional = field.optional;
+        let object = self.transform.ast.move_expression(&mut field.object);
+        *expr = self.transform.private_member(member, object, optional);
+    }
+
+    /// `obj.#m(...args)` -> `_m.get(obj).value.call(obj, ...args)`
+    fn visit_call_expression(&mut self, call: &mut CallExpression<'a>) {
+        for arg in call.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+
+        let member = match &call.callee {
+            Expression::MemberExpression(member_expr) => match &**member_expr {
+                MemberExpression::PrivateFieldExpression(field) => {
+                    self.members.get(&field.field.name).cloned()
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        // Loose mode keeps the member expression as the callee
+        let Some(member) = member.filter(|_| !self.transform.private_fields_as_properties) else {
+            self.visit_expression(&mut call.callee);
+            return;
+        };
+        let Expression::MemberExpression(member_expr) = &mut call.callee else { unreachable!() };
+        let MemberExpression::PrivateFieldExpression(field) = &mut **member_expr else {
+            unreachable!()
+        };
+        self.visit_expression(&mut field.object);
+
+        let optional = field.optional;
+        let ast = Rc::clone(&self.transform.ast);
+        let object = ast.move_expression(&mut field.object);
+        let (object, this_arg) = if member.r#static {
+            (ast.void_0(), object)
+        } else if let Some(ident) = self.transform.maybe_generate_memoised(&object) {
+            let target = ast.simple_assignment_target_identifier(ident.clone());
+            let assignment = ast.assignment_expression(
+                SPAN,
+                AssignmentOperator::Assign,
+                AssignmentTarget::SimpleAssignmentTarget(target),
+                object,
+            );
+            (assignment, ast.identifier_reference_expression(ident))
+        } else {
+            (ast.copy(&object), object)
+        };
+
+        let value = ast.member_expression(self.transform.private_member(&member, object, optional));
+        call.callee = self.transform.member(value, "call");
+        call.arguments.insert(0, Argument::Expression(this_arg));
+    }
+
+    fn visit_class(&mut self, class: &mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_expression(super_class);
+        }
+        // Private names declared by a nested class shadow the outer ones
+        let shadowed: std::vec::Vec<_> = class
+            .body
+            .body
+            .iter()
+            .filter_map(ClassElement::property_key)
+            .filter_map(PropertyKey::private_name)
+            .filter_map(|name| self.members.remove(&name).map(|member| (name, member)))
+            .collect();
+        self.visit_class_body(&mut class.body);
+        self.members.extend(shadowed);
+    }
+}
+
+/// Replace `this` and the name of the class in code moved out of a class
+struct ClassReferenceReplacer<'a, 'b> {
+    ast: &'b AstBuilder<'a>,
+    ctx: &'b TransformerCtx<'a>,
+    class_ref: &'b Atom,
+    inner_symbol: Option<SymbolId>,
+    replace_this: bool,
+}
+
+impl<'a, 'b> VisitMut<'a> for ClassReferenceReplacer<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        match expr {
+            Expression::ThisExpression(this) if self.replace_this => {
+                let ident = IdentifierReference::new(this.span, self.class_ref.clone());
+                *expr = self.ast.identifier_reference_expression(ident);
+                return;
+            }
+            Expression::Identifier(ident) if self.inner_symbol.is_some() => {
+                let symbol_id = ident
+                    .reference_id
+                    .get()
+                    .and_then(|id| self.ctx.symbols().get_reference(id).symbol_id());
+                if symbol_id == self.inner_symbol {
+                    ident.name = self.class_ref.clone();
+                }
+            }
+            _ => {}
+        }
+        self.visit_expression_match(expr);
+    }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, _flags: Option<ScopeFlags>) {
+        let replace_this = mem::replace(&mut self.replace_this, false);
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+        self.replace_this = replace_this;
+    }
+
+    fn visit_class(&mut self, class: &mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_expression(super_class);
+        }
+        let replace_this = mem::replace(&mut self.replace_this, false);
+        self.visit_class_body(&mut class.body);
+        self.replace_this = replace_this;
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = |loose| TransformOptions {
+        class_properties: Some(ClassPropertiesOptions { loose }),
+        ..TransformOptions::default()
+    };
+
+    let tests = &[
+        (
+            "class A { x = 1; static y = this.x; }",
+            r#"class A { constructor() { Object.defineProperty(this, "x", { enumerable: true, configurable: true, writable: true, value: 1 }); } }
+            Object.defineProperty(A, "y", { enumerable: true, configurable: true, writable: true, value: A.x });"#,
+        ),
+        (
+            "class A extends B { #x; constructor() { super(); this.#x++; } has(o) { return #x in o; } }",
+            "var _x = new WeakMap(); class A extends B { constructor() { super(); _x.set(this, { value: void 0 }); _x.get(this).value++; } has(o) { return _x.has(o); } }",
+        ),
+        (
+            "class A extends B { #m() {} static #n() {} f() { this.#m(); a.b.#m(); A.#n(1); } }",
+            "var _a$b; var _m = new WeakMap(); var _m2 = function() {}; var _n = { value: function() {} };
+            class A extends B { constructor(..._args) { super(..._args); _m.set(this, { value: _m2 }); } f() { _m.get(this).value.call(this); _m.get(_a$b = a.b).value.call(_a$b); _n.value.call(A, 1); } }",
+        ),
+        (
+            "class A { get #a() { return 1 } set #a(v) {} }",
+            r#"var _a = new WeakMap(); var _get_a = function() { return 1; }; var _set_a = function(v) {};
+            class A { constructor() { _a.set(this, Object.defineProperty({}, "value", { get: _get_a.bind(this), set: _set_a.bind(this) })); } }"#,
+        ),
+        (
+            "let C = class A { static #x = 1; static { A.#x; foo(); } }",
+            "var _Class, _x; let C = (_Class = class A {}, _x = { value: 1 }, (() => { _x.value; foo(); })(), _Class);",
+        ),
+    ];
+    Tester::new("test.js", options(false)).test(tests);
+
+    let message =
+        "`super` is not supported in private methods and static initializers moved out of the class.";
+    let tester = Tester::new("test.js", options(false));
+    tester.test_error("class A extends B { #m() { return super.x; } }", message);
+    tester.test_error("class A extends B { static s = super.x; }", message);
+    tester.test_error("class A extends B { static { super.x(); } }", message);
+
+    let loose_tests = &[
+        (
+            "class A extends B { x = 1; constructor() { if (a) super(); } }",
+            "class A extends B { constructor() { if (a) (super(), this.x = 1, this); } }",
+        ),
+        (
+            "class A { #x = 1; [k] = 2; #m() { return this.#x; } }",
+            r#"var _key = k; var _m = function() { return this["__private_0_x"]; };
+            class A { constructor() { Object.defineProperty(this, "__private_1_m", { value: _m }); Object.defineProperty(this, "__private_0_x", { writable: true, value: 1 }); this[_key] = 2; } }"#,
+        ),
+    ];
+    Tester::new("test.js", options(true)).test(loose_tests);
+}
@@ -0,0 +1,148 @@
+mod options;
+
+use std::mem;
+
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SourceType, SPAN};
+
+pub use self::options::{HelperLoaderMode, HelpersOptions};
+use crate::{context::TransformerCtx, ModuleFormat, TransformOptions};
+
+/// Loads the runtime helpers requested by the transforms
+///
+/// In the runtime mode each helper is imported once per module, before the other statements:
+///
+/// ```JavaScript
+/// import _asyncToGenerator from "@babel/runtime/helpers/asyncToGenerator";
+/// function f() { return _asyncToGenerator(function* () {}).apply(this, arguments); }
+/// ```
+///
+/// The helpers are required instead of imported in scripts and in modules converted to CommonJS.
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-transform-runtime>
+/// * <https://babeljs.io/docs/babel-plugin-external-helpers>
+pub struct HelperLoader {
+    options: HelpersOptions,
+    /// Whether the output is CommonJS
+    require: bool,
+    /// The names of the helpers imported from the runtime and their local bindings
+    loaded: Vec<(String, Atom)>,
+}
+
+impl HelperLoader {
+    pub fn new(options: &TransformOptions, source_type: SourceType) -> Self {
+        let require = match options.modules.map(|modules| modules.format) {
+            Some(ModuleFormat::CommonJs) => true,
+            Some(ModuleFormat::Esm) => false,
+            None => !source_type.is_module(),
+        };
+        Self { options: options.helpers.clone(), require, loaded: vec![] }
+    }
+
+    /// `babelHelpers.name`, or the local binding of the helper imported from the runtime
+    pub fn load<'a>(&mut self, ctx: &TransformerCtx<'a>, name: &str) -> Expression<'a> {
+        let ast = &ctx.ast;
+        match self.options.mode {
+            HelperLoaderMode::External => {
+                let object = ast.identifier_expression(SPAN, "babelHelpers".into());
+                let property = IdentifierName::new(SPAN, name.into());
+                ast.static_member_expression(SPAN, object, property, false)
+            }
+            HelperLoaderMode::Runtime => {
+                let local = if let Some((_, local)) =
+                    self.loaded.iter().find(|(loaded, _)| loaded == name)
+                {
+                    local.clone()
+                } else {
+                    let local = ctx.scopes().generate_uid(name);
+                    ctx.add_binding(local.clone());
+                    self.loaded.push((name.to_string(), local.clone()));
+                    local
+                };
+                ast.identifier_expression(SPAN, local)
+            }
+        }
+    }
+
+    /// Insert the imports of the loaded helpers at the start of `program`
+    pub fn inject<'a>(&mut self, ast: &AstBuilder<'a>, program: &mut Program<'a>) {
+        let loaded = mem::take(&mut self.loaded);
+        if loaded.is_empty() {
+            return;
+        }
+        let mut body = ast.new_vec_with_capacity(loaded.len() + program.body.len());
+        for (name, local) in loaded {
+            let source = ast.new_str(&format!("{}/helpers/{name}", self.options.module));
+            body.push(self.import(ast, Atom::from(source), local));
+        }
+        body.extend(ast.move_statement_vec(&mut program.body));
+        program.body = body;
+    }
+
+    /// `import local from "source"` or `var local = require("source")`
+    fn import<'a>(&self, ast: &AstBuilder<'a>, source: Atom, local: Atom) -> Statement<'a> {
+        if self.require {
+            let source = ast.string_expression(SPAN, source);
+            let require =
+                ast.call(SPAN, ast.identifier_expression(SPAN, "require".into()), [source]);
+            let kind = VariableDeclarationKind::Var;
+            return ast.variable_statement(SPAN, kind, local, Some(require));
+        }
+        let specifier =
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(ImportDefaultSpecifier {
+                span: SPAN,
+                local: BindingIdentifier::new(SPAN, local),
+            });
+        let decl = ast.import_declaration(
+            SPAN,
+            Some(ast.new_vec_single(specifier)),
+            StringLiteral::new(SPAN, source),
+            None,
+            ImportOrExportKind::Value,
+        );
+        ast.module_declaration(ModuleDeclaration::ImportDeclaration(decl))
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester, ModulesOptions};
+
+    let options = TransformOptions {
+        async_to_generator: true,
+        async_generator_functions: true,
+        helpers: HelpersOptions { mode: HelperLoaderMode::Runtime, ..HelpersOptions::default() },
+        ..TransformOptions::default()
+    };
+    let tests = &[(
+        "async function f() { await g(); } async function h() { await f(); } async function* i() { yield* j; }",
+        r#"
+        import _asyncToGenerator from "@babel/runtime/helpers/asyncToGenerator";
+        import _asyncIterator from "@babel/runtime/helpers/asyncIterator";
+        import _asyncGeneratorDelegate from "@babel/runtime/helpers/asyncGeneratorDelegate";
+        import _wrapAsyncGenerator from "@babel/runtime/helpers/wrapAsyncGenerator";
+        function f() { return _asyncToGenerator(function* () { yield g(); }).apply(this, arguments); }
+        function h() { return _asyncToGenerator(function* () { yield f(); }).apply(this, arguments); }
+        function i() { return _wrapAsyncGenerator(function* () { yield* _asyncGeneratorDelegate(_asyncIterator(j)); }).apply(this, arguments); }
+        "#,
+    )];
+    Tester::new("test.mjs", options.clone()).test(tests);
+
+    let options = TransformOptions {
+        modules: Some(ModulesOptions::default()),
+        helpers: HelpersOptions { mode: HelperLoaderMode::Runtime, module: "runtime".into() },
+        ..options
+    };
+    let tests = &[(
+        "import a from 'a'; async function f() { await a(); }",
+        r#"
+        "use strict";
+        var _asyncToGenerator = require("runtime/helpers/asyncToGenerator");
+        var _interopRequireDefault = require("runtime/helpers/interopRequireDefault");
+        var _a = _interopRequireDefault(require("a"));
+        function f() { return _asyncToGenerator(function* () { yield (0, _a.default)(); }).apply(this, arguments); }
+        "#,
+    )];
+    Tester::new("test.mjs", options).test(tests);
+}
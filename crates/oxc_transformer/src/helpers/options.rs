@@ -0,0 +1,35 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HelpersOptions {
+    /// How the helpers are loaded, defaults to the `babelHelpers` global
+    #[serde(default)]
+    pub mode: HelperLoaderMode,
+    /// The package of the helpers in the runtime mode, each helper is the default export of
+    /// `{module}/helpers/{name}`
+    #[serde(default = "default_module", alias = "moduleName")]
+    pub module: String,
+}
+
+impl Default for HelpersOptions {
+    fn default() -> Self {
+        Self { mode: HelperLoaderMode::default(), module: default_module() }
+    }
+}
+
+fn default_module() -> String {
+    "@babel/runtime".into()
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum HelperLoaderMode {
+    /// `babelHelpers.name`, for the helpers defined by the environment, e.g. with
+    /// `@babel/plugin-external-helpers`
+    #[default]
+    #[serde(rename = "external")]
+    External,
+    /// `import _name from "@babel/runtime/helpers/name"`, with one import per helper and module
+    #[serde(rename = "runtime")]
+    Runtime,
+}
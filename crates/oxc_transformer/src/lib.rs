@@ -8,15 +8,24 @@
 //! * <https://github.com/microsoft/TypeScript/blob/main/src/compiler/transformer.ts>
 
 mod context;
+mod decorators;
+mod define;
+mod env;
 mod es2015;
 mod es2016;
+mod es2017;
+mod es2018;
 mod es2019;
 mod es2020;
 mod es2021;
 mod es2022;
 mod es3;
+mod helpers;
+mod modules;
 mod options;
+mod plugin;
 mod react_jsx;
+mod react_refresh;
 mod regexp;
 #[cfg(test)]
 mod tester;
@@ -33,34 +42,68 @@ use oxc_semantic::{ScopeFlags, Semantic};
 use oxc_span::SourceType;
 
 use crate::{
-    context::TransformerCtx, es2015::*, es2016::ExponentiationOperator,
-    es2019::OptionalCatchBinding, es2020::NullishCoalescingOperator,
-    es2021::LogicalAssignmentOperators, es2022::ClassStaticBlock, es3::PropertyLiteral,
-    react_jsx::ReactJsx, regexp::RegexpFlags, typescript::TypeScript, utils::CreateVars,
+    decorators::Decorators,
+    define::Define,
+    es2015::*,
+    es2016::ExponentiationOperator,
+    es2017::AsyncToGenerator,
+    es2018::AsyncGeneratorFunctions,
+    es2019::OptionalCatchBinding,
+    es2020::{NullishCoalescingOperator, OptionalChaining},
+    es2021::LogicalAssignmentOperators,
+    es2022::{ClassProperties, ClassStaticBlock},
+    es3::PropertyLiteral,
+    helpers::HelperLoader,
+    modules::Modules,
+    react_jsx::ReactJsx,
+    react_refresh::ReactRefresh,
+    regexp::RegexpFlags,
+    typescript::TypeScript,
+    utils::CreateVars,
 };
 
 pub use crate::{
+    context::TransformerCtx,
+    decorators::{DecoratorsOptions, DecoratorsVersion},
+    define::DefineOptions,
+    env::TargetsOptions,
     es2015::ArrowFunctionsOptions,
-    es2020::NullishCoalescingOperatorOptions,
+    es2020::{NullishCoalescingOperatorOptions, OptionalChainingOptions},
+    es2022::ClassPropertiesOptions,
+    helpers::{HelperLoaderMode, HelpersOptions},
+    modules::{ModuleFormat, ModulesOptions},
     options::{TransformOptions, TransformTarget},
+    plugin::TransformerPlugin,
     react_jsx::{ReactJsxOptions, ReactJsxRuntime, ReactJsxRuntimeOption},
+    react_refresh::ReactRefreshOptions,
     typescript::TypescriptOptions,
 };
 
 pub struct Transformer<'a> {
     ctx: TransformerCtx<'a>,
+    plugins: Vec<std::boxed::Box<dyn TransformerPlugin<'a> + 'a>>,
     #[allow(unused)]
     typescript: Option<TypeScript<'a>>,
+    define: Option<Define<'a>>,
     react_jsx: Option<ReactJsx<'a>>,
+    react_refresh: Option<ReactRefresh<'a>>,
     regexp_flags: Option<RegexpFlags<'a>>,
+    decorators: Option<Decorators<'a>>,
+    modules: Option<Modules<'a>>,
     // es2022
     es2022_class_static_block: Option<ClassStaticBlock<'a>>,
+    es2022_class_properties: Option<ClassProperties<'a>>,
     // es2021
     es2021_logical_assignment_operators: Option<LogicalAssignmentOperators<'a>>,
     // es2020
     es2020_nullish_coalescing_operators: Option<NullishCoalescingOperator<'a>>,
+    es2020_optional_chaining: Option<OptionalChaining<'a>>,
     // es2019
     es2019_optional_catch_binding: Option<OptionalCatchBinding<'a>>,
+    // es2018
+    es2018_async_generator_functions: Option<AsyncGeneratorFunctions<'a>>,
+    // es2017
+    es2017_async_to_generator: Option<AsyncToGenerator<'a>>,
     // es2016
     es2016_exponentiation_operator: Option<ExponentiationOperator<'a>>,
     // es2015
@@ -80,27 +123,40 @@ impl<'a> Transformer<'a> {
         allocator: &'a Allocator,
         source_type: SourceType,
         semantic: Semantic<'a>,
-        options: TransformOptions,
+        mut options: TransformOptions,
     ) -> Self {
         let ast = Rc::new(AstBuilder::new(allocator));
-        let ctx = TransformerCtx::new(
+        let helpers = HelperLoader::new(&options, source_type);
+        let mut ctx = TransformerCtx::new(
             Rc::clone(&ast),
             Rc::new(RefCell::new(semantic)),
+            helpers,
         );
+        env::enable_unsupported_features(&mut options, &mut ctx);
 
         Self {
             ctx: ctx.clone(),
+            plugins: vec![],
             // TODO: pass verbatim_module_syntax from user config
             typescript: source_type.is_typescript().then(|| TypeScript::new(Rc::clone(&ast), ctx.clone(), false, &options)),
+            define: Define::new(Rc::clone(&ast), ctx.clone(), &options),
             regexp_flags: RegexpFlags::new(Rc::clone(&ast), &options),
+            decorators: Decorators::new(Rc::clone(&ast), ctx.clone(), &options),
+            modules: Modules::new(Rc::clone(&ast), ctx.clone(), &options),
             // es2022
             es2022_class_static_block: es2022::ClassStaticBlock::new(Rc::clone(&ast), &options),
+            es2022_class_properties: ClassProperties::new(Rc::clone(&ast), ctx.clone(), &options),
             // es2021
             es2021_logical_assignment_operators: LogicalAssignmentOperators::new(Rc::clone(&ast), ctx.clone(), &options),
             // es2020
             es2020_nullish_coalescing_operators: NullishCoalescingOperator::new(Rc::clone(&ast), ctx.clone(), &options),
+            es2020_optional_chaining: OptionalChaining::new(Rc::clone(&ast), ctx.clone(), &options),
             // es2019
             es2019_optional_catch_binding: OptionalCatchBinding::new(Rc::clone(&ast), &options),
+            // es2018
+            es2018_async_generator_functions: AsyncGeneratorFunctions::new(Rc::clone(&ast), ctx.clone(), &options),
+            // es2017
+            es2017_async_to_generator: AsyncToGenerator::new(Rc::clone(&ast), ctx.clone(), &options),
             // es2016
             es2016_exponentiation_operator: ExponentiationOperator::new(Rc::clone(&ast), ctx.clone(), &options),
             // es2015
@@ -113,10 +169,18 @@ impl<'a> Transformer<'a> {
             es2015_new_target: NewTarget::new(Rc::clone(&ast),ctx.clone(), &options),
             // other
             es3_property_literal: PropertyLiteral::new(Rc::clone(&ast), &options),
+            react_refresh: ReactRefresh::new(Rc::clone(&ast), ctx.clone(), &options),
             react_jsx: ReactJsx::new(Rc::clone(&ast), ctx.clone(), options)
         }
     }
 
+    /// Run `plugin` with the built-in passes, after the plugins added before it
+    #[must_use]
+    pub fn with_plugin<P: TransformerPlugin<'a> + 'a>(mut self, plugin: P) -> Self {
+        self.plugins.push(std::boxed::Box::new(plugin));
+        self
+    }
+
     /// # Errors
     /// Returns `Vec<Error>` if any errors were collected during the transformation.
     pub fn build(mut self, program: &mut Program<'a>) -> Result<(), Vec<Error>> {
@@ -138,10 +202,12 @@ impl<'a> Transformer<'a> {
 
 impl<'a> VisitMut<'a> for Transformer<'a> {
     fn enter_node(&mut self, kind: oxc_ast::AstKind<'a>) {
+        self.react_jsx.as_mut().map(|t| t.enter_node(kind));
         self.es2015_new_target.as_mut().map(|t| t.enter_node(kind));
     }
 
     fn leave_node(&mut self, kind: oxc_ast::AstKind<'a>) {
+        self.react_jsx.as_mut().map(|t| t.leave_node(kind));
         self.es2015_new_target.as_mut().map(|t| t.leave_node(kind));
     }
 
@@ -160,10 +226,15 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
             self.visit_directive(directive);
         }
 
+        self.plugins.iter_mut().for_each(|p| p.enter_program(program, &mut self.ctx));
+        self.react_refresh.as_mut().map(|t| t.transform_program(program));
         self.typescript.as_mut().map(|t| t.transform_program(program));
         self.visit_statements(&mut program.body);
 
         self.react_jsx.as_mut().map(|t| t.add_react_jsx_runtime_imports(program));
+        self.modules.as_mut().map(|t| t.transform_program(program));
+        self.plugins.iter_mut().for_each(|p| p.exit_program(program, &mut self.ctx));
+        self.ctx.inject_helpers(program);
 
         self.leave_node(kind);
         self.leave_scope();
@@ -182,21 +253,39 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
     }
 
     fn visit_statements(&mut self, stmts: &mut oxc_allocator::Vec<'a, Statement<'a>>) {
+        self.decorators.as_mut().map(CreateVars::enter_statements);
+        self.es2022_class_properties.as_mut().map(CreateVars::enter_statements);
+        self.es2021_logical_assignment_operators.as_mut().map(CreateVars::enter_statements);
+        self.es2020_nullish_coalescing_operators.as_mut().map(CreateVars::enter_statements);
+        self.es2020_optional_chaining.as_mut().map(CreateVars::enter_statements);
+        self.es2016_exponentiation_operator.as_mut().map(CreateVars::enter_statements);
+
+        self.plugins.iter_mut().for_each(|p| p.enter_statements(stmts, &mut self.ctx));
+        self.define.as_mut().map(|t| t.transform_statements(stmts));
         self.typescript.as_mut().map(|t| t.transform_statements(stmts));
+        self.decorators.as_mut().map(|t| t.transform_statements(stmts));
+        self.es2022_class_properties.as_mut().map(|t| t.transform_statements(stmts));
 
         for stmt in stmts.iter_mut() {
             self.visit_statement(stmt);
         }
-        // TODO: we need scope id to insert the vars into the correct statements
+
+        self.decorators.as_mut().map(|t| t.add_vars_to_statements(stmts));
+        self.es2022_class_properties.as_mut().map(|t| t.add_vars_to_statements(stmts));
         self.es2021_logical_assignment_operators.as_mut().map(|t| t.add_vars_to_statements(stmts));
         self.es2020_nullish_coalescing_operators.as_mut().map(|t| t.add_vars_to_statements(stmts));
+        self.es2020_optional_chaining.as_mut().map(|t| t.add_vars_to_statements(stmts));
         self.es2016_exponentiation_operator.as_mut().map(|t| t.add_vars_to_statements(stmts));
         self.es2015_arrow_functions.as_mut().map(|t| t.transform_statements(stmts));
+        self.plugins.iter_mut().for_each(|p| p.exit_statements(stmts, &mut self.ctx));
     }
 
     fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
+        self.plugins.iter_mut().for_each(|p| p.enter_statement(stmt, &mut self.ctx));
         self.typescript.as_mut().map(|t| t.transform_statement(stmt));
+        self.es2018_async_generator_functions.as_mut().map(|t| t.transform_statement(stmt));
         self.visit_statement_match(stmt);
+        self.plugins.iter_mut().for_each(|p| p.exit_statement(stmt, &mut self.ctx));
     }
 
     fn visit_declaration(&mut self, decl: &mut Declaration<'a>) {
@@ -205,12 +294,18 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
     }
 
     fn visit_expression(&mut self, expr: &mut Expression<'a>) {
-        // self.typescript.as_mut().map(|t| t.transform_expression(expr));
+        self.plugins.iter_mut().for_each(|p| p.enter_expression(expr, &mut self.ctx));
+        self.define.as_mut().map(|t| t.transform_expression(expr));
+        self.typescript.as_mut().map(|t| t.transform_expression(expr));
         self.react_jsx.as_mut().map(|t| t.transform_expression(expr));
         self.regexp_flags.as_mut().map(|t| t.transform_expression(expr));
 
+        self.decorators.as_mut().map(|t| t.transform_expression(expr));
+        self.es2022_class_properties.as_mut().map(|t| t.transform_expression(expr));
+
         self.es2021_logical_assignment_operators.as_mut().map(|t| t.transform_expression(expr));
         self.es2020_nullish_coalescing_operators.as_mut().map(|t| t.transform_expression(expr));
+        self.es2020_optional_chaining.as_mut().map(|t| t.transform_expression(expr));
         self.es2015_arrow_functions.as_mut().map(|t| t.transform_expression(expr));
         self.es2015_instanceof.as_mut().map(|t| t.transform_expression(expr));
         self.es2016_exponentiation_operator.as_mut().map(|t| t.transform_expression(expr));
@@ -218,6 +313,102 @@ impl<'a> VisitMut<'a> for Transformer<'a> {
         self.es2015_new_target.as_mut().map(|t| t.transform_expression(expr));
 
         self.visit_expression_match(expr);
+        self.plugins.iter_mut().for_each(|p| p.exit_expression(expr, &mut self.ctx));
+    }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, flags: Option<ScopeFlags>) {
+        let kind = AstKind::Function(self.alloc(func));
+        self.enter_scope({
+            let mut flags = flags.unwrap_or(ScopeFlags::empty()) | ScopeFlags::Function;
+            if func.is_strict() {
+                flags |= ScopeFlags::StrictMode;
+            }
+            flags
+        });
+        self.enter_node(kind);
+
+        self.plugins.iter_mut().for_each(|p| p.enter_function(func, &mut self.ctx));
+        self.typescript.as_mut().map(|t| t.transform_function(func));
+
+        if let Some(ident) = &mut func.id {
+            self.visit_binding_identifier(ident);
+        }
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+
+        self.es2018_async_generator_functions.as_mut().map(|t| t.transform_function(func));
+        self.es2017_async_to_generator.as_mut().map(|t| t.transform_function(func));
+        self.plugins.iter_mut().for_each(|p| p.exit_function(func, &mut self.ctx));
+
+        self.leave_node(kind);
+        self.leave_scope();
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &mut ArrowExpression<'a>) {
+        let kind = AstKind::ArrowExpression(self.alloc(expr));
+        self.enter_scope(ScopeFlags::Function | ScopeFlags::Arrow);
+        self.enter_node(kind);
+        self.visit_formal_parameters(&mut expr.params);
+        if expr.expression {
+            // The variables created in an expression body are declared by the enclosing statements
+            let body_kind = AstKind::FunctionBody(self.alloc(&*expr.body));
+            self.enter_node(body_kind);
+            for stmt in expr.body.statements.iter_mut() {
+                self.visit_statement(stmt);
+            }
+            self.leave_node(body_kind);
+        } else {
+            self.visit_function_body(&mut expr.body);
+        }
+
+        self.es2017_async_to_generator.as_mut().map(|t| t.transform_arrow_expression(expr));
+
+        self.leave_node(kind);
+        self.leave_scope();
+    }
+
+    fn visit_class(&mut self, class: &mut Class<'a>) {
+        for decorator in class.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+
+        let kind = AstKind::Class(self.alloc(class));
+        let is_class_expr = class.r#type == ClassType::ClassExpression;
+        if is_class_expr {
+            self.enter_scope(ScopeFlags::empty());
+        }
+        self.enter_node(kind);
+
+        self.plugins.iter_mut().for_each(|p| p.enter_class(class, &mut self.ctx));
+        self.typescript.as_mut().map(|t| t.transform_class(class));
+
+        if let Some(id) = &mut class.id {
+            self.visit_binding_identifier(id);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        self.visit_class_body(&mut class.body);
+        self.plugins.iter_mut().for_each(|p| p.exit_class(class, &mut self.ctx));
+        self.leave_node(kind);
+        if is_class_expr {
+            self.leave_scope();
+        }
+    }
+
+    fn visit_binding_pattern(&mut self, pat: &mut BindingPattern<'a>) {
+        self.typescript.as_mut().map(|t| t.transform_binding_pattern(pat));
+
+        match &mut pat.kind {
+            BindingPatternKind::BindingIdentifier(ident) => {
+                self.visit_binding_identifier(ident);
+            }
+            BindingPatternKind::ObjectPattern(pat) => self.visit_object_pattern(pat),
+            BindingPatternKind::ArrayPattern(pat) => self.visit_array_pattern(pat),
+            BindingPatternKind::AssignmentPattern(pat) => self.visit_assignment_pattern(pat),
+        }
     }
 
     fn visit_catch_clause(&mut self, clause: &mut CatchClause<'a>) {
@@ -0,0 +1,518 @@
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, VisitMut};
+use oxc_semantic::{SymbolId, SymbolTable};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::Modules;
+
+/// How a required module is wrapped
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Interop {
+    /// `require("a")`
+    None,
+    /// `babelHelpers.interopRequireDefault(require("a"))`
+    Default,
+    /// `babelHelpers.interopRequireWildcard(require("a"))`
+    Wildcard,
+}
+
+/// The local names of the imports, and the members of the required modules replacing them
+#[derive(Default)]
+struct Imports {
+    /// `None` for the namespace of the module
+    bindings: FxHashMap<Atom, (Atom, Option<Atom>)>,
+    symbols: FxHashSet<SymbolId>,
+}
+
+/// ES modules to CommonJS
+///
+/// ```JavaScript
+/// import foo, { bar } from "foo";
+/// export const x = foo(bar);
+/// ```
+/// ```JavaScript
+/// "use strict";
+/// Object.defineProperty(exports, "__esModule", { value: true });
+/// Object.defineProperty(exports, "x", { enumerable: true, get: function () { return x; } });
+/// var _foo = babelHelpers.interopRequireWildcard(require("foo"));
+/// const x = (0, _foo.default)(_foo.bar);
+/// ```
+///
+/// The exports are getters, so they are live bindings of the exported declarations. The
+/// imports are replaced by the members of the required modules.
+impl<'a> Modules<'a> {
+    pub(super) fn transform_to_commonjs(&mut self, program: &mut Program<'a>) {
+        if !program.body.iter().any(|stmt| matches!(stmt, Statement::ModuleDeclaration(_))) {
+            return;
+        }
+
+        let mut imports = Imports::default();
+        let mut requires = self.ast.new_vec();
+        let mut getters = self.ast.new_vec();
+        let mut body = self.ast.new_vec();
+        let mut has_exports = false;
+        for stmt in self.ast.move_statement_vec(&mut program.body) {
+            let Statement::ModuleDeclaration(decl) = stmt else {
+                body.push(stmt);
+                continue;
+            };
+            match decl.unbox() {
+                ModuleDeclaration::ImportDeclaration(decl) => {
+                    if decl.import_kind.is_value() {
+                        self.transform_import(decl.unbox(), &mut imports, &mut requires);
+                    }
+                }
+                ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                    if decl.export_kind.is_type() {
+                        continue;
+                    }
+                    has_exports = true;
+                    let decl = decl.unbox();
+                    if let Some(source) = &decl.source {
+                        // `export { a as b } from "a"`
+                        let has_default = decl
+                            .specifiers
+                            .iter()
+                            .any(|spec| spec.local.name().as_str() == "default");
+                        let has_named = decl
+                            .specifiers
+                            .iter()
+                            .any(|spec| spec.local.name().as_str() != "default");
+                        let interop = self.interop(has_default, has_named, false);
+                        let object = self.require(&source.value, interop, &mut requires);
+                        for spec in &decl.specifiers {
+                            let value = self.member(self.identifier(&object), spec.local.name());
+                            getters.push(self.export_getter(spec.exported.name(), value));
+                        }
+                    } else if let Some(declaration) = decl.declaration {
+                        // `export const a = 1`
+                        declaration.bound_names(&mut |ident| {
+                            let value = self.identifier(&ident.name);
+                            getters.push(self.export_getter(&ident.name, value));
+                        });
+                        body.push(Statement::Declaration(declaration));
+                    } else {
+                        // `export { a as b }`
+                        for spec in &decl.specifiers {
+                            let value = self.identifier(spec.local.name());
+                            getters.push(self.export_getter(spec.exported.name(), value));
+                        }
+                    }
+                }
+                ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                    has_exports = true;
+                    let default = Atom::from("default");
+                    match decl.unbox().declaration {
+                        // `exports.default = expr`
+                        ExportDefaultDeclarationKind::Expression(expr) => {
+                            let target = self.exports_member(&default);
+                            body.push(self.ast.expression_statement(
+                                SPAN,
+                                self.ast.assignment_expression(
+                                    SPAN,
+                                    AssignmentOperator::Assign,
+                                    target,
+                                    expr,
+                                ),
+                            ));
+                        }
+                        ExportDefaultDeclarationKind::FunctionDeclaration(mut func) => {
+                            let name = self.default_name(&mut func.id);
+                            getters.push(self.export_getter(&default, self.identifier(&name)));
+                            body.push(Statement::Declaration(Declaration::FunctionDeclaration(
+                                func,
+                            )));
+                        }
+                        ExportDefaultDeclarationKind::ClassDeclaration(mut class) => {
+                            let name = self.default_name(&mut class.id);
+                            getters.push(self.export_getter(&default, self.identifier(&name)));
+                            body.push(Statement::Declaration(Declaration::ClassDeclaration(class)));
+                        }
+                        ExportDefaultDeclarationKind::TSInterfaceDeclaration(_)
+                        | ExportDefaultDeclarationKind::TSEnumDeclaration(_) => {}
+                    }
+                }
+                ModuleDeclaration::ExportAllDeclaration(decl) => {
+                    if decl.export_kind.is_type() {
+                        continue;
+                    }
+                    has_exports = true;
+                    if let Some(exported) = &decl.exported {
+                        // `export * as ns from "a"`
+                        let object =
+                            self.require(&decl.source.value, Interop::Wildcard, &mut requires);
+                        getters.push(self.export_getter(exported.name(), self.identifier(&object)));
+                    } else {
+                        // `export * from "a"`
+                        let object = self.require(&decl.source.value, Interop::None, &mut requires);
+                        requires.push(self.export_star(&object));
+                    }
+                }
+                decl => body.push(self.ast.module_declaration(decl)),
+            }
+        }
+
+        if !program.directives.iter().any(|directive| directive.directive == "use strict") {
+            let use_strict = Atom::from("use strict");
+            program.directives.insert(
+                0,
+                self.ast.directive(SPAN, StringLiteral::new(SPAN, use_strict.clone()), use_strict),
+            );
+        }
+        if has_exports && !self.options.strict {
+            // `exports.__esModule = true`
+            let properties = self.ast.new_vec_single(self.property("value", self.boolean(true)));
+            let descriptor = self.ast.object_expression(SPAN, properties, None);
+            program.body.push(self.define_property(self.string(&"__esModule".into()), descriptor));
+        }
+        program.body.extend(getters);
+        program.body.extend(requires);
+        program.body.extend(body);
+
+        let symbols = self.ctx.symbols();
+        let mut rewriter = ImportRewriter { modules: self, symbols: &symbols, imports: &imports };
+        for stmt in program.body.iter_mut() {
+            rewriter.visit_statement(stmt);
+        }
+    }
+
+    /// `import a, { b } from "a"` -> `var _a = babelHelpers.interopRequireWildcard(require("a"))`
+    fn transform_import(
+        &self,
+        decl: ImportDeclaration<'a>,
+        imports: &mut Imports,
+        requires: &mut Vec<'a, Statement<'a>>,
+    ) {
+        let specifiers = match decl.specifiers {
+            Some(specifiers) if !specifiers.is_empty() => specifiers,
+            // `import "a"` -> `require("a")`
+            _ => {
+                let require = self.require_call(&decl.source.value);
+                requires.push(self.ast.expression_statement(SPAN, require));
+                return;
+            }
+        };
+        let is_default = |spec: &ImportDeclarationSpecifier| match spec {
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => true,
+            ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                spec.imported.name().as_str() == "default"
+            }
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => false,
+        };
+        let has_namespace = specifiers
+            .iter()
+            .any(|spec| matches!(spec, ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)));
+        let has_default = specifiers.iter().any(is_default);
+        let has_named = specifiers.iter().any(|spec| {
+            matches!(spec, ImportDeclarationSpecifier::ImportSpecifier(_)) && !is_default(spec)
+        });
+        let interop = self.interop(has_default, has_named, has_namespace);
+        let object = self.require(&decl.source.value, interop, requires);
+
+        for spec in &specifiers {
+            let (local, property) = match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                    (&spec.local, Some(spec.imported.name().clone()))
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                    (&spec.local, Some("default".into()))
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => (&spec.local, None),
+            };
+            imports.bindings.insert(local.name.clone(), (object.clone(), property));
+            imports.symbols.extend(local.symbol_id.get());
+        }
+    }
+
+    fn interop(&self, has_default: bool, has_named: bool, has_namespace: bool) -> Interop {
+        if self.options.no_interop {
+            Interop::None
+        } else if has_namespace || (has_default && has_named) {
+            Interop::Wildcard
+        } else if has_default {
+            Interop::Default
+        } else {
+            Interop::None
+        }
+    }
+
+    /// `var _a = require("a");`, returns `_a`
+    fn require(
+        &self,
+        source: &Atom,
+        interop: Interop,
+        requires: &mut Vec<'a, Statement<'a>>,
+    ) -> Atom {
+        let name = self.generate_uid(&module_name(source));
+        let mut init = self.require_call(source);
+        let helper = match interop {
+            Interop::None => None,
+            Interop::Default => Some("interopRequireDefault"),
+            Interop::Wildcard => Some("interopRequireWildcard"),
+        };
+        if let Some(helper) = helper {
+            init = self.call(self.ctx.helper(helper), vec![init]);
+        }
+        let decl = self.variable_statement(VariableDeclarationKind::Var, name.clone(), init);
+        requires.push(Statement::Declaration(Declaration::VariableDeclaration(decl)));
+        name
+    }
+
+    fn require_call(&self, source: &Atom) -> Expression<'a> {
+        self.call(self.identifier(&"require".into()), vec![self.string(source)])
+    }
+
+    /// The name of an anonymous default export, `_default`
+    fn default_name(&self, id: &mut Option<BindingIdentifier>) -> Atom {
+        id.get_or_insert_with(|| BindingIdentifier::new(SPAN, self.generate_uid("default")))
+            .name
+            .clone()
+    }
+
+    /// `Object.defineProperty(exports, "name", { enumerable: true, get: function () { return value; } });`
+    fn export_getter(&self, name: &Atom, value: Expression<'a>) -> Statement<'a> {
+        let descriptor = self.getter_descriptor(value);
+        self.define_property(self.string(name), descriptor)
+    }
+
+    fn getter_descriptor(&self, value: Expression<'a>) -> Expression<'a> {
+        let body = self.ast.new_vec_single(self.ast.return_statement(SPAN, Some(value)));
+        let getter = self.function_expression(self.ast.new_vec(), body);
+        let mut properties = self.ast.new_vec();
+        properties.push(self.property("enumerable", self.boolean(true)));
+        properties.push(self.property("get", getter));
+        self.ast.object_expression(SPAN, properties, None)
+    }
+
+    /// `Object.defineProperty(exports, key, descriptor);`
+    fn define_property(&self, key: Expression<'a>, descriptor: Expression<'a>) -> Statement<'a> {
+        let callee = self.member(self.identifier(&"Object".into()), &"defineProperty".into());
+        let exports = self.identifier(&"exports".into());
+        self.ast.expression_statement(SPAN, self.call(callee, vec![exports, key, descriptor]))
+    }
+
+    /// ```JavaScript
+    /// Object.keys(_a).forEach(function (key) {
+    ///   if (key === "default" || key === "__esModule") return;
+    ///   if (key in exports && exports[key] === _a[key]) return;
+    ///   Object.defineProperty(exports, key, { enumerable: true, get: function () { return _a[key]; } });
+    /// });
+    /// ```
+    fn export_star(&self, object: &Atom) -> Statement<'a> {
+        let key = Atom::from("key");
+        let key_is = |name: &str| {
+            self.ast.binary_expression(
+                SPAN,
+                self.identifier(&key),
+                BinaryOperator::StrictEquality,
+                self.string(&name.into()),
+            )
+        };
+        let object_key = || {
+            self.ast.computed_member_expression(
+                SPAN,
+                self.identifier(object),
+                self.identifier(&key),
+                false,
+            )
+        };
+        let exports_key = self.ast.computed_member_expression(
+            SPAN,
+            self.identifier(&"exports".into()),
+            self.identifier(&key),
+            false,
+        );
+
+        let is_reserved = self.ast.logical_expression(
+            SPAN,
+            key_is("default"),
+            LogicalOperator::Or,
+            key_is("__esModule"),
+        );
+        let is_exported = self.ast.logical_expression(
+            SPAN,
+            self.ast.binary_expression(
+                SPAN,
+                self.identifier(&key),
+                BinaryOperator::In,
+                self.identifier(&"exports".into()),
+            ),
+            LogicalOperator::And,
+            self.ast.binary_expression(
+                SPAN,
+                exports_key,
+                BinaryOperator::StrictEquality,
+                object_key(),
+            ),
+        );
+        let mut body = self.ast.new_vec();
+        for test in [is_reserved, is_exported] {
+            let skip = self.ast.return_statement(SPAN, None);
+            body.push(self.ast.if_statement(SPAN, test, skip, None));
+        }
+        body.push(
+            self.define_property(self.identifier(&key), self.getter_descriptor(object_key())),
+        );
+
+        let params = self.ast.new_vec_single(self.ast.formal_parameter(
+            SPAN,
+            self.binding(key.clone()),
+            None,
+            false,
+            self.ast.new_vec(),
+        ));
+        let callback = self.function_expression(params, body);
+        let keys = self.member(self.identifier(&"Object".into()), &"keys".into());
+        let keys = self.call(keys, vec![self.identifier(object)]);
+        let for_each = self.call(self.member(keys, &"forEach".into()), vec![callback]);
+        self.ast.expression_statement(SPAN, for_each)
+    }
+
+    /// `exports.name`
+    fn exports_member(&self, name: &Atom) -> AssignmentTarget<'a> {
+        let Expression::MemberExpression(member) =
+            self.member(self.identifier(&"exports".into()), name)
+        else {
+            unreachable!()
+        };
+        AssignmentTarget::SimpleAssignmentTarget(SimpleAssignmentTarget::MemberAssignmentTarget(
+            member,
+        ))
+    }
+
+    fn function_expression(
+        &self,
+        params: Vec<'a, FormalParameter<'a>>,
+        body: Vec<'a, Statement<'a>>,
+    ) -> Expression<'a> {
+        let params =
+            self.ast.formal_parameters(SPAN, FormalParameterKind::FormalParameter, params, None);
+        let body = self.ast.function_body(SPAN, self.ast.new_vec(), body);
+        self.ast.function_expression(self.ast.function(
+            FunctionType::FunctionExpression,
+            SPAN,
+            None,
+            false,
+            false,
+            false,
+            None,
+            params,
+            Some(body),
+            None,
+            None,
+            Modifiers::empty(),
+        ))
+    }
+
+    fn property(&self, name: &str, value: Expression<'a>) -> ObjectPropertyKind<'a> {
+        let key = self.ast.property_key_identifier(IdentifierName::new(SPAN, name.into()));
+        ObjectPropertyKind::ObjectProperty(self.ast.object_property(
+            SPAN,
+            PropertyKind::Init,
+            key,
+            value,
+            None,
+            false,
+            false,
+            false,
+        ))
+    }
+
+    fn boolean(&self, value: bool) -> Expression<'a> {
+        self.ast.literal_boolean_expression(BooleanLiteral { span: SPAN, value })
+    }
+}
+
+/// The identifier of the last part of a module path: `./foo-bar.js` -> `fooBar`
+fn module_name(source: &str) -> String {
+    let name = source.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+    let name = name.split_once('.').map_or(name, |(stem, _)| stem);
+    let mut result = String::new();
+    for (i, part) in name.split(|c: char| !c.is_ascii_alphanumeric()).enumerate() {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            if i > 0 && !result.is_empty() {
+                result.push(first.to_ascii_uppercase());
+            } else {
+                result.push(first);
+            }
+            result.extend(chars);
+        }
+    }
+    if result.is_empty() {
+        "module".to_string()
+    } else {
+        result
+    }
+}
+
+/// Replace the references of the imports by the members of the required modules
+struct ImportRewriter<'a, 'b> {
+    modules: &'b Modules<'a>,
+    symbols: &'b SymbolTable,
+    imports: &'b Imports,
+}
+
+impl<'a, 'b> ImportRewriter<'a, 'b> {
+    /// The synthesized references, without a reference id, are resolved by name
+    fn find(&self, ident: &IdentifierReference) -> Option<&'b (Atom, Option<Atom>)> {
+        if let Some(reference_id) = ident.reference_id.get() {
+            let symbol_id = self.symbols.get_reference(reference_id).symbol_id()?;
+            if !self.imports.symbols.contains(&symbol_id) {
+                return None;
+            }
+        }
+        self.imports.bindings.get(&ident.name)
+    }
+
+    fn import_member(&self, expr: &Expression<'a>) -> Option<Expression<'a>> {
+        let Expression::Identifier(ident) = expr else { return None };
+        let (object, property) = self.find(ident)?;
+        let object = self.modules.identifier(object);
+        Some(match property {
+            Some(property) => self.modules.member(object, property),
+            None => object,
+        })
+    }
+}
+
+impl<'a, 'b> VisitMut<'a> for ImportRewriter<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if let Some(member) = self.import_member(expr) {
+            *expr = member;
+            return;
+        }
+        // `foo()` -> `(0, _foo.default)()`, so `this` is not the module
+        if let Expression::CallExpression(call) = expr {
+            if let Some(member @ Expression::MemberExpression(_)) = self.import_member(&call.callee)
+            {
+                let ast = &self.modules.ast;
+                let mut exprs = ast.new_vec_with_capacity(2);
+                exprs.push(ast.literal_number_expression(NumberLiteral::new(
+                    SPAN,
+                    0.0,
+                    "0",
+                    oxc_syntax::NumberBase::Decimal,
+                )));
+                exprs.push(member);
+                call.callee =
+                    ast.parenthesized_expression(SPAN, ast.sequence_expression(SPAN, exprs));
+            }
+        }
+        self.visit_expression_match(expr);
+    }
+
+    fn visit_object_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        // `{ foo }` -> `{ foo: _foo.default }`
+        if prop.shorthand && self.import_member(&prop.value).is_some() {
+            prop.shorthand = false;
+        }
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        if let Some(init) = &mut prop.init {
+            self.visit_expression(init);
+        }
+    }
+}
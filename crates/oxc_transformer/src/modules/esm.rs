@@ -0,0 +1,240 @@
+use oxc_ast::ast::*;
+use oxc_semantic::ScopeId;
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+
+use super::Modules;
+
+/// CommonJS to ES modules, for the `require` calls and the assignments of `exports` at the top
+/// level of the program:
+///
+/// ```JavaScript
+/// const fs = require("fs");
+/// const { join } = require("path");
+/// exports.read = (f) => fs.readFileSync(join(f));
+/// module.exports = { f };
+/// ```
+/// ```JavaScript
+/// import fs from "fs";
+/// import { join } from "path";
+/// export const read = (f) => fs.readFileSync(join(f));
+/// export default { f };
+/// ```
+///
+/// The other uses of `require`, `module` and `exports` are kept.
+impl<'a> Modules<'a> {
+    pub(super) fn transform_to_esm(&mut self, program: &mut Program<'a>) {
+        // ES modules are strict
+        program.directives.retain(|directive| directive.directive != "use strict");
+        let mut exported = vec![];
+        for stmt in self.ast.move_statement_vec(&mut program.body) {
+            if is_es_module_flag(&stmt) {
+                continue;
+            }
+            match self.require_to_import(stmt) {
+                Ok(import) => program.body.push(import),
+                Err(stmt) => match self.exports_to_export(stmt, &mut exported) {
+                    Ok(exports) => program.body.extend(exports),
+                    Err(stmt) => program.body.push(stmt),
+                },
+            }
+        }
+    }
+
+    /// * `require("a")` -> `import "a"`
+    /// * `const a = require("a")` -> `import a from "a"`
+    /// * `const { a, b: c } = require("a")` -> `import { a, b as c } from "a"`
+    fn require_to_import(&self, stmt: Statement<'a>) -> Result<Statement<'a>, Statement<'a>> {
+        let specifiers = match &stmt {
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.require_source(&expr_stmt.expression).map(|source| (source, None))
+            }
+            Statement::Declaration(Declaration::VariableDeclaration(decl))
+                if decl.declarations.len() == 1 =>
+            {
+                let declarator = &decl.declarations[0];
+                declarator
+                    .init
+                    .as_ref()
+                    .and_then(|init| self.require_source(init))
+                    .zip(import_specifiers(&declarator.id))
+                    .map(|(source, specifiers)| (source, Some(specifiers)))
+            }
+            _ => None,
+        };
+        let Some((source, specifiers)) = specifiers else { return Err(stmt) };
+        let specifiers = specifiers.map(|specifiers| {
+            let mut vec = self.ast.new_vec_with_capacity(specifiers.len());
+            vec.extend(specifiers);
+            vec
+        });
+        let decl = self.ast.import_declaration(
+            SPAN,
+            specifiers,
+            StringLiteral::new(SPAN, source),
+            None,
+            ImportOrExportKind::Value,
+        );
+        Ok(self.ast.module_declaration(ModuleDeclaration::ImportDeclaration(decl)))
+    }
+
+    /// The source of `require("a")`, when `require` is the global
+    fn require_source(&self, expr: &Expression<'a>) -> Option<Atom> {
+        let Expression::CallExpression(call) = expr else { return None };
+        let Expression::Identifier(callee) = &call.callee else { return None };
+        if callee.name != "require" || !self.is_global(callee) || call.arguments.len() != 1 {
+            return None;
+        }
+        match &call.arguments[0] {
+            Argument::Expression(Expression::StringLiteral(source)) => Some(source.value.clone()),
+            _ => None,
+        }
+    }
+
+    /// * `module.exports = expr` -> `export default expr`
+    /// * `exports.a = expr` -> `export const a = expr`
+    /// * `exports.a = expr` -> `const _a = expr; export { _a as a }` when `a` is declared
+    fn exports_to_export(
+        &self,
+        stmt: Statement<'a>,
+        exported: &mut std::vec::Vec<Atom>,
+    ) -> Result<std::vec::Vec<Statement<'a>>, Statement<'a>> {
+        let Statement::ExpressionStatement(expr_stmt) = &stmt else { return Err(stmt) };
+        let Expression::AssignmentExpression(assign) = &expr_stmt.expression else {
+            return Err(stmt);
+        };
+        let AssignmentTarget::SimpleAssignmentTarget(
+            SimpleAssignmentTarget::MemberAssignmentTarget(target),
+        ) = &assign.left
+        else {
+            return Err(stmt);
+        };
+        if assign.operator != AssignmentOperator::Assign {
+            return Err(stmt);
+        }
+        let name = if self.is_global_name(target.object(), "module") {
+            (target.static_property_name() == Some("exports")).then(|| Atom::from("default"))
+        } else if self.is_exports(target.object()) {
+            target.static_property_name().map(Atom::from)
+        } else {
+            None
+        };
+        let Some(name) = name.filter(|name| !exported.contains(name)) else { return Err(stmt) };
+        exported.push(name.clone());
+
+        let Statement::ExpressionStatement(expr_stmt) = stmt else { unreachable!() };
+        let Expression::AssignmentExpression(assign) = expr_stmt.unbox().expression else {
+            unreachable!()
+        };
+        let value = assign.unbox().right;
+        let decl = if name == "default" {
+            let decl = self.ast.alloc(ExportDefaultDeclaration {
+                span: SPAN,
+                declaration: ExportDefaultDeclarationKind::Expression(value),
+                exported: ModuleExportName::Identifier(IdentifierName::new(SPAN, name)),
+            });
+            return Ok(vec![self
+                .ast
+                .module_declaration(ModuleDeclaration::ExportDefaultDeclaration(decl))]);
+        } else if self.ctx.scopes().has_binding(ScopeId::new(0), &name) {
+            let local = self.generate_uid(&name);
+            let decl =
+                self.variable_statement(VariableDeclarationKind::Const, local.clone(), value);
+            let specifier = ExportSpecifier {
+                span: SPAN,
+                local: ModuleExportName::Identifier(IdentifierName::new(SPAN, local)),
+                exported: ModuleExportName::Identifier(IdentifierName::new(SPAN, name)),
+                export_kind: ImportOrExportKind::Value,
+            };
+            let export = self.ast.alloc(ExportNamedDeclaration {
+                span: SPAN,
+                declaration: None,
+                specifiers: self.ast.new_vec_single(specifier),
+                source: None,
+                export_kind: ImportOrExportKind::Value,
+            });
+            return Ok(vec![
+                Statement::Declaration(Declaration::VariableDeclaration(decl)),
+                self.ast.module_declaration(ModuleDeclaration::ExportNamedDeclaration(export)),
+            ]);
+        } else {
+            self.variable_statement(VariableDeclarationKind::Const, name, value)
+        };
+        let export = self.ast.alloc(ExportNamedDeclaration {
+            span: SPAN,
+            declaration: Some(Declaration::VariableDeclaration(decl)),
+            specifiers: self.ast.new_vec(),
+            source: None,
+            export_kind: ImportOrExportKind::Value,
+        });
+        Ok(vec![self.ast.module_declaration(ModuleDeclaration::ExportNamedDeclaration(export))])
+    }
+
+    /// `exports` or `module.exports`
+    fn is_exports(&self, expr: &Expression<'a>) -> bool {
+        match expr {
+            Expression::MemberExpression(member) => {
+                self.is_global_name(member.object(), "module")
+                    && member.static_property_name() == Some("exports")
+            }
+            _ => self.is_global_name(expr, "exports"),
+        }
+    }
+
+    fn is_global_name(&self, expr: &Expression<'a>, name: &str) -> bool {
+        matches!(expr, Expression::Identifier(ident) if ident.name == name && self.is_global(ident))
+    }
+
+    /// Whether `ident` is an unresolved reference of the source text
+    fn is_global(&self, ident: &IdentifierReference) -> bool {
+        ident.reference_id.get().is_some_and(|reference_id| {
+            self.ctx.symbols().get_reference(reference_id).symbol_id().is_none()
+        })
+    }
+}
+
+/// `Object.defineProperty(exports, "__esModule", { value: true })`
+fn is_es_module_flag(stmt: &Statement) -> bool {
+    let Statement::ExpressionStatement(stmt) = stmt else { return false };
+    let Expression::CallExpression(call) = &stmt.expression else { return false };
+    matches!(
+        &call.callee,
+        Expression::MemberExpression(member) if member.static_property_name() == Some("defineProperty")
+    ) && matches!(
+        call.arguments.get(1),
+        Some(Argument::Expression(Expression::StringLiteral(key))) if key.value == "__esModule"
+    )
+}
+
+fn import_specifiers(
+    pattern: &BindingPattern,
+) -> Option<std::vec::Vec<ImportDeclarationSpecifier>> {
+    match &pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => {
+            Some(vec![ImportDeclarationSpecifier::ImportDefaultSpecifier(ImportDefaultSpecifier {
+                span: SPAN,
+                local: BindingIdentifier::new(SPAN, ident.name.clone()),
+            })])
+        }
+        BindingPatternKind::ObjectPattern(object) if object.rest.is_none() => object
+            .properties
+            .iter()
+            .map(|property| {
+                let BindingPatternKind::BindingIdentifier(local) = &property.value.kind else {
+                    return None;
+                };
+                let imported = match &property.key {
+                    PropertyKey::Identifier(key) if !property.computed => key.name.clone(),
+                    _ => return None,
+                };
+                Some(ImportDeclarationSpecifier::ImportSpecifier(ImportSpecifier {
+                    span: SPAN,
+                    imported: ModuleExportName::Identifier(IdentifierName::new(SPAN, imported)),
+                    local: BindingIdentifier::new(SPAN, local.name.clone()),
+                    import_kind: ImportOrExportKind::Value,
+                }))
+            })
+            .collect(),
+        _ => None,
+    }
+}
@@ -0,0 +1,166 @@
+mod commonjs;
+mod esm;
+mod options;
+
+use std::rc::Rc;
+
+use oxc_allocator::Box;
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::identifier::is_identifier_name;
+
+pub use self::options::{ModuleFormat, ModulesOptions};
+use crate::{context::TransformerCtx, TransformOptions};
+
+/// Convert between ES modules and CommonJS
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-transform-modules-commonjs>
+/// * <https://github.com/babel/babel/tree/main/packages/babel-helper-module-transforms>
+/// * <https://esbuild.github.io/content-types/#es5>
+pub struct Modules<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+    options: ModulesOptions,
+}
+
+impl<'a> Modules<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        options.modules.map(|options| Self { ast, ctx, options })
+    }
+
+    pub fn transform_program(&mut self, program: &mut Program<'a>) {
+        match self.options.format {
+            ModuleFormat::CommonJs => self.transform_to_commonjs(program),
+            ModuleFormat::Esm => self.transform_to_esm(program),
+        }
+    }
+
+    fn generate_uid(&self, name: &str) -> Atom {
+        let name = self.ctx.scopes().generate_uid(name);
+        self.ctx.add_binding(name.clone());
+        name
+    }
+
+    fn identifier(&self, name: &Atom) -> Expression<'a> {
+        self.ast.identifier_expression(SPAN, name.clone())
+    }
+
+    fn string(&self, value: &Atom) -> Expression<'a> {
+        self.ast.string_expression(SPAN, value.clone())
+    }
+
+    /// `object.property`, or `object["property"]` when `property` is not an identifier name
+    fn member(&self, object: Expression<'a>, property: &Atom) -> Expression<'a> {
+        if is_identifier_name(property) {
+            let property = IdentifierName::new(SPAN, property.clone());
+            self.ast.static_member_expression(SPAN, object, property, false)
+        } else {
+            self.ast.computed_member_expression(SPAN, object, self.string(property), false)
+        }
+    }
+
+    fn call(&self, callee: Expression<'a>, args: std::vec::Vec<Expression<'a>>) -> Expression<'a> {
+        let mut arguments = self.ast.new_vec_with_capacity(args.len());
+        arguments.extend(args.into_iter().map(Argument::Expression));
+        self.ast.call_expression(SPAN, callee, arguments, false, None)
+    }
+
+    fn binding(&self, name: Atom) -> BindingPattern<'a> {
+        let ident = BindingIdentifier::new(SPAN, name);
+        self.ast.binding_pattern(self.ast.binding_pattern_identifier(ident), None, false)
+    }
+
+    /// `kind name = init;`
+    fn variable_statement(
+        &self,
+        kind: VariableDeclarationKind,
+        name: Atom,
+        init: Expression<'a>,
+    ) -> Box<'a, VariableDeclaration<'a>> {
+        let declarator =
+            self.ast.variable_declarator(SPAN, kind, self.binding(name), Some(init), false);
+        self.ast.variable_declaration(
+            SPAN,
+            kind,
+            self.ast.new_vec_single(declarator),
+            Modifiers::empty(),
+        )
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = TransformOptions {
+        modules: Some(ModulesOptions::default()),
+        ..TransformOptions::default()
+    };
+    let tests = &[
+        (
+            "import foo, { bar as baz } from './foo-bar.js'; import * as ns from 'ns'; import 'side-effect'; foo(baz, ns.x, { baz });",
+            r#""use strict";
+            var _fooBar = babelHelpers.interopRequireWildcard(require("./foo-bar.js"));
+            var _ns = babelHelpers.interopRequireWildcard(require("ns"));
+            require("side-effect");
+            (0, _fooBar.default)(_fooBar.bar, _ns.x, { baz: _fooBar.bar });"#,
+        ),
+        (
+            "import a from 'a'; import { b } from 'b'; function f(a) { return a + b(); } export { f, b as c }; export let x = a;",
+            r#""use strict";
+            Object.defineProperty(exports, "__esModule", { value: true });
+            Object.defineProperty(exports, "f", { enumerable: true, get: function() { return f; } });
+            Object.defineProperty(exports, "c", { enumerable: true, get: function() { return _b.b; } });
+            Object.defineProperty(exports, "x", { enumerable: true, get: function() { return x; } });
+            var _a = babelHelpers.interopRequireDefault(require("a"));
+            var _b = require("b");
+            function f(a) { return a + (0, _b.b)(); }
+            let x = _a.default;"#,
+        ),
+        (
+            "export default function () {} export { y } from 'y'; export * from 'z'; export * as w from 'w';",
+            r#""use strict";
+            Object.defineProperty(exports, "__esModule", { value: true });
+            Object.defineProperty(exports, "default", { enumerable: true, get: function() { return _default; } });
+            Object.defineProperty(exports, "y", { enumerable: true, get: function() { return _y.y; } });
+            Object.defineProperty(exports, "w", { enumerable: true, get: function() { return _w; } });
+            var _y = require("y");
+            var _z = require("z");
+            Object.keys(_z).forEach(function(key) {
+              if (key === "default" || key === "__esModule") return;
+              if (key in exports && exports[key] === _z[key]) return;
+              Object.defineProperty(exports, key, { enumerable: true, get: function() { return _z[key]; } });
+            });
+            var _w = babelHelpers.interopRequireWildcard(require("w"));
+            function _default() {}"#,
+        ),
+        (
+            "export default 1 + 1;",
+            r#""use strict";
+            Object.defineProperty(exports, "__esModule", { value: true });
+            exports.default = 1 + 1;"#,
+        ),
+    ];
+    Tester::new("test.mjs", options).test(tests);
+
+    let options = TransformOptions {
+        modules: Some(ModulesOptions { format: ModuleFormat::Esm, ..ModulesOptions::default() }),
+        ..TransformOptions::default()
+    };
+    let tests = &[
+        (
+            r#""use strict"; const fs = require("fs"); const { join, resolve: r } = require("path"); require("polyfill"); exports.read = (f) => fs.readFileSync(join(f)); module.exports.r = r;"#,
+            r#"import fs from "fs"; import { join, resolve as r } from "path"; import "polyfill"; export const read = (f) => fs.readFileSync(join(f)); const _r = r; export { _r as r };"#,
+        ),
+        (
+            r#"Object.defineProperty(exports, "__esModule", { value: true }); module.exports = { f }; function f() { return require("lazy"); }"#,
+            r#"export default { f }; function f() { return require("lazy"); }"#,
+        ),
+    ];
+    Tester::new("test.js", options).test(tests);
+}
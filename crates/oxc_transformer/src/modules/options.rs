@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModulesOptions {
+    /// The module format of the output, defaults to CommonJS
+    #[serde(default)]
+    pub format: ModuleFormat,
+    /// Do not define `exports.__esModule` for the ES modules converted to CommonJS
+    #[serde(default)]
+    pub strict: bool,
+    /// Require the modules without the `interopRequireDefault` and `interopRequireWildcard`
+    /// helpers, when all the imported modules are ES modules converted to CommonJS
+    #[serde(default)]
+    pub no_interop: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ModuleFormat {
+    /// ES modules to CommonJS
+    #[default]
+    #[serde(rename = "commonjs")]
+    CommonJs,
+    /// CommonJS to ES modules, for `require` and `exports` at the top level
+    #[serde(rename = "esm")]
+    Esm,
+}
@@ -1,27 +1,58 @@
 use oxc_syntax::assumptions::CompilerAssumptions;
 
 use crate::{
-    es2015::ArrowFunctionsOptions, es2020::NullishCoalescingOperatorOptions,
-    react_jsx::ReactJsxOptions, typescript::TypescriptOptions,
+    decorators::DecoratorsOptions,
+    define::DefineOptions,
+    env::TargetsOptions,
+    es2015::ArrowFunctionsOptions,
+    es2020::{NullishCoalescingOperatorOptions, OptionalChainingOptions},
+    es2022::ClassPropertiesOptions,
+    helpers::HelpersOptions,
+    modules::ModulesOptions,
+    react_jsx::ReactJsxOptions,
+    react_refresh::ReactRefreshOptions,
+    typescript::TypescriptOptions,
 };
 
 #[derive(Debug, Default, Clone)]
 pub struct TransformOptions {
     pub target: TransformTarget,
     pub assumptions: CompilerAssumptions,
+    /// Enables the transforms of the features not supported by the targeted engines
+    pub targets: Option<TargetsOptions>,
 
     pub react_jsx: Option<ReactJsxOptions>,
 
+    pub react_refresh: Option<ReactRefreshOptions>,
+
     pub typescript: Option<TypescriptOptions>,
 
+    pub decorators: Option<DecoratorsOptions>,
+
+    pub define: Option<DefineOptions>,
+
+    pub modules: Option<ModulesOptions>,
+
+    /// How the runtime helpers requested by the transforms are loaded
+    pub helpers: HelpersOptions,
+
+    // es2024
+    pub unicode_sets_regex: bool,
     // es2022
     pub class_static_block: bool,
+    pub class_properties: Option<ClassPropertiesOptions>,
     // es2021
     pub logical_assignment_operators: bool,
     // es2020
     pub nullish_coalescing_operator: Option<NullishCoalescingOperatorOptions>,
+    pub optional_chaining: Option<OptionalChainingOptions>,
     // es2019
     pub optional_catch_binding: bool,
+    // es2018
+    pub dotall_regex: bool,
+    pub async_generator_functions: bool,
+    // es2017
+    pub async_to_generator: bool,
     // es2016
     pub exponentiation_operator: bool,
     // es2015
@@ -30,6 +61,7 @@ pub struct TransformOptions {
     pub arrow_functions: Option<ArrowFunctionsOptions>,
     pub shorthand_properties: bool,
     pub sticky_regex: bool,
+    pub unicode_regex: bool,
     pub template_literals: bool,
     pub property_literals: bool,
     pub babel_8_breaking: Option<bool>,
@@ -44,6 +76,7 @@ pub enum TransformTarget {
     ES5,
     ES2015,
     ES2016,
+    ES2017,
     ES2018,
     ES2019,
     ES2020,
@@ -0,0 +1,132 @@
+use oxc_allocator::Vec;
+use oxc_ast::ast::*;
+
+use crate::context::TransformerCtx;
+
+/// Custom AST rewrites run by the [`Transformer`](crate::Transformer) with its built-in passes
+///
+/// The AST builder of the context allocates the new nodes in the arena of the program, and the
+/// semantic model of the source text is available through the context.
+///
+/// * `enter_*` hooks are called before the built-in passes transform a node and its children.
+/// * `exit_*` hooks are called after the node and its children are transformed.
+///
+/// ```ignore
+/// struct RemoveDebugger;
+///
+/// impl<'a> TransformerPlugin<'a> for RemoveDebugger {
+///     fn exit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>, _ctx: &mut TransformerCtx<'a>) {
+///         stmts.retain(|stmt| !matches!(stmt, Statement::DebuggerStatement(_)));
+///     }
+/// }
+///
+/// Transformer::new(&allocator, source_type, semantic, options)
+///     .with_plugin(RemoveDebugger)
+///     .build(program);
+/// ```
+#[allow(unused_variables)]
+pub trait TransformerPlugin<'a> {
+    /// Called before the built-in passes transform the program
+    fn enter_program(&mut self, program: &mut Program<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    /// Called after the built-in passes transformed the program
+    fn exit_program(&mut self, program: &mut Program<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn enter_statements(
+        &mut self,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        ctx: &mut TransformerCtx<'a>,
+    ) {
+    }
+
+    fn exit_statements(
+        &mut self,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        ctx: &mut TransformerCtx<'a>,
+    ) {
+    }
+
+    fn enter_statement(&mut self, stmt: &mut Statement<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn exit_statement(&mut self, stmt: &mut Statement<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn enter_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn exit_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn enter_function(&mut self, func: &mut Function<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn exit_function(&mut self, func: &mut Function<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn enter_class(&mut self, class: &mut Class<'a>, ctx: &mut TransformerCtx<'a>) {}
+
+    fn exit_class(&mut self, class: &mut Class<'a>, ctx: &mut TransformerCtx<'a>) {}
+}
+
+#[test]
+fn test() {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::{Codegen, CodegenOptions};
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::{SourceType, SPAN};
+
+    use crate::{TransformOptions, TransformTarget, Transformer};
+
+    /// Renames the unresolved references of `from` to `to`, and counts the functions
+    struct Rename {
+        from: &'static str,
+        to: &'static str,
+        functions: usize,
+    }
+
+    impl<'a> TransformerPlugin<'a> for Rename {
+        fn enter_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TransformerCtx<'a>) {
+            let Expression::Identifier(ident) = expr else { return };
+            let is_global = ident.reference_id.get().is_some_and(|reference_id| {
+                ctx.symbols().get_reference(reference_id).symbol_id().is_none()
+            });
+            if is_global && ident.name == self.from {
+                *expr = ctx.ast.identifier_reference_expression(IdentifierReference::new(
+                    SPAN,
+                    self.to.into(),
+                ));
+            }
+        }
+
+        fn exit_function(&mut self, _func: &mut Function<'a>, _ctx: &mut TransformerCtx<'a>) {
+            self.functions += 1;
+        }
+
+        fn exit_program(&mut self, program: &mut Program<'a>, ctx: &mut TransformerCtx<'a>) {
+            let count = ctx.ast.literal_number_expression(NumberLiteral::new(
+                SPAN,
+                0.0,
+                ctx.ast.new_str(&self.functions.to_string()),
+                oxc_syntax::NumberBase::Decimal,
+            ));
+            program.body.push(ctx.ast.expression_statement(SPAN, count));
+        }
+    }
+
+    let source_text = "const f = () => foo(); function g(foo) { return foo; }";
+    let allocator = Allocator::default();
+    let source_type = SourceType::default();
+    let program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let semantic = SemanticBuilder::new(source_text, source_type).build(&program).semantic;
+    let program = allocator.alloc(program);
+    // The arrow function is lowered by the built-in passes before the plugins exit it
+    let options = TransformOptions { target: TransformTarget::ES5, ..TransformOptions::default() };
+    Transformer::new(&allocator, source_type, semantic, options)
+        .with_plugin(Rename { from: "foo", to: "bar", functions: 0 })
+        .build(program)
+        .unwrap();
+    let printed =
+        Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program);
+
+    let expected = "const f = function() { return bar(); }; function g(foo) { return foo; } 2;";
+    let expected_program = Parser::new(&allocator, expected, source_type).parse().program;
+    let expected =
+        Codegen::<false>::new(expected.len(), CodegenOptions::default()).build(&expected_program);
+    assert_eq!(printed, expected);
+}
@@ -3,15 +3,16 @@ mod options;
 use std::rc::Rc;
 
 use oxc_allocator::Vec;
-use oxc_ast::{ast::*, AstBuilder};
+use oxc_ast::{ast::*, AstBuilder, AstKind};
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::Error,
 };
-use oxc_span::{Atom, GetSpan, Span, SPAN};
+use oxc_span::{Atom, GetSpan, LineColumn, LineIndex, Span, SPAN};
 use oxc_syntax::{
     identifier::{is_irregular_whitespace, is_line_terminator},
     xml_entities::XML_ENTITIES,
+    NumberBase,
 };
 
 pub use self::options::{ReactJsxOptions, ReactJsxRuntime, ReactJsxRuntimeOption};
@@ -61,6 +62,18 @@ pub struct ReactJsx<'a> {
     jsx_runtime_importer: Atom,
     pub babel_8_breaking: Option<bool>,
     default_runtime: ReactJsxRuntime,
+
+    // Development mode
+    import_jsx_dev: bool,
+    /// Whether `var _jsxFileName` needs to be declared
+    jsx_file_name: bool,
+    /// The lines of the source text, for `lineNumber` and `columnNumber` of `__source`
+    line_index: Option<LineIndex>,
+    /// Whether `this` is allowed in each enclosing non-arrow function,
+    /// it is not allowed in constructors of derived classes before `super()` is called.
+    this_allowed: std::vec::Vec<bool>,
+    derived_classes: std::vec::Vec<bool>,
+    constructor_span: Option<Span>,
 }
 
 enum JSXElementOrFragment<'a, 'b> {
@@ -78,6 +91,13 @@ impl<'a, 'b> JSXElementOrFragment<'a, 'b> {
         }
     }
 
+    fn span(&self) -> Span {
+        match self {
+            Self::Element(e) => e.span,
+            Self::Fragment(e) => e.span,
+        }
+    }
+
     fn children(&self) -> &'b Vec<'a, JSXChild<'a>> {
         match self {
             Self::Element(e) => &e.children,
@@ -127,12 +147,15 @@ impl<'a> ReactJsx<'a> {
             &mut ctx,
         )?;
 
+        let runtime = if jsx_options.development { "jsx-dev-runtime" } else { "jsx-runtime" };
         let jsx_runtime_importer =
             if jsx_options.import_source == "react" || default_runtime.is_classic() {
-                Atom::new_inline("react/jsx-runtime")
+                Atom::from(format!("react/{runtime}"))
             } else {
-                Atom::from(format!("{}/jsx-runtime", jsx_options.import_source))
+                Atom::from(format!("{}/{runtime}", jsx_options.import_source))
             };
+        let line_index =
+            jsx_options.development.then(|| LineIndex::new(ctx.semantic().source_text()));
         Some(Self {
             ast,
             ctx,
@@ -146,6 +169,12 @@ impl<'a> ReactJsx<'a> {
             import_create_element: false,
             babel_8_breaking: options.babel_8_breaking,
             default_runtime,
+            import_jsx_dev: false,
+            jsx_file_name: false,
+            line_index,
+            this_allowed: vec![],
+            derived_classes: vec![],
+            constructor_span: None,
         })
     }
 
@@ -173,6 +202,33 @@ impl<'a> ReactJsx<'a> {
         }
     }
 
+    pub fn enter_node(&mut self, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Class(class) => self.derived_classes.push(class.super_class.is_some()),
+            AstKind::MethodDefinition(def) if def.kind == MethodDefinitionKind::Constructor => {
+                self.constructor_span = Some(def.value.span);
+            }
+            AstKind::Function(func) => {
+                let is_derived_constructor = self.constructor_span == Some(func.span)
+                    && self.derived_classes.last().copied().unwrap_or_default();
+                self.this_allowed.push(!is_derived_constructor);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn leave_node(&mut self, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Class(_) => {
+                self.derived_classes.pop();
+            }
+            AstKind::Function(_) => {
+                self.this_allowed.pop();
+            }
+            _ => {}
+        }
+    }
+
     pub fn transform_expression(&mut self, expr: &mut Expression<'a>) {
         match expr {
             Expression::JSXElement(e) => {
@@ -190,6 +246,9 @@ impl<'a> ReactJsx<'a> {
             if self.options.import_source != "react" {
                 self.ctx.error(ImportSourceCannotBeSet);
             }
+            if self.jsx_file_name {
+                program.body.insert(0, self.jsx_file_name_declaration());
+            }
             return;
         }
 
@@ -200,6 +259,11 @@ impl<'a> ReactJsx<'a> {
             return;
         }
 
+        if self.jsx_file_name {
+            let declaration = self.jsx_file_name_declaration();
+            self.imports.push(declaration);
+        }
+
         let imports = self.ast.move_statement_vec(&mut self.imports);
         let index = program
             .body
@@ -237,11 +301,20 @@ impl<'a> ReactJsx<'a> {
         }
     }
 
+    /// The variable `require("react/jsx-runtime")` is assigned to in scripts
+    fn jsx_runtime_variable(&self) -> &'static str {
+        if self.options.development {
+            "_reactJsxDevRuntime"
+        } else {
+            "_reactJsxRuntime"
+        }
+    }
+
     fn add_require_jsx_runtime(&mut self) {
         if !self.require_jsx_runtime {
             self.require_jsx_runtime = true;
             self.add_require_statement(
-                "_reactJsxRuntime",
+                self.jsx_runtime_variable(),
                 Self::new_string_literal(self.jsx_runtime_importer.as_str()),
                 false,
             );
@@ -249,7 +322,9 @@ impl<'a> ReactJsx<'a> {
     }
 
     fn add_import_jsx(&mut self) {
-        if self.ctx.source_type().is_script() {
+        if self.options.development {
+            self.add_import_jsx_dev();
+        } else if self.ctx.source_type().is_script() {
             self.add_require_jsx_runtime();
         } else if !self.import_jsx {
             self.import_jsx = true;
@@ -262,7 +337,9 @@ impl<'a> ReactJsx<'a> {
     }
 
     fn add_import_jsxs(&mut self) {
-        if self.ctx.source_type().is_script() {
+        if self.options.development {
+            self.add_import_jsx_dev();
+        } else if self.ctx.source_type().is_script() {
             self.add_require_jsx_runtime();
         } else if !self.import_jsxs {
             self.import_jsxs = true;
@@ -271,6 +348,16 @@ impl<'a> ReactJsx<'a> {
         }
     }
 
+    fn add_import_jsx_dev(&mut self) {
+        if self.ctx.source_type().is_script() {
+            self.add_require_jsx_runtime();
+        } else if !self.import_jsx_dev {
+            self.import_jsx_dev = true;
+            let source = Self::new_string_literal(self.jsx_runtime_importer.as_str());
+            self.add_import_statement("jsxDEV", "_jsxDEV", source);
+        }
+    }
+
     fn add_import_fragment(&mut self) {
         if self.ctx.source_type().is_script() {
             self.add_require_jsx_runtime();
@@ -358,6 +445,7 @@ impl<'a> ReactJsx<'a> {
     fn transform_jsx<'b>(&mut self, e: &JSXElementOrFragment<'a, 'b>) -> Expression<'a> {
         let is_classic = self.default_runtime.is_classic();
         let is_automatic = self.default_runtime.is_automatic();
+        let is_development = self.options.development;
         let has_key_after_props_spread = e.has_key_after_props_spread();
 
         // TODO: compute the correct capacity for both runtimes
@@ -377,7 +465,7 @@ impl<'a> ReactJsx<'a> {
         let attributes_len = attributes.map_or(0, |attrs| attrs.len());
 
         // Add `null` to second argument in classic mode
-        if is_classic && attributes_len == 0 {
+        if is_classic && attributes_len == 0 && !is_development {
             let null_expr = self.ast.literal_null_expression(NullLiteral::new(SPAN));
             arguments.push(Argument::Expression(null_expr));
         }
@@ -390,7 +478,7 @@ impl<'a> ReactJsx<'a> {
 
             for attribute in attributes {
                 // optimize `{...prop}` to `prop` in static mode
-                if is_classic && attributes_len == 1 {
+                if is_classic && attributes_len == 1 && !is_development {
                     if let JSXAttributeItem::SpreadAttribute(spread) = attribute {
                         // deopt if spreading an object with `__proto__` key
                         if !matches!(&spread.argument, Expression::ObjectExpression(o) if o.has_proto())
@@ -419,6 +507,15 @@ impl<'a> ReactJsx<'a> {
             }
         }
 
+        // `__self` and `__source` props in classic mode
+        if is_classic && is_development {
+            if let Some(this) = self.get_self() {
+                properties.push(self.new_object_property("__self", this));
+            }
+            let source = self.get_source(e.span());
+            properties.push(self.new_object_property("__source", source));
+        }
+
         let mut need_jsxs = false;
 
         let children = e.children();
@@ -459,7 +556,25 @@ impl<'a> ReactJsx<'a> {
             arguments.push(Argument::Expression(object_expression));
         }
 
-        if is_automatic && key_prop.is_some() {
+        if is_automatic && is_development && !has_key_after_props_spread {
+            // jsxDEV(type, props, key, isStaticChildren, source, self)
+            let key = if key_prop.is_some() {
+                self.transform_jsx_attribute_value(key_prop)
+            } else {
+                self.ast.void_0()
+            };
+            arguments.push(Argument::Expression(key));
+            let is_static_children = BooleanLiteral::new(SPAN, need_jsxs);
+            arguments.push(Argument::Expression(
+                self.ast.literal_boolean_expression(is_static_children),
+            ));
+            arguments.push(Argument::Expression(self.get_source(e.span())));
+            let this = self.get_self().unwrap_or_else(|| {
+                let ident = IdentifierReference::new(SPAN, "undefined".into());
+                self.ast.identifier_reference_expression(ident)
+            });
+            arguments.push(Argument::Expression(this));
+        } else if is_automatic && key_prop.is_some() {
             arguments.push(Argument::Expression(self.transform_jsx_attribute_value(key_prop)));
         }
 
@@ -476,6 +591,68 @@ impl<'a> ReactJsx<'a> {
         self.ast.call_expression(SPAN, callee, arguments, false, None)
     }
 
+    fn new_object_property(&self, name: &str, value: Expression<'a>) -> ObjectPropertyKind<'a> {
+        let key = self.ast.property_key_identifier(IdentifierName::new(SPAN, name.into()));
+        let kind = PropertyKind::Init;
+        let property = self.ast.object_property(SPAN, kind, key, value, None, false, false, false);
+        ObjectPropertyKind::ObjectProperty(property)
+    }
+
+    /// `this` for `__self`, `None` if `this` is not allowed
+    fn get_self(&self) -> Option<Expression<'a>> {
+        self.this_allowed.last().copied().unwrap_or(true).then(|| self.ast.this_expression(SPAN))
+    }
+
+    /// `{ fileName: _jsxFileName, lineNumber: 1, columnNumber: 1 }` for `__source`
+    fn get_source(&mut self, span: Span) -> Expression<'a> {
+        self.jsx_file_name = true;
+        let LineColumn { line, column } =
+            self.line_index.as_ref().map_or_else(LineColumn::default, |line_index| {
+                line_index.line_column_utf16(span.start)
+            });
+
+        let file_name = {
+            let ident = IdentifierReference::new(SPAN, "_jsxFileName".into());
+            self.ast.identifier_reference_expression(ident)
+        };
+        let mut properties = self.ast.new_vec_with_capacity(3);
+        properties.push(self.new_object_property("fileName", file_name));
+        for (name, value) in [("lineNumber", line + 1), ("columnNumber", column + 1)] {
+            let raw = self.ast.new_str(&value.to_string());
+            let literal = self.ast.number_literal(SPAN, f64::from(value), raw, NumberBase::Decimal);
+            properties
+                .push(self.new_object_property(name, self.ast.literal_number_expression(literal)));
+        }
+        self.ast.object_expression(SPAN, properties, None)
+    }
+
+    /// `var _jsxFileName = "path/to/file.jsx";`
+    fn jsx_file_name_declaration(&self) -> Statement<'a> {
+        let path = self
+            .ctx
+            .semantic()
+            .module_record()
+            .resolved_absolute_path
+            .to_string_lossy()
+            .to_string();
+        let init = self.ast.literal_string_expression(StringLiteral::new(SPAN, path.into()));
+        let kind = VariableDeclarationKind::Var;
+        let id = self.ast.binding_pattern(
+            self.ast
+                .binding_pattern_identifier(BindingIdentifier::new(SPAN, "_jsxFileName".into())),
+            None,
+            false,
+        );
+        let decl = self.ast.variable_declarator(SPAN, kind, id, Some(init), false);
+        let decls = self.ast.new_vec_single(decl);
+        Statement::Declaration(Declaration::VariableDeclaration(self.ast.variable_declaration(
+            SPAN,
+            kind,
+            decls,
+            Modifiers::empty(),
+        )))
+    }
+
     fn get_react_references(&mut self) -> Expression<'a> {
         let ident = IdentifierReference::new(SPAN, "React".into());
         self.ast.identifier_reference_expression(ident)
@@ -523,9 +700,12 @@ impl<'a> ReactJsx<'a> {
             }
             ReactJsxRuntime::Automatic => {
                 let is_script = self.ctx.source_type().is_script();
+                let is_development = self.options.development;
                 let name = if is_script {
                     if has_key_after_props_spread {
                         "createElement"
+                    } else if is_development {
+                        "jsxDEV"
                     } else if jsxs {
                         "jsxs"
                     } else {
@@ -533,6 +713,8 @@ impl<'a> ReactJsx<'a> {
                     }
                 } else if has_key_after_props_spread {
                     "_createElement"
+                } else if is_development {
+                    "_jsxDEV"
                 } else if jsxs {
                     "_jsxs"
                 } else {
@@ -540,8 +722,11 @@ impl<'a> ReactJsx<'a> {
                 };
 
                 if is_script {
-                    let object_ident_name =
-                        if has_key_after_props_spread { "_react" } else { "_reactJsxRuntime" };
+                    let object_ident_name = if has_key_after_props_spread {
+                        "_react"
+                    } else {
+                        self.jsx_runtime_variable()
+                    };
                     self.get_static_member_expression(object_ident_name, name)
                 } else {
                     let ident = IdentifierReference::new(SPAN, name.into());
@@ -564,7 +749,7 @@ impl<'a> ReactJsx<'a> {
             }
             ReactJsxRuntime::Automatic => {
                 if self.ctx.source_type().is_script() {
-                    self.get_static_member_expression("_reactJsxRuntime", "Fragment")
+                    self.get_static_member_expression(self.jsx_runtime_variable(), "Fragment")
                 } else {
                     let ident = IdentifierReference::new(SPAN, "_Fragment".into());
                     self.ast.identifier_reference_expression(ident)
@@ -794,3 +979,36 @@ impl<'a> ReactJsx<'a> {
         unsafe { String::from_utf8_unchecked(buffer) }
     }
 }
+
+#[test]
+fn test_development() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = |runtime| TransformOptions {
+        react_jsx: Some(ReactJsxOptions {
+            runtime: Some(ReactJsxRuntimeOption::Valid(runtime)),
+            development: true,
+            ..ReactJsxOptions::default()
+        }),
+        ..TransformOptions::default()
+    };
+
+    Tester::new("test.jsx", options(ReactJsxRuntime::Classic)).test(&[(
+        "<div>\n  <a />\n</div>",
+        r#"var _jsxFileName = "";
+        React.createElement("div", { __self: this, __source: { fileName: _jsxFileName, lineNumber: 1, columnNumber: 1 } },
+          React.createElement("a", { __self: this, __source: { fileName: _jsxFileName, lineNumber: 2, columnNumber: 3 } }));"#,
+    )]);
+
+    Tester::new("test.mjs", options(ReactJsxRuntime::Automatic)).test(&[(
+        "class A extends B { constructor() { super(<a key=\"k\" />); } }\n<><b /><c /></>",
+        r#"import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+        import { Fragment as _Fragment } from "react/jsx-dev-runtime";
+        var _jsxFileName = "";
+        class A extends B { constructor() { super(_jsxDEV("a", {}, "k", false, { fileName: _jsxFileName, lineNumber: 1, columnNumber: 43 }, undefined)); } }
+        _jsxDEV(_Fragment, { children: [
+          _jsxDEV("b", {}, void 0, false, { fileName: _jsxFileName, lineNumber: 2, columnNumber: 3 }, this),
+          _jsxDEV("c", {}, void 0, false, { fileName: _jsxFileName, lineNumber: 2, columnNumber: 8 }, this)
+        ] }, void 0, true, { fileName: _jsxFileName, lineNumber: 2, columnNumber: 1 }, this);"#,
+    )]);
+}
@@ -25,6 +25,10 @@ pub struct ReactJsxOptions {
     /// Replace the component used when compiling JSX fragments. It should be a valid JSX tag name. default to `React.Fragment`
     #[serde(default = "default_pragma_frag")]
     pub pragma_frag: Cow<'static, str>,
+    /// Toggles plugins that aid in development, such as the `__source` and `__self` props in the
+    /// classic runtime and `jsxDEV` in the automatic runtime. default to false
+    #[serde(default)]
+    pub development: bool,
 
     /// When spreading props, use Object.assign directly instead of Babel's extend helper.
     /// Use `Some<T>` instead of `bool` because we want to know if user set this field explicitly,
@@ -60,6 +64,7 @@ impl Default for ReactJsxOptions {
             import_source: default_import_source(),
             pragma: default_pragma(),
             pragma_frag: default_pragma_frag(),
+            development: false,
             use_built_ins: None,
             use_spread: None,
         }
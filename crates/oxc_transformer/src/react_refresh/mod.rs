@@ -0,0 +1,609 @@
+mod options;
+
+use std::{mem, rc::Rc};
+
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_semantic::{react::is_component_name, react::is_hook_name, ScopeFlags};
+use oxc_span::{Atom, GetSpan, Span, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+
+pub use self::options::ReactRefreshOptions;
+use crate::{context::TransformerCtx, TransformOptions};
+
+/// The hooks built into React, which do not need to be tracked by the signatures
+const BUILTIN_HOOKS: [&str; 18] = [
+    "useState",
+    "useReducer",
+    "useEffect",
+    "useLayoutEffect",
+    "useMemo",
+    "useCallback",
+    "useRef",
+    "useContext",
+    "useImperativeHandle",
+    "useDebugValue",
+    "useId",
+    "useDeferredValue",
+    "useTransition",
+    "useInsertionEffect",
+    "useSyncExternalStore",
+    "useFormStatus",
+    "useActionState",
+    "useOptimistic",
+];
+
+/// React Fast Refresh
+///
+/// Registers the components of the module and the signatures of the hooks they call, so the
+/// components can be hot reloaded while keeping their state.
+///
+/// ```JavaScript
+/// export default function App() {
+///   const [count] = useState(0);
+///   return <div>{count}</div>;
+/// }
+/// ```
+/// ```JavaScript
+/// var _s = $RefreshSig$();
+/// export default function App() {
+///   _s();
+///   const [count] = useState(0);
+///   return <div>{count}</div>;
+/// }
+/// _s(App, "useState{[count](0)}");
+/// _c = App;
+/// var _c;
+/// $RefreshReg$(_c, "App");
+/// ```
+///
+/// The signatures are emitted in full instead of being hashed.
+///
+/// References:
+/// * <https://github.com/facebook/react/blob/main/packages/react-refresh/src/ReactFreshBabelPlugin.js>
+/// * <https://github.com/facebook/react/issues/16604#issuecomment-528663101>
+pub struct ReactRefresh<'a> {
+    ast: Rc<AstBuilder<'a>>,
+    ctx: TransformerCtx<'a>,
+    options: ReactRefreshOptions,
+
+    /// The handles of the registered components, and their ids
+    registrations: std::vec::Vec<(Atom, String)>,
+    /// The hook calls of each enclosing function
+    hook_calls: std::vec::Vec<std::vec::Vec<HookCall<'a>>>,
+    /// The hook calls of the function visited last
+    last_hook_calls: Option<std::vec::Vec<HookCall<'a>>>,
+    /// The hook call initializing the variable declarator being visited
+    declarator_hook_call: Option<Span>,
+    /// The signature variables of each enclosing statement list
+    signature_vars: std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>,
+}
+
+struct HookCall<'a> {
+    name: Atom,
+    key: String,
+    callee: Expression<'a>,
+}
+
+impl<'a> ReactRefresh<'a> {
+    pub fn new(
+        ast: Rc<AstBuilder<'a>>,
+        ctx: TransformerCtx<'a>,
+        options: &TransformOptions,
+    ) -> Option<Self> {
+        options.react_refresh.clone().map(|options| Self {
+            ast,
+            ctx,
+            options,
+            registrations: vec![],
+            hook_calls: vec![],
+            last_hook_calls: None,
+            declarator_hook_call: None,
+            signature_vars: vec![],
+        })
+    }
+
+    pub fn transform_program(&mut self, program: &mut Program<'a>) {
+        self.register_statements(&mut program.body);
+        self.visit_statements(&mut program.body);
+
+        if self.registrations.is_empty() {
+            return;
+        }
+        // var _c, _c2;
+        let mut declarators = self.ast.new_vec_with_capacity(self.registrations.len());
+        for (handle, _) in &self.registrations {
+            declarators.push(self.declarator(handle.clone(), None));
+        }
+        program.body.push(self.var_declaration(declarators));
+        // $RefreshReg$(_c, "App");
+        for (handle, id) in mem::take(&mut self.registrations) {
+            let callee = self.identifier(&self.options.refresh_reg.as_ref().into());
+            let call = self.call(callee, vec![self.identifier(&handle), self.string(&id)]);
+            program.body.push(self.ast.expression_statement(SPAN, call));
+        }
+    }
+
+    /// Register the components declared by the top level statements
+    fn register_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            let mut handles = vec![];
+            match &mut stmt {
+                Statement::Declaration(decl) => self.register_declaration(decl, &mut handles),
+                Statement::ModuleDeclaration(decl) => match &mut **decl {
+                    ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                        if let Some(decl) = &mut decl.declaration {
+                            self.register_declaration(decl, &mut handles);
+                        }
+                    }
+                    ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                        match &mut decl.declaration {
+                            ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                                self.register_function(func, &mut handles);
+                            }
+                            ExportDefaultDeclarationKind::Expression(expr) => {
+                                self.find_inner_components("%default%", expr, None);
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+            stmts.push(stmt);
+            // _c = App;
+            for (handle, name) in handles {
+                let expr = self.assign(handle, self.identifier(&name));
+                stmts.push(self.ast.expression_statement(SPAN, expr));
+            }
+        }
+    }
+
+    fn register_declaration(
+        &mut self,
+        decl: &mut Declaration<'a>,
+        handles: &mut std::vec::Vec<(Atom, Atom)>,
+    ) {
+        match decl {
+            Declaration::FunctionDeclaration(func) => self.register_function(func, handles),
+            Declaration::VariableDeclaration(decl) => {
+                for declarator in decl.declarations.iter_mut() {
+                    let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+                        continue;
+                    };
+                    let name = ident.name.clone();
+                    if !is_component_name(&name) {
+                        continue;
+                    }
+                    let Some(init) = &mut declarator.init else { continue };
+                    if matches!(
+                        init,
+                        Expression::ArrowExpression(_)
+                            | Expression::FunctionExpression(_)
+                            | Expression::CallExpression(_)
+                    ) {
+                        self.find_inner_components(&name, init, Some((&name, handles)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn register_function(
+        &mut self,
+        func: &Function<'a>,
+        handles: &mut std::vec::Vec<(Atom, Atom)>,
+    ) {
+        if let Some(id) = &func.id {
+            if is_component_name(&id.name) {
+                let handle = self.create_registration(id.name.to_string());
+                handles.push((handle, id.name.clone()));
+            }
+        }
+    }
+
+    /// Register the components of `expr`, named `id`, and of the higher order components it
+    /// calls, e.g. `id$memo` for `memo(() => {})`.
+    ///
+    /// The component initializing the declarator `declarator` is registered after the
+    /// declaration, the others are registered inline: `memo(_c = () => {})`.
+    fn find_inner_components(
+        &mut self,
+        id: &str,
+        expr: &mut Expression<'a>,
+        declarator: Option<(&Atom, &mut std::vec::Vec<(Atom, Atom)>)>,
+    ) -> bool {
+        match expr {
+            Expression::Identifier(ident) => return is_component_name(&ident.name),
+            Expression::ArrowExpression(arrow) => {
+                // Higher order functions, `() => () => {}`
+                if arrow.expression
+                    && matches!(arrow.get_expression(), Some(Expression::ArrowExpression(_)))
+                {
+                    return false;
+                }
+            }
+            Expression::FunctionExpression(_) => {}
+            Expression::CallExpression(call) => {
+                let callee = call.callee.span().source_text(self.ctx.semantic().source_text());
+                let inner_id = format!("{id}${callee}");
+                let Some(Argument::Expression(arg)) = call.arguments.first_mut() else {
+                    return false;
+                };
+                if !self.find_inner_components(&inner_id, arg, None) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
+        let handle = self.create_registration(id.to_string());
+        if let Some((name, handles)) = declarator {
+            handles.push((handle, name.clone()));
+        } else {
+            let value = self.ast.move_expression(expr);
+            *expr = self.assign(handle, value);
+        }
+        true
+    }
+
+    fn create_registration(&mut self, id: String) -> Atom {
+        let handle = self.generate_uid("c");
+        self.registrations.push((handle.clone(), id));
+        handle
+    }
+
+    /// Create the signature of a function calling `hook_calls`, and call it at the start of
+    /// `body`.
+    ///
+    /// Returns the arguments of the signature following the function:
+    /// `_s(Foo, "useState{[a](0)}\nuseCustom{}", false, function () { return [useCustom]; })`
+    fn create_signature(
+        &mut self,
+        hook_calls: std::vec::Vec<HookCall<'a>>,
+        body: &mut FunctionBody<'a>,
+    ) -> (Atom, std::vec::Vec<Expression<'a>>) {
+        let handle = self.generate_uid("s");
+        let callee = self.identifier(&self.options.refresh_sig.as_ref().into());
+        let init = self.call(callee, vec![]);
+        let declarator = self.declarator(handle.clone(), Some(init));
+        self.signature_vars
+            .last_mut()
+            .expect("functions are visited inside statement lists")
+            .push(declarator);
+
+        let call = self.call(self.identifier(&handle), vec![]);
+        body.statements.insert(0, self.ast.expression_statement(SPAN, call));
+
+        let key = hook_calls
+            .iter()
+            .map(|hook_call| format!("{}{{{}}}", hook_call.name, hook_call.key))
+            .collect::<std::vec::Vec<_>>()
+            .join("\n");
+        let mut args = vec![self.string(&key)];
+
+        // A custom hook which is not in scope can not be tracked, so the state is reset.
+        let mut force_reset = false;
+        let mut custom_hooks = self.ast.new_vec();
+        for hook_call in hook_calls {
+            if BUILTIN_HOOKS.contains(&hook_call.name.as_str()) {
+                continue;
+            }
+            if self.is_in_scope(&hook_call.callee) {
+                custom_hooks.push(ArrayExpressionElement::Expression(hook_call.callee));
+            } else {
+                force_reset = true;
+            }
+        }
+        if force_reset || !custom_hooks.is_empty() {
+            args.push(self.ast.literal_boolean_expression(BooleanLiteral::new(SPAN, force_reset)));
+        }
+        if !custom_hooks.is_empty() {
+            args.push(self.custom_hooks_function(custom_hooks));
+        }
+        (handle, args)
+    }
+
+    /// `function () { return [useCustom]; }`
+    fn custom_hooks_function(
+        &self,
+        elements: Vec<'a, ArrayExpressionElement<'a>>,
+    ) -> Expression<'a> {
+        let array = self.ast.array_expression(SPAN, elements, None);
+        let statements = self.ast.new_vec_single(self.ast.return_statement(SPAN, Some(array)));
+        let params = self.ast.formal_parameters(
+            SPAN,
+            FormalParameterKind::FormalParameter,
+            self.ast.new_vec(),
+            None,
+        );
+        let body = self.ast.function_body(SPAN, self.ast.new_vec(), statements);
+        let function = self.ast.function(
+            FunctionType::FunctionExpression,
+            SPAN,
+            None,
+            false,
+            false,
+            false,
+            None,
+            params,
+            Some(body),
+            None,
+            None,
+            Modifiers::empty(),
+        );
+        self.ast.function_expression(function)
+    }
+
+    /// Whether the binding of the hook, or of the namespace of `Namespace.useHook`, is declared
+    fn is_in_scope(&self, callee: &Expression<'a>) -> bool {
+        let ident = match callee {
+            Expression::Identifier(ident) => ident,
+            Expression::MemberExpression(member) => match member.object() {
+                Expression::Identifier(ident) => ident,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        ident.reference_id.get().is_some_and(|reference_id| {
+            self.ctx.symbols().get_reference(reference_id).symbol_id().is_some()
+        })
+    }
+
+    /// `useState`, `React.useState`
+    fn hook_name(call: &CallExpression<'a>) -> Option<Atom> {
+        let name = match &call.callee {
+            Expression::Identifier(ident) => ident.name.clone(),
+            Expression::MemberExpression(member) => member.static_property_name()?.into(),
+            _ => return None,
+        };
+        is_hook_name(&name).then_some(name)
+    }
+
+    fn record_hook_call(&mut self, call: &CallExpression<'a>, mut key: String) {
+        let Some(name) = Self::hook_name(call) else { return };
+        let Some(hook_calls) = self.hook_calls.last_mut() else { return };
+        // The initial state is part of the signature
+        let arg = match name.as_str() {
+            "useState" => call.arguments.first(),
+            "useReducer" => call.arguments.get(1),
+            _ => None,
+        };
+        if let Some(arg) = arg {
+            let source_text = self.ctx.semantic().source_text();
+            key.push_str(&format!("({})", arg.span().source_text(source_text)));
+        }
+        hook_calls.push(HookCall { name, key, callee: self.ast.copy(&call.callee) });
+    }
+
+    fn generate_uid(&self, name: &str) -> Atom {
+        let name = self.ctx.scopes().generate_uid(name);
+        self.ctx.add_binding(name.clone());
+        name
+    }
+
+    fn declarator(&self, name: Atom, init: Option<Expression<'a>>) -> VariableDeclarator<'a> {
+        let kind = VariableDeclarationKind::Var;
+        let binding = self.ast.binding_pattern(
+            self.ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, name)),
+            None,
+            false,
+        );
+        self.ast.variable_declarator(SPAN, kind, binding, init, false)
+    }
+
+    fn var_declaration(&self, declarators: Vec<'a, VariableDeclarator<'a>>) -> Statement<'a> {
+        let decl = self.ast.variable_declaration(
+            SPAN,
+            VariableDeclarationKind::Var,
+            declarators,
+            Modifiers::empty(),
+        );
+        Statement::Declaration(Declaration::VariableDeclaration(decl))
+    }
+
+    fn assign(&self, name: Atom, value: Expression<'a>) -> Expression<'a> {
+        let target =
+            self.ast.simple_assignment_target_identifier(IdentifierReference::new(SPAN, name));
+        self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            value,
+        )
+    }
+
+    fn identifier(&self, name: &Atom) -> Expression<'a> {
+        self.ast.identifier_reference_expression(IdentifierReference::new(SPAN, name.clone()))
+    }
+
+    fn string(&self, value: &str) -> Expression<'a> {
+        self.ast.literal_string_expression(StringLiteral::new(SPAN, value.into()))
+    }
+
+    fn call(&self, callee: Expression<'a>, args: std::vec::Vec<Expression<'a>>) -> Expression<'a> {
+        let mut arguments = self.ast.new_vec_with_capacity(args.len());
+        arguments.extend(args.into_iter().map(Argument::Expression));
+        self.ast.call_expression(SPAN, callee, arguments, false, None)
+    }
+}
+
+/// The signatures of the functions calling hooks
+///
+/// * Declarations are signed after the statement: `function Foo() {}; _s(Foo, "key");`
+/// * Expressions are signed inline: `_s(() => {}, "key")`
+impl<'a> VisitMut<'a> for ReactRefresh<'a> {
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        self.signature_vars.push(self.ast.new_vec());
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        for mut stmt in old_stmts {
+            self.last_hook_calls = None;
+            self.visit_statement(&mut stmt);
+            let hook_calls = self.last_hook_calls.take().filter(|calls| !calls.is_empty());
+            let func = match &mut stmt {
+                Statement::Declaration(Declaration::FunctionDeclaration(func)) => Some(func),
+                Statement::ModuleDeclaration(decl) => match &mut **decl {
+                    ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                        match &mut decl.declaration {
+                            Some(Declaration::FunctionDeclaration(func)) => Some(func),
+                            _ => None,
+                        }
+                    }
+                    ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                        match &mut decl.declaration {
+                            ExportDefaultDeclarationKind::FunctionDeclaration(func) => Some(func),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            let signature = match (hook_calls, func) {
+                (Some(hook_calls), Some(func)) if func.id.is_some() && func.body.is_some() => {
+                    let name = func.id.as_ref().unwrap().name.clone();
+                    let body = func.body.as_mut().unwrap();
+                    Some((name, self.create_signature(hook_calls, body)))
+                }
+                _ => None,
+            };
+            stmts.push(stmt);
+            if let Some((name, (handle, args))) = signature {
+                let mut arguments = vec![self.identifier(&name)];
+                arguments.extend(args);
+                let call = self.call(self.identifier(&handle), arguments);
+                stmts.push(self.ast.expression_statement(SPAN, call));
+            }
+        }
+        let declarators = self.signature_vars.pop().unwrap();
+        if !declarators.is_empty() {
+            stmts.insert(0, self.var_declaration(declarators));
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        if !matches!(expr, Expression::ArrowExpression(_) | Expression::FunctionExpression(_)) {
+            return;
+        }
+        let Some(hook_calls) = self.last_hook_calls.take().filter(|calls| !calls.is_empty()) else {
+            return;
+        };
+        let (handle, args) = match expr {
+            Expression::ArrowExpression(arrow) => {
+                // `() => useHook()` -> `() => { _s(); return useHook(); }`
+                if arrow.expression {
+                    arrow.expression = false;
+                    if let Some(Statement::ExpressionStatement(stmt)) = arrow.body.statements.pop()
+                    {
+                        let argument = stmt.unbox().expression;
+                        let stmt = self.ast.return_statement(SPAN, Some(argument));
+                        arrow.body.statements.push(stmt);
+                    }
+                }
+                self.create_signature(hook_calls, &mut arrow.body)
+            }
+            Expression::FunctionExpression(func) => {
+                let Some(body) = &mut func.body else { return };
+                self.create_signature(hook_calls, body)
+            }
+            _ => unreachable!(),
+        };
+        let mut arguments = vec![self.ast.move_expression(expr)];
+        arguments.extend(args);
+        *expr = self.call(self.identifier(&handle), arguments);
+    }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, _flags: Option<ScopeFlags>) {
+        self.hook_calls.push(vec![]);
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+        self.last_hook_calls = self.hook_calls.pop();
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &mut ArrowExpression<'a>) {
+        self.hook_calls.push(vec![]);
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.last_hook_calls = self.hook_calls.pop();
+    }
+
+    fn visit_variable_declarator(&mut self, declarator: &mut VariableDeclarator<'a>) {
+        // `const [a] = useState(0)` -> `useState{[a](0)}`
+        if let Some(Expression::CallExpression(call)) = &declarator.init {
+            if Self::hook_name(call).is_some() {
+                let source_text = self.ctx.semantic().source_text();
+                let key = declarator.id.span().source_text(source_text).to_string();
+                self.record_hook_call(call, key);
+                self.declarator_hook_call = Some(call.span);
+            }
+        }
+        self.visit_binding_pattern(&mut declarator.id);
+        if let Some(init) = &mut declarator.init {
+            self.visit_expression(init);
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &mut CallExpression<'a>) {
+        if self.declarator_hook_call == Some(expr.span) {
+            self.declarator_hook_call = None;
+        } else {
+            self.record_hook_call(expr, String::new());
+        }
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        self.visit_expression(&mut expr.callee);
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let options = TransformOptions {
+        react_refresh: Some(ReactRefreshOptions::default()),
+        ..TransformOptions::default()
+    };
+
+    let tests = &[
+        (
+            "export default function App() { const [count] = useState(0); return <div>{count}</div>; }",
+            r#"var _s = $RefreshSig$();
+            export default function App() { _s(); const [count] = useState(0); return <div>{count}</div>; }
+            _s(App, "useState{[count](0)}");
+            _c = App;
+            var _c;
+            $RefreshReg$(_c, "App");"#,
+        ),
+        (
+            "function useCustom() { return useRef(); } export const Foo = memo(() => { useCustom(); return <Bar />; }); const helper = () => 1;",
+            r#"var _s = $RefreshSig$(), _s2 = $RefreshSig$();
+            function useCustom() { _s(); return useRef(); }
+            _s(useCustom, "useRef{}");
+            export const Foo = memo(_c = _s2(() => { _s2(); useCustom(); return <Bar />; }, "useCustom{}", false, function() { return [useCustom]; }));
+            _c2 = Foo;
+            const helper = () => 1;
+            var _c, _c2;
+            $RefreshReg$(_c, "Foo$memo");
+            $RefreshReg$(_c2, "Foo");"#,
+        ),
+        (
+            "const A = () => useGlobal(); export default () => <A />;",
+            r#"var _s = $RefreshSig$();
+            const A = _s(() => { _s(); return useGlobal(); }, "useGlobal{}", true);
+            _c = A;
+            export default _c2 = () => <A />;
+            var _c, _c2;
+            $RefreshReg$(_c, "A");
+            $RefreshReg$(_c2, "%default%");"#,
+        ),
+    ];
+
+    Tester::new("test.jsx", options).test(tests);
+}
@@ -0,0 +1,28 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactRefreshOptions {
+    /// The function registering the components, defaults to `$RefreshReg$`
+    #[serde(default = "default_refresh_reg")]
+    pub refresh_reg: Cow<'static, str>,
+    /// The function creating the signatures of the hooks, defaults to `$RefreshSig$`
+    #[serde(default = "default_refresh_sig")]
+    pub refresh_sig: Cow<'static, str>,
+}
+
+fn default_refresh_reg() -> Cow<'static, str> {
+    Cow::Borrowed("$RefreshReg$")
+}
+
+fn default_refresh_sig() -> Cow<'static, str> {
+    Cow::Borrowed("$RefreshSig$")
+}
+
+impl Default for ReactRefreshOptions {
+    fn default() -> Self {
+        Self { refresh_reg: default_refresh_reg(), refresh_sig: default_refresh_sig() }
+    }
+}
@@ -29,17 +29,28 @@ impl Tester {
         }
     }
 
+    /// The transform of `source_text` fails with an error of `message`
+    pub fn test_error(&self, source_text: &str, message: &str) {
+        let errors = self.transform(source_text).expect_err(source_text);
+        assert!(
+            errors.iter().any(|error| error.to_string() == message),
+            "{source_text}: {errors:?}"
+        );
+    }
+
     fn transform(&self, source_text: &str) -> Result<std::string::String, std::vec::Vec<Error>> {
         let program = Parser::new(&self.allocator, source_text, self.source_type).parse().program;
         let semantic = SemanticBuilder::new(source_text, self.source_type).build(&program).semantic;
         let program = self.allocator.alloc(program);
         Transformer::new(&self.allocator, self.source_type, semantic, self.options.clone())
             .build(program)
-            .map(move |()| Codegen::<false>::new(source_text.len(), CodegenOptions).build(program))
+            .map(move |()| {
+                Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program)
+            })
     }
 
     fn codegen(&self, source_text: &str) -> String {
         let program = Parser::new(&self.allocator, source_text, self.source_type).parse().program;
-        Codegen::<false>::new(source_text.len(), CodegenOptions).build(&program)
+        Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(&program)
     }
 }
@@ -0,0 +1,534 @@
+#![allow(clippy::unused_self)]
+
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_semantic::{SymbolId, SymbolTable};
+use oxc_span::{Atom, GetSpan, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+use rustc_hash::FxHashSet;
+
+use super::TypeScript;
+
+/// Remove type annotations and TypeScript only syntax
+///
+/// References:
+/// * <https://babeljs.io/docs/babel-plugin-transform-typescript#caveats>
+/// * <https://esbuild.github.io/content-types/#typescript>
+impl<'a> TypeScript<'a> {
+    /// Unwrap `a as T`, `a satisfies T`, `<T>a`, `a!` and `a<T>`, and remove the type parameters
+    /// of arrow functions and calls.
+    pub fn transform_expression(&mut self, expr: &mut Expression<'a>) {
+        loop {
+            let inner = match expr {
+                Expression::TSAsExpression(e) => &mut e.expression,
+                Expression::TSSatisfiesExpression(e) => &mut e.expression,
+                Expression::TSTypeAssertion(e) => &mut e.expression,
+                Expression::TSNonNullExpression(e) => &mut e.expression,
+                Expression::TSInstantiationExpression(e) => &mut e.expression,
+                _ => break,
+            };
+            *expr = self.ast.move_expression(inner);
+        }
+
+        match expr {
+            Expression::ArrowExpression(arrow) => {
+                arrow.type_parameters = None;
+                arrow.return_type = None;
+            }
+            Expression::CallExpression(call) => call.type_parameters = None,
+            Expression::NewExpression(new) => new.type_parameters = None,
+            Expression::TaggedTemplateExpression(tagged) => tagged.type_parameters = None,
+            _ => {}
+        }
+    }
+
+    pub fn transform_function(&mut self, func: &mut Function<'a>) {
+        func.this_param = None;
+        func.type_parameters = None;
+        func.return_type = None;
+    }
+
+    pub fn transform_binding_pattern(&mut self, pat: &mut BindingPattern<'a>) {
+        pat.type_annotation = None;
+        pat.optional = false;
+    }
+
+    /// * Remove type parameters, `implements` and members without runtime semantics
+    /// * Lower parameter properties to assignments in the constructor
+    ///
+    /// ```TypeScript
+    /// class Foo {
+    ///   constructor(public x: number) {}
+    /// }
+    /// ```
+    /// ```JavaScript
+    /// class Foo {
+    ///   constructor(x) { this.x = x; }
+    /// }
+    /// ```
+    pub fn transform_class(&mut self, class: &mut Class<'a>) {
+        class.type_parameters = None;
+        class.super_type_parameters = None;
+        class.implements = None;
+        class.modifiers = Modifiers::empty();
+
+        class.body.body.retain(|element| match element {
+            ClassElement::TSIndexSignature(_)
+            | ClassElement::TSAbstractMethodDefinition(_)
+            | ClassElement::TSAbstractPropertyDefinition(_) => false,
+            ClassElement::PropertyDefinition(def) => !def.declare,
+            // Overload signatures
+            ClassElement::MethodDefinition(def) => def.value.body.is_some(),
+            ClassElement::StaticBlock(_) | ClassElement::AccessorProperty(_) => true,
+        });
+
+        for element in class.body.body.iter_mut() {
+            match element {
+                ClassElement::PropertyDefinition(def) => {
+                    def.type_annotation = None;
+                    def.accessibility = None;
+                    def.optional = false;
+                    def.definite = false;
+                    def.readonly = false;
+                    def.r#override = false;
+                }
+                ClassElement::MethodDefinition(def) => {
+                    def.accessibility = None;
+                    def.optional = false;
+                    def.r#override = false;
+                    if def.kind == MethodDefinitionKind::Constructor {
+                        self.transform_parameter_properties(&mut def.value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn transform_parameter_properties(&mut self, constructor: &mut Function<'a>) {
+        let mut names = vec![];
+        for param in constructor.params.items.iter_mut() {
+            if param.accessibility.is_none() && !param.readonly {
+                continue;
+            }
+            param.accessibility = None;
+            param.readonly = false;
+            let name = match &param.pattern.kind {
+                BindingPatternKind::BindingIdentifier(ident) => Some(&ident.name),
+                BindingPatternKind::AssignmentPattern(pat) => match &pat.left.kind {
+                    BindingPatternKind::BindingIdentifier(ident) => Some(&ident.name),
+                    _ => None,
+                },
+                _ => None,
+            };
+            names.extend(name.cloned());
+        }
+
+        let Some(body) = &mut constructor.body else { return };
+        if names.is_empty() {
+            return;
+        }
+
+        // Properties are assigned after `super()` in derived classes
+        let index = body
+            .statements
+            .iter()
+            .position(|stmt| match stmt {
+                Statement::ExpressionStatement(stmt) => matches!(
+                    &stmt.expression,
+                    Expression::CallExpression(call) if matches!(call.callee, Expression::Super(_))
+                ),
+                _ => false,
+            })
+            .map_or(0, |index| index + 1);
+        for (offset, name) in names.into_iter().enumerate() {
+            let object = self.ast.this_expression(SPAN);
+            let stmt = self.assign_member(object, name);
+            body.statements.insert(index + offset, stmt);
+        }
+    }
+
+    /// `object.name = name;`
+    pub(super) fn assign_member(&self, object: Expression<'a>, name: Atom) -> Statement<'a> {
+        let property = IdentifierName::new(SPAN, name.clone());
+        let member = self.ast.static_member(SPAN, object, property, false);
+        let left = AssignmentTarget::SimpleAssignmentTarget(
+            self.ast.simple_assignment_target_member_expression(member),
+        );
+        let right = self.ast.identifier_reference_expression(IdentifierReference::new(SPAN, name));
+        let expr = self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, left, right);
+        self.ast.expression_statement(SPAN, expr)
+    }
+
+    /// Remove interfaces, type aliases, `declare` statements, overload signatures and
+    /// `import type A = require()`.
+    pub fn remove_type_only_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        stmts.retain(|stmt| match stmt {
+            Statement::Declaration(decl) => !is_type_only_declaration(decl),
+            Statement::ModuleDeclaration(module_decl) => match &**module_decl {
+                ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                    decl.declaration.as_ref().map_or(true, |decl| !is_type_only_declaration(decl))
+                }
+                ModuleDeclaration::ExportDefaultDeclaration(decl) => !matches!(
+                    &decl.declaration,
+                    ExportDefaultDeclarationKind::TSInterfaceDeclaration(_)
+                ) && !matches!(
+                    &decl.declaration,
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func) if func.body.is_none()
+                ),
+                _ => true,
+            },
+            _ => true,
+        });
+    }
+
+    /// Assign the exported bindings of a namespace body to the namespace object. The exported
+    /// `let` and `var` bindings are the members of the namespace object, like tsc does, so that
+    /// their assignments update the exports.
+    ///
+    /// ```TypeScript
+    /// namespace Foo {
+    ///   export const x = 1;
+    ///   export let y = 2;
+    ///   y++;
+    /// }
+    /// ```
+    /// ```JavaScript
+    /// (function (_Foo) {
+    ///   const x = 1;
+    ///   _Foo.x = x;
+    ///   _Foo.y = 2;
+    ///   _Foo.y++;
+    /// })(Foo || (Foo = {}));
+    /// ```
+    pub(super) fn transform_namespace_exports(
+        &mut self,
+        arg_name: &Atom,
+        stmts: &mut Vec<'a, Statement<'a>>,
+    ) {
+        self.remove_type_only_statements(stmts);
+        let old_stmts = self.ast.move_statement_vec(stmts);
+        let mut member_symbols = FxHashSet::default();
+        for stmt in old_stmts {
+            let Statement::ModuleDeclaration(mut module_decl) = stmt else {
+                stmts.push(stmt);
+                continue;
+            };
+            let ModuleDeclaration::ExportNamedDeclaration(export_decl) = &mut *module_decl else {
+                stmts.push(Statement::ModuleDeclaration(module_decl));
+                continue;
+            };
+            let Some(mut decl) = export_decl.declaration.take() else { continue };
+            if let Declaration::VariableDeclaration(var_decl) = &mut decl {
+                if !var_decl.kind.is_const() {
+                    self.assign_namespace_members(arg_name, var_decl, stmts, &mut member_symbols);
+                    continue;
+                }
+            }
+            let mut names = vec![];
+            match &decl {
+                Declaration::VariableDeclaration(decl) => {
+                    for declarator in &decl.declarations {
+                        declarator.id.bound_names(&mut |ident| names.push(ident.name.clone()));
+                    }
+                }
+                Declaration::FunctionDeclaration(func) => {
+                    names.extend(func.id.as_ref().map(|id| id.name.clone()));
+                }
+                Declaration::ClassDeclaration(class) => {
+                    names.extend(class.id.as_ref().map(|id| id.name.clone()));
+                }
+                Declaration::TSEnumDeclaration(decl) => names.push(decl.id.name.clone()),
+                Declaration::TSModuleDeclaration(decl) => names.push(decl.id.name().clone()),
+                Declaration::TSImportEqualsDeclaration(decl) => names.push(decl.id.name.clone()),
+                Declaration::UsingDeclaration(_)
+                | Declaration::TSInterfaceDeclaration(_)
+                | Declaration::TSTypeAliasDeclaration(_) => {}
+            }
+            let span = decl.span();
+            stmts.push(Statement::Declaration(decl));
+            for name in names {
+                let object = self.ast.identifier_reference_expression(IdentifierReference::new(
+                    span,
+                    arg_name.clone(),
+                ));
+                stmts.push(self.assign_member(object, name));
+            }
+        }
+        if !member_symbols.is_empty() {
+            let symbols = self.ctx.symbols();
+            let mut rewriter = NamespaceMemberRewriter {
+                ast: &self.ast,
+                symbols: &symbols,
+                arg_name,
+                member_symbols: &member_symbols,
+            };
+            rewriter.visit_statements(stmts);
+        }
+    }
+
+    /// `export let x = 1, y, { z } = o;` -> `_N.x = 1; ({ z: _N.z } = o);`
+    fn assign_namespace_members(
+        &self,
+        arg_name: &Atom,
+        decl: &mut VariableDeclaration<'a>,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        member_symbols: &mut FxHashSet<SymbolId>,
+    ) {
+        let declarations = std::mem::replace(&mut decl.declarations, self.ast.new_vec());
+        for declarator in declarations {
+            declarator.id.bound_names(&mut |ident| member_symbols.extend(ident.symbol_id.get()));
+            let Some(init) = declarator.init else { continue };
+            let left = self.member_target(arg_name, declarator.id);
+            let expr = self.ast.assignment_expression(
+                declarator.span,
+                AssignmentOperator::Assign,
+                left,
+                init,
+            );
+            stmts.push(self.ast.expression_statement(declarator.span, expr));
+        }
+    }
+
+    /// The binding pattern as an assignment target with the members of the namespace object,
+    /// `{ z, w: [v] }` -> `{ z: _N.z, w: [_N.v] }`
+    fn member_target(&self, arg_name: &Atom, pattern: BindingPattern<'a>) -> AssignmentTarget<'a> {
+        match pattern.kind {
+            BindingPatternKind::BindingIdentifier(ident) => {
+                let object = self.ast.identifier_reference_expression(IdentifierReference::new(
+                    SPAN,
+                    arg_name.clone(),
+                ));
+                let property = IdentifierName::new(ident.span, ident.name.clone());
+                let member = self.ast.static_member(SPAN, object, property, false);
+                AssignmentTarget::SimpleAssignmentTarget(
+                    self.ast.simple_assignment_target_member_expression(member),
+                )
+            }
+            BindingPatternKind::ObjectPattern(pattern) => {
+                let pattern = pattern.unbox();
+                let mut properties = self.ast.new_vec_with_capacity(pattern.properties.len());
+                for property in pattern.properties {
+                    properties.push(AssignmentTargetProperty::AssignmentTargetPropertyProperty(
+                        self.ast.alloc(AssignmentTargetPropertyProperty {
+                            span: property.span,
+                            name: property.key,
+                            binding: self.member_target_maybe_default(arg_name, property.value),
+                        }),
+                    ));
+                }
+                let rest =
+                    pattern.rest.map(|rest| self.member_target(arg_name, rest.unbox().argument));
+                AssignmentTarget::AssignmentTargetPattern(
+                    AssignmentTargetPattern::ObjectAssignmentTarget(
+                        self.ast.alloc(ObjectAssignmentTarget {
+                            span: pattern.span,
+                            properties,
+                            rest,
+                        }),
+                    ),
+                )
+            }
+            BindingPatternKind::ArrayPattern(pattern) => {
+                let pattern = pattern.unbox();
+                let mut elements = self.ast.new_vec_with_capacity(pattern.elements.len());
+                for element in pattern.elements {
+                    elements.push(
+                        element.map(|element| self.member_target_maybe_default(arg_name, element)),
+                    );
+                }
+                let rest =
+                    pattern.rest.map(|rest| self.member_target(arg_name, rest.unbox().argument));
+                AssignmentTarget::AssignmentTargetPattern(
+                    AssignmentTargetPattern::ArrayAssignmentTarget(self.ast.alloc(
+                        ArrayAssignmentTarget {
+                            span: pattern.span,
+                            elements,
+                            rest,
+                            trailing_comma: None,
+                        },
+                    )),
+                )
+            }
+            BindingPatternKind::AssignmentPattern(_) => {
+                unreachable!("A default value is only allowed in an element or a property")
+            }
+        }
+    }
+
+    /// `z = 1` -> `_N.z = 1`
+    fn member_target_maybe_default(
+        &self,
+        arg_name: &Atom,
+        pattern: BindingPattern<'a>,
+    ) -> AssignmentTargetMaybeDefault<'a> {
+        if let BindingPatternKind::AssignmentPattern(pattern) = pattern.kind {
+            let pattern = pattern.unbox();
+            let binding = self.member_target(arg_name, pattern.left);
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(self.ast.alloc(
+                AssignmentTargetWithDefault { span: pattern.span, binding, init: pattern.right },
+            ))
+        } else {
+            AssignmentTargetMaybeDefault::AssignmentTarget(self.member_target(arg_name, pattern))
+        }
+    }
+}
+
+/// Replace the references of the exported `let` and `var` bindings of a namespace by the
+/// members of the namespace object
+struct NamespaceMemberRewriter<'a, 'b> {
+    ast: &'b AstBuilder<'a>,
+    symbols: &'b SymbolTable,
+    arg_name: &'b Atom,
+    member_symbols: &'b FxHashSet<SymbolId>,
+}
+
+impl<'a, 'b> NamespaceMemberRewriter<'a, 'b> {
+    /// `x` -> `_N.x`
+    fn member(&self, ident: &IdentifierReference) -> Option<MemberExpression<'a>> {
+        let symbol_id = self.symbols.get_reference(ident.reference_id.get()?).symbol_id()?;
+        if !self.member_symbols.contains(&symbol_id) {
+            return None;
+        }
+        let object = self
+            .ast
+            .identifier_reference_expression(IdentifierReference::new(SPAN, self.arg_name.clone()));
+        let property = IdentifierName::new(ident.span, ident.name.clone());
+        Some(self.ast.static_member(ident.span, object, property, false))
+    }
+}
+
+impl<'a, 'b> VisitMut<'a> for NamespaceMemberRewriter<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if let Expression::Identifier(ident) = expr {
+            if let Some(member) = self.member(ident) {
+                *expr = self.ast.member_expression(member);
+                return;
+            }
+        }
+        self.visit_expression_match(expr);
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &mut SimpleAssignmentTarget<'a>) {
+        match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                if let Some(member) = self.member(ident) {
+                    *target = self.ast.simple_assignment_target_member_expression(member);
+                }
+            }
+            SimpleAssignmentTarget::MemberAssignmentTarget(expr) => {
+                self.visit_member_expression(expr);
+            }
+            SimpleAssignmentTarget::TSAsExpression(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+            SimpleAssignmentTarget::TSSatisfiesExpression(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+            SimpleAssignmentTarget::TSNonNullExpression(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+            SimpleAssignmentTarget::TSTypeAssertion(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+        }
+    }
+
+    /// `({ x } = y)` -> `({ x: _N.x } = y)`
+    fn visit_assignment_target_property(&mut self, property: &mut AssignmentTargetProperty<'a>) {
+        if let AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) = property {
+            if let Some(member) = self.member(&ident.binding) {
+                let key = IdentifierName::new(ident.binding.span, ident.binding.name.clone());
+                let target = AssignmentTarget::SimpleAssignmentTarget(
+                    self.ast.simple_assignment_target_member_expression(member),
+                );
+                let binding = match ident.init.take() {
+                    Some(mut init) => {
+                        self.visit_expression(&mut init);
+                        AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(self.ast.alloc(
+                            AssignmentTargetWithDefault { span: ident.span, binding: target, init },
+                        ))
+                    }
+                    None => AssignmentTargetMaybeDefault::AssignmentTarget(target),
+                };
+                *property = AssignmentTargetProperty::AssignmentTargetPropertyProperty(
+                    self.ast.alloc(AssignmentTargetPropertyProperty {
+                        span: ident.span,
+                        name: self.ast.property_key_identifier(key),
+                        binding,
+                    }),
+                );
+                return;
+            }
+        }
+        match property {
+            AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) => {
+                self.visit_assignment_target_property_identifier(ident);
+            }
+            AssignmentTargetProperty::AssignmentTargetPropertyProperty(prop) => {
+                self.visit_assignment_target_property_property(prop);
+            }
+        }
+    }
+
+    /// `{ x }` -> `{ x: _N.x }`
+    fn visit_object_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        if prop.shorthand {
+            if let Expression::Identifier(ident) = &prop.value {
+                prop.shorthand = self.member(ident).is_none();
+            }
+        }
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        if let Some(init) = &mut prop.init {
+            self.visit_expression(init);
+        }
+    }
+}
+
+/// Whether the declaration has no runtime semantics
+pub(super) fn is_type_only_declaration(decl: &Declaration) -> bool {
+    match decl {
+        Declaration::TSInterfaceDeclaration(_) | Declaration::TSTypeAliasDeclaration(_) => true,
+        Declaration::TSImportEqualsDeclaration(decl) => decl.import_kind.is_type(),
+        Declaration::FunctionDeclaration(func) => func.body.is_none(),
+        _ => decl.modifiers().is_some_and(Modifiers::is_contains_declare),
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{options::TransformOptions, tester::Tester};
+
+    let tests = &[
+        ("let a: number = 1 as any; a!; <T>a; a satisfies T;", "let a = 1; a; a; a;"),
+        ("interface A {} type B = A; declare const c: B; declare function d(): void;", ""),
+        ("function f(a: string): void; function f(a?: any) {} f<A>();", "function f(a) {} f();"),
+        (
+            "class A<T> extends B<T> implements C { declare a: T; b!: number; [k: string]: any; constructor(private x: T, readonly y = 1) { super(); } }",
+            "class A extends B { b; constructor(x, y = 1) { super(); this.x = x; this.y = y; } }",
+        ),
+        (
+            "namespace N { export const a = 1; export function b() {} export interface C {} }",
+            "let N; (function(_N) { const a = 1; _N.a = a; function b() {} _N.b = b; })(N || (N = {}));",
+        ),
+        (
+            "namespace N { export let x = 1, y; export function inc() { x++; y = x; ({ x } = { x: 2 }); return { x }; } }",
+            "let N; (function(_N) { _N.x = 1; function inc() { _N.x++; _N.y = _N.x; ({ x: _N.x } = { x: 2 }); return { x: _N.x }; } _N.inc = inc; })(N || (N = {}));",
+        ),
+        (
+            "namespace N { export var x = 1; function f(x) { return x; } }",
+            "let N; (function(_N) { _N.x = 1; function f(x) { return x; } })(N || (N = {}));",
+        ),
+        (
+            "namespace N { export var { z, w: [v = 1, ...r] } = o; z = 3; }",
+            "let N; (function(_N) { ({ z: _N.z, w: [_N.v = 1, ..._N.r] } = o); _N.z = 3; })(N || (N = {}));",
+        ),
+        (
+            "namespace A.B { export const x = 1; }",
+            "let A; (function(_A) { let B; (function(_B) { const x = 1; _B.x = x; })(B = _A.B || (_A.B = {})); })(A || (A = {}));",
+        ),
+    ];
+
+    Tester::new("test.ts", TransformOptions::default()).test(tests);
+}
@@ -9,6 +9,7 @@ use oxc_syntax::{
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::{mem, rc::Rc};
 
+mod annotations;
 mod options;
 
 pub use self::options::TypescriptOptions;
@@ -63,6 +64,7 @@ impl<'a> TypeScript<'a> {
     }
 
     pub fn transform_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        self.remove_type_only_statements(stmts);
         self.insert_let_decl_for_ts_module_block(stmts);
     }
 
@@ -77,7 +79,7 @@ impl<'a> TypeScript<'a> {
                             } else {
                                 match decl {
                                     Declaration::TSModuleDeclaration(ts_module_decl) => {
-                                        Some(self.transform_ts_module_block(ts_module_decl))
+                                        Some(self.transform_ts_module_block(ts_module_decl, None))
                                     }
                                     _ => None,
                                 }
@@ -92,7 +94,7 @@ impl<'a> TypeScript<'a> {
                 if ts_module_decl.modifiers.is_contains_declare() {
                     None
                 } else {
-                    Some(self.transform_ts_module_block(ts_module_decl))
+                    Some(self.transform_ts_module_block(ts_module_decl, None))
                 }
             }
             _ => None,
@@ -641,18 +643,7 @@ impl<'a> TypeScript<'a> {
         }
 
         for (index, name, is_export) in insert_var_decl.into_iter().rev() {
-            let kind = VariableDeclarationKind::Let;
-            let decls = {
-                let binding_identifier = BindingIdentifier::new(SPAN, name.clone());
-                let binding_pattern_kind = self.ast.binding_pattern_identifier(binding_identifier);
-                let binding = self.ast.binding_pattern(binding_pattern_kind, None, false);
-                let decl = self.ast.variable_declarator(SPAN, kind, binding, None, false);
-                self.ast.new_vec_single(decl)
-            };
-            let variable_declaration =
-                self.ast.variable_declaration(SPAN, kind, decls, Modifiers::empty());
-
-            let decl = Declaration::VariableDeclaration(variable_declaration);
+            let decl = self.let_declaration(name);
 
             let stmt = if is_export {
                 self.ast.module_declaration(ModuleDeclaration::ExportNamedDeclaration(
@@ -671,6 +662,34 @@ impl<'a> TypeScript<'a> {
         }
     }
 
+    /// `let name;`
+    fn let_declaration(&self, name: Atom) -> Declaration<'a> {
+        let kind = VariableDeclarationKind::Let;
+        let decls = {
+            let binding_identifier = BindingIdentifier::new(SPAN, name);
+            let binding_pattern_kind = self.ast.binding_pattern_identifier(binding_identifier);
+            let binding = self.ast.binding_pattern(binding_pattern_kind, None, false);
+            let decl = self.ast.variable_declarator(SPAN, kind, binding, None, false);
+            self.ast.new_vec_single(decl)
+        };
+        Declaration::VariableDeclaration(self.ast.variable_declaration(
+            SPAN,
+            kind,
+            decls,
+            Modifiers::empty(),
+        ))
+    }
+
+    /// `_Parent.name`
+    fn namespace_member(&self, parent_arg_name: &Atom, name: &Atom) -> MemberExpression<'a> {
+        let object = self.ast.identifier_reference_expression(IdentifierReference::new(
+            SPAN,
+            parent_arg_name.clone(),
+        ));
+        let property = IdentifierName::new(SPAN, name.clone());
+        self.ast.static_member(SPAN, object, property, false)
+    }
+
     fn get_namespace_arg_name(&mut self, name: &Atom) -> Atom {
         let count = self.namespace_arg_names.entry(name.clone()).or_insert(0);
         *count += 1;
@@ -686,25 +705,40 @@ impl<'a> TypeScript<'a> {
     /// (function (_Foo) {
     /// })(Foo || (Foo = {}));
     /// ```
+    ///
+    /// The inner namespaces of `namespace Foo.Bar {}` are members of their outer namespace,
+    /// `parent_arg_name` is the parameter of the outer namespace:
+    /// ```JavaScript
+    /// let Bar;
+    /// (function (_Bar) {
+    /// })(Bar = _Foo.Bar || (_Foo.Bar = {}));
+    /// ```
     fn transform_ts_module_block(
         &mut self,
         block: &mut Box<'a, TSModuleDeclaration<'a>>,
+        parent_arg_name: Option<&Atom>,
     ) -> Statement<'a> {
+        let name = block.id.name().clone();
+        let arg_name = self.get_namespace_arg_name(&name);
+
         let body_statements = match &mut block.body {
             TSModuleDeclarationBody::TSModuleDeclaration(decl) => {
-                let transformed_module_block = self.transform_ts_module_block(decl);
-                self.ast.new_vec_single(transformed_module_block)
+                let let_decl = Statement::Declaration(self.let_declaration(decl.id.name().clone()));
+                let transformed_module_block =
+                    self.transform_ts_module_block(decl, Some(&arg_name));
+                let mut stmts = self.ast.new_vec_with_capacity(2);
+                stmts.push(let_decl);
+                stmts.push(transformed_module_block);
+                stmts
             }
             TSModuleDeclarationBody::TSModuleBlock(ts_module_block) => {
+                self.transform_namespace_exports(&arg_name, &mut ts_module_block.body);
                 self.ast.move_statement_vec(&mut ts_module_block.body)
             }
         };
 
-        let name = block.id.name();
-
         let callee = {
             let body = self.ast.function_body(SPAN, self.ast.new_vec(), body_statements);
-            let arg_name = self.get_namespace_arg_name(name);
             let params = self.ast.formal_parameters(
                 SPAN,
                 FormalParameterKind::FormalParameter,
@@ -740,30 +774,43 @@ impl<'a> TypeScript<'a> {
         };
 
         let arguments = {
+            // `Foo`, or `_Parent.Foo` for an inner namespace
+            let object = || match parent_arg_name {
+                Some(parent_arg_name) => {
+                    self.ast.member_expression(self.namespace_member(parent_arg_name, &name))
+                }
+                None => self
+                    .ast
+                    .identifier_reference_expression(IdentifierReference::new(SPAN, name.clone())),
+            };
             let right = {
-                let left = AssignmentTarget::SimpleAssignmentTarget(
-                    self.ast.simple_assignment_target_identifier(IdentifierReference::new(
+                let left = AssignmentTarget::SimpleAssignmentTarget(match parent_arg_name {
+                    Some(parent_arg_name) => self.ast.simple_assignment_target_member_expression(
+                        self.namespace_member(parent_arg_name, &name),
+                    ),
+                    None => self.ast.simple_assignment_target_identifier(IdentifierReference::new(
                         SPAN,
                         name.clone(),
                     )),
-                );
+                });
                 let right = self.ast.object_expression(SPAN, self.ast.new_vec(), None);
                 self.ast.parenthesized_expression(
                     SPAN,
                     self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, left, right),
                 )
             };
-            self.ast.new_vec_single(Argument::Expression(
-                self.ast.logical_expression(
-                    SPAN,
-                    self.ast.identifier_reference_expression(IdentifierReference::new(
+            let mut expr = self.ast.logical_expression(SPAN, object(), LogicalOperator::Or, right);
+            if parent_arg_name.is_some() {
+                // `Bar = _Foo.Bar || (_Foo.Bar = {})`
+                let left = AssignmentTarget::SimpleAssignmentTarget(
+                    self.ast.simple_assignment_target_identifier(IdentifierReference::new(
                         SPAN,
                         name.clone(),
                     )),
-                    LogicalOperator::Or,
-                    right,
-                ),
-            ))
+                );
+                expr = self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, left, expr);
+            }
+            self.ast.new_vec_single(Argument::Expression(expr))
         };
         let expr = self.ast.call_expression(SPAN, callee, arguments, false, None);
         self.ast.expression_statement(SPAN, expr)
@@ -1,8 +1,7 @@
-use std::mem;
-
 use oxc_allocator::Vec;
-use oxc_ast::ast::*;
-use oxc_span::{Atom, Span};
+use oxc_ast::{ast::*, AstBuilder, Visit, VisitMut};
+use oxc_semantic::ScopeFlags;
+use oxc_span::{Atom, Span, SPAN};
 use oxc_syntax::unicode_id_start::{is_id_continue, is_id_start};
 
 use crate::context::TransformerCtx;
@@ -10,14 +9,21 @@ use crate::context::TransformerCtx;
 pub trait CreateVars<'a> {
     fn ctx(&self) -> &TransformerCtx<'a>;
 
-    fn vars_mut(&mut self) -> &mut Vec<'a, VariableDeclarator<'a>>;
+    /// The variables to declare in each statement list being visited, innermost last
+    fn vars_mut(&mut self) -> &mut std::vec::Vec<Vec<'a, VariableDeclarator<'a>>>;
+
+    /// Start collecting the variables created inside a statement list, which are declared by
+    /// [`CreateVars::add_vars_to_statements`] when leaving the list.
+    fn enter_statements(&mut self) {
+        let vars = self.ctx().ast.new_vec();
+        self.vars_mut().push(vars);
+    }
 
     fn add_vars_to_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
-        if self.vars_mut().is_empty() {
+        let Some(decls) = self.vars_mut().pop() else { return };
+        if decls.is_empty() {
             return;
         }
-        let new_vec = self.ctx().ast.new_vec();
-        let decls = mem::replace(self.vars_mut(), new_vec);
         let kind = VariableDeclarationKind::Var;
         let decl =
             self.ctx().ast.variable_declaration(Span::default(), kind, decls, Modifiers::empty());
@@ -28,15 +34,21 @@ pub trait CreateVars<'a> {
     fn create_new_var(&mut self, expr: &Expression<'a>) -> IdentifierReference {
         let name = self.ctx().scopes().generate_uid_based_on_node(expr);
         self.ctx().add_binding(name.clone());
+        self.declare_var(name)
+    }
 
-        // Add `var name` to scope
+    /// Add `var name` to the current statement list
+    fn declare_var(&mut self, name: Atom) -> IdentifierReference {
         // TODO: hookup symbol id
         let binding_identifier = BindingIdentifier::new(Span::default(), name.clone());
         let binding_pattern_kind = self.ctx().ast.binding_pattern_identifier(binding_identifier);
         let binding = self.ctx().ast.binding_pattern(binding_pattern_kind, None, false);
         let kind = VariableDeclarationKind::Var;
         let decl = self.ctx().ast.variable_declarator(Span::default(), kind, binding, None, false);
-        self.vars_mut().push(decl);
+        self.vars_mut()
+            .last_mut()
+            .expect("variables are created inside statement lists")
+            .push(decl);
         // TODO: add reference id and flag
         IdentifierReference::new(Span::default(), name)
     }
@@ -168,3 +180,174 @@ pub fn is_reserved_word(name: &Atom, in_module: bool) -> bool {
 pub fn is_valid_es3_identifier(name: &Atom) -> bool {
     is_valid_identifier(name, true) && !RESERVED_WORDS_ES3_ONLY.contains(name.as_str())
 }
+
+/// The class declared by `stmt`, possibly exported
+pub fn statement_class<'b, 'a>(stmt: &'b Statement<'a>) -> Option<&'b Class<'a>> {
+    match stmt {
+        Statement::Declaration(Declaration::ClassDeclaration(class)) => Some(class),
+        Statement::ModuleDeclaration(decl) => match &**decl {
+            ModuleDeclaration::ExportNamedDeclaration(decl) => match &decl.declaration {
+                Some(Declaration::ClassDeclaration(class)) => Some(class),
+                _ => None,
+            },
+            ModuleDeclaration::ExportDefaultDeclaration(decl) => match &decl.declaration {
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => Some(class),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Finds `super` outside of nested functions and classes, for the code which is moved out of
+/// its method
+pub struct SuperFinder {
+    pub span: Option<Span>,
+}
+
+impl<'a> Visit<'a> for SuperFinder {
+    fn visit_super(&mut self, expr: &Super) {
+        self.span.get_or_insert(expr.span);
+    }
+
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    fn visit_class(&mut self, _class: &Class<'a>) {}
+}
+
+pub fn is_super_call(expr: &Expression) -> bool {
+    matches!(expr, Expression::CallExpression(call) if matches!(call.callee, Expression::Super(_)))
+}
+
+/// Run `inits` at the start of the constructor of `class`, or after `super()` in derived classes.
+///
+/// A default constructor is added to classes without one.
+pub fn insert_constructor_inits<'a>(
+    ctx: &TransformerCtx<'a>,
+    class: &mut Class<'a>,
+    inits: std::vec::Vec<Expression<'a>>,
+) {
+    let ast = &ctx.ast;
+    let is_derived = class.super_class.is_some();
+    let is_constructor = |element: &ClassElement| matches!(element, ClassElement::MethodDefinition(def) if def.kind == MethodDefinitionKind::Constructor);
+    if !class.body.body.iter().any(is_constructor) {
+        let constructor = default_constructor(ctx, is_derived);
+        class.body.body.insert(0, constructor);
+    }
+    let Some(body) = class.body.body.iter_mut().find_map(|element| match element {
+        ClassElement::MethodDefinition(def) if def.kind == MethodDefinitionKind::Constructor => {
+            def.value.body.as_mut()
+        }
+        _ => None,
+    }) else {
+        return;
+    };
+
+    if !is_derived {
+        for (index, init) in inits.into_iter().enumerate() {
+            body.statements.insert(index, ast.expression_statement(SPAN, init));
+        }
+        return;
+    }
+
+    let super_call = body.statements.iter().position(|stmt| match stmt {
+        Statement::ExpressionStatement(stmt) => is_super_call(&stmt.expression),
+        _ => false,
+    });
+    if let Some(index) = super_call {
+        for (offset, init) in inits.into_iter().enumerate() {
+            body.statements.insert(index + 1 + offset, ast.expression_statement(SPAN, init));
+        }
+    } else {
+        // `super()` is nested in another statement, `super(); init` -> `(super(), init, this)`
+        SuperCallReplacer { ast, inits }.visit_function_body(body);
+    }
+}
+
+/// * `constructor() {}`
+/// * `constructor(..._args) { super(..._args); }` in derived classes
+fn default_constructor<'a>(ctx: &TransformerCtx<'a>, is_derived: bool) -> ClassElement<'a> {
+    let ast = &ctx.ast;
+    let mut rest = None;
+    let mut statements = ast.new_vec();
+    if is_derived {
+        let name = ctx.scopes().generate_uid("args");
+        ctx.add_binding(name.clone());
+        let binding = ast.binding_pattern(
+            ast.binding_pattern_identifier(BindingIdentifier::new(SPAN, name.clone())),
+            None,
+            false,
+        );
+        rest = Some(ast.rest_element(SPAN, binding));
+        let argument = ast.identifier_reference_expression(IdentifierReference::new(SPAN, name));
+        let spread = ast.spread_element(SPAN, argument);
+        let call = ast.call_expression(
+            SPAN,
+            ast.super_(SPAN),
+            ast.new_vec_single(Argument::SpreadElement(spread)),
+            false,
+            None,
+        );
+        statements.push(ast.expression_statement(SPAN, call));
+    }
+    let params = ast.formal_parameters(
+        SPAN,
+        FormalParameterKind::UniqueFormalParameters,
+        ast.new_vec(),
+        rest,
+    );
+    let body = ast.function_body(SPAN, ast.new_vec(), statements);
+    let value = ast.function(
+        FunctionType::FunctionExpression,
+        SPAN,
+        None,
+        false,
+        false,
+        false,
+        None,
+        params,
+        Some(body),
+        None,
+        None,
+        Modifiers::empty(),
+    );
+    let key = ast.property_key_identifier(IdentifierName::new(SPAN, "constructor".into()));
+    ClassElement::MethodDefinition(ast.alloc(MethodDefinition {
+        span: SPAN,
+        key,
+        value,
+        kind: MethodDefinitionKind::Constructor,
+        computed: false,
+        r#static: false,
+        r#override: false,
+        optional: false,
+        accessibility: None,
+        decorators: ast.new_vec(),
+    }))
+}
+
+/// `super(...)` -> `(super(...), ...inits, this)`
+struct SuperCallReplacer<'a, 'b> {
+    ast: &'b AstBuilder<'a>,
+    inits: std::vec::Vec<Expression<'a>>,
+}
+
+impl<'a, 'b> VisitMut<'a> for SuperCallReplacer<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        if !is_super_call(expr) {
+            return;
+        }
+        let mut exprs = self.ast.new_vec_with_capacity(self.inits.len() + 2);
+        exprs.push(self.ast.move_expression(expr));
+        exprs.extend(self.inits.iter().map(|init| self.ast.copy(init)));
+        exprs.push(self.ast.this_expression(SPAN));
+        let sequence = self.ast.sequence_expression(SPAN, exprs);
+        *expr = self.ast.parenthesized_expression(SPAN, sequence);
+    }
+
+    fn visit_function(&mut self, _func: &mut Function<'a>, _flags: Option<ScopeFlags>) {}
+
+    fn visit_class(&mut self, _class: &mut Class<'a>) {}
+}
@@ -0,0 +1,202 @@
+//! Stateless functions for in-browser tooling, which take plain JS objects as options and return
+//! plain JS objects. The [crate::Oxc] class keeps the state of the playground instead.
+
+use std::{path::PathBuf, rc::Rc};
+
+use oxc::{
+    allocator::Allocator,
+    ast::EstreeComment,
+    diagnostics::SerializedDiagnostic,
+    minifier::{CompressOptions, ManglerOptions, MinifierOptions},
+    parser::{Parser, ParserReturn},
+    semantic::SemanticBuilder,
+    span::SourceType,
+    Compiler,
+};
+use oxc_linter::{AllowWarnDeny, LintContext, LintOptions, Linter};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ParseOptions {
+    /// `script` or `module`, overrides the source type of the file name
+    source_type: Option<String>,
+    /// The JS, JSX, TS and TSX syntax is chosen by the extension
+    source_filename: Option<String>,
+    allow_return_outside_function: bool,
+}
+
+impl ParseOptions {
+    fn path(&self) -> PathBuf {
+        PathBuf::from(self.source_filename.as_deref().unwrap_or("file.js"))
+    }
+
+    fn source_type(&self) -> SourceType {
+        let source_type = SourceType::from_path(self.path()).unwrap_or_default();
+        match self.source_type.as_deref() {
+            Some("script") => source_type.with_script(true),
+            Some("module") => source_type.with_module(true),
+            _ => source_type,
+        }
+    }
+
+    fn parse<'a>(&self, allocator: &'a Allocator, source_text: &'a str) -> ParserReturn<'a> {
+        Parser::new(allocator, source_text, self.source_type())
+            .allow_return_outside_function(self.allow_return_outside_function)
+            .parse()
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct LintFunctionOptions {
+    #[serde(flatten)]
+    parse: ParseOptions,
+    /// The rules or categories allowed, warned or denied in order, as in
+    /// `[["deny", "correctness"], ["allow", "no-debugger"]]`
+    filter: Vec<(String, String)>,
+    import_plugin: bool,
+    jest_plugin: bool,
+    jsx_a11y_plugin: bool,
+    nextjs_plugin: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct MinifyFunctionOptions {
+    #[serde(flatten)]
+    parse: ParseOptions,
+    compress: bool,
+    mangle: bool,
+    /// Remove the whitespace of the output
+    whitespace: bool,
+}
+
+impl Default for MinifyFunctionOptions {
+    fn default() -> Self {
+        Self { parse: ParseOptions::default(), compress: true, mangle: true, whitespace: true }
+    }
+}
+
+#[derive(Serialize)]
+struct ParseResult {
+    /// The ESTree `Program`
+    program: serde_json::Value,
+    comments: Vec<EstreeComment>,
+    errors: Vec<SerializedDiagnostic>,
+}
+
+#[derive(Serialize)]
+struct LintResult {
+    /// The syntax errors and the diagnostics of the rules
+    diagnostics: Vec<SerializedDiagnostic>,
+}
+
+#[derive(Serialize)]
+struct MinifyResult {
+    code: String,
+    errors: Vec<SerializedDiagnostic>,
+}
+
+/// Parses `sourceText` into `{ program, comments, errors }`, the program is an ESTree AST
+///
+/// # Errors
+///
+/// * The options are not an object of the parse options
+#[wasm_bindgen]
+pub fn parse(source_text: &str, options: JsValue) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    let options: ParseOptions = from_options(options)?;
+    let allocator = Allocator::default();
+    let ret = options.parse(&allocator, source_text);
+    let result = ParseResult {
+        program: ret.program.to_estree(source_text),
+        comments: ret.trivias.to_estree_comments(source_text),
+        errors: SerializedDiagnostic::from_errors(&ret.errors),
+    };
+    to_value(&result)
+}
+
+/// Lints `sourceText` into `{ diagnostics }`, the correctness rules are denied by default
+///
+/// # Errors
+///
+/// * The options are not an object of the lint options
+#[wasm_bindgen]
+pub fn lint(source_text: &str, options: JsValue) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    let options: LintFunctionOptions = from_options(options)?;
+    let mut filter = vec![];
+    for (allow_warn_deny, name_or_category) in options.filter {
+        let allow_warn_deny = AllowWarnDeny::try_from(allow_warn_deny.as_str())
+            .map_err(|error| serde_wasm_bindgen::Error::new(error.to_string()))?;
+        filter.push((allow_warn_deny, name_or_category));
+    }
+    let lint_options = LintOptions::default()
+        .with_filter(filter)
+        .with_import_plugin(options.import_plugin)
+        .with_jest_plugin(options.jest_plugin)
+        .with_jsx_a11y_plugin(options.jsx_a11y_plugin)
+        .with_nextjs_plugin(options.nextjs_plugin);
+    let linter = Linter::from_options(lint_options)
+        .map_err(|error| serde_wasm_bindgen::Error::new(error.to_string()))?;
+
+    let allocator = Allocator::default();
+    let ret = options.parse.parse(&allocator, source_text);
+    let mut diagnostics = SerializedDiagnostic::from_errors(&ret.errors);
+    if ret.errors.is_empty() {
+        let program = allocator.alloc(ret.program);
+        let semantic_ret = SemanticBuilder::new(source_text, options.parse.source_type())
+            .with_trivias(ret.trivias)
+            .with_check_syntax_error(true)
+            .build(program);
+        diagnostics.extend(SerializedDiagnostic::from_errors(&semantic_ret.errors));
+        let semantic = Rc::new(semantic_ret.semantic);
+        let lint_ctx = LintContext::new(options.parse.path().into_boxed_path(), &semantic);
+        let errors = linter.run(lint_ctx).into_iter().map(|message| message.error);
+        diagnostics.extend(SerializedDiagnostic::from_errors(&errors.collect::<Vec<_>>()));
+    }
+    to_value(&LintResult { diagnostics })
+}
+
+/// Minifies `sourceText` into `{ code, errors }`, the code is empty when there are syntax
+/// errors, including the ones found by the semantic analysis
+///
+/// # Errors
+///
+/// * The options are not an object of the minify options
+#[wasm_bindgen]
+pub fn minify(source_text: &str, options: JsValue) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    let options: MinifyFunctionOptions = from_options(options)?;
+    let minifier_options = MinifierOptions {
+        mangle: options.mangle.then(ManglerOptions::default),
+        compress: if options.compress {
+            CompressOptions::all_true()
+        } else {
+            CompressOptions::all_false()
+        },
+    };
+    let allocator = Allocator::default();
+    let ret = Compiler::new(options.parse.source_type())
+        .with_allow_return_outside_function(options.parse.allow_return_outside_function)
+        .with_minify(minifier_options)
+        .with_remove_whitespace(options.whitespace)
+        .compile(&allocator, source_text);
+    to_value(&MinifyResult {
+        code: ret.code,
+        errors: SerializedDiagnostic::from_errors(&ret.errors),
+    })
+}
+
+/// `undefined` and `null` are the default options
+fn from_options<T: Default + for<'de> Deserialize<'de>>(
+    options: JsValue,
+) -> Result<T, serde_wasm_bindgen::Error> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(T::default());
+    }
+    serde_wasm_bindgen::from_value(options)
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+}
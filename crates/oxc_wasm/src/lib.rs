@@ -1,12 +1,13 @@
+mod api;
 mod options;
 
 use std::{cell::RefCell, path::PathBuf, rc::Rc};
 
 use oxc::{
     allocator::Allocator,
-    codegen::{Codegen, CodegenOptions},
-    diagnostics::Error,
-    minifier::{CompressOptions, Minifier, MinifierOptions},
+    codegen::{Charset, Codegen, CodegenOptions},
+    diagnostics::{Error, SerializedDiagnostic},
+    minifier::{CompressOptions, ManglerOptions, Minifier, MinifierOptions},
     parser::Parser,
     semantic::{ScopeId, Semantic, SemanticBuilder},
     span::SourceType,
@@ -47,14 +48,6 @@ pub struct Oxc {
     serializer: serde_wasm_bindgen::Serializer,
 }
 
-#[derive(Default, Clone, Serialize)]
-pub struct OxcDiagnostic {
-    pub start: usize,
-    pub end: usize,
-    pub severity: String,
-    pub message: String,
-}
-
 #[wasm_bindgen]
 impl Oxc {
     #[wasm_bindgen(constructor)]
@@ -114,31 +107,15 @@ impl Oxc {
         self.symbols.clone()
     }
 
-    /// Returns Array of String
+    /// Returns Array of `{ start, end, severity, message, help }`
     /// # Errors
-    /// # Panics
+    /// Serde serialization error
     #[wasm_bindgen(js_name = getDiagnostics)]
     pub fn get_diagnostics(&self) -> Result<Vec<JsValue>, serde_wasm_bindgen::Error> {
-        Ok(self
-            .diagnostics
-            .borrow()
+        SerializedDiagnostic::from_errors(&self.diagnostics.borrow())
             .iter()
-            .flat_map(|error| {
-                let Some(labels) = error.labels() else { return vec![] };
-                labels
-                    .map(|label| {
-                        OxcDiagnostic {
-                            start: label.offset(),
-                            end: label.offset() + label.len(),
-                            severity: format!("{:?}", error.severity().unwrap_or_default()),
-                            message: format!("{error}"),
-                        }
-                        .serialize(&self.serializer)
-                        .unwrap()
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>())
+            .map(|diagnostic| diagnostic.serialize(&self.serializer))
+            .collect()
     }
 
     /// # Errors
@@ -244,7 +221,7 @@ impl Oxc {
 
         if minifier_options.compress() || minifier_options.mangle() {
             let options = MinifierOptions {
-                mangle: minifier_options.mangle(),
+                mangle: minifier_options.mangle().then(ManglerOptions::default),
                 compress: if minifier_options.compress() {
                     CompressOptions::all_true()
                 } else {
@@ -255,9 +232,14 @@ impl Oxc {
         }
 
         self.codegen_text = if minifier_options.whitespace() {
-            Codegen::<true>::new(source_text.len(), CodegenOptions).build(program)
+            let options = CodegenOptions {
+                charset: Charset::Ascii,
+                escape_script_end_tags: true,
+                ..CodegenOptions::default()
+            };
+            Codegen::<true>::new(source_text.len(), options).build(program)
         } else {
-            Codegen::<false>::new(source_text.len(), CodegenOptions).build(program)
+            Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program)
         };
 
         Ok(())
@@ -0,0 +1,87 @@
+/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */
+
+/**
+ * Terser Minify Options
+ *
+ * <https://terser.org/docs/api-reference/#minify-options>
+ */
+export interface MinifyOptions {
+  /** Defaults to `true` */
+  compress?: boolean | CompressOptions | undefined
+  /** Defaults to `true` */
+  mangle?: boolean | MangleOptions | undefined
+  format?: FormatOptions
+  /** Return the source map in `map`, defaults to `false` */
+  sourceMap?: boolean | SourceMapOptions | undefined
+  /** Parse the code as an ES module, which implies `toplevel`. Defaults to `false`. */
+  module?: boolean
+  /** Mangle the names of the top level scope. Defaults to `false`. */
+  toplevel?: boolean
+  /** The name of the file in the errors and in the `sources` of the source map */
+  sourcefile?: string
+}
+/** The compress options, all of them default to the ones of the oxc minifier */
+export interface CompressOptions {
+  booleans?: boolean
+  dead_code?: boolean
+  drop_console?: boolean
+  drop_debugger?: boolean
+  evaluate?: boolean
+  if_return?: boolean
+  inline?: boolean
+  join_vars?: boolean
+  loops?: boolean
+  passes?: number
+  pure_funcs?: Array<string>
+  sequences?: boolean
+  typeofs?: boolean
+  unsafe_math?: boolean
+  unused?: boolean
+}
+export interface MangleOptions {
+  keep_classnames?: boolean
+  keep_fnames?: boolean
+  reserved?: Array<string>
+  toplevel?: boolean
+}
+export interface FormatOptions {
+  /** Print the code with whitespace. Defaults to `false`. */
+  beautify?: boolean
+  /**
+   * `some` keeps the license comments, `true` or `all` keeps all the comments and `false`
+   * none of them. Defaults to `some`.
+   */
+  comments?: boolean | 'some' | 'all' | undefined
+  /** Escape the non-ASCII characters. Defaults to `false`. */
+  ascii_only?: boolean
+  /** `1` prints single quotes, the others print double quotes. Defaults to `0`. */
+  quote_style?: number
+  /** `false` omits the semicolons which are not needed. Defaults to `true`. */
+  semicolons?: boolean
+}
+export interface SourceMapOptions {
+  /** The source map of the input code as JSON, the returned source map maps to its sources */
+  content?: string
+  /**
+   * `inline` appends the source map to the code as a data URL, other URLs are appended as
+   * they are
+   */
+  url?: string
+}
+export interface MinifyResult {
+  code: string
+  /** The source map as JSON, with `sourceMap` */
+  map?: string
+  errors: Array<string>
+}
+/**
+ * Compresses and mangles the code
+ *
+ * # Errors
+ *
+ * * The options are invalid, or the input source map is not valid JSON
+ */
+export function minify(sourceText: string, options?: MinifyOptions | undefined | null): MinifyResult
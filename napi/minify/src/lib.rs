@@ -0,0 +1,280 @@
+#![allow(clippy::trailing_empty_array)]
+
+use std::sync::Arc;
+
+use miette::NamedSource;
+use napi::{bindgen_prelude::Either, Error, Status};
+use napi_derive::napi;
+use oxc::{
+    allocator::Allocator,
+    codegen::{Charset, CodegenOptions, CommentOptions, QuoteStyle, SemicolonStyle},
+    minifier::{self, ManglerOptions, MinifierOptions},
+    sourcemap::SourceMap,
+    span::SourceType,
+    Compiler,
+};
+
+/// Terser Minify Options
+///
+/// <https://terser.org/docs/api-reference/#minify-options>
+#[napi(object)]
+#[derive(Default)]
+pub struct MinifyOptions {
+    /// Defaults to `true`
+    #[napi(ts_type = "boolean | CompressOptions | undefined")]
+    pub compress: Option<Either<bool, CompressOptions>>,
+    /// Defaults to `true`
+    #[napi(ts_type = "boolean | MangleOptions | undefined")]
+    pub mangle: Option<Either<bool, MangleOptions>>,
+    pub format: Option<FormatOptions>,
+    /// Return the source map in `map`, defaults to `false`
+    #[napi(js_name = "sourceMap", ts_type = "boolean | SourceMapOptions | undefined")]
+    pub source_map: Option<Either<bool, SourceMapOptions>>,
+    /// Parse the code as an ES module, which implies `toplevel`. Defaults to `false`.
+    pub module: Option<bool>,
+    /// Mangle the names of the top level scope. Defaults to `false`.
+    pub toplevel: Option<bool>,
+    /// The name of the file in the errors and in the `sources` of the source map
+    pub sourcefile: Option<String>,
+}
+
+/// The compress options, all of them default to the ones of the oxc minifier
+#[napi(object)]
+#[derive(Default)]
+pub struct CompressOptions {
+    pub booleans: Option<bool>,
+    #[napi(js_name = "dead_code")]
+    pub dead_code: Option<bool>,
+    #[napi(js_name = "drop_console")]
+    pub drop_console: Option<bool>,
+    #[napi(js_name = "drop_debugger")]
+    pub drop_debugger: Option<bool>,
+    pub evaluate: Option<bool>,
+    #[napi(js_name = "if_return")]
+    pub if_return: Option<bool>,
+    pub inline: Option<bool>,
+    #[napi(js_name = "join_vars")]
+    pub join_vars: Option<bool>,
+    pub loops: Option<bool>,
+    pub passes: Option<u32>,
+    #[napi(js_name = "pure_funcs")]
+    pub pure_funcs: Option<Vec<String>>,
+    pub sequences: Option<bool>,
+    pub typeofs: Option<bool>,
+    #[napi(js_name = "unsafe_math")]
+    pub unsafe_math: Option<bool>,
+    pub unused: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct MangleOptions {
+    #[napi(js_name = "keep_classnames")]
+    pub keep_classnames: Option<bool>,
+    #[napi(js_name = "keep_fnames")]
+    pub keep_fnames: Option<bool>,
+    pub reserved: Option<Vec<String>>,
+    pub toplevel: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct FormatOptions {
+    /// Print the code with whitespace. Defaults to `false`.
+    pub beautify: Option<bool>,
+    /// `some` keeps the license comments, `true` or `all` keeps all the comments and `false`
+    /// none of them. Defaults to `some`.
+    #[napi(ts_type = "boolean | 'some' | 'all' | undefined")]
+    pub comments: Option<Either<bool, String>>,
+    /// Escape the non-ASCII characters. Defaults to `false`.
+    #[napi(js_name = "ascii_only")]
+    pub ascii_only: Option<bool>,
+    /// `1` prints single quotes, the others print double quotes. Defaults to `0`.
+    #[napi(js_name = "quote_style")]
+    pub quote_style: Option<u32>,
+    /// `false` omits the semicolons which are not needed. Defaults to `true`.
+    pub semicolons: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct SourceMapOptions {
+    /// The source map of the input code as JSON, the returned source map maps to its sources
+    pub content: Option<String>,
+    /// `inline` appends the source map to the code as a data URL, other URLs are appended as
+    /// they are
+    pub url: Option<String>,
+}
+
+#[napi(object)]
+pub struct MinifyResult {
+    pub code: String,
+    /// The source map as JSON, with `sourceMap`
+    pub map: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl CompressOptions {
+    fn into_options(self) -> minifier::CompressOptions {
+        let default = minifier::CompressOptions::default();
+        minifier::CompressOptions {
+            booleans: self.booleans.unwrap_or(default.booleans),
+            dead_code: self.dead_code.unwrap_or(default.dead_code),
+            drop_console: self.drop_console.unwrap_or(default.drop_console),
+            drop_debugger: self.drop_debugger.unwrap_or(default.drop_debugger),
+            evaluate: self.evaluate.unwrap_or(default.evaluate),
+            if_return: self.if_return.unwrap_or(default.if_return),
+            inline: self.inline.unwrap_or(default.inline),
+            join_vars: self.join_vars.unwrap_or(default.join_vars),
+            loops: self.loops.unwrap_or(default.loops),
+            passes: self.passes.map_or(default.passes, |passes| passes as usize),
+            pure_funcs: self.pure_funcs.unwrap_or(default.pure_funcs),
+            sequences: self.sequences.unwrap_or(default.sequences),
+            typeofs: self.typeofs.unwrap_or(default.typeofs),
+            unsafe_math: self.unsafe_math.unwrap_or(default.unsafe_math),
+            unused: self.unused.unwrap_or(default.unused),
+            ..default
+        }
+    }
+}
+
+impl MangleOptions {
+    fn into_options(self, toplevel: bool) -> ManglerOptions {
+        ManglerOptions {
+            keep_classnames: self.keep_classnames.unwrap_or_default(),
+            keep_fnames: self.keep_fnames.unwrap_or_default(),
+            reserved: self.reserved.unwrap_or_default(),
+            top_level: self.toplevel.unwrap_or(toplevel),
+            ..ManglerOptions::default()
+        }
+    }
+}
+
+impl FormatOptions {
+    fn codegen_options(&self) -> CodegenOptions {
+        CodegenOptions {
+            quote_style: if self.quote_style == Some(1) {
+                QuoteStyle::Single
+            } else {
+                QuoteStyle::Double
+            },
+            semicolons: if self.semicolons == Some(false) {
+                SemicolonStyle::AsNeeded
+            } else {
+                SemicolonStyle::Always
+            },
+            charset: if self.ascii_only == Some(true) { Charset::Ascii } else { Charset::Utf8 },
+            escape_script_end_tags: true,
+            ..CodegenOptions::default()
+        }
+    }
+
+    fn comment_options(&self) -> napi::Result<CommentOptions> {
+        let (preserve_all_comments, preserve_legal_comments) = match &self.comments {
+            None => (false, true),
+            Some(Either::A(yes)) => (*yes, *yes),
+            Some(Either::B(comments)) if comments == "some" => (false, true),
+            Some(Either::B(comments)) if comments == "all" => (true, true),
+            Some(Either::B(comments)) => {
+                return Err(Error::new(
+                    Status::InvalidArg,
+                    format!("Invalid format.comments: \"{comments}\""),
+                ))
+            }
+        };
+        Ok(CommentOptions {
+            preserve_all_comments,
+            preserve_legal_comments,
+            ..CommentOptions::default()
+        })
+    }
+}
+
+/// Compresses and mangles the code
+///
+/// # Errors
+///
+/// * The options are invalid, or the input source map is not valid JSON
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn minify(source_text: String, options: Option<MinifyOptions>) -> napi::Result<MinifyResult> {
+    let options = options.unwrap_or_default();
+    let module = options.module.unwrap_or_default();
+    let toplevel = options.toplevel.unwrap_or(module);
+
+    let compress = match options.compress {
+        None | Some(Either::A(true)) => minifier::CompressOptions::default(),
+        Some(Either::A(false)) => minifier::CompressOptions::all_false(),
+        Some(Either::B(compress)) => compress.into_options(),
+    };
+    let mangle = match options.mangle {
+        None | Some(Either::A(true)) => Some(MangleOptions::default().into_options(toplevel)),
+        Some(Either::A(false)) => None,
+        Some(Either::B(mangle)) => Some(mangle.into_options(toplevel)),
+    };
+    let format = options.format.unwrap_or_default();
+    let source_map = match options.source_map {
+        None | Some(Either::A(false)) => None,
+        Some(Either::A(true)) => Some(SourceMapOptions::default()),
+        Some(Either::B(source_map)) => Some(source_map),
+    };
+    let input_source_map = source_map
+        .as_ref()
+        .and_then(|source_map| source_map.content.as_deref())
+        .map(|content| {
+            SourceMap::from_json_string(content).map_err(|error| {
+                Error::new(Status::InvalidArg, format!("Invalid sourceMap.content: {error:?}"))
+            })
+        })
+        .transpose()?;
+
+    let source_type = SourceType::default().with_module(module);
+    let mut compiler = Compiler::new(source_type)
+        .with_minify(MinifierOptions { mangle, compress })
+        .with_remove_whitespace(!format.beautify.unwrap_or_default())
+        .with_codegen(format.codegen_options())
+        .with_comments(format.comment_options()?);
+    if source_map.is_some() {
+        compiler = compiler.with_source_map(oxc::codegen::SourceMapOptions {
+            source_name: options.sourcefile.clone().unwrap_or_default(),
+            inline: false,
+        });
+    }
+
+    let allocator = Allocator::default();
+    let ret = compiler.compile(&allocator, &source_text);
+
+    let errors = if ret.errors.is_empty() {
+        vec![]
+    } else {
+        let file_name = options.sourcefile.unwrap_or_default();
+        let source = Arc::new(NamedSource::new(file_name, source_text));
+        ret.errors
+            .into_iter()
+            .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+            .map(|error| format!("{error:?}"))
+            .collect()
+    };
+
+    let mut code = ret.code;
+    let map = ret.source_map.map(|map| match &input_source_map {
+        Some(input_source_map) => map.compose(input_source_map),
+        None => map,
+    });
+    if let (Some(map), Some(url)) =
+        (&map, source_map.as_ref().and_then(|source_map| source_map.url.as_deref()))
+    {
+        if !code.is_empty() && !code.ends_with('\n') {
+            code.push('\n');
+        }
+        code.push_str("//# sourceMappingURL=");
+        if url == "inline" {
+            code.push_str(&map.to_data_url());
+        } else {
+            code.push_str(url);
+        }
+        code.push('\n');
+    }
+
+    Ok(MinifyResult { code, map: map.map(|map| map.to_json_string()), errors })
+}
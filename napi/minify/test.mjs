@@ -0,0 +1,24 @@
+import oxc from './index.js';
+import assert from 'assert';
+
+console.log(`Testing on ${process.platform}-${process.arch}`)
+
+const source = "/*! license */\nfunction foo(bar) { return 'x' + bar }\nfoo(1)";
+
+let ret = oxc.minify(source);
+console.log(ret.code);
+assert(ret.errors.length == 0);
+assert(ret.code == '/*! license */function foo(o){return "x"+o}foo(1);');
+assert(ret.map === undefined);
+
+ret = oxc.minify(source, { mangle: false, format: { comments: false, quote_style: 1 } });
+assert(ret.code == "function foo(bar){return 'x'+bar}foo(1);");
+
+ret = oxc.minify(source, { sourceMap: { url: 'inline' }, sourcefile: 'foo.js' });
+assert.deepEqual(JSON.parse(ret.map).sources, ['foo.js']);
+assert(ret.code.includes('//# sourceMappingURL=data:application/json;'));
+
+ret = oxc.minify("let a = ");
+assert(ret.errors.length == 1);
+
+assert.throws(() => oxc.minify("", { format: { comments: 'none' } }));
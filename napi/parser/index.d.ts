@@ -13,9 +13,18 @@ export interface ParserOptions {
   sourceFilename?: string
 }
 export interface ParseResult {
+  /** The ESTree `Program` serialized as JSON */
   program: string
+  comments: Array<Comment>
   errors: Array<string>
 }
+/** An ESTree comment, `value` is the text without the `//`, `/*` and `*/` */
+export interface Comment {
+  type: 'Line' | 'Block'
+  value: string
+  start: number
+  end: number
+}
 /**
  * Parse without returning anything.
  * This is for benchmark purposes such as measuring napi communication overhead.
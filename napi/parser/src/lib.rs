@@ -8,6 +8,7 @@ use napi::bindgen_prelude::Buffer;
 use napi_derive::napi;
 use oxc_allocator::Allocator;
 pub use oxc_ast::ast::Program;
+use oxc_ast::EstreeComment;
 use oxc_parser::{Parser, ParserReturn};
 use oxc_span::SourceType;
 use serde::Serialize;
@@ -25,10 +26,29 @@ pub struct ParserOptions {
 
 #[napi(object)]
 pub struct ParseResult {
+    /// The ESTree `Program` serialized as JSON
     pub program: String,
+    pub comments: Vec<Comment>,
     pub errors: Vec<String>,
 }
 
+/// An ESTree comment, `value` is the text without the `//`, `/*` and `*/`
+#[napi(object)]
+pub struct Comment {
+    #[napi(js_name = "type", ts_type = "'Line' | 'Block'")]
+    pub kind: String,
+    pub value: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl From<EstreeComment> for Comment {
+    fn from(comment: EstreeComment) -> Self {
+        let EstreeComment { kind, value, start, end } = comment;
+        Self { kind: kind.to_string(), value, start, end }
+    }
+}
+
 fn parse<'a>(
     allocator: &'a Allocator,
     source_text: &'a str,
@@ -73,7 +93,10 @@ pub fn parse_sync(source_text: String, options: Option<ParserOptions>) -> ParseR
 
     let allocator = Allocator::default();
     let ret = parse(&allocator, &source_text, &options);
-    let program = serde_json::to_string(&ret.program).unwrap();
+    let program = ret.program.to_estree_json(&source_text);
+
+    let comments =
+        ret.trivias.to_estree_comments(&source_text).into_iter().map(Comment::from).collect();
 
     let errors = if ret.errors.is_empty() {
         vec![]
@@ -87,7 +110,7 @@ pub fn parse_sync(source_text: String, options: Option<ParserOptions>) -> ParseR
             .collect()
     };
 
-    ParseResult { program, errors }
+    ParseResult { program, comments, errors }
 }
 
 /// Returns a binary AST in flexbuffers format.
@@ -6,14 +6,22 @@ console.log(`Testing on ${process.platform}-${process.arch}`)
 function test(ret) {
   console.log(ret.program);
   console.log(ret.errors);
-  assert(JSON.parse(ret.program).body.length == 1);
+  const program = JSON.parse(ret.program);
+  assert(program.type == 'Program');
+  assert(program.body.length == 1);
+  assert(program.body[0].expression.type == 'Identifier');
+  assert(ret.comments.length == 2);
+  assert(ret.comments[0].type == 'Line' && ret.comments[0].value == ' line');
+  assert(ret.comments[1].type == 'Block' && ret.comments[1].value == ' block ');
   assert(ret.errors.length == 0);
 }
 
-test(oxc.parseSync("foo"));
+const source = "// line\nfoo /* block */";
+
+test(oxc.parseSync(source));
 
 async function main() {
-  test(await oxc.parseAsync("foo"));
+  test(await oxc.parseAsync(source));
 }
 
 main()
@@ -0,0 +1,46 @@
+/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */
+
+/**
+ * esbuild Transform Options
+ *
+ * <https://esbuild.github.io/api/#transform>
+ */
+export interface TransformOptions {
+  /** The syntax of the source, defaults to the extension of `sourcefile` or `js` */
+  loader?: 'js' | 'jsx' | 'ts' | 'tsx' | undefined
+  /** The name of the file in the errors and the source map */
+  sourcefile?: string
+  /** The syntax newer than the target is lowered, defaults to `esnext` */
+  target?: 'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'es2024' | 'esnext' | undefined
+  /**
+   * `transform` compiles JSX to `React.createElement` calls, `automatic` to imports of
+   * `react/jsx-runtime`, and `preserve` keeps it. Defaults to `transform`.
+   */
+  jsx?: 'transform' | 'automatic' | 'preserve' | undefined
+  /** The function of the elements with `jsx: 'transform'`, defaults to `React.createElement` */
+  jsxFactory?: string
+  /** The function of the fragments with `jsx: 'transform'`, defaults to `React.Fragment` */
+  jsxFragment?: string
+  /** The module of the runtime with `jsx: 'automatic'`, defaults to `react` */
+  jsxImportSource?: string
+  jsxDev?: boolean
+  /** `true` returns the source map in `map`, `inline` appends it to the code */
+  sourcemap?: boolean | 'inline' | undefined
+}
+export interface TransformResult {
+  code: string
+  /** The source map as JSON, with `sourcemap: true` */
+  map?: string
+  errors: Array<string>
+}
+/**
+ * Strips the TypeScript types, compiles the JSX and lowers the syntax newer than the target
+ *
+ * # Errors
+ *
+ * * The options are invalid
+ */
+export function transform(sourceText: string, options?: TransformOptions | undefined | null): TransformResult
@@ -0,0 +1,165 @@
+#![allow(clippy::trailing_empty_array)]
+
+use std::{borrow::Cow, sync::Arc};
+
+use miette::NamedSource;
+use napi::{bindgen_prelude::Either, Error, Status};
+use napi_derive::napi;
+use oxc::{
+    allocator::Allocator,
+    codegen::{CommentOptions, SourceMapOptions},
+    span::SourceType,
+    transformer::{self, ReactJsxOptions, ReactJsxRuntime, ReactJsxRuntimeOption, TransformTarget},
+    Compiler,
+};
+
+/// esbuild Transform Options
+///
+/// <https://esbuild.github.io/api/#transform>
+#[napi(object)]
+#[derive(Default)]
+pub struct TransformOptions {
+    /// The syntax of the source, defaults to the extension of `sourcefile` or `js`
+    #[napi(ts_type = "'js' | 'jsx' | 'ts' | 'tsx' | undefined")]
+    pub loader: Option<String>,
+    /// The name of the file in the errors and the source map
+    pub sourcefile: Option<String>,
+    /// The syntax newer than the target is lowered, defaults to `esnext`
+    #[napi(ts_type = "'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | \
+                      'es2021' | 'es2022' | 'es2024' | 'esnext' | undefined")]
+    pub target: Option<String>,
+    /// `transform` compiles JSX to `React.createElement` calls, `automatic` to imports of
+    /// `react/jsx-runtime`, and `preserve` keeps it. Defaults to `transform`.
+    #[napi(ts_type = "'transform' | 'automatic' | 'preserve' | undefined")]
+    pub jsx: Option<String>,
+    /// The function of the elements with `jsx: 'transform'`, defaults to `React.createElement`
+    pub jsx_factory: Option<String>,
+    /// The function of the fragments with `jsx: 'transform'`, defaults to `React.Fragment`
+    pub jsx_fragment: Option<String>,
+    /// The module of the runtime with `jsx: 'automatic'`, defaults to `react`
+    pub jsx_import_source: Option<String>,
+    pub jsx_dev: Option<bool>,
+    /// `true` returns the source map in `map`, `inline` appends it to the code
+    #[napi(ts_type = "boolean | 'inline' | undefined")]
+    pub sourcemap: Option<Either<bool, String>>,
+}
+
+#[napi(object)]
+pub struct TransformResult {
+    pub code: String,
+    /// The source map as JSON, with `sourcemap: true`
+    pub map: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl TransformOptions {
+    fn source_type(&self) -> napi::Result<SourceType> {
+        let source_type = match (self.loader.as_deref(), &self.sourcefile) {
+            (Some(loader @ ("js" | "jsx" | "ts" | "tsx")), _) => {
+                SourceType::from_path(format!("file.{loader}"))
+            }
+            (Some(loader), _) => return Err(invalid_option("loader", loader)),
+            (None, Some(sourcefile)) => SourceType::from_path(sourcefile),
+            (None, None) => SourceType::from_path("file.js"),
+        };
+        Ok(source_type.unwrap_or_default())
+    }
+
+    fn transform_options(&self) -> napi::Result<transformer::TransformOptions> {
+        let target = match self.target.as_deref().unwrap_or("esnext") {
+            "es5" => TransformTarget::ES5,
+            "es2015" | "es6" => TransformTarget::ES2015,
+            "es2016" => TransformTarget::ES2016,
+            "es2017" => TransformTarget::ES2017,
+            "es2018" => TransformTarget::ES2018,
+            "es2019" => TransformTarget::ES2019,
+            "es2020" => TransformTarget::ES2020,
+            "es2021" => TransformTarget::ES2021,
+            "es2022" => TransformTarget::ES2022,
+            "es2024" => TransformTarget::ES2024,
+            "esnext" => TransformTarget::ESNext,
+            target => return Err(invalid_option("target", target)),
+        };
+        let runtime = match self.jsx.as_deref().unwrap_or("transform") {
+            "transform" => Some(ReactJsxRuntime::Classic),
+            "automatic" => Some(ReactJsxRuntime::Automatic),
+            "preserve" => None,
+            jsx => return Err(invalid_option("jsx", jsx)),
+        };
+        let react_jsx = runtime.map(|runtime| {
+            let default = ReactJsxOptions::default();
+            ReactJsxOptions {
+                runtime: Some(ReactJsxRuntimeOption::Valid(runtime)),
+                pragma: self.jsx_factory.clone().map_or(default.pragma, Cow::Owned),
+                pragma_frag: self.jsx_fragment.clone().map_or(default.pragma_frag, Cow::Owned),
+                import_source: self
+                    .jsx_import_source
+                    .clone()
+                    .map_or(default.import_source, Cow::Owned),
+                development: self.jsx_dev.unwrap_or_default(),
+                ..default
+            }
+        });
+        Ok(transformer::TransformOptions {
+            target,
+            react_jsx,
+            ..transformer::TransformOptions::default()
+        })
+    }
+
+    fn source_map_options(&self) -> napi::Result<Option<SourceMapOptions>> {
+        let inline = match &self.sourcemap {
+            None | Some(Either::A(false)) => return Ok(None),
+            Some(Either::A(true)) => false,
+            Some(Either::B(sourcemap)) if sourcemap == "inline" => true,
+            Some(Either::B(sourcemap)) => return Err(invalid_option("sourcemap", sourcemap)),
+        };
+        let source_name = self.sourcefile.clone().unwrap_or_default();
+        Ok(Some(SourceMapOptions { source_name, inline }))
+    }
+}
+
+fn invalid_option(name: &str, value: &str) -> Error {
+    Error::new(Status::InvalidArg, format!("Invalid {name}: \"{value}\""))
+}
+
+/// Strips the TypeScript types, compiles the JSX and lowers the syntax newer than the target
+///
+/// # Errors
+///
+/// * The options are invalid
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn transform(
+    source_text: String,
+    options: Option<TransformOptions>,
+) -> napi::Result<TransformResult> {
+    let options = options.unwrap_or_default();
+    let mut compiler = Compiler::new(options.source_type()?)
+        .with_transform(options.transform_options()?)
+        .with_comments(CommentOptions::default());
+    if let Some(source_map_options) = options.source_map_options()? {
+        compiler = compiler.with_source_map(source_map_options);
+    }
+
+    let allocator = Allocator::default();
+    let ret = compiler.compile(&allocator, &source_text);
+
+    let errors = if ret.errors.is_empty() {
+        vec![]
+    } else {
+        let file_name = options.sourcefile.unwrap_or_default();
+        let source = Arc::new(NamedSource::new(file_name, source_text));
+        ret.errors
+            .into_iter()
+            .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+            .map(|error| format!("{error:?}"))
+            .collect()
+    };
+    let map = ret
+        .source_map
+        .filter(|_| matches!(options.sourcemap, Some(Either::A(true))))
+        .map(|source_map| source_map.to_json_string());
+
+    Ok(TransformResult { code: ret.code, map, errors })
+}
@@ -0,0 +1,21 @@
+import oxc from './index.js';
+import assert from 'assert';
+
+console.log(`Testing on ${process.platform}-${process.arch}`)
+
+let ret = oxc.transform("const x: number = <div />", { loader: 'tsx', sourcemap: true });
+console.log(ret.code);
+assert(ret.errors.length == 0);
+assert(ret.code == "const x = React.createElement('div', null);\n");
+assert(JSON.parse(ret.map).version == 3);
+
+ret = oxc.transform("a ??= b", { target: 'es2020' });
+assert(!ret.code.includes('??='));
+
+ret = oxc.transform("<div />", { loader: 'jsx', jsx: 'preserve' });
+assert(ret.code.includes('<div />'));
+
+ret = oxc.transform("let x: = 1", { loader: 'ts' });
+assert(ret.errors.length == 1);
+
+assert.throws(() => oxc.transform("", { target: 'es1' }));
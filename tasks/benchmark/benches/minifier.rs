@@ -19,7 +19,7 @@ fn bench_minifier(criterion: &mut Criterion) {
                     let allocator = Allocator::default();
                     let program = Parser::new(&allocator, source_text, source_type).parse().program;
                     let program = allocator.alloc(program);
-                    Minifier::new(options).build(&allocator, program);
+                    Minifier::new(options.clone()).build(&allocator, program);
                     allocator
                 });
             },
@@ -70,7 +70,7 @@ impl Case for CodegenTest262Case {
         let source_text = self.base.code();
         let is_module = self.base.meta().flags.contains(&TestFlag::Module);
         let source_type = SourceType::default().with_module(is_module);
-        let result = get_result(source_text, source_type, CodegenOptions);
+        let result = get_result(source_text, source_type, CodegenOptions::default());
         self.base.set_result(result);
     }
 }
@@ -103,7 +103,7 @@ impl Case for CodegenBabelCase {
     fn run(&mut self) {
         let source_text = self.base.code();
         let source_type = self.base.source_type();
-        let result = get_result(source_text, source_type, CodegenOptions);
+        let result = get_result(source_text, source_type, CodegenOptions::default());
         self.base.set_result(result);
     }
 }
@@ -136,7 +136,7 @@ impl Case for CodegenTypeScriptCase {
     fn run(&mut self) {
         let source_text = self.base.code();
         let source_type = self.base.source_type();
-        let result = get_result(source_text, source_type, CodegenOptions);
+        let result = get_result(source_text, source_type, CodegenOptions::default());
         self.base.set_result(result);
     }
 }
@@ -169,7 +169,7 @@ impl Case for CodegenMiscCase {
     fn run(&mut self) {
         let source_text = self.base.code();
         let source_type = self.base.source_type();
-        let result = get_result(source_text, source_type, CodegenOptions);
+        let result = get_result(source_text, source_type, CodegenOptions::default());
         self.base.set_result(result);
     }
 }
@@ -2,12 +2,14 @@ mod babel;
 mod codegen;
 mod minifier;
 mod misc;
+mod prettier;
 mod runtime;
 mod suite;
 mod test262;
+mod transformer;
 mod typescript;
 
-use std::{path::PathBuf, process::Command};
+use std::{cell::RefCell, path::PathBuf, process::Command};
 
 use runtime::CodegenRuntimeTest262Case;
 use similar::DiffableStr;
@@ -17,8 +19,13 @@ use crate::{
     codegen::{CodegenBabelCase, CodegenMiscCase, CodegenTest262Case, CodegenTypeScriptCase},
     minifier::{MinifierBabelCase, MinifierTest262Case},
     misc::{MiscCase, MiscSuite},
+    prettier::{PrettierBabelCase, PrettierMiscCase, PrettierTest262Case, PrettierTypeScriptCase},
     suite::Suite,
     test262::{Test262Case, Test262Suite},
+    transformer::{
+        TransformerBabelCase, TransformerMiscCase, TransformerTest262Case,
+        TransformerTypeScriptCase,
+    },
     typescript::{TypeScriptCase, TypeScriptSuite},
 };
 
@@ -34,6 +41,10 @@ pub struct AppArgs {
     pub detail: bool,
     /// Print mismatch diff
     pub diff: bool,
+    /// Compare the reports with the snapshots instead of writing them
+    pub check: bool,
+    /// The snapshots which `check` found to be outdated
+    pub outdated_snapshots: RefCell<Vec<String>>,
 }
 
 impl AppArgs {
@@ -46,6 +57,8 @@ impl AppArgs {
         self.run_codegen();
         // self.run_codegen_runtime();
         self.run_minifier();
+        self.run_prettier();
+        self.run_transformer();
     }
 
     pub fn run_parser(&self) {
@@ -84,11 +97,25 @@ impl AppArgs {
         Test262Suite::<MinifierTest262Case>::new().run("minifier_test262", self);
         BabelSuite::<MinifierBabelCase>::new().run("minifier_babel", self);
     }
+
+    pub fn run_transformer(&self) {
+        Test262Suite::<TransformerTest262Case>::new().run("transformer_test262", self);
+        BabelSuite::<TransformerBabelCase>::new().run("transformer_babel", self);
+        TypeScriptSuite::<TransformerTypeScriptCase>::new().run("transformer_typescript", self);
+        MiscSuite::<TransformerMiscCase>::new().run("transformer_misc", self);
+    }
+
+    pub fn run_prettier(&self) {
+        Test262Suite::<PrettierTest262Case>::new().run("prettier_test262", self);
+        BabelSuite::<PrettierBabelCase>::new().run("prettier_babel", self);
+        TypeScriptSuite::<PrettierTypeScriptCase>::new().run("prettier_typescript", self);
+        MiscSuite::<PrettierMiscCase>::new().run("prettier_misc", self);
+    }
 }
 
 #[test]
 #[cfg(any(coverage, coverage_nightly))]
 fn test() {
-    let args = AppArgs { filter: None, detail: false, diff: false };
+    let args = AppArgs::default();
     args.run_all()
 }
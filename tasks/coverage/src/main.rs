@@ -6,10 +6,12 @@ static GLOBAL: jemallocator::Jemalloc = jemallocator::Jemalloc;
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+use std::process::ExitCode;
+
 use oxc_coverage::AppArgs;
 use pico_args::Arguments;
 
-fn main() {
+fn main() -> ExitCode {
     let mut args = Arguments::from_env();
     let command = args.subcommand().expect("subcommands");
 
@@ -17,6 +19,8 @@ fn main() {
         filter: args.opt_value_from_str("--filter").unwrap(),
         detail: args.contains("--detail"),
         diff: args.contains("--diff"),
+        check: args.contains("--check"),
+        ..AppArgs::default()
     };
 
     let task = command.as_deref().unwrap_or("default");
@@ -26,6 +30,16 @@ fn main() {
         "codegen" => args.run_codegen(),
         "codegen-runtime" => args.run_codegen_runtime(),
         "minifier" => args.run_minifier(),
+        "prettier" => args.run_prettier(),
+        "transformer" => args.run_transformer(),
         _ => args.run_all(),
     };
+
+    let outdated_snapshots = args.outdated_snapshots.borrow();
+    if outdated_snapshots.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        println!("Outdated snapshots: {}", outdated_snapshots.join(", "));
+        ExitCode::FAILURE
+    }
 }
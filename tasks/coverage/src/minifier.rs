@@ -86,7 +86,7 @@ fn get_result(source_text: &str, source_type: SourceType) -> TestResult {
         compress: CompressOptions { evaluate: false, ..CompressOptions::default() },
         ..MinifierOptions::default()
     };
-    let source_text1 = minify(source_text, source_type, options);
+    let source_text1 = minify(source_text, source_type, options.clone());
     let source_text2 = minify(&source_text1, source_type, options);
     if source_text1 == source_text2 {
         TestResult::Passed
@@ -100,5 +100,5 @@ fn minify(source_text: &str, source_type: SourceType, options: MinifierOptions)
     let program = Parser::new(&allocator, source_text, source_type).parse().program;
     let program = allocator.alloc(program);
     Minifier::new(options).build(&allocator, program);
-    Codegen::<true>::new(source_text.len(), CodegenOptions).build(program)
+    Codegen::<true>::new(source_text.len(), CodegenOptions::default()).build(program)
 }
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_prettier::{Prettier, PrettierOptions};
+use oxc_span::SourceType;
+use serde_json::Value;
+
+use crate::{
+    babel::BabelCase,
+    misc::MiscCase,
+    suite::{Case, TestResult},
+    test262::{Test262Case, TestFlag},
+    typescript::TypeScriptCase,
+};
+
+/// The formatter is stable when the formatted code formats to itself, and correct when the
+/// formatted code parses to the same AST as the source code
+fn get_result(source_text: &str, source_type: SourceType) -> TestResult {
+    let allocator = Allocator::default();
+    let Ok((source_text1, ast1)) = format(&allocator, source_text, source_type) else {
+        return TestResult::ParseError(String::new(), false);
+    };
+    let Ok((source_text2, ast2)) = format(&allocator, &source_text1, source_type) else {
+        return TestResult::ParseError(
+            format!("Failed to parse the formatted code:\n{source_text1}"),
+            false,
+        );
+    };
+    if ast1 != ast2 {
+        return TestResult::Mismatch(ast2, ast1);
+    }
+    if source_text1 != source_text2 {
+        return TestResult::Mismatch(source_text2, source_text1);
+    }
+    TestResult::Passed
+}
+
+/// The formatted code and the shape of the AST of `source_text`
+fn format(
+    allocator: &Allocator,
+    source_text: &str,
+    source_type: SourceType,
+) -> Result<(String, String), ()> {
+    let ret = Parser::new(allocator, source_text, source_type).preserve_parens(false).parse();
+    if !ret.errors.is_empty() || ret.panicked {
+        return Err(());
+    }
+    let mut ast = serde_json::to_value(&ret.program).map_err(|_| ())?;
+    remove_positions(&mut ast);
+    let ast = serde_json::to_string_pretty(&ast).map_err(|_| ())?;
+    let formatted = Prettier::new(allocator, source_text, ret.trivias, PrettierOptions::default())
+        .build(&ret.program);
+    Ok((formatted, ast))
+}
+
+/// Removes the spans and the raw values, which are changed by the formatting
+fn remove_positions(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for key in ["span", "start", "end", "raw"] {
+                object.remove(key);
+            }
+            object.values_mut().for_each(remove_positions);
+        }
+        Value::Array(array) => array.iter_mut().for_each(remove_positions),
+        _ => {}
+    }
+}
+
+pub struct PrettierTest262Case {
+    base: Test262Case,
+}
+
+impl Case for PrettierTest262Case {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: Test262Case::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let is_module = self.base.meta().flags.contains(&TestFlag::Module);
+        let source_type = SourceType::default().with_module(is_module);
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
+
+pub struct PrettierBabelCase {
+    base: BabelCase,
+}
+
+impl Case for PrettierBabelCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: BabelCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
+
+pub struct PrettierTypeScriptCase {
+    base: TypeScriptCase,
+}
+
+impl Case for PrettierTypeScriptCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: TypeScriptCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
+
+pub struct PrettierMiscCase {
+    base: MiscCase,
+}
+
+impl Case for PrettierMiscCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: MiscCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
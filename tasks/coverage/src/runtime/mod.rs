@@ -119,8 +119,8 @@ impl Case for CodegenRuntimeTest262Case {
                 let source_type = SourceType::default().with_module(is_module);
                 let allocator = Allocator::default();
                 let program = Parser::new(&allocator, source_text, source_type).parse().program;
-                let mut text =
-                    Codegen::<false>::new(source_text.len(), CodegenOptions).build(&program);
+                let mut text = Codegen::<false>::new(source_text.len(), CodegenOptions::default())
+                    .build(&program);
                 if is_only_strict {
                     text = format!("\"use strict\";\n{text}");
                 }
@@ -1,5 +1,5 @@
 use std::{
-    fs::{self, File},
+    fs,
     io::{stdout, Read, Write},
     panic::UnwindSafe,
     path::{Path, PathBuf},
@@ -78,7 +78,7 @@ pub trait Suite<T: Case> {
         self.print_coverage(name, args, &report, &mut out).unwrap();
 
         if args.filter.is_none() {
-            self.snapshot_errors(name, &report).unwrap();
+            self.snapshot_errors(name, args, &report).unwrap();
         }
     }
 
@@ -234,10 +234,17 @@ pub trait Suite<T: Case> {
         Ok(())
     }
 
+    /// Write the report to `tasks/coverage/{name}.snap`, or with `--check` compare it with the
+    /// snapshot and print the differences
+    ///
     /// # Errors
-    fn snapshot_errors(&self, name: &str, report: &CoverageReport<T>) -> std::io::Result<()> {
+    fn snapshot_errors(
+        &self,
+        name: &str,
+        args: &AppArgs,
+        report: &CoverageReport<T>,
+    ) -> std::io::Result<()> {
         let path = project_root().join(format!("tasks/coverage/{}.snap", name.to_lowercase()));
-        let mut file = File::create(path).unwrap();
 
         let mut tests = self
             .get_test_cases()
@@ -247,18 +254,33 @@ pub trait Suite<T: Case> {
 
         tests.sort_by_key(|case| case.path());
 
-        let args = AppArgs { detail: true, ..AppArgs::default() };
-        self.print_coverage(name, &args, report, &mut file)?;
-
-        let mut out = String::new();
+        let mut out = vec![];
+        let detail_args = AppArgs { detail: true, ..AppArgs::default() };
+        self.print_coverage(name, &detail_args, report, &mut out)?;
         for case in &tests {
             if let TestResult::CorrectError(error, _) = &case.test_result() {
-                out.push_str(error);
+                out.extend_from_slice(error.as_bytes());
             }
         }
 
-        file.write_all(out.as_bytes())?;
-        file.flush()?;
+        if !args.check {
+            return fs::write(path, out);
+        }
+        let snapshot = fs::read_to_string(&path).unwrap_or_default();
+        let out = String::from_utf8_lossy(&out);
+        if snapshot != out {
+            println!("{}.snap is outdated:", name.to_lowercase());
+            let diff = TextDiff::from_lines(snapshot.as_str(), &*out);
+            for change in diff.iter_all_changes() {
+                let (sign, style) = match change.tag() {
+                    ChangeTag::Delete => ("-", Style::new().red()),
+                    ChangeTag::Insert => ("+", Style::new().green()),
+                    ChangeTag::Equal => continue,
+                };
+                print!("{}{}", style.apply_to(sign).bold(), style.apply_to(change));
+            }
+            args.outdated_snapshots.borrow_mut().push(name.to_string());
+        }
         Ok(())
     }
 }
@@ -0,0 +1,193 @@
+use std::{
+    panic::catch_unwind,
+    path::{Path, PathBuf},
+};
+
+use oxc_allocator::Allocator;
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_diagnostics::Error;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::SourceType;
+use oxc_transformer::{ReactJsxOptions, TransformOptions, TransformTarget, Transformer};
+
+use crate::{
+    babel::BabelCase,
+    misc::MiscCase,
+    suite::{Case, TestResult},
+    test262::{Test262Case, TestFlag},
+    typescript::TypeScriptCase,
+};
+
+/// Transform down to ES5, with the TypeScript and the JSX transforms for their files, and test
+/// that the output is JavaScript without syntax errors
+fn get_result(source_text: &str, source_type: SourceType) -> TestResult {
+    catch_unwind(|| transform(source_text, source_type))
+        .unwrap_or_else(|_| TestResult::RuntimeError("The transformer panicked".to_string()))
+}
+
+fn transform(source_text: &str, source_type: SourceType) -> TestResult {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    if !ret.errors.is_empty() {
+        return TestResult::ParseError(format!("{} syntax errors\n", ret.errors.len()), false);
+    }
+    let program = allocator.alloc(ret.program);
+    let semantic = SemanticBuilder::new(source_text, source_type).build(program).semantic;
+    let options = TransformOptions {
+        target: TransformTarget::ES5,
+        react_jsx: source_type.is_jsx().then(ReactJsxOptions::default),
+        ..TransformOptions::default()
+    };
+    if let Err(errors) = Transformer::new(&allocator, source_type, semantic, options).build(program)
+    {
+        return TestResult::ParseError(join_errors(&errors), false);
+    }
+
+    let output = Codegen::<false>::new(source_text.len(), CodegenOptions::default()).build(program);
+    let output_source_type = SourceType::default().with_module(source_type.is_module());
+    let ret = Parser::new(&allocator, &output, output_source_type).parse();
+    if ret.errors.is_empty() {
+        TestResult::Passed
+    } else {
+        let errors = join_errors(&ret.errors);
+        TestResult::ParseError(format!("The output is invalid:\n{errors}"), false)
+    }
+}
+
+fn join_errors(errors: &[Error]) -> String {
+    errors.iter().map(|error| format!("{error}\n")).collect::<Vec<_>>().concat()
+}
+
+pub struct TransformerTest262Case {
+    base: Test262Case,
+}
+
+impl Case for TransformerTest262Case {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: Test262Case::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let is_module = self.base.meta().flags.contains(&TestFlag::Module);
+        let source_type = SourceType::default().with_module(is_module);
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
+
+pub struct TransformerBabelCase {
+    base: BabelCase,
+}
+
+impl Case for TransformerBabelCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: BabelCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
+
+pub struct TransformerTypeScriptCase {
+    base: TypeScriptCase,
+}
+
+impl Case for TransformerTypeScriptCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: TypeScriptCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
+
+pub struct TransformerMiscCase {
+    base: MiscCase,
+}
+
+impl Case for TransformerMiscCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: MiscCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let result = get_result(source_text, source_type);
+        self.base.set_result(result);
+    }
+}
@@ -70,7 +70,7 @@ fn minify_twice(file: &TestFile) -> String {
         compress: CompressOptions { evaluate: false, ..CompressOptions::default() },
         ..MinifierOptions::default()
     };
-    let source_text1 = minify(&file.source_text, source_type, options);
+    let source_text1 = minify(&file.source_text, source_type, options.clone());
     let source_text2 = minify(&source_text1, source_type, options);
     assert!(source_text1 == source_text2, "Minification failed for {}", &file.file_name);
     source_text2
@@ -81,7 +81,9 @@ fn minify(source_text: &str, source_type: SourceType, options: MinifierOptions)
     let program = Parser::new(&allocator, source_text, source_type).parse().program;
     let program = allocator.alloc(program);
     Minifier::new(options).build(&allocator, program);
-    Codegen::<true>::new(source_text.len(), CodegenOptions).build(program)
+    let codegen_options =
+        CodegenOptions { escape_script_end_tags: true, ..CodegenOptions::default() };
+    Codegen::<true>::new(source_text.len(), codegen_options).build(program)
 }
 
 fn gzip_size(s: &str) -> usize {
@@ -11,8 +11,10 @@ use oxc_semantic::SemanticBuilder;
 use oxc_span::{SourceType, VALID_EXTENSIONS};
 use oxc_tasks_common::{normalize_path, print_diff_in_terminal, BabelOptions};
 use oxc_transformer::{
-    ArrowFunctionsOptions, NullishCoalescingOperatorOptions, ReactJsxOptions, TransformOptions,
-    TransformTarget, Transformer, TypescriptOptions,
+    ArrowFunctionsOptions, ClassPropertiesOptions, DecoratorsOptions, HelperLoaderMode,
+    HelpersOptions, ModulesOptions, NullishCoalescingOperatorOptions, OptionalChainingOptions,
+    ReactJsxOptions, ReactRefreshOptions, TransformOptions, TransformTarget, Transformer,
+    TypescriptOptions,
 };
 use serde::de::DeserializeOwned;
 use serde_json::Value;
@@ -92,11 +94,32 @@ pub trait TestCase {
             react_jsx: options
                 .get_plugin("transform-react-jsx")
                 .map(get_options::<ReactJsxOptions>),
+            react_refresh: options
+                .get_plugin("react-refresh/babel")
+                .map(get_options::<ReactRefreshOptions>),
             typescript: options
                 .get_plugin("transform-typescript")
                 .map(get_options::<TypescriptOptions>),
+            decorators: options
+                .get_plugin("proposal-decorators")
+                .map(get_options::<DecoratorsOptions>),
             assumptions: options.assumptions,
+            targets: None,
+            define: None,
+            modules: options
+                .get_plugin("transform-modules-commonjs")
+                .map(get_options::<ModulesOptions>),
+            helpers: options.get_plugin("transform-runtime").map_or_else(
+                HelpersOptions::default,
+                |options| HelpersOptions {
+                    mode: HelperLoaderMode::Runtime,
+                    ..get_options::<HelpersOptions>(options)
+                },
+            ),
             class_static_block: options.get_plugin("transform-class-static-block").is_some(),
+            class_properties: options
+                .get_plugin("transform-class-properties")
+                .map(get_options::<ClassPropertiesOptions>),
             instanceof: options.get_plugin("transform-instanceof").is_some(),
             function_name: options.get_plugin("transform-function-name").is_some(),
             arrow_functions: options
@@ -108,14 +131,24 @@ pub trait TestCase {
             nullish_coalescing_operator: options
                 .get_plugin("transform-nullish-coalescing-operator")
                 .map(get_options::<NullishCoalescingOperatorOptions>),
+            optional_chaining: options
+                .get_plugin("transform-optional-chaining")
+                .map(get_options::<OptionalChainingOptions>),
             optional_catch_binding: options
                 .get_plugin("transform-optional-catch-binding")
                 .is_some(),
+            async_generator_functions: options
+                .get_plugin("transform-async-generator-functions")
+                .is_some(),
+            async_to_generator: options.get_plugin("transform-async-to-generator").is_some(),
             exponentiation_operator: options
                 .get_plugin("transform-exponentiation-operator")
                 .is_some(),
             shorthand_properties: options.get_plugin("transform-shorthand-properties").is_some(),
             sticky_regex: options.get_plugin("transform-sticky-regex").is_some(),
+            unicode_regex: options.get_plugin("transform-unicode-regex").is_some(),
+            dotall_regex: options.get_plugin("transform-dotall-regex").is_some(),
+            unicode_sets_regex: options.get_plugin("transform-unicode-sets-regex").is_some(),
             template_literals: options.get_plugin("transform-template-literals").is_some(),
             property_literals: options.get_plugin("transform-property-literals").is_some(),
             duplicate_keys: options.get_plugin("transform-duplicate-keys").is_some(),
@@ -169,7 +202,8 @@ pub trait TestCase {
             .build(transformed_program);
 
         result.map(|()| {
-            Codegen::<false>::new(source_text.len(), CodegenOptions).build(transformed_program)
+            Codegen::<false>::new(source_text.len(), CodegenOptions::default())
+                .build(transformed_program)
         })
     }
 }
@@ -237,7 +271,8 @@ impl TestCase for ConformanceTestCase {
         let mut actual_errors = String::new();
         let result = transformer.build(program);
         if result.is_ok() {
-            transformed_code = Codegen::<false>::new(input.len(), CodegenOptions).build(program);
+            transformed_code =
+                Codegen::<false>::new(input.len(), CodegenOptions::default()).build(program);
         } else {
             actual_errors =
                 result.err().unwrap().iter().map(std::string::ToString::to_string).collect();
@@ -253,12 +288,12 @@ impl TestCase for ConformanceTestCase {
                 }
                 // The transformation should be equal to input.js If output.js does not exist.
                 let program = Parser::new(&allocator, &input, source_type).parse().program;
-                Codegen::<false>::new(input.len(), CodegenOptions).build(&program)
+                Codegen::<false>::new(input.len(), CodegenOptions::default()).build(&program)
             },
             |output| {
                 // Get expected code by parsing the source text, so we can get the same code generated result.
                 let program = Parser::new(&allocator, &output, source_type).parse().program;
-                Codegen::<false>::new(output.len(), CodegenOptions).build(&program)
+                Codegen::<false>::new(output.len(), CodegenOptions::default()).build(&program)
             },
         );
 
@@ -318,8 +353,8 @@ impl ExecTestCase {
         let source_type = SourceType::from_path(&target_path).unwrap();
         let transformed_program =
             Parser::new(&allocator, &source_text, source_type).parse().program;
-        let result =
-            Codegen::<false>::new(source_text.len(), CodegenOptions).build(&transformed_program);
+        let result = Codegen::<false>::new(source_text.len(), CodegenOptions::default())
+            .build(&transformed_program);
 
         fs::write(&target_path, result).unwrap();
 